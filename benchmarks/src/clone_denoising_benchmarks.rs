@@ -188,7 +188,10 @@ fn bench_phase1_weighted_shingling(c: &mut Criterion) {
                 b.iter(|| {
                     let mut analyzer = WeightedShingleAnalyzer::new(9);
                     analyzer.build_idf_table(entities).unwrap();
-                    let signatures = analyzer.compute_weighted_signatures(entities).unwrap();
+                    let signatures = analyzer
+                        .compute_weighted_signatures(entities)
+                        .unwrap()
+                        .signatures;
 
                     // Calculate similarities between all pairs (limited to avoid O(n²) explosion)
                     let comparison_limit = 10.min(entities.len());
@@ -291,7 +294,10 @@ fn bench_memory_scalability(c: &mut Criterion) {
                     let entity_refs: Vec<&CodeEntity> = entities.iter().collect();
                     let mut analyzer = WeightedShingleAnalyzer::new(9);
                     analyzer.build_idf_table(&entity_refs).unwrap();
-                    let signatures = analyzer.compute_weighted_signatures(&entity_refs).unwrap();
+                    let signatures = analyzer
+                        .compute_weighted_signatures(&entity_refs)
+                        .unwrap()
+                        .signatures;
 
                     // Force memory allocation and prevent optimization
                     let signature_count = signatures.len();
@@ -339,7 +345,10 @@ fn bench_memory_scalability(c: &mut Criterion) {
                     let entity_refs: Vec<&CodeEntity> = entities.iter().collect();
                     let mut analyzer = WeightedShingleAnalyzer::new(9);
                     analyzer.build_idf_table(&entity_refs).unwrap();
-                    let signatures = analyzer.compute_weighted_signatures(&entity_refs).unwrap();
+                    let signatures = analyzer
+                        .compute_weighted_signatures(&entity_refs)
+                        .unwrap()
+                        .signatures;
 
                     // Compare first 15 entities with each other to avoid O(n²) explosion
                     let comparison_limit = 15.min(entities.len());
@@ -383,7 +392,10 @@ fn bench_kgram_sizes(c: &mut Criterion) {
                 b.iter(|| {
                     let mut analyzer = WeightedShingleAnalyzer::new(k);
                     analyzer.build_idf_table(entities).unwrap();
-                    let signatures = analyzer.compute_weighted_signatures(entities).unwrap();
+                    let signatures = analyzer
+                        .compute_weighted_signatures(entities)
+                        .unwrap()
+                        .signatures;
                     black_box(signatures);
                 });
             },
@@ -384,7 +384,9 @@ fn benchmark_optimized_weighted_signatures(c: &mut Criterion) {
                     let mut analyzer = valknut_rs::detectors::lsh::WeightedShingleAnalyzer::new(3);
                     
                     // This will use parallel IDF table construction
-                    let signatures_result = analyzer.compute_weighted_signatures(&entities_refs);
+                    let signatures_result = analyzer
+                        .compute_weighted_signatures(&entities_refs)
+                        .map(|set| set.signatures);
                     
                     if let Ok(signatures) = signatures_result {
                         // Test SIMD similarity calculations
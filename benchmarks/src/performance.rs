@@ -10,7 +10,7 @@ use valknut_rs::core::{
     featureset::FeatureVector,
     pipeline::{AnalysisConfig, AnalysisPipeline},
 };
-use valknut_rs::detectors::lsh::LshExtractor;
+use valknut_rs::detectors::lsh::{LshCache, LshExtractor, LshIndex, MinHashSignature};
 
 /// Generate synthetic feature vectors for benchmarking
 fn generate_test_vectors(count: usize, features_per_vector: usize) -> Vec<FeatureVector> {
@@ -212,6 +212,66 @@ fn benchmark_lsh_minhash(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark cold versus warm signature cache startup
+fn benchmark_signature_cache_startup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signature_cache_startup");
+    group.sample_size(10);
+
+    let extractor = LshExtractor::new();
+    let code_samples = generate_test_code(200);
+
+    // Without persistence every run starts cold and recomputes all signatures
+    group.bench_function("in_memory_cold", |b| {
+        b.iter(|| {
+            let cache = LshCache::new();
+            for code in &code_samples {
+                let signature = extractor.generate_minhash_signature(code);
+                cache.cache_signature(code, signature.len(), 3, signature);
+            }
+            std_black_box(cache.cache_sizes());
+        });
+    });
+
+    #[cfg(feature = "persistent-cache")]
+    {
+        use valknut_rs::io::cache::PersistentLshCache;
+
+        group.bench_function("persistent_cold", |b| {
+            b.iter(|| {
+                let dir = tempfile::tempdir().expect("temp dir");
+                let cache = PersistentLshCache::open(dir.path()).expect("open cache");
+                for code in &code_samples {
+                    let signature = extractor.generate_minhash_signature(code);
+                    cache.cache_signature(code, signature.len(), 3, signature);
+                }
+                cache.flush().expect("flush cache");
+            });
+        });
+
+        let warm_dir = tempfile::tempdir().expect("temp dir");
+        {
+            let cache = PersistentLshCache::open(warm_dir.path()).expect("open cache");
+            for code in &code_samples {
+                let signature = extractor.generate_minhash_signature(code);
+                cache.cache_signature(code, signature.len(), 3, signature);
+            }
+            cache.flush().expect("flush cache");
+        }
+        let num_hashes = extractor.generate_minhash_signature(&code_samples[0]).len();
+
+        group.bench_function("persistent_warm", |b| {
+            b.iter(|| {
+                let cache = PersistentLshCache::open(warm_dir.path()).expect("open cache");
+                for code in &code_samples {
+                    std_black_box(cache.get_signature(code, num_hashes, 3));
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
 /// Benchmark pipeline performance
 fn benchmark_pipeline_performance(c: &mut Criterion) {
     let mut group = c.benchmark_group("pipeline_performance");
@@ -313,6 +373,90 @@ fn benchmark_memory_optimization(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark the doc audit over a fixture tree with more than 500 directories
+fn benchmark_doc_audit(c: &mut Criterion) {
+    use valknut_rs::doc_audit::{run_audit, DocAuditConfig};
+
+    let mut group = c.benchmark_group("doc_audit");
+    group.sample_size(10);
+
+    let fixture = tempfile::tempdir().unwrap();
+    for pkg in 0..40 {
+        for module in 0..15 {
+            let dir = fixture
+                .path()
+                .join(format!("pkg_{}", pkg))
+                .join(format!("module_{}", module));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("lib.rs"), "//! Module docs.\n").unwrap();
+        }
+    }
+
+    let mut config = DocAuditConfig::new(fixture.path().to_path_buf());
+    config.ignore_globs.clear();
+
+    group.bench_function("run_audit_640_dirs", |b| {
+        b.iter(|| {
+            let result = run_audit(black_box(&config)).unwrap();
+            std_black_box(result);
+        });
+    });
+
+    group.finish();
+}
+
+/// Benchmark LSH index insertion and candidate lookup
+fn benchmark_lsh_index(c: &mut Criterion) {
+    const NUM_HASHES: usize = 128;
+    const NUM_BANDS: usize = 16;
+
+    let mut group = c.benchmark_group("lsh_index");
+
+    for size in [100, 1000, 5000].iter() {
+        // Every 10th entity shares its first band with its neighbour so lookups
+        // return a realistic mix of hits and misses.
+        let signatures: Vec<(String, MinHashSignature)> = (0..*size)
+            .map(|i| {
+                let hashes = (0..NUM_HASHES as u64)
+                    .map(|h| {
+                        let seed = if h < 8 { (i / 10) as u64 } else { i as u64 };
+                        seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ h
+                    })
+                    .collect();
+                (
+                    format!("entity_{}", i),
+                    MinHashSignature::new(hashes, NUM_HASHES, 3),
+                )
+            })
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("add_entity", size), size, |b, _| {
+            b.iter(|| {
+                let mut index = LshIndex::new(NUM_BANDS);
+                for (id, signature) in black_box(signatures.as_slice()) {
+                    index.add_entity(id, signature.clone());
+                }
+                std_black_box(index);
+            });
+        });
+
+        let mut index = LshIndex::new(NUM_BANDS);
+        for (id, signature) in &signatures {
+            index.add_entity(id, signature.clone());
+        }
+
+        group.bench_with_input(BenchmarkId::new("find_candidates", size), size, |b, _| {
+            b.iter(|| {
+                for (id, _) in black_box(signatures.as_slice()) {
+                    std_black_box(index.find_candidates(id));
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
 /// Benchmark concurrent data structure performance
 #[cfg(feature = "parallel")]
 fn benchmark_concurrent_structures(c: &mut Criterion) {
@@ -371,8 +515,11 @@ criterion_group!(
     benches,
     benchmark_bayesian_normalization,
     benchmark_lsh_minhash,
+    benchmark_lsh_index,
+    benchmark_signature_cache_startup,
     benchmark_pipeline_performance,
     benchmark_memory_optimization,
+    benchmark_doc_audit,
 );
 
 #[cfg(feature = "simd")]
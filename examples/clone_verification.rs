@@ -84,7 +84,7 @@ fn main() -> Result<()> {
     };
 
     let (analysis_config, valknut_config) = build_configs(verify_with_apted);
-    let pipeline = AnalysisPipeline::new_with_config(analysis_config, valknut_config);
+    let pipeline = AnalysisPipeline::new_with_config(analysis_config, valknut_config)?;
 
     let runtime = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
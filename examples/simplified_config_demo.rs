@@ -14,7 +14,7 @@ fn main() -> Result<(), DynError> {
     // Example 1: Simple configuration for basic code analysis
     println!("📊 Example 1: Basic Code Quality Analysis");
     let basic_config = AnalysisConfig::new()
-        .with_languages(vec!["rust".to_string(), "python".to_string()])
+        .with_languages(&["rust", "python"])?
         .with_confidence_threshold(0.8)
         .with_max_files(1000);
 
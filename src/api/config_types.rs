@@ -5,6 +5,7 @@
 
 use crate::core::config::{validate_unit_range, ValknutConfig};
 use crate::core::errors::{Result, ValknutError};
+use crate::lang::language_info;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -28,6 +29,11 @@ pub struct AnalysisConfig {
 
     /// Coverage analysis configuration
     pub coverage: CoverageSettings,
+
+    /// Git repository root that result paths are expressed against
+    /// (`None` detects it from the analyzed directory)
+    #[serde(default)]
+    pub git_root: Option<PathBuf>,
 }
 
 /// Analysis modules that can be enabled/disabled
@@ -55,7 +61,8 @@ pub struct AnalysisModules {
 /// Language configuration for analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LanguageSettings {
-    /// Languages to analyze (if empty, auto-detect from files)
+    /// Languages to analyze, as [`ValknutConfig`] language keys
+    /// (if empty, auto-detect from file extensions under the root)
     pub enabled: Vec<String>,
 
     /// Maximum file size per language (in MB)
@@ -127,6 +134,7 @@ impl Default for AnalysisConfig {
             files: FileSettings::default(),
             quality: QualitySettings::default(),
             coverage: CoverageSettings::default(),
+            git_root: None,
         }
     }
 }
@@ -151,11 +159,7 @@ impl Default for LanguageSettings {
     /// Returns the default language settings.
     fn default() -> Self {
         Self {
-            enabled: vec![
-                "python".to_string(),
-                "javascript".to_string(),
-                "typescript".to_string(),
-            ],
+            enabled: Vec::new(),
             max_file_size_mb: Some(10.0),
             complexity_thresholds: [
                 ("python".to_string(), 10.0),
@@ -260,16 +264,29 @@ impl AnalysisConfig {
 
     // Convenience methods for common operations
 
-    /// Set the languages to analyze
-    pub fn with_languages(mut self, languages: Vec<String>) -> Self {
-        self.languages.enabled = languages;
-        self
+    /// Replace the languages to analyze
+    ///
+    /// Languages left out are not analyzed; an empty slice restores
+    /// auto-detection from the file extensions found under the analyzed root.
+    /// Fails on the first unsupported language.
+    pub fn with_languages(mut self, languages: &[&str]) -> Result<Self> {
+        self.languages.enabled.clear();
+        languages
+            .iter()
+            .try_fold(self, |config, language| config.add_language(language))
     }
 
-    /// Add a language to analyze
-    pub fn with_language(mut self, language: impl Into<String>) -> Self {
-        self.languages.enabled.push(language.into());
-        self
+    /// Add a language to analyze, keeping those already selected
+    ///
+    /// Aliases such as `"py"` or `"golang"` are stored under their
+    /// [`ValknutConfig`] language key. Adding a language that is already
+    /// selected is a no-op.
+    pub fn add_language(mut self, language: &str) -> Result<Self> {
+        let language = validate_language(language)?;
+        if !self.languages.enabled.contains(&language) {
+            self.languages.enabled.push(language);
+        }
+        Ok(self)
     }
 
     /// Set confidence threshold
@@ -284,6 +301,12 @@ impl AnalysisConfig {
         self
     }
 
+    /// Set the git repository root that result paths are expressed against
+    pub fn with_git_root(mut self, root: PathBuf) -> Self {
+        self.git_root = Some(root);
+        self
+    }
+
     /// Add an exclusion pattern
     pub fn exclude_pattern(mut self, pattern: impl Into<String>) -> Self {
         self.files.exclude_patterns.push(pattern.into());
@@ -403,20 +426,24 @@ impl AnalysisConfig {
         config.coverage.auto_discover = self.coverage.auto_discover;
         config.coverage.max_age_days = self.coverage.max_age_days;
         config.coverage.search_paths = self.coverage.search_paths;
+        config.analysis.repo_root = self.git_root;
+
+        // Configure languages; an empty selection keeps every language enabled so
+        // files are picked up by extension (auto-detect)
+        let auto_detect = self.languages.enabled.is_empty();
+        for (language, lang_config) in config.languages.iter_mut() {
+            lang_config.enabled = auto_detect || self.languages.enabled.contains(language);
+            if !lang_config.enabled {
+                continue;
+            }
 
-        // Configure languages
-        for language in &self.languages.enabled {
-            if let Some(lang_config) = config.languages.get_mut(language) {
-                lang_config.enabled = true;
-
-                // Apply language-specific settings
-                if let Some(max_size) = self.languages.max_file_size_mb {
-                    lang_config.max_file_size_mb = max_size;
-                }
+            // Apply language-specific settings
+            if let Some(max_size) = self.languages.max_file_size_mb {
+                lang_config.max_file_size_mb = max_size;
+            }
 
-                if let Some(&threshold) = self.languages.complexity_thresholds.get(language) {
-                    lang_config.complexity_threshold = threshold;
-                }
+            if let Some(&threshold) = self.languages.complexity_thresholds.get(language) {
+                lang_config.complexity_threshold = threshold;
             }
         }
 
@@ -502,10 +529,24 @@ impl AnalysisConfig {
                 max_age_days: valknut_config.coverage.max_age_days,
                 search_paths: valknut_config.coverage.search_paths,
             },
+            git_root: valknut_config.analysis.repo_root,
         })
     }
 }
 
+/// Resolve a language name or alias to its [`ValknutConfig::languages`] key,
+/// rejecting languages valknut cannot parse.
+fn validate_language(language: &str) -> Result<String> {
+    let language = language.trim();
+    let unsupported = || ValknutError::unsupported(format!("Unsupported language: {}", language));
+    let key = language_info(language).ok_or_else(unsupported)?.key;
+    ValknutConfig::default()
+        .languages
+        .into_keys()
+        .find(|name| language_info(name).is_some_and(|info| info.key == key))
+        .ok_or_else(unsupported)
+}
+
 // Additional convenience implementations for the new config components
 
 /// Factory methods for [`AnalysisModules`] presets.
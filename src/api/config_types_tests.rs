@@ -13,10 +13,7 @@ fn test_unified_config_default() {
     assert!(config.modules.coverage);
 
     // Check language defaults
-    assert_eq!(
-        config.languages.enabled,
-        vec!["python", "javascript", "typescript"]
-    );
+    assert!(config.languages.enabled.is_empty()); // auto-detect
     assert_eq!(config.languages.max_file_size_mb, Some(10.0));
 
     // Check quality defaults
@@ -80,7 +77,8 @@ fn test_fluent_interface() {
 #[test]
 fn test_convenience_methods() {
     let config = AnalysisConfig::new()
-        .with_languages(vec!["rust".to_string(), "go".to_string()])
+        .with_languages(&["rust", "go"])
+        .unwrap()
         .with_confidence_threshold(0.85)
         .with_max_files(500)
         .exclude_pattern("*/tests/*")
@@ -99,6 +97,75 @@ fn test_convenience_methods() {
         .contains(&"src/**/*.rs".to_string()));
 }
 
+#[test]
+fn test_add_language_is_additive_and_with_languages_replaces() {
+    let config = AnalysisConfig::new()
+        .add_language("python")
+        .unwrap()
+        .add_language("TypeScript")
+        .unwrap()
+        .add_language("python")
+        .unwrap();
+    assert_eq!(config.languages.enabled, vec!["python", "typescript"]);
+
+    let replaced = config.with_languages(&["rust"]).unwrap();
+    assert_eq!(replaced.languages.enabled, vec!["rust"]);
+
+    let auto_detect = replaced.with_languages(&[]).unwrap();
+    assert!(auto_detect.languages.enabled.is_empty());
+    let valknut_config = auto_detect.to_valknut_config();
+    assert!(valknut_config.languages.values().all(|lang| lang.enabled));
+}
+
+#[test]
+fn test_language_aliases_are_stored_as_config_keys() {
+    let config = AnalysisConfig::new()
+        .with_languages(&["py", "ts", "golang", "c++"])
+        .unwrap();
+    assert_eq!(
+        config.languages.enabled,
+        vec!["python", "typescript", "go", "cpp"]
+    );
+
+    let defaults = ValknutConfig::default();
+    assert!(config
+        .languages
+        .enabled
+        .iter()
+        .all(|language| defaults.languages.contains_key(language)));
+}
+
+#[test]
+fn test_language_builders_reject_unsupported_languages() {
+    assert!(AnalysisConfig::new().add_language("cobol").is_err());
+    assert!(AnalysisConfig::new()
+        .with_languages(&["python", "cobol"])
+        .is_err());
+}
+
+#[test]
+fn test_enable_all_modules_keeps_language_selection() {
+    let config = AnalysisConfig::new()
+        .with_languages(&["python", "typescript"])
+        .unwrap()
+        .disable_all_modules()
+        .enable_all_modules();
+
+    assert!(config.modules.duplicates);
+    assert_eq!(config.languages.enabled, vec!["python", "typescript"]);
+    assert!(config.validate().is_ok());
+
+    let valknut_config = config.to_valknut_config();
+    assert!(valknut_config.languages["python"].enabled);
+    assert!(valknut_config.languages["typescript"].enabled);
+    for unlisted in ["javascript", "rust", "go", "cpp"] {
+        assert!(
+            !valknut_config.languages[unlisted].enabled,
+            "{unlisted} was not selected and should be disabled"
+        );
+    }
+}
+
 #[test]
 fn test_module_presets() {
     let essential = AnalysisModules::essential();
@@ -143,7 +210,8 @@ fn test_validation() {
 #[test]
 fn test_config_conversion() {
     let original_config = AnalysisConfig::new()
-        .with_languages(vec!["python".to_string(), "rust".to_string()])
+        .with_languages(&["python", "rust"])
+        .unwrap()
         .modules(|_| AnalysisModules::code_quality())
         .with_confidence_threshold(0.8)
         .with_max_files(200);
@@ -171,7 +239,8 @@ fn test_config_conversion() {
 #[test]
 fn test_serialization() {
     let config = AnalysisConfig::new()
-        .with_language("rust")
+        .add_language("rust")
+        .unwrap()
         .with_confidence_threshold(0.75);
 
     // Test that it can be serialized and deserialized
@@ -199,7 +268,8 @@ fn test_builder_pattern_immutability() {
 fn test_backward_compatibility() {
     // Test that old-style method calls still work
     let config = AnalysisConfig::new()
-        .with_languages(vec!["rust".to_string()])
+        .with_languages(&["rust"])
+        .unwrap()
         .with_confidence_threshold(0.9)
         .with_max_files(500)
         .exclude_pattern("*/tests/*")
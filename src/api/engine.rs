@@ -1,16 +1,47 @@
 //! Main analysis engine implementation.
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use futures::stream::{self, Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 use crate::api::config_types::AnalysisConfig as ApiAnalysisConfig;
+use crate::core::ast_service::AstService;
 use crate::core::config::ValknutConfig;
 use crate::core::errors::{Result, ValknutError};
-use crate::core::featureset::FeatureVector;
-use crate::core::pipeline::AnalysisResults;
+use crate::core::featureset::{
+    CodeEntity, ExtractionContext, FeatureExtractorRegistry, FeatureVector,
+};
+use crate::core::git_utils::auto_detect_git_root;
+use crate::core::pipeline::discovery::code_dictionary::known_code_dictionary;
+use crate::core::pipeline::discovery::file_discovery::discover_files;
 use crate::core::pipeline::{AnalysisConfig as PipelineAnalysisConfig, AnalysisPipeline};
+use crate::core::pipeline::{AnalysisResults, CodeDictionary};
+use crate::detectors::complexity::{AstComplexityExtractor, ComplexityConfig};
+use crate::detectors::refactoring::{RefactoringConfig, RefactoringExtractor};
+use crate::lang::registry::{detect_language_from_path, language_info, language_key_for_path};
+
+/// Ensure `path` exists and is a directory before analysis.
+fn validate_directory(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Err(ValknutError::io(
+            format!("Path does not exist: {}", path.display()),
+            std::io::Error::new(std::io::ErrorKind::NotFound, "Path not found"),
+        ));
+    }
+
+    if !path.is_dir() {
+        return Err(ValknutError::validation(format!(
+            "Path is not a directory: {}",
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
 
 /// Compute the common root directory from a list of paths.
 /// Returns the longest common prefix that ends at a directory boundary.
@@ -43,6 +74,15 @@ fn compute_common_root(paths: &[PathBuf]) -> PathBuf {
     common
 }
 
+/// Empirical number of entities per kilobyte of source.
+const ESTIMATED_ENTITIES_PER_KB: f64 = 10.0;
+
+/// Empirical analysis time per entity, in milliseconds.
+const ESTIMATED_MS_PER_ENTITY: f64 = 1.0;
+
+/// Peak memory per byte of source, covering parse trees and feature vectors.
+const ESTIMATED_MEMORY_BYTES_PER_SOURCE_BYTE: f64 = 8.0;
+
 /// Main valknut analysis engine
 pub struct ValknutEngine {
     /// Internal analysis pipeline
@@ -50,6 +90,9 @@ pub struct ValknutEngine {
 
     /// Engine configuration
     config: Arc<ValknutConfig>,
+
+    /// Definitions for every code the configured detectors can emit
+    code_dictionary: CodeDictionary,
 }
 
 /// Factory and analysis methods for [`ValknutEngine`].
@@ -64,9 +107,11 @@ impl ValknutEngine {
         // Validate configuration
         internal_config.validate()?;
 
+        let code_dictionary =
+            known_code_dictionary(internal_config.analysis.enable_coverage_analysis);
         let config_arc = Arc::new(internal_config.clone());
         let analysis_config = PipelineAnalysisConfig::from(internal_config.clone());
-        let pipeline = AnalysisPipeline::new_with_config(analysis_config, internal_config);
+        let pipeline = AnalysisPipeline::new_with_config(analysis_config, internal_config)?;
 
         // TODO: Register feature extractors based on enabled languages
         // For now, we'll create a minimal setup
@@ -75,12 +120,14 @@ impl ValknutEngine {
         // For this initial implementation, we'll skip the training phase
         // and rely on default configurations
 
-        info!("Valknut engine initialized successfully");
-
-        Ok(Self {
+        let engine = Self {
             pipeline,
             config: config_arc,
-        })
+            code_dictionary,
+        };
+        engine.log_configuration();
+
+        Ok(engine)
     }
 
     /// Create a new engine directly from a fully-populated ValknutConfig.
@@ -92,15 +139,93 @@ impl ValknutEngine {
 
         valknut_config.validate()?;
 
+        let code_dictionary =
+            known_code_dictionary(valknut_config.analysis.enable_coverage_analysis);
         let config_arc = Arc::new(valknut_config.clone());
         let analysis_config = PipelineAnalysisConfig::from(valknut_config.clone());
-        let pipeline = AnalysisPipeline::new_with_config(analysis_config, valknut_config);
-
-        info!("Valknut engine initialized successfully");
+        let pipeline = AnalysisPipeline::new_with_config(analysis_config, valknut_config)?;
 
-        Ok(Self {
+        let engine = Self {
             pipeline,
             config: config_arc,
+            code_dictionary,
+        };
+        engine.log_configuration();
+
+        Ok(engine)
+    }
+
+    /// Estimate the cost of analyzing `root` without running the analysis.
+    ///
+    /// Files are discovered with the same filters the pipeline applies; entity
+    /// count, duration and memory are extrapolated from their total size.
+    pub async fn estimate_analysis_cost(
+        root: &Path,
+        config: &ApiAnalysisConfig,
+    ) -> Result<AnalysisCostEstimate> {
+        Self::estimate_analysis_cost_from_valknut_config(root, &config.clone().to_valknut_config())
+            .await
+    }
+
+    /// Estimate the cost of analyzing `root` directly from a fully-populated ValknutConfig.
+    ///
+    /// Like [`Self::new_from_valknut_config`], this avoids the lossy round-trip
+    /// through the public API config, so discovery sees every filter the CLI set.
+    pub async fn estimate_analysis_cost_from_valknut_config(
+        root: &Path,
+        valknut_config: &ValknutConfig,
+    ) -> Result<AnalysisCostEstimate> {
+        validate_directory(root)?;
+
+        let pipeline_config = PipelineAnalysisConfig::from(valknut_config.clone());
+        let files = discover_files(
+            &[root.to_path_buf()],
+            &pipeline_config,
+            Some(valknut_config),
+        )?;
+        let total_bytes: u64 = files
+            .iter()
+            .filter_map(|file| std::fs::metadata(file).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+
+        Ok(AnalysisCostEstimate::from_totals(files.len(), total_bytes))
+    }
+
+    /// Report what analyzing `root` would cover without running the analysis.
+    ///
+    /// Files are discovered and their languages detected with the pipeline's
+    /// filters, but nothing is parsed; entity count and duration are
+    /// extrapolated from total file size as in [`Self::estimate_analysis_cost`].
+    pub async fn dry_run(&self, root: &Path) -> Result<DryRunReport> {
+        validate_directory(root)?;
+
+        let pipeline_config = PipelineAnalysisConfig::from((*self.config).clone());
+        let files_to_analyze =
+            discover_files(&[root.to_path_buf()], &pipeline_config, Some(&self.config))?;
+        let total_bytes: u64 = files_to_analyze
+            .iter()
+            .filter_map(|file| std::fs::metadata(file).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        let estimate = AnalysisCostEstimate::from_totals(files_to_analyze.len(), total_bytes);
+
+        let mut files_per_language = BTreeMap::new();
+        for file in &files_to_analyze {
+            let language = detect_language_from_path(&file.to_string_lossy());
+            *files_per_language.entry(language).or_insert(0) += 1;
+        }
+
+        Ok(DryRunReport {
+            files_to_analyze,
+            estimated_entities: estimate.estimated_entity_count,
+            modules_to_run: self
+                .enabled_modules()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            estimated_duration_secs: estimate.estimated_duration_secs,
+            files_per_language,
         })
     }
 
@@ -109,27 +234,15 @@ impl ValknutEngine {
         let path = path.as_ref();
         info!("Starting directory analysis: {}", path.display());
 
-        // Verify path exists
-        if !path.exists() {
-            return Err(ValknutError::io(
-                format!("Path does not exist: {}", path.display()),
-                std::io::Error::new(std::io::ErrorKind::NotFound, "Path not found"),
-            ));
-        }
-
-        if !path.is_dir() {
-            return Err(ValknutError::validation(format!(
-                "Path is not a directory: {}",
-                path.display()
-            )));
-        }
+        validate_directory(path)?;
 
         // Run the pipeline
         let pipeline_results = self.pipeline.analyze_directory(path).await?;
 
         // Convert to public API format with the directory as project root
         let project_root = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-        let results = AnalysisResults::from_pipeline_results(pipeline_results, project_root);
+        let mut results = AnalysisResults::from_pipeline_results(pipeline_results, project_root);
+        results.repo_root = self.repo_root_for(&results.project_root);
 
         info!(
             "Directory analysis completed: {} files processed, {} entities analyzed",
@@ -140,6 +253,104 @@ impl ValknutEngine {
         Ok(results)
     }
 
+    /// Analyze a directory, stopping early once `token` is cancelled.
+    ///
+    /// Cancellation is checked between files and between detector stages. A
+    /// cancelled run is not an error: the returned results have `cancelled`
+    /// set and cover only the entities processed before the token fired.
+    pub async fn analyze_directory_with_cancellation(
+        &mut self,
+        root: &Path,
+        token: CancellationToken,
+    ) -> Result<AnalysisResults> {
+        info!(
+            "Starting cancellable directory analysis: {}",
+            root.display()
+        );
+        validate_directory(root)?;
+
+        let paths = vec![root.to_path_buf()];
+        let comprehensive = self
+            .pipeline
+            .analyze_paths_with_cancellation(&paths, None, &token)
+            .await?;
+        let pipeline_results = self.pipeline.wrap_results(comprehensive);
+
+        let project_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let mut results = AnalysisResults::from_pipeline_results(pipeline_results, project_root);
+        results.repo_root = self.repo_root_for(&results.project_root);
+
+        info!(
+            "Directory analysis {}: {} files processed, {} entities analyzed",
+            if results.cancelled {
+                "cancelled"
+            } else {
+                "completed"
+            },
+            results.files_analyzed(),
+            results.summary.entities_analyzed
+        );
+
+        Ok(results)
+    }
+
+    /// Analyze several independent directories, yielding each result as it completes.
+    ///
+    /// Every directory gets its own engine built from this engine's configuration
+    /// and runs on the blocking pool, with at most `performance.max_concurrent`
+    /// analyses in flight. Items arrive in completion order, not in the order of
+    /// `paths`.
+    pub fn batch_analyze(
+        &self,
+        paths: &[PathBuf],
+    ) -> impl Stream<Item = Result<(PathBuf, AnalysisResults)>> {
+        let max_concurrent = self.config.performance.max_concurrent.max(1);
+        let config = Arc::clone(&self.config);
+
+        stream::iter(paths.to_vec())
+            .map(move |path| {
+                let config = Arc::clone(&config);
+                let handle = tokio::runtime::Handle::current();
+                tokio::task::spawn_blocking(move || {
+                    handle.block_on(async move {
+                        let mut engine =
+                            ValknutEngine::new_from_valknut_config((*config).clone()).await?;
+                        let results = engine.analyze_directory(&path).await?;
+                        Ok((path, results))
+                    })
+                })
+            })
+            .buffer_unordered(max_concurrent)
+            .map(|joined| match joined {
+                Ok(result) => result,
+                Err(e) => Err(ValknutError::map_generic("batch analysis task")(e)),
+            })
+    }
+
+    /// Analyze a single file as if it were the only file in the project.
+    ///
+    /// The file's parent directory becomes the project root. Cross-file signals
+    /// such as clone similarity have nothing to compare against and stay at zero.
+    pub async fn analyze_file(&mut self, path: &PathBuf) -> Result<AnalysisResults> {
+        info!("Starting single file analysis: {}", path.display());
+
+        if !path.exists() {
+            return Err(ValknutError::io(
+                format!("Path does not exist: {}", path.display()),
+                std::io::Error::new(std::io::ErrorKind::NotFound, "Path not found"),
+            ));
+        }
+
+        if !path.is_file() {
+            return Err(ValknutError::validation(format!(
+                "Path is not a file: {}",
+                path.display()
+            )));
+        }
+
+        self.analyze_files(std::slice::from_ref(path)).await
+    }
+
     /// Analyze specific files
     pub async fn analyze_files<P: AsRef<Path>>(&mut self, files: &[P]) -> Result<AnalysisResults> {
         info!("Starting analysis of {} specific files", files.len());
@@ -165,10 +376,9 @@ impl ValknutEngine {
 
         // Compute project root from common prefix of file paths
         let project_root = compute_common_root(&paths);
-        Ok(AnalysisResults::from_pipeline_results(
-            pipeline_results,
-            project_root,
-        ))
+        let mut results = AnalysisResults::from_pipeline_results(pipeline_results, project_root);
+        results.repo_root = self.repo_root_for(&results.project_root);
+        Ok(results)
     }
 
     /// Analyze pre-extracted feature vectors (for testing and advanced usage)
@@ -199,11 +409,92 @@ impl ValknutEngine {
         Ok(results)
     }
 
+    /// Analyze an in-memory source snippet without touching the filesystem.
+    ///
+    /// The snippet becomes a single module-level entity that is run through the
+    /// enabled feature extractors. `language` selects the parser; `virtual_path`
+    /// only labels the entity in the results.
+    pub async fn analyze_string(
+        &mut self,
+        source: &str,
+        language: &str,
+        virtual_path: &str,
+    ) -> Result<AnalysisResults> {
+        let language_info = language_info(language).ok_or_else(|| {
+            ValknutError::unsupported(format!("Unsupported language: {}", language))
+        })?;
+        info!(
+            "Analyzing in-memory {} source: {}",
+            language_info.name, virtual_path
+        );
+
+        // Parsers pick their grammar from the path extension, so make sure the
+        // path handed to them matches the requested language.
+        let analysis_path = if language_key_for_path(Path::new(virtual_path)).as_deref()
+            == Some(language_info.key)
+        {
+            virtual_path.to_string()
+        } else {
+            format!("{}.{}", virtual_path, language_info.extensions[0])
+        };
+
+        let name = Path::new(virtual_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| virtual_path.to_string());
+        let entity = CodeEntity::new(
+            format!("{}:module:{}", virtual_path, name),
+            "module",
+            name,
+            analysis_path.clone(),
+        )
+        .with_line_range(1, source.lines().count().max(1))
+        .with_source_code(source);
+
+        let registry = self.in_memory_extractors(&analysis_path, source).await?;
+        let mut context = ExtractionContext::new(self.config.clone(), language_info.key);
+        context.add_entity(entity.clone());
+        let vector = registry.extract_all_features(&entity, &context).await?;
+
+        self.analyze_vectors(vec![vector]).await
+    }
+
+    /// Build the enabled per-entity extractors, primed with in-memory source.
+    async fn in_memory_extractors(
+        &self,
+        analysis_path: &str,
+        source: &str,
+    ) -> Result<FeatureExtractorRegistry> {
+        let ast_service = Arc::new(AstService::new());
+        let mut registry = FeatureExtractorRegistry::new();
+
+        let complexity =
+            AstComplexityExtractor::new(ComplexityConfig::default(), ast_service.clone());
+        complexity.prime_source(analysis_path, source).await?;
+        registry.register(Arc::new(complexity));
+
+        if self.config.analysis.enable_refactoring_analysis {
+            let refactoring =
+                RefactoringExtractor::with_config(RefactoringConfig::default(), ast_service);
+            refactoring.prime_source(analysis_path, source).await?;
+            registry.register(Arc::new(refactoring));
+        }
+
+        Ok(registry)
+    }
+
     /// Get the current configuration
     pub fn config(&self) -> &ValknutConfig {
         &self.config
     }
 
+    /// Get definitions for the issue and suggestion codes this engine can emit.
+    ///
+    /// Built once at construction, so tooling can look up codes without running an analysis.
+    pub fn code_dictionary(&self) -> &CodeDictionary {
+        &self.code_dictionary
+    }
+
     /// Get pipeline status information
     pub fn get_status(&self) -> EngineStatus {
         let pipeline_status = self.pipeline.get_status();
@@ -227,6 +518,94 @@ impl ValknutEngine {
             .collect()
     }
 
+    /// Names of the analysis modules this engine will run, sorted alphabetically
+    fn enabled_modules(&self) -> Vec<&'static str> {
+        let pipeline_config = PipelineAnalysisConfig::from((*self.config).clone());
+        let mut modules: Vec<&'static str> = [
+            ("cohesion", self.config.analysis.enable_cohesion_analysis),
+            ("complexity", pipeline_config.enable_complexity_analysis),
+            ("coverage", pipeline_config.enable_coverage_analysis),
+            ("impact", pipeline_config.enable_impact_analysis),
+            ("lsh", pipeline_config.enable_lsh_analysis),
+            ("names", self.config.analysis.enable_names_analysis),
+            ("refactoring", pipeline_config.enable_refactoring_analysis),
+            ("structure", pipeline_config.enable_structure_analysis),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect();
+        modules.sort_unstable();
+        modules
+    }
+
+    /// Grammar versions of the given languages, skipping unregistered ones
+    fn language_versions(languages: &[String]) -> Vec<&'static str> {
+        languages
+            .iter()
+            .filter_map(|language| language_info(language))
+            .map(|info| info.version)
+            .collect()
+    }
+
+    /// Human-readable summary of the enabled modules and key settings.
+    ///
+    /// One `key: value` line per setting, sorted by key, so the output is
+    /// stable enough to snapshot.
+    pub fn configuration_summary(&self) -> String {
+        let mut languages = self.get_supported_languages();
+        languages.sort_unstable();
+
+        let entries = [
+            (
+                "language_versions",
+                format!("[{}]", Self::language_versions(&languages).join(", ")),
+            ),
+            ("languages", format!("[{}]", languages.join(", "))),
+            ("lsh.num_hashes", self.config.lsh.num_hashes.to_string()),
+            (
+                "lsh.similarity_threshold",
+                self.config.lsh.similarity_threshold.to_string(),
+            ),
+            (
+                "modules_enabled",
+                format!("[{}]", self.enabled_modules().join(", ")),
+            ),
+        ];
+
+        entries
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(key, value)| format!("{key}: {value}\n"))
+            .collect()
+    }
+
+    /// Emit the startup diagnostic describing the active configuration
+    fn log_configuration(&self) {
+        let mut languages = self.get_supported_languages();
+        languages.sort_unstable();
+
+        info!(
+            modules_enabled = ?self.enabled_modules(),
+            language_versions = ?Self::language_versions(&languages),
+            languages = ?languages,
+            lsh.num_hashes = self.config.lsh.num_hashes,
+            lsh.similarity_threshold = self.config.lsh.similarity_threshold,
+            "Valknut engine initialized successfully"
+        );
+    }
+
+    /// Repository root for results rooted at `project_root`: the configured
+    /// root, canonicalized when it exists on disk, or else the git working
+    /// directory containing `project_root`
+    fn repo_root_for(&self, project_root: &Path) -> Option<PathBuf> {
+        let root = match &self.config.analysis.repo_root {
+            Some(root) => root.clone(),
+            None => auto_detect_git_root(project_root).ok()?,
+        };
+        Some(root.canonicalize().unwrap_or(root))
+    }
+
     /// Check if the engine is ready for analysis
     pub fn is_ready(&self) -> bool {
         self.pipeline.is_ready()
@@ -313,6 +692,89 @@ impl ValknutEngine {
     }
 }
 
+/// Projected cost of an analysis run, from [`ValknutEngine::estimate_analysis_cost`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisCostEstimate {
+    /// Number of files that would be analyzed
+    pub file_count: usize,
+
+    /// Approximate number of entities extracted from those files
+    pub estimated_entity_count: usize,
+
+    /// Approximate wall-clock duration in seconds
+    pub estimated_duration_secs: f64,
+
+    /// Approximate peak memory usage in megabytes
+    pub estimated_memory_mb: f64,
+}
+
+/// Factory methods for [`AnalysisCostEstimate`].
+impl AnalysisCostEstimate {
+    /// Extrapolate an estimate from the number and total size of source files
+    pub fn from_totals(file_count: usize, total_bytes: u64) -> Self {
+        let kilobytes = total_bytes as f64 / 1024.0;
+        let estimated_entity_count = (kilobytes * ESTIMATED_ENTITIES_PER_KB).round() as usize;
+
+        Self {
+            file_count,
+            estimated_entity_count,
+            estimated_duration_secs: estimated_entity_count as f64 * ESTIMATED_MS_PER_ENTITY
+                / 1000.0,
+            estimated_memory_mb: total_bytes as f64 * ESTIMATED_MEMORY_BYTES_PER_SOURCE_BYTE
+                / (1024.0 * 1024.0),
+        }
+    }
+}
+
+/// What an analysis run would cover, from [`ValknutEngine::dry_run`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunReport {
+    /// Files that would be analyzed
+    pub files_to_analyze: Vec<PathBuf>,
+
+    /// Approximate number of entities extracted from those files
+    pub estimated_entities: usize,
+
+    /// Analysis modules that would run, sorted by name
+    pub modules_to_run: Vec<String>,
+
+    /// Approximate wall-clock duration in seconds
+    pub estimated_duration_secs: f64,
+
+    /// Number of files per detected language key
+    pub files_per_language: BTreeMap<String, usize>,
+}
+
+/// Formatting methods for [`DryRunReport`].
+impl DryRunReport {
+    /// Multi-line text summary suitable for printing before a full run
+    pub fn display(&self) -> String {
+        let languages = self
+            .files_per_language
+            .iter()
+            .map(|(language, count)| format!("{language} ({count})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "Dry run: {} files would be analyzed\n  \
+             Languages: {}\n  \
+             Modules: {}\n  \
+             Estimated entities: {}\n  \
+             Estimated duration: {:.1}s\n",
+            self.files_to_analyze.len(),
+            if languages.is_empty() {
+                "none".to_string()
+            } else {
+                languages
+            },
+            self.modules_to_run.join(", "),
+            self.estimated_entities,
+            self.estimated_duration_secs,
+        )
+    }
+}
+
 /// Status information about the analysis engine
 #[derive(Debug)]
 pub struct EngineStatus {
@@ -426,6 +888,86 @@ mod tests {
         assert!(!engine.get_supported_languages().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_configuration_summary_is_sorted_and_stable() {
+        let engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+
+        let summary = engine.configuration_summary();
+        assert_eq!(
+            summary,
+            "language_versions: [cpp:20, go:1.22, javascript:es2023, python:3.12, rust:2021, typescript:5.4]\n\
+             languages: [cpp, go, javascript, python, rust, typescript]\n\
+             lsh.num_hashes: 128\n\
+             lsh.similarity_threshold: 0.7\n\
+             modules_enabled: [complexity, coverage, impact, names, refactoring, structure]\n"
+        );
+        assert_eq!(summary, engine.configuration_summary());
+    }
+
+    #[tokio::test]
+    async fn test_estimate_analysis_cost_counts_supported_files() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("lib.rs"), "x".repeat(2048)).unwrap();
+        std::fs::write(temp_dir.path().join("app.py"), "y".repeat(1024)).unwrap();
+        std::fs::write(temp_dir.path().join("notes.bin"), "z".repeat(4096)).unwrap();
+
+        let estimate =
+            ValknutEngine::estimate_analysis_cost(temp_dir.path(), &AnalysisConfig::default())
+                .await
+                .unwrap();
+
+        assert_eq!(estimate, AnalysisCostEstimate::from_totals(2, 3072));
+        assert_eq!(estimate.estimated_entity_count, 30);
+        assert!((estimate.estimated_duration_secs - 0.03).abs() < 1e-9);
+
+        let missing = Path::new("/nonexistent/path");
+        assert!(
+            ValknutEngine::estimate_analysis_cost(missing, &AnalysisConfig::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_files_modules_and_estimates() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("lib.rs"), "x".repeat(2048)).unwrap();
+        std::fs::write(temp_dir.path().join("app.py"), "y".repeat(1024)).unwrap();
+        std::fs::write(temp_dir.path().join("notes.bin"), "z".repeat(4096)).unwrap();
+        let engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+
+        let report = engine.dry_run(temp_dir.path()).await.unwrap();
+
+        assert_eq!(report.files_to_analyze.len(), 2);
+        assert_eq!(report.estimated_entities, 30);
+        assert_eq!(
+            report.files_per_language,
+            BTreeMap::from([("py".to_string(), 1), ("rs".to_string(), 1)])
+        );
+        assert!(report.modules_to_run.contains(&"complexity".to_string()));
+        assert_eq!(
+            report.display(),
+            "Dry run: 2 files would be analyzed\n  \
+             Languages: py (1), rs (1)\n  \
+             Modules: complexity, coverage, impact, names, refactoring, structure\n  \
+             Estimated entities: 30\n  \
+             Estimated duration: 0.0s\n"
+        );
+
+        assert!(engine
+            .dry_run(Path::new("/nonexistent/path"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_code_dictionary_available_without_analysis() {
+        let engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+        let dictionary = engine.code_dictionary();
+        assert!(dictionary.issues.contains_key("CMPLX"));
+        assert!(dictionary.suggestions.contains_key("XTRMTH"));
+    }
+
     #[tokio::test]
     async fn test_analyze_nonexistent_directory() {
         let config = AnalysisConfig::default();
@@ -461,6 +1003,114 @@ mod tests {
         assert_eq!(results.summary.entities_analyzed, 0);
     }
 
+    #[tokio::test]
+    async fn test_analyze_subdirectory_paths_stay_relative_to_git_root() {
+        let temp_dir = TempDir::new().unwrap();
+        git2::Repository::init(temp_dir.path()).unwrap();
+        let src = temp_dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("app.py"), "def app():\n    return 1\n").unwrap();
+
+        let mut engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+        let results = engine.analyze_directory(&src).await.unwrap();
+
+        let git_root = temp_dir.path().canonicalize().unwrap();
+        assert_eq!(results.repo_root.as_deref(), Some(git_root.as_path()));
+        assert_eq!(
+            results.repo_relative_path("app.py"),
+            PathBuf::from("src").join("app.py")
+        );
+        assert_eq!(
+            auto_detect_git_root(&src).unwrap().canonicalize().unwrap(),
+            git_root
+        );
+    }
+
+    #[tokio::test]
+    async fn test_configured_git_root_overrides_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("app.py"), "def app():\n    return 1\n").unwrap();
+        // A nested repository makes detection land on `src` no matter what
+        // encloses the temp dir, so only the configured root can yield `src/`.
+        git2::Repository::init(&src).unwrap();
+        assert_eq!(
+            auto_detect_git_root(&src).unwrap().canonicalize().unwrap(),
+            src.canonicalize().unwrap()
+        );
+
+        let config = AnalysisConfig::default().with_git_root(temp_dir.path().to_path_buf());
+        let mut engine = ValknutEngine::new(config).await.unwrap();
+        let results = engine.analyze_directory(&src).await.unwrap();
+
+        assert_eq!(
+            results.repo_relative_path("app.py"),
+            PathBuf::from("src").join("app.py")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_analyze_directory_with_cancelled_token_returns_partial_results() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.py"), "def a():\n    return 1\n").unwrap();
+        let mut engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let results = engine
+            .analyze_directory_with_cancellation(temp_dir.path(), token)
+            .await
+            .unwrap();
+
+        assert!(results.cancelled);
+        assert_eq!(results.summary.entities_analyzed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_directory_with_cancellation_rejects_missing_path() {
+        let mut engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+
+        let result = engine
+            .analyze_directory_with_cancellation(
+                Path::new("/nonexistent/path"),
+                CancellationToken::new(),
+            )
+            .await;
+        assert!(matches!(result, Err(ValknutError::Io { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_batch_analyze_yields_result_per_directory() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        std::fs::write(first.path().join("a.py"), "def a():\n    return 1\n").unwrap();
+        std::fs::write(second.path().join("b.py"), "def b():\n    return 2\n").unwrap();
+        let engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+
+        let paths = vec![
+            first.path().to_path_buf(),
+            second.path().to_path_buf(),
+            PathBuf::from("/nonexistent/path"),
+        ];
+        let items: Vec<_> = engine.batch_analyze(&paths).collect().await;
+
+        assert_eq!(items.len(), 3);
+        let mut analyzed: Vec<PathBuf> = items
+            .iter()
+            .filter_map(|item| item.as_ref().ok())
+            .map(|(path, results)| {
+                assert_eq!(results.files_analyzed(), 1);
+                path.clone()
+            })
+            .collect();
+        analyzed.sort();
+        let mut expected = paths[..2].to_vec();
+        expected.sort();
+        assert_eq!(analyzed, expected);
+        assert_eq!(items.iter().filter(|item| item.is_err()).count(), 1);
+    }
+
     #[tokio::test]
     async fn test_analyze_vectors() {
         let config = AnalysisConfig::default();
@@ -530,6 +1180,34 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_analyze_file_scores_fixture() {
+        let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/datasets/refactoring_test_cases/guaranteed_long_method.py");
+        let mut engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+
+        let results = engine.analyze_file(&fixture).await.unwrap();
+
+        assert_eq!(results.summary.files_processed, 1);
+        assert!(results.summary.entities_analyzed > 0);
+        assert!(!results.passes.complexity.detailed_results.is_empty());
+        assert!(results.passes.structure.enabled);
+        assert!(results.passes.refactoring.enabled);
+        assert_eq!(
+            results.project_root,
+            fixture.parent().unwrap().canonicalize().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_analyze_file_rejects_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut engine = ValknutEngine::new(AnalysisConfig::default()).await.unwrap();
+
+        let result = engine.analyze_file(&temp_dir.path().to_path_buf()).await;
+        assert!(matches!(result, Err(ValknutError::Validation { .. })));
+    }
+
     #[tokio::test]
     async fn test_analyze_files_empty_list() {
         let config = AnalysisConfig::default();
@@ -611,6 +1289,29 @@ mod tests {
         // Engine should process something (entities_analyzed is unsigned, always >= 0)
     }
 
+    #[tokio::test]
+    async fn test_analyze_string_scores_single_entity() {
+        let config = AnalysisConfig::default();
+        let mut engine = ValknutEngine::new(config).await.unwrap();
+
+        let source = "def check(x):\n    if x > 1:\n        return x\n    return 0\n";
+        let results = engine
+            .analyze_string(source, "python", "snippets/check")
+            .await
+            .unwrap();
+
+        assert_eq!(results.summary.entities_analyzed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_string_rejects_unknown_language() {
+        let config = AnalysisConfig::default();
+        let mut engine = ValknutEngine::new(config).await.unwrap();
+
+        let result = engine.analyze_string("x", "cobol", "legacy.cbl").await;
+        assert!(matches!(result, Err(ValknutError::Unsupported { .. })));
+    }
+
     #[tokio::test]
     async fn test_config_access() {
         let original_config = AnalysisConfig::default()
@@ -637,7 +1338,8 @@ mod tests {
     #[tokio::test]
     async fn test_get_supported_languages() {
         let config = AnalysisConfig::default()
-            .with_languages(vec!["python".to_string(), "javascript".to_string()]);
+            .with_languages(&["python", "javascript"])
+            .unwrap();
         let engine = ValknutEngine::new(config).await.unwrap();
 
         let languages = engine.get_supported_languages();
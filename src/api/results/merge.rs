@@ -14,6 +14,8 @@ use super::models::{
     AnalysisResults, CloneAnalysisPerformance, CloneAnalysisResults, MemoryStats,
     PhaseFilteringStats,
 };
+use crate::core::pipeline::results::sort_change_coupled_pairs;
+use crate::core::pipeline::MAX_COVERAGE_HOT_SPOTS;
 
 /// Merge operations for combining [`AnalysisResults`] from parallel runs.
 impl AnalysisResults {
@@ -102,6 +104,19 @@ impl AnalysisResults {
         }
 
         self.coverage_packs.extend(other.coverage_packs.into_iter());
+        self.coverage_hot_spots.extend(other.coverage_hot_spots);
+        self.coverage_hot_spots
+            .sort_by(|a, b| b.gap_severity.total_cmp(&a.gap_severity));
+        self.coverage_hot_spots.truncate(MAX_COVERAGE_HOT_SPOTS);
+        self.shotgun_surgery.extend(other.shotgun_surgery);
+        self.shotgun_surgery.sort_by(|a, b| {
+            b.co_change_count
+                .cmp(&a.co_change_count)
+                .then_with(|| b.importer_count.cmp(&a.importer_count))
+                .then_with(|| a.file.cmp(&b.file))
+        });
+        self.change_coupled_pairs.extend(other.change_coupled_pairs);
+        sort_change_coupled_pairs(&mut self.change_coupled_pairs);
         self.warnings.extend(other.warnings.into_iter());
     }
 }
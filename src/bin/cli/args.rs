@@ -260,10 +260,18 @@ pub struct DocAuditArgs {
     #[arg(long, default_value_t = valknut_rs::doc_audit::DEFAULT_MAX_README_COMMITS)]
     pub max_readme_commits: usize,
 
-    /// Exit with non-zero status when any issues are detected
+    /// Also mark README as stale once it is older than this many days and the directory changed since
     #[arg(long)]
+    pub stale_after_days: Option<u64>,
+
+    /// Exit with non-zero status when any issues are detected
+    #[arg(long, alias = "fail-on-issues")]
     pub strict: bool,
 
+    /// Insert TODO stub doc comments for undocumented items
+    #[arg(long)]
+    pub fix: bool,
+
     /// Output format for audit results
     #[arg(long, value_enum, default_value = "text")]
     pub format: DocAuditFormat,
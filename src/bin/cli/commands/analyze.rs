@@ -45,6 +45,7 @@ use owo_colors::OwoColorize;
 use serde_json;
 use serde_yaml;
 use std::env;
+use std::io::IsTerminal;
 use std::path::Path;
 use std::path::PathBuf;
 use tabled::{settings::Style as TableStyle, Table, Tabled};
@@ -64,10 +65,13 @@ use valknut_rs::core::pipeline::{
 use valknut_rs::core::scoring::Priority;
 use valknut_rs::detectors::structure::StructureConfig;
 use valknut_rs::io::reports::ReportGenerator;
-use valknut_rs::lang::{extension_is_supported, registered_languages, LanguageStability};
+use valknut_rs::lang::{extension_is_supported, registered_language_info, LanguageStability};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// File count above which interactive runs ask for confirmation before analyzing.
+const LARGE_CODEBASE_FILE_COUNT: usize = 5_000;
+
 /// Main analyze command implementation with comprehensive analysis pipeline
 pub async fn analyze_command(
     args: AnalyzeArgs,
@@ -149,12 +153,49 @@ async fn display_pre_analysis_info(
         return Ok(());
     }
     print_run_overview(valid_paths, args, config, detail_mode);
+    confirm_analysis_cost(valid_paths, config).await?;
     if config.analysis.enable_coverage_analysis {
         preview_coverage_discovery(valid_paths, &config.coverage, detail_mode).await?;
     }
     Ok(())
 }
 
+/// Print the projected analysis cost and, for large codebases on an interactive
+/// terminal, ask the user to confirm before continuing.
+async fn confirm_analysis_cost(
+    valid_paths: &[PathBuf],
+    config: &ValknutConfig,
+) -> anyhow::Result<()> {
+    let mut estimates = Vec::new();
+    for path in valid_paths.iter().filter(|path| path.is_dir()) {
+        estimates
+            .push(ValknutEngine::estimate_analysis_cost_from_valknut_config(path, config).await?);
+    }
+    if estimates.is_empty() {
+        return Ok(());
+    }
+
+    let file_count: usize = estimates.iter().map(|e| e.file_count).sum();
+    let entity_count: usize = estimates.iter().map(|e| e.estimated_entity_count).sum();
+    let duration_secs: f64 = estimates.iter().map(|e| e.estimated_duration_secs).sum();
+    let memory_mb: f64 = estimates.iter().map(|e| e.estimated_memory_mb).sum();
+    println!(
+        "Estimate: {file_count} files | ~{entity_count} entities | ~{duration_secs:.1}s | ~{memory_mb:.0} MB"
+    );
+
+    if file_count < LARGE_CODEBASE_FILE_COUNT || !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+    let proceed = dialoguer::Confirm::new()
+        .with_prompt(format!("Analyze {file_count} files?"))
+        .default(true)
+        .interact()?;
+    if !proceed {
+        return Err(anyhow::anyhow!("Analysis cancelled by user"));
+    }
+    Ok(())
+}
+
 /// Run the analysis phase with appropriate progress display.
 async fn run_analysis_phase(
     valid_paths: &[PathBuf],
@@ -519,7 +560,7 @@ pub async fn list_languages() -> anyhow::Result<()> {
         "{}",
         "🔤 Supported Programming Languages".bright_blue().bold()
     );
-    let languages = registered_languages();
+    let languages = registered_language_info();
     println!("   Found {} supported languages", languages.len());
     println!();
 
@@ -528,6 +569,7 @@ pub async fn list_languages() -> anyhow::Result<()> {
     struct LanguageRow {
         language: String,
         extension: String,
+        version: String,
         status: String,
         features: String,
     }
@@ -549,6 +591,7 @@ pub async fn list_languages() -> anyhow::Result<()> {
             LanguageRow {
                 language: info.name.to_string(),
                 extension: extensions,
+                version: info.version.to_string(),
                 status: status.to_string(),
                 features: info.notes.to_string(),
             }
@@ -601,7 +644,8 @@ pub async fn run_analysis_with_progress(
         display_analysis_config(&pipeline_config, valknut_config.cohesion.enabled);
     }
 
-    let pipeline = AnalysisPipeline::new_with_config(pipeline_config, valknut_config);
+    let pipeline = AnalysisPipeline::new_with_config(pipeline_config, valknut_config)
+        .map_err(|e| anyhow::anyhow!("Invalid analysis configuration: {}", e))?;
 
     let progress_callback: ProgressCallback = Box::new({
         let pb = main_pb.clone();
@@ -634,7 +678,8 @@ pub async fn run_analysis_without_progress(
 
     let valknut_config = build_analysis_config(args).await?;
     let pipeline_config = PipelineAnalysisConfig::from(valknut_config.clone());
-    let pipeline = AnalysisPipeline::new_with_config(pipeline_config, valknut_config);
+    let pipeline = AnalysisPipeline::new_with_config(pipeline_config, valknut_config)
+        .map_err(|e| anyhow::anyhow!("Invalid analysis configuration: {}", e))?;
 
     info!("Starting comprehensive analysis for {} paths", paths.len());
     let analysis_result = pipeline
@@ -316,7 +316,9 @@ fn create_doc_args(root: PathBuf) -> DocAuditArgs {
         root,
         complexity_threshold: usize::MAX,
         max_readme_commits: usize::MAX,
+        stale_after_days: None,
         strict: false,
+        fix: false,
         format: DocAuditFormat::Text,
         ignore_dir: vec![],
         ignore_suffix: vec![],
@@ -449,6 +451,10 @@ fn sample_analysis_results() -> AnalysisResults {
         }),
         clone_analysis: None,
         coverage_packs: Vec::new(),
+        coverage_hot_spots: Vec::new(),
+        shotgun_surgery: Vec::new(),
+        change_coupled_pairs: Vec::new(),
+        unique_entities: Vec::new(),
         warnings: Vec::new(),
         code_dictionary: CodeDictionary::default(),
         documentation: None,
@@ -456,6 +462,10 @@ fn sample_analysis_results() -> AnalysisResults {
         file_health: HashMap::new(),
         entity_health: HashMap::new(),
         directory_health_tree: None,
+        cancelled: false,
+        repo_root: None,
+        hub_files: Vec::new(),
+        import_cycles: Vec::new(),
     }
 }
 
@@ -468,6 +478,7 @@ fn sample_oracle_response() -> RefactoringOracleResponse {
             ),
             architectural_narrative: None,
             architectural_style: Some("Modular Architecture".to_string()),
+            architectural_patterns: vec![],
             strengths: vec!["Good separation of concerns".to_string()],
             issues: vec!["Clone density".to_string()],
         },
@@ -4,6 +4,7 @@
 //! documentation quality and coverage in a codebase.
 
 use std::collections::HashSet;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
@@ -18,6 +19,8 @@ pub struct DocAuditConfigFile {
     pub root: Option<PathBuf>,
     pub complexity_threshold: Option<usize>,
     pub max_readme_commits: Option<usize>,
+    pub staleness_mode: Option<doc_audit::StalenessMode>,
+    pub stale_after_days: Option<u64>,
     #[serde(default)]
     pub ignore_dir: Vec<String>,
     #[serde(default)]
@@ -39,6 +42,9 @@ pub fn doc_audit_command(args: DocAuditArgs) -> anyhow::Result<()> {
 
     config.complexity_threshold = args.complexity_threshold;
     config.max_readme_commits = args.max_readme_commits;
+    if let Some(days) = args.stale_after_days {
+        config = config.with_git_age_window(days);
+    }
     apply_cli_ignores_to_doc_audit(
         &mut config,
         &args.ignore_dir,
@@ -49,6 +55,11 @@ pub fn doc_audit_command(args: DocAuditArgs) -> anyhow::Result<()> {
     let result = doc_audit::run_audit(&config)?;
     render_doc_audit_output(&result, &args.format)?;
 
+    if args.fix {
+        let fixes = doc_audit::apply_fixes(&result.documentation_issues, &config)?;
+        eprintln!("Inserted {} stub doc comment(s).", fixes.len());
+    }
+
     if args.strict && result.has_issues() {
         anyhow::bail!("Documentation audit found issues");
     }
@@ -128,6 +139,12 @@ pub fn apply_file_config_to_doc_audit(
     if let Some(commits) = file_cfg.max_readme_commits {
         config.max_readme_commits = commits;
     }
+    if let Some(mode) = file_cfg.staleness_mode {
+        config.staleness_mode = mode;
+    }
+    if let Some(days) = file_cfg.stale_after_days {
+        config.stale_after_days = Some(days);
+    }
     extend_ignore_set(&mut config.ignore_dirs, file_cfg.ignore_dir);
     extend_ignore_set(&mut config.ignore_suffixes, file_cfg.ignore_suffix);
     extend_ignore_vec(&mut config.ignore_globs, file_cfg.ignore);
@@ -170,7 +187,11 @@ pub fn render_doc_audit_output(
 ) -> anyhow::Result<()> {
     match format {
         DocAuditFormat::Text => println!("{}", doc_audit::render_text(result)),
-        DocAuditFormat::Json => println!("{}", doc_audit::render_json(result)?),
+        DocAuditFormat::Json => {
+            let mut stdout = std::io::stdout().lock();
+            doc_audit::render_json_to_writer(result, &mut stdout)?;
+            writeln!(stdout)?;
+        }
     }
     Ok(())
 }
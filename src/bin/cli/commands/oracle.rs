@@ -10,7 +10,11 @@ use tracing::warn;
 use crate::cli::args::AnalyzeArgs;
 use crate::cli::reports::is_quiet;
 use valknut_rs::api::results::AnalysisResults;
-use valknut_rs::oracle::{OracleConfig, RefactoringOracle, RefactoringOracleResponse};
+use valknut_rs::oracle::{
+    default_test_path_patterns, default_vendored_path_patterns, estimate_token_cost, OracleBackend,
+    OracleConfig, RefactoringOracle, RefactoringOracleResponse, TokenCostEstimate,
+    GEMINI_2_5_PRO_COST_PER_MILLION_TOKENS, GEMINI_2_5_PRO_OUTPUT_COST_PER_MILLION_TOKENS,
+};
 
 /// Run Oracle dry-run to show slicing plan without calling the API.
 ///
@@ -28,20 +32,33 @@ pub fn run_oracle_dry_run(paths: &[PathBuf], args: &AnalyzeArgs) -> anyhow::Resu
         slice_token_budget: args.ai_features.oracle_slice_budget.unwrap_or(200_000),
         slice_model: String::new(),
         slicing_threshold: args.ai_features.oracle_slicing_threshold.unwrap_or(300_000),
+        test_path_patterns: default_test_path_patterns(),
+        vendored_path_patterns: default_vendored_path_patterns(),
+        thinking_budget: None,
+        backend: OracleBackend::Gemini,
+        cost_per_million_tokens: GEMINI_2_5_PRO_COST_PER_MILLION_TOKENS,
+        output_cost_per_million_tokens: GEMINI_2_5_PRO_OUTPUT_COST_PER_MILLION_TOKENS,
     };
 
     if let Some(max_tokens) = args.ai_features.oracle_max_tokens {
         config.max_tokens = max_tokens;
     }
 
-    let oracle = RefactoringOracle::new(config);
     let project_path = paths
         .first()
         .ok_or_else(|| anyhow::anyhow!("No paths provided"))?;
+    let estimate = estimate_token_cost(&config, project_path);
 
+    let oracle = RefactoringOracle::new(config);
     oracle
         .dry_run(project_path)
-        .map_err(|e| anyhow::anyhow!("Oracle dry-run failed: {}", e))
+        .map_err(|e| anyhow::anyhow!("Oracle dry-run failed: {}", e))?;
+
+    match estimate {
+        Ok(estimate) => print_token_cost_estimate(&estimate),
+        Err(e) => warn!("Failed to estimate oracle token cost: {}", e),
+    }
+    Ok(())
 }
 
 /// Run Oracle analysis to get AI refactoring suggestions.
@@ -89,6 +106,10 @@ pub async fn run_oracle_analysis(
             "Oracle: analyzing {} for refactoring suggestions",
             project_path.display()
         );
+        match oracle.estimate_token_cost(project_path).await {
+            Ok(estimate) => print_token_cost_estimate(&estimate),
+            Err(e) => warn!("Failed to estimate oracle token cost: {}", e),
+        }
     }
 
     match oracle
@@ -109,6 +130,7 @@ pub async fn run_oracle_analysis(
                     required_tasks,
                     optional_tasks
                 );
+                print!("{}", response.assessment.render_text());
             }
 
             // Save oracle response to a separate file for review
@@ -137,3 +159,14 @@ pub async fn run_oracle_analysis(
         }
     }
 }
+
+/// Print the projected token usage and API cost of an oracle run.
+fn print_token_cost_estimate(estimate: &TokenCostEstimate) {
+    println!(
+        "Oracle: ~{} input + ~{} output tokens across {} call(s), estimated cost ${:.2}",
+        estimate.estimated_input_tokens,
+        estimate.estimated_output_tokens,
+        estimate.slice_count,
+        estimate.estimated_cost_usd
+    );
+}
@@ -63,12 +63,12 @@ pub fn apply_performance_profile(config: &mut ValknutConfig, profile: &Performan
 pub fn apply_dev_clone_presets(config: &mut ValknutConfig) {
     config.analysis.enable_lsh_analysis = true;
     config.denoise.enabled = true;
-    config.denoise.min_function_tokens = config.denoise.min_function_tokens.min(8).max(1);
+    config.denoise.min_function_tokens = config.denoise.min_function_tokens.min(8).max(5);
     config.denoise.min_match_tokens = config.denoise.min_match_tokens.min(6).max(1);
     config.denoise.require_blocks = 1;
     config.denoise.similarity = config.denoise.similarity.min(0.7);
     config.denoise.threshold_s = config.denoise.similarity;
-    config.dedupe.min_ast_nodes = config.dedupe.min_ast_nodes.min(8).max(1);
+    config.dedupe.min_ast_nodes = config.dedupe.min_ast_nodes.min(8).max(3);
     config.dedupe.min_match_tokens = config.dedupe.min_match_tokens.min(8).max(1);
     config.lsh.use_semantic_similarity = true;
     config.lsh.similarity_threshold = config.lsh.similarity_threshold.min(0.7);
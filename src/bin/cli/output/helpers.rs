@@ -12,6 +12,7 @@ pub fn refactoring_type_emoji(refactoring_type: &str) -> &'static str {
         "ImproveNaming" => "📝",
         "SimplifyConditionals" => "🔀",
         "RemoveDeadCode" => "🧹",
+        "IntroduceParameterObject" => "🧳",
         _ => "🔧",
     }
 }
@@ -25,6 +26,8 @@ pub fn format_refactoring_type(refactoring_type: &str) -> String {
         .replace("Improve", "Improve ")
         .replace("Simplify", "Simplify ")
         .replace("Remove", "Remove ")
+        .replace("Introduce", "Introduce ")
+        .replace("ParameterObject", "Parameter Object")
 }
 
 /// Format location from a recommendation's location array.
@@ -246,6 +246,10 @@ fn build_sample_analysis_results() -> AnalysisResults {
         health_metrics: None,
         clone_analysis: None,
         coverage_packs: Vec::new(),
+        coverage_hot_spots: Vec::new(),
+        shotgun_surgery: Vec::new(),
+        change_coupled_pairs: Vec::new(),
+        unique_entities: Vec::new(),
         warnings: vec!["Sample warning".to_string()],
         code_dictionary: CodeDictionary::default(),
         documentation: None,
@@ -253,6 +257,10 @@ fn build_sample_analysis_results() -> AnalysisResults {
         file_health: HashMap::new(),
         entity_health: HashMap::new(),
         directory_health_tree: None,
+        cancelled: false,
+        repo_root: None,
+        hub_files: Vec::new(),
+        import_cycles: Vec::new(),
     }
 }
 
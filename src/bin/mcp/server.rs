@@ -138,12 +138,8 @@ impl McpServer {
         let analysis_config = valknut_rs::api::config_types::AnalysisConfig::default()
             .with_confidence_threshold(0.75)
             .with_max_files(5000)
-            .with_languages(vec![
-                "python".to_string(),
-                "typescript".to_string(),
-                "javascript".to_string(),
-                "rust".to_string(),
-            ]);
+            .with_languages(&["python", "typescript", "javascript", "rust"])
+            .expect("built-in languages are supported");
 
         let mut engine = valknut_rs::api::engine::ValknutEngine::new(analysis_config)
             .await
@@ -598,6 +594,10 @@ mod tests {
             health_metrics: None,
             clone_analysis: None,
             coverage_packs: Vec::new(),
+            coverage_hot_spots: Vec::new(),
+            shotgun_surgery: Vec::new(),
+            change_coupled_pairs: Vec::new(),
+            unique_entities: Vec::new(),
             warnings: vec!["Minor warning".to_string()],
             code_dictionary,
             documentation: None,
@@ -605,6 +605,10 @@ mod tests {
             file_health: HashMap::new(),
             entity_health: HashMap::new(),
             directory_health_tree: None,
+            cancelled: false,
+            repo_root: None,
+            hub_files: Vec::new(),
+            import_cycles: Vec::new(),
         }
     }
 
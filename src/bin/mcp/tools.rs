@@ -100,12 +100,8 @@ pub async fn execute_analyze_code(params: AnalyzeCodeParams) -> Result<ToolResul
     let analysis_config = AnalysisConfig::default()
         .with_confidence_threshold(0.75)
         .with_max_files(5000)
-        .with_languages(vec![
-            "python".to_string(),
-            "typescript".to_string(),
-            "javascript".to_string(),
-            "rust".to_string(),
-        ]);
+        .with_languages(&["python", "typescript", "javascript", "rust"])
+        .expect("built-in languages are supported");
 
     // Initialize the analysis engine
     let results = match analyze_with_cache(&analysis_config, path).await {
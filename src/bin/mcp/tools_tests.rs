@@ -114,6 +114,10 @@ fn sample_results() -> AnalysisResults {
         health_metrics: None,
         clone_analysis: None,
         coverage_packs: Vec::new(),
+        coverage_hot_spots: Vec::new(),
+        shotgun_surgery: Vec::new(),
+        change_coupled_pairs: Vec::new(),
+        unique_entities: Vec::new(),
         warnings: Vec::new(),
         code_dictionary,
         documentation: None,
@@ -121,6 +125,10 @@ fn sample_results() -> AnalysisResults {
         file_health: HashMap::new(),
         entity_health: HashMap::new(),
         directory_health_tree: None,
+        cancelled: false,
+        repo_root: None,
+        hub_files: Vec::new(),
+        import_cycles: Vec::new(),
     }
 }
 
@@ -459,7 +467,8 @@ pub fn coverage_demo() -> i32 {
     }
 
     let config = AnalysisConfig::default()
-        .with_languages(vec!["rust".to_string()])
+        .with_languages(&["rust"])
+        .unwrap()
         .with_max_files(1);
 
     let refreshed = analyze_with_session_cache(&config, project_path, &cache)
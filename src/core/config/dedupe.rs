@@ -75,6 +75,11 @@ pub struct DedupeConfig {
     /// Adaptive denoising configuration
     #[serde(default)]
     pub adaptive: AdaptiveDenoiseConfig,
+
+    /// Drop import lines before shingling so shared import headers do not
+    /// make unrelated files look similar
+    #[serde(default)]
+    pub strip_imports: bool,
 }
 
 /// Clone denoising configuration for reducing noise in clone detection
@@ -179,6 +184,13 @@ pub type DenoiseWeights = SimilarityWeights;
 pub type DedupeWeights = SimilarityWeights;
 
 /// Stop motifs configuration for AST-based boilerplate filtering
+///
+/// This config only selects how many mined patterns count as boilerplate; it
+/// carries no motif sequences itself. Token k-grams are mined into the
+/// stop-motif cache at `CacheRefreshPolicy::k_gram_size`, which should not
+/// exceed [`DedupeConfig::shingle_k`] or the mined motifs can never match a
+/// shingle. Because the sequences only exist once the cache is built, there is
+/// nothing to cross-check when an `LshExtractor` is constructed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StopMotifsConfig {
     /// Enable stop motifs filtering
@@ -290,6 +302,9 @@ pub enum RankingBy {
 
     /// Rank by frequency/occurrence count
     Frequency,
+
+    /// Rank by TF-IDF weighted MinHash similarity
+    WeightedSimilarity,
 }
 
 /// Default implementation for [`RankingConfig`].
@@ -436,6 +451,24 @@ pub struct AdaptiveDenoiseConfig {
     /// Enable automatic cache refresh
     #[serde(default)]
     pub auto_refresh_cache: bool,
+
+    /// Starting relaxation of the fragment thresholds (0.0 keeps them fixed)
+    #[serde(default)]
+    pub min_denoise_threshold: f64,
+
+    /// Largest relaxation the adaptive thresholds may reach; equal to the
+    /// minimum disables adaptation
+    #[serde(default)]
+    pub max_denoise_threshold: f64,
+
+    /// Relaxation added per adjustment
+    #[serde(default = "default_denoise_step_size")]
+    pub step_size: f64,
+}
+
+/// Default relaxation step for [`AdaptiveDenoiseConfig::step_size`].
+fn default_denoise_step_size() -> f64 {
+    0.1
 }
 
 /// Default implementation for [`AdaptiveDenoiseConfig`].
@@ -457,6 +490,9 @@ impl Default for AdaptiveDenoiseConfig {
             external_call_jaccard_threshold: 0.2,
             cache_refresh_days: 7,
             auto_refresh_cache: true,
+            min_denoise_threshold: 0.0,
+            max_denoise_threshold: 0.0,
+            step_size: default_denoise_step_size(),
         }
     }
 }
@@ -495,6 +531,7 @@ impl Default for DedupeConfig {
             min_saved_tokens: 100,
             keep_top_per_file: 3,
             adaptive: AdaptiveDenoiseConfig::default(),
+            strip_imports: false,
         }
     }
 }
@@ -520,6 +557,16 @@ impl AdaptiveDenoiseConfig {
             "adaptive.external_call_jaccard_threshold",
         )?;
         validate_positive_i64(self.cache_refresh_days, "adaptive.cache_refresh_days")?;
+        validate_unit_range(self.min_denoise_threshold, "adaptive.min_denoise_threshold")?;
+        validate_unit_range(self.max_denoise_threshold, "adaptive.max_denoise_threshold")?;
+        if self.max_denoise_threshold < self.min_denoise_threshold {
+            return Err(ValknutError::validation(
+                "adaptive.max_denoise_threshold must not be below adaptive.min_denoise_threshold",
+            ));
+        }
+        if self.max_denoise_threshold > self.min_denoise_threshold {
+            validate_positive_f64(self.step_size, "adaptive.step_size")?;
+        }
         Ok(())
     }
 }
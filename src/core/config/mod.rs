@@ -369,6 +369,17 @@ pub struct AnalysisConfig {
     /// Files larger than this are skipped during file discovery
     #[serde(default = "AnalysisConfig::default_max_file_size_bytes")]
     pub max_file_size_bytes: u64,
+
+    /// Directory for caches persisted between runs: project import snapshots,
+    /// plus LSH MinHash signatures with the `persistent-cache` feature
+    /// (`None` keeps caches in memory)
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Repository root that report paths are made relative to
+    /// (`None` keeps paths relative to the analyzed directory)
+    #[serde(default)]
+    pub repo_root: Option<PathBuf>,
 }
 
 /// Default implementation for [`AnalysisConfig`].
@@ -396,6 +407,8 @@ impl Default for AnalysisConfig {
             include_patterns: vec!["**/*".to_string()],
             ignore_patterns: Vec::new(),
             max_file_size_bytes: Self::default_max_file_size_bytes(),
+            cache_dir: None,
+            repo_root: None,
         }
     }
 }
@@ -467,13 +480,18 @@ impl GraphConfig {
 }
 
 /// LSH and similarity detection configuration
+///
+/// `num_hashes` must be divisible by `num_bands` so every band covers the same
+/// number of hashes. [`validate`](Self::validate) rejects other values, and
+/// [`LshExtractor::with_lsh_config`](crate::detectors::lsh::LshExtractor::with_lsh_config)
+/// rounds `num_bands` down to the nearest divisor.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LshConfig {
-    /// Number of hash functions per band
+    /// Total number of MinHash functions, split evenly across the bands
     #[serde(default)]
     pub num_hashes: usize,
 
-    /// Number of LSH bands
+    /// Number of LSH bands; must divide `num_hashes`
     #[serde(default)]
     pub num_bands: usize,
 
@@ -493,6 +511,10 @@ pub struct LshConfig {
     #[serde(default)]
     pub use_semantic_similarity: bool,
 
+    /// Drop repeated shingles before hashing
+    #[serde(default)]
+    pub deduplicate_shingles: bool,
+
     /// Verify candidate clone pairs using tree edit distance (APTED)
     #[serde(default)]
     pub verify_with_apted: bool,
@@ -517,6 +539,7 @@ impl Default for LshConfig {
             similarity_threshold: 0.7,
             max_candidates: 100,
             use_semantic_similarity: false, // Keep name for backward compatibility
+            deduplicate_shingles: false,
             verify_with_apted: true,
             apted_max_nodes: LshConfig::default_apted_max_nodes(),
             apted_max_pairs_per_entity: 25,
@@ -693,6 +716,10 @@ pub struct PerformanceConfig {
     /// Batch size for parallel processing
     #[serde(default)]
     pub batch_size: usize,
+
+    /// Maximum number of repositories analyzed at once by batch analysis
+    #[serde(default = "PerformanceConfig::default_max_concurrent")]
+    pub max_concurrent: usize,
 }
 
 /// Default implementation for [`PerformanceConfig`].
@@ -706,12 +733,20 @@ impl Default for PerformanceConfig {
             total_timeout_seconds: None, // No limit
             enable_simd: cfg!(feature = "simd"),
             batch_size: 100,
+            max_concurrent: Self::default_max_concurrent(),
         }
     }
 }
 
-/// Validation for [`PerformanceConfig`].
+/// Defaults and validation for [`PerformanceConfig`].
 impl PerformanceConfig {
+    /// Number of logical CPUs, or 1 when it cannot be determined.
+    fn default_max_concurrent() -> usize {
+        std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1)
+    }
+
     /// Validate performance configuration
     pub fn validate(&self) -> Result<()> {
         if let Some(threads) = self.max_threads {
@@ -736,6 +771,12 @@ impl PerformanceConfig {
             ));
         }
 
+        if self.max_concurrent == 0 {
+            return Err(ValknutError::validation(
+                "max_concurrent must be greater than 0",
+            ));
+        }
+
         Ok(())
     }
 }
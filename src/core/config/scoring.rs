@@ -96,6 +96,15 @@ pub struct WeightsConfig {
     /// Coverage-based feature weights
     #[serde(default)]
     pub coverage: f64,
+
+    /// Maintainability Index weight; low MI raises refactoring priority
+    #[serde(default = "default_maintainability_weight")]
+    pub maintainability: f64,
+}
+
+/// Default weight for [`WeightsConfig::maintainability`].
+fn default_maintainability_weight() -> f64 {
+    0.6
 }
 
 /// Default implementation for [`WeightsConfig`].
@@ -108,6 +117,7 @@ impl Default for WeightsConfig {
             structure: 0.9,
             style: 0.5,
             coverage: 0.7,
+            maintainability: default_maintainability_weight(),
         }
     }
 }
@@ -122,11 +132,19 @@ impl WeightsConfig {
             self.structure,
             self.style,
             self.coverage,
+            self.maintainability,
         ];
 
-        for (name, &weight) in ["complexity", "graph", "structure", "style", "coverage"]
-            .iter()
-            .zip(&weights)
+        for (name, &weight) in [
+            "complexity",
+            "graph",
+            "structure",
+            "style",
+            "coverage",
+            "maintainability",
+        ]
+        .iter()
+        .zip(&weights)
         {
             if weight < 0.0 || weight > 10.0 {
                 return Err(ValknutError::validation(format!(
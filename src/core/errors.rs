@@ -168,6 +168,15 @@ pub enum ValknutError {
         /// Error description
         message: String,
     },
+
+    /// Oracle backend could not be reached
+    #[error("Oracle backend unavailable at {endpoint}: {message}")]
+    OracleUnavailable {
+        /// Error description
+        message: String,
+        /// Endpoint that refused or dropped the connection
+        endpoint: String,
+    },
 }
 
 /// Factory methods and context utilities for [`ValknutError`].
@@ -256,6 +265,14 @@ impl ValknutError {
         }
     }
 
+    /// Create a new cache error
+    pub fn cache(message: impl Into<String>) -> Self {
+        Self::Cache {
+            message: message.into(),
+            key: None,
+        }
+    }
+
     /// Create a new pipeline error
     pub fn pipeline(stage: impl Into<String>, message: impl Into<String>) -> Self {
         Self::Pipeline {
@@ -298,6 +315,14 @@ impl ValknutError {
         }
     }
 
+    /// Create a new oracle unavailable error
+    pub fn oracle_unavailable(endpoint: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::OracleUnavailable {
+            message: message.into(),
+            endpoint: endpoint.into(),
+        }
+    }
+
     /// Add context to an existing error
     pub fn with_context(mut self, context: impl Into<String>) -> Self {
         match &mut self {
@@ -599,6 +624,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cache_error() {
+        let err = ValknutError::cache("Failed to open database");
+
+        if let ValknutError::Cache { message, key } = err {
+            assert_eq!(message, "Failed to open database");
+            assert_eq!(key, None);
+        } else {
+            panic!("Expected Cache error");
+        }
+    }
+
     #[test]
     fn test_lsh_error() {
         let err = ValknutError::lsh("Invalid hash function");
@@ -676,6 +713,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_oracle_unavailable_error() {
+        let err = ValknutError::oracle_unavailable("http://localhost:11434", "connection refused");
+
+        assert_eq!(
+            err.to_string(),
+            "Oracle backend unavailable at http://localhost:11434: connection refused"
+        );
+        assert!(matches!(err, ValknutError::OracleUnavailable { .. }));
+    }
+
     #[test]
     fn test_from_io_error() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "File not found");
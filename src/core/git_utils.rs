@@ -0,0 +1,26 @@
+//! Git repository discovery shared across analysis stages.
+
+use git2::Repository;
+use std::path::{Path, PathBuf};
+
+use crate::core::errors::{Result, ValknutError};
+
+/// Working directory of the git repository containing `path`.
+///
+/// Searches `path` and its ancestors like `git` itself does. Fails when no
+/// repository is found or the repository is bare.
+pub fn auto_detect_git_root(path: &Path) -> Result<PathBuf> {
+    let repo = Repository::discover(path).map_err(|e| {
+        ValknutError::config(format!(
+            "No git repository found for {}: {}",
+            path.display(),
+            e.message()
+        ))
+    })?;
+    repo.workdir().map(Path::to_path_buf).ok_or_else(|| {
+        ValknutError::config(format!(
+            "Git repository for {} has no working directory",
+            path.display()
+        ))
+    })
+}
@@ -106,6 +106,8 @@ pub struct InternedParsedEntity {
     pub location: InternedSourceLocation,
     /// Additional metadata (keys are interned for performance)
     pub metadata: HashMap<InternedString, serde_json::Value>,
+    /// Whether the entity is test code
+    pub is_test: bool,
 }
 
 /// Factory, conversion, and mutation methods for [`InternedParsedEntity`].
@@ -120,6 +122,7 @@ impl InternedParsedEntity {
             children: Vec::new(),
             location,
             metadata: HashMap::new(),
+            is_test: false,
         }
     }
 
@@ -137,6 +140,7 @@ impl InternedParsedEntity {
                 .iter()
                 .map(|(k, v)| (intern(k), v.clone()))
                 .collect(),
+            is_test: entity.is_test,
         }
     }
 
@@ -158,6 +162,7 @@ impl InternedParsedEntity {
                 .iter()
                 .map(|(k, v)| (resolve(*k).to_string(), v.clone()))
                 .collect(),
+            is_test: self.is_test,
         }
     }
 
@@ -506,6 +511,7 @@ mod tests {
             children: vec![],
             location,
             metadata: HashMap::new(),
+            is_test: false,
         };
 
         // Convert to interned and back
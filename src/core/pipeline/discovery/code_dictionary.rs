@@ -1,6 +1,9 @@
 //! Issue and suggestion code dictionary for analysis output.
 
-use crate::core::pipeline::results::result_types::CodeDefinition;
+use crate::core::pipeline::results::result_types::{CodeDefinition, CodeDictionary};
+use crate::detectors::complexity::MULTIPLE_RETURNS_CODE;
+use crate::detectors::refactoring::LONG_PARAMETER_LIST_CODE;
+use crate::detectors::structure::METHOD_CHAIN_CODE;
 
 /// Sanitizes a category name into a short alphanumeric code (max 8 chars).
 fn sanitize_code(source: &str) -> String {
@@ -29,6 +32,21 @@ fn title_case(word: &str) -> String {
     }
 }
 
+/// Issue categories with dedicated definitions in [`issue_definition_for_category`].
+const KNOWN_ISSUE_CATEGORIES: &[&str] = &[
+    "complexity",
+    "cognitive",
+    "structure",
+    "graph",
+    "style",
+    "coverage",
+    "debt",
+    "maintainability",
+    "readability",
+    "refactoring",
+    "method_chain",
+];
+
 /// Returns the issue code definition for a given category name.
 pub fn issue_definition_for_category(category: &str) -> CodeDefinition {
     let lowered = category.to_ascii_lowercase();
@@ -94,6 +112,12 @@ pub fn issue_definition_for_category(category: &str) -> CodeDefinition {
             summary: "General refactoring signals indicate room for improvement.".to_string(),
             category: Some("refactoring".to_string()),
         },
+        "method_chain" => CodeDefinition {
+            code: METHOD_CHAIN_CODE.to_string(),
+            title: "Long Method Chain".to_string(),
+            summary: "Calls reach through a long chain of objects, which is hard to debug and couples the caller to distant structure.".to_string(),
+            category: Some("method_chain".to_string()),
+        },
         known => {
             let code = sanitize_code(known);
             CodeDefinition {
@@ -166,6 +190,18 @@ const SUGGESTION_ENTRIES: &[SuggestionEntry] = &[
         title: "Simplify Conditionals",
         summary: "Flatten or reorganize complex branching to clarify intent.",
     },
+    SuggestionEntry {
+        pattern: PatternMatch::StartsWith("introduce_parameter_object"),
+        code: LONG_PARAMETER_LIST_CODE,
+        title: "Introduce Parameter Object",
+        summary: "Bundle related parameters into an object so calls stay short and readable.",
+    },
+    SuggestionEntry {
+        pattern: PatternMatch::Contains("multiple_return"),
+        code: MULTIPLE_RETURNS_CODE,
+        title: "Restructure Multiple Returns",
+        summary: "Use early guard clauses so complex functions keep a single main exit.",
+    },
     SuggestionEntry {
         pattern: PatternMatch::StartsWith("reduce_cyclomatic_complexity"),
         code: "RDCYCLEX",
@@ -335,6 +371,46 @@ pub fn issue_code_for_category(category: &str) -> String {
     issue_definition_for_category(category).code
 }
 
+/// Returns the definition for the suggestion attached to coverage gaps.
+pub fn add_test_definition() -> CodeDefinition {
+    CodeDefinition {
+        code: "ADDTEST".to_string(),
+        title: "Add Test Coverage".to_string(),
+        summary: "Write tests to cover this untested code path and improve safety.".to_string(),
+        category: Some("coverage".to_string()),
+    }
+}
+
+/// Builds a dictionary containing every known issue and suggestion code.
+///
+/// Coverage codes are only included when `include_coverage` is set, so the
+/// dictionary matches what an analysis with that configuration can emit.
+pub fn known_code_dictionary(include_coverage: bool) -> CodeDictionary {
+    let mut dict = CodeDictionary::default();
+    for category in KNOWN_ISSUE_CATEGORIES {
+        if *category == "coverage" && !include_coverage {
+            continue;
+        }
+        let definition = issue_definition_for_category(category);
+        dict.issues.insert(definition.code.clone(), definition);
+    }
+    for entry in SUGGESTION_ENTRIES {
+        dict.suggestions
+            .entry(entry.code.to_string())
+            .or_insert_with(|| CodeDefinition {
+                code: entry.code.to_string(),
+                title: entry.title.to_string(),
+                summary: entry.summary.to_string(),
+                category: Some("refactoring".to_string()),
+            });
+    }
+    if include_coverage {
+        let definition = add_test_definition();
+        dict.suggestions.insert(definition.code.clone(), definition);
+    }
+    dict
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,6 +464,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn known_code_dictionary_respects_coverage_flag() {
+        let with_coverage = known_code_dictionary(true);
+        assert!(with_coverage.issues.contains_key("CMPLX"));
+        assert!(with_coverage.issues.contains_key("COVGAP"));
+        assert!(with_coverage.suggestions.contains_key("XTRMTH"));
+        assert!(with_coverage.suggestions.contains_key("ADDTEST"));
+        assert_eq!(
+            with_coverage.suggestions.len(),
+            SUGGESTION_ENTRIES.len() + 1
+        );
+
+        let without_coverage = known_code_dictionary(false);
+        assert!(!without_coverage.issues.contains_key("COVGAP"));
+        assert!(!without_coverage.suggestions.contains_key("ADDTEST"));
+        assert!(without_coverage.suggestions.contains_key("DEDUP"));
+        assert!(without_coverage
+            .suggestions
+            .contains_key(MULTIPLE_RETURNS_CODE));
+    }
+
     #[test]
     fn sanitize_and_title_case_helpers() {
         assert_eq!(sanitize_code("refactor"), "REFACTOR");
@@ -407,6 +504,11 @@ mod tests {
         let rename = suggestion_definition_for_kind("rename_variable");
         assert_eq!(rename.code, "RENVAR");
         assert!(rename.summary.contains("Rename identifiers"));
+
+        let parameter_object =
+            suggestion_definition_for_kind("introduce_parameter_object_2_functions");
+        assert_eq!(parameter_object.code, "R_LONG_PARAM");
+        assert_eq!(parameter_object.title, "Introduce Parameter Object");
     }
 
     #[test]
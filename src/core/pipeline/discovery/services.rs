@@ -10,6 +10,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use futures::future;
+use tokio_util::sync::CancellationToken;
 
 use crate::core::arena_analysis::ArenaAnalysisResult;
 use crate::core::config::ValknutConfig;
@@ -263,6 +264,10 @@ impl StageResultsBundle {
                 directory_recommendations: Vec::new(),
                 file_splitting_recommendations: Vec::new(),
                 issues_count: 0,
+                shotgun_surgery: Vec::new(),
+                change_coupled_pairs: Vec::new(),
+                method_chains: Vec::new(),
+                entity_health: Vec::new(),
             },
             coverage: CoverageAnalysisResults {
                 enabled: false,
@@ -292,6 +297,8 @@ impl StageResultsBundle {
                 chokepoints: Vec::new(),
                 clone_groups: Vec::new(),
                 issues_count: 0,
+                hub_files: Vec::new(),
+                import_cycles: Vec::new(),
             },
             lsh: LshAnalysisResults {
                 enabled: false,
@@ -303,6 +310,7 @@ impl StageResultsBundle {
                 verification: None,
                 denoising_enabled: false,
                 tfidf_stats: None,
+                unique_entities: Vec::new(),
             },
             cohesion: CohesionAnalysisResults::default(),
         }
@@ -347,6 +355,23 @@ pub trait StageOrchestrator: Send + Sync {
         files: &[PathBuf],
         arena_results: &[ArenaAnalysisResult],
     ) -> Result<StageResultsBundle>;
+
+    /// Runs all enabled analysis stages, skipping any not yet started once
+    /// `token` is cancelled.
+    ///
+    /// The default implementation ignores the token and runs every stage.
+    async fn run_all_stages_with_cancellation(
+        &self,
+        config: &AnalysisConfig,
+        paths: &[PathBuf],
+        files: &[PathBuf],
+        arena_results: &[ArenaAnalysisResult],
+        token: &CancellationToken,
+    ) -> Result<StageResultsBundle> {
+        let _ = token;
+        self.run_all_stages(config, paths, files, arena_results)
+            .await
+    }
 }
 
 /// Aggregates stage results into summary metrics and evaluates quality gates.
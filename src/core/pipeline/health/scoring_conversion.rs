@@ -7,7 +7,7 @@ use std::collections::{HashMap, HashSet};
 
 use crate::core::featureset::FeatureVector;
 use crate::core::scoring::{Priority, ScoringResult};
-use crate::detectors::complexity::ComplexitySeverity;
+use crate::detectors::complexity::{ComplexitySeverity, MAINTAINABILITY_INDEX_SCALE};
 
 use crate::core::pipeline::results::pipeline_results::{
     ComprehensiveAnalysisResult, HealthMetrics,
@@ -405,6 +405,15 @@ fn confidence_from_loc(loc: f64) -> f64 {
 use crate::detectors::complexity::ComplexityAnalysisResult;
 use crate::detectors::refactoring::RefactoringAnalysisResult;
 
+/// Priority penalty for a normalized Maintainability Index.
+///
+/// The penalty was tuned on the raw MI scale, where only values below 100 were
+/// penalized, so the normalized MI is mapped back before subtracting; entities
+/// above `100 * MAINTAINABILITY_INDEX_SCALE` (about 58.5) carry no penalty.
+fn maintainability_penalty(maintainability_index: f64) -> f64 {
+    clamp_score(100.0 - maintainability_index / MAINTAINABILITY_INDEX_SCALE)
+}
+
 /// Convert a single complexity result to a ScoringResult.
 fn complexity_to_scoring(result: &ComplexityAnalysisResult) -> ScoringResult {
     let entity_id = format!(
@@ -417,7 +426,7 @@ fn complexity_to_scoring(result: &ComplexityAnalysisResult) -> ScoringResult {
     let cognitive_score = clamp_score((metrics.cognitive() / 15.0) * 30.0);
     let nesting_score = clamp_score(metrics.max_nesting_depth * 6.0);
     let debt_score = clamp_score(metrics.technical_debt_score);
-    let maintainability_penalty = clamp_score(100.0 - metrics.maintainability_index);
+    let maintainability_penalty = maintainability_penalty(metrics.maintainability_index);
 
     let category_scores = HashMap::from([
         ("complexity".to_string(), cyclomatic_score),
@@ -760,6 +769,14 @@ mod tests {
         assert_eq!(metrics.technical_debt_ratio, 0.0);
     }
 
+    #[test]
+    fn test_maintainability_penalty_uses_the_raw_mi_scale() {
+        assert_eq!(maintainability_penalty(100.0), 0.0);
+        assert!(maintainability_penalty(100.0 * MAINTAINABILITY_INDEX_SCALE) < 1e-9);
+        assert!((maintainability_penalty(50.0 * MAINTAINABILITY_INDEX_SCALE) - 50.0).abs() < 1e-9);
+        assert_eq!(maintainability_penalty(0.0), 100.0);
+    }
+
     #[test]
     fn test_logistic_over() {
         // Value below mid should be < 0.5
@@ -101,6 +101,8 @@ pub fn suggestion_for_feature(
             ("extract_class_{}_areas", 0.7, 0.0, 0.1)
         } else if name.contains("simplify_conditionals_count") {
             ("simplify_{}_conditionals", 0.45, 0.0, 0.0)
+        } else if name.contains("long_parameter_list_count") {
+            ("introduce_parameter_object_{}_functions", 0.35, 0.0, 0.0)
         } else if name.contains("cyclomatic") {
             ("reduce_cyclomatic_complexity_{}", 0.5, 0.1, 0.0)
         } else if name.contains("cognitive") {
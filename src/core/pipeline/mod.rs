@@ -138,6 +138,10 @@ mod inline_tests {
                 directory_recommendations: vec![],
                 file_splitting_recommendations: vec![],
                 issues_count: 0,
+                shotgun_surgery: Vec::new(),
+                change_coupled_pairs: Vec::new(),
+                method_chains: Vec::new(),
+                entity_health: Vec::new(),
             },
             complexity: ComplexityAnalysisResults {
                 enabled: true,
@@ -159,6 +163,8 @@ mod inline_tests {
                 chokepoints: vec![],
                 clone_groups: vec![],
                 issues_count: 0,
+                hub_files: Vec::new(),
+                import_cycles: Vec::new(),
             },
             lsh: LshAnalysisResults {
                 enabled: false,
@@ -170,6 +176,7 @@ mod inline_tests {
                 verification: None,
                 denoising_enabled: false,
                 tfidf_stats: None,
+                unique_entities: Vec::new(),
             },
             coverage: CoverageAnalysisResults {
                 enabled: false,
@@ -189,6 +196,7 @@ mod inline_tests {
                 structure_quality_score: 90.0,
                 doc_health_score: 100.0,
             },
+            cancelled: false,
         };
 
         let gate_result = pipeline.evaluate_quality_gates(&config, &results);
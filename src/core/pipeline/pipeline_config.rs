@@ -100,14 +100,10 @@ impl From<ValknutConfig> for AnalysisConfig {
             .collect();
 
         let final_file_extensions = if file_extensions.is_empty() {
-            let mut fallback: Vec<String> = registry::registered_languages()
-                .iter()
-                .flat_map(|info| info.extensions.iter())
-                .map(|ext| ext.trim_start_matches('.').to_string())
-                .collect();
-            fallback.sort();
-            fallback.dedup();
-            fallback
+            registry::supported_extensions()
+                .into_iter()
+                .map(str::to_string)
+                .collect()
         } else {
             file_extensions
         };
@@ -4,10 +4,12 @@ use chrono::Utc;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 use tokio::fs;
+use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 use uuid::Uuid;
 use walkdir;
 
+use crate::core::arena_analysis::ArenaAnalysisResult;
 use crate::core::ast_service::AstService;
 use crate::core::config::{DocHealthConfig, ScoringConfig, ValknutConfig};
 use crate::core::errors::{Result, ValknutError};
@@ -92,7 +94,13 @@ impl AnalysisPipeline {
     }
 
     /// Create new analysis pipeline with full ValknutConfig support
-    pub fn new_with_config(analysis_config: AnalysisConfig, valknut_config: ValknutConfig) -> Self {
+    ///
+    /// Returns a validation error when the dedupe thresholds derived from
+    /// `valknut_config` are invalid and LSH analysis is enabled.
+    pub fn new_with_config(
+        analysis_config: AnalysisConfig,
+        valknut_config: ValknutConfig,
+    ) -> Result<Self> {
         // Debug output removed - LSH integration is working
 
         let ast_service = Arc::new(AstService::new());
@@ -110,7 +118,8 @@ impl AnalysisPipeline {
         );
 
         // Create common analyzers once
-        let structure_extractor = StructureExtractor::with_config(structure_config);
+        let structure_extractor = StructureExtractor::with_config(structure_config)
+            .with_import_cache_dir(valknut_config.analysis.cache_dir.clone());
         let complexity_analyzer =
             ComplexityAnalyzer::new(ComplexityConfig::default(), ast_service.clone());
         let refactoring_analyzer =
@@ -130,10 +139,19 @@ impl AnalysisPipeline {
             dedupe_config.require_distinct_blocks = valknut_config.denoise.require_blocks;
             dedupe_config.shingle_k = valknut_config.lsh.shingle_size;
             dedupe_config.threshold_s = valknut_config.denoise.similarity;
+            let adaptive = &valknut_config.dedupe.adaptive;
+            dedupe_config.adaptive.min_denoise_threshold = adaptive.min_denoise_threshold;
+            dedupe_config.adaptive.max_denoise_threshold = adaptive.max_denoise_threshold;
+            dedupe_config.adaptive.step_size = adaptive.step_size;
+            dedupe_config.strip_imports = valknut_config.dedupe.strip_imports;
 
-            let lsh_extractor = LshExtractor::with_dedupe_config(dedupe_config)
+            let lsh_extractor = LshExtractor::with_dedupe_config(dedupe_config)?
                 .with_lsh_config(valknut_config.lsh.clone().into())
+                .with_ranking_config(valknut_config.denoise.ranking.clone().into())
                 .with_denoise_enabled(valknut_config.denoise.enabled);
+            #[cfg(feature = "persistent-cache")]
+            let lsh_extractor =
+                lsh_extractor.with_persistent_cache_dir(valknut_config.analysis.cache_dir.clone());
 
             info!(
                 "LSH extractor configured (denoise: {}, k={}, min_ast_nodes={}, min_tokens={}, similarity={:.2})",
@@ -167,7 +185,7 @@ impl AnalysisPipeline {
         let scoring_config = valknut_config.scoring.clone();
         let feature_scorer = FeatureScorer::new(scoring_config);
 
-        Self {
+        Ok(Self {
             config: analysis_config,
             valknut_config: Some(valknut_config.clone()),
             feature_scorer,
@@ -175,7 +193,7 @@ impl AnalysisPipeline {
             file_reader: BatchedFileReader::shared_with_bundled_detection(valknut_config.bundled),
             stage_runner,
             result_aggregator: Arc::new(DefaultResultAggregator::default()),
-        }
+        })
     }
 
     /// Create with default configuration
@@ -205,6 +223,31 @@ impl AnalysisPipeline {
         &self,
         paths: &[PathBuf],
         progress_callback: Option<ProgressCallback>,
+    ) -> Result<ComprehensiveAnalysisResult> {
+        self.run_analysis(paths, progress_callback, None).await
+    }
+
+    /// Run comprehensive analysis that stops early once `token` is cancelled.
+    ///
+    /// The token is checked between files during entity extraction and between
+    /// detector stages. A cancelled run still returns `Ok`, with `cancelled` set
+    /// and results covering only the files processed before cancellation.
+    pub async fn analyze_paths_with_cancellation(
+        &self,
+        paths: &[PathBuf],
+        progress_callback: Option<ProgressCallback>,
+        token: &CancellationToken,
+    ) -> Result<ComprehensiveAnalysisResult> {
+        self.run_analysis(paths, progress_callback, Some(token))
+            .await
+    }
+
+    /// Shared implementation of the cancellable and uncancellable analysis paths.
+    async fn run_analysis(
+        &self,
+        paths: &[PathBuf],
+        progress_callback: Option<ProgressCallback>,
+        token: Option<&CancellationToken>,
     ) -> Result<ComprehensiveAnalysisResult> {
         let start_time = Instant::now();
         let analysis_id = Uuid::new_v4().to_string();
@@ -222,7 +265,7 @@ impl AnalysisPipeline {
 
         // Stage 1: File discovery and reading
         report("Discovering files...", 0.0);
-        let files = self.discover_files(paths).await?;
+        let mut files = self.discover_files(paths).await?;
         info!("Discovered {} files for analysis", files.len());
 
         report("Reading file contents in batches...", 5.0);
@@ -231,10 +274,22 @@ impl AnalysisPipeline {
 
         // Stage 2: Arena-based entity extraction
         report("Running arena-based entity extraction...", 7.5);
-        let arena_results = self
-            .stage_runner
-            .run_arena_analysis_with_content(&file_contents)
-            .await?;
+        let arena_results = match token {
+            Some(token) => {
+                let (processed, arena_results) = self
+                    .run_arena_analysis_until_cancelled(&file_contents, token, &report)
+                    .await?;
+                if token.is_cancelled() {
+                    files = processed;
+                }
+                arena_results
+            }
+            None => {
+                self.stage_runner
+                    .run_arena_analysis_with_content(&file_contents)
+                    .await?
+            }
+        };
         info!(
             "Arena analysis completed: {} files processed with {:.2} KB total arena usage",
             arena_results.len(),
@@ -243,18 +298,40 @@ impl AnalysisPipeline {
 
         // Stage 3: Run all analysis stages
         report("Running parallel analysis stages...", 10.0);
-        let stages = self
-            .stage_runner
-            .run_all_stages(&self.config, paths, &files, &arena_results)
-            .await?;
+        let stages = match token {
+            Some(token) => {
+                self.stage_runner
+                    .run_all_stages_with_cancellation(
+                        &self.config,
+                        paths,
+                        &files,
+                        &arena_results,
+                        token,
+                    )
+                    .await?
+            }
+            None => {
+                self.stage_runner
+                    .run_all_stages(&self.config, paths, &files, &arena_results)
+                    .await?
+            }
+        };
+        let cancelled = token.is_some_and(CancellationToken::is_cancelled);
 
         // Stage 4: Calculate health metrics
         report("Calculating health metrics...", 90.0);
         let (mut summary, mut health_metrics) = self.build_metrics(&files, &stages);
-        let documentation_results =
-            self.compute_documentation_health(paths, &files, &mut summary, &mut health_metrics);
+        let documentation_results = if cancelled {
+            DocumentationAnalysisResults::default()
+        } else {
+            self.compute_documentation_health(paths, &files, &mut summary, &mut health_metrics)
+        };
 
-        report("Analysis complete", 100.0);
+        if cancelled {
+            report("Analysis cancelled", 100.0);
+        } else {
+            report("Analysis complete", 100.0);
+        }
         let processing_time = start_time.elapsed().as_secs_f64();
         self.log_completion(&summary, &health_metrics, processing_time);
 
@@ -273,9 +350,46 @@ impl AnalysisPipeline {
             documentation: documentation_results,
             cohesion: stages.cohesion,
             health_metrics,
+            cancelled,
         })
     }
 
+    /// Run arena extraction one file at a time, stopping once `token` is cancelled.
+    ///
+    /// Returns the paths of the files that were processed alongside their results.
+    async fn run_arena_analysis_until_cancelled(
+        &self,
+        file_contents: &[(PathBuf, String)],
+        token: &CancellationToken,
+        report: &impl Fn(&str, f64),
+    ) -> Result<(Vec<PathBuf>, Vec<ArenaAnalysisResult>)> {
+        let mut processed = Vec::with_capacity(file_contents.len());
+        let mut arena_results = Vec::with_capacity(file_contents.len());
+
+        for file in file_contents {
+            if token.is_cancelled() {
+                info!(
+                    "Analysis cancelled after {} of {} files",
+                    processed.len(),
+                    file_contents.len()
+                );
+                break;
+            }
+            arena_results.extend(
+                self.stage_runner
+                    .run_arena_analysis_with_content(std::slice::from_ref(file))
+                    .await?,
+            );
+            processed.push(file.0.clone());
+            report(
+                &format!("Extracted entities from {}", file.0.display()),
+                7.5,
+            );
+        }
+
+        Ok((processed, arena_results))
+    }
+
     /// Build summary and health metrics from stage results.
     fn build_metrics(
         &self,
@@ -530,6 +644,10 @@ impl AnalysisPipeline {
                 directory_recommendations: Vec::new(),
                 file_splitting_recommendations: Vec::new(),
                 issues_count: 0,
+                shotgun_surgery: Vec::new(),
+                change_coupled_pairs: Vec::new(),
+                method_chains: Vec::new(),
+                entity_health: Vec::new(),
             },
             complexity: super::results::pipeline_results::ComplexityAnalysisResults {
                 enabled: false,
@@ -551,6 +669,8 @@ impl AnalysisPipeline {
                 chokepoints: Vec::new(),
                 clone_groups: Vec::new(),
                 issues_count: 0,
+                hub_files: Vec::new(),
+                import_cycles: Vec::new(),
             },
             lsh: super::results::pipeline_results::LshAnalysisResults {
                 enabled: false,
@@ -562,6 +682,7 @@ impl AnalysisPipeline {
                 verification: None,
                 denoising_enabled: false,
                 tfidf_stats: None,
+                unique_entities: Vec::new(),
             },
             coverage: CoverageAnalysisResults {
                 enabled: false,
@@ -574,6 +695,7 @@ impl AnalysisPipeline {
             documentation: DocumentationAnalysisResults::default(),
             cohesion: CohesionAnalysisResults::default(),
             health_metrics,
+            cancelled: false,
         };
 
         Ok(PipelineResults {
@@ -27,6 +27,7 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tempfile::tempdir;
+use tokio_util::sync::CancellationToken;
 
 fn sample_complexity_result(
     file_path: &str,
@@ -49,6 +50,7 @@ fn sample_complexity_result(
             parameter_count: 2.0,
             lines_of_code: 24.0,
             statement_count: 12.0,
+            return_count: 1.0,
             halstead: HalsteadMetrics::default(),
             technical_debt_score: technical_debt,
             maintainability_index: maintainability,
@@ -120,6 +122,10 @@ fn build_sample_results() -> ComprehensiveAnalysisResult {
             directory_recommendations: vec![json!({"path": "src", "reason": "Deep tree"})],
             file_splitting_recommendations: vec![],
             issues_count: 1,
+            shotgun_surgery: Vec::new(),
+            change_coupled_pairs: Vec::new(),
+            method_chains: Vec::new(),
+            entity_health: Vec::new(),
         },
         complexity: crate::core::pipeline::pipeline_results::ComplexityAnalysisResults {
             enabled: true,
@@ -141,6 +147,8 @@ fn build_sample_results() -> ComprehensiveAnalysisResult {
             chokepoints: vec![],
             clone_groups: vec![],
             issues_count: 1,
+            hub_files: Vec::new(),
+            import_cycles: Vec::new(),
         },
         lsh: LshAnalysisResults {
             enabled: false,
@@ -152,6 +160,7 @@ fn build_sample_results() -> ComprehensiveAnalysisResult {
             verification: None,
             denoising_enabled: false,
             tfidf_stats: None,
+            unique_entities: Vec::new(),
         },
         coverage: CoverageAnalysisResults {
             enabled: true,
@@ -176,6 +185,7 @@ fn build_sample_results() -> ComprehensiveAnalysisResult {
             structure_quality_score: 45.0,
             doc_health_score: 100.0,
         },
+        cancelled: false,
     }
 }
 
@@ -372,23 +382,38 @@ fn new_with_config_enables_lsh_variants() {
 
     let mut valknut_config = ValknutConfig::default();
     valknut_config.denoise.enabled = true;
-    valknut_config.denoise.min_function_tokens = 4;
+    valknut_config.denoise.min_function_tokens = 5;
     valknut_config.denoise.min_match_tokens = 6;
     valknut_config.lsh.similarity_threshold = 0.4;
 
     let pipeline_with_denoise =
-        AnalysisPipeline::new_with_config(analysis_config.clone(), valknut_config.clone());
+        AnalysisPipeline::new_with_config(analysis_config.clone(), valknut_config.clone())
+            .expect("valid config");
     assert!(pipeline_with_denoise.valknut_config.is_some());
 
     let mut no_denoise_config = valknut_config;
     no_denoise_config.denoise.enabled = false;
     let _pipeline_without_denoise =
-        AnalysisPipeline::new_with_config(analysis_config.clone(), no_denoise_config);
+        AnalysisPipeline::new_with_config(analysis_config.clone(), no_denoise_config)
+            .expect("valid config");
 
     let mut disabled_analysis = analysis_config;
     disabled_analysis.enable_lsh_analysis = false;
     let _pipeline_disabled =
-        AnalysisPipeline::new_with_config(disabled_analysis, ValknutConfig::default());
+        AnalysisPipeline::new_with_config(disabled_analysis, ValknutConfig::default())
+            .expect("valid config");
+}
+
+#[test]
+fn new_with_config_rejects_invalid_dedupe_thresholds() {
+    let mut analysis_config = AnalysisConfig::default();
+    analysis_config.enable_lsh_analysis = true;
+
+    let mut valknut_config = ValknutConfig::default();
+    valknut_config.dedupe.min_ast_nodes = 1;
+
+    let result = AnalysisPipeline::new_with_config(analysis_config, valknut_config);
+    assert!(result.is_err());
 }
 
 #[tokio::test]
@@ -443,6 +468,10 @@ fn calculate_health_metrics_handles_disabled_modules() {
         directory_recommendations: Vec::new(),
         file_splitting_recommendations: Vec::new(),
         issues_count: 0,
+        shotgun_surgery: Vec::new(),
+        change_coupled_pairs: Vec::new(),
+        method_chains: Vec::new(),
+        entity_health: Vec::new(),
     };
     let impact = ImpactAnalysisResults {
         enabled: false,
@@ -450,6 +479,8 @@ fn calculate_health_metrics_handles_disabled_modules() {
         chokepoints: Vec::new(),
         clone_groups: Vec::new(),
         issues_count: 0,
+        hub_files: Vec::new(),
+        import_cycles: Vec::new(),
     };
 
     let metrics = aggregator.build_health_metrics(&complexity, &structure, &impact);
@@ -474,6 +505,10 @@ fn calculate_summary_extracts_languages_and_counts_issues() {
         directory_recommendations: Vec::new(),
         file_splitting_recommendations: Vec::new(),
         issues_count: 2,
+        shotgun_surgery: Vec::new(),
+        change_coupled_pairs: Vec::new(),
+        method_chains: Vec::new(),
+        entity_health: Vec::new(),
     };
 
     let complexity_entry =
@@ -513,6 +548,8 @@ fn calculate_summary_extracts_languages_and_counts_issues() {
         chokepoints: Vec::new(),
         clone_groups: Vec::new(),
         issues_count: 0,
+        hub_files: Vec::new(),
+        import_cycles: Vec::new(),
     };
 
     let summary = aggregator.build_summary(&files, &structure, &complexity, &refactoring, &impact);
@@ -523,3 +560,64 @@ fn calculate_summary_extracts_languages_and_counts_issues() {
     assert_eq!(summary.high_priority_issues, 1);
     assert!(summary.total_lines_of_code > 0);
 }
+
+#[tokio::test]
+async fn analyze_paths_with_cancellation_stops_after_first_file() {
+    let temp = tempdir().expect("temp dir");
+    let root = temp.path();
+    for idx in 0..3 {
+        let file_path = root.join(format!("module_{idx}.py"));
+        tokio::fs::write(
+            &file_path,
+            format!("def func_{idx}(x):\n    return x + {idx}\n"),
+        )
+        .await
+        .unwrap();
+    }
+
+    let pipeline = AnalysisPipeline::default();
+    let token = CancellationToken::new();
+    let callback_token = token.clone();
+    let progress: ProgressCallback = Box::new(move |msg, _| {
+        if msg.starts_with("Extracted entities from") {
+            callback_token.cancel();
+        }
+    });
+
+    let results = pipeline
+        .analyze_paths_with_cancellation(&[root.to_path_buf()], Some(progress), &token)
+        .await
+        .expect("cancelled analysis still returns results");
+
+    assert!(results.cancelled);
+    assert_eq!(results.summary.total_files, 1);
+    assert_eq!(results.complexity.detailed_results.len(), 1);
+    assert!(!results.structure.enabled);
+    assert!(!results.lsh.enabled);
+}
+
+#[tokio::test]
+async fn analyze_paths_with_cancellation_runs_to_completion_when_not_cancelled() {
+    let temp = tempdir().expect("temp dir");
+    let root = temp.path();
+    for idx in 0..2 {
+        let file_path = root.join(format!("module_{idx}.py"));
+        tokio::fs::write(
+            &file_path,
+            format!("def func_{idx}(x):\n    return x + {idx}\n"),
+        )
+        .await
+        .unwrap();
+    }
+
+    let pipeline = AnalysisPipeline::default();
+    let token = CancellationToken::new();
+    let results = pipeline
+        .analyze_paths_with_cancellation(&[root.to_path_buf()], None, &token)
+        .await
+        .unwrap();
+
+    assert!(!results.cancelled);
+    assert_eq!(results.summary.total_files, 2);
+    assert_eq!(results.complexity.detailed_results.len(), 2);
+}
@@ -5,6 +5,7 @@ use futures::future;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 use super::discovery::services::{StageOrchestrator, StageResultsBundle};
@@ -430,6 +431,48 @@ impl StageOrchestrator for AnalysisStages {
             cohesion: cohesion_result,
         })
     }
+
+    /// Runs the stages one at a time, checking `token` before each.
+    ///
+    /// Complexity always runs because it only summarises entities the arena
+    /// pass already extracted; the remaining stages fall back to their
+    /// disabled results once the token is cancelled.
+    async fn run_all_stages_with_cancellation(
+        &self,
+        config: &AnalysisConfig,
+        paths: &[PathBuf],
+        files: &[PathBuf],
+        arena_results: &[ArenaAnalysisResult],
+        token: &CancellationToken,
+    ) -> Result<StageResultsBundle> {
+        let mut bundle = StageResultsBundle::disabled();
+        bundle.complexity = self.run_complexity_stage(config, arena_results).await?;
+
+        if !token.is_cancelled() {
+            bundle.structure = self
+                .run_structure_stage(config, paths, arena_results)
+                .await?;
+        }
+        if !token.is_cancelled() {
+            bundle.coverage = self.run_coverage_stage(config, paths).await?;
+        }
+        if !token.is_cancelled() {
+            bundle.refactoring = self.run_refactoring_stage(config, files).await?;
+        }
+        if !token.is_cancelled() {
+            bundle.impact = self.run_impact_stage(config, files).await?;
+        }
+        if !token.is_cancelled() {
+            bundle.lsh = self.run_lsh_stage(config, files).await?;
+        }
+        if !token.is_cancelled() {
+            bundle.cohesion = self.run_cohesion_stage(paths, arena_results).await?;
+        } else {
+            info!("Analysis cancelled; remaining stages skipped");
+        }
+
+        Ok(bundle)
+    }
 }
 
 /// Stage execution helpers for [`AnalysisStages`].
@@ -11,7 +11,10 @@ use crate::core::pipeline::pipeline_config::AnalysisConfig;
 use crate::core::scoring::ScoringResult;
 use crate::detectors::cohesion::CohesionAnalysisResults;
 use crate::detectors::complexity::ComplexityAnalysisResult;
+use crate::detectors::graph::{HubFile, Scc};
 use crate::detectors::refactoring::RefactoringAnalysisResult;
+use crate::detectors::structure::file::{ChangeCoupledPair, ShotgunCandidate};
+use crate::detectors::structure::{FileHealthReport, FileMethodChains};
 
 /// Comprehensive analysis result containing all analysis types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +49,9 @@ pub struct ComprehensiveAnalysisResult {
     pub cohesion: CohesionAnalysisResults,
     /// Overall health metrics
     pub health_metrics: HealthMetrics,
+    /// Whether the run was cancelled before every file and stage completed
+    #[serde(default)]
+    pub cancelled: bool,
 }
 
 /// Structure analysis results
@@ -59,6 +65,18 @@ pub struct StructureAnalysisResults {
     pub file_splitting_recommendations: Vec<serde_json::Value>,
     /// Structure issues count
     pub issues_count: usize,
+    /// Files flagged with the Shotgun Surgery smell, most co-changed first
+    #[serde(default)]
+    pub shotgun_surgery: Vec<ShotgunCandidate>,
+    /// File pairs that most often change in the same commits
+    #[serde(default)]
+    pub change_coupled_pairs: Vec<ChangeCoupledPair>,
+    /// Files containing method chains longer than the configured maximum
+    #[serde(default)]
+    pub method_chains: Vec<FileMethodChains>,
+    /// Per-entity health for each file with at least one function or class
+    #[serde(default)]
+    pub entity_health: Vec<FileHealthReport>,
 }
 
 /// Factory methods for [`StructureAnalysisResults`].
@@ -70,6 +88,10 @@ impl StructureAnalysisResults {
             directory_recommendations: Vec::new(),
             file_splitting_recommendations: Vec::new(),
             issues_count: 0,
+            shotgun_surgery: Vec::new(),
+            change_coupled_pairs: Vec::new(),
+            method_chains: Vec::new(),
+            entity_health: Vec::new(),
         }
     }
 }
@@ -145,6 +167,12 @@ pub struct ImpactAnalysisResults {
     pub clone_groups: Vec<serde_json::Value>,
     /// Impact issues count
     pub issues_count: usize,
+    /// Files imported by more dependents than the hub threshold
+    #[serde(default)]
+    pub hub_files: Vec<HubFile>,
+    /// Import cycles: file-level strongly connected components spanning several files
+    #[serde(default)]
+    pub import_cycles: Vec<Scc>,
 }
 
 /// Factory methods for [`ImpactAnalysisResults`].
@@ -157,6 +185,8 @@ impl ImpactAnalysisResults {
             chokepoints: Vec::new(),
             clone_groups: Vec::new(),
             issues_count: 0,
+            hub_files: Vec::new(),
+            import_cycles: Vec::new(),
         }
     }
 }
@@ -183,6 +213,9 @@ pub struct LshAnalysisResults {
     pub denoising_enabled: bool,
     /// TF-IDF statistics (if denoising enabled)
     pub tfidf_stats: Option<TfIdfStats>,
+    /// Entities least similar to all others, most novel first
+    #[serde(default)]
+    pub unique_entities: Vec<String>,
 }
 
 /// Summary of structural verification applied to clone pairs
@@ -226,6 +259,7 @@ impl LshAnalysisResults {
             verification: None,
             denoising_enabled: false,
             tfidf_stats: None,
+            unique_entities: Vec::new(),
         }
     }
 
@@ -241,6 +275,7 @@ impl LshAnalysisResults {
             verification: None,
             denoising_enabled: denoise_enabled,
             tfidf_stats: None,
+            unique_entities: Vec::new(),
         }
     }
 }
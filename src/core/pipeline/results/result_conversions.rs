@@ -8,14 +8,37 @@ use super::pipeline_results::DocumentationAnalysisResults;
 use crate::core::featureset::FeatureVector;
 use crate::core::pipeline::{PipelineResults, ResultSummary, StageResultsBundle};
 use crate::core::scoring::{Priority, ScoringResult};
+use crate::detectors::coverage::{
+    coverage_entity_id, prioritize_coverage_gaps, CoveragePack, PrioritizedCoverageGap,
+};
+use crate::detectors::graph::{HubFile, Scc};
+use crate::detectors::structure::file::{
+    ChangeCoupledPair, ShotgunCandidate, MAX_CHANGE_COUPLED_PAIRS,
+};
+use crate::detectors::structure::{FileMethodChains, MethodChainDiagnosis};
 
 use super::result_types::*;
 use crate::core::pipeline::discovery::code_dictionary::{
-    issue_code_for_category, issue_definition_for_category, suggestion_code_for_kind,
-    suggestion_definition_for_kind,
+    add_test_definition, issue_code_for_category, issue_definition_for_category,
+    suggestion_code_for_kind, suggestion_definition_for_kind,
 };
 use crate::core::pipeline::health::suggestion_generator::generate_suggestions;
 
+/// Number of coverage hot spots surfaced in [`AnalysisResults`].
+pub const MAX_COVERAGE_HOT_SPOTS: usize = 10;
+
+/// Order change-coupled pairs most coupled first and keep the top
+/// [`MAX_CHANGE_COUPLED_PAIRS`].
+pub(crate) fn sort_change_coupled_pairs(pairs: &mut Vec<ChangeCoupledPair>) {
+    pairs.sort_by(|a, b| {
+        b.coupling
+            .total_cmp(&a.coupling)
+            .then_with(|| a.first.cmp(&b.first))
+            .then_with(|| a.second.cmp(&b.second))
+    });
+    pairs.truncate(MAX_CHANGE_COUPLED_PAIRS);
+}
+
 /// Hierarchy building and conversion methods for [`AnalysisResults`].
 impl AnalysisResults {
     /// Build a minimal unified hierarchy; falls back to candidate-based grouping when directory data is empty.
@@ -94,6 +117,10 @@ impl AnalysisResults {
             },
             clone_analysis: None,
             coverage_packs: Vec::new(),
+            coverage_hot_spots: Vec::new(),
+            shotgun_surgery: Vec::new(),
+            change_coupled_pairs: Vec::new(),
+            unique_entities: Vec::new(),
             warnings: Vec::new(),
             health_metrics: None,
             code_dictionary: CodeDictionary::default(),
@@ -102,6 +129,10 @@ impl AnalysisResults {
             file_health: HashMap::new(),
             entity_health: HashMap::new(),
             directory_health_tree: None,
+            cancelled: false,
+            repo_root: None,
+            hub_files: Vec::new(),
+            import_cycles: Vec::new(),
         }
     }
 
@@ -189,6 +220,11 @@ impl AnalysisResults {
         let summary_stats = pipeline_results.summary();
         let mut refactoring_candidates =
             Self::build_refactoring_candidates(&pipeline_results, &project_root);
+        Self::attach_method_chains(
+            &mut refactoring_candidates,
+            &pipeline_results.results.structure.method_chains,
+            &project_root,
+        );
         let (priority_distribution, critical_count, high_priority_count) =
             Self::count_priorities(&pipeline_results.scoring_results.files);
         let summary = Self::build_summary(
@@ -215,10 +251,19 @@ impl AnalysisResults {
             &coverage_packs,
         );
 
+        let coverage_hot_spots =
+            Self::build_coverage_hot_spots(&pipeline_results, &coverage_packs, &project_root);
         let code_dictionary = Self::build_code_dictionary(&refactoring_candidates, &coverage_packs);
         let passes = Self::build_passes(&pipeline_results);
         let documentation = Self::build_documentation(&pipeline_results);
         let health_metrics = Some(pipeline_results.results.health_metrics.clone());
+        let cancelled = pipeline_results.results.cancelled;
+        let hub_files = Self::build_hub_files(&pipeline_results, &project_root);
+        let import_cycles = Self::build_import_cycles(&pipeline_results, &project_root);
+        let shotgun_surgery = Self::build_shotgun_surgery(&pipeline_results, &project_root);
+        let change_coupled_pairs =
+            Self::build_change_coupled_pairs(&pipeline_results, &project_root);
+        let unique_entities = pipeline_results.results.lsh.unique_entities.clone();
 
         // Compute per-directory, per-file, and per-entity health using the same formula as overall health
         // This ensures consistency across all granularity levels
@@ -254,6 +299,7 @@ impl AnalysisResults {
             clone_analysis,
             warnings,
             coverage_packs,
+            coverage_hot_spots,
             health_metrics,
             code_dictionary,
             documentation,
@@ -261,6 +307,13 @@ impl AnalysisResults {
             file_health,
             entity_health,
             directory_health_tree,
+            cancelled,
+            repo_root: None,
+            hub_files,
+            import_cycles,
+            shotgun_surgery,
+            change_coupled_pairs,
+            unique_entities,
         }
     }
 
@@ -275,6 +328,134 @@ impl AnalysisResults {
         (health_score - score_penalty).clamp(0.0, 1.0)
     }
 
+    /// Copy hub files from the impact pass with paths relative to `project_root`.
+    fn build_hub_files(pipeline_results: &PipelineResults, project_root: &Path) -> Vec<HubFile> {
+        pipeline_results
+            .results
+            .impact
+            .hub_files
+            .iter()
+            .map(|hub| HubFile {
+                path: PathBuf::from(convert_to_relative_path(
+                    &hub.path.to_string_lossy(),
+                    project_root,
+                )),
+                ..hub.clone()
+            })
+            .collect()
+    }
+
+    /// Copy import cycles from the impact pass with paths relative to `project_root`.
+    fn build_import_cycles(pipeline_results: &PipelineResults, project_root: &Path) -> Vec<Scc> {
+        pipeline_results
+            .results
+            .impact
+            .import_cycles
+            .iter()
+            .map(|scc| Scc {
+                members: scc
+                    .members
+                    .iter()
+                    .map(|member| {
+                        PathBuf::from(convert_to_relative_path(
+                            &member.to_string_lossy(),
+                            project_root,
+                        ))
+                    })
+                    .collect(),
+                ..scc.clone()
+            })
+            .collect()
+    }
+
+    /// Copy Shotgun Surgery candidates from the structure pass with paths
+    /// relative to `project_root`.
+    fn build_shotgun_surgery(
+        pipeline_results: &PipelineResults,
+        project_root: &Path,
+    ) -> Vec<ShotgunCandidate> {
+        pipeline_results
+            .results
+            .structure
+            .shotgun_surgery
+            .iter()
+            .map(|candidate| ShotgunCandidate {
+                file: PathBuf::from(convert_to_relative_path(
+                    &candidate.file.to_string_lossy(),
+                    project_root,
+                )),
+                ..candidate.clone()
+            })
+            .collect()
+    }
+
+    /// Rank change-coupled pairs from the structure pass, keeping the top
+    /// [`MAX_CHANGE_COUPLED_PAIRS`] with paths relative to `project_root`.
+    fn build_change_coupled_pairs(
+        pipeline_results: &PipelineResults,
+        project_root: &Path,
+    ) -> Vec<ChangeCoupledPair> {
+        let relative = |path: &Path| {
+            PathBuf::from(convert_to_relative_path(
+                &path.to_string_lossy(),
+                project_root,
+            ))
+        };
+        let mut pairs: Vec<ChangeCoupledPair> = pipeline_results
+            .results
+            .structure
+            .change_coupled_pairs
+            .iter()
+            .map(|pair| ChangeCoupledPair {
+                first: relative(&pair.first),
+                second: relative(&pair.second),
+                ..pair.clone()
+            })
+            .collect();
+        sort_change_coupled_pairs(&mut pairs);
+        pairs
+    }
+
+    /// Rank uncovered functions by complexity, keeping the top
+    /// [`MAX_COVERAGE_HOT_SPOTS`].
+    fn build_coverage_hot_spots(
+        pipeline_results: &PipelineResults,
+        coverage_packs: &[CoveragePack],
+        project_root: &Path,
+    ) -> Vec<PrioritizedCoverageGap> {
+        if coverage_packs.is_empty() {
+            return Vec::new();
+        }
+
+        let complexity_results: HashMap<String, f64> = pipeline_results
+            .results
+            .complexity
+            .detailed_results
+            .iter()
+            .map(|result| {
+                let path = convert_to_relative_path(&result.file_path, project_root);
+                let id =
+                    coverage_entity_id(Path::new(&path), &result.entity_name, result.start_line);
+                (id, result.metrics.cyclomatic_complexity)
+            })
+            .collect();
+
+        let relative_packs: Vec<CoveragePack> = coverage_packs
+            .iter()
+            .map(|pack| CoveragePack {
+                path: PathBuf::from(convert_to_relative_path(
+                    &pack.path.to_string_lossy(),
+                    project_root,
+                )),
+                ..pack.clone()
+            })
+            .collect();
+
+        let mut hot_spots = prioritize_coverage_gaps(&relative_packs, &complexity_results);
+        hot_spots.truncate(MAX_COVERAGE_HOT_SPOTS);
+        hot_spots
+    }
+
     fn build_refactoring_candidates(
         pipeline_results: &PipelineResults,
         project_root: &PathBuf,
@@ -294,6 +475,50 @@ impl AnalysisResults {
             .collect()
     }
 
+    /// Report method chains from the structure pass as `R_METHOD_CHAIN` issues.
+    ///
+    /// Chains are attached to the narrowest candidate whose line range contains
+    /// them; chains outside every candidate get one candidate per file.
+    fn attach_method_chains(
+        candidates: &mut Vec<RefactoringCandidate>,
+        method_chains: &[FileMethodChains],
+        project_root: &Path,
+    ) {
+        for file in method_chains {
+            let file_path = convert_to_relative_path(&file.path.to_string_lossy(), project_root);
+            let mut by_candidate: BTreeMap<usize, Vec<&MethodChainDiagnosis>> = BTreeMap::new();
+            let mut unmatched = Vec::new();
+
+            for chain in &file.chains {
+                let owner = candidates
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.file_path == file_path)
+                    .filter_map(|(index, c)| {
+                        let (start, end) = c.line_range?;
+                        (start <= chain.start_line && chain.start_line <= end)
+                            .then_some((index, end - start))
+                    })
+                    .min_by_key(|&(_, span)| span)
+                    .map(|(index, _)| index);
+                match owner {
+                    Some(index) => by_candidate.entry(index).or_default().push(chain),
+                    None => unmatched.push(chain),
+                }
+            }
+
+            for (index, chains) in by_candidate {
+                let candidate = &mut candidates[index];
+                candidate.issues.push(method_chain_issue(&chains));
+                candidate.issue_count = candidate.issues.len();
+            }
+
+            if !unmatched.is_empty() {
+                candidates.push(method_chain_candidate(&file_path, &unmatched));
+            }
+        }
+    }
+
     fn count_priorities(
         files: &[crate::core::scoring::features::ScoringResult],
     ) -> (HashMap<String, usize>, usize, usize) {
@@ -384,13 +609,7 @@ impl AnalysisResults {
         if !coverage_packs.is_empty() {
             dict.suggestions
                 .entry("ADDTEST".to_string())
-                .or_insert_with(|| CodeDefinition {
-                    code: "ADDTEST".to_string(),
-                    title: "Add Test Coverage".to_string(),
-                    summary: "Write tests to cover this untested code path and improve safety."
-                        .to_string(),
-                    category: Some("coverage".to_string()),
-                });
+                .or_insert_with(add_test_definition);
         }
         dict
     }
@@ -472,6 +691,18 @@ impl AnalysisResults {
         })
     }
 
+    /// Path of `file_path` relative to [`Self::repo_root`], falling back to
+    /// the path as stored when no repository root is known or it does not
+    /// contain the file
+    pub fn repo_relative_path(&self, file_path: &str) -> PathBuf {
+        let absolute = self.project_root.join(file_path);
+        self.repo_root
+            .as_deref()
+            .and_then(|root| absolute.strip_prefix(root).ok())
+            .unwrap_or_else(|| Path::new(file_path))
+            .to_path_buf()
+    }
+
     /// Get the number of files processed
     pub fn files_analyzed(&self) -> usize {
         self.summary.files_processed
@@ -565,6 +796,72 @@ fn extract_line_range_from_metadata(vector: &FeatureVector) -> Option<(usize, us
         })
 }
 
+/// Build the `R_METHOD_CHAIN` issue summarising `chains`.
+fn method_chain_issue(chains: &[&MethodChainDiagnosis]) -> RefactoringIssue {
+    let longest = chains
+        .iter()
+        .map(|chain| chain.chain_length)
+        .max()
+        .unwrap_or(0) as f64;
+
+    RefactoringIssue {
+        code: issue_code_for_category("method_chain"),
+        category: "method_chain".to_string(),
+        severity: longest,
+        contributing_features: vec![
+            FeatureContribution {
+                feature_name: "method_chain_length".to_string(),
+                value: longest,
+                normalized_value: 0.0,
+                contribution: 0.0,
+            },
+            FeatureContribution {
+                feature_name: "method_chain_count".to_string(),
+                value: chains.len() as f64,
+                normalized_value: 0.0,
+                contribution: 0.0,
+            },
+        ],
+    }
+}
+
+/// Build a file-level candidate for method chains outside every other candidate.
+fn method_chain_candidate(
+    file_path: &str,
+    chains: &[&MethodChainDiagnosis],
+) -> RefactoringCandidate {
+    let first_line = chains
+        .iter()
+        .map(|chain| chain.start_line)
+        .min()
+        .unwrap_or(1);
+    let last_line = chains
+        .iter()
+        .map(|chain| chain.start_line)
+        .max()
+        .unwrap_or(first_line);
+    let name = Path::new(file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| file_path.to_string());
+    let issue = method_chain_issue(chains);
+
+    RefactoringCandidate {
+        entity_id: format!("{}:file:{}", file_path, name),
+        name,
+        file_path: file_path.to_string(),
+        line_range: Some((first_line, last_line)),
+        priority: Priority::Low,
+        score: issue.severity,
+        confidence: 0.9,
+        issues: vec![issue],
+        suggestions: Vec::new(),
+        issue_count: 1,
+        suggestion_count: 0,
+        coverage_percentage: None,
+    }
+}
+
 /// Factory and conversion methods for [`RefactoringCandidate`].
 impl RefactoringCandidate {
     /// Create a refactoring candidate from a scoring result
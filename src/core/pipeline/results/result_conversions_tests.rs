@@ -15,6 +15,7 @@ use crate::detectors::coverage::{
     CoverageGap, CoveragePack, FileInfo, GapFeatures, GapMarkers, GapSymbol, PackEffort, PackValue,
     SnippetPreview, SymbolKind, UncoveredSpan,
 };
+use crate::detectors::structure::{FileMethodChains, MethodChainDiagnosis};
 use chrono::Utc;
 use serde_json::json;
 use std::collections::HashMap;
@@ -73,6 +74,10 @@ fn pipeline_results_fixture() -> PipelineResults {
         directory_recommendations: Vec::new(),
         file_splitting_recommendations: Vec::new(),
         issues_count: 0,
+        shotgun_surgery: Vec::new(),
+        change_coupled_pairs: Vec::new(),
+        method_chains: Vec::new(),
+        entity_health: Vec::new(),
     };
 
     let complexity = ComplexityAnalysisResults {
@@ -97,6 +102,8 @@ fn pipeline_results_fixture() -> PipelineResults {
         chokepoints: Vec::new(),
         clone_groups: Vec::new(),
         issues_count: 0,
+        hub_files: Vec::new(),
+        import_cycles: Vec::new(),
     };
 
     let lsh = PipelineLshAnalysisResult {
@@ -109,6 +116,7 @@ fn pipeline_results_fixture() -> PipelineResults {
         verification: None,
         denoising_enabled: false,
         tfidf_stats: None,
+        unique_entities: Vec::new(),
     };
 
     let coverage = CoverageAnalysisResults {
@@ -154,6 +162,7 @@ fn pipeline_results_fixture() -> PipelineResults {
         documentation,
         cohesion: crate::detectors::cohesion::CohesionAnalysisResults::default(),
         health_metrics,
+        cancelled: false,
     };
 
     let pipeline_statistics = PipelineStatistics {
@@ -257,6 +266,7 @@ fn sample_coverage_pack_json() -> serde_json::Value {
             coverage_after_if_filled: 64.0,
         },
         gaps: vec![gap],
+        functions: Vec::new(),
         value: PackValue {
             file_cov_gain: 12.0,
             repo_cov_gain_est: 2.4,
@@ -406,6 +416,47 @@ fn from_pipeline_results_populates_dictionary_and_warnings() {
     assert_eq!(analysis.warnings, vec!["engine warning".to_string()]);
 }
 
+#[test]
+fn from_pipeline_results_reports_method_chains() {
+    let mut pipeline_results = pipeline_results_fixture();
+    pipeline_results.results.structure.method_chains = vec![FileMethodChains {
+        path: PathBuf::from("src/lib.rs"),
+        chains: vec![
+            MethodChainDiagnosis {
+                chain_length: 6,
+                start_line: 20,
+                root_object: "builder".to_string(),
+            },
+            MethodChainDiagnosis {
+                chain_length: 5,
+                start_line: 80,
+                root_object: "client".to_string(),
+            },
+        ],
+    }];
+
+    let analysis = AnalysisResults::from_pipeline_results(pipeline_results, PathBuf::new());
+
+    assert_eq!(analysis.refactoring_candidates.len(), 2);
+    let owner = &analysis.refactoring_candidates[0];
+    assert_eq!(owner.name, "process_data");
+    assert!(owner
+        .issues
+        .iter()
+        .any(|issue| issue.code == "R_METHOD_CHAIN"));
+    assert_eq!(owner.issue_count, owner.issues.len());
+
+    let file_level = &analysis.refactoring_candidates[1];
+    assert_eq!(file_level.file_path, "src/lib.rs");
+    assert_eq!(file_level.line_range, Some((80, 80)));
+    assert_eq!(file_level.issues[0].code, "R_METHOD_CHAIN");
+    assert_eq!(file_level.issues[0].severity, 5.0);
+    assert_eq!(
+        analysis.code_dictionary.issues["R_METHOD_CHAIN"].title,
+        "Long Method Chain"
+    );
+}
+
 #[test]
 fn convert_coverage_to_packs_filters_invalid_entries() {
     let mut coverage = CoverageAnalysisResults {
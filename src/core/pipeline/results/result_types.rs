@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use crate::core::pipeline::StageResultsBundle;
 use crate::core::pipeline::{CloneVerificationResults, HealthMetrics};
 use crate::core::scoring::Priority;
+use crate::detectors::graph::{HubFile, Scc};
+use crate::detectors::structure::file::{ChangeCoupledPair, ShotgunCandidate};
 // use crate::detectors::names::{RenamePack, ContractMismatchPack, ConsistencyIssue};
 
 #[cfg(test)]
@@ -22,6 +24,10 @@ pub struct AnalysisResults {
     #[serde(default)]
     pub project_root: PathBuf,
 
+    /// Repository root used to express file paths for external tools, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_root: Option<PathBuf>,
+
     /// Summary of the analysis
     pub summary: AnalysisSummary,
 
@@ -67,6 +73,30 @@ pub struct AnalysisResults {
     /// Coverage analysis results - test gap analysis with prioritized packs
     pub coverage_packs: Vec<crate::detectors::coverage::CoveragePack>,
 
+    /// Most complex uncovered functions, most severe test gap first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coverage_hot_spots: Vec<crate::detectors::coverage::PrioritizedCoverageGap>,
+
+    /// Files imported by many dependents, most depended-on first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hub_files: Vec<HubFile>,
+
+    /// Import cycles between files, largest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub import_cycles: Vec<Scc>,
+
+    /// Files showing the Shotgun Surgery smell, most co-changed first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shotgun_surgery: Vec<ShotgunCandidate>,
+
+    /// File pairs that most often change in the same commits, most coupled first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub change_coupled_pairs: Vec<ChangeCoupledPair>,
+
+    /// Entity IDs least similar to anything else in the codebase, most novel first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unique_entities: Vec<String>,
+
     /// Documentation analysis results (lightweight view for reports)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub documentation: Option<DocumentationResults>,
@@ -77,6 +107,10 @@ pub struct AnalysisResults {
     /// Dictionary describing issue/suggestion codes for downstream consumers
     #[serde(default, skip_serializing_if = "CodeDictionary::is_empty")]
     pub code_dictionary: CodeDictionary,
+
+    /// Whether the analysis was cancelled, leaving these results partial
+    #[serde(default)]
+    pub cancelled: bool,
 }
 
 /// Lightweight documentation results for public consumers
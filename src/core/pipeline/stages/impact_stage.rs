@@ -1,7 +1,7 @@
 //! Impact analysis stage for the pipeline.
 //!
-//! This module handles dependency impact analysis including cycle detection
-//! and chokepoint identification.
+//! This module handles dependency impact analysis including cycle detection,
+//! chokepoint identification, hub file and import cycle detection.
 
 use std::path::PathBuf;
 
@@ -10,6 +10,9 @@ use tracing::debug;
 use crate::core::dependency::ProjectDependencyAnalysis;
 use crate::core::errors::Result;
 use crate::core::pipeline::results::pipeline_results::ImpactAnalysisResults;
+use crate::detectors::graph::{
+    hub_files, strongly_connected_components, DependencyGraph, DEFAULT_HUB_THRESHOLD,
+};
 
 /// Impact analysis stage implementation.
 pub struct ImpactStage;
@@ -35,6 +38,8 @@ impl ImpactStage {
                 chokepoints: Vec::new(),
                 clone_groups: Vec::new(),
                 issues_count: 0,
+                hub_files: Vec::new(),
+                import_cycles: Vec::new(),
             });
         }
 
@@ -47,6 +52,8 @@ impl ImpactStage {
                 chokepoints: Vec::new(),
                 clone_groups: Vec::new(),
                 issues_count: 0,
+                hub_files: Vec::new(),
+                import_cycles: Vec::new(),
             });
         }
 
@@ -82,6 +89,12 @@ impl ImpactStage {
             .collect::<Vec<_>>();
 
         let issues_count = dependency_cycles.len() + chokepoints.len();
+        let file_graph = DependencyGraph::from_module_graph(analysis.module_graph());
+        let hub_files = hub_files(&file_graph, DEFAULT_HUB_THRESHOLD);
+        let import_cycles = strongly_connected_components(&file_graph)
+            .into_iter()
+            .filter(|scc| scc.is_cycle())
+            .collect();
 
         Ok(ImpactAnalysisResults {
             enabled: true,
@@ -89,6 +102,8 @@ impl ImpactStage {
             chokepoints,
             clone_groups: Vec::new(),
             issues_count,
+            hub_files,
+            import_cycles,
         })
     }
 }
@@ -20,7 +20,10 @@ use crate::core::pipeline::verification::clone_detection::{
     CloneDetectionStats, CloneEndpoint, ClonePairReport, LshDetectionParams, LshEntityCollection,
 };
 use crate::detectors::graph::SimilarityCliquePartitioner;
-use crate::detectors::lsh::{LshExtractor, LshSimilarityContext};
+use crate::detectors::lsh::{rank_by_novelty, LshExtractor, LshSimilarityContext};
+
+/// Number of most novel entities reported in [`LshAnalysisResults::unique_entities`].
+const TOP_UNIQUE_ENTITIES: usize = 10;
 
 /// LSH analysis stage implementation.
 pub struct LshStage<'a> {
@@ -119,9 +122,25 @@ impl<'a> LshStage<'a> {
             )
             .await;
 
-        let clone_pairs = filter_small_pairs(clone_pairs, min_ast_nodes);
+        let mut clone_pairs = filter_small_pairs(clone_pairs, min_ast_nodes);
+        let entity_refs: Vec<&CodeEntity> = entities.iter().collect();
+        self.lsh_extractor.rank_clone_pairs(
+            &mut clone_pairs,
+            |pair| (&pair.source.id, &pair.target.id, pair.similarity),
+            &entity_refs,
+        );
         let clone_pair_count = clone_pairs.len();
         let serialized_pairs = serialize_clone_pairs(clone_pairs, min_ast_nodes);
+        let unique_entities = similarity_context
+            .as_deref()
+            .map(|ctx| {
+                rank_by_novelty(ctx)
+                    .into_iter()
+                    .take(TOP_UNIQUE_ENTITIES)
+                    .map(|(id, _)| id)
+                    .collect()
+            })
+            .unwrap_or_default();
 
         Ok(LshAnalysisResults {
             enabled: true,
@@ -137,6 +156,7 @@ impl<'a> LshStage<'a> {
             } else {
                 None
             },
+            unique_entities,
         })
     }
 
@@ -228,6 +248,7 @@ impl<'a> LshStage<'a> {
         max_entities_per_file: usize,
     ) -> LshEntityCollection {
         let mut collection = LshEntityCollection::new();
+        let mut candidates = Vec::new();
 
         for file_path in files.iter() {
             let content = match tokio::fs::read_to_string(file_path).await {
@@ -272,22 +293,15 @@ impl<'a> LshStage<'a> {
                 continue;
             }
 
-            // Filter entities through LSH thresholds
-            for entity in extracted_entities {
-                if !self
-                    .lsh_extractor
-                    .entity_passes_thresholds(&entity)
-                    .await
-                    .unwrap_or(false)
-                {
-                    continue;
-                }
+            candidates.extend(extracted_entities);
+        }
 
-                collection
-                    .entity_index
-                    .insert(entity.id.clone(), entity.clone());
-                collection.entities.push(entity);
-            }
+        // Filter entities through LSH thresholds once all files are collected
+        for entity in self.lsh_extractor.filter_by_thresholds(candidates).await {
+            collection
+                .entity_index
+                .insert(entity.id.clone(), entity.clone());
+            collection.entities.push(entity);
         }
 
         collection
@@ -12,13 +12,18 @@ use crate::core::errors::Result;
 use crate::core::pipeline::results::pipeline_results::StructureAnalysisResults;
 use crate::detectors::structure::{PrecomputedFileMetrics, StructureExtractor};
 
-use crate::detectors::structure::StructureRecommendations;
+use crate::detectors::structure::file::{ChangeCoupledPair, ShotgunCandidate};
+use crate::detectors::structure::{FileHealthReport, FileMethodChains, StructureRecommendations};
 
 /// Collect recommendations from StructureRecommendations and convert to JSON values.
 fn collect_recommendations(
     recs: StructureRecommendations,
     all_recommendations: &mut Vec<serde_json::Value>,
     file_splitting_recommendations: &mut Vec<serde_json::Value>,
+    shotgun_surgery: &mut Vec<ShotgunCandidate>,
+    change_coupled_pairs: &mut Vec<ChangeCoupledPair>,
+    method_chains: &mut Vec<FileMethodChains>,
+    entity_health: &mut Vec<FileHealthReport>,
 ) {
     // Convert branch reorg packs to JSON and add to all_recommendations
     for pack in recs.branch_reorg_packs {
@@ -32,6 +37,10 @@ fn collect_recommendations(
             file_splitting_recommendations.push(value);
         }
     }
+    shotgun_surgery.extend(recs.shotgun_surgery);
+    change_coupled_pairs.extend(recs.change_coupled_pairs);
+    method_chains.extend(recs.method_chains);
+    entity_health.extend(recs.entity_health);
 }
 
 /// Structure analysis stage implementation.
@@ -57,6 +66,10 @@ impl<'a> StructureStage<'a> {
 
         let mut all_recommendations = Vec::new();
         let mut file_splitting_recommendations = Vec::new();
+        let mut shotgun_surgery = Vec::new();
+        let mut change_coupled_pairs = Vec::new();
+        let mut method_chains = Vec::new();
+        let mut entity_health = Vec::new();
 
         for path in paths {
             match self
@@ -68,6 +81,10 @@ impl<'a> StructureStage<'a> {
                     recs,
                     &mut all_recommendations,
                     &mut file_splitting_recommendations,
+                    &mut shotgun_surgery,
+                    &mut change_coupled_pairs,
+                    &mut method_chains,
+                    &mut entity_health,
                 ),
                 Err(e) => warn!("Structure analysis failed for {}: {}", path.display(), e),
             }
@@ -80,6 +97,10 @@ impl<'a> StructureStage<'a> {
             directory_recommendations: all_recommendations,
             file_splitting_recommendations,
             issues_count,
+            shotgun_surgery,
+            change_coupled_pairs,
+            method_chains,
+            entity_health,
         })
     }
 
@@ -101,6 +122,10 @@ impl<'a> StructureStage<'a> {
 
         let mut all_recommendations = Vec::new();
         let mut file_splitting_recommendations = Vec::new();
+        let mut shotgun_surgery = Vec::new();
+        let mut change_coupled_pairs = Vec::new();
+        let mut method_chains = Vec::new();
+        let mut entity_health = Vec::new();
 
         for path in paths {
             match self
@@ -112,6 +137,10 @@ impl<'a> StructureStage<'a> {
                     recs,
                     &mut all_recommendations,
                     &mut file_splitting_recommendations,
+                    &mut shotgun_surgery,
+                    &mut change_coupled_pairs,
+                    &mut method_chains,
+                    &mut entity_health,
                 ),
                 Err(e) => warn!("Structure analysis failed for {}: {}", path.display(), e),
             }
@@ -124,6 +153,10 @@ impl<'a> StructureStage<'a> {
             directory_recommendations: all_recommendations,
             file_splitting_recommendations,
             issues_count,
+            shotgun_surgery,
+            change_coupled_pairs,
+            method_chains,
+            entity_health,
         })
     }
 }
@@ -14,6 +14,9 @@ use crate::core::config::{NormalizationScheme, ScoringConfig, WeightsConfig};
 use crate::core::errors::{Result, ValknutError};
 use crate::core::featureset::FeatureVector;
 
+/// Features where a higher value means healthier code, so their contribution is inverted.
+const HIGHER_IS_BETTER_FEATURES: &[&str] = &["maintainability_index"];
+
 /// Main feature normalization engine that supports multiple schemes
 #[derive(Debug)]
 pub struct FeatureNormalizer {
@@ -412,7 +415,13 @@ impl FeatureScorer {
         for (feature_name, &normalized_value) in &vector.normalized_features {
             let (category, weight) = self.get_feature_category_and_weight(feature_name);
 
-            let contribution = normalized_value * weight;
+            // Features where higher values are healthier pull the priority down
+            let polarity = if HIGHER_IS_BETTER_FEATURES.contains(&feature_name.as_str()) {
+                -1.0
+            } else {
+                1.0
+            };
+            let contribution = normalized_value * weight * polarity;
             feature_contributions.insert(feature_name.clone(), contribution);
 
             // Accumulate category score
@@ -456,6 +465,7 @@ impl FeatureScorer {
     fn get_feature_category_and_weight(&self, feature_name: &str) -> (String, f64) {
         // Category patterns: (keywords, category_name, weight_getter)
         const CATEGORY_PATTERNS: &[(&[&str], &str)] = &[
+            (&["maintainability"], "maintainability"),
             (&["cyclomatic", "cognitive", "complexity"], "complexity"),
             (&["betweenness", "centrality", "fan_"], "graph"),
             (
@@ -492,6 +502,7 @@ impl FeatureScorer {
             "structure" => self.weights.structure,
             "style" => self.weights.style,
             "coverage" => self.weights.coverage,
+            "maintainability" => self.weights.maintainability,
             _ => 1.0,
         }
     }
@@ -251,6 +251,28 @@ fn test_feature_scorer_compute_scores() {
     assert!(result.confidence >= 0.0); // Can be 0.0 if not properly calculated
 }
 
+#[test]
+fn test_low_maintainability_index_raises_score() {
+    let mut config = create_test_config();
+    config.weights.maintainability = 2.0;
+    let mut scorer = FeatureScorer::new(config);
+
+    let mut vectors = vec![FeatureVector::new("tangled"), FeatureVector::new("tidy")];
+    vectors[0].add_feature("maintainability_index", 30.0);
+    vectors[1].add_feature("maintainability_index", 90.0);
+
+    scorer.fit(&vectors).unwrap();
+    let results = scorer.score(&mut vectors).unwrap();
+
+    assert!(results[0].overall_score > 0.0);
+    assert!(results[1].overall_score < 0.0);
+    assert!(results[0].category_scores.contains_key("maintainability"));
+    assert_eq!(
+        results[0].feature_contributions["maintainability_index"],
+        -vectors[0].normalized_features["maintainability_index"] * 2.0
+    );
+}
+
 #[test]
 fn test_feature_scorer_get_category_weight() {
     let config = create_test_config();
@@ -17,6 +17,9 @@ use crate::core::errors::Result;
 use crate::core::featureset::{CodeEntity, ExtractionContext, FeatureDefinition, FeatureExtractor};
 use crate::core::file_utils::ranges_overlap;
 
+/// Feature name for the Maintainability Index, emitted only when Halstead volume is known.
+const MAINTAINABILITY_INDEX_FEATURE: &str = "maintainability_index";
+
 /// Feature extractor implementation for AST-based complexity
 pub struct AstComplexityExtractor {
     analyzer: AstComplexityAnalyzer,
@@ -49,6 +52,17 @@ impl AstComplexityExtractor {
                 .with_range(1.0, 1000.0)
                 .with_default(1.0)
                 .with_polarity(true),
+            FeatureDefinition::new("return_count", "Number of return points")
+                .with_range(0.0, 20.0)
+                .with_default(0.0)
+                .with_polarity(true),
+            FeatureDefinition::new(
+                MAINTAINABILITY_INDEX_FEATURE,
+                "Maintainability Index from cyclomatic complexity, Halstead volume and LOC",
+            )
+            .with_range(0.0, 100.0)
+            .with_default(100.0)
+            .with_polarity(false),
         ];
 
         Self {
@@ -101,11 +115,30 @@ impl AstComplexityExtractor {
         }
     }
 
+    /// Analyze in-memory source and cache the results under `file_path`.
+    ///
+    /// Subsequent extractions for entities in `file_path` use these results
+    /// instead of reading the file from disk.
+    pub async fn prime_source(&self, file_path: &str, source: &str) -> Result<()> {
+        let results = self
+            .analyzer
+            .analyze_file_with_results(file_path, source)
+            .await?;
+        self.analysis_cache
+            .insert(file_path.to_owned(), Arc::new(results));
+        Ok(())
+    }
+
     /// Initialize a feature map with default values.
+    ///
+    /// The Maintainability Index has no meaningful default and is left out
+    /// until its component metrics are known.
     fn initialise_feature_map(&self) -> HashMap<String, f64> {
         let mut map = HashMap::with_capacity(self.feature_definitions.len());
         for definition in &self.feature_definitions {
-            map.insert(definition.name.clone(), definition.default_value);
+            if definition.name != MAINTAINABILITY_INDEX_FEATURE {
+                map.insert(definition.name.clone(), definition.default_value);
+            }
         }
         map
     }
@@ -177,30 +210,45 @@ fn find_relevant_results<'a>(
     relevant
 }
 
-/// Aggregate maximum metrics from relevant results into the feature map.
+/// Aggregate the worst metrics from relevant results into the feature map.
+///
+/// Size and complexity take the maximum; the Maintainability Index takes the
+/// minimum over results with a known Halstead volume.
 fn aggregate_metrics_into_features(
     relevant: &[&ComplexityAnalysisResult],
     features: &mut HashMap<String, f64>,
 ) {
     let (mut cyclomatic, mut cognitive, mut nesting, mut parameters, mut loc) =
         (0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64);
+    let mut returns = 0.0_f64;
+    let mut maintainability: Option<f64> = None;
 
     for result in relevant {
         let m = &result.metrics;
+        if m.halstead.volume > 0.0 {
+            maintainability = Some(maintainability.map_or(m.maintainability_index, |mi| {
+                mi.min(m.maintainability_index)
+            }));
+        }
         cyclomatic = cyclomatic.max(m.cyclomatic_complexity);
         cognitive = cognitive.max(m.cognitive_complexity);
         nesting = nesting.max(m.max_nesting_depth);
         parameters = parameters.max(m.parameter_count);
         loc = loc.max(m.lines_of_code);
+        returns = returns.max(m.return_count);
     }
 
     features.insert("cyclomatic_complexity".to_string(), cyclomatic);
     features.insert("cognitive_complexity".to_string(), cognitive);
     features.insert("nesting_depth".to_string(), nesting);
     features.insert("parameter_count".to_string(), parameters);
+    features.insert("return_count".to_string(), returns);
     if loc > 0.0 {
         features.insert("lines_of_code".to_string(), loc);
     }
+    if let Some(mi) = maintainability {
+        features.insert(MAINTAINABILITY_INDEX_FEATURE.to_string(), mi);
+    }
 }
 
 /// Ensure a lines_of_code value exists, computing from entity if needed.
@@ -5,9 +5,11 @@
 
 mod extractor;
 mod halstead;
+mod returns;
 pub mod types;
 
 pub use extractor::AstComplexityExtractor;
+pub use returns::count_return_statements;
 
 use serde_json::json;
 use std::collections::HashMap;
@@ -27,6 +29,30 @@ pub use types::{
     HalsteadMetrics,
 };
 
+/// Suggestion code for functions with too many return points.
+pub const MULTIPLE_RETURNS_CODE: &str = "R_MULTIPLE_RETURNS";
+
+/// Cyclomatic complexity above which multiple return points are flagged.
+const MULTIPLE_RETURNS_MIN_CYCLOMATIC: f64 = 5.0;
+
+/// Factor that rescales the raw Maintainability Index (at most 171) onto `0..=100`.
+pub const MAINTAINABILITY_INDEX_SCALE: f64 = 100.0 / 171.0;
+
+/// Maintainability Index normalized to `0..=100` (higher is more maintainable).
+///
+/// `MI = max(0, (171 - 5.2*ln(HV) - 0.23*CC - 16.2*ln(LOC)) * 100/171)`, where a
+/// Halstead volume or line count below one is treated as one so the logarithms
+/// stay finite. Values are [`MAINTAINABILITY_INDEX_SCALE`] times the raw MI that
+/// was previously reported clamped to 100, so thresholds tuned on the raw scale
+/// must be divided by it. `loc` is the entity's full line span
+/// ([`CodeEntity::line_count`]), blank and comment lines included.
+pub fn maintainability_index(cyclomatic: f64, halstead_volume: f64, loc: usize) -> f64 {
+    let volume = halstead_volume.max(1.0);
+    let lines = loc.max(1) as f64;
+    let mi = 171.0 - 5.2 * volume.ln() - 0.23 * cyclomatic - 16.2 * lines.ln();
+    (mi * MAINTAINABILITY_INDEX_SCALE).max(0.0)
+}
+
 /// AST-based complexity analyzer - the CORRECT implementation
 #[derive(Clone)]
 pub struct AstComplexityAnalyzer {
@@ -110,6 +136,11 @@ impl AstComplexityAnalyzer {
     ) -> ComplexityAnalysisResult {
         let issues = self.generate_issues_from_metrics(&entity.id, &metrics);
         let start_line = entity.line_range.map(|(start, _)| start).unwrap_or(1);
+        let recommendations = issues
+            .iter()
+            .filter(|issue| issue.issue_type == "multiple_return_points")
+            .map(|issue| format!("{}: {}", MULTIPLE_RETURNS_CODE, issue.recommendation))
+            .collect();
 
         ComplexityAnalysisResult {
             entity_id: entity.id.clone(),
@@ -124,7 +155,7 @@ impl AstComplexityAnalyzer {
                 .into_iter()
                 .map(|issue| self.convert_issue(&entity.id, file_path, start_line, issue))
                 .collect(),
-            recommendations: Vec::new(),
+            recommendations,
         }
     }
 
@@ -159,6 +190,7 @@ impl AstComplexityAnalyzer {
             "excessive_nesting" => ComplexityIssueType::DeepNesting,
             "too_many_parameters" => ComplexityIssueType::TooManyParameters,
             "large_file" => ComplexityIssueType::LongFile,
+            "multiple_return_points" => ComplexityIssueType::MultipleReturns,
             _ => ComplexityIssueType::HighTechnicalDebt,
         }
     }
@@ -406,9 +438,12 @@ impl AstComplexityAnalyzer {
         let lines_of_code = entity.line_count() as f64;
         let parameter_count = self.count_parameters_in_entity(entity, context)?;
         let statement_count = self.count_statements_in_entity(entity, context)?;
+        let return_count = find_entity_node(context, entity)
+            .map(|node| returns::count_return_nodes(node, true) as f64)
+            .unwrap_or(0.0);
         let halstead = self.calculate_halstead_for_entity(entity, context)?;
         let maintainability_index =
-            self.calculate_maintainability_index(entity_cyclomatic, lines_of_code, &halstead);
+            maintainability_index(entity_cyclomatic, halstead.volume, entity.line_count());
 
         let metrics = ComplexityMetrics {
             cyclomatic_complexity: entity_cyclomatic,
@@ -417,6 +452,7 @@ impl AstComplexityAnalyzer {
             parameter_count,
             lines_of_code,
             statement_count,
+            return_count,
             halstead,
             technical_debt_score: self.calculate_technical_debt(
                 entity_cyclomatic,
@@ -635,23 +671,6 @@ impl AstComplexityAnalyzer {
             * 100.0
     }
 
-    /// Calculate maintainability index
-    fn calculate_maintainability_index(
-        &self,
-        cyclomatic: f64,
-        lines: f64,
-        halstead: &HalsteadMetrics,
-    ) -> f64 {
-        // Microsoft maintainability index formula
-        let volume = if halstead.volume > 0.0 {
-            halstead.volume
-        } else {
-            1.0
-        };
-        let mi = 171.0 - 5.2 * volume.ln() - 0.23 * cyclomatic - 16.2 * lines.ln();
-        mi.max(0.0).min(100.0)
-    }
-
     /// Generate complexity issues from metrics
     fn generate_issues_from_metrics(
         &self,
@@ -690,6 +709,27 @@ impl AstComplexityAnalyzer {
             "Reduce nesting by using early returns or extracting functions",
         );
 
+        let max_returns = self.config.max_returns as f64;
+        if metrics.return_count > max_returns
+            && metrics.cyclomatic_complexity > MULTIPLE_RETURNS_MIN_CYCLOMATIC
+        {
+            issues.push(ComplexityIssue {
+                entity_id: entity_id.clone(),
+                issue_type: "multiple_return_points".to_string(),
+                severity: "medium".to_string(),
+                description: format!(
+                    "{:.0} return points exceed the limit of {}",
+                    metrics.return_count, self.config.max_returns
+                ),
+                recommendation:
+                    "Restructure with early guard clauses so the main path has a single exit"
+                        .to_string(),
+                location: entity_id.clone(),
+                metric_value: metrics.return_count,
+                threshold: max_returns,
+            });
+        }
+
         issues
     }
 
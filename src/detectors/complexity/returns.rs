@@ -0,0 +1,91 @@
+//! Return point counting for the multiple-returns smell.
+//!
+//! Python, JavaScript and TypeScript expose `return_statement` nodes while
+//! Rust models `return` as a `return_expression`; both are counted. A Rust
+//! function whose body ends in an expression returns it implicitly, which
+//! counts as one more return point.
+
+use tree_sitter::Parser;
+
+use crate::core::errors::{Result, ValknutError};
+use crate::lang::registry::get_tree_sitter_language;
+
+/// Node kinds that represent an explicit `return`.
+const RETURN_NODE_KINDS: &[&str] = &["return_statement", "return_expression"];
+
+/// Node kinds that open a nested function scope whose returns belong elsewhere.
+const NESTED_SCOPE_KINDS: &[&str] = &[
+    "function_definition",
+    "function_declaration",
+    "function_expression",
+    "arrow_function",
+    "method_definition",
+    "function_item",
+    "closure_expression",
+    "method_declaration",
+];
+
+/// Count return points in `source` parsed as `language`: explicit `return`s
+/// plus the implicit tail return of each Rust function.
+///
+/// Every return in the snippet is counted, including those inside nested
+/// functions, so pass a single function to get a per-function count.
+pub fn count_return_statements(source: &str, language: &str) -> Result<usize> {
+    let ts_language = get_tree_sitter_language(language)?;
+    let mut parser = Parser::new();
+    parser.set_language(&ts_language).map_err(|e| {
+        ValknutError::parse(language, format!("Failed to set parser language: {}", e))
+    })?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| ValknutError::parse(language, "Failed to parse source code"))?;
+
+    Ok(count_return_nodes(tree.root_node(), false))
+}
+
+/// Count return nodes beneath `root`.
+///
+/// With `skip_nested_scopes` set, returns inside nested functions and closures
+/// are ignored so only the exits of `root` itself are counted.
+pub(crate) fn count_return_nodes(root: tree_sitter::Node<'_>, skip_nested_scopes: bool) -> usize {
+    let mut count = 0;
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        let kind = node.kind();
+        if RETURN_NODE_KINDS.contains(&kind) {
+            count += 1;
+        }
+        if skip_nested_scopes && node.id() != root.id() && NESTED_SCOPE_KINDS.contains(&kind) {
+            continue;
+        }
+        if kind == "function_item" && has_tail_expression(node) {
+            count += 1;
+        }
+
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+
+    count
+}
+
+/// Whether a Rust function body ends in an expression, its implicit return value.
+fn has_tail_expression(function: tree_sitter::Node<'_>) -> bool {
+    let Some(body) = function.child_by_field_name("body") else {
+        return false;
+    };
+    let mut cursor = body.walk();
+    let tail = body
+        .named_children(&mut cursor)
+        .filter(|child| !child.kind().ends_with("comment"))
+        .last();
+
+    tail.is_some_and(|node| {
+        let kind = node.kind();
+        kind != "return_expression"
+            && !kind.ends_with("_statement")
+            && !kind.ends_with("_declaration")
+            && !kind.ends_with("_item")
+    })
+}
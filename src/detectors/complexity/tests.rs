@@ -144,6 +144,12 @@ def complex_target(a, b):
             >= 2.0
     );
     assert!(features.get("lines_of_code").copied().unwrap_or_default() >= 5.0);
+
+    let mi = features["maintainability_index"];
+    assert!(
+        mi > 0.0 && mi < 100.0,
+        "unexpected maintainability index {mi}"
+    );
 }
 
 #[tokio::test]
@@ -296,6 +302,18 @@ fn test_complexity_config() {
     assert!(config.enabled);
 }
 
+#[test]
+fn test_maintainability_index_formula() {
+    let expected =
+        (171.0 - 5.2 * 100.0_f64.ln() - 0.23 * 4.0 - 16.2 * 20.0_f64.ln()) * 100.0 / 171.0;
+    assert!((maintainability_index(4.0, 100.0, 20) - expected).abs() < 1e-9);
+
+    // Degenerate inputs stay finite, and huge entities bottom out at zero.
+    assert!(maintainability_index(1.0, 0.0, 0) <= 100.0);
+    assert_eq!(maintainability_index(80.0, 1e9, 100_000), 0.0);
+    assert!(maintainability_index(2.0, 50.0, 10) > maintainability_index(20.0, 500.0, 200));
+}
+
 #[test]
 fn test_halstead_metrics() {
     let metrics = HalsteadMetrics::default();
@@ -402,3 +420,107 @@ fn test_complexity_thresholds_validation() {
     assert!(file_thresholds.medium < file_thresholds.high);
     assert!(file_thresholds.high < file_thresholds.very_high);
 }
+
+#[test]
+fn test_count_return_statements_rust() {
+    let source = r#"
+fn classify(value: i32) -> &'static str {
+    if value < 0 {
+        return "negative";
+    }
+    if value == 0 {
+        return "zero";
+    }
+    return "positive";
+}
+"#;
+    assert_eq!(count_return_statements(source, "rust").unwrap(), 3);
+
+    let implicit = r#"
+fn sign(value: i32) -> i32 {
+    if value < 0 {
+        return -1;
+    }
+    // Zero and positive values
+    value.signum()
+}
+
+fn log(value: i32) {
+    println!("{}", value);
+}
+"#;
+    assert_eq!(count_return_statements(implicit, "rust").unwrap(), 2);
+}
+
+#[test]
+fn test_count_return_statements_python() {
+    let source = r#"
+def classify(value):
+    if value < 0:
+        return "negative"
+    elif value == 0:
+        return "zero"
+    return "positive"
+"#;
+    assert_eq!(count_return_statements(source, "python").unwrap(), 3);
+}
+
+#[test]
+fn test_count_return_statements_typescript() {
+    let source = r#"
+function classify(value: number): string {
+    if (value < 0) {
+        return "negative";
+    }
+    return value === 0 ? "zero" : "positive";
+}
+"#;
+    assert_eq!(count_return_statements(source, "typescript").unwrap(), 2);
+    assert!(count_return_statements(source, "cobol").is_err());
+}
+
+#[tokio::test]
+async fn test_multiple_returns_flagged_for_complex_functions() {
+    let analyzer =
+        AstComplexityAnalyzer::new(ComplexityConfig::default(), Arc::new(AstService::new()));
+
+    let source = r#"
+def route(kind, value, flag):
+    if kind == "a":
+        return 1
+    if kind == "b":
+        return 2
+    if kind == "c":
+        return 3
+    if kind == "d" and flag:
+        return 4
+    if value > 10:
+        return 5
+    return 0
+
+def simple(kind):
+    if kind:
+        return 1
+    return 0
+"#;
+
+    let results = analyzer
+        .analyze_file_with_results("route.py", source)
+        .await
+        .unwrap();
+
+    let route = results.iter().find(|r| r.entity_name == "route").unwrap();
+    assert_eq!(route.metrics.return_count, 6.0);
+    assert!(route
+        .issues
+        .iter()
+        .any(|issue| issue.issue_type == "MultipleReturns"));
+    assert!(route
+        .recommendations
+        .iter()
+        .any(|rec| rec.starts_with(MULTIPLE_RETURNS_CODE)));
+
+    let simple = results.iter().find(|r| r.entity_name == "simple").unwrap();
+    assert_eq!(simple.metrics.return_count, 2.0);
+    assert!(simple.recommendations.is_empty());
+}
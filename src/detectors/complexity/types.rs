@@ -19,6 +19,17 @@ pub struct ComplexityConfig {
     pub file_length_thresholds: ComplexityThresholds,
     /// Function length thresholds (lines)
     pub function_length_thresholds: ComplexityThresholds,
+    /// Maximum explicit return points before a complex function is flagged
+    #[serde(default = "ComplexityConfig::default_max_returns")]
+    pub max_returns: usize,
+}
+
+/// Serde defaults for [`ComplexityConfig`].
+impl ComplexityConfig {
+    /// Default maximum number of return points per function.
+    pub fn default_max_returns() -> usize {
+        4
+    }
 }
 
 /// Default implementation for [`ComplexityConfig`].
@@ -33,6 +44,7 @@ impl Default for ComplexityConfig {
             parameter_thresholds: ComplexityThresholds::default_parameters(),
             file_length_thresholds: ComplexityThresholds::default_file_length(),
             function_length_thresholds: ComplexityThresholds::default_function_length(),
+            max_returns: Self::default_max_returns(),
         }
     }
 }
@@ -164,6 +176,7 @@ pub enum ComplexityIssueType {
     LongFunction,
     LongFile,
     HighTechnicalDebt,
+    MultipleReturns,
 }
 
 /// Enhanced complexity metrics from AST analysis
@@ -181,6 +194,9 @@ pub struct ComplexityMetrics {
     pub lines_of_code: f64,
     /// Number of statements
     pub statement_count: f64,
+    /// Number of explicit return points
+    #[serde(default)]
+    pub return_count: f64,
     /// Halstead complexity metrics
     pub halstead: HalsteadMetrics,
     /// Technical debt score
@@ -4,12 +4,12 @@
 //! based on various metrics like complexity, fan-in, and file centrality.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::core::errors::Result;
 
 use super::config::CoverageConfig;
-use super::types::CoverageGap;
+use super::types::{CoverageGap, CoveragePack, PrioritizedCoverageGap, SymbolKind};
 
 /// Metrics computed for a single file's coverage gaps.
 #[derive(Debug, Clone)]
@@ -154,3 +154,64 @@ pub fn normalize_fan_in_score(fan_in: usize) -> f64 {
     let x = fan_in as f64;
     (x / (x + 5.0)).clamp(0.0, 1.0)
 }
+
+/// Identifier linking a function in a coverage pack to its complexity result.
+pub fn coverage_entity_id(path: &Path, name: &str, line_start: usize) -> String {
+    let path = path.display().to_string();
+    let path = path.strip_prefix("./").unwrap_or(&path);
+    format!("{}:{}:{}", path, name, line_start)
+}
+
+/// Rank uncovered functions by `complexity * (1.0 - coverage_ratio)`, most severe first.
+///
+/// A function's coverage ratio comes from intersecting its line range (from
+/// [`CoveragePack::functions`]) with the pack's uncovered spans, so partially
+/// covered functions are ranked too. `complexity_results` is keyed by
+/// [`coverage_entity_id`]; functions without a complexity measurement are skipped.
+pub fn prioritize_coverage_gaps(
+    coverage: &[CoveragePack],
+    complexity_results: &HashMap<String, f64>,
+) -> Vec<PrioritizedCoverageGap> {
+    let mut prioritized: Vec<PrioritizedCoverageGap> = coverage
+        .iter()
+        .flat_map(|pack| {
+            pack.functions
+                .iter()
+                .filter(|symbol| matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method))
+                .map(move |symbol| (pack, symbol))
+        })
+        .filter_map(|(pack, symbol)| {
+            let symbol_loc = symbol.line_end.saturating_sub(symbol.line_start) + 1;
+            let uncovered_lines = pack
+                .gaps
+                .iter()
+                .map(|gap| {
+                    let overlap_start = gap.span.start.max(symbol.line_start);
+                    let overlap_end = gap.span.end.min(symbol.line_end);
+                    (overlap_end + 1).saturating_sub(overlap_start)
+                })
+                .sum::<usize>()
+                .min(symbol_loc);
+            if uncovered_lines == 0 {
+                return None;
+            }
+
+            let entity_id = coverage_entity_id(&pack.path, &symbol.name, symbol.line_start);
+            let complexity = *complexity_results.get(&entity_id)?;
+            let coverage_ratio = 1.0 - uncovered_lines as f64 / symbol_loc as f64;
+            Some(PrioritizedCoverageGap {
+                entity_id,
+                complexity,
+                coverage_ratio,
+                gap_severity: complexity * (1.0 - coverage_ratio),
+            })
+        })
+        .collect();
+
+    prioritized.sort_by(|a, b| {
+        b.gap_severity
+            .total_cmp(&a.gap_severity)
+            .then_with(|| a.entity_id.cmp(&b.entity_id))
+    });
+    prioritized
+}
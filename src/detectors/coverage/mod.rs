@@ -4,6 +4,7 @@ pub mod config;
 pub use config::CoverageConfig;
 
 mod gap_scoring;
+pub use gap_scoring::{coverage_entity_id, prioritize_coverage_gaps};
 mod parsers;
 pub mod types;
 
@@ -103,7 +104,7 @@ impl CoverageExtractor {
         }
 
         let language = self.detect_language(path);
-        let file_gaps = self.build_gaps_for_file(path, spans, &language).await?;
+        let (file_gaps, functions) = self.build_gaps_for_file(path, spans, &language).await?;
         if file_gaps.is_empty() {
             return Ok(None);
         }
@@ -125,6 +126,7 @@ impl CoverageExtractor {
                 coverage_after_if_filled: coverage_stats.coverage_after,
             },
             gaps,
+            functions,
             value: PackValue {
                 file_cov_gain: coverage_stats.file_cov_gain,
                 repo_cov_gain_est: coverage_stats.repo_cov_gain_est,
@@ -187,7 +189,7 @@ impl CoverageExtractor {
         path: &PathBuf,
         spans: Vec<UncoveredSpan>,
         language: &str,
-    ) -> Result<Vec<CoverageGap>> {
+    ) -> Result<(Vec<CoverageGap>, Vec<GapSymbol>)> {
         let coalesced = self.coalesce_spans_for_file(&spans)?;
         let chunked = self.chunk_spans_by_language(path, language, &coalesced)?;
 
@@ -199,7 +201,7 @@ impl CoverageExtractor {
                     path.display(),
                     err
                 );
-                return Ok(Vec::new());
+                return Ok((Vec::new(), Vec::new()));
             }
         };
 
@@ -220,7 +222,12 @@ impl CoverageExtractor {
             }
         }
 
-        Ok(gaps)
+        let functions = cached_tree
+            .as_ref()
+            .map(|tree| self.functions_intersecting_gaps(&content, tree, &gaps))
+            .unwrap_or_default();
+
+        Ok((gaps, functions))
     }
 
     /// Get cached AST for a file, returning None for empty content.
@@ -698,6 +705,43 @@ impl CoverageExtractor {
         cached_tree: &Arc<crate::core::ast_service::CachedTree>,
         start_line: usize,
         end_line: usize,
+    ) -> Vec<GapSymbol> {
+        self.symbols_intersecting(content, cached_tree, start_line, end_line)
+            .into_iter()
+            .filter(|symbol| symbol.line_start >= start_line && symbol.line_end <= end_line)
+            .collect()
+    }
+
+    /// Functions and methods whose line range intersects any gap, ordered by position.
+    fn functions_intersecting_gaps(
+        &self,
+        content: &str,
+        cached_tree: &Arc<crate::core::ast_service::CachedTree>,
+        gaps: &[CoverageGap],
+    ) -> Vec<GapSymbol> {
+        let mut functions: Vec<GapSymbol> = gaps
+            .iter()
+            .flat_map(|gap| {
+                self.symbols_intersecting(content, cached_tree, gap.span.start, gap.span.end)
+            })
+            .filter(|symbol| matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method))
+            .collect();
+        functions.sort_by(|a, b| {
+            (a.line_start, a.line_end, &a.name).cmp(&(b.line_start, b.line_end, &b.name))
+        });
+        functions.dedup_by(|a, b| {
+            a.line_start == b.line_start && a.line_end == b.line_end && a.name == b.name
+        });
+        functions
+    }
+
+    /// Symbols whose line range intersects `start_line..=end_line`.
+    fn symbols_intersecting(
+        &self,
+        content: &str,
+        cached_tree: &Arc<crate::core::ast_service::CachedTree>,
+        start_line: usize,
+        end_line: usize,
     ) -> Vec<GapSymbol> {
         let mut symbols = Vec::new();
         let tree = &cached_tree.tree;
@@ -753,9 +797,6 @@ impl CoverageExtractor {
         }
 
         symbols
-            .into_iter()
-            .filter(|symbol| symbol.line_start >= start_line && symbol.line_end <= end_line)
-            .collect()
     }
 
     fn cognitive_weight(&self, kind: &DecisionKind) -> u32 {
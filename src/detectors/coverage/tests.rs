@@ -135,3 +135,64 @@ def b():
     assert_eq!(chunked[0].start, 1);
     assert!(chunked.iter().any(|s| s.start > 1));
 }
+
+#[tokio::test]
+async fn prioritize_coverage_gaps_ranks_partially_covered_functions() {
+    let tmp = tempdir().expect("temp dir");
+    let source_path = tmp.path().join("lib.rs");
+    let source = r#"pub fn parse(input: &str) -> i32 {
+    if input.is_empty() {
+        return 0;
+    }
+    if input.starts_with('-') {
+        return -1;
+    }
+    input.len() as i32
+}
+
+
+pub fn get() -> i32 {
+    1
+}
+"#;
+    fs::write(&source_path, source).expect("write source file");
+
+    // `parse` (lines 1-9) has lines 5-8 uncovered, `get` (lines 12-14) is fully uncovered.
+    let lcov_path = tmp.path().join("coverage.lcov");
+    let lcov_report = format!(
+        "TN:\nSF:{}\nDA:1,1\nDA:2,1\nDA:3,1\nDA:4,1\nDA:5,0\nDA:6,0\nDA:7,0\nDA:8,0\nDA:9,1\nDA:12,0\nDA:13,0\nDA:14,0\nend_of_record\n",
+        source_path.display()
+    );
+    fs::write(&lcov_path, lcov_report).expect("write lcov file");
+
+    let mut config = CoverageConfig::default();
+    config.min_gap_loc = 1;
+    let packs = make_extractor(config)
+        .build_coverage_packs(vec![lcov_path])
+        .await
+        .expect("pack generation");
+    let pack = packs
+        .iter()
+        .find(|pack| pack.path == source_path)
+        .expect("pack for source file");
+
+    let complexity: HashMap<String, f64> = [
+        (coverage_entity_id(&pack.path, "parse", 1), 12.0),
+        (coverage_entity_id(&pack.path, "get", 12), 1.0),
+    ]
+    .into_iter()
+    .collect();
+
+    let gaps = prioritize_coverage_gaps(&packs, &complexity);
+
+    assert_eq!(gaps.len(), 2);
+    assert_eq!(
+        gaps[0].entity_id,
+        coverage_entity_id(&pack.path, "parse", 1)
+    );
+    assert!((gaps[0].coverage_ratio - 5.0 / 9.0).abs() < 1e-9);
+    assert!((gaps[0].gap_severity - 12.0 * 4.0 / 9.0).abs() < 1e-9);
+    assert_eq!(gaps[1].entity_id, coverage_entity_id(&pack.path, "get", 12));
+    assert_eq!(gaps[1].coverage_ratio, 0.0);
+    assert!((gaps[1].gap_severity - 1.0).abs() < 1e-9);
+}
@@ -101,6 +101,16 @@ pub struct PackEffort {
     pub mocks_est: usize,
 }
 
+/// An uncovered function ranked by how urgently it needs tests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrioritizedCoverageGap {
+    pub entity_id: String,
+    pub complexity: f64,
+    pub coverage_ratio: f64,
+    /// `complexity * (1.0 - coverage_ratio)`
+    pub gap_severity: f64,
+}
+
 /// A collection of prioritized coverage gaps
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoveragePack {
@@ -109,6 +119,9 @@ pub struct CoveragePack {
     pub path: PathBuf,
     pub file_info: FileInfo,
     pub gaps: Vec<CoverageGap>,
+    /// Functions intersecting any gap, with their full line ranges
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<GapSymbol>,
     pub value: PackValue,
     pub effort: PackEffort,
 }
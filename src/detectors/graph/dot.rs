@@ -0,0 +1,358 @@
+//! Graphviz DOT export for [`DependencyGraph`].
+//!
+//! The output is deterministic: nodes and edges are sorted by their relative
+//! paths so the file can be committed and diffed between runs.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fmt::Write;
+use std::path::Path;
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+
+use super::DependencyGraph;
+
+/// Relative betweenness above which a node is drawn bold.
+const HIGH_CENTRALITY_THRESHOLD: f64 = 0.5;
+
+/// Render a dependency graph as a Graphviz DOT document.
+///
+/// Node labels are paths relative to `project_root`. Edge `penwidth` is the
+/// number of imports between two files, nodes in import cycles are red, and
+/// nodes whose betweenness exceeds half that of the most central file are bold.
+pub fn to_dot(graph: &DependencyGraph, project_root: &Path) -> String {
+    let label = |index: NodeIndex| relative_label(&graph.graph[index], project_root);
+
+    let cyclic: HashSet<NodeIndex> = graph
+        .detect_cycles()
+        .iter()
+        .flatten()
+        .filter_map(|id| graph.get_node(id))
+        .collect();
+    let betweenness = relative_betweenness(&graph.graph);
+
+    let nodes: BTreeMap<String, NodeIndex> = graph
+        .graph
+        .node_indices()
+        .map(|index| (label(index), index))
+        .collect();
+
+    let mut edges: BTreeMap<(String, String), usize> = BTreeMap::new();
+    for edge in graph.graph.edge_references() {
+        *edges
+            .entry((label(edge.source()), label(edge.target())))
+            .or_insert(0) += 1;
+    }
+
+    let mut dot = String::from("digraph dependencies {\n");
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str("    node [shape=box, fontname=\"Helvetica\"];\n");
+
+    for (name, index) in &nodes {
+        let mut attributes = vec![format!("label={}", quote(name))];
+        if cyclic.contains(index) {
+            attributes.push("color=\"red\"".to_string());
+            attributes.push("fontcolor=\"red\"".to_string());
+        }
+        if betweenness[index.index()] > HIGH_CENTRALITY_THRESHOLD {
+            attributes.push("style=\"bold\"".to_string());
+        }
+        let _ = writeln!(dot, "    {} [{}];", quote(name), attributes.join(", "));
+    }
+
+    for ((from, to), imports) in &edges {
+        let _ = writeln!(
+            dot,
+            "    {} -> {} [penwidth={}, weight={}];",
+            quote(from),
+            quote(to),
+            imports,
+            imports
+        );
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Express a node identifier relative to the project root when possible.
+fn relative_label(id: &str, project_root: &Path) -> String {
+    Path::new(id)
+        .strip_prefix(project_root)
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| id.to_string())
+}
+
+/// Quote a string as a DOT identifier.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Brandes betweenness centrality scaled so the most central node scores 1.0.
+///
+/// Scaling by the maximum rather than by `(n-1)(n-2)` keeps the threshold
+/// meaningful on directed import graphs, where absolute normalized scores rarely
+/// exceed 0.5. Parallel edges are collapsed so repeated imports do not inflate
+/// path counts.
+fn relative_betweenness(graph: &petgraph::Graph<String, (), petgraph::Directed>) -> Vec<f64> {
+    let node_count = graph.node_count();
+    let mut centrality = vec![0.0; node_count];
+    if node_count < 3 {
+        return centrality;
+    }
+
+    let successors: Vec<BTreeSet<usize>> = graph
+        .node_indices()
+        .map(|index| {
+            graph
+                .neighbors(index)
+                .map(|n| n.index())
+                .filter(|&n| n != index.index())
+                .collect()
+        })
+        .collect();
+
+    for source in 0..node_count {
+        let mut stack = Vec::with_capacity(node_count);
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+        let mut paths = vec![0.0_f64; node_count];
+        let mut distance = vec![usize::MAX; node_count];
+        paths[source] = 1.0;
+        distance[source] = 0;
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            stack.push(node);
+            for &next in &successors[node] {
+                if distance[next] == usize::MAX {
+                    distance[next] = distance[node] + 1;
+                    queue.push_back(next);
+                }
+                if distance[next] == distance[node] + 1 {
+                    paths[next] += paths[node];
+                    predecessors[next].push(node);
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0_f64; node_count];
+        while let Some(node) = stack.pop() {
+            for &previous in &predecessors[node] {
+                dependency[previous] += paths[previous] / paths[node] * (1.0 + dependency[node]);
+            }
+            if node != source {
+                centrality[node] += dependency[node];
+            }
+        }
+    }
+
+    let max = centrality.iter().copied().fold(0.0_f64, f64::max);
+    if max > 0.0 {
+        centrality.iter_mut().for_each(|value| *value /= max);
+    }
+    centrality
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal recursive-descent validator for the DOT language subset we emit:
+    /// `digraph ID { (attr_stmt | node_stmt | edge_stmt | ID = ID) ; ... }`.
+    fn validate_dot(input: &str) -> Result<(), String> {
+        let tokens = tokenize(input)?;
+        let mut parser = DotParser { tokens, pos: 0 };
+        parser.graph()
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Token {
+        Id(String),
+        Symbol(&'static str),
+    }
+
+    fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+        let mut tokens = Vec::new();
+        let mut chars = input.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            match c {
+                c if c.is_whitespace() => {
+                    chars.next();
+                }
+                '{' | '}' | '[' | ']' | ';' | ',' | '=' => {
+                    chars.next();
+                    tokens.push(Token::Symbol(match c {
+                        '{' => "{",
+                        '}' => "}",
+                        '[' => "[",
+                        ']' => "]",
+                        ';' => ";",
+                        ',' => ",",
+                        _ => "=",
+                    }));
+                }
+                '-' => {
+                    chars.next();
+                    if chars.next() != Some('>') {
+                        return Err("expected '->'".into());
+                    }
+                    tokens.push(Token::Symbol("->"));
+                }
+                '"' => {
+                    chars.next();
+                    let mut value = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('\\') => value.push(chars.next().ok_or("dangling escape")?),
+                            Some('"') => break,
+                            Some(other) => value.push(other),
+                            None => return Err("unterminated string".into()),
+                        }
+                    }
+                    tokens.push(Token::Id(value));
+                }
+                c if c.is_ascii_alphanumeric() || c == '_' || c == '.' => {
+                    let mut value = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+                            value.push(c);
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    tokens.push(Token::Id(value));
+                }
+                other => return Err(format!("unexpected character {other:?}")),
+            }
+        }
+        Ok(tokens)
+    }
+
+    struct DotParser {
+        tokens: Vec<Token>,
+        pos: usize,
+    }
+
+    impl DotParser {
+        fn peek(&self) -> Option<&Token> {
+            self.tokens.get(self.pos)
+        }
+
+        fn expect_symbol(&mut self, symbol: &str) -> Result<(), String> {
+            match self.tokens.get(self.pos) {
+                Some(Token::Symbol(s)) if *s == symbol => {
+                    self.pos += 1;
+                    Ok(())
+                }
+                other => Err(format!("expected {symbol}, found {other:?}")),
+            }
+        }
+
+        fn id(&mut self) -> Result<String, String> {
+            match self.tokens.get(self.pos) {
+                Some(Token::Id(value)) => {
+                    self.pos += 1;
+                    Ok(value.clone())
+                }
+                other => Err(format!("expected identifier, found {other:?}")),
+            }
+        }
+
+        fn graph(&mut self) -> Result<(), String> {
+            if self.id()? != "digraph" {
+                return Err("expected digraph".into());
+            }
+            self.id()?;
+            self.expect_symbol("{")?;
+            while self.peek() != Some(&Token::Symbol("}")) {
+                self.statement()?;
+                self.expect_symbol(";")?;
+            }
+            self.expect_symbol("}")?;
+            if self.pos != self.tokens.len() {
+                return Err("trailing tokens after graph".into());
+            }
+            Ok(())
+        }
+
+        fn statement(&mut self) -> Result<(), String> {
+            self.id()?;
+            match self.peek() {
+                Some(Token::Symbol("=")) => {
+                    self.pos += 1;
+                    self.id().map(|_| ())
+                }
+                Some(Token::Symbol("->")) => {
+                    self.pos += 1;
+                    self.id()?;
+                    self.attributes()
+                }
+                _ => self.attributes(),
+            }
+        }
+
+        fn attributes(&mut self) -> Result<(), String> {
+            if self.peek() != Some(&Token::Symbol("[")) {
+                return Ok(());
+            }
+            self.pos += 1;
+            loop {
+                self.id()?;
+                self.expect_symbol("=")?;
+                self.id()?;
+                match self.peek() {
+                    Some(Token::Symbol(",")) => self.pos += 1,
+                    _ => break,
+                }
+            }
+            self.expect_symbol("]")
+        }
+    }
+
+    fn sample_graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency("/repo/src/app.py", "/repo/src/service.py", 1.0);
+        graph.add_dependency("/repo/src/service.py", "/repo/src/db.py", 1.0);
+        graph.add_dependency("/repo/src/service.py", "/repo/src/db.py", 1.0);
+        graph.add_dependency("/repo/src/db.py", "/repo/src/models.py", 1.0);
+        graph.add_dependency("/repo/src/models.py", "/repo/src/db.py", 1.0);
+        graph
+    }
+
+    #[test]
+    fn to_dot_emits_valid_deterministic_graph() {
+        let dot = to_dot(&sample_graph(), Path::new("/repo"));
+
+        validate_dot(&dot).unwrap_or_else(|err| panic!("invalid DOT ({err}):\n{dot}"));
+        assert_eq!(dot, to_dot(&sample_graph(), Path::new("/repo")));
+
+        let app = dot.find("\"src/app.py\" [").unwrap();
+        let db = dot.find("\"src/db.py\" [").unwrap();
+        let service = dot.find("\"src/service.py\" [").unwrap();
+        assert!(app < db && db < service, "nodes should be sorted by path");
+        assert!(!dot.contains("/repo"), "labels should be relative");
+    }
+
+    #[test]
+    fn to_dot_encodes_imports_cycles_and_centrality() {
+        let dot = to_dot(&sample_graph(), Path::new("/repo"));
+
+        assert!(dot.contains("\"src/service.py\" -> \"src/db.py\" [penwidth=2, weight=2];"));
+        assert!(dot.contains("\"src/app.py\" -> \"src/service.py\" [penwidth=1, weight=1];"));
+
+        let node_line = |name: &str| {
+            dot.lines()
+                .find(|line| line.trim_start().starts_with(&format!("\"{name}\" [")))
+                .unwrap()
+                .to_string()
+        };
+        assert!(node_line("src/db.py").contains("color=\"red\""));
+        assert!(node_line("src/models.py").contains("color=\"red\""));
+        assert!(!node_line("src/app.py").contains("color=\"red\""));
+
+        // service and db relay every multi-hop path; app and models relay none.
+        assert!(node_line("src/db.py").contains("style=\"bold\""));
+        assert!(!node_line("src/app.py").contains("style=\"bold\""));
+    }
+}
@@ -0,0 +1,127 @@
+//! Hub file detection for [`DependencyGraph`].
+//!
+//! A hub is a file imported by many others; changes to it ripple out to every
+//! dependent, so hubs are flagged as change-risk hot spots.
+
+use std::path::PathBuf;
+
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use super::DependencyGraph;
+
+/// Dependent count above which the pipeline reports a file as a hub.
+pub const DEFAULT_HUB_THRESHOLD: usize = 10;
+
+/// A file imported by more dependents than the hub threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HubFile {
+    /// Path of the hub file
+    pub path: PathBuf,
+
+    /// Number of distinct files that depend on it
+    pub dependent_count: usize,
+
+    /// Share of the graph's files that depend on it (0.0-1.0)
+    pub risk_score: f64,
+}
+
+/// Files with more than `threshold` distinct dependents, most depended-on first.
+///
+/// Self-imports are not counted. Ties are ordered by path so the result is
+/// deterministic.
+pub fn hub_files(graph: &DependencyGraph, threshold: usize) -> Vec<HubFile> {
+    let total_files = graph.graph.node_count();
+    if total_files == 0 {
+        return Vec::new();
+    }
+
+    let mut hubs: Vec<HubFile> = graph
+        .graph
+        .node_indices()
+        .filter_map(|index| {
+            let mut dependents: Vec<_> = graph
+                .graph
+                .neighbors_directed(index, Direction::Incoming)
+                .filter(|&dependent| dependent != index)
+                .collect();
+            dependents.sort_unstable();
+            dependents.dedup();
+
+            let dependent_count = dependents.len();
+            (dependent_count > threshold).then(|| HubFile {
+                path: PathBuf::from(&graph.graph[index]),
+                dependent_count,
+                risk_score: (dependent_count as f64 / total_files as f64).clamp(0.0, 1.0),
+            })
+        })
+        .collect();
+
+    hubs.sort_by(|a, b| {
+        b.dependent_count
+            .cmp(&a.dependent_count)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    hubs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hub_files_reports_files_above_threshold() {
+        let mut graph = DependencyGraph::new();
+        for dependent in ["a.py", "b.py", "c.py"] {
+            graph.add_dependency(dependent, "util.py", 1.0);
+        }
+        graph.add_dependency("a.py", "util.py", 1.0);
+        graph.add_dependency("a.py", "models.py", 1.0);
+        graph.add_dependency("b.py", "models.py", 1.0);
+
+        let hubs = hub_files(&graph, 1);
+        assert_eq!(hubs.len(), 2);
+        assert_eq!(hubs[0].path, PathBuf::from("util.py"));
+        assert_eq!(hubs[0].dependent_count, 3);
+        assert!((hubs[0].risk_score - 3.0 / 5.0).abs() < 1e-9);
+        assert_eq!(hubs[1].path, PathBuf::from("models.py"));
+
+        assert_eq!(hub_files(&graph, 3), Vec::new());
+    }
+
+    #[test]
+    fn hub_files_counts_module_graph_edges_once_per_dependent() {
+        use crate::core::dependency::{ModuleGraph, ModuleGraphEdge, ModuleGraphNode};
+
+        let node = |path: &str| ModuleGraphNode {
+            id: path.to_string(),
+            path: PathBuf::from(path),
+            functions: 1,
+            fan_in: 0,
+            fan_out: 0,
+            chokepoint_score: 0.0,
+            in_cycle: false,
+        };
+        let edge = |source, target, weight| ModuleGraphEdge {
+            source,
+            target,
+            weight,
+        };
+        let module_graph = ModuleGraph {
+            nodes: vec![node("/p/core.rs"), node("/p/a.rs"), node("/p/b.rs")],
+            edges: vec![edge(1, 0, 4), edge(2, 0, 1)],
+        };
+
+        let hubs = hub_files(&DependencyGraph::from_module_graph(&module_graph), 1);
+        assert_eq!(hubs.len(), 1);
+        assert_eq!(hubs[0].path, PathBuf::from("/p/core.rs"));
+        assert_eq!(hubs[0].dependent_count, 2);
+    }
+
+    #[test]
+    fn hub_files_ignores_self_imports() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency("loop.py", "loop.py", 1.0);
+        assert!(hub_files(&graph, 0).is_empty());
+    }
+}
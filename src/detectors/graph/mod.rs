@@ -4,12 +4,19 @@
 //! - [`GraphExtractor`], a feature extractor that surfaces dependency metrics for
 //!   individual code entities.
 //! - [`DependencyGraph`], a lightweight helper that can be used in tests and tools to
-//!   construct and inspect dependency structures programmatically.
+//!   construct and inspect dependency structures programmatically, and export them
+//!   to Graphviz with [`to_dot`] or break into [`strongly_connected_components`].
 
 pub mod clique;
 pub mod config;
+pub mod dot;
+pub mod hubs;
+pub mod scc;
 pub use clique::{CliquePartitions, SimilarityCliquePartitioner};
 pub use config::GraphConfig;
+pub use dot::to_dot;
+pub use hubs::{hub_files, HubFile, DEFAULT_HUB_THRESHOLD};
+pub use scc::{strongly_connected_components, Scc};
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -21,7 +28,8 @@ use once_cell::sync::Lazy;
 use tracing::debug;
 
 use crate::core::dependency::{
-    canonicalize_path, DependencyMetrics as DepMetrics, EntityKey, ProjectDependencyAnalysis,
+    canonicalize_path, DependencyMetrics as DepMetrics, EntityKey, ModuleGraph,
+    ProjectDependencyAnalysis,
 };
 use crate::core::errors::Result;
 use crate::core::featureset::{CodeEntity, ExtractionContext, FeatureDefinition, FeatureExtractor};
@@ -190,6 +198,28 @@ impl DependencyGraph {
         }
     }
 
+    /// Build a file-level graph from a module graph, keyed by file path.
+    pub fn from_module_graph(module_graph: &ModuleGraph) -> Self {
+        let mut graph = Self::new();
+        for node in &module_graph.nodes {
+            graph.get_or_add_node(&node.path.to_string_lossy());
+        }
+        for edge in &module_graph.edges {
+            let (Some(source), Some(target)) = (
+                module_graph.nodes.get(edge.source),
+                module_graph.nodes.get(edge.target),
+            ) else {
+                continue;
+            };
+            graph.add_dependency(
+                &source.path.to_string_lossy(),
+                &target.path.to_string_lossy(),
+                edge.weight as f64,
+            );
+        }
+        graph
+    }
+
     /// Add a dependency edge (`from` -> `to`).
     pub fn add_dependency(&mut self, from: &str, to: &str, _weight: f64) {
         let from_index = self.get_or_add_node(from);
@@ -0,0 +1,131 @@
+//! Strongly connected components of a [`DependencyGraph`].
+//!
+//! Every file in a multi-file component can reach every other one through
+//! imports, so such components are import cycles; their internal edge density
+//! shows how tightly the cycle is knotted.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use petgraph::algo::tarjan_scc;
+use serde::{Deserialize, Serialize};
+
+use super::DependencyGraph;
+
+/// A strongly connected component of the file dependency graph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scc {
+    /// Files in the component, sorted by path
+    pub members: Vec<PathBuf>,
+
+    /// Number of files in the component
+    pub size: usize,
+
+    /// Distinct member-to-member edges over the `size × (size - 1)` possible
+    /// (0.0-1.0, 0.0 for single files)
+    pub internal_coupling: f64,
+}
+
+/// Factory and query methods for [`Scc`].
+impl Scc {
+    /// Whether the component is an import cycle spanning several files.
+    pub fn is_cycle(&self) -> bool {
+        self.size > 1
+    }
+}
+
+/// All strongly connected components of `graph`, largest first.
+///
+/// Single files form their own component; only components with `size > 1`
+/// are import cycles. Ties are ordered by coupling, then by first member path,
+/// so the result is deterministic.
+pub fn strongly_connected_components(graph: &DependencyGraph) -> Vec<Scc> {
+    let mut components: Vec<Scc> = tarjan_scc(&graph.graph)
+        .into_iter()
+        .map(|component| {
+            let size = component.len();
+            let member_set: HashSet<_> = component.iter().copied().collect();
+            let mut internal_edges = HashSet::new();
+            for &source in &component {
+                for target in graph.graph.neighbors(source) {
+                    if target != source && member_set.contains(&target) {
+                        internal_edges.insert((source, target));
+                    }
+                }
+            }
+
+            let possible_edges = size * size.saturating_sub(1);
+            let internal_coupling = if possible_edges == 0 {
+                0.0
+            } else {
+                internal_edges.len() as f64 / possible_edges as f64
+            };
+
+            let mut members: Vec<PathBuf> = component
+                .iter()
+                .map(|&index| PathBuf::from(&graph.graph[index]))
+                .collect();
+            members.sort();
+
+            Scc {
+                members,
+                size,
+                internal_coupling,
+            }
+        })
+        .collect();
+
+    components.sort_by(|a, b| {
+        b.size
+            .cmp(&a.size)
+            .then_with(|| b.internal_coupling.total_cmp(&a.internal_coupling))
+            .then_with(|| a.members.cmp(&b.members))
+    });
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strongly_connected_components_reports_cycles_with_coupling() {
+        let mut graph = DependencyGraph::new();
+        graph.add_dependency("a.py", "b.py", 1.0);
+        graph.add_dependency("b.py", "c.py", 1.0);
+        graph.add_dependency("c.py", "a.py", 1.0);
+        graph.add_dependency("c.py", "a.py", 1.0);
+        graph.add_dependency("a.py", "util.py", 1.0);
+        graph.add_dependency("x.py", "y.py", 1.0);
+        graph.add_dependency("y.py", "x.py", 1.0);
+
+        let components = strongly_connected_components(&graph);
+        let cycles: Vec<&Scc> = components.iter().filter(|scc| scc.is_cycle()).collect();
+
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(
+            cycles[0].members,
+            vec![
+                PathBuf::from("a.py"),
+                PathBuf::from("b.py"),
+                PathBuf::from("c.py")
+            ]
+        );
+        assert_eq!(cycles[0].size, 3);
+        assert!((cycles[0].internal_coupling - 3.0 / 6.0).abs() < 1e-9);
+        assert_eq!(cycles[1].size, 2);
+        assert_eq!(cycles[1].internal_coupling, 1.0);
+
+        let util = components
+            .iter()
+            .find(|scc| scc.members == vec![PathBuf::from("util.py")])
+            .unwrap();
+        assert!(!util.is_cycle());
+        assert_eq!(util.internal_coupling, 0.0);
+    }
+
+    #[test]
+    fn strongly_connected_components_of_empty_graph_is_empty() {
+        assert!(strongly_connected_components(&DependencyGraph::new()).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+//! Adaptive fragment thresholds driven by [`AdaptiveDenoiseConfig`].
+//!
+//! The fixed [`DedupeConfig`](super::DedupeConfig) thresholds can filter out
+//! most of a codebase made of small functions. The adaptive threshold starts
+//! at `min_denoise_threshold` and, while too many of the measured entities
+//! would be filtered, relaxes the fixed thresholds by another `step_size`,
+//! never going past `max_denoise_threshold`.
+
+use std::sync::Mutex;
+
+use super::config::AdaptiveDenoiseConfig;
+
+/// Filtered fraction at or above which the threshold keeps relaxing.
+pub const ADAPTIVE_TARGET_FILTERED_RATIO: f64 = 0.5;
+
+/// Relaxation factor `k` that scales fragment thresholds by `1 - k`.
+#[derive(Debug)]
+pub struct AdaptiveThreshold {
+    min: f64,
+    max: f64,
+    step: f64,
+    /// Adjustments made by the last [`settle`](AdaptiveThreshold::settle)
+    steps: Mutex<usize>,
+}
+
+/// Adjustment methods for [`AdaptiveThreshold`].
+impl AdaptiveThreshold {
+    /// Build the controller, or `None` when the configured range is empty and
+    /// the fixed thresholds apply unchanged.
+    pub fn from_config(config: &AdaptiveDenoiseConfig) -> Option<Self> {
+        (config.max_denoise_threshold > config.min_denoise_threshold && config.step_size > 0.0)
+            .then(|| Self {
+                min: config.min_denoise_threshold,
+                max: config.max_denoise_threshold,
+                step: config.step_size,
+                steps: Mutex::new(0),
+            })
+    }
+
+    /// Upper bound on the number of adjustments, `ceil((max - min) / step)`.
+    pub fn max_steps(&self) -> usize {
+        ((self.max - self.min) / self.step).ceil() as usize
+    }
+
+    /// Largest relaxation factor the threshold can reach.
+    pub fn max_relaxation(&self) -> f64 {
+        self.max
+    }
+
+    /// Current relaxation factor `k`.
+    pub fn current(&self) -> f64 {
+        let steps = *self.lock();
+        self.value_at(steps)
+    }
+
+    /// Relax from `min_denoise_threshold` one step at a time while
+    /// `filtered_ratio_at(k)` is at or above [`ADAPTIVE_TARGET_FILTERED_RATIO`],
+    /// and keep the settled factor for [`current`](Self::current).
+    ///
+    /// The ratio should cover the whole entity set, so the settled factor does
+    /// not depend on the order entities were measured in.
+    pub fn settle(&self, filtered_ratio_at: impl Fn(f64) -> f64) -> f64 {
+        let max_steps = self.max_steps();
+        let mut steps = 0;
+        while steps < max_steps
+            && filtered_ratio_at(self.value_at(steps)) >= ADAPTIVE_TARGET_FILTERED_RATIO
+        {
+            steps += 1;
+        }
+        *self.lock() = steps;
+        self.value_at(steps)
+    }
+
+    /// Scale a fixed threshold down by the current relaxation factor.
+    pub fn relax(threshold: usize, k: f64) -> usize {
+        (threshold as f64 * (1.0 - k)).ceil() as usize
+    }
+
+    fn value_at(&self, steps: usize) -> f64 {
+        (self.min + steps as f64 * self.step).min(self.max)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, usize> {
+        self.steps
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(min: f64, max: f64, step: f64) -> AdaptiveDenoiseConfig {
+        AdaptiveDenoiseConfig {
+            min_denoise_threshold: min,
+            max_denoise_threshold: max,
+            step_size: step,
+            ..AdaptiveDenoiseConfig::default()
+        }
+    }
+
+    #[test]
+    fn empty_range_disables_adaptation() {
+        assert!(AdaptiveThreshold::from_config(&config(0.0, 0.0, 0.1)).is_none());
+        assert!(AdaptiveThreshold::from_config(&config(0.0, 0.5, 0.0)).is_none());
+    }
+
+    #[test]
+    fn relaxes_while_filtered_ratio_stays_above_target() {
+        let threshold = AdaptiveThreshold::from_config(&config(0.1, 0.5, 0.2)).unwrap();
+        assert_eq!(threshold.max_steps(), 2);
+        assert!((threshold.current() - 0.1).abs() < 1e-9);
+
+        // Half the entities pass once the factor reaches 0.3.
+        let settled = threshold.settle(|k| if k < 0.2 { 0.9 } else { 0.25 });
+        assert!((settled - 0.3).abs() < 1e-9);
+        assert!((threshold.current() - 0.3).abs() < 1e-9);
+
+        // Settling again starts from the minimum rather than the last factor.
+        let settled = threshold.settle(|_| 0.25);
+        assert!((settled - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn converges_within_max_steps() {
+        let threshold = AdaptiveThreshold::from_config(&config(0.0, 0.45, 0.1)).unwrap();
+        let settled = threshold.settle(|_| 1.0);
+        assert_eq!(*threshold.lock(), threshold.max_steps());
+        assert!((settled - 0.45).abs() < 1e-9);
+        assert_eq!(AdaptiveThreshold::relax(40, 0.45), 22);
+    }
+}
@@ -17,6 +17,7 @@ use crate::core::errors::Result;
 use crate::core::featureset::CodeEntity;
 use crate::lang::common::{EntityKind, ParseIndex};
 
+use super::adaptive::AdaptiveThreshold;
 use super::config::DedupeConfig;
 use super::signatures::shingles::count_tokens;
 
@@ -31,6 +32,33 @@ pub struct EntityAstStats {
     pub has_stop_motif: bool,
 }
 
+/// Measurements an entity is compared against the fragment thresholds with.
+#[derive(Debug, Clone)]
+pub struct FragmentMeasurements {
+    /// Number of tokens in the entity source
+    pub token_count: usize,
+    /// AST statistics, `None` when they were skipped or the entity node was not found
+    pub stats: Option<EntityAstStats>,
+}
+
+/// Threshold checks for [`FragmentMeasurements`].
+impl FragmentMeasurements {
+    /// Whether the measurements meet `config` with every size threshold scaled
+    /// by `1 - relaxation`.
+    pub fn meets(&self, config: &DedupeConfig, relaxation: f64) -> bool {
+        if self.token_count < AdaptiveThreshold::relax(config.min_function_tokens, relaxation) {
+            return false;
+        }
+        let Some(stats) = &self.stats else {
+            return false;
+        };
+        stats.node_count >= AdaptiveThreshold::relax(config.min_ast_nodes, relaxation)
+            && stats.block_count
+                >= AdaptiveThreshold::relax(config.require_distinct_blocks, relaxation)
+            && !stats.has_stop_motif
+    }
+}
+
 /// AST analyzer for computing entity statistics and detecting stop motifs.
 #[derive(Debug)]
 pub struct AstAnalyzer {
@@ -148,36 +176,38 @@ impl AstAnalyzer {
         }
     }
 
+    /// Measure an entity for the fragment thresholds.
+    ///
+    /// AST statistics are skipped when the token count fails the token
+    /// threshold even at `max_relaxation`, the most it can be relaxed.
+    pub async fn measure_fragment(
+        &self,
+        entity: &CodeEntity,
+        config: &DedupeConfig,
+        max_relaxation: f64,
+    ) -> Result<FragmentMeasurements> {
+        let token_count = count_tokens(&entity.source_code);
+        let stats =
+            if token_count < AdaptiveThreshold::relax(config.min_function_tokens, max_relaxation) {
+                None
+            } else {
+                self.compute_entity_ast_stats(entity).await?
+            };
+        Ok(FragmentMeasurements { token_count, stats })
+    }
+
     /// Check if entity meets fragment analysis thresholds using structural data.
+    ///
+    /// `relaxation` scales every size threshold by `1 - relaxation`; pass 0.0
+    /// to apply the configured thresholds as-is.
     pub async fn meets_fragment_thresholds(
         &self,
         entity: &CodeEntity,
         config: &DedupeConfig,
+        relaxation: f64,
     ) -> Result<bool> {
-        let source_code = &entity.source_code;
-
-        let token_count = count_tokens(source_code);
-        if token_count < config.min_function_tokens {
-            return Ok(false);
-        }
-
-        let Some(stats) = self.compute_entity_ast_stats(entity).await? else {
-            return Ok(false);
-        };
-
-        if stats.node_count < config.min_ast_nodes {
-            return Ok(false);
-        }
-
-        if stats.block_count < config.require_distinct_blocks {
-            return Ok(false);
-        }
-
-        if stats.has_stop_motif {
-            return Ok(false);
-        }
-
-        Ok(true)
+        let measurements = self.measure_fragment(entity, config, relaxation).await?;
+        Ok(measurements.meets(config, relaxation))
     }
 }
 
@@ -0,0 +1,222 @@
+//! Clone clusters built from reported clone pairs.
+//!
+//! Clone pairs link entities two at a time; a cluster is a connected component
+//! of those links. Clusters let downstream consumers treat every member of a
+//! duplicated routine as one refactoring opportunity instead of N.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::core::pipeline::discovery::code_dictionary::suggestion_code_for_kind;
+use crate::core::pipeline::{RefactoringCandidate, RefactoringSuggestion};
+
+/// Suggestion kind attached to a merged clone-cluster candidate.
+const SHARED_HELPER_SUGGESTION: &str = "eliminate_duplication_extract_shared_helper";
+
+/// Connected group of entities linked by reported clone pairs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CloneCluster {
+    /// Entity ids of every cluster member
+    pub members: BTreeSet<String>,
+    /// Files holding at least one member
+    pub files: BTreeSet<String>,
+    /// Reported clone pairs, each ordered `(smaller id, larger id)`
+    pub pairs: BTreeSet<(String, String)>,
+}
+
+/// Query methods for [`CloneCluster`].
+impl CloneCluster {
+    /// Fraction of member pairs that were reported as clones of each other.
+    pub fn density(&self) -> f64 {
+        let n = self.members.len();
+        if n < 2 {
+            return 0.0;
+        }
+        self.pairs.len() as f64 / (n * (n - 1) / 2) as f64
+    }
+}
+
+/// Group clone pairs into connected clusters.
+///
+/// Each pair is a JSON object with `source` and `target` endpoints carrying
+/// `id` and `path`; malformed pairs and self-pairs are skipped.
+pub fn clone_clusters(clone_pairs: &[serde_json::Value]) -> Vec<CloneCluster> {
+    let mut parent: BTreeMap<String, String> = BTreeMap::new();
+    let mut edges = Vec::new();
+    for pair in clone_pairs {
+        let endpoint = |side: &str| {
+            let value = pair.get(side)?;
+            Some((
+                value.get("id")?.as_str()?.to_string(),
+                value.get("path")?.as_str()?.to_string(),
+            ))
+        };
+        let (Some(source), Some(target)) = (endpoint("source"), endpoint("target")) else {
+            continue;
+        };
+        if source.0 == target.0 {
+            continue;
+        }
+        parent
+            .entry(source.0.clone())
+            .or_insert_with(|| source.0.clone());
+        parent
+            .entry(target.0.clone())
+            .or_insert_with(|| target.0.clone());
+        let root_a = find_root(&mut parent, &source.0);
+        let root_b = find_root(&mut parent, &target.0);
+        if root_a != root_b {
+            parent.insert(root_b, root_a);
+        }
+        edges.push((source, target));
+    }
+
+    let mut clusters: BTreeMap<String, CloneCluster> = BTreeMap::new();
+    for ((source_id, source_path), (target_id, target_path)) in edges {
+        let root = find_root(&mut parent, &source_id);
+        let cluster = clusters.entry(root).or_default();
+        let pair = if source_id <= target_id {
+            (source_id.clone(), target_id.clone())
+        } else {
+            (target_id.clone(), source_id.clone())
+        };
+        cluster.pairs.insert(pair);
+        cluster.members.insert(source_id);
+        cluster.members.insert(target_id);
+        cluster.files.insert(source_path);
+        cluster.files.insert(target_path);
+    }
+
+    clusters.into_values().collect()
+}
+
+/// Number of members in the clone cluster containing each `(source, target)` pair.
+pub(crate) fn cluster_sizes(pairs: &[(&str, &str)]) -> Vec<usize> {
+    let mut parent: BTreeMap<String, String> = BTreeMap::new();
+    for &(source, target) in pairs {
+        parent
+            .entry(source.to_string())
+            .or_insert_with(|| source.to_string());
+        parent
+            .entry(target.to_string())
+            .or_insert_with(|| target.to_string());
+        let root_a = find_root(&mut parent, source);
+        let root_b = find_root(&mut parent, target);
+        if root_a != root_b {
+            parent.insert(root_b, root_a);
+        }
+    }
+
+    let ids: Vec<String> = parent.keys().cloned().collect();
+    let mut sizes: HashMap<String, usize> = HashMap::new();
+    for id in &ids {
+        *sizes.entry(find_root(&mut parent, id)).or_insert(0) += 1;
+    }
+    pairs
+        .iter()
+        .map(|&(source, _)| sizes[&find_root(&mut parent, source)])
+        .collect()
+}
+
+/// Replace the candidates of each clone cluster with a single merged candidate.
+///
+/// The merged candidate takes the place of the first member in `candidates`,
+/// keeps the highest score and priority of its members, lists every cluster
+/// file in its name, and recommends extracting a shared helper. Clusters with
+/// fewer than two member candidates leave `candidates` unchanged.
+pub fn deduplicate_results(
+    candidates: Vec<RefactoringCandidate>,
+    clusters: &[CloneCluster],
+) -> Vec<RefactoringCandidate> {
+    let cluster_of: HashMap<&str, usize> = clusters
+        .iter()
+        .enumerate()
+        .flat_map(|(index, cluster)| cluster.members.iter().map(move |id| (id.as_str(), index)))
+        .collect();
+
+    let mut member_counts = vec![0usize; clusters.len()];
+    for candidate in &candidates {
+        if let Some(&index) = cluster_of.get(candidate.entity_id.as_str()) {
+            member_counts[index] += 1;
+        }
+    }
+
+    let mut merged: Vec<Option<RefactoringCandidate>> = Vec::with_capacity(candidates.len());
+    let mut merged_slot: HashMap<usize, usize> = HashMap::new();
+    for candidate in candidates {
+        let cluster_index = cluster_of
+            .get(candidate.entity_id.as_str())
+            .copied()
+            .filter(|&index| member_counts[index] > 1);
+        let Some(cluster_index) = cluster_index else {
+            merged.push(Some(candidate));
+            continue;
+        };
+
+        match merged_slot.get(&cluster_index) {
+            Some(&slot) => {
+                if let Some(existing) = merged[slot].as_mut() {
+                    absorb_clone(existing, candidate);
+                }
+            }
+            None => {
+                merged_slot.insert(cluster_index, merged.len());
+                merged.push(Some(candidate));
+            }
+        }
+    }
+
+    for (cluster_index, slot) in merged_slot {
+        if let Some(candidate) = merged[slot].as_mut() {
+            describe_cluster(candidate, &clusters[cluster_index]);
+        }
+    }
+
+    merged.into_iter().flatten().collect()
+}
+
+/// Fold another cluster member into the merged candidate.
+fn absorb_clone(merged: &mut RefactoringCandidate, member: RefactoringCandidate) {
+    if member.score > merged.score {
+        merged.score = member.score;
+    }
+    merged.priority = merged.priority.max(member.priority);
+    merged.confidence = merged.confidence.max(member.confidence);
+}
+
+/// Rename the merged candidate after its cluster and attach the shared-helper suggestion.
+fn describe_cluster(candidate: &mut RefactoringCandidate, cluster: &CloneCluster) {
+    let files: Vec<&str> = cluster.files.iter().map(String::as_str).collect();
+    candidate.name = format!(
+        "{} (+{} clones in {})",
+        candidate.name,
+        cluster.members.len() - 1,
+        files.join(", ")
+    );
+    candidate.suggestions.insert(
+        0,
+        RefactoringSuggestion {
+            refactoring_type: SHARED_HELPER_SUGGESTION.to_string(),
+            code: suggestion_code_for_kind(SHARED_HELPER_SUGGESTION),
+            priority: 1.0,
+            effort: 0.5,
+            impact: 1.0,
+        },
+    );
+    candidate.suggestion_count = candidate.suggestions.len();
+}
+
+/// Resolves the union-find root for `id`, compressing the path along the way.
+fn find_root(parent: &mut BTreeMap<String, String>, id: &str) -> String {
+    let mut root = id.to_string();
+    while let Some(next) = parent.get(&root).filter(|next| **next != root) {
+        root = next.clone();
+    }
+    let mut current = id.to_string();
+    while current != root {
+        let next = parent
+            .insert(current, root.clone())
+            .unwrap_or_else(|| root.clone());
+        current = next;
+    }
+    root
+}
@@ -4,6 +4,7 @@
 //! and weighted Jaccard similarity.
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 
 use tracing::debug;
 
@@ -16,12 +17,20 @@ use super::config::LshConfig;
 use super::signatures::weighted::{WeightedMinHashSignature, WeightedShingleAnalyzer};
 use super::similarity_context::LshSimilarityContext;
 
+/// MinHash similarities already computed, keyed by [`pair_key`].
+pub type KnownPairSimilarities = HashMap<(EntityId, EntityId), f64>;
+
+/// Upper bound on remembered pair similarities before the map is cleared.
+pub const MAX_KNOWN_PAIRS: usize = 250_000;
+
 /// Similarity comparator for LSH-based clone detection.
 pub struct SimilarityComparator<'a> {
     /// LSH configuration
     lsh_config: &'a LshConfig,
     /// Weighted shingle analyzer (if enabled)
     weighted_analyzer: Option<&'a WeightedShingleAnalyzer>,
+    /// Brute-force similarities shared across the entities this comparator sees
+    known_pairs: Mutex<KnownPairSimilarities>,
 }
 
 /// Factory and similarity comparison methods for [`SimilarityComparator`].
@@ -34,6 +43,7 @@ impl<'a> SimilarityComparator<'a> {
         Self {
             lsh_config,
             weighted_analyzer,
+            known_pairs: Mutex::new(HashMap::new()),
         }
     }
 
@@ -264,6 +274,7 @@ impl<'a> SimilarityComparator<'a> {
             candidate_filter,
             max_candidates,
             self.lsh_config.similarity_threshold,
+            &self.known_pairs,
             generate_signature_fn,
         )
     }
@@ -323,6 +334,13 @@ pub fn collect_weighted_similarities(
 
 /// Fallback to basic minhash similarity comparison using a closure for signature generation.
 ///
+/// Pairs already present in `known_pairs` reuse the stored similarity instead
+/// of regenerating the candidate signature, so when every entity of a clone
+/// cluster is compared in turn each pair is only computed once. The lock is
+/// only held to look up known pairs and to record the newly computed ones, so
+/// parallel callers never wait on each other's signature generation. The map
+/// is cleared once it would grow past [`MAX_KNOWN_PAIRS`].
+///
 /// This is a shared utility function used by both `SimilarityComparator` and `LshExtractor`.
 pub fn fallback_minhash_comparison<F>(
     entity: &CodeEntity,
@@ -331,19 +349,67 @@ pub fn fallback_minhash_comparison<F>(
     candidate_filter: Option<&Vec<EntityId>>,
     max_candidates: usize,
     threshold: f64,
+    known_pairs: &Mutex<KnownPairSimilarities>,
     generate_signature_fn: F,
 ) -> Vec<f64>
 where
     F: Fn(&str, &str) -> Vec<u64>,
 {
-    iterate_candidates(context, candidate_filter, &entity.id, max_candidates)
-        .filter_map(|other_id| {
-            let other_entity = context.entity_index.get(other_id)?;
-            let other_signature = generate_signature_fn(&other_entity.source_code, other_id);
-            let similarity = jaccard_similarity(signature, &other_signature);
+    let candidates: Vec<(&EntityId, (EntityId, EntityId))> =
+        iterate_candidates(context, candidate_filter, &entity.id, max_candidates)
+            .map(|other_id| (other_id, pair_key(&entity.id, other_id)))
+            .collect();
+
+    let known: Vec<Option<f64>> = {
+        let guard = known_pairs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        candidates
+            .iter()
+            .map(|(_, key)| guard.get(key).copied())
+            .collect()
+    };
+
+    let mut computed = Vec::new();
+    let similarities: Vec<f64> = candidates
+        .into_iter()
+        .zip(known)
+        .filter_map(|((other_id, key), known)| {
+            let similarity = match known {
+                Some(similarity) => similarity,
+                None => {
+                    let other_entity = context.entity_index.get(other_id)?;
+                    let other_signature =
+                        generate_signature_fn(&other_entity.source_code, other_id);
+                    let similarity = jaccard_similarity(signature, &other_signature);
+                    computed.push((key, similarity));
+                    similarity
+                }
+            };
             (similarity >= threshold).then_some(similarity)
         })
-        .collect()
+        .collect();
+
+    if !computed.is_empty() {
+        let mut guard = known_pairs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if guard.len() + computed.len() > MAX_KNOWN_PAIRS {
+            guard.clear();
+        }
+        guard.extend(computed);
+    }
+
+    similarities
+}
+
+/// Order an entity pair so `(a, b)` and `(b, a)` share one key.
+pub fn pair_key(a: &str, b: &str) -> (EntityId, EntityId) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
 }
 
 /// Calculate Jaccard similarity between two MinHash signatures.
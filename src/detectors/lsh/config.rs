@@ -13,12 +13,19 @@ use crate::core::errors::{Result, ValknutError};
 pub use crate::core::config::{DedupeWeights, DenoiseWeights, SimilarityWeights};
 
 /// LSH and similarity detection configuration
+///
+/// `num_hashes` must be divisible by `num_bands` so every band covers the same
+/// number of hashes. [`validate`](Self::validate) rejects other values, and
+/// [`LshExtractor::from_config`](crate::detectors::lsh::LshExtractor::from_config)
+/// rounds `num_bands` down to the nearest divisor.
+///
+/// Build custom configurations with [`LshConfig::builder`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LshConfig {
-    /// Number of hash functions per band
+    /// Total number of MinHash functions, split evenly across the bands
     pub num_hashes: usize,
 
-    /// Number of LSH bands
+    /// Number of LSH bands; must divide `num_hashes`
     pub num_bands: usize,
 
     /// Shingle size for text similarity
@@ -32,6 +39,10 @@ pub struct LshConfig {
 
     /// Use advanced similarity algorithms
     pub use_semantic_similarity: bool,
+
+    /// Drop repeated shingles before hashing
+    #[serde(default)]
+    pub deduplicate_shingles: bool,
 }
 
 /// Default implementation for [`LshConfig`].
@@ -45,6 +56,7 @@ impl Default for LshConfig {
             similarity_threshold: 0.7,
             max_candidates: 100,
             use_semantic_similarity: false,
+            deduplicate_shingles: false,
         }
     }
 }
@@ -60,6 +72,7 @@ impl From<crate::core::config::LshConfig> for LshConfig {
             similarity_threshold: value.similarity_threshold,
             max_candidates: value.max_candidates,
             use_semantic_similarity: value.use_semantic_similarity,
+            deduplicate_shingles: value.deduplicate_shingles,
         }
     }
 }
@@ -78,6 +91,12 @@ impl LshConfig {
             return Err(ValknutError::validation("num_bands must be greater than 0"));
         }
 
+        if self.num_bands > self.num_hashes {
+            return Err(ValknutError::validation(
+                "num_bands must not exceed num_hashes",
+            ));
+        }
+
         if self.num_hashes % self.num_bands != 0 {
             return Err(ValknutError::validation(
                 "num_hashes must be divisible by num_bands",
@@ -85,6 +104,11 @@ impl LshConfig {
         }
 
         validate_unit_range(self.similarity_threshold, "similarity_threshold")?;
+        if self.similarity_threshold <= 0.0 {
+            return Err(ValknutError::validation(
+                "similarity_threshold must be greater than 0.0",
+            ));
+        }
 
         Ok(())
     }
@@ -95,6 +119,67 @@ impl LshConfig {
     pub fn hashes_per_band(&self) -> usize {
         self.num_hashes / self.num_bands
     }
+
+    /// Start building a configuration from the defaults.
+    pub fn builder() -> LshConfigBuilder {
+        LshConfigBuilder::default()
+    }
+}
+
+/// Builder for [`LshConfig`]; unset fields keep their default values.
+#[derive(Debug, Clone, Default)]
+pub struct LshConfigBuilder {
+    config: LshConfig,
+}
+
+/// Fluent setter methods for [`LshConfigBuilder`].
+impl LshConfigBuilder {
+    /// Set the total number of MinHash functions
+    pub fn num_hashes(mut self, num_hashes: usize) -> Self {
+        self.config.num_hashes = num_hashes;
+        self
+    }
+
+    /// Set the number of LSH bands; must divide `num_hashes`
+    pub fn num_bands(mut self, num_bands: usize) -> Self {
+        self.config.num_bands = num_bands;
+        self
+    }
+
+    /// Set the shingle size for text similarity
+    pub fn shingle_size(mut self, shingle_size: usize) -> Self {
+        self.config.shingle_size = shingle_size;
+        self
+    }
+
+    /// Set the minimum Jaccard similarity threshold
+    pub fn similarity_threshold(mut self, similarity_threshold: f64) -> Self {
+        self.config.similarity_threshold = similarity_threshold;
+        self
+    }
+
+    /// Set the maximum candidates considered per query
+    pub fn max_candidates(mut self, max_candidates: usize) -> Self {
+        self.config.max_candidates = max_candidates;
+        self
+    }
+
+    /// Enable or disable advanced similarity algorithms
+    pub fn use_semantic_similarity(mut self, use_semantic_similarity: bool) -> Self {
+        self.config.use_semantic_similarity = use_semantic_similarity;
+        self
+    }
+
+    /// Enable or disable dropping repeated shingles before hashing
+    pub fn deduplicate_shingles(mut self, deduplicate_shingles: bool) -> Self {
+        self.config.deduplicate_shingles = deduplicate_shingles;
+        self
+    }
+
+    /// Build the final configuration
+    pub fn build(self) -> LshConfig {
+        self.config
+    }
 }
 
 /// Enhanced duplicate detection configuration with adaptive features
@@ -148,6 +233,11 @@ pub struct DedupeConfig {
     /// Adaptive denoising configuration
     #[serde(default)]
     pub adaptive: AdaptiveDenoiseConfig,
+
+    /// Drop import lines before shingling so shared import headers do not
+    /// make unrelated files look similar
+    #[serde(default)]
+    pub strip_imports: bool,
 }
 
 /// Clone denoising configuration for reducing noise in clone detection
@@ -194,6 +284,9 @@ pub struct DenoiseConfig {
 }
 
 /// Stop motifs configuration for AST-based boilerplate filtering
+///
+/// Mirrors the core [`StopMotifsConfig`](crate::core::config::StopMotifsConfig),
+/// which documents how mined motifs relate to `shingle_k`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StopMotifsConfig {
     /// Enable stop motifs filtering
@@ -261,7 +354,7 @@ pub struct RankingConfig {
 }
 
 /// Ranking criteria options
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RankingBy {
     /// Rank by potential token savings
@@ -269,6 +362,26 @@ pub enum RankingBy {
 
     /// Rank by frequency/occurrence count
     Frequency,
+
+    /// Rank by TF-IDF weighted MinHash similarity, so pairs that mostly share
+    /// common boilerplate fall below pairs sharing rare structure
+    WeightedSimilarity,
+}
+
+/// Conversion from core config [`RankingConfig`](crate::core::config::RankingConfig).
+impl From<crate::core::config::RankingConfig> for RankingConfig {
+    /// Converts from the core config ranking settings.
+    fn from(value: crate::core::config::RankingConfig) -> Self {
+        Self {
+            by: match value.by {
+                crate::core::config::RankingBy::SavedTokens => RankingBy::SavedTokens,
+                crate::core::config::RankingBy::Frequency => RankingBy::Frequency,
+                crate::core::config::RankingBy::WeightedSimilarity => RankingBy::WeightedSimilarity,
+            },
+            min_saved_tokens: value.min_saved_tokens,
+            min_rarity_gain: value.min_rarity_gain,
+        }
+    }
 }
 
 /// Default implementation for [`RankingConfig`].
@@ -426,6 +539,24 @@ pub struct AdaptiveDenoiseConfig {
 
     /// Enable automatic cache refresh
     pub auto_refresh_cache: bool,
+
+    /// Starting relaxation of the fragment thresholds (0.0 keeps them fixed)
+    #[serde(default)]
+    pub min_denoise_threshold: f64,
+
+    /// Largest relaxation the adaptive thresholds may reach; equal to the
+    /// minimum disables adaptation
+    #[serde(default)]
+    pub max_denoise_threshold: f64,
+
+    /// Relaxation added per adjustment
+    #[serde(default = "default_denoise_step_size")]
+    pub step_size: f64,
+}
+
+/// Default relaxation step for [`AdaptiveDenoiseConfig::step_size`].
+fn default_denoise_step_size() -> f64 {
+    0.1
 }
 
 /// Default implementation for [`AdaptiveDenoiseConfig`].
@@ -446,6 +577,9 @@ impl Default for AdaptiveDenoiseConfig {
             external_call_jaccard_threshold: 0.2,
             cache_refresh_days: 7,
             auto_refresh_cache: true,
+            min_denoise_threshold: 0.0,
+            max_denoise_threshold: 0.0,
+            step_size: default_denoise_step_size(),
         }
     }
 }
@@ -484,12 +618,19 @@ impl Default for DedupeConfig {
             min_saved_tokens: 100,
             keep_top_per_file: 3,
             adaptive: AdaptiveDenoiseConfig::default(),
+            strip_imports: false,
         }
     }
 }
 
-/// Validation for [`DedupeConfig`].
+/// Validation and query methods for [`DedupeConfig`].
 impl DedupeConfig {
+    /// Whether boilerplate filtering is configured, either through explicit
+    /// `stop_phrases` or adaptive learning of boilerplate patterns
+    pub fn has_stop_motifs(&self) -> bool {
+        !self.stop_phrases.is_empty() || self.adaptive.adaptive_learning
+    }
+
     /// Validate dedupe configuration
     pub fn validate(&self) -> Result<()> {
         self.validate_basic_params()?;
@@ -501,10 +642,12 @@ impl DedupeConfig {
     }
 
     fn validate_basic_params(&self) -> Result<()> {
-        validate_positive(self.min_function_tokens, "min_function_tokens")?;
-        validate_positive(self.min_ast_nodes, "min_ast_nodes")?;
+        // Lower bounds below which every fragment would trivially pass the thresholds
+        validate_at_least(self.min_function_tokens, 5, "min_function_tokens")?;
+        validate_at_least(self.min_ast_nodes, 3, "min_ast_nodes")?;
         validate_positive(self.min_match_tokens, "min_match_tokens")?;
-        validate_positive(self.shingle_k, "shingle_k")?;
+        validate_at_least(self.require_distinct_blocks, 1, "require_distinct_blocks")?;
+        validate_at_least(self.shingle_k, 2, "shingle_k")?;
         Ok(())
     }
 
@@ -547,6 +690,17 @@ fn validate_positive(value: usize, name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate that a value meets a minimum bound.
+fn validate_at_least(value: usize, minimum: usize, name: &str) -> Result<()> {
+    if value < minimum {
+        return Err(ValknutError::validation(format!(
+            "{} must be at least {} (got {})",
+            name, minimum, value
+        )));
+    }
+    Ok(())
+}
+
 /// Validation for [`AdaptiveDenoiseConfig`].
 impl AdaptiveDenoiseConfig {
     fn validate(&self) -> Result<()> {
@@ -564,6 +718,23 @@ impl AdaptiveDenoiseConfig {
             "adaptive.external_call_jaccard_threshold",
         )?;
         self.validate_bounded_params()?;
+        self.validate_denoise_range()?;
+        Ok(())
+    }
+
+    fn validate_denoise_range(&self) -> Result<()> {
+        validate_unit_range(self.min_denoise_threshold, "adaptive.min_denoise_threshold")?;
+        validate_unit_range(self.max_denoise_threshold, "adaptive.max_denoise_threshold")?;
+        if self.max_denoise_threshold < self.min_denoise_threshold {
+            return Err(ValknutError::validation(
+                "adaptive.max_denoise_threshold must not be below adaptive.min_denoise_threshold",
+            ));
+        }
+        if self.max_denoise_threshold > self.min_denoise_threshold && self.step_size <= 0.0 {
+            return Err(ValknutError::validation(
+                "adaptive.step_size must be greater than 0.0",
+            ));
+        }
         Ok(())
     }
 
@@ -63,6 +63,20 @@ fn lsh_config_validation_and_conversion() {
         .unwrap_err()
         .to_string()
         .contains("similarity_threshold"));
+    cfg.similarity_threshold = 0.0;
+    assert!(cfg
+        .validate()
+        .unwrap_err()
+        .to_string()
+        .contains("similarity_threshold"));
+    cfg.similarity_threshold = 0.5;
+    cfg.num_hashes = 4;
+    cfg.num_bands = 8;
+    assert!(cfg
+        .validate()
+        .unwrap_err()
+        .to_string()
+        .contains("must not exceed num_hashes"));
 }
 
 #[test]
@@ -115,6 +129,13 @@ fn dedupe_config_validation_rules() {
     expect_dedupe_error(|cfg| cfg.min_match_tokens = 0, "min_match_tokens");
     expect_dedupe_error(|cfg| cfg.min_match_coverage = 1.5, "min_match_coverage");
     expect_dedupe_error(|cfg| cfg.shingle_k = 0, "shingle_k");
+    expect_dedupe_error(|cfg| cfg.min_function_tokens = 4, "min_function_tokens");
+    expect_dedupe_error(|cfg| cfg.min_ast_nodes = 2, "min_ast_nodes");
+    expect_dedupe_error(
+        |cfg| cfg.require_distinct_blocks = 0,
+        "require_distinct_blocks",
+    );
+    expect_dedupe_error(|cfg| cfg.shingle_k = 1, "shingle_k");
     expect_dedupe_error(|cfg| cfg.io_mismatch_penalty = -0.1, "io_mismatch_penalty");
     expect_dedupe_error(|cfg| cfg.threshold_s = 2.0, "threshold_s");
     expect_dedupe_error(
@@ -149,4 +170,18 @@ fn dedupe_config_validation_rules() {
         |cfg| cfg.adaptive.cache_refresh_days = 0,
         "cache_refresh_days",
     );
+    expect_dedupe_error(
+        |cfg| {
+            cfg.adaptive.min_denoise_threshold = 0.4;
+            cfg.adaptive.max_denoise_threshold = 0.2;
+        },
+        "max_denoise_threshold",
+    );
+    expect_dedupe_error(
+        |cfg| {
+            cfg.adaptive.max_denoise_threshold = 0.5;
+            cfg.adaptive.step_size = 0.0;
+        },
+        "step_size",
+    );
 }
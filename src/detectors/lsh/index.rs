@@ -3,9 +3,11 @@
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
-use ahash::AHasher;
+use ahash::{AHashMap, AHasher};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::signatures::types::MinHashSignature;
+use crate::core::interning::{InternedString, StringInterner};
 
 /// LSH index for efficient similarity search
 #[derive(Debug)]
@@ -13,26 +15,115 @@ pub struct LshIndex {
     /// Number of bands for LSH
     num_bands: usize,
 
-    /// Hash tables for each band
-    bands: Vec<HashMap<u64, Vec<String>>>,
+    /// Hash tables for each band. Keys are already well-mixed band hashes, so
+    /// aHash is used instead of the slower default SipHash.
+    bands: Vec<AHashMap<u64, Vec<InternedString>>>,
 
     /// Stored signatures
+    signatures: AHashMap<InternedString, MinHashSignature>,
+
+    /// Owns the entity ids referenced by `bands` and `signatures`, so each id
+    /// is stored once and freed together with the index
+    entity_ids: StringInterner,
+}
+
+/// Owned form of [`LshIndex`] used for serialization, with entity ids resolved
+/// to strings.
+#[derive(Serialize, Deserialize)]
+struct LshIndexSnapshot {
+    num_bands: usize,
+    bands: Vec<HashMap<u64, Vec<String>>>,
     signatures: HashMap<String, MinHashSignature>,
 }
 
+/// Serializes the index through an [`LshIndexSnapshot`].
+impl Serialize for LshIndex {
+    /// Resolves interned ids and serializes the bands and signatures.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let resolve = |id: &InternedString| self.entity_ids.resolve(*id).to_string();
+        LshIndexSnapshot {
+            num_bands: self.num_bands,
+            bands: self
+                .bands
+                .iter()
+                .map(|band| {
+                    band.iter()
+                        .map(|(hash, ids)| (*hash, ids.iter().map(resolve).collect()))
+                        .collect()
+                })
+                .collect(),
+            signatures: self
+                .signatures
+                .iter()
+                .map(|(id, signature)| (resolve(id), signature.clone()))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes the index from an [`LshIndexSnapshot`].
+impl<'de> Deserialize<'de> for LshIndex {
+    /// Re-interns entity ids and restores the bands and signatures as written,
+    /// rejecting snapshots without bands or whose band count disagrees with
+    /// `num_bands`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let snapshot = LshIndexSnapshot::deserialize(deserializer)?;
+        if snapshot.num_bands == 0 {
+            return Err(serde::de::Error::custom(
+                "LSH index must have at least one band",
+            ));
+        }
+        if snapshot.bands.len() != snapshot.num_bands {
+            return Err(serde::de::Error::custom(format!(
+                "LSH index has {} band tables but num_bands is {}",
+                snapshot.bands.len(),
+                snapshot.num_bands
+            )));
+        }
+        let entity_ids = StringInterner::new();
+        let bands = snapshot
+            .bands
+            .into_iter()
+            .map(|band| {
+                band.into_iter()
+                    .map(|(hash, ids)| {
+                        let ids = ids.iter().map(|id| entity_ids.get_or_intern(id)).collect();
+                        (hash, ids)
+                    })
+                    .collect()
+            })
+            .collect();
+        let signatures = snapshot
+            .signatures
+            .into_iter()
+            .map(|(id, signature)| (entity_ids.get_or_intern(id), signature))
+            .collect();
+
+        Ok(Self {
+            num_bands: snapshot.num_bands,
+            bands,
+            signatures,
+            entity_ids,
+        })
+    }
+}
+
 /// Factory, indexing, and query methods for [`LshIndex`].
 impl LshIndex {
     /// Create a new LSH index
     pub fn new(num_bands: usize) -> Self {
         Self {
             num_bands,
-            bands: vec![HashMap::with_capacity(32); num_bands], // Estimate 32 entities per band
-            signatures: HashMap::with_capacity(256),            // Estimate 256 total entities
+            bands: vec![AHashMap::with_capacity(32); num_bands], // Estimate 32 entities per band
+            signatures: AHashMap::with_capacity(256),            // Estimate 256 total entities
+            entity_ids: StringInterner::new(),
         }
     }
 
     /// Add an entity to the index
-    pub fn add_entity(&mut self, entity_id: String, signature: MinHashSignature) {
+    pub fn add_entity(&mut self, entity_id: &str, signature: MinHashSignature) {
+        let entity_id = self.entity_ids.get_or_intern(entity_id);
         let hashes_per_band = signature.signature.len() / self.num_bands;
 
         // Calculate band hashes first
@@ -54,7 +145,7 @@ impl LshIndex {
             self.bands[band_idx]
                 .entry(band_hash)
                 .or_default()
-                .push(entity_id.clone());
+                .push(entity_id);
         }
 
         // Store the signature
@@ -63,13 +154,14 @@ impl LshIndex {
 
     /// Find candidate duplicates for an entity
     pub fn find_candidates(&self, entity_id: &str) -> Vec<(String, f64)> {
-        let signature = match self.signatures.get(entity_id) {
-            Some(sig) => sig,
-            None => return Vec::new(),
+        let Some((entity_id, signature)) = self.lookup(entity_id) else {
+            return Vec::new();
         };
 
-        let mut candidates = std::collections::HashSet::new();
+        // Number of bands each candidate collides with the query in
+        let mut band_collision_counts: AHashMap<InternedString, usize> = AHashMap::new();
         let hashes_per_band = signature.signature.len() / self.num_bands;
+        let mut scanned_bands = 0;
 
         // Find candidates from each band
         for (band_idx, band) in self.bands.iter().enumerate() {
@@ -77,26 +169,42 @@ impl LshIndex {
             let end_idx = (start_idx + hashes_per_band).min(signature.signature.len());
 
             if start_idx < signature.signature.len() {
+                scanned_bands += 1;
                 let band_signature = &signature.signature[start_idx..end_idx];
                 let band_hash = self.hash_band(band_signature);
 
                 if let Some(entities) = band.get(&band_hash) {
-                    for candidate_id in entities {
+                    for &candidate_id in entities {
                         if candidate_id != entity_id {
-                            candidates.insert(candidate_id.clone());
+                            *band_collision_counts.entry(candidate_id).or_insert(0) += 1;
                         }
                     }
                 }
             }
         }
 
-        // Calculate similarities for candidates
-        let mut results = Vec::with_capacity(candidates.len());
-        for candidate_id in candidates {
-            if let Some(candidate_sig) = self.signatures.get(&candidate_id) {
-                if let Some(similarity) = signature.jaccard_similarity(candidate_sig) {
-                    results.push((candidate_id, similarity));
-                }
+        // Calculate similarities for candidates. A pair colliding in every band
+        // is identical under the MinHash approximation, so skip the full comparison
+        // unless trailing hashes fall outside the bands and were never compared.
+        // Ids are resolved back to owned strings only for the returned pairs.
+        let bands_cover_signature = hashes_per_band * self.num_bands == signature.signature.len();
+        let mut results = Vec::with_capacity(band_collision_counts.len());
+        for (candidate_id, band_collision_count) in band_collision_counts {
+            let similarity = if bands_cover_signature
+                && band_collision_count == scanned_bands
+                && scanned_bands == self.num_bands
+            {
+                Some(1.0)
+            } else {
+                self.signatures
+                    .get(&candidate_id)
+                    .and_then(|candidate_sig| signature.jaccard_similarity(candidate_sig))
+            };
+            if let Some(similarity) = similarity {
+                results.push((
+                    self.entity_ids.resolve(candidate_id).to_string(),
+                    similarity,
+                ));
             }
         }
 
@@ -105,9 +213,29 @@ impl LshIndex {
         results
     }
 
+    /// Number of band hash tables in the index
+    pub fn num_band_tables(&self) -> usize {
+        self.bands.len()
+    }
+
+    /// Average number of buckets per band hash table
+    pub fn average_bucket_count(&self) -> f64 {
+        if self.bands.is_empty() {
+            return 0.0;
+        }
+        let total: usize = self.bands.iter().map(|band| band.len()).sum();
+        total as f64 / self.bands.len() as f64
+    }
+
     /// Get a reference to a stored signature
     pub fn get_signature(&self, entity_id: &str) -> Option<&MinHashSignature> {
-        self.signatures.get(entity_id)
+        self.lookup(entity_id).map(|(_, signature)| signature)
+    }
+
+    /// Resolve an entity id to its interned key and stored signature
+    fn lookup(&self, entity_id: &str) -> Option<(InternedString, &MinHashSignature)> {
+        let key = self.entity_ids.get(entity_id)?;
+        self.signatures.get(&key).map(|signature| (key, signature))
     }
 
     /// Hash a band signature
@@ -38,6 +38,13 @@ pub struct CacheStatistics {
     pub signature_misses: usize,
     /// Cache evictions performed
     pub evictions: usize,
+    /// Combined length of the cached token strings, filled in by
+    /// [`LshCache::get_statistics`]
+    pub token_bytes: usize,
+    /// Number of cached signatures
+    pub signature_count: usize,
+    /// Hash functions per cached signature, summed over all signatures
+    pub signature_hashes: usize,
 }
 
 /// Hit rate calculation methods for [`CacheStatistics`].
@@ -72,6 +79,12 @@ impl CacheStatistics {
             total_hits as f64 / total_requests as f64
         }
     }
+
+    /// Estimated bytes held by both caches: token string lengths plus
+    /// 8 bytes per signature hash
+    pub fn bytes_in_use_estimate(&self) -> usize {
+        self.token_bytes + self.signature_hashes * std::mem::size_of::<u64>()
+    }
 }
 
 /// Factory, caching, lookup, and eviction methods for [`LshCache`].
@@ -180,14 +193,23 @@ impl LshCache {
         }
     }
 
-    /// Get cache statistics
+    /// Get cache statistics, including the current size of each cache
     pub fn get_statistics(&self) -> CacheStatistics {
-        if let Ok(stats) = self.stats.read() {
+        let mut stats = if let Ok(stats) = self.stats.read() {
             stats.clone()
         } else {
             // If lock is poisoned, return default stats
             CacheStatistics::default()
+        };
+
+        if let Ok(cache) = self.token_cache.read() {
+            stats.token_bytes = cache.values().flatten().map(String::len).sum();
         }
+        if let Ok(cache) = self.signature_cache.read() {
+            stats.signature_count = cache.len();
+            stats.signature_hashes = cache.keys().map(|(_, num_hashes, _)| num_hashes).sum();
+        }
+        stats
     }
 
     /// Reset cache statistics
@@ -342,6 +364,23 @@ mod tests {
 
         let stats = cache.get_statistics();
         assert!(stats.overall_hit_rate() > 0.0);
+        assert_eq!(stats.token_hit_rate(), 0.5);
+        assert_eq!(stats.signature_hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_bytes_in_use_estimate_counts_tokens_and_signatures() {
+        let cache = LshCache::new();
+        assert_eq!(cache.get_statistics().bytes_in_use_estimate(), 0);
+
+        cache.cache_tokens("fn demo()", vec!["fn".into(), "demo".into()]);
+        cache.cache_signature("fn demo()", 8, 3, vec![0; 8]);
+        cache.cache_signature("fn other()", 4, 3, vec![0; 4]);
+
+        let stats = cache.get_statistics();
+        assert_eq!(stats.token_bytes, 6);
+        assert_eq!(stats.signature_count, 2);
+        assert_eq!(stats.bytes_in_use_estimate(), 6 + (8 + 4) * 8);
     }
 
     #[test]
@@ -3,17 +3,137 @@
 //! This module provides memory pools for frequently allocated objects
 //! to reduce GC pressure and improve performance in hot paths.
 
+#[cfg(feature = "parallel")]
+use crossbeam::queue::SegQueue;
+#[cfg(not(feature = "parallel"))]
 use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+#[cfg(not(feature = "parallel"))]
+use std::sync::Mutex;
 use tracing::debug;
 
+/// Shared queue of recycled vectors.
+///
+/// With the `parallel` feature this is a lock-free [`SegQueue`], so rayon workers
+/// never block each other when borrowing or returning buffers. Without it a
+/// mutex-guarded deque is sufficient.
+#[derive(Debug)]
+struct RecycleQueue<T> {
+    #[cfg(feature = "parallel")]
+    items: SegQueue<T>,
+    #[cfg(not(feature = "parallel"))]
+    items: Mutex<VecDeque<T>>,
+}
+
+/// Push, pop, and length operations for [`RecycleQueue`].
+impl<T> RecycleQueue<T> {
+    /// Create an empty queue sized for `capacity` items
+    #[cfg(feature = "parallel")]
+    fn with_capacity(_capacity: usize) -> Self {
+        Self {
+            items: SegQueue::new(),
+        }
+    }
+
+    /// Create an empty queue sized for `capacity` items
+    #[cfg(not(feature = "parallel"))]
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Take the oldest item, if any
+    #[cfg(feature = "parallel")]
+    fn pop(&self) -> Option<T> {
+        self.items.pop()
+    }
+
+    /// Take the oldest item, if any
+    #[cfg(not(feature = "parallel"))]
+    fn pop(&self) -> Option<T> {
+        self.items.lock().ok()?.pop_front()
+    }
+
+    /// Add an item unless the queue already holds `max_size` items
+    #[cfg(feature = "parallel")]
+    fn push_bounded(&self, item: T, max_size: usize) -> bool {
+        // The length check is racy, so the pool may briefly overshoot `max_size`
+        // by the number of concurrent returners; that is harmless.
+        if self.items.len() >= max_size {
+            return false;
+        }
+        self.items.push(item);
+        true
+    }
+
+    /// Add an item unless the queue already holds `max_size` items
+    #[cfg(not(feature = "parallel"))]
+    fn push_bounded(&self, item: T, max_size: usize) -> bool {
+        match self.items.lock() {
+            Ok(mut items) if items.len() < max_size => {
+                items.push_back(item);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Number of items currently queued
+    #[cfg(feature = "parallel")]
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Number of items currently queued
+    #[cfg(not(feature = "parallel"))]
+    fn len(&self) -> usize {
+        self.items.lock().map(|items| items.len()).unwrap_or(0)
+    }
+}
+
+/// Allocation counters shared by every clone of a pool
+#[derive(Debug, Default)]
+struct PoolCounters {
+    created: AtomicUsize,
+    reused: AtomicUsize,
+    contention_avoided: AtomicUsize,
+}
+
+/// Recording and snapshot methods for [`PoolCounters`].
+impl PoolCounters {
+    /// Record a vector served from the pool
+    fn record_reuse(&self) {
+        self.reused.fetch_add(1, Ordering::Relaxed);
+        if cfg!(feature = "parallel") {
+            self.contention_avoided.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record a freshly allocated vector
+    fn record_creation(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Build statistics from the current counter values
+    fn snapshot(&self, current_pool_size: usize, max_pool_size: usize) -> PoolStatistics {
+        PoolStatistics {
+            created_count: self.created.load(Ordering::Relaxed),
+            reused_count: self.reused.load(Ordering::Relaxed),
+            current_pool_size,
+            max_pool_size,
+            contention_avoided: self.contention_avoided.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// Memory pool for reusing `Vec<String>` allocations (for shingles)
 #[derive(Debug, Clone)]
 pub struct StringVecPool {
-    pool: Arc<Mutex<VecDeque<Vec<String>>>>,
+    pool: Arc<RecycleQueue<Vec<String>>>,
     max_size: usize,
-    created_count: Arc<Mutex<usize>>,
-    reused_count: Arc<Mutex<usize>>,
+    counters: Arc<PoolCounters>,
 }
 
 /// Factory, allocation, and statistics methods for [`StringVecPool`].
@@ -21,69 +141,49 @@ impl StringVecPool {
     /// Create a new string vector pool
     pub fn new(max_size: usize) -> Self {
         Self {
-            pool: Arc::new(Mutex::new(VecDeque::with_capacity(max_size))),
+            pool: Arc::new(RecycleQueue::with_capacity(max_size)),
             max_size,
-            created_count: Arc::new(Mutex::new(0)),
-            reused_count: Arc::new(Mutex::new(0)),
+            counters: Arc::new(PoolCounters::default()),
         }
     }
 
     /// Get a `Vec<String>` from the pool or create a new one
     pub fn get(&self) -> Vec<String> {
-        if let Ok(mut pool) = self.pool.lock() {
-            if let Some(mut vec) = pool.pop_front() {
-                vec.clear(); // Clear but keep capacity
-                if let Ok(mut count) = self.reused_count.lock() {
-                    *count += 1;
-                }
-                debug!("Reused String vector from pool");
-                return vec;
-            }
+        if let Some(mut vec) = self.pool.pop() {
+            vec.clear(); // Clear but keep capacity
+            self.counters.record_reuse();
+            debug!("Reused String vector from pool");
+            return vec;
         }
 
         // Create new vector if pool is empty
-        if let Ok(mut count) = self.created_count.lock() {
-            *count += 1;
-        }
+        self.counters.record_creation();
         debug!("Created new String vector");
         Vec::new()
     }
 
     /// Return a `Vec<String>` to the pool
     pub fn return_vec(&self, vec: Vec<String>) {
-        if let Ok(mut pool) = self.pool.lock() {
-            if pool.len() < self.max_size {
-                pool.push_back(vec);
-                debug!("Returned String vector to pool");
-            } else {
-                debug!("Pool full, dropping String vector");
-            }
+        if self.pool.push_bounded(vec, self.max_size) {
+            debug!("Returned String vector to pool");
+        } else {
+            debug!("Pool full, dropping String vector");
         }
     }
 
     /// Get pool statistics
     pub fn get_statistics(&self) -> PoolStatistics {
-        let created = self.created_count.lock().map(|c| *c).unwrap_or(0);
-        let reused = self.reused_count.lock().map(|c| *c).unwrap_or(0);
-        let pool_size = self.pool.lock().map(|p| p.len()).unwrap_or(0);
-
-        PoolStatistics {
-            created_count: created,
-            reused_count: reused,
-            current_pool_size: pool_size,
-            max_pool_size: self.max_size,
-        }
+        self.counters.snapshot(self.pool.len(), self.max_size)
     }
 }
 
 /// Memory pool for reusing `Vec<u64>` allocations (for signatures)
 #[derive(Debug, Clone)]
 pub struct U64VecPool {
-    pool: Arc<Mutex<VecDeque<Vec<u64>>>>,
+    pool: Arc<RecycleQueue<Vec<u64>>>,
     max_size: usize,
     signature_size: usize,
-    created_count: Arc<Mutex<usize>>,
-    reused_count: Arc<Mutex<usize>>,
+    counters: Arc<PoolCounters>,
 }
 
 /// Factory, allocation, and statistics methods for [`U64VecPool`].
@@ -91,63 +191,44 @@ impl U64VecPool {
     /// Create a new u64 vector pool
     pub fn new(max_size: usize, signature_size: usize) -> Self {
         Self {
-            pool: Arc::new(Mutex::new(VecDeque::with_capacity(max_size))),
+            pool: Arc::new(RecycleQueue::with_capacity(max_size)),
             max_size,
             signature_size,
-            created_count: Arc::new(Mutex::new(0)),
-            reused_count: Arc::new(Mutex::new(0)),
+            counters: Arc::new(PoolCounters::default()),
         }
     }
 
     /// Get a `Vec<u64>` from the pool or create a new one
     pub fn get(&self) -> Vec<u64> {
-        if let Ok(mut pool) = self.pool.lock() {
-            if let Some(mut vec) = pool.pop_front() {
-                vec.clear();
-                vec.resize(self.signature_size, u64::MAX); // Pre-fill with MAX values
-                if let Ok(mut count) = self.reused_count.lock() {
-                    *count += 1;
-                }
-                debug!("Reused u64 vector from pool");
-                return vec;
-            }
+        if let Some(mut vec) = self.pool.pop() {
+            vec.clear();
+            vec.resize(self.signature_size, u64::MAX); // Pre-fill with MAX values
+            self.counters.record_reuse();
+            debug!("Reused u64 vector from pool");
+            return vec;
         }
 
         // Create new vector if pool is empty
         let mut vec = Vec::with_capacity(self.signature_size);
         vec.resize(self.signature_size, u64::MAX);
 
-        if let Ok(mut count) = self.created_count.lock() {
-            *count += 1;
-        }
+        self.counters.record_creation();
         debug!("Created new u64 vector");
         vec
     }
 
     /// Return a `Vec<u64>` to the pool
     pub fn return_vec(&self, vec: Vec<u64>) {
-        if let Ok(mut pool) = self.pool.lock() {
-            if pool.len() < self.max_size && vec.capacity() >= self.signature_size {
-                pool.push_back(vec);
-                debug!("Returned u64 vector to pool");
-            } else {
-                debug!("Pool full or wrong size, dropping u64 vector");
-            }
+        if vec.capacity() >= self.signature_size && self.pool.push_bounded(vec, self.max_size) {
+            debug!("Returned u64 vector to pool");
+        } else {
+            debug!("Pool full or wrong size, dropping u64 vector");
         }
     }
 
     /// Get pool statistics
     pub fn get_statistics(&self) -> PoolStatistics {
-        let created = self.created_count.lock().map(|c| *c).unwrap_or(0);
-        let reused = self.reused_count.lock().map(|c| *c).unwrap_or(0);
-        let pool_size = self.pool.lock().map(|p| p.len()).unwrap_or(0);
-
-        PoolStatistics {
-            created_count: created,
-            reused_count: reused,
-            current_pool_size: pool_size,
-            max_pool_size: self.max_size,
-        }
+        self.counters.snapshot(self.pool.len(), self.max_size)
     }
 }
 
@@ -158,6 +239,8 @@ pub struct PoolStatistics {
     pub reused_count: usize,
     pub current_pool_size: usize,
     pub max_pool_size: usize,
+    /// Reuses served by a non-blocking pop that would otherwise have taken a lock
+    pub contention_avoided: usize,
 }
 
 /// Analysis methods for [`PoolStatistics`].
@@ -183,7 +266,10 @@ impl PoolStatistics {
 }
 
 /// Combined memory pools for LSH operations
-#[derive(Debug, Clone)]
+///
+/// Cloning creates fresh, empty pools with the same capacities rather than
+/// sharing the queues, so each parallel worker can own an uncontended pool.
+#[derive(Debug)]
 pub struct LshMemoryPools {
     string_pool: StringVecPool,
     signature_pool: U64VecPool,
@@ -237,23 +323,36 @@ impl LshMemoryPools {
         let (string_stats, sig_stats) = self.get_statistics();
 
         debug!(
-            "String Pool Stats: created={}, reused={}, utilization={:.1}%, reuse_rate={:.1}%",
+            "String Pool Stats: created={}, reused={}, contention_avoided={}, utilization={:.1}%, reuse_rate={:.1}%",
             string_stats.created_count,
             string_stats.reused_count,
+            string_stats.contention_avoided,
             string_stats.utilization() * 100.0,
             string_stats.reuse_rate() * 100.0
         );
 
         debug!(
-            "Signature Pool Stats: created={}, reused={}, utilization={:.1}%, reuse_rate={:.1}%",
+            "Signature Pool Stats: created={}, reused={}, contention_avoided={}, utilization={:.1}%, reuse_rate={:.1}%",
             sig_stats.created_count,
             sig_stats.reused_count,
+            sig_stats.contention_avoided,
             sig_stats.utilization() * 100.0,
             sig_stats.reuse_rate() * 100.0
         );
     }
 }
 
+/// Independent-pool [`Clone`] implementation for [`LshMemoryPools`].
+impl Clone for LshMemoryPools {
+    /// Returns empty pools sized like `self`; pooled vectors and statistics are not shared.
+    fn clone(&self) -> Self {
+        Self::with_capacity(
+            self.string_pool.max_size,
+            self.signature_pool.signature_size,
+        )
+    }
+}
+
 /// Default implementation for [`LshMemoryPools`].
 impl Default for LshMemoryPools {
     /// Returns memory pools with default sizes.
@@ -291,6 +390,67 @@ mod tests {
         assert_eq!(stats.reuse_rate(), 0.5);
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_pools_recycle_across_rayon_workers() {
+        use rayon::prelude::*;
+
+        let pools = LshMemoryPools::with_capacity(16, 8);
+        (0..256).into_par_iter().for_each(|i| {
+            let mut sig = pools.get_signature_vec();
+            sig[0] = i;
+            pools.return_signature_vec(sig);
+            let strings = pools.get_string_vec();
+            pools.return_string_vec(strings);
+        });
+
+        let (string_stats, sig_stats) = pools.get_statistics();
+        assert_eq!(string_stats.created_count + string_stats.reused_count, 256);
+        assert_eq!(sig_stats.created_count + sig_stats.reused_count, 256);
+        assert_eq!(sig_stats.contention_avoided, sig_stats.reused_count);
+        assert!(sig_stats.reused_count > 0);
+    }
+
+    #[test]
+    fn test_cloned_pools_are_independent_across_threads() {
+        let pools = LshMemoryPools::with_capacity(4, 16);
+        let mut sig = pools.get_signature_vec();
+        sig[0] = 7;
+        pools.return_signature_vec(sig);
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let local = pools.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        let mut sig = local.get_signature_vec();
+                        assert_eq!(sig.len(), 16);
+                        sig[0] = i;
+                        local.return_signature_vec(sig);
+                        let mut strings = local.get_string_vec();
+                        strings.push(i.to_string());
+                        local.return_string_vec(strings);
+                    }
+                    local.get_statistics()
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let (string_stats, sig_stats) = handle.join().unwrap();
+            assert_eq!(sig_stats.created_count, 1);
+            assert_eq!(sig_stats.reused_count, 99);
+            assert_eq!(sig_stats.max_pool_size, 4);
+            assert_eq!(string_stats.created_count, 1);
+            assert_eq!(string_stats.reused_count, 99);
+        }
+
+        let (_, sig_stats) = pools.get_statistics();
+        assert_eq!(sig_stats.created_count, 1);
+        assert_eq!(sig_stats.reused_count, 0);
+        assert_eq!(sig_stats.current_pool_size, 1);
+    }
+
     #[test]
     fn test_u64_vec_pool() {
         let pool = U64VecPool::new(3, 64);
@@ -1,9 +1,14 @@
 //! LSH performance metrics and statistics.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use tracing::info;
 
 /// Performance metrics for LSH operations
-#[derive(Debug, Default, Clone)]
+///
+/// Cache counters are atomic so signature generation can record them through
+/// the shared `&self` references the extractor is used with.
+#[derive(Debug, Default)]
 pub struct LshPerformanceMetrics {
     /// Time spent generating MinHash signatures
     pub signature_generation_time: std::time::Duration,
@@ -15,10 +20,26 @@ pub struct LshPerformanceMetrics {
     pub entities_processed: usize,
     /// Number of similarity comparisons performed
     pub comparisons_performed: usize,
-    /// Number of cache hits
-    pub cache_hits: usize,
-    /// Number of cache misses
-    pub cache_misses: usize,
+    /// Number of signature cache hits
+    pub cache_hits: AtomicUsize,
+    /// Number of signature cache misses
+    pub cache_misses: AtomicUsize,
+}
+
+/// Snapshot cloning for [`LshPerformanceMetrics`].
+impl Clone for LshPerformanceMetrics {
+    /// Copies the current counter values into a new metrics instance.
+    fn clone(&self) -> Self {
+        Self {
+            signature_generation_time: self.signature_generation_time,
+            comparison_time: self.comparison_time,
+            index_build_time: self.index_build_time,
+            entities_processed: self.entities_processed,
+            comparisons_performed: self.comparisons_performed,
+            cache_hits: AtomicUsize::new(self.cache_hits()),
+            cache_misses: AtomicUsize::new(self.cache_misses()),
+        }
+    }
 }
 
 /// Factory and logging methods for [`LshPerformanceMetrics`].
@@ -28,6 +49,26 @@ impl LshPerformanceMetrics {
         Self::default()
     }
 
+    /// Record a signature cache hit
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a signature cache miss
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of signature cache hits recorded so far
+    pub fn cache_hits(&self) -> usize {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of signature cache misses recorded so far
+    pub fn cache_misses(&self) -> usize {
+        self.cache_misses.load(Ordering::Relaxed)
+    }
+
     /// Log performance summary
     pub fn log_summary(&self) {
         info!("LSH Performance Summary:");
@@ -39,8 +80,9 @@ impl LshPerformanceMetrics {
         info!("  Index build time: {:?}", self.index_build_time);
         info!("  Entities processed: {}", self.entities_processed);
         info!("  Comparisons performed: {}", self.comparisons_performed);
-        if self.cache_hits + self.cache_misses > 0 {
-            let hit_rate = self.cache_hits as f64 / (self.cache_hits + self.cache_misses) as f64;
+        let (cache_hits, cache_misses) = (self.cache_hits(), self.cache_misses());
+        if cache_hits + cache_misses > 0 {
+            let hit_rate = cache_hits as f64 / (cache_hits + cache_misses) as f64;
             info!("  Cache hit rate: {:.2}%", hit_rate * 100.0);
         }
 
@@ -95,4 +137,14 @@ pub struct LshContextStatistics {
     pub num_bands: usize,
     pub num_hashes: usize,
     pub theoretical_complexity: String,
+    /// Estimated heap footprint of the stored signatures and band tables
+    pub estimated_memory_bytes: usize,
+}
+
+/// Convenience accessors for [`LshContextStatistics`].
+impl LshContextStatistics {
+    /// Estimated memory usage in mebibytes
+    pub fn estimated_memory_mb(&self) -> f64 {
+        self.estimated_memory_bytes as f64 / (1024.0 * 1024.0)
+    }
 }
@@ -3,15 +3,19 @@
 //! This module provides efficient duplicate code detection using MinHash signatures
 //! and LSH banding techniques for sub-linear similarity search.
 
+pub mod adaptive;
 pub mod ast_analysis;
+pub mod clusters;
 pub mod comparison;
 pub mod config;
 pub mod memory_pool;
+pub mod near_duplicates;
 pub mod signatures;
 
 pub use config::{
     AdaptiveDenoiseConfig, AutoCalibrationConfig, DedupeConfig, DedupeWeights, DenoiseConfig,
-    DenoiseWeights, LshConfig, RankingBy, RankingConfig, RankingCriteria, StopMotifsConfig,
+    DenoiseWeights, LshConfig, LshConfigBuilder, RankingBy, RankingConfig, RankingCriteria,
+    StopMotifsConfig,
 };
 
 mod index;
@@ -20,23 +24,28 @@ mod metrics;
 mod similarity_context;
 
 // Re-export submodule types
+pub use adaptive::AdaptiveThreshold;
 pub use ast_analysis::{
     count_ast_nodes_from_index, count_distinct_blocks_from_index, AstAnalyzer, EntityAstStats,
+    FragmentMeasurements,
 };
+pub use clusters::{clone_clusters, deduplicate_results, CloneCluster};
 pub use comparison::{
     collect_weighted_similarities, fallback_minhash_comparison, iterate_candidates,
-    jaccard_similarity, summarise_similarities, SimilarityComparator,
+    jaccard_similarity, pair_key, summarise_similarities, KnownPairSimilarities,
+    SimilarityComparator,
 };
 pub use index::LshIndex;
 pub use lsh_cache::{CacheStatistics, LshCache};
 pub use memory_pool::{LshMemoryPools, PoolStatistics};
 pub use metrics::{LshContextStatistics, LshPerformanceMetrics};
-pub use similarity_context::LshSimilarityContext;
+pub use near_duplicates::{ClonePair, CloneType};
+pub use similarity_context::{rank_by_novelty, LshSimilarityContext, LSH_CONTEXT_FORMAT_VERSION};
 
 // Re-export from signatures submodule
 pub use signatures::{
-    count_tokens, MinHashSignature, ShingleGenerator, SignatureGenerator, WeightedMinHashSignature,
-    WeightedShingleAnalyzer, WeightedShingleStats,
+    count_tokens, MinHashSignature, NormalizationOptions, ShingleGenerator, SignatureGenerator,
+    WeightedMinHashSignature, WeightedShingleAnalyzer, WeightedShingleStats, WeightedSignatureSet,
 };
 
 use std::collections::{HashMap, HashSet};
@@ -44,7 +53,8 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use rayon::prelude::*;
-use tracing::{debug, info};
+use tracing::{debug, debug_span, field, info, info_span, warn};
+use xxhash_rust::xxh3::xxh3_64;
 
 use crate::core::ast_service::AstService;
 use crate::core::errors::{Result, ValknutError};
@@ -52,6 +62,52 @@ use crate::core::featureset::{
     CodeEntity, EntityId, ExtractionContext, FeatureDefinition, FeatureExtractor,
 };
 use crate::core::interning::InternedString;
+#[cfg(feature = "persistent-cache")]
+use crate::io::cache::PersistentLshCache;
+
+/// Default minimum token count an entity needs before it is compared for similarity.
+///
+/// Empty or near-empty sources produce degenerate MinHash signatures that match
+/// each other perfectly, so they are excluded from comparison.
+pub const DEFAULT_MIN_TOKENS: usize = 5;
+
+/// Subdirectory of the analysis cache directory holding persisted MinHash signatures.
+#[cfg(feature = "persistent-cache")]
+const PERSISTENT_SIGNATURE_DIR: &str = "lsh_signatures";
+
+/// Largest divisor of `n` that does not exceed `max`, or 1 when there is none.
+fn largest_divisor_at_most(n: usize, max: usize) -> usize {
+    (1..=max.min(n))
+        .rev()
+        .find(|divisor| n % divisor == 0)
+        .unwrap_or(1)
+}
+
+/// Clone group size for each ranked pair.
+fn group_sizes(keys: &[(&str, &str, f64)]) -> Vec<usize> {
+    let pairs: Vec<(&str, &str)> = keys
+        .iter()
+        .map(|&(source, target, _)| (source, target))
+        .collect();
+    clusters::cluster_sizes(&pairs)
+}
+
+/// Estimated tokens saved by deduplicating each pair's clone group.
+fn saved_token_scores(keys: &[(&str, &str, f64)], entities: &[&CodeEntity]) -> Vec<f64> {
+    let token_counts: HashMap<&str, usize> = entities
+        .iter()
+        .map(|entity| (entity.id.as_str(), count_tokens(&entity.source_code)))
+        .collect();
+    let tokens = |id: &str| token_counts.get(id).copied().unwrap_or(0);
+
+    keys.iter()
+        .zip(group_sizes(keys))
+        .map(|(&(source, target, similarity), occurrences)| {
+            let shared_tokens = similarity * tokens(source).min(tokens(target)) as f64;
+            shared_tokens * occurrences as f64
+        })
+        .collect()
+}
 
 /// LSH-based similarity feature extractor with O(n) candidate search
 #[derive(Debug)]
@@ -67,12 +123,24 @@ pub struct LshExtractor {
     /// Shingle size for text processing
     shingle_size: usize,
 
+    /// Literal normalization applied before shingling
+    normalization: NormalizationOptions,
+
+    /// Entities with fewer whitespace-separated tokens are skipped
+    min_tokens: usize,
+
     /// Enhanced dedupe configuration for strict clone detection
     dedupe_config: Option<DedupeConfig>,
 
+    /// Adaptive relaxation of the dedupe fragment thresholds, when configured
+    adaptive_threshold: Option<AdaptiveThreshold>,
+
     /// Weighted shingle analyzer for clone denoising
     weighted_analyzer: Option<WeightedShingleAnalyzer>,
 
+    /// How detected clone pairs are ordered
+    ranking: RankingConfig,
+
     /// LSH configuration for efficient candidate search
     lsh_config: LshConfig,
 
@@ -94,6 +162,18 @@ pub struct LshExtractor {
 
     /// Cached similarity context built from the last extraction pass
     similarity_context_cache: std::sync::RwLock<Option<(String, Arc<LshSimilarityContext>)>>,
+
+    /// Brute-force pair similarities for the context identified by the key
+    known_pair_similarities:
+        std::sync::RwLock<Option<(String, Arc<std::sync::Mutex<KnownPairSimilarities>>)>>,
+
+    /// Directory that signatures are persisted under between runs
+    #[cfg(feature = "persistent-cache")]
+    persistent_cache_dir: Option<std::path::PathBuf>,
+
+    /// Persistent signature cache, opened on first use
+    #[cfg(feature = "persistent-cache")]
+    persistent_cache: std::sync::OnceLock<Option<PersistentLshCache>>,
 }
 
 // EntityAstStats has been moved to ast_analysis module
@@ -103,13 +183,25 @@ impl LshExtractor {
     /// Create with specific parameters and optional dedupe config (internal helper).
     fn create(num_hashes: usize, shingle_size: usize, dedupe_config: Option<DedupeConfig>) -> Self {
         let ast_service = Arc::new(AstService::new());
+        let adaptive_threshold = dedupe_config
+            .as_ref()
+            .and_then(|config| AdaptiveThreshold::from_config(&config.adaptive));
         let mut extractor = Self {
             ast_analyzer: AstAnalyzer::new(ast_service),
             features: Vec::new(),
             num_hashes,
             shingle_size,
+            normalization: NormalizationOptions {
+                strip_imports: dedupe_config
+                    .as_ref()
+                    .is_some_and(|config| config.strip_imports),
+                ..NormalizationOptions::default()
+            },
+            min_tokens: DEFAULT_MIN_TOKENS,
             dedupe_config,
+            adaptive_threshold,
             weighted_analyzer: None,
+            ranking: RankingConfig::default(),
             lsh_config: LshConfig::default(),
             cache: LshCache::new(),
             memory_pools: LshMemoryPools::new(),
@@ -117,6 +209,11 @@ impl LshExtractor {
             cached_weighted_signatures: std::sync::RwLock::new(None),
             weighted_signatures_cache_key: std::sync::RwLock::new(None),
             similarity_context_cache: std::sync::RwLock::new(None),
+            known_pair_similarities: std::sync::RwLock::new(None),
+            #[cfg(feature = "persistent-cache")]
+            persistent_cache_dir: None,
+            #[cfg(feature = "persistent-cache")]
+            persistent_cache: std::sync::OnceLock::new(),
         };
         extractor.initialize_features();
         extractor
@@ -127,15 +224,32 @@ impl LshExtractor {
         Self::create(128, 3, None)
     }
 
+    /// Create from an LSH configuration, usually built with [`LshConfig::builder`].
+    ///
+    /// `num_bands` is rounded down to a divisor of `num_hashes` as in
+    /// [`with_lsh_config`](Self::with_lsh_config).
+    pub fn from_config(config: LshConfig) -> Self {
+        Self::create(config.num_hashes, config.shingle_size, None).with_lsh_config(config)
+    }
+
     /// Create with custom hash and shingle parameters.
+    #[deprecated(note = "use `LshExtractor::from_config(LshConfig::builder()...build())`")]
     pub fn with_params(num_hashes: usize, shingle_size: usize) -> Self {
-        Self::create(num_hashes, shingle_size, None)
+        Self::from_config(
+            LshConfig::builder()
+                .num_hashes(num_hashes)
+                .shingle_size(shingle_size)
+                .build(),
+        )
     }
 
     /// Create with enhanced dedupe configuration.
-    pub fn with_dedupe_config(dedupe_config: DedupeConfig) -> Self {
+    ///
+    /// Returns a validation error when the thresholds are too permissive to be meaningful.
+    pub fn with_dedupe_config(dedupe_config: DedupeConfig) -> Result<Self> {
+        dedupe_config.validate()?;
         let shingle_size = dedupe_config.shingle_k;
-        Self::create(128, shingle_size, Some(dedupe_config))
+        Ok(Self::create(128, shingle_size, Some(dedupe_config)))
     }
 
     /// Replace the internal AST service with a shared instance so multiple
@@ -150,6 +264,11 @@ impl LshExtractor {
         self.lsh_config.similarity_threshold
     }
 
+    /// Number of LSH bands signatures are split into
+    pub fn num_bands(&self) -> usize {
+        self.lsh_config.num_bands
+    }
+
     /// Maximum number of candidates to consider per entity
     pub fn max_candidates(&self) -> Option<usize> {
         if self.lsh_config.max_candidates == 0 {
@@ -169,7 +288,7 @@ impl LshExtractor {
         &self,
         context: &ExtractionContext,
     ) -> Option<Arc<LshSimilarityContext>> {
-        self.get_similarity_context(context)
+        self.get_similarity_context(context, &self.context_cache_key(context))
     }
 
     /// Returns candidate entities from partition map for similarity comparison.
@@ -186,11 +305,59 @@ impl LshExtractor {
     }
 
     /// Check whether an entity passes the fragment thresholds configured for dedupe analysis
+    ///
+    /// Uses the adaptive factor as last settled by [`Self::filter_by_thresholds`]
+    /// and never moves it.
     pub async fn entity_passes_thresholds(&self, entity: &CodeEntity) -> Result<bool> {
-        if let Some(ref config) = self.dedupe_config {
-            return self.meets_fragment_thresholds(entity, config).await;
+        let Some(ref config) = self.dedupe_config else {
+            return Ok(true);
+        };
+        self.meets_fragment_thresholds(entity, config).await
+    }
+
+    /// Keep the entities that pass the fragment thresholds configured for dedupe analysis.
+    ///
+    /// Every entity is measured first and the adaptive factor settles on the
+    /// whole set before anything is filtered, so the outcome does not depend on
+    /// entity order. Entities that cannot be measured are dropped.
+    pub async fn filter_by_thresholds(&self, entities: Vec<CodeEntity>) -> Vec<CodeEntity> {
+        let Some(ref config) = self.dedupe_config else {
+            return entities;
+        };
+        let max_relaxation = self
+            .adaptive_threshold
+            .as_ref()
+            .map_or(0.0, AdaptiveThreshold::max_relaxation);
+
+        let mut measured = Vec::with_capacity(entities.len());
+        for entity in entities {
+            match self
+                .ast_analyzer
+                .measure_fragment(&entity, config, max_relaxation)
+                .await
+            {
+                Ok(measurements) => measured.push((entity, measurements)),
+                Err(e) => debug!("Skipping {} for fragment thresholds: {}", entity.id, e),
+            }
         }
-        Ok(true)
+
+        let relaxation = match &self.adaptive_threshold {
+            Some(adaptive) if !measured.is_empty() => adaptive.settle(|k| {
+                let filtered = measured
+                    .iter()
+                    .filter(|(_, measurements)| !measurements.meets(config, k))
+                    .count();
+                filtered as f64 / measured.len() as f64
+            }),
+            Some(adaptive) => adaptive.current(),
+            None => 0.0,
+        };
+
+        measured
+            .into_iter()
+            .filter(|(_, measurements)| measurements.meets(config, relaxation))
+            .map(|(entity, _)| entity)
+            .collect()
     }
 
     /// Compute AST statistics for an entity (delegates to AstAnalyzer)
@@ -217,9 +384,49 @@ impl LshExtractor {
             .as_ref()
             .ok_or_else(|| "Weighted analyzer not enabled".to_string())?;
 
-        let mut analyzer_copy = WeightedShingleAnalyzer::new(analyzer_template.k);
-        let signatures = analyzer_copy.compute_weighted_signatures(entities)?;
-        let stats = analyzer_copy.statistics();
+        Self::compute_weighted_signatures_with_stats(analyzer_template.fresh_copy(), entities)
+    }
+
+    /// Async variant of [`Self::weighted_signatures_with_stats`] for large corpora.
+    ///
+    /// The entities are cloned and the TF-IDF pass runs on tokio's blocking pool so
+    /// it does not stall the async executor.
+    pub async fn weighted_signatures_with_stats_async(
+        &self,
+        entities: &[&CodeEntity],
+    ) -> Result<(
+        HashMap<String, WeightedMinHashSignature>,
+        WeightedShingleStats,
+    )> {
+        let analyzer = self
+            .weighted_analyzer
+            .as_ref()
+            .map(WeightedShingleAnalyzer::fresh_copy)
+            .ok_or_else(|| ValknutError::lsh("Weighted analyzer not enabled"))?;
+        let owned: Vec<CodeEntity> = entities.iter().map(|entity| (*entity).clone()).collect();
+
+        tokio::task::spawn_blocking(move || {
+            let refs: Vec<&CodeEntity> = owned.iter().collect();
+            Self::compute_weighted_signatures_with_stats(analyzer, &refs)
+        })
+        .await
+        .map_err(|e| ValknutError::lsh(format!("Weighted signature task failed: {}", e)))?
+        .map_err(ValknutError::lsh)
+    }
+
+    /// Run a fresh weighted `analyzer` over `entities`.
+    fn compute_weighted_signatures_with_stats(
+        mut analyzer: WeightedShingleAnalyzer,
+        entities: &[&CodeEntity],
+    ) -> std::result::Result<
+        (
+            HashMap<String, WeightedMinHashSignature>,
+            WeightedShingleStats,
+        ),
+        String,
+    > {
+        let signatures = analyzer.compute_weighted_signatures(entities)?.signatures;
+        let stats = analyzer.statistics();
 
         Ok((signatures, stats))
     }
@@ -234,9 +441,19 @@ impl LshExtractor {
     }
 
     /// Enable weighted shingle analysis for clone denoising
+    ///
+    /// Enabling denoising also switches clone pair ranking from the default
+    /// [`RankingBy::SavedTokens`] to [`RankingBy::WeightedSimilarity`];
+    /// [`RankingBy::Frequency`] is kept.
     pub fn with_denoise_enabled(mut self, enable_denoise: bool) -> Self {
         if enable_denoise {
-            self.weighted_analyzer = Some(WeightedShingleAnalyzer::new(self.shingle_size));
+            self.weighted_analyzer = Some(
+                WeightedShingleAnalyzer::new(self.shingle_size)
+                    .with_normalization_options(self.normalization),
+            );
+            if self.ranking.by == RankingBy::SavedTokens {
+                self.ranking.by = RankingBy::WeightedSimilarity;
+            }
             info!(
                 "WeightedShingleAnalyzer enabled for clone denoising with k={}",
                 self.shingle_size
@@ -245,8 +462,99 @@ impl LshExtractor {
         self
     }
 
+    /// Set how detected clone pairs are ranked
+    pub fn with_ranking_config(mut self, ranking: RankingConfig) -> Self {
+        self.ranking = ranking;
+        self
+    }
+
+    /// Get the clone pair ranking configuration
+    pub fn ranking_config(&self) -> &RankingConfig {
+        &self.ranking
+    }
+
+    /// Order clone pairs best-first according to the ranking configuration.
+    ///
+    /// `pair_key` yields each pair's entity IDs and raw MinHash Jaccard
+    /// similarity. Pairs are scored by the configured criterion:
+    /// - [`RankingBy::SavedTokens`]: shared tokens (similarity times the
+    ///   smaller entity's token count in `entities`) times the number of
+    ///   occurrences in the pair's clone group
+    /// - [`RankingBy::Frequency`]: size of the pair's clone group
+    /// - [`RankingBy::WeightedSimilarity`]: weighted Jaccard over the weighted
+    ///   signatures of `entities`, or raw similarity when the weighted
+    ///   analyzer is not enabled
+    ///
+    /// Ties fall back to raw similarity; the sort is stable, so remaining ties
+    /// keep their discovery order.
+    pub fn rank_clone_pairs<T, F>(&self, pairs: &mut Vec<T>, pair_key: F, entities: &[&CodeEntity])
+    where
+        F: Fn(&T) -> (&str, &str, f64),
+    {
+        let keys: Vec<(&str, &str, f64)> = pairs.iter().map(&pair_key).collect();
+        let similarities: Vec<f64> = keys.iter().map(|key| key.2).collect();
+        let scores = match self.ranking.by {
+            RankingBy::SavedTokens => saved_token_scores(&keys, entities),
+            RankingBy::Frequency => group_sizes(&keys)
+                .into_iter()
+                .map(|size| size as f64)
+                .collect(),
+            RankingBy::WeightedSimilarity => self
+                .weighted_ranking_scores(&keys, entities)
+                .unwrap_or_else(|| similarities.clone()),
+        };
+
+        let mut order: Vec<usize> = (0..pairs.len()).collect();
+        order.sort_by(|&a, &b| {
+            scores[b]
+                .total_cmp(&scores[a])
+                .then_with(|| similarities[b].total_cmp(&similarities[a]))
+        });
+
+        let mut slots: Vec<Option<T>> = pairs.drain(..).map(Some).collect();
+        pairs.extend(order.into_iter().filter_map(|index| slots[index].take()));
+    }
+
+    /// Weighted Jaccard score for each pair, or `None` when weighting is unavailable.
+    fn weighted_ranking_scores(
+        &self,
+        keys: &[(&str, &str, f64)],
+        entities: &[&CodeEntity],
+    ) -> Option<Vec<f64>> {
+        let analyzer = self.weighted_analyzer.as_ref()?;
+        let signatures = match self.get_or_compute_weighted_signatures(entities) {
+            Ok(signatures) => signatures,
+            Err(e) => {
+                warn!("Falling back to raw similarity ranking: {}", e);
+                return None;
+            }
+        };
+
+        let scores = keys
+            .iter()
+            .map(
+                |&(source, target, _)| match (signatures.get(source), signatures.get(target)) {
+                    (Some(a), Some(b)) => analyzer.weighted_jaccard_similarity(a, b),
+                    _ => 0.0,
+                },
+            )
+            .collect();
+        Some(scores)
+    }
+
     /// Configure LSH parameters for efficient similarity search
-    pub fn with_lsh_config(mut self, lsh_config: LshConfig) -> Self {
+    ///
+    /// `num_bands` must divide `num_hashes` so every band holds the same number
+    /// of hashes; otherwise it is rounded down to the nearest divisor.
+    pub fn with_lsh_config(mut self, mut lsh_config: LshConfig) -> Self {
+        if lsh_config.num_bands == 0 || lsh_config.num_hashes % lsh_config.num_bands != 0 {
+            let rounded = largest_divisor_at_most(lsh_config.num_hashes, lsh_config.num_bands);
+            warn!(
+                "LSH num_hashes ({}) is not divisible by num_bands ({}); using {} bands",
+                lsh_config.num_hashes, lsh_config.num_bands, rounded
+            );
+            lsh_config.num_bands = rounded;
+        }
         self.num_hashes = lsh_config.num_hashes;
         self.shingle_size = lsh_config.shingle_size;
 
@@ -261,6 +569,52 @@ impl LshExtractor {
         self
     }
 
+    /// Configure which literals are collapsed into placeholder tokens before shingling
+    pub fn with_normalization_options(mut self, options: NormalizationOptions) -> Self {
+        self.normalization = options;
+        self.weighted_analyzer = self
+            .weighted_analyzer
+            .take()
+            .map(|analyzer| analyzer.with_normalization_options(options));
+        self
+    }
+
+    /// Read and write MinHash signatures through a persistent cache under `cache_dir`.
+    ///
+    /// Signatures are stored separately per normalization setting so changed
+    /// options never reuse stale entries. A cache that cannot be opened is
+    /// logged and the in-memory cache is used alone.
+    #[cfg(feature = "persistent-cache")]
+    pub fn with_persistent_cache_dir(mut self, cache_dir: Option<std::path::PathBuf>) -> Self {
+        self.persistent_cache_dir = cache_dir;
+        self.persistent_cache = std::sync::OnceLock::new();
+        self
+    }
+
+    /// Open the persistent signature cache for the current normalization settings.
+    #[cfg(feature = "persistent-cache")]
+    fn open_persistent_cache(&self) -> Option<PersistentLshCache> {
+        let settings = format!("{:?}", self.normalization);
+        let dir = self
+            .persistent_cache_dir
+            .as_ref()?
+            .join(PERSISTENT_SIGNATURE_DIR)
+            .join(format!("{:016x}", xxh3_64(settings.as_bytes())));
+        match PersistentLshCache::open(&dir) {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                warn!("Persistent LSH signature cache disabled: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Set the minimum token count an entity needs before it is compared
+    pub fn with_min_tokens(mut self, min_tokens: usize) -> Self {
+        self.min_tokens = min_tokens;
+        self
+    }
+
     /// Get performance metrics for optimization analysis
     pub fn get_performance_metrics(&self) -> &LshPerformanceMetrics {
         &self.performance_metrics
@@ -291,6 +645,13 @@ impl LshExtractor {
             cache_stats.token_misses + cache_stats.signature_misses,
             cache_stats.overall_hit_rate() * 100.0
         );
+        info!(
+            "LSH Cache Breakdown: token_hit_rate={:.1}%, signature_hit_rate={:.1}%, signatures={}, bytes_in_use~{}",
+            cache_stats.token_hit_rate() * 100.0,
+            cache_stats.signature_hit_rate() * 100.0,
+            cache_stats.signature_count,
+            cache_stats.bytes_in_use_estimate()
+        );
 
         // Log memory pool statistics
         self.memory_pools.log_statistics();
@@ -314,22 +675,22 @@ impl LshExtractor {
         }
     }
 
-    /// Generate a cache key for the current context
-    fn generate_cache_key(&self, entities: &[&crate::core::featureset::CodeEntity]) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-
-        // Include extractor configuration in cache key
-        self.k().hash(&mut hasher);
-
-        // Include all entity IDs sorted for consistent key generation
-        let mut entity_ids: Vec<&str> = entities.iter().map(|e| e.id.as_str()).collect();
-        entity_ids.sort();
-        entity_ids.hash(&mut hasher);
+    /// Generate an order-insensitive cache key for a set of entity ids
+    ///
+    /// Ids are hashed individually and summed, so the key is built in one pass
+    /// without sorting and only changes when ids are added or removed.
+    fn generate_cache_key<'a>(&self, entity_ids: impl IntoIterator<Item = &'a str>) -> String {
+        let mut count = 0usize;
+        let combined = entity_ids.into_iter().fold(0u64, |sum, id| {
+            count += 1;
+            sum.wrapping_add(xxh3_64(id.as_bytes()))
+        });
+        format!("context_{}_{}_{:x}", self.k(), count, combined)
+    }
 
-        format!("weighted_signatures_{:x}", hasher.finish())
+    /// Cache key for the entities indexed by an extraction context
+    fn context_cache_key(&self, context: &ExtractionContext) -> String {
+        self.generate_cache_key(context.entity_index.keys().map(String::as_str))
     }
 
     /// Get the shingle size (k) for this extractor
@@ -342,33 +703,59 @@ impl LshExtractor {
     }
 
     /// Gets or creates a cached similarity context for fast lookups.
+    ///
+    /// `cache_key` must come from [`Self::context_cache_key`] for `context`.
     fn get_similarity_context(
         &self,
         context: &ExtractionContext,
+        cache_key: &str,
     ) -> Option<Arc<LshSimilarityContext>> {
         if context.entity_index.is_empty() {
             return None;
         }
 
-        let entity_refs: Vec<&CodeEntity> = context.entity_index.values().collect();
-        let cache_key = self.generate_cache_key(&entity_refs);
-
         if let Ok(cache_guard) = self.similarity_context_cache.read() {
             if let Some((ref existing_key, ref cached_context)) = *cache_guard {
-                if *existing_key == cache_key {
+                if existing_key == cache_key {
                     return Some(cached_context.clone());
                 }
             }
         }
 
+        let entity_refs: Vec<&CodeEntity> = context.entity_index.values().collect();
         let context_instance = Arc::new(self.create_similarity_search_context(&entity_refs));
         if let Ok(mut cache_guard) = self.similarity_context_cache.write() {
-            *cache_guard = Some((cache_key, context_instance.clone()));
+            *cache_guard = Some((cache_key.to_string(), context_instance.clone()));
         }
 
         Some(context_instance)
     }
 
+    /// Gets the remembered pair similarities for the context, starting a fresh
+    /// map whenever `cache_key` moves on to another context.
+    fn known_pairs_for(&self, cache_key: &str) -> Arc<std::sync::Mutex<KnownPairSimilarities>> {
+        if let Ok(known_guard) = self.known_pair_similarities.read() {
+            if let Some((ref existing_key, ref known_pairs)) = *known_guard {
+                if existing_key == cache_key {
+                    return known_pairs.clone();
+                }
+            }
+        }
+
+        let mut known_guard = self
+            .known_pair_similarities
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match &*known_guard {
+            Some((existing_key, known_pairs)) if existing_key == cache_key => known_pairs.clone(),
+            _ => {
+                let known_pairs = Arc::new(std::sync::Mutex::new(KnownPairSimilarities::new()));
+                *known_guard = Some((cache_key.to_string(), known_pairs.clone()));
+                known_pairs
+            }
+        }
+    }
+
     /// Try to get cached weighted signatures if the cache key matches.
     fn try_get_cached_weighted_signatures(
         &self,
@@ -395,7 +782,8 @@ impl LshExtractor {
         entities: &[&crate::core::featureset::CodeEntity],
     ) -> std::result::Result<HashMap<String, WeightedMinHashSignature>, String> {
         if let Some(ref analyzer) = self.weighted_analyzer {
-            let cache_key = self.generate_cache_key(entities);
+            let cache_key =
+                self.generate_cache_key(entities.iter().map(|entity| entity.id.as_str()));
 
             // Check if signatures are cached
             if let Some(cached) = self.try_get_cached_weighted_signatures(&cache_key) {
@@ -407,8 +795,10 @@ impl LshExtractor {
                 "Computing weighted signatures for {} entities (cache miss)",
                 entities.len()
             );
-            let mut analyzer_copy = WeightedShingleAnalyzer::new(analyzer.k);
-            let signatures = analyzer_copy.compute_weighted_signatures(entities)?;
+            let mut analyzer_copy = analyzer.fresh_copy();
+            let signatures = analyzer_copy
+                .compute_weighted_signatures(entities)?
+                .signatures;
 
             // Cache the results
             if let Ok(mut cache) = self.cached_weighted_signatures.write() {
@@ -429,13 +819,11 @@ impl LshExtractor {
         &self,
         context_entities: &[&crate::core::featureset::CodeEntity],
         current_entity: &crate::core::featureset::CodeEntity,
+        cache_key: &str,
     ) -> std::result::Result<HashMap<String, WeightedMinHashSignature>, String> {
         if let Some(ref analyzer) = self.weighted_analyzer {
-            // Use stable cache key based only on context entities
-            let cache_key = self.generate_cache_key(context_entities);
-
             // Check if signatures are cached
-            if let Some(cached) = self.try_get_cached_weighted_signatures(&cache_key) {
+            if let Some(cached) = self.try_get_cached_weighted_signatures(cache_key) {
                 return Ok(cached);
             }
 
@@ -447,15 +835,17 @@ impl LshExtractor {
                 "Computing weighted signatures for {} entities (cache miss)",
                 all_entities.len()
             );
-            let mut analyzer_copy = WeightedShingleAnalyzer::new(analyzer.k);
-            let signatures = analyzer_copy.compute_weighted_signatures(&all_entities)?;
+            let mut analyzer_copy = analyzer.fresh_copy();
+            let signatures = analyzer_copy
+                .compute_weighted_signatures(&all_entities)?
+                .signatures;
 
             // Cache the results using stable key
             if let Ok(mut cache) = self.cached_weighted_signatures.write() {
                 *cache = Some(signatures.clone());
             }
             if let Ok(mut cache_key_write) = self.weighted_signatures_cache_key.write() {
-                *cache_key_write = Some(cache_key);
+                *cache_key_write = Some(cache_key.to_string());
             }
 
             Ok(signatures)
@@ -464,6 +854,11 @@ impl LshExtractor {
         }
     }
 
+    /// Normalize source code using this extractor's normalization options
+    pub fn normalize_code(&self, source_code: &str) -> String {
+        signatures::generator::normalize_code(source_code, self.normalization)
+    }
+
     /// Public access to create_shingles for benchmarking
     pub fn create_shingles(&self, source_code: &str) -> Vec<String> {
         signatures::generator::create_shingles(self, source_code)
@@ -527,6 +922,19 @@ impl Default for LshExtractor {
     }
 }
 
+/// Flushes persisted signatures when the extractor is dropped.
+#[cfg(feature = "persistent-cache")]
+impl Drop for LshExtractor {
+    /// Writes pending persistent cache entries to disk.
+    fn drop(&mut self) {
+        if let Some(Some(cache)) = self.persistent_cache.get() {
+            if let Err(e) = cache.flush() {
+                warn!("Failed to flush persistent LSH signature cache: {}", e);
+            }
+        }
+    }
+}
+
 /// [`SignatureGenerator`] implementation for LSH signature creation.
 impl SignatureGenerator for LshExtractor {
     /// Returns the number of hash functions used.
@@ -548,6 +956,29 @@ impl SignatureGenerator for LshExtractor {
     fn memory_pools(&self) -> &LshMemoryPools {
         &self.memory_pools
     }
+
+    /// Returns the literal normalization applied before tokenization.
+    fn normalization_options(&self) -> NormalizationOptions {
+        self.normalization
+    }
+
+    /// Returns the metrics that record signature cache hits and misses.
+    fn performance_metrics(&self) -> Option<&LshPerformanceMetrics> {
+        Some(&self.performance_metrics)
+    }
+
+    /// Returns whether repeated shingles are dropped before hashing.
+    fn deduplicate_shingles(&self) -> bool {
+        self.lsh_config.deduplicate_shingles
+    }
+
+    /// Returns the persistent signature cache, opening it on first use.
+    #[cfg(feature = "persistent-cache")]
+    fn persistent_cache(&self) -> Option<&PersistentLshCache> {
+        self.persistent_cache
+            .get_or_init(|| self.open_persistent_cache())
+            .as_ref()
+    }
 }
 
 /// [`FeatureExtractor`] implementation for LSH-based similarity features.
@@ -571,14 +1002,19 @@ impl FeatureExtractor for LshExtractor {
     ) -> Result<HashMap<String, f64>> {
         let mut features = HashMap::with_capacity(8); // Typical LSH analysis produces 5-10 features
 
+        let token_count = count_tokens(&entity.source_code);
+        if token_count < self.min_tokens {
+            debug!(
+                "Skipping LSH comparison for {}: {} tokens is below minimum of {}",
+                entity.id, token_count, self.min_tokens
+            );
+            return Ok(Self::zero_features(features));
+        }
+
         // Apply enhanced fragment analysis if dedupe config is available
         if let Some(ref config) = self.dedupe_config {
             if !self.meets_fragment_thresholds(entity, config).await? {
-                features.insert("clone_mass".to_string(), 0.0);
-                features.insert("max_similarity".to_string(), 0.0);
-                features.insert("avg_similarity".to_string(), 0.0);
-                features.insert("duplicate_count".to_string(), 0.0);
-                return Ok(features);
+                return Ok(Self::zero_features(features));
             }
         }
 
@@ -630,14 +1066,31 @@ impl LshExtractor {
             .collect()
     }
 
-    /// Check if entity meets fragment analysis thresholds using structural data
+    /// Fill the similarity features with zeros for entities excluded from comparison
+    fn zero_features(mut features: HashMap<String, f64>) -> HashMap<String, f64> {
+        features.insert("clone_mass".to_string(), 0.0);
+        features.insert("max_similarity".to_string(), 0.0);
+        features.insert("avg_similarity".to_string(), 0.0);
+        features.insert("duplicate_count".to_string(), 0.0);
+        features
+    }
+
+    /// Check if entity meets fragment analysis thresholds using structural data.
+    ///
+    /// Thresholds are relaxed by the adaptive factor settled in
+    /// [`Self::filter_by_thresholds`]. Nothing is recorded here, so every
+    /// entity extracted in parallel sees the same factor.
     async fn meets_fragment_thresholds(
         &self,
         entity: &CodeEntity,
         config: &DedupeConfig,
     ) -> Result<bool> {
+        let relaxation = self
+            .adaptive_threshold
+            .as_ref()
+            .map_or(0.0, AdaptiveThreshold::current);
         self.ast_analyzer
-            .meets_fragment_thresholds(entity, config)
+            .meets_fragment_thresholds(entity, config, relaxation)
             .await
     }
 
@@ -658,7 +1111,7 @@ impl LshExtractor {
                 &entity.source_code,
             );
             let minhash_sig = MinHashSignature::new(signature, self.num_hashes, self.shingle_size);
-            lsh_index.add_entity(entity_id.clone(), minhash_sig);
+            lsh_index.add_entity(entity_id, minhash_sig);
         }
 
         let elapsed = start_time.elapsed();
@@ -694,28 +1147,63 @@ impl LshExtractor {
             );
             let minhash_sig =
                 MinHashSignature::new(signature.clone(), self.num_hashes, self.shingle_size);
-            lsh_index.add_entity(entity.id.clone(), minhash_sig);
+            lsh_index.add_entity(&entity.id, minhash_sig);
             signatures.insert(entity.id.clone(), signature);
         }
 
         let elapsed = start_time.elapsed();
-        info!("Built LSH similarity context in {:?}", elapsed);
-
-        LshSimilarityContext::new(
+        let similarity_context = LshSimilarityContext::new(
             lsh_index,
             signatures,
             self.lsh_config.clone(),
             entities.len(),
-        )
+        );
+
+        let stats = similarity_context.get_statistics();
+        info!(
+            "Built LSH similarity context in {:?} (estimated memory: {:.2} MB)",
+            elapsed,
+            stats.estimated_memory_mb()
+        );
+
+        similarity_context
     }
 
     /// Compare entity with others in the context using efficient LSH-based candidate search
+    ///
+    /// Runs inside an `lsh_compare` span that records the number of candidates
+    /// found and the comparison duration once the search completes.
     fn compare_with_others(
         &self,
         entity: &CodeEntity,
         context: &ExtractionContext,
         signature: &[u64],
     ) -> (f64, f64, f64) {
+        let span = info_span!(
+            "lsh_compare",
+            entity_id = %entity.id,
+            entity_index_size = context.entity_index.len(),
+            candidates_found = field::Empty,
+            duration_ms = field::Empty,
+        );
+        let _entered = span.enter();
+        let comparison_start = std::time::Instant::now();
+
+        let similarities = self.find_similarities(entity, context, signature);
+
+        span.record("candidates_found", similarities.len());
+        span.record("duration_ms", comparison_start.elapsed().as_millis() as u64);
+        summarise_similarities(&similarities)
+    }
+
+    /// Collect similarity scores above threshold, preferring the LSH index.
+    fn find_similarities(
+        &self,
+        entity: &CodeEntity,
+        context: &ExtractionContext,
+        signature: &[u64],
+    ) -> Vec<f64> {
+        let cache_key = self.context_cache_key(context);
         let (candidate_filter, candidate_lookup): (Option<&Vec<EntityId>>, Option<HashSet<&str>>) =
             if let Some(filter) = self.candidate_filter(entity, context) {
                 let lookup = filter.iter().map(|s| s.as_str()).collect::<HashSet<&str>>();
@@ -736,6 +1224,7 @@ impl LshExtractor {
                 context,
                 signature,
                 candidate_filter,
+                &cache_key,
             );
         }
 
@@ -744,10 +1233,10 @@ impl LshExtractor {
                 entity = %entity.id,
                 "No clique peers found; skipping similarity comparisons"
             );
-            return (0.0, 0.0, 0.0);
+            return Vec::new();
         }
 
-        if let Some(similarity_context) = self.get_similarity_context(context) {
+        if let Some(similarity_context) = self.get_similarity_context(context, &cache_key) {
             let max_results = if self.lsh_config.max_candidates == 0 {
                 None
             } else {
@@ -755,7 +1244,7 @@ impl LshExtractor {
             };
 
             let threshold = self.lsh_config.similarity_threshold;
-            let mut similarities: Vec<f64> = similarity_context
+            let similarities: Vec<f64> = similarity_context
                 .find_similar_entities(&entity.id, max_results)
                 .into_iter()
                 .filter(|(candidate_id, _)| {
@@ -772,11 +1261,17 @@ impl LshExtractor {
                     similarities.len(),
                     entity.id
                 );
-                return summarise_similarities(&similarities);
+                return similarities;
             }
         }
 
-        self.compare_with_others_bruteforce(entity, context, signature, candidate_filter)
+        self.compare_with_others_bruteforce(
+            entity,
+            context,
+            signature,
+            candidate_filter,
+            &cache_key,
+        )
     }
 
     /// Compares entity against others using brute-force MinHash comparison.
@@ -786,15 +1281,24 @@ impl LshExtractor {
         context: &ExtractionContext,
         signature: &[u64],
         candidate_filter: Option<&Vec<EntityId>>,
-    ) -> (f64, f64, f64) {
-        let comparison_start = std::time::Instant::now();
+        cache_key: &str,
+    ) -> Vec<f64> {
         let candidate_count =
             candidate_filter.map_or(context.entity_index.len(), |filter| filter.len());
+        let span = debug_span!(
+            "lsh_bruteforce_compare",
+            entity_id = %entity.id,
+            candidate_count,
+            candidates_found = field::Empty,
+            duration_ms = field::Empty,
+        );
+        let _entered = span.enter();
+        let comparison_start = std::time::Instant::now();
         let max_candidates = self.effective_max_candidates(candidate_count);
 
         // Try weighted comparison first
         let similarities = self
-            .try_weighted_comparison(entity, context, candidate_filter, max_candidates)
+            .try_weighted_comparison(entity, context, candidate_filter, max_candidates, cache_key)
             .unwrap_or_default();
 
         // Fall back to basic minhash if weighted produced no results
@@ -805,19 +1309,23 @@ impl LshExtractor {
                 signature,
                 candidate_filter,
                 max_candidates,
+                cache_key,
             )
         } else {
             similarities
         };
 
+        let elapsed = comparison_start.elapsed();
+        span.record("candidates_found", similarities.len());
+        span.record("duration_ms", elapsed.as_millis() as u64);
         debug!(
             "Fallback similarity comparison for {} completed in {:?} with {} matches",
             entity.id,
-            comparison_start.elapsed(),
+            elapsed,
             similarities.len()
         );
 
-        summarise_similarities(&similarities)
+        similarities
     }
 
     /// Compute effective max candidates based on config and available count.
@@ -836,11 +1344,12 @@ impl LshExtractor {
         context: &ExtractionContext,
         candidate_filter: Option<&Vec<EntityId>>,
         max_candidates: usize,
+        cache_key: &str,
     ) -> Option<Vec<f64>> {
         let analyzer = self.weighted_analyzer.as_ref()?;
         let context_entities: Vec<&CodeEntity> = context.entity_index.values().collect();
         let weighted_signatures = self
-            .get_or_compute_weighted_signatures_with_current(&context_entities, entity)
+            .get_or_compute_weighted_signatures_with_current(&context_entities, entity, cache_key)
             .ok()?;
         let entity_sig = weighted_signatures.get(&entity.id)?;
 
@@ -881,6 +1390,9 @@ impl LshExtractor {
     }
 
     /// Fallback to basic minhash similarity comparison.
+    ///
+    /// Pair similarities are remembered for the current context, so the second
+    /// entity of a pair reuses the value computed for the first.
     fn fallback_minhash_comparison(
         &self,
         entity: &CodeEntity,
@@ -888,7 +1400,10 @@ impl LshExtractor {
         signature: &[u64],
         candidate_filter: Option<&Vec<EntityId>>,
         max_candidates: usize,
+        cache_key: &str,
     ) -> Vec<f64> {
+        let known_pairs = self.known_pairs_for(cache_key);
+
         comparison::fallback_minhash_comparison(
             entity,
             context,
@@ -896,6 +1411,7 @@ impl LshExtractor {
             candidate_filter,
             max_candidates,
             self.lsh_config.similarity_threshold,
+            &known_pairs,
             |source_code, entity_id| {
                 signatures::generator::generate_minhash_signature_cached(
                     self,
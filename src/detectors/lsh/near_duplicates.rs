@@ -0,0 +1,140 @@
+//! Near-duplicate search across an explicit set of files.
+//!
+//! Wraps entity extraction, similarity context construction and candidate
+//! filtering behind a single call for callers that only want clone pairs and
+//! do not need the full feature extraction pipeline.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use tracing::debug;
+
+use super::signatures::generator::normalize_code;
+use super::{pair_key, LshExtractor, NormalizationOptions};
+use crate::core::errors::{Result, ValknutError};
+use crate::core::featureset::CodeEntity;
+use crate::lang::registry::adapter_for_file;
+
+/// Classic clone taxonomy, derived by comparing normalized sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CloneType {
+    /// Type-1: identical apart from whitespace, comments and letter case
+    Exact,
+    /// Type-2: identical once identifiers and literals are replaced by placeholders
+    Renamed,
+    /// Type-3: similar above the threshold, with added, removed or changed statements
+    NearMiss,
+}
+
+/// Classification methods for [`CloneType`].
+impl CloneType {
+    /// Classify a pair of sources that were already found to be similar.
+    pub fn classify(source_a: &str, source_b: &str) -> Self {
+        let verbatim = NormalizationOptions::default();
+        if normalize_code(source_a, verbatim) == normalize_code(source_b, verbatim) {
+            return Self::Exact;
+        }
+
+        let placeholders = NormalizationOptions {
+            normalize_numbers: true,
+            normalize_strings: true,
+            normalize_identifiers: true,
+            strip_imports: false,
+        };
+        if normalize_code(source_a, placeholders) == normalize_code(source_b, placeholders) {
+            return Self::Renamed;
+        }
+
+        Self::NearMiss
+    }
+}
+
+/// Two entities whose estimated similarity meets the extractor's threshold.
+#[derive(Debug, Clone)]
+pub struct ClonePair {
+    /// Entity with the lexicographically smaller id
+    pub entity_a: CodeEntity,
+    /// Entity with the lexicographically larger id
+    pub entity_b: CodeEntity,
+    /// MinHash similarity estimate in `[0, 1]`
+    pub similarity: f64,
+    /// Clone category of the pair
+    pub clone_type: CloneType,
+}
+
+/// File-level near-duplicate search for [`LshExtractor`].
+impl LshExtractor {
+    /// Find near-duplicate entities across `files`.
+    ///
+    /// Entities are extracted with each file's language adapter; files without
+    /// an adapter are skipped, while unreadable or unparsable files are errors.
+    /// Entities below the minimum token count or the dedupe fragment thresholds
+    /// are ignored. Pairs are returned by descending similarity.
+    pub async fn find_near_duplicates_in_files(&self, files: &[PathBuf]) -> Result<Vec<ClonePair>> {
+        let mut entities = Vec::new();
+        for file_path in files {
+            let mut adapter = match adapter_for_file(file_path) {
+                Ok(adapter) => adapter,
+                Err(e) => {
+                    debug!("No language adapter for {}: {}", file_path.display(), e);
+                    continue;
+                }
+            };
+
+            let content = tokio::fs::read_to_string(file_path).await.map_err(|e| {
+                ValknutError::io(format!("Failed to read file {}", file_path.display()), e)
+            })?;
+
+            entities.extend(
+                adapter
+                    .extract_code_entities(&content, &file_path.to_string_lossy())?
+                    .into_iter()
+                    .filter(|entity| super::count_tokens(&entity.source_code) >= self.min_tokens),
+            );
+        }
+        let entities = self.filter_by_thresholds(entities).await;
+
+        let entity_refs: Vec<&CodeEntity> = entities.iter().collect();
+        let context = self.create_similarity_search_context(&entity_refs);
+        let entity_index: HashMap<&str, &CodeEntity> = entities
+            .iter()
+            .map(|entity| (entity.id.as_str(), entity))
+            .collect();
+
+        let threshold = self.similarity_threshold();
+        let mut seen = HashSet::new();
+        let mut pairs = Vec::new();
+        for entity in &entities {
+            for (candidate_id, similarity) in
+                context.find_similar_entities(&entity.id, self.max_candidates())
+            {
+                if similarity < threshold || !seen.insert(pair_key(&entity.id, &candidate_id)) {
+                    continue;
+                }
+                let Some(candidate) = entity_index.get(candidate_id.as_str()) else {
+                    continue;
+                };
+
+                let (entity_a, entity_b) = if entity.id <= candidate.id {
+                    (entity, *candidate)
+                } else {
+                    (*candidate, entity)
+                };
+                pairs.push(ClonePair {
+                    clone_type: CloneType::classify(&entity_a.source_code, &entity_b.source_code),
+                    entity_a: entity_a.clone(),
+                    entity_b: entity_b.clone(),
+                    similarity,
+                });
+            }
+        }
+
+        pairs.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then_with(|| a.entity_a.id.cmp(&b.entity_a.id))
+                .then_with(|| a.entity_b.id.cmp(&b.entity_b.id))
+        });
+        Ok(pairs)
+    }
+}
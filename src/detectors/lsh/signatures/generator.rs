@@ -11,8 +11,10 @@ use xxhash_rust::xxh3::Xxh3;
 #[cfg(feature = "simd")]
 use wide::u64x4;
 
-use super::super::{LshCache, LshMemoryPools};
+use super::super::{LshCache, LshMemoryPools, LshPerformanceMetrics};
 use crate::core::interning::{intern, resolve, InternedString};
+#[cfg(feature = "persistent-cache")]
+use crate::io::cache::PersistentLshCache;
 
 /// Trait for MinHash signature generation operations.
 ///
@@ -30,13 +32,156 @@ pub trait SignatureGenerator {
 
     /// Get memory pools for allocation
     fn memory_pools(&self) -> &LshMemoryPools;
+
+    /// Get the literal normalization applied before tokenization
+    fn normalization_options(&self) -> NormalizationOptions {
+        NormalizationOptions::default()
+    }
+
+    /// Get the metrics that signature cache hits and misses are recorded in
+    fn performance_metrics(&self) -> Option<&LshPerformanceMetrics> {
+        None
+    }
+
+    /// Whether repeated shingles are dropped before hashing
+    fn deduplicate_shingles(&self) -> bool {
+        false
+    }
+
+    /// Get the signature cache persisted between runs, when one is configured
+    #[cfg(feature = "persistent-cache")]
+    fn persistent_cache(&self) -> Option<&PersistentLshCache> {
+        None
+    }
+}
+
+/// Controls which literal kinds `normalize_code` collapses into placeholder tokens
+/// and which lines it drops.
+///
+/// All flags default to `false`, which keeps literals and import lines verbatim.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizationOptions {
+    /// Replace numeric literals (`42`, `3.14`, `0xFF`) with `<num>`
+    pub normalize_numbers: bool,
+    /// Replace quoted string literals with `<str>`
+    pub normalize_strings: bool,
+    /// Replace non-keyword identifiers with `<id>`
+    pub normalize_identifiers: bool,
+    /// Drop import statements (`import`, `from`, `require`, `use`), including
+    /// the inner lines of multi-line import blocks, so shared import headers
+    /// do not dominate the shingles. `#include` needs no flag: `#` lines are
+    /// always dropped as comments.
+    pub strip_imports: bool,
+}
+
+/// Factory and query methods for [`NormalizationOptions`].
+impl NormalizationOptions {
+    /// Returns true when any literal normalization is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.normalize_numbers || self.normalize_strings || self.normalize_identifiers
+    }
+}
+
+/// Line prefixes that start an import statement in the supported languages.
+const IMPORT_KEYWORDS: &[&str] = &["import", "from", "require", "use"];
+
+/// Check whether a trimmed line starts with an import keyword.
+fn is_import_line(line: &str) -> bool {
+    IMPORT_KEYWORDS.iter().any(|keyword| {
+        line.strip_prefix(keyword).is_some_and(|rest| {
+            rest.starts_with(|c: char| c.is_whitespace() || matches!(c, '(' | '"' | '<'))
+        })
+    })
+}
+
+/// Closing delimiter of a multi-line import block opened by `line`, such as
+/// Go's `import (` or Rust's `use std::{`.
+fn import_block_closer(line: &str) -> Option<char> {
+    match line.chars().last()? {
+        '(' => Some(')'),
+        '{' => Some('}'),
+        _ => None,
+    }
 }
 
+/// Keywords kept verbatim when identifiers are normalized.
+const NORMALIZATION_KEYWORDS: &[&str] = &[
+    "and",
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "def",
+    "default",
+    "del",
+    "do",
+    "elif",
+    "else",
+    "enum",
+    "except",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "fn",
+    "for",
+    "from",
+    "func",
+    "function",
+    "go",
+    "if",
+    "impl",
+    "import",
+    "in",
+    "interface",
+    "is",
+    "lambda",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "new",
+    "nil",
+    "none",
+    "not",
+    "null",
+    "or",
+    "pass",
+    "pub",
+    "raise",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "use",
+    "var",
+    "void",
+    "where",
+    "while",
+    "with",
+    "yield",
+];
+
 /// Create shingles from source code.
 ///
 /// Normalizes the code and creates overlapping n-grams (shingles) for similarity comparison.
 pub fn create_shingles<T: SignatureGenerator>(gen: &T, source_code: &str) -> Vec<String> {
-    let normalized = normalize_code(source_code);
+    let normalized = normalize_code(source_code, gen.normalization_options());
     let tokens: Vec<&str> = normalized
         .split_whitespace()
         .filter(|token| !token.is_empty())
@@ -52,6 +197,7 @@ pub fn create_shingles<T: SignatureGenerator>(gen: &T, source_code: &str) -> Vec
         }
     }
 
+    dedup_if_enabled(gen, &mut shingles);
     shingles
 }
 
@@ -62,7 +208,7 @@ pub fn create_shingles_interned<T: SignatureGenerator>(
     gen: &T,
     source_code: &str,
 ) -> Vec<InternedString> {
-    let normalized = normalize_code(source_code);
+    let normalized = normalize_code(source_code, gen.normalization_options());
     let shingle_size = gen.shingle_size();
 
     // Split into tokens and intern them immediately
@@ -87,24 +233,63 @@ pub fn create_shingles_interned<T: SignatureGenerator>(
         }
     }
 
+    dedup_if_enabled(gen, &mut shingles);
     shingles
 }
 
+/// Read a signature from the persistent cache and promote it to the in-memory cache.
+#[cfg(feature = "persistent-cache")]
+fn persistent_signature<T: SignatureGenerator>(
+    gen: &T,
+    source_code: &str,
+    num_hashes: usize,
+    shingle_size: usize,
+) -> Option<Vec<u64>> {
+    let signature = gen
+        .persistent_cache()?
+        .get_signature(source_code, num_hashes, shingle_size)?;
+    gen.cache()
+        .cache_signature(source_code, num_hashes, shingle_size, signature.clone());
+    Some(signature)
+}
+
+/// Write a freshly generated signature to the persistent cache, if one is configured.
+#[cfg(feature = "persistent-cache")]
+fn persist_signature<T: SignatureGenerator>(gen: &T, source_code: &str, signature: &[u64]) {
+    if let Some(cache) = gen.persistent_cache() {
+        cache.cache_signature(
+            source_code,
+            gen.num_hashes(),
+            gen.shingle_size(),
+            signature.to_vec(),
+        );
+    }
+}
+
 /// Generate MinHash signature for source code with performance tracking and caching.
 pub fn generate_minhash_signature<T: SignatureGenerator>(gen: &T, source_code: &str) -> Vec<u64> {
     let start_time = std::time::Instant::now();
     let num_hashes = gen.num_hashes();
     let shingle_size = gen.shingle_size();
 
-    // Check cache first
-    if let Some(cached_signature) = gen
+    // Check cache first, then the persistent cache from earlier runs
+    let cached = gen
         .cache()
-        .get_signature(source_code, num_hashes, shingle_size)
-    {
+        .get_signature(source_code, num_hashes, shingle_size);
+    #[cfg(feature = "persistent-cache")]
+    let cached =
+        cached.or_else(|| persistent_signature(gen, source_code, num_hashes, shingle_size));
+    if let Some(cached_signature) = cached {
+        if let Some(metrics) = gen.performance_metrics() {
+            metrics.record_cache_hit();
+        }
         let elapsed = start_time.elapsed();
         debug!("Signature cache hit, returned in {:?}", elapsed);
         return cached_signature;
     }
+    if let Some(metrics) = gen.performance_metrics() {
+        metrics.record_cache_miss();
+    }
 
     // Create shingles from the source code (with caching)
     let shingles = create_shingles_cached(gen, source_code);
@@ -130,6 +315,8 @@ pub fn generate_minhash_signature<T: SignatureGenerator>(gen: &T, source_code: &
         shingle_size,
         signature_clone.clone(),
     );
+    #[cfg(feature = "persistent-cache")]
+    persist_signature(gen, source_code, &signature_clone);
 
     // Return signature vector to memory pool for reuse
     gen.memory_pools().return_signature_vec(signature);
@@ -150,6 +337,12 @@ pub fn generate_minhash_signature_interned<T: SignatureGenerator>(
     let start_time = std::time::Instant::now();
     let num_hashes = gen.num_hashes();
 
+    #[cfg(feature = "persistent-cache")]
+    if let Some(signature) = persistent_signature(gen, source_code, num_hashes, gen.shingle_size())
+    {
+        return signature;
+    }
+
     // Create interned shingles (minimal allocations)
     let shingles = create_shingles_interned(gen, source_code);
 
@@ -173,6 +366,8 @@ pub fn generate_minhash_signature_interned<T: SignatureGenerator>(
 
     // Return signature vector to memory pool for reuse
     gen.memory_pools().return_signature_vec(signature);
+    #[cfg(feature = "persistent-cache")]
+    persist_signature(gen, source_code, &signature_clone);
 
     let elapsed = start_time.elapsed();
     debug!("Interned MinHash signature generation took: {:?}", elapsed);
@@ -199,8 +394,14 @@ pub fn generate_minhash_signature_simd<T: SignatureGenerator>(
     gen: &T,
     source_code: &str,
 ) -> Vec<u64> {
-    let shingles = create_shingles(gen, source_code);
     let num_hashes = gen.num_hashes();
+    #[cfg(feature = "persistent-cache")]
+    if let Some(signature) = persistent_signature(gen, source_code, num_hashes, gen.shingle_size())
+    {
+        return signature;
+    }
+
+    let shingles = create_shingles(gen, source_code);
     let mut signature = vec![u64::MAX; num_hashes];
 
     // Process hashes in chunks of 4 for SIMD
@@ -244,6 +445,8 @@ pub fn generate_minhash_signature_simd<T: SignatureGenerator>(
         }
     }
 
+    #[cfg(feature = "persistent-cache")]
+    persist_signature(gen, source_code, &signature);
     signature
 }
 
@@ -284,7 +487,7 @@ pub fn create_shingles_cached<T: SignatureGenerator>(gen: &T, source_code: &str)
     }
 
     // Generate tokens and shingles using memory pool
-    let normalized = normalize_code(source_code);
+    let normalized = normalize_code(source_code, gen.normalization_options());
     let mut tokens = gen.memory_pools().get_string_vec();
     tokens.extend(
         normalized
@@ -315,25 +518,54 @@ pub fn tokens_to_shingles<T: SignatureGenerator>(gen: &T, tokens: Vec<String>) -
     // Return tokens vector to pool for reuse
     gen.memory_pools().return_string_vec(tokens);
 
+    dedup_if_enabled(gen, &mut shingles);
     shingles
 }
 
+/// Sort and deduplicate shingles when the generator asks for it.
+///
+/// MinHash keeps the per-seed minimum, so repeats never change the signature;
+/// dropping them only skips redundant hashing.
+fn dedup_if_enabled<T: SignatureGenerator, S: Ord>(gen: &T, shingles: &mut Vec<S>) {
+    if gen.deduplicate_shingles() {
+        shingles.sort_unstable();
+        shingles.dedup();
+    }
+}
+
 /// Normalize source code for comparison using basic text processing.
-pub fn normalize_code(source_code: &str) -> String {
+///
+/// Comment lines (and import statements when `options.strip_imports` is set)
+/// are dropped, text is lowercased, whitespace is collapsed and literals are
+/// replaced with placeholders according to `options`.
+pub fn normalize_code(source_code: &str, options: NormalizationOptions) -> String {
     let mut normalized = String::new();
+    let mut open_import_block: Option<char> = None;
 
     for line in source_code.lines() {
         let line = line.trim();
+        if let Some(closer) = open_import_block {
+            if line.starts_with(closer) {
+                open_import_block = None;
+            }
+            continue;
+        }
         if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
             continue;
         }
+        if options.strip_imports && is_import_line(line) {
+            open_import_block = import_block_closer(line);
+            continue;
+        }
 
         // Basic normalization: lowercase, remove extra whitespace
-        let clean_line = line
-            .to_lowercase()
-            .split_whitespace()
-            .collect::<Vec<_>>()
-            .join(" ");
+        let lowered = line.to_lowercase();
+        let line = if options.is_enabled() {
+            normalize_literals(&lowered, options)
+        } else {
+            lowered
+        };
+        let clean_line = line.split_whitespace().collect::<Vec<_>>().join(" ");
 
         normalized.push_str(&clean_line);
         normalized.push(' ');
@@ -342,6 +574,89 @@ pub fn normalize_code(source_code: &str) -> String {
     normalized
 }
 
+/// Replace literals in a single line with placeholder tokens.
+fn normalize_literals(line: &str, options: NormalizationOptions) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let after_ident = i > 0 && is_ident_char(chars[i - 1]);
+
+        if options.normalize_strings && matches!(c, '"' | '\'' | '`') {
+            if let Some(end) = string_literal_end(&chars, i) {
+                out.push_str("<str>");
+                i = end;
+                continue;
+            }
+        }
+
+        if options.normalize_numbers && c.is_ascii_digit() && !after_ident {
+            out.push_str("<num>");
+            i = numeric_literal_end(&chars, i);
+            continue;
+        }
+
+        if options.normalize_identifiers && (c.is_alphabetic() || c == '_') && !after_ident {
+            let end = (i..chars.len())
+                .find(|&idx| !is_ident_char(chars[idx]))
+                .unwrap_or(chars.len());
+            let word: String = chars[i..end].iter().collect();
+            if NORMALIZATION_KEYWORDS.contains(&word.as_str()) {
+                out.push_str(&word);
+            } else {
+                out.push_str("<id>");
+            }
+            i = end;
+            continue;
+        }
+
+        out.push(c);
+        i += 1;
+    }
+
+    out
+}
+
+/// Returns true for characters that can continue an identifier.
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Find the index just past the closing quote of a string literal starting at `start`.
+///
+/// Returns `None` when the quote is not closed on the same line.
+fn string_literal_end(chars: &[char], start: usize) -> Option<usize> {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            c if c == quote => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Find the index just past a numeric literal (including suffixes) starting at `start`.
+fn numeric_literal_end(chars: &[char], start: usize) -> usize {
+    let is_hex = chars[start] == '0' && matches!(chars.get(start + 1), Some('x' | 'X'));
+    let mut i = start;
+    while i < chars.len() {
+        let c = chars[i];
+        let continues = is_ident_char(c)
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+            || (matches!(c, '+' | '-') && !is_hex && matches!(chars[i - 1], 'e' | 'E'));
+        if !continues {
+            break;
+        }
+        i += 1;
+    }
+    i
+}
+
 /// Hash a string with a seed using xxHash3.
 pub fn hash_with_seed(data: &str, seed: u64) -> u64 {
     let mut hasher = Xxh3::with_seed(seed);
@@ -11,7 +11,9 @@ pub mod shingles;
 pub mod types;
 pub mod weighted;
 
-pub use generator::SignatureGenerator;
+pub use generator::{NormalizationOptions, SignatureGenerator};
 pub use shingles::{count_tokens, ShingleGenerator};
 pub use types::MinHashSignature;
-pub use weighted::{WeightedMinHashSignature, WeightedShingleAnalyzer, WeightedShingleStats};
+pub use weighted::{
+    WeightedMinHashSignature, WeightedShingleAnalyzer, WeightedShingleStats, WeightedSignatureSet,
+};
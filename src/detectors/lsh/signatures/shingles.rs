@@ -9,6 +9,7 @@ use crate::core::interning::{intern, resolve, InternedString};
 
 use super::super::lsh_cache::LshCache;
 use super::super::memory_pool::LshMemoryPools;
+use super::generator::NormalizationOptions;
 
 /// Shingle generator for creating n-grams from source code.
 pub struct ShingleGenerator {
@@ -156,7 +157,7 @@ impl ShingleGenerator {
 
     /// Normalize source code for comparison using basic text processing.
     pub fn normalize_code(&self, source_code: &str) -> String {
-        super::generator::normalize_code(source_code)
+        super::generator::normalize_code(source_code, NormalizationOptions::default())
     }
 }
 
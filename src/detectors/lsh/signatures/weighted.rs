@@ -3,15 +3,17 @@
 //! This module implements TF-IDF weighted shingling to reduce the contribution
 //! of common boilerplate patterns in clone detection.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use xxhash_rust::xxh3::Xxh3;
 
 use crate::core::featureset::CodeEntity;
 
+use super::generator::NormalizationOptions;
+
 /// Summary statistics generated while building TF-IDF weighted shingles.
 #[derive(Debug, Clone)]
 pub struct WeightedShingleStats {
@@ -23,8 +25,17 @@ pub struct WeightedShingleStats {
     pub unique_grams: usize,
     /// Contribution percentage of the top 1% most frequent k-grams
     pub top1pct_contribution: f64,
+    /// 25th percentile of the IDF weights
+    pub idf_p25: f64,
+    /// Median IDF weight
+    pub idf_median: f64,
+    /// 75th percentile of the IDF weights
+    pub idf_p75: f64,
 }
 
+/// IDF inter-quartile range below which the weighting is considered non-discriminative.
+pub const MIN_DISCRIMINATIVE_IDF_IQR: f64 = 0.1;
+
 /// TF-IDF weighted shingling to reduce the contribution of common boilerplate patterns.
 #[derive(Debug)]
 pub struct WeightedShingleAnalyzer {
@@ -39,6 +50,12 @@ pub struct WeightedShingleAnalyzer {
 
     /// Pre-computed IDF weights for efficient lookup
     idf_weights: HashMap<String, f64>,
+
+    /// Fraction of entity pairs sharing a k-gram, per measured k
+    coverage_by_k: HashMap<usize, f64>,
+
+    /// Normalization applied to source code before k-grams are built
+    normalization: NormalizationOptions,
 }
 
 /// Factory, IDF table construction, and weighted signature methods for [`WeightedShingleAnalyzer`].
@@ -50,9 +67,22 @@ impl WeightedShingleAnalyzer {
             document_frequencies: HashMap::new(),
             total_documents: 0,
             idf_weights: HashMap::new(),
+            coverage_by_k: HashMap::new(),
+            normalization: NormalizationOptions::default(),
         }
     }
 
+    /// Set the normalization applied to source code before k-grams are built
+    pub fn with_normalization_options(mut self, options: NormalizationOptions) -> Self {
+        self.normalization = options;
+        self
+    }
+
+    /// Create an analyzer with the same settings and an empty IDF table
+    pub(crate) fn fresh_copy(&self) -> Self {
+        Self::new(self.k).with_normalization_options(self.normalization)
+    }
+
     /// Build global IDF table from a collection of entities
     pub fn build_idf_table(&mut self, entities: &[&CodeEntity]) -> std::result::Result<(), String> {
         info!(
@@ -164,6 +194,19 @@ impl WeightedShingleAnalyzer {
             "grams_total: {}, grams_top1pct_pctcontrib: {:.1}%",
             stats.unique_grams, stats.top1pct_contribution
         );
+        debug!(
+            "IDF weights p25: {:.3}, median: {:.3}, p75: {:.3}",
+            stats.idf_p25, stats.idf_median, stats.idf_p75
+        );
+        if !self.idf_weights.is_empty()
+            && stats.idf_p75 - stats.idf_p25 < MIN_DISCRIMINATIVE_IDF_IQR
+        {
+            warn!(
+                "IDF weights are nearly uniform (IQR {:.3}); k-gram size {} may be too large",
+                stats.idf_p75 - stats.idf_p25,
+                self.k
+            );
+        }
 
         let mut kgram_freqs: Vec<_> = self.document_frequencies.iter().collect();
         kgram_freqs.sort_by(|a, b| b.1.cmp(a.1));
@@ -182,12 +225,17 @@ impl WeightedShingleAnalyzer {
 
     /// Generate k-grams from source code tokens
     pub(crate) fn generate_kgrams(&self, source_code: &str) -> Vec<String> {
+        self.generate_kgrams_of_size(source_code, self.k)
+    }
+
+    /// Generate k-grams of an explicit size `k` from source code tokens
+    fn generate_kgrams_of_size(&self, source_code: &str, k: usize) -> Vec<String> {
         let tokens = self.tokenize_code(source_code);
         let mut kgrams = Vec::new();
 
-        if tokens.len() >= self.k {
-            for i in 0..=tokens.len() - self.k {
-                let kgram = tokens[i..i + self.k].join(" ");
+        if k > 0 && tokens.len() >= k {
+            for i in 0..=tokens.len() - k {
+                let kgram = tokens[i..i + k].join(" ");
                 kgrams.push(kgram);
             }
         }
@@ -195,6 +243,70 @@ impl WeightedShingleAnalyzer {
         kgrams
     }
 
+    /// Measure the number of distinct k-grams across `entities` for each k in `k_values`.
+    ///
+    /// Small k values produce heavily shared vocabularies (more false positives)
+    /// while large ones make nearly every shingle unique (more false negatives).
+    /// The pair coverage for each k is recorded and available via [`Self::coverage_at_k`].
+    pub fn vocabulary_size_for_k(
+        &mut self,
+        entities: &[&CodeEntity],
+        k_values: &[usize],
+    ) -> HashMap<usize, usize> {
+        let mut vocabulary_sizes = HashMap::new();
+
+        for &k in k_values {
+            let entity_kgrams: Vec<HashSet<String>> = entities
+                .iter()
+                .map(|entity| {
+                    self.generate_kgrams_of_size(&entity.source_code, k)
+                        .into_iter()
+                        .collect()
+                })
+                .collect();
+
+            let vocabulary: HashSet<&String> = entity_kgrams.iter().flatten().collect();
+            vocabulary_sizes.insert(k, vocabulary.len());
+            self.coverage_by_k
+                .insert(k, Self::pair_coverage(&entity_kgrams));
+        }
+
+        vocabulary_sizes
+    }
+
+    /// Fraction of entity pairs sharing at least one k-gram at size `k`.
+    ///
+    /// Returns 0.0 for k values not measured by [`Self::vocabulary_size_for_k`].
+    pub fn coverage_at_k(&self, k: usize) -> f64 {
+        self.coverage_by_k.get(&k).copied().unwrap_or(0.0)
+    }
+
+    /// Fraction of document pairs that share at least one k-gram.
+    fn pair_coverage(entity_kgrams: &[HashSet<String>]) -> f64 {
+        let n = entity_kgrams.len();
+        if n < 2 {
+            return 0.0;
+        }
+
+        let mut postings: HashMap<&String, Vec<usize>> = HashMap::new();
+        for (index, kgrams) in entity_kgrams.iter().enumerate() {
+            for kgram in kgrams {
+                postings.entry(kgram).or_default().push(index);
+            }
+        }
+
+        let mut partners: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+        for documents in postings.values().filter(|docs| docs.len() > 1) {
+            for (position, &first) in documents.iter().enumerate() {
+                partners[first].extend(&documents[position + 1..]);
+            }
+        }
+
+        let sharing_pairs: usize = partners.iter().map(HashSet::len).sum();
+        let total_pairs = n * (n - 1) / 2;
+        sharing_pairs as f64 / total_pairs as f64
+    }
+
     /// Tokenize source code using basic text processing (matching create_shingles approach)
     fn tokenize_code(&self, source_code: &str) -> Vec<String> {
         // Use the same normalization as create_shingles for consistency
@@ -212,46 +324,56 @@ impl WeightedShingleAnalyzer {
 
     /// Normalize source code matching the approach used in create_shingles
     fn normalize_code_like_shingles(&self, source_code: &str) -> String {
-        super::generator::normalize_code(source_code)
+        super::generator::normalize_code(source_code, self.normalization)
     }
 
     /// Compute weighted MinHash signatures for all entities
+    ///
+    /// Entities that yield no k-grams get no signature; their ids are listed in
+    /// [`WeightedSignatureSet::filtered_entities`] instead.
     pub fn compute_weighted_signatures(
         &mut self,
         entities: &[&CodeEntity],
-    ) -> std::result::Result<HashMap<String, WeightedMinHashSignature>, String> {
+    ) -> std::result::Result<WeightedSignatureSet, String> {
         // First build/update the IDF table
         self.build_idf_table(entities)?;
 
-        let mut signatures = HashMap::new();
+        let mut result = WeightedSignatureSet::default();
 
         for entity in entities {
-            let signature = self.compute_weighted_signature_for_entity(entity)?;
-            signatures.insert(entity.id.clone(), signature);
+            match self.compute_weighted_signature_for_entity(entity)? {
+                Some(signature) => {
+                    result.signatures.insert(entity.id.clone(), signature);
+                }
+                None => result.filtered_entities.push(entity.id.clone()),
+            }
         }
 
         info!(
-            "Computed weighted signatures for {} entities",
-            signatures.len()
+            "Computed weighted signatures for {} entities ({} filtered)",
+            result.signatures.len(),
+            result.filtered_entities.len()
         );
-        Ok(signatures)
+        Ok(result)
     }
 
     /// Compute weighted MinHash signature for a single entity
+    ///
+    /// Returns `None` when the normalized source is too short to form a k-gram.
     pub(crate) fn compute_weighted_signature_for_entity(
         &self,
         entity: &CodeEntity,
-    ) -> std::result::Result<WeightedMinHashSignature, String> {
+    ) -> std::result::Result<Option<WeightedMinHashSignature>, String> {
         let kgrams = self.generate_kgrams(&entity.source_code);
 
         if kgrams.is_empty() {
-            return Ok(WeightedMinHashSignature::empty());
+            return Ok(None);
         }
 
         let weighted_bag = self.build_weighted_bag(kgrams);
         let signature = self.compute_minhash_signature(&weighted_bag);
 
-        Ok(WeightedMinHashSignature::new(signature))
+        Ok(Some(WeightedMinHashSignature::new(signature)))
     }
 
     /// Build weighted bag from k-grams using IDF weights.
@@ -379,12 +501,26 @@ impl WeightedShingleAnalyzer {
         let total_grams: usize = self.document_frequencies.values().copied().sum();
         let top1pct_contribution = self.calculate_top1pct_contribution(unique_grams, total_grams);
 
+        let mut idf_values: Vec<f64> = self.idf_weights.values().copied().collect();
+        idf_values.sort_by(f64::total_cmp);
+
         WeightedShingleStats {
             total_documents: self.total_documents,
             total_grams,
             unique_grams,
             top1pct_contribution,
+            idf_p25: Self::percentile(&idf_values, 25),
+            idf_median: Self::percentile(&idf_values, 50),
+            idf_p75: Self::percentile(&idf_values, 75),
+        }
+    }
+
+    /// Nearest-rank percentile of an ascending slice; 0.0 when empty.
+    fn percentile(sorted: &[f64], pct: usize) -> f64 {
+        if sorted.is_empty() {
+            return 0.0;
         }
+        sorted[(sorted.len() - 1) * pct / 100]
     }
 
     /// Calculate the contribution percentage of the top 1% most frequent k-grams.
@@ -407,6 +543,16 @@ impl WeightedShingleAnalyzer {
     }
 }
 
+/// Weighted signatures computed for a batch of entities
+#[derive(Debug, Clone, Default)]
+pub struct WeightedSignatureSet {
+    /// Signatures keyed by entity id
+    pub signatures: HashMap<String, WeightedMinHashSignature>,
+    /// Ids of entities without a signature because normalization left no
+    /// k-grams, such as sources made up only of comments or imports
+    pub filtered_entities: Vec<String>,
+}
+
 /// Weighted MinHash signature for clone denoising
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeightedMinHashSignature {
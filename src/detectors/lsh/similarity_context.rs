@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use rayon::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize};
 use tracing::debug;
 
 use super::comparison::jaccard_similarity;
@@ -9,9 +11,18 @@ use super::config::LshConfig;
 use super::index::LshIndex;
 use super::metrics::LshContextStatistics;
 
+/// Current serialized format version of [`LshSimilarityContext`].
+pub const LSH_CONTEXT_FORMAT_VERSION: u32 = 1;
+
 /// O(n) similarity search context with prebuilt LSH index
-#[derive(Debug)]
+///
+/// Serializable so long-running pipelines can checkpoint and restore it; a
+/// payload written by a different format version fails to deserialize.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct LshSimilarityContext {
+    /// Serialized format version
+    #[serde(deserialize_with = "deserialize_format_version")]
+    pub(crate) version: u32,
     /// LSH index for efficient candidate search
     pub(crate) lsh_index: LshIndex,
     /// Signature storage for similarity computation
@@ -32,6 +43,7 @@ impl LshSimilarityContext {
         entities_count: usize,
     ) -> Self {
         Self {
+            version: LSH_CONTEXT_FORMAT_VERSION,
             lsh_index,
             signatures,
             lsh_config,
@@ -81,6 +93,63 @@ impl LshSimilarityContext {
             num_bands: self.lsh_config.num_bands,
             num_hashes: self.lsh_config.num_hashes,
             theoretical_complexity: format!("O(n) with {} bands", self.lsh_config.num_bands),
+            estimated_memory_bytes: self.estimate_memory_bytes(),
         }
     }
+
+    /// Estimate the memory held by signatures (u64 per hash) and band buckets
+    fn estimate_memory_bytes(&self) -> usize {
+        let signature_bytes =
+            self.signatures.len() * self.lsh_config.num_hashes * std::mem::size_of::<u64>();
+        let bucket_bytes = (self.lsh_index.num_band_tables() as f64
+            * self.lsh_index.average_bucket_count()
+            * std::mem::size_of::<(u64, Vec<String>)>() as f64) as usize;
+        signature_bytes + bucket_bytes
+    }
+}
+
+/// Rank entities by how unlike every other entity in `context` they are.
+///
+/// Novelty is `1.0 - ` the mean MinHash similarity to all other entities,
+/// where entities sharing no LSH band are treated as dissimilar. Only LSH
+/// candidates are compared, so cost follows candidate set sizes rather than
+/// all pairs, and an entity without candidates scores 1.0. Results are sorted
+/// most novel first, ties broken by entity ID.
+pub fn rank_by_novelty(context: &LshSimilarityContext) -> Vec<(String, f64)> {
+    let ids: Vec<&String> = context.signatures.keys().collect();
+    let others = ids.len().saturating_sub(1);
+
+    let mut ranked: Vec<(String, f64)> = ids
+        .par_iter()
+        .map(|&id| {
+            if others == 0 {
+                return (id.clone(), 1.0);
+            }
+            let total: f64 = context
+                .lsh_index
+                .find_candidates(id)
+                .iter()
+                .map(|(_, similarity)| similarity)
+                .sum();
+            (id.clone(), 1.0 - total / others as f64)
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+/// Accept only [`LSH_CONTEXT_FORMAT_VERSION`], rejecting incompatible checkpoints.
+fn deserialize_format_version<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let version = u32::deserialize(deserializer)?;
+    if version != LSH_CONTEXT_FORMAT_VERSION {
+        return Err(serde::de::Error::custom(format!(
+            "unsupported LSH context format version {} (expected {})",
+            version, LSH_CONTEXT_FORMAT_VERSION
+        )));
+    }
+    Ok(version)
 }
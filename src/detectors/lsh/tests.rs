@@ -32,18 +32,124 @@ async fn test_lsh_extractor() {
     assert!(features.contains_key("duplicate_count"));
 }
 
+#[tokio::test]
+async fn test_empty_entities_are_not_reported_as_clones() {
+    let extractor = LshExtractor::new();
+    let config = Arc::new(ValknutConfig::default());
+
+    let entity_a = entity("forward_a", "");
+    let entity_b = entity("forward_b", "");
+
+    let mut context = ExtractionContext::new(config, "rust");
+    context.add_entity(entity_a.clone());
+    context.add_entity(entity_b.clone());
+
+    let features = extractor.extract(&entity_a, &context).await.unwrap();
+
+    assert_eq!(features.get("max_similarity"), Some(&0.0));
+    assert_eq!(features.get("duplicate_count"), Some(&0.0));
+}
+
 #[test]
 fn test_shingle_creation() {
-    let extractor = LshExtractor::with_params(64, 2);
+    let extractor =
+        LshExtractor::from_config(LshConfig::builder().num_hashes(64).shingle_size(2).build());
     let code = "def func():\n    return 1";
     let shingles = extractor.create_shingles(code);
 
     assert!(!shingles.is_empty());
 }
 
+#[test]
+fn test_normalize_code_keeps_literals_by_default() {
+    let extractor = LshExtractor::new();
+    let normalized = extractor.normalize_code("if count > 42:\n    return \"Big\"");
+
+    assert_eq!(normalized, "if count > 42: return \"big\" ");
+}
+
+#[test]
+fn test_normalize_code_collapses_numeric_literals() {
+    let extractor = LshExtractor::new().with_normalization_options(NormalizationOptions {
+        normalize_numbers: true,
+        ..Default::default()
+    });
+
+    let normalized = extractor.normalize_code("x = 42 + 3.14 * 0xFF - 1e-5 + item2[0]");
+    assert_eq!(
+        normalized,
+        "x = <num> + <num> * <num> - <num> + item2[<num>] "
+    );
+
+    let a = extractor.create_shingles("if value > 10:\n    return value * 2");
+    let b = extractor.create_shingles("if value > 250:\n    return value * 7");
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_normalize_code_collapses_strings_and_identifiers() {
+    let extractor = LshExtractor::new().with_normalization_options(NormalizationOptions {
+        normalize_numbers: false,
+        normalize_strings: true,
+        normalize_identifiers: true,
+        strip_imports: false,
+    });
+
+    let normalized = extractor.normalize_code("let total = greet(\"it's\", 'x');");
+    assert_eq!(normalized, "let <id> = <id>(<str>, <str>); ");
+}
+
+#[test]
+fn test_normalize_code_strips_import_lines() {
+    let source = "import os\nfrom typing import List\nconst fs = require('fs');\nrequire(\"path\")\nuse std::fmt;\n#include <stdio.h>\nuser = imported(os)\nfrom_value = 1";
+
+    let keeps = LshExtractor::new().normalize_code(source);
+    assert!(keeps.contains("import os"));
+    assert!(keeps.contains("use std::fmt;"));
+
+    let extractor = LshExtractor::new().with_normalization_options(NormalizationOptions {
+        strip_imports: true,
+        ..Default::default()
+    });
+    assert_eq!(
+        extractor.normalize_code(source),
+        "const fs = require('fs'); user = imported(os) from_value = 1 "
+    );
+}
+
+#[test]
+fn test_normalize_code_strips_multi_line_import_blocks() {
+    let source = "import (\n    \"fmt\"\n    \"os\"\n)\nuse std::{\n    fmt,\n    io,\n};\nfunc main() {\n    fmt.println(os.args)\n}";
+    let extractor = LshExtractor::new().with_normalization_options(NormalizationOptions {
+        strip_imports: true,
+        ..Default::default()
+    });
+    assert_eq!(
+        extractor.normalize_code(source),
+        "func main() { fmt.println(os.args) } "
+    );
+}
+
+#[test]
+fn test_dedupe_config_strips_imports_only_when_requested() {
+    let config = DedupeConfig::default();
+    assert!(config.has_stop_motifs());
+    let extractor = LshExtractor::with_dedupe_config(config).unwrap();
+    assert!(!extractor.normalization_options().strip_imports);
+
+    let config = DedupeConfig {
+        strip_imports: true,
+        ..DedupeConfig::default()
+    };
+    let extractor = LshExtractor::with_dedupe_config(config).unwrap();
+    assert!(extractor.normalization_options().strip_imports);
+    assert!(!LshExtractor::new().normalization_options().strip_imports);
+}
+
 #[test]
 fn test_interned_shingle_creation() {
-    let extractor = LshExtractor::with_params(64, 2);
+    let extractor =
+        LshExtractor::from_config(LshConfig::builder().num_hashes(64).shingle_size(2).build());
     let code = "def func():\n    return 1";
 
     // Test interned shingles
@@ -63,7 +169,8 @@ fn test_interned_shingle_creation() {
 
 #[test]
 fn test_interned_minhash_signature() {
-    let extractor = LshExtractor::with_params(16, 2);
+    let extractor =
+        LshExtractor::from_config(LshConfig::builder().num_hashes(16).shingle_size(2).build());
     let code = "def test(): return 1";
 
     // Test interned signature
@@ -81,7 +188,8 @@ fn test_interned_minhash_signature() {
 
 #[test]
 fn test_minhash_signature() {
-    let extractor = LshExtractor::with_params(16, 2);
+    let extractor =
+        LshExtractor::from_config(LshConfig::builder().num_hashes(16).shingle_size(2).build());
     let code = "def test(): return 1";
     let signature = extractor.generate_minhash_signature(code);
 
@@ -111,13 +219,50 @@ fn test_lsh_index() {
     let sig1 = MinHashSignature::new(vec![1, 2, 3, 4, 5, 6, 7, 8], 8, 2);
     let sig2 = MinHashSignature::new(vec![1, 2, 3, 4, 9, 10, 11, 12], 8, 2);
 
-    index.add_entity("entity1".to_string(), sig1);
-    index.add_entity("entity2".to_string(), sig2);
+    index.add_entity("entity1", sig1);
+    index.add_entity("entity2", sig2);
 
     let candidates = index.find_candidates("entity1");
     assert!(!candidates.is_empty());
 }
 
+#[test]
+fn test_lsh_index_full_band_collision_compares_unbanded_hashes() {
+    let mut index = LshIndex::new(2);
+
+    // Two bands of two hashes cover the first four values; the trailing hash
+    // differs but is never banded, so the pair collides in every band and must
+    // still be compared in full.
+    let sig1 = MinHashSignature::new(vec![1, 2, 3, 4, 5], 5, 2);
+    let sig2 = MinHashSignature::new(vec![1, 2, 3, 4, 6], 5, 2);
+    let sig3 = MinHashSignature::new(vec![1, 2, 7, 8, 5], 5, 2);
+    assert_eq!(sig1.jaccard_similarity(&sig2), Some(0.8));
+
+    index.add_entity("entity1", sig1);
+    index.add_entity("entity2", sig2);
+    index.add_entity("entity3", sig3);
+
+    let candidates = index.find_candidates("entity1");
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0], ("entity2".to_string(), 0.8));
+    assert_eq!(candidates[1], ("entity3".to_string(), 0.6));
+}
+
+#[test]
+fn test_lsh_index_looks_up_signatures_by_id() {
+    let mut index = LshIndex::new(2);
+    let signature = MinHashSignature::new(vec![1, 2, 3, 4], 4, 2);
+    index.add_entity("src/lib.rs:parse:10", signature.clone());
+
+    assert_eq!(
+        index
+            .get_signature("src/lib.rs:parse:10")
+            .map(|sig| &sig.signature),
+        Some(&signature.signature)
+    );
+    assert!(index.get_signature("src/lib.rs:render:20").is_none());
+}
+
 #[test]
 fn test_lsh_index_returns_empty_for_missing_entity() {
     let index = LshIndex::new(2);
@@ -142,15 +287,40 @@ fn test_weighted_shingle_analyzer() {
     let signatures_result = analyzer.compute_weighted_signatures(&entities);
     assert!(signatures_result.is_ok());
 
-    let signatures = signatures_result.unwrap();
-    assert_eq!(signatures.len(), 2);
-    assert!(signatures.contains_key("test1"));
-    assert!(signatures.contains_key("test2"));
+    let result = signatures_result.unwrap();
+    assert_eq!(result.signatures.len(), 2);
+    assert!(result.signatures.contains_key("test1"));
+    assert!(result.signatures.contains_key("test2"));
+    assert!(result.filtered_entities.is_empty());
 
     let stats = analyzer.statistics();
     assert_eq!(stats.total_documents, 2);
     assert!(stats.unique_grams > 0);
     assert!(stats.top1pct_contribution >= 0.0);
+    assert!(stats.idf_p25 <= stats.idf_median);
+    assert!(stats.idf_median <= stats.idf_p75);
+}
+
+#[test]
+fn test_weighted_shingle_stats_idf_quartiles() {
+    let mut analyzer = WeightedShingleAnalyzer::new(1);
+    let stats = analyzer.statistics();
+    assert_eq!(stats.idf_median, 0.0);
+
+    // "a" appears in every document, "b" in two, "c"/"d"/"e" in one each.
+    let docs = [
+        entity("d1", "a b c"),
+        entity("d2", "a b d"),
+        entity("d3", "a e"),
+    ];
+    let refs: Vec<_> = docs.iter().collect();
+    analyzer.build_idf_table(&refs).unwrap();
+
+    let stats = analyzer.statistics();
+    let idf = |df: f64| ((1.0 + 3.0) / (1.0 + df)).ln() + 1.0;
+    assert!((stats.idf_p25 - idf(2.0)).abs() < 1e-9);
+    assert!((stats.idf_median - idf(1.0)).abs() < 1e-9);
+    assert!((stats.idf_p75 - idf(1.0)).abs() < 1e-9);
 }
 
 #[test]
@@ -181,6 +351,27 @@ fn test_kgram_generation() {
     // Should contain k-grams like "def func", "func (", etc.
 }
 
+#[test]
+fn test_vocabulary_size_and_coverage_for_k() {
+    let mut analyzer = WeightedShingleAnalyzer::new(3);
+    let shared = entity("shared_a", "a b c d");
+    let shared_twin = entity("shared_b", "a b c e");
+    let distinct = entity("distinct", "w x y z");
+    let entities = vec![&shared, &shared_twin, &distinct];
+
+    let sizes = analyzer.vocabulary_size_for_k(&entities, &[1, 3, 5]);
+
+    // k=1: a b c d e w x y z; k=3: "a b c", "b c d", "b c e", "w x y", "x y z"
+    assert_eq!(sizes[&1], 9);
+    assert_eq!(sizes[&3], 5);
+    assert_eq!(sizes[&5], 0);
+
+    assert!((analyzer.coverage_at_k(1) - 1.0 / 3.0).abs() < 1e-9);
+    assert!((analyzer.coverage_at_k(3) - 1.0 / 3.0).abs() < 1e-9);
+    assert_eq!(analyzer.coverage_at_k(5), 0.0);
+    assert_eq!(analyzer.coverage_at_k(7), 0.0);
+}
+
 #[test]
 fn test_weighted_shingle_analyzer_handles_edge_cases() {
     let mut analyzer = WeightedShingleAnalyzer::new(4);
@@ -190,7 +381,25 @@ fn test_weighted_shingle_analyzer_handles_edge_cases() {
     let signature = analyzer
         .compute_weighted_signature_for_entity(&short_entity)
         .expect("signature for short entity");
-    assert!(signature.signature.is_empty());
+    assert!(signature.is_none());
+}
+
+#[test]
+fn test_weighted_signatures_report_filtered_entities() {
+    let mut analyzer = WeightedShingleAnalyzer::new(4);
+    let body = entity(
+        "body",
+        "def total(items):\n    result = 0\n    for item in items:\n        result += item\n    return result\n",
+    );
+    let comments_only = entity("comments", "# setup\n# more setup\n");
+
+    let result = analyzer
+        .compute_weighted_signatures(&[&body, &comments_only])
+        .unwrap();
+
+    assert!(result.signatures.contains_key("body"));
+    assert!(!result.signatures.contains_key("comments"));
+    assert_eq!(result.filtered_entities, vec!["comments".to_string()]);
 }
 
 #[test]
@@ -221,6 +430,23 @@ fn test_lsh_performance_metrics_validation_paths() {
     assert!(metrics.validate_performance().is_err());
 }
 
+#[test]
+fn test_signature_cache_records_hits_and_misses() {
+    let extractor =
+        LshExtractor::from_config(LshConfig::builder().num_hashes(16).shingle_size(2).build());
+    let first = entity("first", "def total(xs): return sum(x * 2 for x in xs)");
+    let second = entity("second", "def total(xs): return sum(x * 2 for x in xs)");
+
+    for entity in [&first, &second] {
+        signatures::generator::generate_minhash_signature(&extractor, &entity.source_code);
+    }
+
+    let metrics = extractor.get_performance_metrics();
+    assert_eq!(metrics.cache_misses(), 1);
+    assert_eq!(metrics.cache_hits(), 1);
+    assert_eq!(metrics.clone().cache_hits(), 1);
+}
+
 #[test]
 fn test_lsh_extractor_configuration_helpers() {
     let mut custom_config = LshConfig::default();
@@ -250,6 +476,134 @@ fn test_lsh_extractor_configuration_helpers() {
     second_extractor.log_performance_statistics();
 }
 
+#[test]
+fn test_with_lsh_config_rounds_bands_down_to_a_divisor() {
+    let mut config = LshConfig::default();
+    config.num_hashes = 100;
+    config.num_bands = 7;
+    let extractor = LshExtractor::new().with_lsh_config(config.clone());
+    assert_eq!(extractor.num_bands(), 5);
+
+    config.num_bands = 10;
+    assert_eq!(
+        LshExtractor::new()
+            .with_lsh_config(config.clone())
+            .num_bands(),
+        10
+    );
+
+    config.num_bands = 0;
+    assert_eq!(LshExtractor::new().with_lsh_config(config).num_bands(), 1);
+}
+
+#[test]
+fn test_lsh_config_builder_overrides_defaults() {
+    let config = LshConfig::builder()
+        .num_hashes(64)
+        .num_bands(16)
+        .shingle_size(4)
+        .similarity_threshold(0.85)
+        .max_candidates(20)
+        .use_semantic_similarity(true)
+        .build();
+    assert_eq!(config.num_hashes, 64);
+    assert_eq!(config.num_bands, 16);
+    assert_eq!(config.shingle_size, 4);
+    assert_eq!(config.similarity_threshold, 0.85);
+    assert_eq!(config.max_candidates, 20);
+    assert!(config.use_semantic_similarity);
+
+    let defaults = LshConfig::default();
+    let partial = LshConfig::builder().shingle_size(5).build();
+    assert_eq!(partial.shingle_size, 5);
+    assert_eq!(partial.num_hashes, defaults.num_hashes);
+    assert_eq!(partial.num_bands, defaults.num_bands);
+}
+
+#[test]
+fn test_from_config_matches_deprecated_with_params() {
+    let extractor = LshExtractor::from_config(
+        LshConfig::builder()
+            .num_hashes(100)
+            .num_bands(7)
+            .shingle_size(2)
+            .similarity_threshold(0.9)
+            .build(),
+    );
+    assert_eq!(extractor.num_bands(), 5);
+    assert_eq!(extractor.similarity_threshold(), 0.9);
+
+    #[allow(deprecated)]
+    let legacy = LshExtractor::with_params(64, 2);
+    let modern =
+        LshExtractor::from_config(LshConfig::builder().num_hashes(64).shingle_size(2).build());
+    let code = "fn total(values: &[u32]) -> u32 { values.iter().sum() }";
+    assert_eq!(
+        legacy.generate_minhash_signature(code),
+        modern.generate_minhash_signature(code)
+    );
+}
+
+#[test]
+fn test_deduplicate_shingles_collapses_repeated_patterns() {
+    let code = format!(
+        "fn noisy() {{\n{}}}",
+        "    println!(\"tick\");\n".repeat(50)
+    );
+    let builder = || LshConfig::builder().num_hashes(64).shingle_size(3);
+    let plain = LshExtractor::from_config(builder().build());
+    let deduped = LshExtractor::from_config(builder().deduplicate_shingles(true).build());
+
+    let plain_shingles = plain.create_shingles(&code);
+    let unique_shingles = deduped.create_shingles(&code);
+    let expected: std::collections::BTreeSet<_> = plain_shingles.iter().cloned().collect();
+    assert!(plain_shingles.len() > 50);
+    assert!(unique_shingles.len() <= 5);
+    assert_eq!(unique_shingles, expected.into_iter().collect::<Vec<_>>());
+
+    // MinHash keeps the per-seed minimum, so duplicates never move the signature
+    assert_eq!(
+        plain.generate_minhash_signature(&code),
+        deduped.generate_minhash_signature(&code)
+    );
+}
+
+#[test]
+fn test_fallback_minhash_comparison_reuses_known_pairs() {
+    let code = "fn duplicated() { let value = 42; value }";
+    let extractor = LshExtractor::new();
+    let mut context = ExtractionContext::new(Arc::new(ValknutConfig::default()), "rust");
+    let members: Vec<CodeEntity> = ["a", "b", "c"].iter().map(|id| entity(id, code)).collect();
+    for member in &members {
+        context.add_entity(member.clone());
+    }
+
+    let generated = std::sync::atomic::AtomicUsize::new(0);
+    let known_pairs = std::sync::Mutex::new(KnownPairSimilarities::new());
+    for member in &members {
+        let signature = extractor.generate_minhash_signature(&member.source_code);
+        let similarities = fallback_minhash_comparison(
+            member,
+            &context,
+            &signature,
+            None,
+            usize::MAX,
+            0.5,
+            &known_pairs,
+            |source, _| {
+                generated.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                extractor.generate_minhash_signature(source)
+            },
+        );
+        assert_eq!(similarities, vec![1.0, 1.0]);
+    }
+
+    assert_eq!(generated.into_inner(), 3);
+    let known_pairs = known_pairs.into_inner().unwrap();
+    assert_eq!(known_pairs.len(), 3);
+    assert!(known_pairs.contains_key(&pair_key("c", "a")));
+}
+
 #[test]
 fn test_weighted_signature_statistics_helpers() {
     let extractor = LshExtractor::new().with_denoise_enabled(true);
@@ -271,6 +625,130 @@ fn test_weighted_signature_statistics_helpers() {
     assert_eq!(stats_only.total_documents, stats.total_documents);
 }
 
+#[test]
+fn test_weighted_signatures_use_configured_normalization() {
+    let entity1 = entity("n1", "fn scale(value: u32) -> u32 { value * 10 + 1 }");
+    let entity2 = entity("n2", "fn scale(value: u32) -> u32 { value * 250 + 7 }");
+    let entities = vec![&entity1, &entity2];
+
+    let extractor =
+        || LshExtractor::from_config(LshConfig::builder().num_hashes(64).shingle_size(3).build());
+    let verbatim = extractor().with_denoise_enabled(true);
+    let (_, verbatim_stats) = verbatim.weighted_signatures_with_stats(&entities).unwrap();
+
+    let normalized = extractor()
+        .with_denoise_enabled(true)
+        .with_normalization_options(NormalizationOptions {
+            normalize_numbers: true,
+            ..Default::default()
+        });
+    let (signatures, stats) = normalized
+        .weighted_signatures_with_stats(&entities)
+        .unwrap();
+
+    assert!(stats.unique_grams < verbatim_stats.unique_grams);
+    let analyzer = WeightedShingleAnalyzer::new(3);
+    let similarity = analyzer.weighted_jaccard_similarity(&signatures["n1"], &signatures["n2"]);
+    assert!((similarity - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_weighted_signatures_strip_imports_when_configured() {
+    let body = "def total(items):\n    result = 0\n    for item in items:\n        result += item.price * item.quantity\n    return result\n";
+    let entity1 = entity("i1", &format!("import os\nimport sys\n{body}"));
+    let entity2 = entity(
+        "i2",
+        &format!("from typing import List\nimport json\n{body}"),
+    );
+    let entities = vec![&entity1, &entity2];
+    let analyzer = WeightedShingleAnalyzer::new(9);
+    let similarity = |extractor: LshExtractor| {
+        let (signatures, _) = extractor.weighted_signatures_with_stats(&entities).unwrap();
+        analyzer.weighted_jaccard_similarity(&signatures["i1"], &signatures["i2"])
+    };
+
+    let stripped = LshExtractor::with_dedupe_config(DedupeConfig {
+        strip_imports: true,
+        ..DedupeConfig::default()
+    })
+    .unwrap()
+    .with_denoise_enabled(true);
+    assert!((similarity(stripped) - 1.0).abs() < 1e-9);
+
+    let verbatim = LshExtractor::from_config(LshConfig::builder().shingle_size(9).build())
+        .with_denoise_enabled(true);
+    assert!(similarity(verbatim) < 1.0);
+}
+
+#[cfg(feature = "persistent-cache")]
+#[test]
+fn test_signatures_persist_between_extractors_sharing_a_cache_dir() {
+    let dir = tempdir().unwrap();
+    let code = "fn total(values: &[u32]) -> u32 { values.iter().sum() }";
+    let extractor =
+        || LshExtractor::new().with_persistent_cache_dir(Some(dir.path().to_path_buf()));
+
+    let first = extractor();
+    let signature = first.generate_minhash_signature_interned(code);
+    assert_eq!(first.persistent_cache().unwrap().cache_sizes().1, 1);
+    drop(first);
+
+    let second = extractor();
+    assert_eq!(second.generate_minhash_signature_interned(code), signature);
+    assert_eq!(
+        second
+            .persistent_cache()
+            .unwrap()
+            .get_statistics()
+            .signature_hits,
+        1
+    );
+
+    let normalized = LshExtractor::new()
+        .with_normalization_options(NormalizationOptions {
+            normalize_numbers: true,
+            ..Default::default()
+        })
+        .with_persistent_cache_dir(Some(dir.path().to_path_buf()));
+    normalized.generate_minhash_signature_interned(code);
+    assert_eq!(
+        normalized
+            .persistent_cache()
+            .unwrap()
+            .get_statistics()
+            .signature_hits,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_weighted_signatures_async_matches_sync() {
+    let extractor = LshExtractor::new().with_denoise_enabled(true);
+    let entity1 = CodeEntity::new("w1", "function", "alpha", "alpha.rs")
+        .with_source_code("fn alpha() { let value = 1; value }");
+    let entity2 = CodeEntity::new("w2", "function", "beta", "beta.rs")
+        .with_source_code("fn beta() { let other = 2; other }");
+    let entities = vec![&entity1, &entity2];
+
+    let (sync_signatures, sync_stats) = extractor
+        .weighted_signatures_with_stats(&entities)
+        .expect("sync weighted signatures");
+    let (async_signatures, async_stats) = extractor
+        .weighted_signatures_with_stats_async(&entities)
+        .await
+        .expect("async weighted signatures");
+
+    assert_eq!(async_signatures.len(), sync_signatures.len());
+    assert_eq!(async_stats.total_documents, sync_stats.total_documents);
+    assert_eq!(async_stats.unique_grams, sync_stats.unique_grams);
+
+    let disabled = LshExtractor::new();
+    assert!(disabled
+        .weighted_signatures_with_stats_async(&entities)
+        .await
+        .is_err());
+}
+
 #[tokio::test]
 async fn test_entity_threshold_short_circuit_behavior() {
     let extractor = LshExtractor::new();
@@ -283,7 +761,8 @@ async fn test_entity_threshold_short_circuit_behavior() {
         .expect("no config should bypass thresholds"));
 
     let config = DedupeConfig::default();
-    let extractor_with_config = LshExtractor::with_dedupe_config(config);
+    let extractor_with_config =
+        LshExtractor::with_dedupe_config(config).expect("default dedupe config is valid");
     assert!(!extractor_with_config
         .entity_passes_thresholds(&entity)
         .await
@@ -323,6 +802,37 @@ fn test_similarity_context_cache_is_invalidateable() {
     );
 }
 
+#[test]
+fn test_similarity_context_rebuilds_when_entities_added() {
+    let extractor = LshExtractor::new();
+    let config = Arc::new(ValknutConfig::default());
+    let mut context = ExtractionContext::new(config, "rust");
+
+    context.add_entity(entity("first_a", "fn alpha() { 1 + 2 }"));
+    context.add_entity(entity("first_b", "fn beta() { 1 + 2 }"));
+
+    let first_batch = extractor
+        .similarity_context(&context)
+        .expect("context should be built for the first batch");
+    assert_eq!(first_batch.get_statistics().entities_count, 2);
+
+    context.add_entity(entity("second_a", "fn gamma() { 3 + 4 }"));
+
+    let second_batch = extractor
+        .similarity_context(&context)
+        .expect("context should rebuild for the second batch");
+    assert!(
+        !Arc::ptr_eq(&first_batch, &second_batch),
+        "adding entities should invalidate the similarity context"
+    );
+    assert_eq!(second_batch.get_statistics().entities_count, 3);
+
+    let cached = extractor
+        .similarity_context(&context)
+        .expect("unchanged context should be cached");
+    assert!(Arc::ptr_eq(&second_batch, &cached));
+}
+
 #[tokio::test]
 async fn test_candidate_filter_bruteforce_uses_weighted_cache() {
     let extractor = LshExtractor::new().with_denoise_enabled(true);
@@ -360,6 +870,117 @@ async fn test_candidate_filter_bruteforce_uses_weighted_cache() {
     );
 }
 
+/// Records `(span, field, value)` triples for every span created or updated.
+#[derive(Clone, Default)]
+struct SpanFieldRecorder {
+    fields: Arc<std::sync::Mutex<Vec<(String, String, String)>>>,
+}
+
+/// Field visitor that stores values against the owning span name.
+struct SpanFieldVisitor<'a> {
+    span: &'static str,
+    fields: &'a mut Vec<(String, String, String)>,
+}
+
+/// [`tracing::field::Visit`] implementation that formats every value with `Debug`.
+impl tracing::field::Visit for SpanFieldVisitor<'_> {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.fields.push((
+            self.span.to_string(),
+            field.name().to_string(),
+            format!("{value:?}"),
+        ));
+    }
+}
+
+/// [`tracing_subscriber::Layer`] implementation capturing span fields.
+impl<S> tracing_subscriber::Layer<S> for SpanFieldRecorder
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        _id: &tracing::span::Id,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut fields = self.fields.lock().unwrap();
+        attrs.record(&mut SpanFieldVisitor {
+            span: attrs.metadata().name(),
+            fields: &mut fields,
+        });
+    }
+
+    fn on_record(
+        &self,
+        id: &tracing::span::Id,
+        values: &tracing::span::Record<'_>,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let span = ctx.span(id).expect("recorded span exists").name();
+        let mut fields = self.fields.lock().unwrap();
+        values.record(&mut SpanFieldVisitor {
+            span,
+            fields: &mut fields,
+        });
+    }
+}
+
+#[tokio::test]
+async fn test_compare_with_others_records_comparison_spans() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let recorder = SpanFieldRecorder::default();
+    let subscriber = tracing_subscriber::registry().with(recorder.clone());
+    let _default = tracing::subscriber::set_default(subscriber);
+
+    let extractor = LshExtractor::new();
+    let entity_a = entity("entity_a", "fn duplicated() { let value = 42; value }");
+    let entity_b = entity("entity_b", "fn duplicated() { let value = 42; value }");
+
+    let mut context = ExtractionContext::new(Arc::new(ValknutConfig::default()), "rust");
+    context.add_entity(entity_a.clone());
+    context.add_entity(entity_b.clone());
+    let context = context.with_candidate_partitions(Arc::new(HashMap::from([(
+        entity_a.id.clone(),
+        vec![entity_b.id.clone()],
+    )])));
+
+    extractor
+        .extract(&entity_a, &context)
+        .await
+        .expect("extraction succeeds");
+
+    let fields = recorder.fields.lock().unwrap().clone();
+    let value = |span: &str, field: &str| {
+        fields
+            .iter()
+            .find(|(s, f, _)| s == span && f == field)
+            .map(|(_, _, value)| value.clone())
+    };
+    assert_eq!(
+        value("lsh_compare", "entity_id").as_deref(),
+        Some("entity_a")
+    );
+    assert_eq!(
+        value("lsh_compare", "entity_index_size").as_deref(),
+        Some("2")
+    );
+    assert_eq!(
+        value("lsh_compare", "candidates_found").as_deref(),
+        Some("1")
+    );
+    assert!(value("lsh_compare", "duration_ms").is_some());
+    assert_eq!(
+        value("lsh_bruteforce_compare", "candidate_count").as_deref(),
+        Some("1")
+    );
+    assert_eq!(
+        value("lsh_bruteforce_compare", "candidates_found").as_deref(),
+        Some("1")
+    );
+}
+
 #[tokio::test]
 async fn test_partitions_without_entry_skip_similarity_search() {
     let extractor = LshExtractor::new().with_denoise_enabled(true);
@@ -431,7 +1052,8 @@ fn long_enough() {\n    let mut total = 0;\n    for value in 0..5 {\n        tot
     config.min_ast_nodes = 20;
     config.require_distinct_blocks = 1;
 
-    let extractor = LshExtractor::with_dedupe_config(config.clone());
+    let extractor =
+        LshExtractor::with_dedupe_config(config.clone()).expect("dedupe config is valid");
 
     let short_entity = CodeEntity::new(
         "short",
@@ -486,6 +1108,37 @@ fn long_enough() {\n    let mut total = 0;\n    for value in 0..5 {\n        tot
     );
 }
 
+#[tokio::test]
+async fn test_adaptive_threshold_settles_on_the_whole_entity_set() {
+    let mut config = DedupeConfig::default();
+    config.min_function_tokens = 50;
+    config.adaptive.max_denoise_threshold = 0.5;
+    config.adaptive.step_size = 0.25;
+    let extractor = LshExtractor::with_dedupe_config(config.clone()).expect("valid config");
+    let threshold = extractor
+        .adaptive_threshold
+        .as_ref()
+        .expect("adaptive range");
+    let entity =
+        CodeEntity::new("tiny", "function", "tiny", "tiny.rs").with_source_code("fn tiny() {}");
+
+    // Single-entity checks read the factor without moving it.
+    for _ in 0..8 {
+        assert!(!extractor.entity_passes_thresholds(&entity).await.unwrap());
+        assert!(!extractor
+            .meets_fragment_thresholds(&entity, &config)
+            .await
+            .unwrap());
+    }
+    assert_eq!(threshold.current(), 0.0);
+
+    assert!(extractor
+        .filter_by_thresholds(vec![entity.clone(); 8])
+        .await
+        .is_empty());
+    assert_eq!(threshold.current(), 0.5);
+}
+
 #[test]
 fn test_similarity_context_cache_reuses_last_context() {
     let extractor = LshExtractor::new();
@@ -513,15 +1166,12 @@ fn test_similarity_context_cache_reuses_last_context() {
 #[test]
 fn test_generate_cache_key_is_order_insensitive() {
     let extractor = LshExtractor::new().with_denoise_enabled(true);
-    let entity_a = CodeEntity::new("alpha", "function", "alpha", "alpha.rs")
-        .with_source_code("fn alpha() { 1 }");
-    let entity_b =
-        CodeEntity::new("beta", "function", "beta", "beta.rs").with_source_code("fn beta() { 2 }");
 
-    let forward_key = extractor.generate_cache_key(&[&entity_a, &entity_b]);
-    let reverse_key = extractor.generate_cache_key(&[&entity_b, &entity_a]);
+    let forward_key = extractor.generate_cache_key(["alpha", "beta"]);
+    let reverse_key = extractor.generate_cache_key(["beta", "alpha"]);
 
     assert_eq!(forward_key, reverse_key);
+    assert_ne!(forward_key, extractor.generate_cache_key(["alpha"]));
 }
 
 #[test]
@@ -563,7 +1213,8 @@ fn test_weighted_signature_cache_hits() {
 
 #[test]
 fn test_shingle_variants_produce_consistent_lengths() {
-    let extractor = LshExtractor::with_params(32, 3);
+    let extractor =
+        LshExtractor::from_config(LshConfig::builder().num_hashes(32).shingle_size(3).build());
     let code = "fn compute(value: i32) -> i32 { if value > 0 { value } else { -value } }";
     let standard = extractor.create_shingles(code);
     let interned = extractor.create_shingles_interned(code);
@@ -571,3 +1222,445 @@ fn test_shingle_variants_produce_consistent_lengths() {
     assert_eq!(standard.len(), interned.len());
     assert!(!standard.is_empty());
 }
+
+#[test]
+fn test_similarity_context_statistics_estimate_memory() {
+    let extractor = LshExtractor::new();
+    let entity_a = entity("mem_alpha", "fn alpha() { let a = 1; a + 2 }");
+    let entity_b = entity("mem_beta", "fn beta() { let b = 3; b * 4 }");
+
+    let context = extractor.create_similarity_search_context(&[&entity_a, &entity_b]);
+    let stats = context.get_statistics();
+
+    let signature_bytes = 2 * stats.num_hashes * std::mem::size_of::<u64>();
+    assert!(stats.estimated_memory_bytes >= signature_bytes);
+    assert!(stats.estimated_memory_mb() > 0.0);
+    assert!(
+        (stats.estimated_memory_mb() * 1024.0 * 1024.0 - stats.estimated_memory_bytes as f64).abs()
+            < 1e-6
+    );
+}
+
+#[test]
+fn test_with_dedupe_config_rejects_permissive_thresholds() {
+    let mut config = DedupeConfig::default();
+    config.min_function_tokens = 0;
+
+    let err = LshExtractor::with_dedupe_config(config).expect_err("zero tokens should fail");
+    assert!(err.to_string().contains("min_function_tokens"));
+}
+
+/// Canary for the seeded xxHash3 used to build MinHash signatures.
+///
+/// Known values (xxhash-rust 0.8, `str` hashed via `Hash` so a 0xff
+/// terminator follows the bytes):
+/// - `hash_with_seed("hello world", 42)` = 10453631160505676801
+/// - `hash_with_seed("hello world", 0)`  = 1312102073844821397
+/// - `hash_with_seed("", 42)`            = 12089293768444341469
+///
+/// If this fails after a dependency upgrade, persisted LSH caches and stored
+/// signatures are no longer comparable and must be regenerated.
+#[test]
+fn test_hash_with_seed_is_stable_across_versions() {
+    use super::signatures::generator::hash_with_seed;
+
+    assert_eq!(hash_with_seed("hello world", 42), 10453631160505676801);
+    assert_eq!(hash_with_seed("hello world", 0), 1312102073844821397);
+    assert_eq!(hash_with_seed("", 42), 12089293768444341469);
+    assert_eq!(
+        hash_with_seed("hello world", 42),
+        hash_with_seed("hello world", 42),
+        "seeded hashing must be deterministic"
+    );
+
+    #[cfg(feature = "simd")]
+    assert_eq!(
+        super::signatures::generator::hash_with_seed_fast("hello world", 42),
+        hash_with_seed("hello world", 42),
+        "SIMD batch hashing must agree with the scalar path"
+    );
+}
+
+#[test]
+fn test_weighted_ranking_demotes_boilerplate_clone_pair() {
+    let boilerplate = "let config = load_config();\nlet logger = init_logger(&config);\nlet client = connect(&config);\nlet metrics = Metrics::register(&config);\nlet cache = Cache::open(&config);\nlogger.info(\"starting\");\nmetrics.increment(\"runs\");\nclient.ping();\ncache.warm();\n";
+    let mut entities: Vec<CodeEntity> = (0..40)
+        .map(|i| {
+            entity(
+                &format!("filler_{i}"),
+                &format!("fn filler_{i}() {{\n{boilerplate}filler_step_{i}(&client);\n}}"),
+            )
+        })
+        .collect();
+    entities.push(entity(
+        "boiler_a",
+        &format!("fn boiler_a() {{\n{boilerplate}export_invoices(&client);\n}}"),
+    ));
+    entities.push(entity(
+        "boiler_b",
+        &format!("fn boiler_b() {{\n{boilerplate}purge_sessions(&client);\n}}"),
+    ));
+    let structural = "let mut heap = BinaryHeap::new();\nheap.push(Reverse((0, start)));\nwhile let Some(Reverse((dist, node))) = heap.pop() {\nfor (next, weight) in graph.edges(node) {\nrelax(&mut best, next, dist + weight);\n}\n}\n";
+    entities.push(entity(
+        "shortest_a",
+        &format!("fn shortest_a(graph: &Graph) {{\n{structural}best\n}}"),
+    ));
+    entities.push(entity(
+        "shortest_b",
+        &format!("fn shortest_b(graph: &Graph, limit: usize) {{\nlet mut seen = 0;\n{structural}seen += best.len().min(limit);\nseen\n}}"),
+    ));
+    let refs: Vec<&CodeEntity> = entities.iter().collect();
+
+    let extractor = LshExtractor::new().with_denoise_enabled(true);
+    assert_eq!(extractor.ranking_config().by, RankingBy::WeightedSimilarity);
+
+    let raw = |a: &CodeEntity, b: &CodeEntity| {
+        jaccard_similarity(
+            &extractor.generate_minhash_signature(&a.source_code),
+            &extractor.generate_minhash_signature(&b.source_code),
+        )
+    };
+    let boiler_raw = raw(&entities[40], &entities[41]);
+    let structural_raw = raw(&entities[42], &entities[43]);
+    assert!(
+        boiler_raw > structural_raw,
+        "boilerplate pair should lead on raw Jaccard ({boiler_raw} vs {structural_raw})"
+    );
+
+    let pairs = vec![
+        ("boiler_a", "boiler_b", boiler_raw),
+        ("shortest_a", "shortest_b", structural_raw),
+    ];
+
+    // Without a weighted analyzer, weighted ranking falls back to raw Jaccard
+    let mut standard = pairs.clone();
+    LshExtractor::new()
+        .with_ranking_config(RankingConfig {
+            by: RankingBy::WeightedSimilarity,
+            ..RankingConfig::default()
+        })
+        .rank_clone_pairs(&mut standard, |pair| *pair, &refs);
+    assert_eq!(standard[0].0, "boiler_a");
+
+    let mut weighted = pairs;
+    extractor.rank_clone_pairs(&mut weighted, |pair| *pair, &refs);
+    assert_eq!(weighted[0].0, "shortest_a");
+    assert_eq!(weighted[1].0, "boiler_a");
+}
+
+#[test]
+fn test_weighted_ranking_falls_back_without_weighted_analyzer() {
+    let extractor = LshExtractor::new().with_ranking_config(RankingConfig {
+        by: RankingBy::WeightedSimilarity,
+        ..RankingConfig::default()
+    });
+    let refs: Vec<&CodeEntity> = Vec::new();
+
+    let mut pairs = vec![("a", "b", 0.6), ("c", "d", 0.9), ("e", "f", 0.6)];
+    extractor.rank_clone_pairs(&mut pairs, |pair| *pair, &refs);
+    assert_eq!(
+        pairs,
+        vec![("c", "d", 0.9), ("a", "b", 0.6), ("e", "f", 0.6)]
+    );
+}
+
+fn clone_candidate(entity_id: &str, score: f64) -> crate::core::pipeline::RefactoringCandidate {
+    crate::core::pipeline::RefactoringCandidate {
+        entity_id: entity_id.to_string(),
+        name: entity_id.to_string(),
+        file_path: format!("src/{entity_id}.rs"),
+        line_range: Some((1, 10)),
+        priority: crate::core::scoring::Priority::Medium,
+        score,
+        confidence: 0.8,
+        issues: Vec::new(),
+        suggestions: Vec::new(),
+        issue_count: 0,
+        suggestion_count: 0,
+        coverage_percentage: None,
+    }
+}
+
+fn clone_pair_json(source: &str, target: &str) -> serde_json::Value {
+    serde_json::json!({
+        "source": { "id": source, "path": format!("src/{source}.rs") },
+        "target": { "id": target, "path": format!("src/{target}.rs") },
+    })
+}
+
+#[test]
+fn test_clone_clusters_group_connected_pairs() {
+    let clusters = clone_clusters(&[
+        clone_pair_json("a", "b"),
+        clone_pair_json("c", "b"),
+        clone_pair_json("x", "y"),
+        clone_pair_json("z", "z"),
+        serde_json::json!({ "source": { "id": "broken" } }),
+    ]);
+
+    assert_eq!(clusters.len(), 2);
+    let abc = clusters.iter().find(|c| c.members.contains("a")).unwrap();
+    assert_eq!(abc.members.len(), 3);
+    assert_eq!(abc.files.len(), 3);
+    assert!((abc.density() - 2.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_deduplicate_results_merges_cluster_members() {
+    let clusters = clone_clusters(&[clone_pair_json("a", "b"), clone_pair_json("b", "c")]);
+    let candidates = vec![
+        clone_candidate("solo", 90.0),
+        clone_candidate("b", 60.0),
+        clone_candidate("other", 50.0),
+        clone_candidate("a", 75.0),
+    ];
+
+    let deduplicated = deduplicate_results(candidates, &clusters);
+
+    let ids: Vec<&str> = deduplicated.iter().map(|c| c.entity_id.as_str()).collect();
+    assert_eq!(ids, vec!["solo", "b", "other"]);
+    let merged = &deduplicated[1];
+    assert_eq!(merged.score, 75.0);
+    assert_eq!(merged.name, "b (+2 clones in src/a.rs, src/b.rs, src/c.rs)");
+    assert_eq!(merged.suggestions[0].code, "DEDUP");
+    assert_eq!(merged.suggestion_count, 1);
+
+    // A cluster with a single candidate member is left alone.
+    let single = deduplicate_results(vec![clone_candidate("a", 10.0)], &clusters);
+    assert_eq!(single[0].name, "a");
+    assert!(single[0].suggestions.is_empty());
+}
+
+#[tokio::test]
+async fn test_find_near_duplicates_in_files_classifies_pairs() {
+    let dir = tempdir().unwrap();
+    let body = "    total = 0\n    skipped = 0\n    for item in items:\n        if not item.active:\n            skipped += 1\n            continue\n        price = item.price * item.quantity\n        if item.discount:\n            price -= price * item.discount\n        total += price\n    if skipped:\n        log_skipped(skipped)\n    return round(total, 2)\n";
+    let first = dir.path().join("orders.py");
+    let second = dir.path().join("invoices.py");
+    let unrelated = dir.path().join("notes.txt");
+    fs::write(&first, format!("def order_total(items):\n{body}")).unwrap();
+    fs::write(
+        &second,
+        format!("def order_total(items):\n{body}\n\ndef invoice_total(items):\n{body}"),
+    )
+    .unwrap();
+    fs::write(&unrelated, "not source code").unwrap();
+
+    let extractor = LshExtractor::new();
+    let pairs = extractor
+        .find_near_duplicates_in_files(&[first, second, unrelated])
+        .await
+        .unwrap();
+
+    assert!(pairs
+        .iter()
+        .all(|pair| pair.similarity >= extractor.similarity_threshold()));
+    assert!(pairs
+        .windows(2)
+        .all(|window| window[0].similarity >= window[1].similarity));
+
+    let pairs: Vec<_> = pairs
+        .into_iter()
+        .filter(|pair| {
+            pair.entity_a.name.ends_with("_total") && pair.entity_b.name.ends_with("_total")
+        })
+        .collect();
+    assert_eq!(pairs.len(), 3);
+
+    let exact: Vec<_> = pairs
+        .iter()
+        .filter(|pair| pair.clone_type == CloneType::Exact)
+        .collect();
+    assert_eq!(exact.len(), 1);
+    assert_eq!(exact[0].entity_a.name, exact[0].entity_b.name);
+    assert!(pairs
+        .iter()
+        .filter(|pair| pair.clone_type != CloneType::Exact)
+        .all(|pair| pair.clone_type == CloneType::Renamed));
+}
+
+#[tokio::test]
+async fn test_find_near_duplicates_in_files_reports_missing_file() {
+    let dir = tempdir().unwrap();
+    let extractor = LshExtractor::new();
+
+    let result = extractor
+        .find_near_duplicates_in_files(&[dir.path().join("missing.py")])
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_similarity_context_roundtrips_through_serde() {
+    let extractor = LshExtractor::new();
+    let entities: Vec<CodeEntity> = (0..100)
+        .map(|i| {
+            entity(
+                &format!("entity_{i}"),
+                &format!(
+                    "fn compute_{i}(values: &[u64]) -> u64 {{ let mut total = {}; for v in values {{ total += v * {}; }} total }}",
+                    i % 7,
+                    i % 5
+                ),
+            )
+        })
+        .collect();
+    let refs: Vec<&CodeEntity> = entities.iter().collect();
+    let context = extractor.create_similarity_search_context(&refs);
+
+    let json = serde_json::to_string(&context).unwrap();
+    let restored: LshSimilarityContext = serde_json::from_str(&json).unwrap();
+
+    let sorted = |mut results: Vec<(String, f64)>| {
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        results
+    };
+    let mut total_matches = 0;
+    for entity in &entities {
+        let original = sorted(context.find_similar_entities(&entity.id, None));
+        total_matches += original.len();
+        assert_eq!(
+            original,
+            sorted(restored.find_similar_entities(&entity.id, None))
+        );
+    }
+    assert!(total_matches > 0);
+    assert_eq!(restored.get_statistics().entities_count, 100);
+
+    let mut stale: serde_json::Value = serde_json::from_str(&json).unwrap();
+    stale["version"] = serde_json::json!(LSH_CONTEXT_FORMAT_VERSION + 1);
+    let err = serde_json::from_value::<LshSimilarityContext>(stale).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unsupported LSH context format version"));
+
+    let mut no_bands: serde_json::Value = serde_json::from_str(&json).unwrap();
+    no_bands["lsh_index"]["num_bands"] = serde_json::json!(0);
+    no_bands["lsh_index"]["bands"] = serde_json::json!([]);
+    let err = serde_json::from_value::<LshSimilarityContext>(no_bands).unwrap_err();
+    assert!(err.to_string().contains("at least one band"));
+
+    let mut mismatched: serde_json::Value = serde_json::from_str(&json).unwrap();
+    mismatched["lsh_index"]["bands"]
+        .as_array_mut()
+        .unwrap()
+        .pop();
+    let err = serde_json::from_value::<LshSimilarityContext>(mismatched).unwrap_err();
+    assert!(err.to_string().contains("band tables but num_bands is"));
+}
+
+#[test]
+fn test_rank_by_novelty_surfaces_entities_without_peers() {
+    let extractor = LshExtractor::new();
+    let clone_source = |name: &str| {
+        format!(
+            "fn {name}(values: &[u64]) -> u64 {{ let mut total = 0; for v in values {{ total += v * 2; }} total }}"
+        )
+    };
+    let entities = vec![
+        entity("clone_a", &clone_source("sum_a")),
+        entity("clone_b", &clone_source("sum_b")),
+        entity("clone_c", &clone_source("sum_c")),
+        entity(
+            "parser",
+            "fn parse(input: &str) -> Option<Token> { match input.chars().next()? { '(' => Some(Token::Open), ')' => Some(Token::Close), c if c.is_alphabetic() => Some(Token::Word(input.to_string())), _ => None } }",
+        ),
+    ];
+    let refs: Vec<&CodeEntity> = entities.iter().collect();
+    let context = extractor.create_similarity_search_context(&refs);
+
+    let ranked = rank_by_novelty(&context);
+
+    assert_eq!(ranked.len(), 4);
+    assert_eq!(ranked[0].0, "parser");
+    assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    assert!(ranked
+        .iter()
+        .all(|(_, novelty)| (0.0..=1.0).contains(novelty)));
+
+    let single = extractor.create_similarity_search_context(&refs[..1]);
+    assert_eq!(rank_by_novelty(&single), vec![("clone_a".to_string(), 1.0)]);
+}
+
+#[test]
+fn test_rank_by_novelty_only_compares_lsh_candidates() {
+    // Every signature shares its first hash but no whole band, so an all-pairs
+    // comparison would see a small similarity while no LSH candidates exist.
+    const ENTITY_COUNT: u64 = 5_000;
+    let config = LshConfig::default();
+    let mut index = LshIndex::new(config.num_bands);
+    let mut signatures = HashMap::new();
+    for entity in 0..ENTITY_COUNT {
+        let signature: Vec<u64> = (0..config.num_hashes as u64)
+            .map(|hash| if hash == 0 { 0 } else { entity * 1_000 + hash })
+            .collect();
+        let id = format!("entity_{entity}");
+        index.add_entity(
+            &id,
+            MinHashSignature::new(signature.clone(), config.num_hashes, config.shingle_size),
+        );
+        signatures.insert(id, signature);
+    }
+    let context = LshSimilarityContext::new(index, signatures, config, ENTITY_COUNT as usize);
+
+    let ranked = rank_by_novelty(&context);
+
+    assert_eq!(ranked.len(), ENTITY_COUNT as usize);
+    assert!(ranked.iter().all(|(_, novelty)| *novelty == 1.0));
+}
+
+#[test]
+fn test_rank_clone_pairs_orders_by_each_criterion() {
+    let sized = |id: &str, tokens: usize| entity(id, &"tok ".repeat(tokens));
+    let entities = vec![
+        sized("a1", 100),
+        sized("a2", 100),
+        sized("b1", 10),
+        sized("b2", 10),
+        sized("b3", 10),
+        sized("c1", 20),
+        sized("c2", 20),
+    ];
+    let refs: Vec<&CodeEntity> = entities.iter().collect();
+    let pairs = vec![
+        ("a1", "a2", 0.5),
+        ("b1", "b2", 0.9),
+        ("b2", "b3", 0.9),
+        ("c1", "c2", 0.95),
+    ];
+    let ranked = |by: RankingBy| {
+        let extractor = LshExtractor::new().with_ranking_config(RankingConfig {
+            by,
+            ..RankingConfig::default()
+        });
+        let mut ranked = pairs.clone();
+        extractor.rank_clone_pairs(&mut ranked, |pair| *pair, &refs);
+        ranked
+            .into_iter()
+            .map(|(source, _, _)| source)
+            .collect::<Vec<_>>()
+    };
+
+    // Saved tokens: a = 0.5 * 100 * 2, c = 0.95 * 20 * 2, b = 0.9 * 10 * 3
+    assert_eq!(ranked(RankingBy::SavedTokens), vec!["a1", "c1", "b1", "b2"]);
+    // Frequency: the three-member b group leads, ties fall back to similarity
+    assert_eq!(ranked(RankingBy::Frequency), vec!["b1", "b2", "c1", "a1"]);
+    // Weighted similarity without a weighted analyzer uses raw similarity
+    assert_eq!(
+        ranked(RankingBy::WeightedSimilarity),
+        vec!["c1", "b1", "b2", "a1"]
+    );
+}
+
+#[test]
+fn test_ranking_config_converts_from_core_config() {
+    let core = crate::core::config::RankingConfig {
+        by: crate::core::config::RankingBy::Frequency,
+        ..crate::core::config::RankingConfig::default()
+    };
+    let extractor = LshExtractor::new()
+        .with_ranking_config(core.into())
+        .with_denoise_enabled(true);
+    assert_eq!(extractor.ranking_config().by, RankingBy::Frequency);
+}
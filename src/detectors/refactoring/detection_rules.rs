@@ -1,12 +1,16 @@
 //! Refactoring detection rules for identifying code improvement opportunities.
 //!
 //! This module contains the heuristics for detecting various refactoring opportunities
-//! such as long methods, complex conditionals, duplicate code, and large types.
+//! such as long methods, complex conditionals, duplicate code, large types,
+//! long parameter lists, and speculative generality.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
 
 use crate::core::featureset::CodeEntity;
 use crate::detectors::complexity::ComplexityMetrics as AnalyzerComplexityMetrics;
+use crate::lang::{EntityKind, ParseIndex, ParsedEntity};
 
 use super::{RefactoringRecommendation, RefactoringType};
 
@@ -22,6 +26,296 @@ pub const COMPLEX_CONDITIONAL_THRESHOLD: usize = 4;
 pub const DUPLICATE_MIN_LINE_COUNT: usize = 4;
 /// Minimum tokens required before we consider a block a meaningful duplication target
 pub const DUPLICATE_MIN_TOKEN_COUNT: usize = 10;
+/// Parameter count above which a function has a long parameter list
+pub const LONG_PARAMETER_LIST_THRESHOLD: usize = 5;
+/// Issue code reported for long parameter lists
+pub const LONG_PARAMETER_LIST_CODE: &str = "R_LONG_PARAM";
+/// Minimum parameters sharing a name prefix before they are suggested as a group
+const PARAMETER_GROUP_MIN_SIZE: usize = 2;
+/// Receiver parameters that never belong in a parameter object
+const RECEIVER_PARAMETERS: &[&str] = &["self", "cls", "this"];
+
+/// Metadata keys that name the types a class implements or inherits from
+const IMPLEMENTATION_KEYS: &[&str] = &["implements", "extends", "base_classes"];
+/// Associated functions treated as constructing their type (e.g. `Point::new`)
+const CONSTRUCTOR_NAMES: &[&str] = &["new", "default"];
+
+/// Form of speculative generality found on an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeculativeGeneralityKind {
+    /// Interface implemented by exactly one class
+    SingleImplementor,
+    /// Struct constructed at exactly one site
+    SingleInstantiation,
+    /// Generic function whose call sites all pass the same type arguments
+    SingleTypeArguments,
+}
+
+/// Diagnosis for an abstraction that has only one concrete use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeculativeGeneralityDiagnosis {
+    /// Id of the speculative entity
+    pub entity_id: String,
+    /// Name of the speculative entity
+    pub name: String,
+    /// Which form of speculative generality was found
+    pub kind: SpeculativeGeneralityKind,
+    /// Line range of the entity (start_line, end_line)
+    pub location: (usize, usize),
+    /// The sole implementor, the entity holding the sole instantiation, or the concrete type arguments
+    pub concrete_uses: Vec<String>,
+}
+
+/// Detect abstractions with a single concrete use in a parse index.
+///
+/// Relies on adapter metadata: `implements`/`extends`/`base_classes` for
+/// implementors, `function_calls` and `instantiations` for construction and
+/// call sites, and `generic_parameters` for generic functions. Generic functions
+/// with any call site that infers its type arguments are not reported.
+///
+/// The index covers a single file, so only file-private entities are checked;
+/// anything other files could reference may have uses this index cannot see.
+pub fn detect_speculative_generality(index: &ParseIndex) -> Vec<SpeculativeGeneralityDiagnosis> {
+    let mut diagnoses: Vec<_> = index
+        .entities
+        .values()
+        .filter(|entity| is_file_private(entity))
+        .filter_map(|entity| {
+            let (kind, concrete_uses) = match entity.kind {
+                EntityKind::Interface => (
+                    SpeculativeGeneralityKind::SingleImplementor,
+                    sole_implementor(index, entity)?,
+                ),
+                EntityKind::Struct => (
+                    SpeculativeGeneralityKind::SingleInstantiation,
+                    sole_instantiation_site(index, entity)?,
+                ),
+                EntityKind::Function | EntityKind::Method => (
+                    SpeculativeGeneralityKind::SingleTypeArguments,
+                    sole_type_arguments(index, entity)?,
+                ),
+                _ => return None,
+            };
+            Some(SpeculativeGeneralityDiagnosis {
+                entity_id: entity.id.clone(),
+                name: entity.name.clone(),
+                kind,
+                location: (entity.location.start_line, entity.location.end_line),
+                concrete_uses,
+            })
+        })
+        .collect();
+
+    diagnoses.sort_by(|a, b| a.location.cmp(&b.location).then(a.name.cmp(&b.name)));
+    diagnoses
+}
+
+/// Whether an entity cannot be referenced from other files.
+///
+/// Uses the Rust `visibility` and TypeScript `exported` metadata; entities
+/// without either are assumed to be reachable from elsewhere.
+fn is_file_private(entity: &ParsedEntity) -> bool {
+    if let Some(visibility) = entity.metadata.get("visibility").and_then(|v| v.as_str()) {
+        return !visibility.trim_start().starts_with("pub");
+    }
+    entity.metadata.get("exported").and_then(|v| v.as_bool()) == Some(false)
+}
+
+/// String values of a metadata entry that may hold a single string or an array.
+fn metadata_strings<'a>(entity: &'a ParsedEntity, key: &str) -> Vec<&'a str> {
+    match entity.metadata.get(key) {
+        Some(serde_json::Value::String(value)) => vec![value.as_str()],
+        Some(serde_json::Value::Array(values)) => {
+            values.iter().filter_map(|value| value.as_str()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Path segments of a type or call target with type arguments removed.
+fn path_segments(target: &str) -> Vec<&str> {
+    let path = target.split('<').next().unwrap_or_default();
+    path.split(|c| c == ':' || c == '.')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Name of the only class implementing `interface`, if exactly one does.
+fn sole_implementor(index: &ParseIndex, interface: &ParsedEntity) -> Option<Vec<String>> {
+    let mut implementors = index.entities.values().filter(|entity| {
+        entity.kind == EntityKind::Class
+            && IMPLEMENTATION_KEYS.iter().any(|key| {
+                metadata_strings(entity, key)
+                    .into_iter()
+                    .any(|base| path_segments(base).last() == Some(&interface.name.as_str()))
+            })
+    });
+
+    let implementor = implementors.next()?;
+    if implementors.next().is_some() {
+        return None;
+    }
+    Some(vec![implementor.name.clone()])
+}
+
+/// Whether a call target or struct literal constructs a type named `name`.
+fn constructs(target: &str, name: &str) -> bool {
+    match path_segments(target).as_slice() {
+        [.., last] if *last == name => true,
+        [.., owner, last] => *owner == name && CONSTRUCTOR_NAMES.contains(last),
+        _ => false,
+    }
+}
+
+/// Name of the entity holding the only construction site of `structure`, if there is exactly one.
+fn sole_instantiation_site(index: &ParseIndex, structure: &ParsedEntity) -> Option<Vec<String>> {
+    let mut sites = index
+        .entities
+        .values()
+        .filter(|entity| entity.id != structure.id)
+        .flat_map(|entity| {
+            metadata_strings(entity, "function_calls")
+                .into_iter()
+                .chain(metadata_strings(entity, "instantiations"))
+                .filter(|target| constructs(target, &structure.name))
+                .map(move |_| entity)
+        });
+
+    let site = sites.next()?;
+    if sites.next().is_some() {
+        return None;
+    }
+    Some(vec![site.name.clone()])
+}
+
+/// Explicit type arguments of a call target (e.g. `["u32"]` for `parse::<u32>`).
+fn type_arguments(target: &str) -> Option<Vec<String>> {
+    let open = target.find('<')?;
+    let close = target.rfind('>')?;
+    let inner = target.get(open + 1..close)?;
+
+    let mut arguments = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (offset, ch) in inner.char_indices() {
+        match ch {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                arguments.push(inner[start..offset].trim().to_string());
+                start = offset + 1;
+            }
+            _ => {}
+        }
+    }
+    arguments.push(inner[start..].trim().to_string());
+    arguments.retain(|argument| !argument.is_empty());
+    Some(arguments)
+}
+
+/// Concrete type arguments shared by every call of a generic `function`, if all calls agree.
+fn sole_type_arguments(index: &ParseIndex, function: &ParsedEntity) -> Option<Vec<String>> {
+    let generic_parameters = metadata_strings(function, "generic_parameters");
+    if generic_parameters.is_empty() {
+        return None;
+    }
+
+    let mut resolved: Option<Vec<String>> = None;
+    let calls = index
+        .entities
+        .values()
+        .flat_map(|entity| metadata_strings(entity, "function_calls"))
+        .filter(|target| path_segments(target).last() == Some(&function.name.as_str()));
+    for target in calls {
+        let arguments = type_arguments(target)?;
+        if arguments.len() != generic_parameters.len() {
+            return None;
+        }
+        match &resolved {
+            Some(previous) if *previous != arguments => return None,
+            Some(_) => {}
+            None => resolved = Some(arguments),
+        }
+    }
+    resolved
+}
+
+/// Diagnosis for a function whose parameter list exceeds the threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LongParamDiagnosis {
+    /// Number of parameters, excluding receivers such as `self`
+    pub param_count: usize,
+    /// Suggested parameter object names (e.g. `UserParams`), largest group first
+    pub suggested_groupings: Vec<String>,
+}
+
+/// Detect a long parameter list from the `parameters` metadata populated by language adapters.
+///
+/// Returns `None` when the entity has no parameter metadata or at most `threshold` parameters.
+pub fn detect_long_parameter_list(
+    entity: &ParsedEntity,
+    threshold: usize,
+) -> Option<LongParamDiagnosis> {
+    let parameters: Vec<&str> = entity
+        .metadata
+        .get("parameters")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| value.as_str())
+        .filter(|name| !RECEIVER_PARAMETERS.contains(name))
+        .collect();
+
+    if parameters.len() <= threshold {
+        return None;
+    }
+
+    Some(LongParamDiagnosis {
+        param_count: parameters.len(),
+        suggested_groupings: suggest_parameter_groupings(&parameters),
+    })
+}
+
+/// Suggest parameter object names from parameters sharing a name prefix.
+fn suggest_parameter_groupings(parameters: &[&str]) -> Vec<String> {
+    let mut groups: BTreeMap<String, usize> = BTreeMap::new();
+    for prefix in parameters.iter().filter_map(|name| parameter_prefix(name)) {
+        *groups.entry(prefix).or_insert(0) += 1;
+    }
+
+    let mut groups: Vec<_> = groups
+        .into_iter()
+        .filter(|(_, count)| *count >= PARAMETER_GROUP_MIN_SIZE)
+        .collect();
+    groups.sort_by(|a, b| b.1.cmp(&a.1));
+
+    groups
+        .into_iter()
+        .map(|(prefix, _)| {
+            let mut chars = prefix.chars();
+            let head = chars.next().map(|c| c.to_ascii_uppercase());
+            format!(
+                "{}{}Params",
+                head.into_iter().collect::<String>(),
+                chars.as_str()
+            )
+        })
+        .collect()
+}
+
+/// Leading word of a snake_case or camelCase parameter name, if it has more than one word.
+fn parameter_prefix(name: &str) -> Option<String> {
+    let name = name.trim_start_matches('_');
+    let split = name
+        .find('_')
+        .or_else(|| name.find(|c: char| c.is_ascii_uppercase()))?;
+    let (prefix, rest) = name.split_at(split);
+    if prefix.is_empty() || rest.trim_start_matches('_').is_empty() {
+        return None;
+    }
+    Some(prefix.to_ascii_lowercase())
+}
 
 /// Detect long methods that should be split into smaller functions.
 pub fn detect_long_methods(
@@ -211,6 +505,94 @@ pub fn detect_large_types(
     recommendations
 }
 
+/// Detect functions whose parameter lists should become parameter objects.
+pub fn detect_long_parameter_lists(
+    functions: &[CodeEntity],
+    diagnosis_fn: impl Fn(&CodeEntity) -> Option<LongParamDiagnosis>,
+    entity_location_fn: impl Fn(&CodeEntity) -> (usize, usize),
+) -> Vec<RefactoringRecommendation> {
+    let mut recommendations = Vec::new();
+
+    for function in functions {
+        let Some(diagnosis) = diagnosis_fn(function) else {
+            continue;
+        };
+
+        let impact = (4.0 + diagnosis.param_count as f64 * 0.5)
+            .min(10.0)
+            .max(5.0);
+        let effort = 3.0;
+        let priority = (impact / effort).max(0.1);
+        let grouping_note = if diagnosis.suggested_groupings.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " Candidate objects: {}.",
+                diagnosis.suggested_groupings.join(", ")
+            )
+        };
+
+        recommendations.push(RefactoringRecommendation {
+            refactoring_type: RefactoringType::IntroduceParameterObject,
+            description: format!(
+                "Function `{}` takes {} parameters. Group related parameters into a parameter object.{}",
+                function.name, diagnosis.param_count, grouping_note
+            ),
+            estimated_impact: impact,
+            estimated_effort: effort,
+            priority_score: priority,
+            location: entity_location_fn(function),
+        });
+    }
+
+    recommendations
+}
+
+/// Recommend collapsing abstractions that have a single concrete use.
+pub fn detect_speculative_generality_recommendations(
+    diagnoses: &[SpeculativeGeneralityDiagnosis],
+) -> Vec<RefactoringRecommendation> {
+    diagnoses
+        .iter()
+        .map(|diagnosis| {
+            let concrete = diagnosis.concrete_uses.join(", ");
+            let (impact, description) = match diagnosis.kind {
+                SpeculativeGeneralityKind::SingleImplementor => (
+                    6.0,
+                    format!(
+                        "Interface `{}` has a single implementor `{}`. Inline the interface until a second implementation exists.",
+                        diagnosis.name, concrete
+                    ),
+                ),
+                SpeculativeGeneralityKind::SingleInstantiation => (
+                    5.0,
+                    format!(
+                        "Struct `{}` is only constructed in `{}`. Consider folding it into its sole user.",
+                        diagnosis.name, concrete
+                    ),
+                ),
+                SpeculativeGeneralityKind::SingleTypeArguments => (
+                    5.5,
+                    format!(
+                        "Generic function `{}` is only called with `<{}>`. Replace its type parameters with the concrete types.",
+                        diagnosis.name, concrete
+                    ),
+                ),
+            };
+            let effort = 2.0;
+
+            RefactoringRecommendation {
+                refactoring_type: RefactoringType::RemoveSpeculativeGenerality,
+                description,
+                estimated_impact: impact,
+                estimated_effort: effort,
+                priority_score: impact / effort,
+                location: diagnosis.location,
+            }
+        })
+        .collect()
+}
+
 /// Estimate logical operator complexity from source code.
 pub fn estimate_logical_operator_complexity(snippet: &str) -> usize {
     let mut count = 0;
@@ -89,6 +89,18 @@ impl RefactoringExtractor {
             )
             .with_range(0.0, 50.0)
             .with_default(0.0),
+            FeatureDefinition::new(
+                "refactoring_long_parameter_list_count",
+                "Occurrences of long parameter lists that should become parameter objects",
+            )
+            .with_range(0.0, 50.0)
+            .with_default(0.0),
+            FeatureDefinition::new(
+                "refactoring_speculative_generality_count",
+                "Occurrences of abstractions with a single concrete use",
+            )
+            .with_range(0.0, 50.0)
+            .with_default(0.0),
         ];
 
         Self {
@@ -134,6 +146,20 @@ impl RefactoringExtractor {
         }
     }
 
+    /// Analyze in-memory source and cache the result under `file_path`.
+    ///
+    /// Subsequent extractions for entities in `file_path` use this analysis
+    /// instead of reading the file from disk.
+    pub async fn prime_source(&self, file_path: &str, source: &str) -> Result<()> {
+        let result = self
+            .analyzer
+            .analyze_source(Path::new(file_path), source)
+            .await?;
+        self.file_cache
+            .insert(file_path.to_owned(), Arc::new(result));
+        Ok(())
+    }
+
     /// Initialise the feature vector with configured defaults
     fn initialise_feature_map(&self) -> HashMap<String, f64> {
         let mut map = HashMap::with_capacity(self.feature_definitions.len());
@@ -187,6 +213,8 @@ impl FeatureExtractor for RefactoringExtractor {
         let mut extract_class = 0.0_f64;
         let mut eliminate_duplication = 0.0_f64;
         let mut simplify_conditionals = 0.0_f64;
+        let mut long_parameter_lists = 0.0_f64;
+        let mut speculative_generality = 0.0_f64;
 
         for recommendation in &analysis.recommendations {
             let location = recommendation.location;
@@ -206,6 +234,8 @@ impl FeatureExtractor for RefactoringExtractor {
                     eliminate_duplication += 1.0;
                 }
                 RefactoringType::SimplifyConditionals => simplify_conditionals += 1.0,
+                RefactoringType::IntroduceParameterObject => long_parameter_lists += 1.0,
+                RefactoringType::RemoveSpeculativeGenerality => speculative_generality += 1.0,
                 RefactoringType::ReduceComplexity
                 | RefactoringType::ImproveNaming
                 | RefactoringType::RemoveDeadCode => {
@@ -239,6 +269,14 @@ impl FeatureExtractor for RefactoringExtractor {
                 "refactoring_simplify_conditionals_count".to_string(),
                 simplify_conditionals,
             );
+            features.insert(
+                "refactoring_long_parameter_list_count".to_string(),
+                long_parameter_lists,
+            );
+            features.insert(
+                "refactoring_speculative_generality_count".to_string(),
+                speculative_generality,
+            );
         }
 
         // Propagate the file-level refactoring score regardless of overlap results
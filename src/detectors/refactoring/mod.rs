@@ -4,8 +4,11 @@ mod detection_rules;
 mod extractor;
 
 pub use detection_rules::{
-    COMPLEX_CONDITIONAL_THRESHOLD, DUPLICATE_MIN_LINE_COUNT, DUPLICATE_MIN_TOKEN_COUNT,
-    LARGE_CLASS_LINE_THRESHOLD, LARGE_CLASS_MEMBER_THRESHOLD, LONG_METHOD_LINE_THRESHOLD,
+    detect_long_parameter_list, detect_speculative_generality, LongParamDiagnosis,
+    SpeculativeGeneralityDiagnosis, SpeculativeGeneralityKind, COMPLEX_CONDITIONAL_THRESHOLD,
+    DUPLICATE_MIN_LINE_COUNT, DUPLICATE_MIN_TOKEN_COUNT, LARGE_CLASS_LINE_THRESHOLD,
+    LARGE_CLASS_MEMBER_THRESHOLD, LONG_METHOD_LINE_THRESHOLD, LONG_PARAMETER_LIST_CODE,
+    LONG_PARAMETER_LIST_THRESHOLD,
 };
 pub use extractor::RefactoringExtractor;
 
@@ -33,6 +36,7 @@ const PROP_DUPLICATE_FINGERPRINT: &str = "duplicate_fingerprint";
 const PROP_FINGERPRINT_TOKENS: &str = "duplicate_token_count";
 const PROP_MEMBER_COUNT: &str = "member_count";
 const PROP_COMPLEXITY_METRICS: &str = "complexity_metrics";
+const PROP_LONG_PARAMETER_LIST: &str = "long_parameter_list";
 
 /// Configuration for refactoring analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +68,8 @@ pub enum RefactoringType {
     ImproveNaming,
     SimplifyConditionals,
     RemoveDeadCode,
+    IntroduceParameterObject,
+    RemoveSpeculativeGenerality,
 }
 
 /// Refactoring recommendation
@@ -163,9 +169,20 @@ impl RefactoringAnalyzer {
         );
 
         let content = FileReader::read_to_string(file_path)?;
+        self.analyze_source(file_path, &content).await
+    }
+
+    /// Analyze in-memory source for refactoring opportunities.
+    ///
+    /// The language adapter is selected from `file_path`'s extension.
+    pub(crate) async fn analyze_source(
+        &self,
+        file_path: &Path,
+        content: &str,
+    ) -> Result<RefactoringAnalysisResult> {
         let file_path_str = file_path.to_string_lossy().to_string();
 
-        let complexity_by_id = self.get_complexity_map(&file_path_str, &content).await;
+        let complexity_by_id = self.get_complexity_map(&file_path_str, content).await;
 
         let mut adapter = match adapter_for_file(file_path) {
             Ok(adapter) => adapter,
@@ -175,20 +192,20 @@ impl RefactoringAnalyzer {
             }
         };
 
-        let parse_index = adapter.parse_source(&content, &file_path_str)?;
-        let cached_tree = self.ast_service.get_ast(&file_path_str, &content).await?;
+        let parse_index = adapter.parse_source(content, &file_path_str)?;
+        let cached_tree = self.ast_service.get_ast(&file_path_str, content).await?;
         let ast_context = self
             .ast_service
             .create_context(&cached_tree, &file_path_str);
         let entity_summaries =
-            self.collect_entity_summaries(&parse_index, &content, &complexity_by_id, &ast_context)?;
+            self.collect_entity_summaries(&parse_index, content, &complexity_by_id, &ast_context)?;
 
         if entity_summaries.is_empty() {
             return Ok(Self::empty_result(file_path_str));
         }
 
-        let recommendations = self.collect_recommendations(&entity_summaries, &content);
-        let refactoring_score = self.calculate_refactoring_score(&recommendations, &content);
+        let recommendations = self.collect_recommendations(&entity_summaries, &parse_index);
+        let refactoring_score = self.calculate_refactoring_score(&recommendations, content);
 
         Ok(RefactoringAnalysisResult {
             file_path: file_path_str,
@@ -232,7 +249,7 @@ impl RefactoringAnalyzer {
     fn collect_recommendations(
         &self,
         entities: &[CodeEntity],
-        _content: &str,
+        index: &ParseIndex,
     ) -> Vec<RefactoringRecommendation> {
         let functions: Vec<_> = entities
             .iter()
@@ -250,6 +267,12 @@ impl RefactoringAnalyzer {
         recs.extend(self.detect_complex_conditionals(&functions));
         recs.extend(self.detect_duplicate_code(&functions));
         recs.extend(self.detect_large_types(&types));
+        recs.extend(self.detect_long_parameter_lists(&functions));
+        recs.extend(
+            detection_rules::detect_speculative_generality_recommendations(
+                &detect_speculative_generality(index),
+            ),
+        );
 
         recs.retain(|r| r.estimated_impact >= self.config.min_impact_threshold);
         recs.sort_by(|a, b| b.priority_score.partial_cmp(&a.priority_score).unwrap());
@@ -312,6 +335,11 @@ impl RefactoringAnalyzer {
             if let Some(count) = child_function_counts.get(&entity.id) {
                 code_entity.add_property(PROP_MEMBER_COUNT, json!(count));
             }
+            if let Some(value) = detect_long_parameter_list(entity, LONG_PARAMETER_LIST_THRESHOLD)
+                .and_then(|diagnosis| serde_json::to_value(diagnosis).ok())
+            {
+                code_entity.add_property(PROP_LONG_PARAMETER_LIST, value);
+            }
 
             summaries.push(code_entity);
         }
@@ -377,6 +405,14 @@ impl RefactoringAnalyzer {
         Some((hash, tokens))
     }
 
+    /// Extracts the long parameter list diagnosis from an entity's properties.
+    fn long_parameter_list(entity: &CodeEntity) -> Option<LongParamDiagnosis> {
+        entity
+            .properties
+            .get(PROP_LONG_PARAMETER_LIST)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
     /// Gets the member count from an entity's properties.
     fn member_count_from_entity(entity: &CodeEntity) -> usize {
         entity
@@ -592,6 +628,18 @@ impl RefactoringAnalyzer {
         )
     }
 
+    /// Detects functions whose parameter lists exceed the threshold.
+    fn detect_long_parameter_lists(
+        &self,
+        functions: &[CodeEntity],
+    ) -> Vec<RefactoringRecommendation> {
+        detection_rules::detect_long_parameter_lists(
+            functions,
+            Self::long_parameter_list,
+            Self::entity_location,
+        )
+    }
+
     /// Calculate overall refactoring score for the file
     fn calculate_refactoring_score(
         &self,
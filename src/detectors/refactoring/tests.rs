@@ -219,3 +219,202 @@ async fn test_refactoring_extractor_produces_features() {
             >= 0.0
     );
 }
+
+#[test]
+fn test_detect_long_parameter_list_suggests_groupings() {
+    use crate::lang::SourceLocation;
+
+    let mut entity = ParsedEntity {
+        id: "module::create_order".into(),
+        kind: EntityKind::Function,
+        name: "create_order".into(),
+        parent: None,
+        children: Vec::new(),
+        location: SourceLocation {
+            file_path: "orders.py".into(),
+            start_line: 1,
+            end_line: 3,
+            start_column: 0,
+            end_column: 0,
+        },
+        metadata: HashMap::new(),
+        is_test: false,
+    };
+    entity.metadata.insert(
+        "parameters".into(),
+        json!([
+            "self",
+            "user_id",
+            "user_name",
+            "user_email",
+            "orderTotal",
+            "orderCurrency",
+            "notify"
+        ]),
+    );
+
+    let diagnosis = detect_long_parameter_list(&entity, LONG_PARAMETER_LIST_THRESHOLD)
+        .expect("six parameters exceed the threshold");
+    assert_eq!(diagnosis.param_count, 6);
+    assert_eq!(
+        diagnosis.suggested_groupings,
+        vec!["UserParams".to_string(), "OrderParams".to_string()]
+    );
+
+    assert!(detect_long_parameter_list(&entity, 6).is_none());
+}
+
+#[tokio::test]
+async fn test_refactoring_extractor_counts_long_parameter_lists() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("signup.py");
+    let content =
+        "def signup(user_id, user_name, user_email, plan, coupon, referrer):\n    return user_id\n";
+    fs::write(&file_path, content).unwrap();
+
+    let results = analyzer()
+        .analyze_files(&[file_path.clone()])
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    let recommendation = results[0]
+        .recommendations
+        .iter()
+        .find(|rec| rec.refactoring_type == RefactoringType::IntroduceParameterObject)
+        .expect("expected parameter object recommendation");
+    assert!(recommendation.description.contains("UserParams"));
+
+    let entity = CodeEntity::new(
+        "entity::signup",
+        "function",
+        "signup",
+        file_path.to_string_lossy(),
+    )
+    .with_line_range(1, 2)
+    .with_source_code(content);
+    let context = ExtractionContext::new(Arc::new(ValknutConfig::default()), "python");
+    let features = RefactoringExtractor::default()
+        .extract(&entity, &context)
+        .await
+        .unwrap();
+    assert_eq!(
+        features.get("refactoring_long_parameter_list_count"),
+        Some(&1.0)
+    );
+}
+
+fn parse_fixture(file_name: &str, source: &str) -> ParseIndex {
+    adapter_for_file(Path::new(file_name))
+        .unwrap()
+        .parse_source(source, file_name)
+        .unwrap()
+}
+
+#[test]
+fn test_detect_speculative_generality_finds_single_implementor_interface() {
+    let index = parse_fixture(
+        "shapes.ts",
+        "interface Shape {\n  area(): number;\n}\n\ninterface Named {\n  name(): string;\n}\n\n\
+         class Square implements Shape, Named {\n  area(): number { return 4; }\n  name(): string { return 'sq'; }\n}\n\n\
+         class Label implements Named {\n  name(): string { return 'label'; }\n}\n",
+    );
+
+    let diagnoses = detect_speculative_generality(&index);
+    let shape = diagnoses
+        .iter()
+        .find(|diagnosis| diagnosis.name == "Shape")
+        .expect("Shape has a single implementor");
+    assert_eq!(shape.kind, SpeculativeGeneralityKind::SingleImplementor);
+    assert_eq!(shape.concrete_uses, vec!["Square".to_string()]);
+    assert!(diagnoses.iter().all(|diagnosis| diagnosis.name != "Named"));
+}
+
+#[test]
+fn test_detect_speculative_generality_finds_single_use_struct_and_generic() {
+    let index = parse_fixture(
+        "lib.rs",
+        "struct Point { x: i32 }\n\nstruct Pair { a: i32 }\n\n\
+         fn identity<T>(value: T) -> T { value }\n\n\
+         fn widen<T>(value: T) -> T { value }\n\n\
+         fn build() -> i32 {\n    let p = Point { x: 1 };\n    let a = Pair { a: 1 };\n    let b = Pair::new();\n    \
+         identity::<u32>(1);\n    identity::<u32>(2);\n    widen::<u8>(1);\n    widen(2u16);\n    p.x\n}\n",
+    );
+
+    let diagnoses = detect_speculative_generality(&index);
+    let point = diagnoses
+        .iter()
+        .find(|diagnosis| diagnosis.name == "Point")
+        .expect("Point is constructed once");
+    assert_eq!(point.kind, SpeculativeGeneralityKind::SingleInstantiation);
+    assert_eq!(point.concrete_uses, vec!["build".to_string()]);
+
+    let identity = diagnoses
+        .iter()
+        .find(|diagnosis| diagnosis.name == "identity")
+        .expect("identity is only called with u32");
+    assert_eq!(
+        identity.kind,
+        SpeculativeGeneralityKind::SingleTypeArguments
+    );
+    assert_eq!(identity.concrete_uses, vec!["u32".to_string()]);
+
+    // `Pair` is built twice and `widen` has an inferred call site.
+    assert!(diagnoses
+        .iter()
+        .all(|diagnosis| diagnosis.name != "Pair" && diagnosis.name != "widen"));
+}
+
+#[test]
+fn test_detect_speculative_generality_skips_items_visible_to_other_files() {
+    let rust_index = parse_fixture(
+        "lib.rs",
+        "pub struct Point { x: i32 }\n\npub(crate) fn identity<T>(value: T) -> T { value }\n\n\
+         fn build() -> i32 {\n    identity::<u32>(1);\n    Point { x: 1 }.x\n}\n",
+    );
+    assert!(detect_speculative_generality(&rust_index).is_empty());
+
+    let ts_index = parse_fixture(
+        "shapes.ts",
+        "export interface Shape {\n  area(): number;\n}\n\n\
+         class Square implements Shape {\n  area(): number { return 4; }\n}\n",
+    );
+    assert!(detect_speculative_generality(&ts_index).is_empty());
+}
+
+#[tokio::test]
+async fn test_refactoring_extractor_counts_speculative_generality() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("convert.rs");
+    let content = "fn convert<T>(value: T) -> T {\n    value\n}\n\nfn run() -> u32 {\n    convert::<u32>(1)\n}\n";
+    fs::write(&file_path, content).unwrap();
+
+    let results = analyzer()
+        .analyze_files(&[file_path.clone()])
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    let recommendation = results[0]
+        .recommendations
+        .iter()
+        .find(|rec| rec.refactoring_type == RefactoringType::RemoveSpeculativeGenerality)
+        .expect("expected speculative generality recommendation");
+    assert_eq!(recommendation.location, (1, 3));
+
+    let entity = CodeEntity::new(
+        "entity::convert",
+        "function",
+        "convert",
+        file_path.to_string_lossy(),
+    )
+    .with_line_range(1, 3)
+    .with_source_code(content);
+    let context = ExtractionContext::new(Arc::new(ValknutConfig::default()), "rust");
+    let features = RefactoringExtractor::default()
+        .extract(&entity, &context)
+        .await
+        .unwrap();
+    assert_eq!(
+        features.get("refactoring_speculative_generality_count"),
+        Some(&1.0)
+    );
+}
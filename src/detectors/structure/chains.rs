@@ -0,0 +1,251 @@
+//! Detection of overly long method chains.
+//!
+//! Chains such as `a.b().c().d().e().f()` hide intermediate values from the
+//! debugger and usually reach through several objects, a Law of Demeter smell.
+//! The chain length is the number of member accesses along the receiver spine,
+//! so `a.b().c()` has length 2.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Parser};
+
+use crate::lang::registry::get_tree_sitter_language;
+
+/// Issue code reported on refactoring candidates with overly long method chains.
+pub const METHOD_CHAIN_CODE: &str = "R_METHOD_CHAIN";
+
+/// Default longest chain tolerated before it is reported.
+pub const DEFAULT_MAX_METHOD_CHAIN_LENGTH: usize = 4;
+
+/// A method chain longer than the configured maximum.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodChainDiagnosis {
+    /// Number of member accesses in the chain
+    pub chain_length: usize,
+    /// 1-based line on which the chain starts
+    pub start_line: usize,
+    /// Source text of the object the chain starts from
+    pub root_object: String,
+}
+
+/// Method chains found in a single file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileMethodChains {
+    /// File containing the chains
+    pub path: PathBuf,
+    /// Chains longer than the configured maximum, in source order
+    pub chains: Vec<MethodChainDiagnosis>,
+}
+
+/// How a node kind links to the next receiver in a chain.
+struct ChainLink {
+    /// Node kind of the link
+    kind: &'static str,
+    /// Field holding the receiver (or callee for calls)
+    receiver_field: &'static str,
+    /// Whether the link is a member access that adds to the chain length
+    is_member: bool,
+}
+
+/// Chain links for Python.
+const PYTHON_LINKS: &[ChainLink] = &[
+    ChainLink {
+        kind: "attribute",
+        receiver_field: "object",
+        is_member: true,
+    },
+    ChainLink {
+        kind: "call",
+        receiver_field: "function",
+        is_member: false,
+    },
+];
+
+/// Chain links for JavaScript and TypeScript.
+const JS_LINKS: &[ChainLink] = &[
+    ChainLink {
+        kind: "member_expression",
+        receiver_field: "object",
+        is_member: true,
+    },
+    ChainLink {
+        kind: "call_expression",
+        receiver_field: "function",
+        is_member: false,
+    },
+];
+
+/// Chain links for Rust.
+const RUST_LINKS: &[ChainLink] = &[
+    ChainLink {
+        kind: "field_expression",
+        receiver_field: "value",
+        is_member: true,
+    },
+    ChainLink {
+        kind: "call_expression",
+        receiver_field: "function",
+        is_member: false,
+    },
+];
+
+/// Chain links for Go.
+const GO_LINKS: &[ChainLink] = &[
+    ChainLink {
+        kind: "selector_expression",
+        receiver_field: "operand",
+        is_member: true,
+    },
+    ChainLink {
+        kind: "call_expression",
+        receiver_field: "function",
+        is_member: false,
+    },
+];
+
+/// Chain links for C++.
+const CPP_LINKS: &[ChainLink] = &[
+    ChainLink {
+        kind: "field_expression",
+        receiver_field: "argument",
+        is_member: true,
+    },
+    ChainLink {
+        kind: "call_expression",
+        receiver_field: "function",
+        is_member: false,
+    },
+];
+
+/// Chain links for a language name or key, if the language is supported.
+fn chain_links(language: &str) -> Option<&'static [ChainLink]> {
+    match crate::lang::registry::language_info(language)?.key {
+        "py" => Some(PYTHON_LINKS),
+        "js" | "ts" => Some(JS_LINKS),
+        "rs" => Some(RUST_LINKS),
+        "go" => Some(GO_LINKS),
+        "cpp" => Some(CPP_LINKS),
+        _ => None,
+    }
+}
+
+/// Find method chains in `source` with more than `max_length` member accesses.
+///
+/// Each chain is reported once, from its outermost expression. Unsupported
+/// languages and unparsable sources yield no diagnoses.
+pub fn detect_method_chains(
+    source: &str,
+    language: &str,
+    max_length: usize,
+) -> Vec<MethodChainDiagnosis> {
+    let Some(links) = chain_links(language) else {
+        return Vec::new();
+    };
+    let Ok(ts_language) = get_tree_sitter_language(language) else {
+        return Vec::new();
+    };
+    let mut parser = Parser::new();
+    if parser.set_language(&ts_language).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+
+    let mut diagnoses = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if find_link(links, node).is_some() && !continues_chain(links, node) {
+            let (chain_length, root) = measure_chain(links, node);
+            if chain_length > max_length {
+                diagnoses.push(MethodChainDiagnosis {
+                    chain_length,
+                    start_line: node.start_position().row + 1,
+                    root_object: source[root.byte_range()].to_string(),
+                });
+            }
+        }
+
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
+    }
+
+    diagnoses.sort_by_key(|diagnosis| diagnosis.start_line);
+    diagnoses
+}
+
+/// The chain link matching `node`'s kind.
+fn find_link(links: &'static [ChainLink], node: Node<'_>) -> Option<&'static ChainLink> {
+    links.iter().find(|link| link.kind == node.kind())
+}
+
+/// Whether `node` is the receiver of an enclosing link, i.e. not the chain's outermost node.
+fn continues_chain(links: &'static [ChainLink], node: Node<'_>) -> bool {
+    node.parent()
+        .and_then(|parent| {
+            let link = find_link(links, parent)?;
+            parent.child_by_field_name(link.receiver_field)
+        })
+        .is_some_and(|receiver| receiver.id() == node.id())
+}
+
+/// Count member accesses from `node` down the receiver spine and return the root object.
+fn measure_chain<'tree>(links: &'static [ChainLink], node: Node<'tree>) -> (usize, Node<'tree>) {
+    let mut length = 0;
+    let mut current = node;
+    while let Some(link) = find_link(links, current) {
+        let Some(receiver) = current.child_by_field_name(link.receiver_field) else {
+            break;
+        };
+        if link.is_member {
+            length += 1;
+        }
+        current = receiver;
+    }
+    (length, current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_javascript_chain_longer_than_max() {
+        let source = "const x = 1;\nconst y = a.b().c().d().e().f();\nconst z = a.b().c();\n";
+        let chains = detect_method_chains(source, "javascript", DEFAULT_MAX_METHOD_CHAIN_LENGTH);
+
+        assert_eq!(
+            chains,
+            vec![MethodChainDiagnosis {
+                chain_length: 5,
+                start_line: 2,
+                root_object: "a".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn chain_at_max_length_is_not_reported() {
+        let source = "value = items.filter(x).map(y).sort().first()\n";
+
+        assert!(detect_method_chains(source, "python", 4).is_empty());
+        assert_eq!(detect_method_chains(source, "python", 3)[0].chain_length, 4);
+    }
+
+    #[test]
+    fn nested_chains_in_arguments_are_reported_separately() {
+        let source = "fn run() {\n    let v = self.a().b().c().d().e(x.p().q().r().s().t());\n}\n";
+        let chains = detect_method_chains(source, "rust", 4);
+
+        let roots: Vec<&str> = chains.iter().map(|c| c.root_object.as_str()).collect();
+        assert_eq!(chains.len(), 2);
+        assert!(roots.contains(&"self"));
+        assert!(roots.contains(&"x"));
+    }
+
+    #[test]
+    fn unsupported_language_yields_nothing() {
+        assert!(detect_method_chains("a.b().c().d().e().f()", "cobol", 1).is_empty());
+    }
+}
@@ -2,9 +2,12 @@
 
 use petgraph::{Directed, Graph, Undirected};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 
+use super::chains::DEFAULT_MAX_METHOD_CHAIN_LENGTH;
+use super::health::EntityHealth;
+
 /// Code file extensions recognized for structure analysis
 pub const CODE_EXTENSIONS: &[&str] = &[
     "py", "pyi", "js", "mjs", "ts", "jsx", "tsx", "rs", "go", "java", "cpp", "c", "h", "hpp",
@@ -55,9 +58,60 @@ pub struct StructureConfig {
     pub partitioning: PartitioningConfig,
     /// Entity health scoring settings
     pub entity_health: EntityHealthConfig,
+    /// Score function size against a short-utility mode as well as the
+    /// configured one, taking the healthier result.
+    ///
+    /// Prefer this for codebases built from many tiny helpers alongside
+    /// medium-sized functions, where function sizes are bimodal and a single
+    /// lognormal misjudges one of the two groups.
+    #[serde(default)]
+    pub use_bimodal_function_scoring: bool,
     /// Directory patterns to exclude from structure analysis
     #[serde(default)]
     pub exclude_patterns: Vec<String>,
+    /// Algorithm used to group cohesive entities when suggesting file splits
+    #[serde(default)]
+    pub community_algorithm: CommunityAlgorithm,
+    /// Package boundaries for workspaces; `None` treats the tree as one namespace
+    #[serde(default)]
+    pub workspace: Option<WorkspaceConfig>,
+    /// Longest method chain tolerated before it is reported as `R_METHOD_CHAIN`
+    #[serde(default = "default_max_method_chain_length")]
+    pub max_method_chain_length: usize,
+}
+
+/// Community detection algorithms for entity cohesion graphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CommunityAlgorithm {
+    /// Greedy merging of the strongest cohesion edges
+    Greedy,
+    /// Louvain modularity optimization
+    #[default]
+    Louvain,
+}
+
+/// Package boundaries within a Cargo, npm, or pnpm workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct WorkspaceConfig {
+    /// Package root directories, relative to the project root
+    #[serde(default)]
+    pub package_roots: Vec<PathBuf>,
+    /// How package roots are discovered
+    #[serde(default)]
+    pub resolver: WorkspaceResolver,
+}
+
+/// Strategy for discovering workspace package roots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceResolver {
+    /// Detect packages from `Cargo.toml` and `package.json` manifests, in
+    /// addition to any listed package roots
+    #[default]
+    Auto,
+    /// Use only the listed package roots
+    Manual,
 }
 
 /// Feature toggles for structure analysis outputs.
@@ -113,6 +167,44 @@ pub struct FsFileConfig {
     /// AST node count at 95th percentile of the lognormal distribution.
     /// Default 6000 corresponds to ~600 LOC. Used to derive distribution shape.
     pub ast_nodes_95th_percentile: usize,
+    /// Maximum methods per class before it is flagged as a God Class
+    #[serde(default = "default_max_class_methods")]
+    pub max_class_methods: usize,
+    /// Maximum estimated fields per class before it is flagged as a God Class
+    #[serde(default = "default_max_class_fields")]
+    pub max_class_fields: usize,
+    /// Minimum importers and co-change commits before a file is flagged for
+    /// Shotgun Surgery
+    #[serde(default = "default_shotgun_surgery_threshold")]
+    pub shotgun_surgery_threshold: usize,
+    /// Most recent commits inspected for Shotgun Surgery and change coupling
+    #[serde(default = "default_co_change_window")]
+    pub co_change_window: usize,
+}
+
+/// Default longest tolerated method chain.
+fn default_max_method_chain_length() -> usize {
+    DEFAULT_MAX_METHOD_CHAIN_LENGTH
+}
+
+/// Default God Class method threshold.
+fn default_max_class_methods() -> usize {
+    20
+}
+
+/// Default God Class field threshold.
+fn default_max_class_fields() -> usize {
+    15
+}
+
+/// Default Shotgun Surgery importer and co-change threshold.
+fn default_shotgun_surgery_threshold() -> usize {
+    10
+}
+
+/// Default number of recent commits inspected for co-changes.
+fn default_co_change_window() -> usize {
+    1000
 }
 
 /// Configuration for entity health scoring
@@ -209,6 +301,10 @@ impl Default for StructureConfig {
                 min_entities_per_split: 3,
                 optimal_ast_nodes: 2000,
                 ast_nodes_95th_percentile: 6000,
+                max_class_methods: default_max_class_methods(),
+                max_class_fields: default_max_class_fields(),
+                shotgun_surgery_threshold: default_shotgun_surgery_threshold(),
+                co_change_window: default_co_change_window(),
             },
             partitioning: PartitioningConfig {
                 balance_tolerance: 0.25,
@@ -222,7 +318,11 @@ impl Default for StructureConfig {
                 ],
             },
             entity_health: EntityHealthConfig::default(),
+            use_bimodal_function_scoring: false,
             exclude_patterns: Vec::new(),
+            community_algorithm: CommunityAlgorithm::default(),
+            workspace: None,
+            max_method_chain_length: default_max_method_chain_length(),
         }
     }
 }
@@ -267,12 +367,30 @@ pub struct FileMetrics {
     pub loc: usize,
     /// Lognormal distribution-based score for file size (1.0 = optimal, decreases away from optimal)
     pub size_score: f64,
-    /// Entity health summary for functions/classes in this file
-    pub entity_health: Option<FileEntityHealth>,
+    /// Entity health summary and per-entity scores for functions/classes in this file
+    pub entity_health: Option<HealthReport>,
+}
+
+/// Entity health for a file: the aggregate plus each entity's score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Aggregated health across all entities
+    pub aggregate: FileEntityHealth,
+    /// `(entity_name, health)` for each entity, in source order
+    pub per_entity: Vec<(String, EntityHealth)>,
+}
+
+/// Entity health report for a single file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileHealthReport {
+    /// File the entities belong to
+    pub path: PathBuf,
+    /// Aggregate and per-entity health for the file
+    pub report: HealthReport,
 }
 
 /// Aggregated entity health metrics for a file
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileEntityHealth {
     /// Number of entities analyzed
     pub entity_count: usize,
@@ -424,8 +542,23 @@ pub struct EntityNode {
     pub loc: usize,
     /// AST node count for entity (for health scoring)
     pub ast_nodes: usize,
-    /// Referenced symbols/identifiers
-    pub symbols: HashSet<String>,
+    /// Referenced symbols/identifiers, kept sorted so iteration is deterministic
+    pub symbols: BTreeSet<String>,
+    /// Names of methods declared on this entity (class-like entities only)
+    pub methods: Vec<String>,
+    /// Estimated number of fields/attributes (class-like entities only)
+    pub field_count: usize,
+}
+
+/// God Class diagnosis for a class-like entity
+#[derive(Debug, Clone, Serialize)]
+pub struct GodClassDiagnosis {
+    /// Number of methods declared on the class
+    pub method_count: usize,
+    /// Estimated number of fields/attributes
+    pub field_count: usize,
+    /// Estimated distinct responsibilities (verb groups in method names)
+    pub responsibility_count: usize,
 }
 
 /// Cohesion edge between entities
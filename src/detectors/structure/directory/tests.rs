@@ -2,8 +2,8 @@ use super::partitioning::GraphPartitioner;
 use super::reorganization::ReorganizationPlanner;
 use super::*;
 use crate::detectors::structure::config::{
-    EntityHealthConfig, FsDirectoryConfig, FsFileConfig, PartitioningConfig, StructureConfig,
-    StructureToggles,
+    CommunityAlgorithm, EntityHealthConfig, FsDirectoryConfig, FsFileConfig, PartitioningConfig,
+    StructureConfig, StructureToggles,
 };
 use crate::lang::registry::adapter_for_language;
 use petgraph::graph::Graph;
@@ -34,6 +34,10 @@ fn create_test_config() -> StructureConfig {
             min_entities_per_split: 3,
             optimal_ast_nodes: 2000,
             ast_nodes_95th_percentile: 6000,
+            max_class_methods: 20,
+            max_class_fields: 15,
+            shotgun_surgery_threshold: 10,
+            co_change_window: 1000,
         },
         partitioning: PartitioningConfig {
             max_clusters: 8,
@@ -47,7 +51,11 @@ fn create_test_config() -> StructureConfig {
             ],
         },
         entity_health: EntityHealthConfig::default(),
+        use_bimodal_function_scoring: false,
         exclude_patterns: Vec::new(),
+        community_algorithm: CommunityAlgorithm::default(),
+        workspace: None,
+        max_method_chain_length: 4,
     }
 }
 
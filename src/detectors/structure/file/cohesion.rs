@@ -2,13 +2,22 @@
 
 use petgraph::graph::NodeIndex;
 use petgraph::Graph;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use crate::core::errors::Result;
 use crate::detectors::structure::config::{
-    CohesionEdge, CohesionGraph, EntityNode, StructureConfig,
+    CohesionEdge, CohesionGraph, CommunityAlgorithm, EntityNode, StructureConfig,
 };
 
+/// Minimum edge similarity considered when grouping entities into communities.
+const MIN_COMMUNITY_SIMILARITY: f64 = 0.2;
+
+/// Upper bound on local-move passes per Louvain level.
+const LOUVAIN_MAX_PASSES: usize = 64;
+
+/// Minimum modularity gain required for a Louvain move, guarding against float noise.
+const LOUVAIN_GAIN_EPSILON: f64 = 1e-12;
+
 /// Build entity cohesion graph from entities
 pub fn build_cohesion_graph(entities: Vec<EntityNode>) -> CohesionGraph {
     let mut graph = Graph::new_undirected();
@@ -53,7 +62,7 @@ pub fn build_cohesion_graph(entities: Vec<EntityNode>) -> CohesionGraph {
 }
 
 /// Calculate Jaccard similarity between two symbol sets
-pub fn calculate_jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+pub fn calculate_jaccard_similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
     if a.is_empty() && b.is_empty() {
         return 1.0;
     }
@@ -88,8 +97,26 @@ impl<'a> CommunityFinder<'a> {
             return Ok(vec![node_indices]);
         }
 
+        let mut communities = match self.config.community_algorithm {
+            CommunityAlgorithm::Greedy => self.greedy_communities(graph, node_indices),
+            CommunityAlgorithm::Louvain => self.louvain_communities(graph, &node_indices),
+        };
+
+        // Filter and limit communities
+        communities.retain(|comm| comm.len() >= self.config.fsfile.min_entities_per_split);
+        communities.truncate(3);
+
+        Ok(communities)
+    }
+
+    /// Group nodes by greedily merging the strongest cohesion edges
+    fn greedy_communities(
+        &self,
+        graph: &CohesionGraph,
+        node_indices: Vec<NodeIndex>,
+    ) -> Vec<Vec<NodeIndex>> {
         let edges = self.collect_sorted_edges(graph);
-        let (mut communities, mut assigned_nodes) = self.build_communities_from_edges(&edges);
+        let (mut communities, assigned_nodes) = self.build_communities_from_edges(&edges);
 
         // Add remaining nodes as singleton communities
         for node in node_indices {
@@ -98,11 +125,42 @@ impl<'a> CommunityFinder<'a> {
             }
         }
 
-        // Filter and limit communities
-        communities.retain(|comm| comm.len() >= self.config.fsfile.min_entities_per_split);
-        communities.truncate(3);
+        communities
+    }
 
-        Ok(communities)
+    /// Group nodes by Louvain modularity optimization, largest communities first
+    fn louvain_communities(
+        &self,
+        graph: &CohesionGraph,
+        node_indices: &[NodeIndex],
+    ) -> Vec<Vec<NodeIndex>> {
+        let positions: HashMap<NodeIndex, usize> = node_indices
+            .iter()
+            .enumerate()
+            .map(|(position, &node)| (node, position))
+            .collect();
+
+        let mut adjacency = vec![HashMap::new(); node_indices.len()];
+        for (source, target, similarity) in self.collect_sorted_edges(graph) {
+            if similarity < MIN_COMMUNITY_SIMILARITY || source == target {
+                continue;
+            }
+            let (a, b) = (positions[&source], positions[&target]);
+            *adjacency[a].entry(b).or_insert(0.0) += similarity;
+            *adjacency[b].entry(a).or_insert(0.0) += similarity;
+        }
+
+        let mut grouped: BTreeMap<usize, Vec<NodeIndex>> = BTreeMap::new();
+        for (position, community) in louvain_partition(adjacency).into_iter().enumerate() {
+            grouped
+                .entry(community)
+                .or_default()
+                .push(node_indices[position]);
+        }
+
+        let mut communities: Vec<_> = grouped.into_values().collect();
+        communities.sort_by(|a, b| b.len().cmp(&a.len()));
+        communities
     }
 
     /// Collect edges sorted by cohesion strength (descending)
@@ -129,7 +187,7 @@ impl<'a> CommunityFinder<'a> {
         let mut assigned_nodes = HashSet::new();
 
         for &(source, target, similarity) in edges {
-            if similarity < 0.2 {
+            if similarity < MIN_COMMUNITY_SIMILARITY {
                 break;
             }
 
@@ -213,6 +271,124 @@ impl<'a> CommunityFinder<'a> {
     }
 }
 
+/// Partition a weighted undirected graph with the Louvain method.
+///
+/// Each level moves nodes into the neighbouring community with the largest
+/// modularity gain until no move helps, then collapses communities into single
+/// nodes and repeats. Returns the final community id of every input node.
+fn louvain_partition(mut adjacency: Vec<HashMap<usize, f64>>) -> Vec<usize> {
+    let mut membership: Vec<usize> = (0..adjacency.len()).collect();
+    let mut degrees: Vec<f64> = adjacency.iter().map(|links| links.values().sum()).collect();
+    let total_weight: f64 = degrees.iter().sum();
+
+    if total_weight <= 0.0 {
+        return membership;
+    }
+
+    loop {
+        let (level, moved) = louvain_local_moves(&adjacency, &degrees, total_weight);
+        if !moved {
+            return membership;
+        }
+
+        // Renumber communities densely so they can become the next level's nodes
+        let mut renumbered: HashMap<usize, usize> = HashMap::new();
+        let level: Vec<usize> = level
+            .into_iter()
+            .map(|community| {
+                let next = renumbered.len();
+                *renumbered.entry(community).or_insert(next)
+            })
+            .collect();
+
+        for community in &mut membership {
+            *community = level[*community];
+        }
+
+        // Internal edges are dropped: they only contribute to a node's degree,
+        // which is carried over unchanged.
+        let mut aggregated = vec![HashMap::new(); renumbered.len()];
+        let mut aggregated_degrees = vec![0.0; renumbered.len()];
+        for (node, links) in adjacency.iter().enumerate() {
+            aggregated_degrees[level[node]] += degrees[node];
+            for (&neighbour, &weight) in links {
+                if level[node] != level[neighbour] {
+                    *aggregated[level[node]]
+                        .entry(level[neighbour])
+                        .or_insert(0.0) += weight;
+                }
+            }
+        }
+
+        adjacency = aggregated;
+        degrees = aggregated_degrees;
+    }
+}
+
+/// Run Louvain local-move passes, returning each node's community and whether any node moved.
+fn louvain_local_moves(
+    adjacency: &[HashMap<usize, f64>],
+    degrees: &[f64],
+    total_weight: f64,
+) -> (Vec<usize>, bool) {
+    let mut community: Vec<usize> = (0..adjacency.len()).collect();
+    let mut community_totals = degrees.to_vec();
+    let mut moved_any = false;
+
+    for _ in 0..LOUVAIN_MAX_PASSES {
+        let mut moved = false;
+
+        for node in 0..adjacency.len() {
+            let current = community[node];
+            let degree = degrees[node];
+            community_totals[current] -= degree;
+
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for (&neighbour, &weight) in &adjacency[node] {
+                *links.entry(community[neighbour]).or_insert(0.0) += weight;
+            }
+
+            // Modularity gain of joining a community, up to a constant factor of 1/m
+            let gain = |target: usize, weight: f64| {
+                weight - community_totals[target] * degree / total_weight
+            };
+
+            let mut best = current;
+            let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+            for (&target, &weight) in &links {
+                let candidate = gain(target, weight);
+                if candidate > best_gain + LOUVAIN_GAIN_EPSILON {
+                    best = target;
+                    best_gain = candidate;
+                }
+            }
+
+            community_totals[best] += degree;
+            if best != current {
+                community[node] = best;
+                moved = true;
+            }
+        }
+
+        if !moved {
+            break;
+        }
+        moved_any = true;
+    }
+
+    (community, moved_any)
+}
+
+/// LCOM4 (lack of cohesion of methods): connected components of the cohesion graph.
+///
+/// Entities are joined when their symbol sets overlap enough to produce a
+/// cohesion edge, so this approximates the classic method-attribute LCOM4.
+/// Values above 1 mean the entities fall into independent groups that could
+/// be split apart along component boundaries.
+pub fn lcom4(cohesion_graph: &CohesionGraph) -> usize {
+    petgraph::algo::connected_components(cohesion_graph)
+}
+
 /// Estimate clone factor from cohesion graph
 pub fn estimate_clone_factor(graph: &CohesionGraph) -> f64 {
     let node_count = graph.node_count();
@@ -1,23 +1,32 @@
 //! Import resolution and project dependency scanning.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
+use std::time::UNIX_EPOCH;
 
-use crate::core::errors::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::errors::{Result, ValknutError, ValknutResultExt};
 use crate::core::file_utils::FileReader;
 use crate::lang::common::{EntityKind, ParsedEntity};
 use crate::lang::registry::adapter_for_file;
 
 use crate::detectors::structure::config::{
-    is_code_extension, should_skip_directory, ImportStatement, CODE_EXTENSIONS,
+    is_code_extension, should_skip_directory, ImportStatement, WorkspaceConfig, CODE_EXTENSIONS,
 };
 
+use super::workspace::PackageBoundaries;
+
 /// Snapshot of project imports for dependency analysis
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct ProjectImportSnapshot {
     pub imports_by_file: HashMap<PathBuf, Vec<PathBuf>>,
     pub reverse_imports: HashMap<PathBuf, HashSet<PathBuf>>,
+    /// Imports that cross a workspace package boundary, keyed by importing file
+    pub external_imports: HashMap<PathBuf, HashSet<PathBuf>>,
 }
 
 /// Metrics about a file's dependencies
@@ -26,6 +35,16 @@ pub struct FileDependencyMetrics {
     pub exports: Vec<ExportedEntity>,
     pub outgoing_dependencies: HashSet<PathBuf>,
     pub incoming_importers: HashSet<PathBuf>,
+    /// Outgoing dependencies sorted by target, flagged when they leave the package
+    pub dependencies: Vec<DependencyEdge>,
+}
+
+/// A resolved outgoing dependency of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyEdge {
+    pub target: PathBuf,
+    /// True when the target lives in a different workspace package
+    pub external: bool,
 }
 
 /// An exported entity from a file
@@ -35,9 +54,19 @@ pub struct ExportedEntity {
     pub kind: EntityKind,
 }
 
+/// On-disk form of a [`ProjectImportSnapshot`], tagged with the key it was built for
+#[derive(Serialize, Deserialize)]
+struct CachedImportSnapshot {
+    key: String,
+    snapshot: ProjectImportSnapshot,
+}
+
 /// Import resolver for project dependency scanning
 pub struct ImportResolver {
     project_import_cache: Arc<RwLock<HashMap<PathBuf, Arc<ProjectImportSnapshot>>>>,
+    workspace: Option<WorkspaceConfig>,
+    /// Directory persisting snapshots between runs; `None` keeps them in memory
+    cache_dir: Option<PathBuf>,
 }
 
 /// Factory, caching, and resolution methods for [`ImportResolver`].
@@ -46,9 +75,23 @@ impl ImportResolver {
     pub fn new() -> Self {
         Self {
             project_import_cache: Arc::new(RwLock::new(HashMap::new())),
+            workspace: None,
+            cache_dir: None,
         }
     }
 
+    /// Scope import resolution to workspace packages.
+    pub fn with_workspace(mut self, workspace: Option<WorkspaceConfig>) -> Self {
+        self.workspace = workspace;
+        self
+    }
+
+    /// Persist project import snapshots under `cache_dir` between runs.
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
     /// Check if file extension indicates a code file
     pub fn is_code_file(&self, extension: &str) -> bool {
         is_code_extension(extension)
@@ -79,6 +122,17 @@ impl ImportResolver {
                     .extend(targets.iter().cloned());
             }
 
+            let external = snapshot.external_imports.get(&canonical_file);
+            metrics.dependencies = metrics
+                .outgoing_dependencies
+                .iter()
+                .map(|target| DependencyEdge {
+                    target: target.clone(),
+                    external: external.is_some_and(|targets| targets.contains(target)),
+                })
+                .collect();
+            metrics.dependencies.sort_by(|a, b| a.target.cmp(&b.target));
+
             if let Some(importers) = snapshot.reverse_imports.get(&canonical_file) {
                 metrics.incoming_importers.extend(importers.iter().cloned());
             }
@@ -134,8 +188,7 @@ impl ImportResolver {
                 .metadata
                 .get("visibility")
                 .and_then(|value| value.as_str())
-                .map(|vis| vis.contains("pub"))
-                .unwrap_or(false),
+                .is_some_and(is_external_rust_visibility),
             "py" | "pyi" => {
                 if entity.name.starts_with('_') {
                     return false;
@@ -208,7 +261,10 @@ impl ImportResolver {
             return Ok(snapshot);
         }
 
-        let snapshot = Arc::new(self.build_project_import_snapshot(&canonical_root)?);
+        let snapshot = Arc::new(match &self.cache_dir {
+            Some(cache_dir) => self.load_or_build_snapshot(cache_dir, &canonical_root)?,
+            None => self.build_project_import_snapshot(&canonical_root)?,
+        });
         self.project_import_cache
             .write()
             .unwrap()
@@ -217,18 +273,123 @@ impl ImportResolver {
         Ok(snapshot)
     }
 
+    /// Load the snapshot persisted for `project_root`, rebuilding it when stale.
+    ///
+    /// A missing or unreadable cache file is rebuilt; failing to write the
+    /// rebuilt snapshot is logged and does not fail the analysis.
+    fn load_or_build_snapshot(
+        &self,
+        cache_dir: &Path,
+        project_root: &Path,
+    ) -> Result<ProjectImportSnapshot> {
+        let key = self.snapshot_cache_key(project_root)?;
+        let cache_path = cache_dir.join(format!(
+            "import_snapshot.{}.json",
+            hex_digest(project_root.to_string_lossy().as_bytes())
+        ));
+
+        if let Ok(raw) = fs::read_to_string(&cache_path) {
+            match serde_json::from_str::<CachedImportSnapshot>(&raw) {
+                Ok(cached) if cached.key == key => return Ok(cached.snapshot),
+                Ok(_) => {
+                    tracing::debug!("Import snapshot cache is stale: {}", cache_path.display())
+                }
+                Err(e) => tracing::debug!(
+                    "Ignoring unreadable import snapshot cache {}: {}",
+                    cache_path.display(),
+                    e
+                ),
+            }
+        }
+
+        let cached = CachedImportSnapshot {
+            key,
+            snapshot: self.build_project_import_snapshot(project_root)?,
+        };
+        if let Err(e) = save_snapshot(cache_dir, &cache_path, &cached) {
+            tracing::warn!("Failed to persist import snapshot: {}", e);
+        }
+        Ok(cached.snapshot)
+    }
+
+    /// Key identifying the inputs of a snapshot: the project root, the sorted
+    /// code file paths, the newest code file modification time, the contents
+    /// of every package manifest between the root and those files, and the
+    /// workspace.
+    ///
+    /// The path list catches added, removed and renamed files, which can leave
+    /// the newest mtime unchanged; manifests change how imports resolve.
+    fn snapshot_cache_key(&self, project_root: &Path) -> Result<String> {
+        let mut files = self.collect_project_code_files(project_root)?;
+        files.sort();
+        let max_mtime = files
+            .iter()
+            .filter_map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+            .filter_map(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .max()
+            .unwrap_or_default();
+        let absolute_root =
+            std::path::absolute(project_root).unwrap_or_else(|_| project_root.to_path_buf());
+
+        let mut hasher = Sha256::new();
+        hasher.update(
+            format!(
+                "{}\0{}\0{}\0{:?}\0",
+                absolute_root.display(),
+                project_root.display(),
+                max_mtime.as_nanos(),
+                self.workspace
+            )
+            .as_bytes(),
+        );
+        for file in &files {
+            hasher.update(file.to_string_lossy().as_bytes());
+            hasher.update(b"\0");
+        }
+        for manifest in manifest_files(project_root, &files) {
+            hasher.update(manifest.to_string_lossy().as_bytes());
+            hasher.update(b"\0");
+            hasher.update(fs::read(&manifest).unwrap_or_default());
+            hasher.update(b"\0");
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
     /// Build a fresh project import snapshot by scanning all code files.
+    ///
+    /// With a workspace configured, imports resolve within the importing
+    /// file's package first and edges crossing a package boundary are
+    /// recorded in [`ProjectImportSnapshot::external_imports`].
     fn build_project_import_snapshot(&self, project_root: &Path) -> Result<ProjectImportSnapshot> {
         let mut snapshot = ProjectImportSnapshot::default();
+        let boundaries = self
+            .workspace
+            .as_ref()
+            .map(|workspace| PackageBoundaries::resolve(project_root, workspace));
+
         for file in self.collect_project_code_files(project_root)? {
             let canonical_file = self.canonicalize_path(&file);
             let imports = self.extract_imports(&file)?;
 
             for import in imports {
-                if let Some(resolved) =
-                    self.resolve_import_to_project_file(&import, &file, project_root)
-                {
+                let resolved = match &boundaries {
+                    Some(boundaries) => {
+                        self.resolve_import_in_workspace(&import, &file, project_root, boundaries)
+                    }
+                    None => self.resolve_import_to_project_file(&import, &file, project_root),
+                };
+                if let Some(resolved) = resolved {
                     let canonical_target = self.canonicalize_path(&resolved);
+                    if boundaries
+                        .as_ref()
+                        .is_some_and(|b| b.crosses_boundary(&file, &resolved))
+                    {
+                        snapshot
+                            .external_imports
+                            .entry(canonical_file.clone())
+                            .or_default()
+                            .insert(canonical_target.clone());
+                    }
                     snapshot
                         .imports_by_file
                         .entry(canonical_file.clone())
@@ -336,6 +497,22 @@ impl ImportResolver {
         None
     }
 
+    /// Resolve an import against the importing file's package before the whole project.
+    fn resolve_import_in_workspace(
+        &self,
+        import: &ImportStatement,
+        current_file: &Path,
+        project_root: &Path,
+        boundaries: &PackageBoundaries,
+    ) -> Option<PathBuf> {
+        boundaries
+            .package_of(current_file)
+            .and_then(|package_root| {
+                self.resolve_import_to_project_file(import, current_file, package_root)
+            })
+            .or_else(|| self.resolve_import_to_project_file(import, current_file, project_root))
+    }
+
     /// Resolve Python relative import (dot notation) to candidate paths.
     fn resolve_python_relative_module(
         &self,
@@ -467,3 +644,66 @@ impl Default for ImportResolver {
         Self::new()
     }
 }
+
+/// Whether a Rust visibility modifier exposes an item outside its crate.
+///
+/// Only bare `pub` counts; `pub(crate)`, `pub(super)`, `pub(self)` and
+/// `pub(in path)` are crate-internal, and anything else is private.
+fn is_external_rust_visibility(visibility: &str) -> bool {
+    visibility.trim() == "pub"
+}
+
+/// Hex-encoded SHA-256 digest of `bytes`.
+fn hex_digest(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Package manifests that change how imports resolve.
+const MANIFEST_FILE_NAMES: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml", "go.mod"];
+
+/// Manifests in `project_root` or any directory between it and one of
+/// `files`, in sorted order.
+fn manifest_files(project_root: &Path, files: &[PathBuf]) -> BTreeSet<PathBuf> {
+    let mut directories = BTreeSet::new();
+    for file in files {
+        let mut dir = file.parent();
+        while let Some(current) = dir {
+            if !current.starts_with(project_root) || !directories.insert(current.to_path_buf()) {
+                break;
+            }
+            dir = current.parent();
+        }
+    }
+    directories.insert(project_root.to_path_buf());
+
+    directories
+        .iter()
+        .flat_map(|dir| MANIFEST_FILE_NAMES.iter().map(move |name| dir.join(name)))
+        .filter(|manifest| manifest.is_file())
+        .collect()
+}
+
+/// Write a snapshot cache file atomically: write a temporary file, then rename it.
+fn save_snapshot(cache_dir: &Path, cache_path: &Path, cached: &CachedImportSnapshot) -> Result<()> {
+    fs::create_dir_all(cache_dir).map_err(|e| {
+        ValknutError::io(
+            format!("Failed to create cache directory: {}", cache_dir.display()),
+            e,
+        )
+    })?;
+
+    let temp_path = cache_path.with_extension("tmp");
+    let content = serde_json::to_string(cached).map_json_err("import snapshot serialization")?;
+    fs::write(&temp_path, content).map_err(|e| {
+        ValknutError::io(
+            format!("Failed to write cache file: {}", temp_path.display()),
+            e,
+        )
+    })?;
+    fs::rename(&temp_path, cache_path).map_err(|e| {
+        ValknutError::io(
+            format!("Failed to rename cache file: {}", cache_path.display()),
+            e,
+        )
+    })
+}
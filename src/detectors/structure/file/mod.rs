@@ -2,21 +2,26 @@
 
 pub(crate) mod cohesion;
 pub(crate) mod imports;
+pub(crate) mod smells;
 pub(crate) mod splitting;
+pub(crate) mod workspace;
 
 use petgraph::Graph;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use xxhash_rust::xxh3::xxh3_64;
 
 use crate::core::ast_utils::count_named_nodes;
 use crate::core::errors::Result;
 use crate::core::file_utils::FileReader;
+use crate::doc_audit::GitHelper;
 use crate::lang::common::EntityKind;
 use crate::lang::registry::{adapter_for_file, get_tree_sitter_language};
 
 use super::config::{
     is_code_extension, CohesionGraph, EntityNode, FileEntityHealth, FileMetrics, FileSplitPack,
-    StructureConfig, SKIP_DIRECTORIES,
+    HealthReport, StructureConfig, SKIP_DIRECTORIES,
 };
 use super::health::HealthScorer;
 use super::PrecomputedFileMetrics;
@@ -26,26 +31,52 @@ use imports::ImportResolver;
 use splitting::SplitAnalyzer;
 
 // Re-export for backward compatibility
-pub use cohesion::estimate_clone_factor;
-pub use imports::{ExportedEntity, FileDependencyMetrics, ProjectImportSnapshot};
+pub use cohesion::{estimate_clone_factor, lcom4};
+pub use imports::{DependencyEdge, ExportedEntity, FileDependencyMetrics, ProjectImportSnapshot};
+pub use smells::{
+    change_coupling_from_history, compute_change_coupling, detect_god_class,
+    detect_shotgun_surgery, rank_change_coupled_pairs, read_co_change_history, ChangeCoupledPair,
+    ShotgunCandidate, HIDDEN_CHANGE_COUPLING_THRESHOLD, MAX_CHANGE_COUPLED_PAIRS,
+};
 pub use splitting::analyze_entity_names;
+pub use workspace::{detect_package_roots, PackageBoundaries};
 
 /// Analyzer for file-level structure metrics and splitting recommendations.
 pub struct FileAnalyzer {
     config: StructureConfig,
     import_resolver: ImportResolver,
+    /// Extracted entities keyed by file path and xxh3 hash of the content
+    entity_cache: Arc<RwLock<HashMap<(PathBuf, u64), Vec<EntityNode>>>>,
+    /// Shotgun Surgery candidates keyed by canonical project root
+    shotgun_cache: Arc<RwLock<HashMap<PathBuf, Arc<Vec<ShotgunCandidate>>>>>,
+    /// Files changed per recent commit keyed by canonical project root, shared
+    /// by Shotgun Surgery and change coupling so history is walked once
+    co_change_cache: Arc<RwLock<HashMap<PathBuf, Arc<Vec<HashSet<PathBuf>>>>>>,
+    /// LCOM4 of each file's entity cohesion graph keyed by file path
+    lcom4_cache: Arc<RwLock<HashMap<PathBuf, usize>>>,
 }
 
 /// Factory, metrics, cohesion, and splitting methods for [`FileAnalyzer`].
 impl FileAnalyzer {
     /// Creates a new file analyzer with the given configuration.
     pub fn new(config: StructureConfig) -> Self {
+        let import_resolver = ImportResolver::new().with_workspace(config.workspace.clone());
         Self {
             config,
-            import_resolver: ImportResolver::new(),
+            import_resolver,
+            entity_cache: Arc::new(RwLock::new(HashMap::new())),
+            shotgun_cache: Arc::new(RwLock::new(HashMap::new())),
+            co_change_cache: Arc::new(RwLock::new(HashMap::new())),
+            lcom4_cache: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Persist project import snapshots under `cache_dir` between runs.
+    pub fn with_import_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.import_resolver = self.import_resolver.with_cache_dir(cache_dir);
+        self
+    }
+
     /// Check if file extension indicates a code file
     pub fn is_code_file(&self, extension: &str) -> bool {
         is_code_extension(extension)
@@ -142,27 +173,27 @@ impl FileAnalyzer {
         })
     }
 
-    /// Calculate aggregated entity health metrics for a file.
-    pub fn calculate_entity_health(
-        &self,
-        file_path: &Path,
-        content: &str,
-    ) -> Result<FileEntityHealth> {
+    /// Calculate entity health for a file: per-entity scores and their AST-weighted aggregate.
+    pub fn calculate_entity_health(&self, file_path: &Path, content: &str) -> Result<HealthReport> {
         let entities = self.extract_entities_with_treesitter(file_path, content)?;
         let scorer = HealthScorer::new(self.config.clone());
 
         if entities.is_empty() {
-            return Ok(FileEntityHealth {
-                entity_count: 0,
-                total_ast_nodes: 0,
-                health: 1.0,
-                min_health: 1.0,
+            return Ok(HealthReport {
+                aggregate: FileEntityHealth {
+                    entity_count: 0,
+                    total_ast_nodes: 0,
+                    health: 1.0,
+                    min_health: 1.0,
+                },
+                per_entity: Vec::new(),
             });
         }
 
         let mut total_ast_nodes = 0usize;
         let mut weighted_health_sum = 0.0;
         let mut min_health = 1.0f64;
+        let mut per_entity = Vec::with_capacity(entities.len());
 
         for entity in &entities {
             let health = match entity.entity_type.as_str() {
@@ -174,6 +205,7 @@ impl FileAnalyzer {
             total_ast_nodes += entity.ast_nodes;
             weighted_health_sum += health.health * weight;
             min_health = min_health.min(health.health);
+            per_entity.push((entity.name.clone(), health));
         }
 
         let health = if total_ast_nodes > 0 {
@@ -182,11 +214,14 @@ impl FileAnalyzer {
             1.0
         };
 
-        Ok(FileEntityHealth {
-            entity_count: entities.len(),
-            total_ast_nodes,
-            health,
-            min_health,
+        Ok(HealthReport {
+            aggregate: FileEntityHealth {
+                entity_count: entities.len(),
+                total_ast_nodes,
+                health,
+                min_health,
+            },
+            per_entity,
         })
     }
 
@@ -226,14 +261,19 @@ impl FileAnalyzer {
             .collect_dependency_metrics(file_path, project_root)?;
 
         let split_analyzer = SplitAnalyzer::new(&self.config);
-        split_analyzer.build_split_pack(
+        let mut pack = split_analyzer.build_split_pack(
             file_path,
             loc,
             size_bytes,
             &cohesion_graph,
             communities,
             &dependency_metrics,
-        )
+        )?;
+        if let (Some(pack), Some(root)) = (pack.as_mut(), project_root) {
+            pack.reasons
+                .extend(self.shotgun_surgery_reasons(file_path, root)?);
+        }
+        Ok(pack)
     }
 
     /// Analyze file for splitting using pre-computed metrics (avoids file I/O)
@@ -254,14 +294,122 @@ impl FileAnalyzer {
             .collect_dependency_metrics(file_path, Some(project_root))?;
 
         let split_analyzer = SplitAnalyzer::new(&self.config);
-        split_analyzer.build_split_pack(
+        let mut pack = split_analyzer.build_split_pack(
             file_path,
             loc,
             size_bytes,
             &cohesion_graph,
             communities,
             &dependency_metrics,
-        )
+        )?;
+        if let Some(pack) = pack.as_mut() {
+            pack.reasons
+                .extend(self.shotgun_surgery_reasons(file_path, project_root)?);
+        }
+        Ok(pack)
+    }
+
+    /// Detect Shotgun Surgery across a project, caching the result per root.
+    pub fn detect_shotgun_surgery(
+        &self,
+        project_root: &Path,
+    ) -> Result<Arc<Vec<ShotgunCandidate>>> {
+        let canonical_root = self.canonicalize_path(project_root);
+        if let Some(candidates) = self
+            .shotgun_cache
+            .read()
+            .unwrap()
+            .get(&canonical_root)
+            .cloned()
+        {
+            return Ok(candidates);
+        }
+
+        let snapshot = self
+            .import_resolver
+            .get_project_import_snapshot(&canonical_root)?;
+        let git_helper = GitHelper::new(&canonical_root);
+        let history = self.co_change_history(&canonical_root, &git_helper);
+        let candidates = Arc::new(detect_shotgun_surgery(
+            &snapshot,
+            &git_helper,
+            &history,
+            self.config.fsfile.shotgun_surgery_threshold,
+        ));
+        self.shotgun_cache
+            .write()
+            .unwrap()
+            .insert(canonical_root, candidates.clone());
+        Ok(candidates)
+    }
+
+    /// The `limit` most change-coupled project files from recent git history.
+    pub fn detect_change_coupled_pairs(
+        &self,
+        project_root: &Path,
+        limit: usize,
+    ) -> Result<Vec<ChangeCoupledPair>> {
+        let canonical_root = self.canonicalize_path(project_root);
+        let snapshot = self
+            .import_resolver
+            .get_project_import_snapshot(&canonical_root)?;
+        let files: Vec<PathBuf> = self
+            .import_resolver
+            .collect_project_code_files(&canonical_root)?
+            .iter()
+            .map(|file| self.canonicalize_path(file))
+            .collect();
+        let git_helper = GitHelper::new(&canonical_root);
+        let history = self.co_change_history(&canonical_root, &git_helper);
+        let coupling = change_coupling_from_history(&git_helper, &files, &history);
+        Ok(rank_change_coupled_pairs(coupling, &snapshot, limit))
+    }
+
+    /// Recent co-change history for a project, read from git once per root.
+    fn co_change_history(
+        &self,
+        canonical_root: &Path,
+        git_helper: &GitHelper,
+    ) -> Arc<Vec<HashSet<PathBuf>>> {
+        if let Some(history) = self
+            .co_change_cache
+            .read()
+            .unwrap()
+            .get(canonical_root)
+            .cloned()
+        {
+            return history;
+        }
+
+        let history = Arc::new(read_co_change_history(
+            git_helper,
+            self.config.fsfile.co_change_window,
+        ));
+        self.co_change_cache
+            .write()
+            .unwrap()
+            .insert(canonical_root.to_path_buf(), history.clone());
+        history
+    }
+
+    /// Split reasons for a file flagged with the Shotgun Surgery smell.
+    fn shotgun_surgery_reasons(
+        &self,
+        file_path: &Path,
+        project_root: &Path,
+    ) -> Result<Vec<String>> {
+        let canonical_file = self.canonicalize_path(file_path);
+        Ok(self
+            .detect_shotgun_surgery(project_root)?
+            .iter()
+            .filter(|candidate| candidate.file == canonical_file)
+            .map(|candidate| {
+                format!(
+                    "shotgun surgery ({} importers, {} co-change commits)",
+                    candidate.importer_count, candidate.co_change_count
+                )
+            })
+            .collect())
     }
 
     /// Build entity cohesion graph from pre-loaded source (avoids file I/O)
@@ -280,6 +428,21 @@ impl FileAnalyzer {
         self.build_entity_cohesion_graph_from_source(file_path, &content)
     }
 
+    /// LCOM4 of the file's entity cohesion graph, built once per file and
+    /// shared by every entity in it.
+    pub fn file_lcom4(&self, file_path: &Path) -> Result<usize> {
+        if let Some(&lcom) = self.lcom4_cache.read().unwrap().get(file_path) {
+            return Ok(lcom);
+        }
+
+        let lcom = lcom4(&self.build_entity_cohesion_graph(file_path)?);
+        self.lcom4_cache
+            .write()
+            .unwrap()
+            .insert(file_path.to_path_buf(), lcom);
+        Ok(lcom)
+    }
+
     /// Find cohesion communities in entity graph
     pub fn find_cohesion_communities(
         &self,
@@ -294,10 +457,10 @@ impl FileAnalyzer {
         &self,
         file_path: &Path,
         communities: &[Vec<petgraph::graph::NodeIndex>],
+        cohesion_graph: &CohesionGraph,
     ) -> Result<Vec<super::config::SuggestedSplit>> {
-        let cohesion_graph = self.build_entity_cohesion_graph(file_path)?;
         let split_analyzer = SplitAnalyzer::new(&self.config);
-        split_analyzer.generate_split_suggestions(file_path, communities, &cohesion_graph)
+        split_analyzer.generate_split_suggestions(file_path, communities, cohesion_graph)
     }
 
     /// Generate a meaningful name for a split file based on entity analysis
@@ -339,23 +502,38 @@ impl FileAnalyzer {
     }
 
     /// Calculate Jaccard similarity between two symbol sets
-    pub fn calculate_jaccard_similarity(&self, a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    pub fn calculate_jaccard_similarity(&self, a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
         calculate_jaccard_similarity(a, b)
     }
 
     /// Extract entities using tree-sitter for accurate parsing
+    ///
+    /// Results are cached by path and an xxh3 hash of `content`, so repeated
+    /// calls with unchanged content skip the parse.
     pub fn extract_entities_with_treesitter(
         &self,
         file_path: &Path,
         content: &str,
     ) -> Result<Vec<EntityNode>> {
+        let cache_key = (file_path.to_path_buf(), xxh3_64(content.as_bytes()));
+        if let Some(entities) = self.entity_cache.read().unwrap().get(&cache_key) {
+            return Ok(entities.clone());
+        }
+
         let file_path_str = file_path.to_string_lossy().to_string();
-        match adapter_for_file(file_path) {
+        let entities = match adapter_for_file(file_path) {
             Ok(mut adapter) => {
-                self.extract_entities_from_adapter(adapter.as_mut(), content, &file_path_str)
+                self.extract_entities_from_adapter(adapter.as_mut(), content, &file_path_str)?
             }
-            Err(_) => Ok(Vec::new()),
-        }
+            Err(_) => Vec::new(),
+        };
+
+        let mut cache = self.entity_cache.write().unwrap();
+        // Drop entries for earlier content of the same file
+        cache.retain(|(path, _), _| path != &cache_key.0);
+        cache.insert(cache_key, entities.clone());
+
+        Ok(entities)
     }
 
     /// Extracts entities from a language adapter.
@@ -369,7 +547,7 @@ impl FileAnalyzer {
         let parsed_entities = parse_index.get_entities_in_file(file_path);
         let mut entities = Vec::new();
 
-        for parsed in parsed_entities {
+        for parsed in &parsed_entities {
             if !self.is_supported_entity_kind(parsed.kind) {
                 continue;
             }
@@ -384,7 +562,7 @@ impl FileAnalyzer {
 
             let entity_source = self.get_entity_lines_from_source(content, start_line, end_line);
 
-            let mut symbols = HashSet::new();
+            let mut symbols = BTreeSet::new();
             if !entity_source.is_empty() {
                 if let Ok(identifiers) = adapter.extract_identifiers(&entity_source) {
                     for identifier in identifiers {
@@ -395,12 +573,36 @@ impl FileAnalyzer {
 
             let ast_nodes = self.estimate_ast_nodes_from_loc(loc);
 
+            let (methods, field_count) = if matches!(
+                parsed.kind,
+                EntityKind::Class | EntityKind::Struct | EntityKind::Interface
+            ) {
+                let methods = parsed_entities
+                    .iter()
+                    .filter(|child| {
+                        child.parent.as_deref() == Some(parsed.id.as_str())
+                            || child.metadata.get("impl_type").and_then(|ty| ty.as_str())
+                                == Some(parsed.name.as_str())
+                    })
+                    .filter(|child| matches!(child.kind, EntityKind::Method | EntityKind::Function))
+                    .map(|child| child.name.clone())
+                    .collect();
+                (
+                    methods,
+                    smells::estimate_field_count(&entity_source, Path::new(file_path)),
+                )
+            } else {
+                (Vec::new(), 0)
+            };
+
             entities.push(EntityNode {
                 name: parsed.name.clone(),
                 entity_type: format!("{:?}", parsed.kind).to_lowercase(),
                 loc,
                 ast_nodes,
                 symbols,
+                methods,
+                field_count,
             });
         }
 
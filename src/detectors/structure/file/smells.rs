@@ -0,0 +1,380 @@
+//! Structural code smell detection for entities extracted from a single file.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::imports::ProjectImportSnapshot;
+use super::splitting::leading_word;
+use crate::detectors::structure::config::{EntityNode, GodClassDiagnosis, StructureConfig};
+use crate::doc_audit::GitHelper;
+use crate::lang::registry::get_tree_sitter_language;
+
+/// Change coupling above which files that do not import each other are
+/// hidden-coupling suspects.
+pub const HIDDEN_CHANGE_COUPLING_THRESHOLD: f64 = 0.3;
+
+/// Number of change-coupled file pairs surfaced in analysis results.
+pub const MAX_CHANGE_COUPLED_PAIRS: usize = 5;
+
+/// Verb groups used to estimate how many distinct responsibilities a class has.
+const VERB_GROUPS: &[(&str, &[&str])] = &[
+    (
+        "access",
+        &["get", "fetch", "load", "read", "find", "lookup", "query"],
+    ),
+    (
+        "mutate",
+        &["set", "update", "save", "write", "store", "put", "add"],
+    ),
+    (
+        "create",
+        &["create", "build", "make", "new", "init", "spawn"],
+    ),
+    ("delete", &["delete", "remove", "clear", "drop", "reset"]),
+    (
+        "validate",
+        &["validate", "check", "verify", "is", "has", "can", "should"],
+    ),
+    (
+        "present",
+        &["render", "format", "display", "print", "show", "to"],
+    ),
+    (
+        "transform",
+        &["parse", "convert", "transform", "from", "map", "serialize"],
+    ),
+    (
+        "communicate",
+        &[
+            "send", "notify", "emit", "publish", "dispatch", "handle", "on",
+        ],
+    ),
+    (
+        "compute",
+        &["calculate", "compute", "count", "sum", "measure", "score"],
+    ),
+];
+
+/// Detect the God Class smell: a class with too many methods or fields.
+///
+/// Only class-like entities are considered. Returns `None` when the entity is
+/// within the configured method and field limits.
+pub fn detect_god_class(
+    entity: &EntityNode,
+    config: &StructureConfig,
+) -> Option<GodClassDiagnosis> {
+    if !matches!(entity.entity_type.as_str(), "class" | "struct") {
+        return None;
+    }
+
+    let method_count = entity.methods.len();
+    let field_count = entity.field_count;
+    if method_count <= config.fsfile.max_class_methods
+        && field_count <= config.fsfile.max_class_fields
+    {
+        return None;
+    }
+
+    Some(GodClassDiagnosis {
+        method_count,
+        field_count,
+        responsibility_count: estimate_responsibility_count(&entity.methods),
+    })
+}
+
+/// A file showing the Shotgun Surgery smell: widely imported and usually
+/// edited together with its importers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShotgunCandidate {
+    /// Path of the imported file
+    pub file: PathBuf,
+    /// Number of distinct project files importing it
+    pub importer_count: usize,
+    /// Commits that changed the file together with at least one importer
+    pub co_change_count: usize,
+}
+
+/// Files changed by each of the latest `window_size` commits.
+///
+/// Read once per project and shared by [`detect_shotgun_surgery`] and
+/// [`change_coupling_from_history`]. Empty without a git repository or
+/// commits; a history that cannot be read is logged and treated as empty.
+pub fn read_co_change_history(git_helper: &GitHelper, window_size: usize) -> Vec<HashSet<PathBuf>> {
+    if git_helper.head_oid().is_none() {
+        return Vec::new();
+    }
+    git_helper
+        .changed_files_by_commit(window_size)
+        .unwrap_or_else(|| {
+            tracing::warn!("Failed to read git history; skipping co-change analysis");
+            Vec::new()
+        })
+}
+
+/// Detect the Shotgun Surgery smell from the import graph and git history.
+///
+/// A file is reported when at least `threshold` other files import it and at
+/// least `threshold` commits in `history` changed it alongside one of those
+/// importers. Results are ordered by co-change count, then importer count,
+/// then path. With an empty history nothing is reported.
+pub fn detect_shotgun_surgery(
+    snapshot: &ProjectImportSnapshot,
+    git_helper: &GitHelper,
+    history: &[HashSet<PathBuf>],
+    threshold: usize,
+) -> Vec<ShotgunCandidate> {
+    if history.is_empty() {
+        return Vec::new();
+    }
+
+    let widely_imported: Vec<(&PathBuf, Vec<&PathBuf>)> = snapshot
+        .reverse_imports
+        .iter()
+        .map(|(file, importers)| {
+            let importers = importers.iter().filter(|importer| *importer != file);
+            (file, importers.collect::<Vec<_>>())
+        })
+        .filter(|(_, importers)| importers.len() >= threshold)
+        .collect();
+    if widely_imported.is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<ShotgunCandidate> = widely_imported
+        .into_iter()
+        .filter_map(|(file, importers)| {
+            let relative_file = git_helper.relative_to_repo(file)?;
+            let relative_importers: HashSet<PathBuf> = importers
+                .iter()
+                .filter_map(|importer| git_helper.relative_to_repo(importer))
+                .collect();
+            let co_change_count = history
+                .iter()
+                .filter(|changed| {
+                    changed.contains(&relative_file)
+                        && changed.iter().any(|path| relative_importers.contains(path))
+                })
+                .count();
+            (co_change_count >= threshold).then(|| ShotgunCandidate {
+                file: file.clone(),
+                importer_count: importers.len(),
+                co_change_count,
+            })
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| {
+        b.co_change_count
+            .cmp(&a.co_change_count)
+            .then_with(|| b.importer_count.cmp(&a.importer_count))
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    candidates
+}
+
+/// Two files that tend to change in the same commits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeCoupledPair {
+    /// Lexicographically smaller path of the pair
+    pub first: PathBuf,
+    /// Lexicographically larger path of the pair
+    pub second: PathBuf,
+    /// Share of inspected commits that changed both files
+    pub coupling: f64,
+    /// Whether either file imports the other
+    pub imports_each_other: bool,
+}
+
+/// Query methods for [`ChangeCoupledPair`].
+impl ChangeCoupledPair {
+    /// Strongly coupled in history but not linked in the import graph.
+    pub fn is_hidden_coupling(&self) -> bool {
+        !self.imports_each_other && self.coupling > HIDDEN_CHANGE_COUPLING_THRESHOLD
+    }
+}
+
+/// Change coupling between files over the latest `window_size` commits.
+///
+/// Reads the history with [`read_co_change_history`]; see
+/// [`change_coupling_from_history`] for how pairs are scored.
+pub fn compute_change_coupling(
+    git_helper: &GitHelper,
+    files: &[PathBuf],
+    window_size: usize,
+) -> HashMap<(PathBuf, PathBuf), f64> {
+    let history = read_co_change_history(git_helper, window_size);
+    change_coupling_from_history(git_helper, files, &history)
+}
+
+/// Change coupling between files over the commits in `history`.
+///
+/// Each pair of `files` changed by the same commit gains one co-occurrence;
+/// the coefficient is co-occurrences divided by the number of commits
+/// inspected. Keys are ordered `(smaller path, larger path)` and pairs that
+/// never changed together are omitted. With an empty history the map is
+/// empty.
+pub fn change_coupling_from_history(
+    git_helper: &GitHelper,
+    files: &[PathBuf],
+    history: &[HashSet<PathBuf>],
+) -> HashMap<(PathBuf, PathBuf), f64> {
+    if history.is_empty() {
+        return HashMap::new();
+    }
+
+    let tracked: HashMap<PathBuf, &PathBuf> = files
+        .iter()
+        .filter_map(|file| Some((git_helper.relative_to_repo(file)?, file)))
+        .collect();
+
+    let mut co_changes: HashMap<(PathBuf, PathBuf), usize> = HashMap::new();
+    for changed in history {
+        let mut touched: Vec<&PathBuf> = changed
+            .iter()
+            .filter_map(|path| tracked.get(path).copied())
+            .collect();
+        touched.sort();
+        touched.dedup();
+        for (index, first) in touched.iter().enumerate() {
+            for second in &touched[index + 1..] {
+                *co_changes
+                    .entry(((*first).clone(), (*second).clone()))
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    let total = history.len() as f64;
+    co_changes
+        .into_iter()
+        .map(|(pair, count)| (pair, count as f64 / total))
+        .collect()
+}
+
+/// The `limit` most change-coupled pairs, flagged with their import links.
+///
+/// Ties on coupling are broken by path so the ranking is deterministic.
+pub fn rank_change_coupled_pairs(
+    coupling: HashMap<(PathBuf, PathBuf), f64>,
+    snapshot: &ProjectImportSnapshot,
+    limit: usize,
+) -> Vec<ChangeCoupledPair> {
+    let imports = |from: &PathBuf, to: &PathBuf| {
+        snapshot
+            .imports_by_file
+            .get(from)
+            .is_some_and(|targets| targets.contains(to))
+    };
+
+    let mut pairs: Vec<ChangeCoupledPair> = coupling
+        .into_iter()
+        .map(|((first, second), coupling)| ChangeCoupledPair {
+            imports_each_other: imports(&first, &second) || imports(&second, &first),
+            first,
+            second,
+            coupling,
+        })
+        .collect();
+    pairs.sort_by(|a, b| {
+        b.coupling
+            .total_cmp(&a.coupling)
+            .then_with(|| a.first.cmp(&b.first))
+            .then_with(|| a.second.cmp(&b.second))
+    });
+    pairs.truncate(limit);
+    pairs
+}
+
+/// Estimate distinct responsibilities from the leading verbs of method names.
+///
+/// Synonymous verbs share a group; unrecognized verbs count as their own group.
+pub(crate) fn estimate_responsibility_count(methods: &[String]) -> usize {
+    methods
+        .iter()
+        .filter_map(|name| leading_word(name))
+        .map(|verb| {
+            VERB_GROUPS
+                .iter()
+                .find(|(_, verbs)| verbs.contains(&verb.as_str()))
+                .map(|(group, _)| (*group).to_string())
+                .unwrap_or(verb)
+        })
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// Estimate the number of fields a class declares or uses.
+///
+/// Rust and Go declare struct fields explicitly, so their `field_declaration`
+/// nodes are counted. Other languages create fields in methods, so distinct
+/// `self.x` / `this.x` accesses are counted instead.
+pub(crate) fn estimate_field_count(source: &str, file_path: &Path) -> usize {
+    match file_path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext @ ("rs" | "go")) => count_field_declarations(source, ext),
+        _ => count_receiver_fields(source),
+    }
+}
+
+/// Count the fields named by `field_declaration` nodes; a Go embedded field
+/// has no name and counts once.
+fn count_field_declarations(source: &str, extension: &str) -> usize {
+    let Ok(language) = get_tree_sitter_language(extension) else {
+        return 0;
+    };
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&language).is_err() {
+        return 0;
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return 0;
+    };
+
+    let mut count = 0;
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let mut cursor = node.walk();
+        if node.kind() == "field_declaration" {
+            count += node
+                .children_by_field_name("name", &mut cursor)
+                .count()
+                .max(1);
+            continue;
+        }
+        stack.extend(node.children(&mut cursor));
+    }
+    count
+}
+
+/// Count distinct `self.x` / `this.x` accesses.
+///
+/// Accesses immediately followed by `(` are method calls and are not counted.
+fn count_receiver_fields(source: &str) -> usize {
+    let mut fields = HashSet::new();
+    for receiver in ["self.", "this."] {
+        for (idx, _) in source.match_indices(receiver) {
+            let preceded_by_ident = source[..idx]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_');
+            if preceded_by_ident {
+                continue;
+            }
+
+            let rest = &source[idx + receiver.len()..];
+            let name: String = rest
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            if name.is_empty() {
+                continue;
+            }
+            if rest[name.len()..].trim_start().starts_with('(') {
+                continue;
+            }
+            fields.insert(name);
+        }
+    }
+    fields.len()
+}
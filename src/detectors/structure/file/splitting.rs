@@ -8,8 +8,9 @@ use crate::detectors::structure::config::{
     CohesionGraph, FileSplitPack, SplitEffort, SplitValue, StructureConfig, SuggestedSplit,
 };
 
-use super::cohesion::estimate_clone_factor;
+use super::cohesion::{estimate_clone_factor, lcom4};
 use super::imports::FileDependencyMetrics;
+use super::smells::detect_god_class;
 
 /// Split analyzer for file restructuring recommendations
 pub struct SplitAnalyzer<'a> {
@@ -43,6 +44,24 @@ impl<'a> SplitAnalyzer<'a> {
         reasons
     }
 
+    /// Collect reasons for classes in the file that exhibit the God Class smell
+    pub fn collect_god_class_reasons(&self, cohesion_graph: &CohesionGraph) -> Vec<String> {
+        cohesion_graph
+            .node_weights()
+            .filter_map(|entity| {
+                detect_god_class(entity, self.config).map(|diagnosis| {
+                    format!(
+                        "god class '{}' ({} methods, {} fields, {} responsibilities)",
+                        entity.name,
+                        diagnosis.method_count,
+                        diagnosis.field_count,
+                        diagnosis.responsibility_count
+                    )
+                })
+            })
+            .collect()
+    }
+
     /// Build a FileSplitPack from analysis data
     pub fn build_split_pack(
         &self,
@@ -53,16 +72,23 @@ impl<'a> SplitAnalyzer<'a> {
         communities: Vec<Vec<NodeIndex>>,
         dependency_metrics: &FileDependencyMetrics,
     ) -> Result<Option<FileSplitPack>> {
-        if !self.is_huge_file(loc, size_bytes) {
+        // A God Class warrants a pack on its own so its reason is always reported
+        let god_class_reasons = self.collect_god_class_reasons(cohesion_graph);
+        let splittable = self.is_huge_file(loc, size_bytes)
+            && communities.len() >= self.config.partitioning.min_clusters;
+        if !splittable && god_class_reasons.is_empty() {
             return Ok(None);
         }
 
         let mut reasons = self.collect_size_reasons(loc, size_bytes);
-
-        if communities.len() < self.config.partitioning.min_clusters {
-            return Ok(None);
+        if splittable {
+            reasons.push(format!("{} cohesion communities", communities.len()));
+            let lcom = lcom4(cohesion_graph);
+            if lcom > 1 {
+                reasons.push(format!("lcom4 {} > 1", lcom));
+            }
         }
-        reasons.push(format!("{} cohesion communities", communities.len()));
+        reasons.extend(god_class_reasons);
 
         let suggested_splits =
             self.generate_split_suggestions(file_path, &communities, cohesion_graph)?;
@@ -159,7 +185,10 @@ impl<'a> SplitAnalyzer<'a> {
             suffix.to_string()
         };
 
-        format!("{}{}.{}", base_name, final_suffix, extension)
+        let stem = shared_entity_stem(entities)
+            .unwrap_or_else(|| strip_suffix_stem(base_name).to_string());
+
+        format!("{}{}.{}", stem, final_suffix, extension)
     }
 
     /// Calculate value score for file splitting
@@ -202,34 +231,146 @@ impl<'a> SplitAnalyzer<'a> {
     }
 }
 
-/// Check if a lowercased entity name matches any pattern in the list
+/// Suffixes produced by [`analyze_entity_names`] that a split name may already carry
+const SPLIT_SUFFIX_STEMS: &[&str] = &["_io", "_api", "_util", "_core", "_serialization"];
+
+const SERIALIZATION_PATTERNS: &[&str] = &[
+    "serialize",
+    "serializer",
+    "serialization",
+    "deserialize",
+    "validate",
+    "validator",
+    "validation",
+    "marshal",
+    "unmarshal",
+    "encode",
+    "encoder",
+    "decode",
+    "decoder",
+    "schema",
+];
+const IO_PATTERNS: &[&str] = &[
+    "read", "reader", "write", "writer", "load", "loader", "save", "file", "io",
+];
+const API_PATTERNS: &[&str] = &[
+    "api",
+    "endpoint",
+    "route",
+    "router",
+    "handler",
+    "controller",
+];
+const UTIL_PATTERNS: &[&str] = &["util", "utility", "helper", "tool"];
+
+/// Check if any whole word of an entity name is a pattern in the list
+///
+/// Words are split on `_` and camelCase boundaries, so `file` matches
+/// `read_file`, `FileReader` and the plural `load_files` but not `filter` or
+/// `profile`.
 fn matches_patterns(name: &str, patterns: &[&str]) -> bool {
-    patterns.iter().any(|p| name.contains(p))
+    split_name_tokens(name)
+        .iter()
+        .any(|token| patterns.iter().any(|pattern| is_word_form(token, pattern)))
+}
+
+/// Whether `token` is `pattern` or its plural with a trailing `s` or `es`
+fn is_word_form(token: &str, pattern: &str) -> bool {
+    token
+        .strip_prefix(pattern)
+        .is_some_and(|rest| matches!(rest, "" | "s" | "es"))
+}
+
+/// Lowercased words of a snake_case or camelCase identifier
+///
+/// Acronyms stay whole, so `HTTPServer` splits into `http` and `server`.
+fn split_name_tokens(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if !ch.is_alphanumeric() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if ch.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if !prev.is_uppercase() || next_is_lower {
+                tokens.push(std::mem::take(&mut current));
+            }
+        }
+        current.extend(ch.to_lowercase());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Remove a trailing split suffix so `user_io` does not become `user_io_io`
+fn strip_suffix_stem(base_name: &str) -> &str {
+    SPLIT_SUFFIX_STEMS
+        .iter()
+        .find_map(|stem| base_name.strip_suffix(stem).filter(|rest| !rest.is_empty()))
+        .unwrap_or(base_name)
+}
+
+/// First word of a snake_case or CamelCase identifier, lowercased
+pub(super) fn leading_word(name: &str) -> Option<String> {
+    split_name_tokens(name).into_iter().next()
+}
+
+/// Domain word shared by every entity, e.g. `user` for `UserReader` and `UserWriter`
+///
+/// Words that are themselves role keywords (`read_file`, `read_data`) are not
+/// treated as a domain.
+fn shared_entity_stem(entities: &[String]) -> Option<String> {
+    if entities.len() < 2 {
+        return None;
+    }
+    let first = leading_word(&entities[0])?;
+    if !entities[1..]
+        .iter()
+        .all(|entity| leading_word(entity).as_deref() == Some(first.as_str()))
+    {
+        return None;
+    }
+    let is_role_word = [
+        SERIALIZATION_PATTERNS,
+        IO_PATTERNS,
+        API_PATTERNS,
+        UTIL_PATTERNS,
+    ]
+    .iter()
+    .any(|patterns| matches_patterns(&first, patterns));
+    (!is_role_word).then_some(first)
 }
 
 /// Analyze entity names to suggest appropriate suffixes
 pub fn analyze_entity_names(entities: &[String]) -> String {
-    const IO_PATTERNS: &[&str] = &["read", "write", "load", "save", "file", "io"];
-    const API_PATTERNS: &[&str] = &["api", "endpoint", "route", "handler", "controller"];
-    const UTIL_PATTERNS: &[&str] = &["util", "helper", "tool"];
-
-    let (io_count, api_count, util_count, core_count) =
-        entities
-            .iter()
-            .fold((0, 0, 0, 0), |(io, api, util, core), entity| {
-                let lower = entity.to_lowercase();
-                if matches_patterns(&lower, IO_PATTERNS) {
-                    (io + 1, api, util, core)
-                } else if matches_patterns(&lower, API_PATTERNS) {
-                    (io, api + 1, util, core)
-                } else if matches_patterns(&lower, UTIL_PATTERNS) {
-                    (io, api, util + 1, core)
-                } else {
-                    (io, api, util, core + 1)
-                }
-            });
+    let (serialization_count, io_count, api_count, util_count, core_count) = entities.iter().fold(
+        (0, 0, 0, 0, 0),
+        |(serialization, io, api, util, core), entity| {
+            if matches_patterns(entity, SERIALIZATION_PATTERNS) {
+                (serialization + 1, io, api, util, core)
+            } else if matches_patterns(entity, IO_PATTERNS) {
+                (serialization, io + 1, api, util, core)
+            } else if matches_patterns(entity, API_PATTERNS) {
+                (serialization, io, api + 1, util, core)
+            } else if matches_patterns(entity, UTIL_PATTERNS) {
+                (serialization, io, api, util + 1, core)
+            } else {
+                (serialization, io, api, util, core + 1)
+            }
+        },
+    );
 
     let counts = [
+        (serialization_count, "_serialization"),
         (io_count, "_io"),
         (api_count, "_api"),
         (util_count, "_util"),
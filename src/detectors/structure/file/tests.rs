@@ -1,13 +1,15 @@
 use super::*;
 use crate::detectors::structure::config::{
-    CohesionEdge, EntityHealthConfig, FsDirectoryConfig, FsFileConfig, ImportStatement,
-    PartitioningConfig, StructureConfig, StructureToggles,
+    CohesionEdge, CommunityAlgorithm, EntityHealthConfig, FsDirectoryConfig, FsFileConfig,
+    ImportStatement, PartitioningConfig, StructureConfig, StructureToggles, WorkspaceConfig,
+    WorkspaceResolver,
 };
 use crate::lang::common::{EntityKind, ParsedEntity, SourceLocation};
 use crate::lang::registry::adapter_for_language;
+use petgraph::graph::NodeIndex;
 use petgraph::Graph;
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::env;
 use std::fs;
 use tempfile::TempDir;
@@ -36,6 +38,10 @@ fn create_test_config() -> StructureConfig {
             min_entities_per_split: 2,
             optimal_ast_nodes: 2000,
             ast_nodes_95th_percentile: 6000,
+            max_class_methods: 20,
+            max_class_fields: 15,
+            shotgun_surgery_threshold: 10,
+            co_change_window: 1000,
         },
         partitioning: PartitioningConfig {
             max_clusters: 8,
@@ -49,7 +55,11 @@ fn create_test_config() -> StructureConfig {
             ],
         },
         entity_health: EntityHealthConfig::default(),
+        use_bimodal_function_scoring: false,
         exclude_patterns: Vec::new(),
+        community_algorithm: CommunityAlgorithm::default(),
+        workspace: None,
+        max_method_chain_length: 4,
     }
 }
 
@@ -175,7 +185,7 @@ def goodbye():
         metrics.entity_health.is_some(),
         "Entity health should be calculated for Python file"
     );
-    let entity_health = metrics.entity_health.unwrap();
+    let entity_health = metrics.entity_health.unwrap().aggregate;
     assert!(
         entity_health.entity_count >= 2,
         "Should find at least 2 entities"
@@ -207,10 +217,24 @@ def medium_function():
     let config = create_test_config();
     let analyzer = FileAnalyzer::new(config);
     let content = fs::read_to_string(&file_path).unwrap();
-    let health = analyzer
+    let report = analyzer
         .calculate_entity_health(&file_path, &content)
         .unwrap();
 
+    // Per-entity scores are reported in source order
+    let names: Vec<&str> = report
+        .per_entity
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(names, vec!["small_function", "medium_function"]);
+    assert!(report
+        .per_entity
+        .iter()
+        .all(|(_, score)| score.health >= report.aggregate.min_health));
+
+    let health = report.aggregate;
+
     // Should find entities
     assert!(health.entity_count > 0, "Should find entities");
     assert!(health.total_ast_nodes > 0, "Should have AST nodes");
@@ -294,7 +318,7 @@ fn test_find_cohesion_communities_filters_small_clusters() {
     let analyzer = FileAnalyzer::new(config);
 
     let mut graph = Graph::new_undirected();
-    let mut symbols_a = HashSet::new();
+    let mut symbols_a = BTreeSet::new();
     symbols_a.insert("value".to_string());
     symbols_a.insert("count".to_string());
     let node_a = graph.add_node(EntityNode {
@@ -303,9 +327,11 @@ fn test_find_cohesion_communities_filters_small_clusters() {
         loc: 10,
         ast_nodes: 100,
         symbols: symbols_a,
+        methods: Vec::new(),
+        field_count: 0,
     });
 
-    let mut symbols_b = HashSet::new();
+    let mut symbols_b = BTreeSet::new();
     symbols_b.insert("value".to_string());
     symbols_b.insert("result".to_string());
     let node_b = graph.add_node(EntityNode {
@@ -314,9 +340,11 @@ fn test_find_cohesion_communities_filters_small_clusters() {
         loc: 12,
         ast_nodes: 120,
         symbols: symbols_b,
+        methods: Vec::new(),
+        field_count: 0,
     });
 
-    let mut symbols_c = HashSet::new();
+    let mut symbols_c = BTreeSet::new();
     symbols_c.insert("temp".to_string());
     let node_c = graph.add_node(EntityNode {
         name: "gamma".into(),
@@ -324,6 +352,8 @@ fn test_find_cohesion_communities_filters_small_clusters() {
         loc: 8,
         ast_nodes: 80,
         symbols: symbols_c,
+        methods: Vec::new(),
+        field_count: 0,
     });
 
     graph.add_edge(
@@ -350,6 +380,134 @@ fn test_find_cohesion_communities_filters_small_clusters() {
     assert!(communities[0].contains(&node_b));
 }
 
+fn cohesion_test_node(name: &str) -> EntityNode {
+    EntityNode {
+        name: name.into(),
+        entity_type: "function".into(),
+        loc: 10,
+        ast_nodes: 100,
+        symbols: BTreeSet::new(),
+        methods: Vec::new(),
+        field_count: 0,
+    }
+}
+
+fn two_cluster_graph() -> (CohesionGraph, Vec<NodeIndex>) {
+    let mut graph = Graph::new_undirected();
+    let nodes: Vec<_> = ["a", "b", "c", "d", "e", "f"]
+        .iter()
+        .map(|name| graph.add_node(cohesion_test_node(name)))
+        .collect();
+
+    let edges = [
+        (0, 1, 0.9),
+        (1, 2, 0.9),
+        (0, 2, 0.9),
+        (3, 4, 0.8),
+        (4, 5, 0.8),
+        (3, 5, 0.8),
+        (2, 3, 0.95),
+    ];
+    for (a, b, similarity) in edges {
+        graph.add_edge(
+            nodes[a],
+            nodes[b],
+            CohesionEdge {
+                similarity,
+                shared_symbols: 2,
+            },
+        );
+    }
+
+    (graph, nodes)
+}
+
+#[test]
+fn test_find_cohesion_communities_louvain_splits_bridged_clusters() {
+    let config = create_test_config();
+    assert_eq!(config.community_algorithm, CommunityAlgorithm::Louvain);
+    let analyzer = FileAnalyzer::new(config);
+    let (graph, nodes) = two_cluster_graph();
+
+    let communities = analyzer.find_cohesion_communities(&graph).unwrap();
+
+    assert_eq!(communities.len(), 2);
+    let mut sorted: Vec<Vec<NodeIndex>> = communities
+        .into_iter()
+        .map(|mut community| {
+            community.sort();
+            community
+        })
+        .collect();
+    sorted.sort();
+    assert_eq!(sorted[0], nodes[..3].to_vec());
+    assert_eq!(sorted[1], nodes[3..].to_vec());
+}
+
+#[test]
+fn test_lcom4_counts_connected_components() {
+    let (mut graph, _) = two_cluster_graph();
+    assert_eq!(lcom4(&graph), 1);
+
+    let bridge = graph
+        .find_edge(NodeIndex::new(2), NodeIndex::new(3))
+        .unwrap();
+    graph.remove_edge(bridge);
+    assert_eq!(lcom4(&graph), 2);
+
+    graph.add_node(cohesion_test_node("isolated"));
+    assert_eq!(lcom4(&graph), 3);
+    assert_eq!(lcom4(&Graph::new_undirected()), 0);
+}
+
+#[test]
+fn test_build_split_pack_reports_lcom4_when_disconnected() {
+    let config = create_test_config();
+    let (mut graph, nodes) = two_cluster_graph();
+    let bridge = graph.find_edge(nodes[2], nodes[3]).unwrap();
+    graph.remove_edge(bridge);
+    let communities = vec![nodes[..3].to_vec(), nodes[3..].to_vec()];
+
+    let pack = splitting::SplitAnalyzer::new(&config)
+        .build_split_pack(
+            Path::new("src/service.rs"),
+            200,
+            1_000,
+            &graph,
+            communities,
+            &FileDependencyMetrics::default(),
+        )
+        .unwrap()
+        .expect("huge file with two communities should produce a pack");
+
+    assert!(pack.reasons.contains(&"lcom4 2 > 1".to_string()));
+}
+
+#[test]
+fn test_find_cohesion_communities_greedy_follows_strongest_edge() {
+    let mut config = create_test_config();
+    config.community_algorithm = CommunityAlgorithm::Greedy;
+    let analyzer = FileAnalyzer::new(config);
+    let (graph, nodes) = two_cluster_graph();
+
+    let communities = analyzer.find_cohesion_communities(&graph).unwrap();
+
+    // The bridge is the strongest edge, so greedy merging seeds one community
+    // across both clusters before it sees the intra-cluster edges.
+    assert!(communities
+        .iter()
+        .any(|community| community.contains(&nodes[2]) && community.contains(&nodes[3])));
+}
+
+#[test]
+fn test_structure_config_defaults_to_louvain_when_unset() {
+    let mut value = serde_json::to_value(StructureConfig::default()).unwrap();
+    value.as_object_mut().unwrap().remove("community_algorithm");
+
+    let config: StructureConfig = serde_json::from_value(value).unwrap();
+    assert_eq!(config.community_algorithm, CommunityAlgorithm::Louvain);
+}
+
 #[test]
 fn test_estimate_clone_factor_counts_heavy_edges() {
     let analyzer = FileAnalyzer::new(create_test_config());
@@ -359,21 +517,27 @@ fn test_estimate_clone_factor_counts_heavy_edges() {
         entity_type: "fn".into(),
         loc: 10,
         ast_nodes: 100,
-        symbols: HashSet::new(),
+        symbols: BTreeSet::new(),
+        methods: Vec::new(),
+        field_count: 0,
     });
     let n2 = graph.add_node(EntityNode {
         name: "b".into(),
         entity_type: "fn".into(),
         loc: 12,
         ast_nodes: 120,
-        symbols: HashSet::new(),
+        symbols: BTreeSet::new(),
+        methods: Vec::new(),
+        field_count: 0,
     });
     let n3 = graph.add_node(EntityNode {
         name: "c".into(),
         entity_type: "fn".into(),
         loc: 6,
         ast_nodes: 60,
-        symbols: HashSet::new(),
+        symbols: BTreeSet::new(),
+        methods: Vec::new(),
+        field_count: 0,
     });
 
     graph.add_edge(
@@ -452,8 +616,8 @@ fn test_calculate_jaccard_similarity_empty_sets() {
     let config = create_test_config();
     let analyzer = FileAnalyzer::new(config);
 
-    let set1 = HashSet::new();
-    let set2 = HashSet::new();
+    let set1 = BTreeSet::new();
+    let set2 = BTreeSet::new();
     let similarity = analyzer.calculate_jaccard_similarity(&set1, &set2);
 
     assert_eq!(similarity, 1.0);
@@ -464,11 +628,11 @@ fn test_calculate_jaccard_similarity_identical_sets() {
     let config = create_test_config();
     let analyzer = FileAnalyzer::new(config);
 
-    let mut set1 = HashSet::new();
+    let mut set1 = BTreeSet::new();
     set1.insert("a".to_string());
     set1.insert("b".to_string());
 
-    let mut set2 = HashSet::new();
+    let mut set2 = BTreeSet::new();
     set2.insert("a".to_string());
     set2.insert("b".to_string());
 
@@ -482,11 +646,11 @@ fn test_calculate_jaccard_similarity_no_overlap() {
     let config = create_test_config();
     let analyzer = FileAnalyzer::new(config);
 
-    let mut set1 = HashSet::new();
+    let mut set1 = BTreeSet::new();
     set1.insert("a".to_string());
     set1.insert("b".to_string());
 
-    let mut set2 = HashSet::new();
+    let mut set2 = BTreeSet::new();
     set2.insert("c".to_string());
     set2.insert("d".to_string());
 
@@ -500,11 +664,11 @@ fn test_calculate_jaccard_similarity_partial_overlap() {
     let config = create_test_config();
     let analyzer = FileAnalyzer::new(config);
 
-    let mut set1 = HashSet::new();
+    let mut set1 = BTreeSet::new();
     set1.insert("a".to_string());
     set1.insert("b".to_string());
 
-    let mut set2 = HashSet::new();
+    let mut set2 = BTreeSet::new();
     set2.insert("a".to_string());
     set2.insert("c".to_string());
 
@@ -559,6 +723,39 @@ fn test_analyze_entity_names_util_focused() {
     assert!(suffix == "_util" || suffix == "_helper" || suffix == "_tool" || suffix == "_io");
 }
 
+#[test]
+fn test_analyze_entity_names_splits_camel_case() {
+    let config = create_test_config();
+    let analyzer = FileAnalyzer::new(config);
+
+    let io_entities = vec!["readUserFile".to_string(), "SaveSettings".to_string()];
+    assert_eq!(analyzer.analyze_entity_names(&io_entities), "_io");
+
+    let api_entities = vec![
+        "HttpApiController".to_string(),
+        "HTTPRouteTable".to_string(),
+    ];
+    assert_eq!(analyzer.analyze_entity_names(&api_entities), "_api");
+
+    // Plurals with a trailing `s` or `es` count as the same word
+    let plural_entities = vec![
+        "loadFiles".to_string(),
+        "register_routes".to_string(),
+        "string_utils".to_string(),
+        "BuildTools".to_string(),
+    ];
+    assert_eq!(analyzer.analyze_entity_names(&plural_entities), "_util");
+
+    // Patterns match whole words, not substrings or prefixes such as `file` in `filter`
+    let core_entities = vec![
+        "compose_function".to_string(),
+        "rotation".to_string(),
+        "filterRows".to_string(),
+        "apiary_stats".to_string(),
+    ];
+    assert_eq!(analyzer.analyze_entity_names(&core_entities), "_core");
+}
+
 #[test]
 fn test_analyze_entity_names_core_fallback() {
     let config = create_test_config();
@@ -587,6 +784,42 @@ fn test_generate_split_name() {
     assert_eq!(name, "test_io.py"); // Should detect io pattern
 }
 
+#[test]
+fn test_generate_split_name_strips_existing_suffix_stem() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("user_io.py");
+    let analyzer = FileAnalyzer::new(create_test_config());
+
+    let entities = vec!["read_file".to_string(), "write_data".to_string()];
+    let name = analyzer.generate_split_name("user_io", "_io", &entities, &file_path);
+
+    assert_eq!(name, "user_io.py");
+}
+
+#[test]
+fn test_generate_split_name_uses_shared_entity_domain() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("user_management.py");
+    let analyzer = FileAnalyzer::new(create_test_config());
+
+    let io_entities = vec!["UserReader".to_string(), "UserWriter".to_string()];
+    assert_eq!(
+        analyzer.generate_split_name("user_management", "_core", &io_entities, &file_path),
+        "user_io.py"
+    );
+
+    let serialization_entities = vec!["UserValidator".to_string(), "UserSerializer".to_string()];
+    assert_eq!(
+        analyzer.generate_split_name(
+            "user_management",
+            "_core",
+            &serialization_entities,
+            &file_path
+        ),
+        "user_serialization.py"
+    );
+}
+
 #[test]
 fn test_calculate_split_value() {
     let temp_dir = TempDir::new().unwrap();
@@ -610,7 +843,7 @@ fn test_calculate_split_value_includes_cycle_and_clone_factors() {
     let analyzer = FileAnalyzer::new(config);
 
     let mut graph = Graph::new_undirected();
-    let mut symbols_a = HashSet::new();
+    let mut symbols_a = BTreeSet::new();
     symbols_a.insert("shared".to_string());
     symbols_a.insert("extra".to_string());
     let node_a = graph.add_node(EntityNode {
@@ -619,9 +852,11 @@ fn test_calculate_split_value_includes_cycle_and_clone_factors() {
         loc: 20,
         ast_nodes: 200,
         symbols: symbols_a,
+        methods: Vec::new(),
+        field_count: 0,
     });
 
-    let mut symbols_b = HashSet::new();
+    let mut symbols_b = BTreeSet::new();
     symbols_b.insert("shared".to_string());
     symbols_b.insert("another".to_string());
     let node_b = graph.add_node(EntityNode {
@@ -630,6 +865,8 @@ fn test_calculate_split_value_includes_cycle_and_clone_factors() {
         loc: 18,
         ast_nodes: 180,
         symbols: symbols_b,
+        methods: Vec::new(),
+        field_count: 0,
     });
 
     graph.add_edge(
@@ -664,12 +901,12 @@ fn test_estimate_clone_factor_requires_strong_overlap() {
     let analyzer = FileAnalyzer::new(config);
 
     let mut graph = Graph::new_undirected();
-    let mut symbols_a = HashSet::new();
+    let mut symbols_a = BTreeSet::new();
     symbols_a.insert("alpha".to_string());
     symbols_a.insert("beta".to_string());
     symbols_a.insert("gamma".to_string());
 
-    let mut symbols_b = HashSet::new();
+    let mut symbols_b = BTreeSet::new();
     symbols_b.insert("alpha".to_string());
     symbols_b.insert("beta".to_string());
     symbols_b.insert("delta".to_string());
@@ -680,6 +917,8 @@ fn test_estimate_clone_factor_requires_strong_overlap() {
         loc: 15,
         ast_nodes: 150,
         symbols: symbols_a,
+        methods: Vec::new(),
+        field_count: 0,
     });
     let node_b = graph.add_node(EntityNode {
         name: "second".into(),
@@ -687,6 +926,8 @@ fn test_estimate_clone_factor_requires_strong_overlap() {
         loc: 12,
         ast_nodes: 120,
         symbols: symbols_b,
+        methods: Vec::new(),
+        field_count: 0,
     });
 
     graph.add_edge(
@@ -892,6 +1133,26 @@ def func2():
     // node_count() is unsigned, always >= 0
 }
 
+#[test]
+fn test_file_lcom4_builds_the_cohesion_graph_once_per_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("mixed.py");
+    fs::write(&file_path, "# Just a comment").unwrap();
+
+    let analyzer = FileAnalyzer::new(create_test_config());
+    assert_eq!(analyzer.file_lcom4(&file_path).unwrap(), 0);
+
+    // A cached value is served without rebuilding the graph from the new content
+    fs::write(&file_path, "def first():\n    return 1\n").unwrap();
+    assert_eq!(analyzer.file_lcom4(&file_path).unwrap(), 0);
+    assert_eq!(
+        FileAnalyzer::new(create_test_config())
+            .file_lcom4(&file_path)
+            .unwrap(),
+        1
+    );
+}
+
 #[test]
 fn test_find_cohesion_communities_empty_graph() {
     let config = create_test_config();
@@ -914,8 +1175,9 @@ fn test_generate_split_suggestions_empty_communities() {
     let analyzer = FileAnalyzer::new(config);
 
     let communities = Vec::new();
+    let graph: CohesionGraph = Graph::new_undirected();
     let suggestions = analyzer
-        .generate_split_suggestions(&file_path, &communities)
+        .generate_split_suggestions(&file_path, &communities, &graph)
         .unwrap();
 
     // Should generate default splits when no communities found
@@ -923,6 +1185,46 @@ fn test_generate_split_suggestions_empty_communities() {
     assert!(suggestions.iter().all(|s| s.name.contains("test")));
 }
 
+#[test]
+fn test_generate_split_suggestions_prebuilt_graph_matches_fresh_graph() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("mixed.py");
+    let content = r#"
+def load_config():
+    path = settings_path
+    return read(path)
+
+def save_config():
+    path = settings_path
+    write(path)
+
+def render_page():
+    html = template
+    return html
+"#;
+    fs::write(&file_path, content).unwrap();
+
+    let analyzer = FileAnalyzer::new(create_test_config());
+    let prebuilt = analyzer
+        .build_entity_cohesion_graph_from_source(&file_path, content)
+        .unwrap();
+    let fresh = analyzer.build_entity_cohesion_graph(&file_path).unwrap();
+    let communities = analyzer.find_cohesion_communities(&prebuilt).unwrap();
+
+    let from_prebuilt = analyzer
+        .generate_split_suggestions(&file_path, &communities, &prebuilt)
+        .unwrap();
+    let from_fresh = analyzer
+        .generate_split_suggestions(&file_path, &communities, &fresh)
+        .unwrap();
+
+    assert!(!from_prebuilt.is_empty());
+    assert_eq!(
+        serde_json::to_value(&from_prebuilt).unwrap(),
+        serde_json::to_value(&from_fresh).unwrap()
+    );
+}
+
 #[tokio::test]
 async fn test_discover_large_files() {
     let temp_dir = TempDir::new().unwrap();
@@ -1109,6 +1411,120 @@ def run():
     );
 }
 
+/// Two npm packages plus a root-level `lib/util.js` that shadows package `a`'s copy.
+fn write_npm_workspace(root: &Path) -> PathBuf {
+    for dir in ["lib", "packages/a/lib", "packages/a/src", "packages/b"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    fs::write(root.join("packages/a/package.json"), "{\"name\": \"a\"}").unwrap();
+    fs::write(root.join("packages/b/package.json"), "{\"name\": \"b\"}").unwrap();
+    fs::write(root.join("lib/util.js"), "export function util() {}\n").unwrap();
+    fs::write(
+        root.join("packages/a/lib/util.js"),
+        "export function util() {}\n",
+    )
+    .unwrap();
+    fs::write(root.join("packages/b/index.js"), "export function b() {}\n").unwrap();
+
+    let entry = root.join("packages/a/src/index.js");
+    fs::write(
+        &entry,
+        "import { util } from \"lib/util\";\nimport { b } from \"../../b/index\";\n\nexport function run() {\n  return util() + b();\n}\n",
+    )
+    .unwrap();
+    entry
+}
+
+#[test]
+fn test_collect_dependency_metrics_respects_workspace_packages() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let entry = write_npm_workspace(root);
+    let graph: CohesionGraph = Graph::new_undirected();
+
+    let flat = FileAnalyzer::new(create_test_config());
+    let flat_metrics = flat
+        .collect_dependency_metrics(&entry, Some(root), &graph)
+        .unwrap();
+    let root_util = flat.canonicalize_path(&root.join("lib/util.js"));
+    assert!(flat_metrics.outgoing_dependencies.contains(&root_util));
+    assert!(flat_metrics.dependencies.iter().all(|edge| !edge.external));
+
+    let mut config = create_test_config();
+    config.workspace = Some(WorkspaceConfig::default());
+    let analyzer = FileAnalyzer::new(config);
+    let metrics = analyzer
+        .collect_dependency_metrics(&entry, Some(root), &graph)
+        .unwrap();
+
+    let package_util = analyzer.canonicalize_path(&root.join("packages/a/lib/util.js"));
+    assert!(metrics.outgoing_dependencies.contains(&package_util));
+    assert!(!metrics.outgoing_dependencies.contains(&root_util));
+
+    let external: Vec<bool> = metrics
+        .dependencies
+        .iter()
+        .map(|edge| edge.external)
+        .collect();
+    assert_eq!(metrics.dependencies.len(), 2);
+    assert_eq!(
+        metrics.dependencies[1].target,
+        analyzer.canonicalize_path(&root.join("packages/a/src/../../b/index.js"))
+    );
+    assert_eq!(external, vec![false, true]);
+}
+
+#[test]
+fn test_package_boundaries_detection_and_manual_roots() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("crates/core/src")).unwrap();
+    fs::create_dir_all(root.join("apps/web")).unwrap();
+    fs::create_dir_all(root.join("tools")).unwrap();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("crates/core/Cargo.toml"),
+        "[package]\nname = \"core\"\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("pnpm-workspace.yaml"),
+        "packages:\n  - 'apps/*'\n",
+    )
+    .unwrap();
+
+    let auto = PackageBoundaries::resolve(root, &WorkspaceConfig::default());
+    assert_eq!(
+        auto.roots(),
+        &[root.join("apps/web"), root.join("crates/core")]
+    );
+    assert_eq!(
+        auto.package_of(&root.join("crates/core/src/lib.rs")),
+        Some(root.join("crates/core").as_path())
+    );
+    assert!(auto.crosses_boundary(
+        &root.join("crates/core/src/lib.rs"),
+        &root.join("apps/web/index.ts")
+    ));
+
+    let manual = PackageBoundaries::resolve(
+        root,
+        &WorkspaceConfig {
+            package_roots: vec![PathBuf::from("tools")],
+            resolver: WorkspaceResolver::Manual,
+        },
+    );
+    assert_eq!(manual.roots(), &[root.join("tools")]);
+    assert_eq!(
+        manual.package_of(&root.join("crates/core/src/lib.rs")),
+        None
+    );
+}
+
 #[test]
 fn test_collect_dependency_metrics_without_project_root() {
     let temp_dir = TempDir::new().unwrap();
@@ -1314,6 +1730,7 @@ fn build_entity(name: &str, kind: EntityKind, start_line: usize) -> ParsedEntity
             end_column: 20,
         },
         metadata: std::collections::HashMap::new(),
+        is_test: false,
     }
 }
 
@@ -1321,17 +1738,28 @@ fn build_entity(name: &str, kind: EntityKind, start_line: usize) -> ParsedEntity
 fn test_is_entity_exported_handles_language_visibility() {
     let analyzer = FileAnalyzer::new(create_test_config());
 
-    // Rust visibility comes from metadata
+    // Rust visibility comes from metadata; only bare `pub` leaves the crate
     let mut rust_entity = build_entity("do_stuff", EntityKind::Function, 3);
-    rust_entity.metadata.insert(
-        "visibility".to_string(),
-        Value::String("pub(crate)".to_string()),
-    );
-    assert!(analyzer.is_entity_exported(
-        &rust_entity,
-        Path::new("lib.rs"),
-        "pub(crate) fn do_stuff() {}"
-    ));
+    rust_entity
+        .metadata
+        .insert("visibility".to_string(), Value::String("pub".to_string()));
+    assert!(analyzer.is_entity_exported(&rust_entity, Path::new("lib.rs"), "pub fn do_stuff() {}"));
+
+    for restricted in ["pub(crate)", "pub(super)", "pub(in crate::detectors)"] {
+        let mut internal = build_entity("helper", EntityKind::Function, 3);
+        internal.metadata.insert(
+            "visibility".to_string(),
+            Value::String(restricted.to_string()),
+        );
+        assert!(
+            !analyzer.is_entity_exported(
+                &internal,
+                Path::new("lib.rs"),
+                &format!("{restricted} fn helper() {{}}")
+            ),
+            "{restricted} should not count as exported"
+        );
+    }
 
     let mut private_rust = build_entity("internal", EntityKind::Function, 5);
     private_rust
@@ -1408,3 +1836,428 @@ fn test_is_entity_exported_handles_language_visibility() {
     let top_level_unknown = build_entity("Top", EntityKind::Class, 1);
     assert!(analyzer.is_entity_exported(&top_level_unknown, Path::new("README.md"), "irrelevant"));
 }
+
+#[test]
+fn test_extract_entities_populates_class_methods_and_fields() {
+    let analyzer = FileAnalyzer::new(create_test_config());
+    let content = r#"
+class Account:
+    def __init__(self, owner):
+        self.owner = owner
+        self.balance = 0
+
+    def deposit(self, amount):
+        self.balance += amount
+        self.notify_owner()
+
+    def get_balance(self):
+        return self.balance
+"#;
+
+    let entities = analyzer
+        .extract_entities_with_treesitter(Path::new("account.py"), content)
+        .unwrap();
+    let class = entities
+        .iter()
+        .find(|entity| entity.name == "Account")
+        .expect("class entity should be extracted");
+
+    assert_eq!(class.methods.len(), 3);
+    assert!(class.methods.contains(&"deposit".to_string()));
+    assert_eq!(
+        class.field_count, 2,
+        "method calls should not count as fields"
+    );
+}
+
+#[test]
+fn test_extract_entities_counts_declared_struct_fields() {
+    let analyzer = FileAnalyzer::new(create_test_config());
+
+    let rust = r#"
+pub struct Account {
+    owner: String,
+    balance: i64,
+    history: Vec<i64>,
+}
+
+impl Account {
+    fn deposit(&mut self, amount: i64) {
+        self.balance += amount;
+    }
+}
+"#;
+    let entities = analyzer
+        .extract_entities_with_treesitter(Path::new("account.rs"), rust)
+        .unwrap();
+    let account = entities
+        .iter()
+        .find(|entity| entity.name == "Account" && entity.entity_type == "struct")
+        .expect("struct entity should be extracted");
+    assert_eq!(account.field_count, 3);
+
+    let go = r#"
+package bank
+
+type Account struct {
+    sync.Mutex
+    Owner   string
+    X, Y    int
+}
+"#;
+    let entities = analyzer
+        .extract_entities_with_treesitter(Path::new("account.go"), go)
+        .unwrap();
+    let account = entities
+        .iter()
+        .find(|entity| entity.name == "Account")
+        .expect("struct entity should be extracted");
+    assert_eq!(account.field_count, 4, "embedded field counts once");
+}
+
+#[test]
+fn test_extract_entities_cache_hits_until_content_changes() {
+    let file_path = Path::new("src/shapes.py");
+    let original = "def area():\n    return 1\n";
+
+    let analyzer = FileAnalyzer::new(create_test_config());
+    let first = analyzer
+        .extract_entities_with_treesitter(file_path, original)
+        .unwrap();
+    assert_eq!(first.len(), 1);
+
+    // Rename the cached entity; a hit must return the planted name unparsed
+    for entities in analyzer.entity_cache.write().unwrap().values_mut() {
+        entities[0].name = "planted".to_string();
+    }
+    let cached = analyzer
+        .extract_entities_with_treesitter(file_path, original)
+        .unwrap();
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].name, "planted");
+
+    // Same path with new content re-extracts and replaces the stale entry
+    let updated = "def area():\n    return 1\n\ndef perimeter():\n    return 2\n";
+    let refreshed = analyzer
+        .extract_entities_with_treesitter(file_path, updated)
+        .unwrap();
+    assert_eq!(refreshed.len(), 2);
+    assert!(refreshed.iter().all(|entity| entity.name != "planted"));
+    assert_eq!(analyzer.entity_cache.read().unwrap().len(), 1);
+}
+
+#[test]
+fn test_detect_god_class_flags_large_classes() {
+    let config = create_test_config();
+    let verbs = ["get", "set", "render", "parse", "send"];
+    let methods: Vec<String> = (0..25)
+        .map(|idx| format!("{}_item_{idx}", verbs[idx % verbs.len()]))
+        .collect();
+    let god_class = EntityNode {
+        name: "Manager".into(),
+        entity_type: "class".into(),
+        loc: 900,
+        ast_nodes: 9000,
+        symbols: BTreeSet::new(),
+        methods,
+        field_count: 4,
+    };
+
+    let diagnosis = detect_god_class(&god_class, &config).expect("god class detected");
+    assert_eq!(diagnosis.method_count, 25);
+    assert_eq!(diagnosis.field_count, 4);
+    assert_eq!(diagnosis.responsibility_count, 5);
+
+    let small_class = EntityNode {
+        methods: vec!["getValue".into(), "setValue".into()],
+        field_count: 1,
+        ..god_class.clone()
+    };
+    assert!(detect_god_class(&small_class, &config).is_none());
+
+    let field_heavy = EntityNode {
+        methods: Vec::new(),
+        field_count: 40,
+        ..god_class.clone()
+    };
+    assert!(detect_god_class(&field_heavy, &config).is_some());
+
+    let function = EntityNode {
+        entity_type: "function".into(),
+        ..god_class.clone()
+    };
+    assert!(detect_god_class(&function, &config).is_none());
+
+    let mut graph = Graph::new_undirected();
+    graph.add_node(god_class);
+    let reasons = splitting::SplitAnalyzer::new(&config).collect_god_class_reasons(&graph);
+    assert_eq!(
+        reasons,
+        vec!["god class 'Manager' (25 methods, 4 fields, 5 responsibilities)".to_string()]
+    );
+
+    let pack = splitting::SplitAnalyzer::new(&config)
+        .build_split_pack(
+            Path::new("src/manager.py"),
+            10,
+            100,
+            &graph,
+            Vec::new(),
+            &FileDependencyMetrics::default(),
+        )
+        .unwrap()
+        .expect("god class should produce a pack even in a small file");
+    assert_eq!(pack.reasons, reasons);
+}
+
+#[test]
+fn test_detect_god_class_counts_rust_impl_methods() {
+    let analyzer = FileAnalyzer::new(create_test_config());
+    let methods: String = (0..21)
+        .map(|idx| format!("    pub fn get_{idx}(&self) -> u32 {{ self.value + {idx} }}\n"))
+        .collect();
+    let source = format!(
+        "pub struct Registry {{\n    value: u32,\n}}\n\nimpl<T> From<T> for Wrapper<T> {{\n    fn from(value: T) -> Self {{ Wrapper(value) }}\n}}\n\nimpl Registry {{\n{methods}}}\n"
+    );
+
+    let entities = analyzer
+        .extract_entities_with_treesitter(Path::new("registry.rs"), &source)
+        .unwrap();
+    let registry = entities
+        .iter()
+        .find(|entity| entity.name == "Registry")
+        .expect("struct entity");
+
+    assert_eq!(registry.methods.len(), 21);
+    let diagnosis = detect_god_class(registry, &create_test_config()).expect("god class detected");
+    assert_eq!(diagnosis.method_count, 21);
+}
+
+fn commit_files(repo: &git2::Repository, root: &Path, files: &[(&str, &str)]) {
+    let mut index = repo.index().expect("index");
+    for (name, body) in files {
+        fs::write(root.join(name), body).expect("write file");
+        index.add_path(Path::new(name)).expect("add path");
+    }
+    index.write().expect("write index");
+    let tree = repo
+        .find_tree(index.write_tree().expect("write tree"))
+        .expect("find tree");
+    let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+    let parents: Vec<git2::Commit> = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok())
+        .into_iter()
+        .collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, "change", &tree, &parent_refs)
+        .expect("commit");
+}
+
+#[test]
+fn test_detect_shotgun_surgery_requires_importers_and_co_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    let repo = git2::Repository::init(&root).unwrap();
+
+    commit_files(
+        &repo,
+        &root,
+        &[
+            ("core.py", "v1"),
+            ("quiet.py", "v1"),
+            ("a.py", "v1"),
+            ("b.py", "v1"),
+        ],
+    );
+    commit_files(&repo, &root, &[("core.py", "v2"), ("a.py", "v2")]);
+    commit_files(&repo, &root, &[("core.py", "v3"), ("b.py", "v3")]);
+    commit_files(&repo, &root, &[("core.py", "v4")]);
+
+    let mut snapshot = ProjectImportSnapshot::default();
+    for target in ["core.py", "quiet.py"] {
+        snapshot.reverse_imports.insert(
+            root.join(target),
+            [root.join("a.py"), root.join("b.py"), root.join(target)]
+                .into_iter()
+                .collect(),
+        );
+    }
+
+    let git_helper = GitHelper::new(&root);
+    let history = read_co_change_history(&git_helper, 1000);
+    let candidates = detect_shotgun_surgery(&snapshot, &git_helper, &history, 2);
+    assert_eq!(
+        candidates,
+        vec![ShotgunCandidate {
+            file: root.join("core.py"),
+            importer_count: 2,
+            co_change_count: 3,
+        }]
+    );
+    assert!(detect_shotgun_surgery(&snapshot, &git_helper, &history, 4).is_empty());
+
+    let outside_repo = TempDir::new().unwrap();
+    let no_git = GitHelper::new(outside_repo.path());
+    let no_history = read_co_change_history(&no_git, 1000);
+    assert!(no_history.is_empty());
+    assert!(detect_shotgun_surgery(&snapshot, &no_git, &no_history, 2).is_empty());
+}
+
+#[test]
+fn test_compute_change_coupling_normalizes_by_commit_count() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    let repo = git2::Repository::init(&root).unwrap();
+
+    commit_files(
+        &repo,
+        &root,
+        &[("a.py", "v1"), ("b.py", "v1"), ("c.py", "v1")],
+    );
+    commit_files(&repo, &root, &[("a.py", "v2"), ("b.py", "v2")]);
+    commit_files(&repo, &root, &[("a.py", "v3"), ("b.py", "v3")]);
+    commit_files(&repo, &root, &[("c.py", "v4"), ("untracked.txt", "x")]);
+
+    let [a, b, c] = ["a.py", "b.py", "c.py"].map(|name| root.join(name));
+    let files = vec![a.clone(), b.clone(), c.clone()];
+    let git_helper = GitHelper::new(&root);
+
+    let coupling = compute_change_coupling(&git_helper, &files, 1000);
+    assert_eq!(coupling.len(), 3);
+    assert_eq!(coupling[&(a.clone(), b.clone())], 0.75);
+    assert_eq!(coupling[&(a.clone(), c.clone())], 0.25);
+    assert_eq!(coupling[&(b.clone(), c.clone())], 0.25);
+
+    // Only the two most recent commits fall inside the window
+    let recent = compute_change_coupling(&git_helper, &files, 2);
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[&(a.clone(), b.clone())], 0.5);
+
+    let mut snapshot = ProjectImportSnapshot::default();
+    snapshot.imports_by_file.insert(c.clone(), vec![a.clone()]);
+    let ranked = rank_change_coupled_pairs(coupling, &snapshot, 2);
+    assert_eq!(ranked.len(), 2);
+    assert_eq!((&ranked[0].first, &ranked[0].second), (&a, &b));
+    assert!(ranked[0].is_hidden_coupling());
+    assert_eq!((&ranked[1].first, &ranked[1].second), (&a, &c));
+    assert!(ranked[1].imports_each_other);
+    assert!(!ranked[1].is_hidden_coupling());
+
+    let outside_repo = TempDir::new().unwrap();
+    let no_git = GitHelper::new(outside_repo.path());
+    assert!(compute_change_coupling(&no_git, &files, 1000).is_empty());
+}
+
+#[test]
+fn test_import_snapshot_persists_to_cache_dir_between_resolvers() {
+    let project = TempDir::new().unwrap();
+    let cache = TempDir::new().unwrap();
+    let root = project.path();
+    let main_file = root.join("main.py");
+    fs::write(root.join("helpers.py"), "def helper():\n    return 42\n").unwrap();
+    fs::write(&main_file, "from helpers import helper\n").unwrap();
+
+    let resolver = || ImportResolver::new().with_cache_dir(Some(cache.path().to_path_buf()));
+    let first = resolver().get_project_import_snapshot(root).unwrap();
+    assert_eq!(first.imports_by_file.len(), 1);
+
+    let cache_files: Vec<_> = fs::read_dir(cache.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(cache_files.len(), 1);
+
+    // Plant a marker edge in the cache file; a fresh resolver must return it.
+    let mut cached: Value =
+        serde_json::from_str(&fs::read_to_string(&cache_files[0]).unwrap()).unwrap();
+    cached["snapshot"]["imports_by_file"]["marker.py"] = serde_json::json!(["helpers.py"]);
+    fs::write(&cache_files[0], cached.to_string()).unwrap();
+
+    let loaded = resolver().get_project_import_snapshot(root).unwrap();
+    assert!(loaded
+        .imports_by_file
+        .contains_key(&PathBuf::from("marker.py")));
+
+    // Touching a source file changes the newest mtime and invalidates the cache.
+    let newer = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+    fs::File::options()
+        .write(true)
+        .open(&main_file)
+        .unwrap()
+        .set_modified(newer)
+        .unwrap();
+
+    let rebuilt = resolver().get_project_import_snapshot(root).unwrap();
+    assert!(!rebuilt
+        .imports_by_file
+        .contains_key(&PathBuf::from("marker.py")));
+    assert_eq!(rebuilt.imports_by_file.len(), 1);
+
+    // Editing a package manifest invalidates the cache without touching code.
+    let plant_marker = || {
+        let mut cached: Value =
+            serde_json::from_str(&fs::read_to_string(&cache_files[0]).unwrap()).unwrap();
+        cached["snapshot"]["imports_by_file"]["marker.py"] = serde_json::json!(["helpers.py"]);
+        fs::write(&cache_files[0], cached.to_string()).unwrap();
+    };
+    let has_marker = || {
+        resolver()
+            .get_project_import_snapshot(root)
+            .unwrap()
+            .imports_by_file
+            .contains_key(&PathBuf::from("marker.py"))
+    };
+    plant_marker();
+    fs::write(root.join("package.json"), r#"{"name": "demo"}"#).unwrap();
+    assert!(!has_marker());
+
+    // Renaming a file keeps the newest mtime but changes the path list.
+    plant_marker();
+    fs::rename(root.join("helpers.py"), root.join("utils.py")).unwrap();
+    assert!(!has_marker());
+}
+
+#[test]
+fn test_extract_entities_captures_symbols_on_class_boundary_lines() {
+    let analyzer = FileAnalyzer::new(create_test_config());
+    let source = r#"import os
+
+
+class Counter:
+    def __init__(self, start):
+        self.value = start
+
+    def increment(self, step):
+        self.value += step
+        return self.value
+
+
+def helper():
+    return os.getcwd()
+"#;
+
+    let entities = analyzer
+        .extract_entities_with_treesitter(Path::new("counter.py"), source)
+        .unwrap();
+    let counter = entities
+        .iter()
+        .find(|entity| entity.name == "Counter")
+        .expect("class entity");
+
+    assert_eq!(counter.loc, 7);
+    let symbols: Vec<&str> = counter.symbols.iter().map(String::as_str).collect();
+    assert_eq!(
+        symbols,
+        vec![
+            "Counter",
+            "__init__",
+            "increment",
+            "self",
+            "start",
+            "step",
+            "value"
+        ]
+    );
+}
@@ -0,0 +1,167 @@
+//! Package boundary detection for Cargo, npm, and pnpm workspaces.
+
+use std::path::{Component, Path, PathBuf};
+
+use globset::{GlobBuilder, GlobSetBuilder};
+
+use crate::detectors::structure::config::{
+    should_skip_directory, WorkspaceConfig, WorkspaceResolver,
+};
+
+/// Package root directories of a workspace, used to scope import resolution.
+#[derive(Debug, Clone, Default)]
+pub struct PackageBoundaries {
+    roots: Vec<PathBuf>,
+}
+
+/// Discovery and lookup methods for [`PackageBoundaries`].
+impl PackageBoundaries {
+    /// Resolve package roots under `project_root` according to `config`.
+    ///
+    /// Listed roots are joined onto `project_root`; with
+    /// [`WorkspaceResolver::Auto`] the detected manifest roots are added too.
+    pub fn resolve(project_root: &Path, config: &WorkspaceConfig) -> Self {
+        let mut roots: Vec<PathBuf> = config
+            .package_roots
+            .iter()
+            .map(|root| project_root.join(root))
+            .collect();
+
+        if config.resolver == WorkspaceResolver::Auto {
+            roots.extend(detect_package_roots(project_root));
+        }
+
+        let mut roots: Vec<PathBuf> = roots.iter().map(|root| normalize_lexically(root)).collect();
+        roots.sort();
+        roots.dedup();
+        Self { roots }
+    }
+
+    /// Package roots known to this workspace.
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// The innermost package root containing `path`, if any.
+    ///
+    /// `.` and `..` components are resolved lexically so relative imports that
+    /// climb out of a package are attributed to the package they land in.
+    pub fn package_of(&self, path: &Path) -> Option<&Path> {
+        let path = normalize_lexically(path);
+        self.roots
+            .iter()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .map(PathBuf::as_path)
+    }
+
+    /// Whether `from` and `to` belong to different packages.
+    pub fn crosses_boundary(&self, from: &Path, to: &Path) -> bool {
+        self.package_of(from) != self.package_of(to)
+    }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Find directories under `project_root` that hold a package manifest.
+///
+/// A directory is a package when it has a `package.json`, a `Cargo.toml`
+/// with a `[package]` table, or matches a `pnpm-workspace.yaml` glob. Virtual
+/// Cargo workspace roots are not packages themselves.
+pub fn detect_package_roots(project_root: &Path) -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+    collect_directories(project_root, &mut directories);
+
+    let pnpm_patterns = read_pnpm_patterns(project_root);
+    let mut builder = GlobSetBuilder::new();
+    for pattern in &pnpm_patterns {
+        let glob = GlobBuilder::new(pattern.trim_end_matches('/'))
+            .literal_separator(true)
+            .build();
+        if let Ok(glob) = glob {
+            builder.add(glob);
+        }
+    }
+    let pnpm_globs = builder.build().ok();
+
+    directories
+        .into_iter()
+        .filter(|dir| {
+            let is_pnpm_member = pnpm_globs.as_ref().is_some_and(|globs| {
+                dir.strip_prefix(project_root)
+                    .is_ok_and(|relative| globs.is_match(relative))
+            });
+            is_pnpm_member || has_package_manifest(dir)
+        })
+        .collect()
+}
+
+/// Recursively collect `dir` and its analysable subdirectories.
+fn collect_directories(dir: &Path, directories: &mut Vec<PathBuf>) {
+    if should_skip_directory(dir) {
+        return;
+    }
+    directories.push(dir.to_path_buf());
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_directories(&path, directories);
+        }
+    }
+}
+
+/// Whether `dir` declares an npm or Cargo package.
+fn has_package_manifest(dir: &Path) -> bool {
+    if dir.join("package.json").is_file() {
+        return true;
+    }
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .map(|manifest| manifest.lines().any(|line| line.trim() == "[package]"))
+        .unwrap_or(false)
+}
+
+/// Read the `packages` globs from `pnpm-workspace.yaml`, if present.
+fn read_pnpm_patterns(project_root: &Path) -> Vec<String> {
+    let Ok(contents) = std::fs::read_to_string(project_root.join("pnpm-workspace.yaml")) else {
+        return Vec::new();
+    };
+
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) && !trimmed.starts_with('-') {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            let pattern = item.trim().trim_matches(|c| c == '\'' || c == '"');
+            if !pattern.is_empty() && !pattern.starts_with('!') {
+                patterns.push(pattern.to_string());
+            }
+        }
+    }
+    patterns
+}
@@ -1,20 +1,28 @@
 //! Entity health scoring using distributional models and logistic shaping.
 //!
 //! Provides health scores for code entities using:
-//! - Lognormal distribution for entity sizes (AST nodes)
+//! - Lognormal distribution for entity sizes (AST nodes), optionally bimodal
+//!   for functions
 //! - Logistic shaping for flat→steep→saturating penalty curves
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use super::config::{EntityHealthConfig, StructureConfig};
+use super::config::{EntityHealthConfig, EntitySizeParams, StructureConfig};
+
+/// Mode of the short-utility function distribution, in AST nodes.
+const SHORT_UTILITY_OPTIMAL: usize = 6;
+
+/// 95th percentile of the short-utility function distribution, in AST nodes.
+const SHORT_UTILITY_PERCENTILE_95: usize = 30;
 
 /// Health scorer for entity metrics
 pub struct HealthScorer {
     config: EntityHealthConfig,
+    use_bimodal_function_scoring: bool,
 }
 
 /// Health metrics for a single entity
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntityHealth {
     /// Raw metric value (AST nodes)
     pub raw_value: usize,
@@ -30,17 +38,42 @@ impl HealthScorer {
     pub fn new(config: StructureConfig) -> Self {
         Self {
             config: config.entity_health,
+            use_bimodal_function_scoring: config.use_bimodal_function_scoring,
         }
     }
 
     /// Creates a new health scorer from entity health configuration.
     pub fn from_entity_config(config: EntityHealthConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            use_bimodal_function_scoring: false,
+        }
     }
 
     /// Score function/method size health based on AST node count.
+    ///
+    /// With bimodal scoring enabled the healthier of the short-utility and
+    /// configured modes is reported.
     pub fn score_function(&self, ast_nodes: usize) -> EntityHealth {
-        self.score_with_params(ast_nodes, &self.config.function_size)
+        let medium = self.score_with_params(ast_nodes, &self.config.function_size);
+        if !self.use_bimodal_function_scoring {
+            return medium;
+        }
+
+        let short = self.score_with_params(ast_nodes, &self.short_utility_params());
+        if short.health > medium.health {
+            short
+        } else {
+            medium
+        }
+    }
+
+    /// Function size health under a bimodal model: the maximum of the
+    /// short-utility and medium-complexity (configured) lognormal scores.
+    pub fn score_function_bimodal(&self, ast_nodes: usize) -> f64 {
+        let short = self.score_with_params(ast_nodes, &self.short_utility_params());
+        let medium = self.score_with_params(ast_nodes, &self.config.function_size);
+        short.health.max(medium.health)
     }
 
     /// Score class/struct size health based on AST node count.
@@ -53,12 +86,17 @@ impl HealthScorer {
         self.score_with_params(ast_nodes, &self.config.file_size)
     }
 
+    /// Short-utility mode, sharing the configured function penalty shaping.
+    fn short_utility_params(&self) -> EntitySizeParams {
+        EntitySizeParams {
+            optimal: SHORT_UTILITY_OPTIMAL,
+            percentile_95: SHORT_UTILITY_PERCENTILE_95,
+            ..self.config.function_size.clone()
+        }
+    }
+
     /// Computes health score using the given entity size parameters.
-    fn score_with_params(
-        &self,
-        ast_nodes: usize,
-        params: &super::config::EntitySizeParams,
-    ) -> EntityHealth {
+    fn score_with_params(&self, ast_nodes: usize, params: &EntitySizeParams) -> EntityHealth {
         let percentile = lognormal_cdf(ast_nodes, params.optimal, params.percentile_95);
         let health = logistic_health(percentile, params.penalty_center, params.penalty_steepness);
 
@@ -260,6 +298,25 @@ mod tests {
         assert!(health_large.health >= 0.0 && health_large.health <= 1.0);
     }
 
+    #[test]
+    fn test_bimodal_function_scoring_is_opt_in() {
+        let unimodal = HealthScorer::new(StructureConfig::default());
+        let bimodal = HealthScorer::new(StructureConfig {
+            use_bimodal_function_scoring: true,
+            ..StructureConfig::default()
+        });
+
+        for nodes in [3, 5, 40, 150, 600, 2000] {
+            let bimodal_health = bimodal.score_function_bimodal(nodes);
+            assert!(bimodal_health >= unimodal.score_function(nodes).health);
+            assert_eq!(bimodal.score_function(nodes).health, bimodal_health);
+        }
+
+        // Tiny utilities are healthy, and oversized functions stay unhealthy.
+        assert!(bimodal.score_function_bimodal(5) > 0.9);
+        assert!(bimodal.score_function_bimodal(2000) < 0.3);
+    }
+
     #[test]
     fn test_health_scorer_class() {
         let config = StructureConfig::default();
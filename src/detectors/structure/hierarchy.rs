@@ -0,0 +1,365 @@
+//! Parallel Inheritance Hierarchies smell detection.
+//!
+//! Two hierarchies are parallel when adding a subclass to one forces a
+//! matching subclass in the other (`Vehicle`/`Car`/`Truck` alongside
+//! `VehicleDrawer`/`CarDrawer`/`TruckDrawer`). Hierarchies are rebuilt from the
+//! base classes recorded by the language adapters, and correspondence is
+//! decided by name: one hierarchy's names carry a shared prefix or suffix, and
+//! the stripped names must match the other hierarchy's names by normalized
+//! Levenshtein similarity.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::lang::common::{EntityKind, ParseIndex};
+
+/// Normalized Levenshtein similarity at which two class names correspond.
+const NAME_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Corresponding classes, root included, needed before a pair is reported.
+const MIN_CORRESPONDING_CLASSES: usize = 3;
+
+/// Coupling score below which corresponding names are treated as coincidence.
+const MIN_COUPLING_SCORE: f64 = 0.5;
+
+/// Two inheritance hierarchies whose classes mirror each other.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParallelHierarchyPair {
+    /// Root class followed by its subclasses, for the hierarchy without the shared affix
+    pub hierarchy_a: Vec<String>,
+    /// Root class followed by its subclasses, for the hierarchy carrying the shared affix
+    pub hierarchy_b: Vec<String>,
+    /// Share of classes in both hierarchies that have a counterpart, in `[0, 1]`
+    pub coupling_score: f64,
+}
+
+/// Where a hierarchy's shared name affix sits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AffixPosition {
+    Prefix,
+    Suffix,
+}
+
+/// Detect pairs of parallel inheritance hierarchies in `index`.
+///
+/// Only classes and interfaces are considered. Results are ordered by
+/// descending coupling score, then by the root of `hierarchy_a`.
+pub fn detect_parallel_hierarchies(index: &ParseIndex) -> Vec<ParallelHierarchyPair> {
+    let hierarchies = collect_hierarchies(index);
+
+    let mut pairs = Vec::new();
+    for (i, first) in hierarchies.iter().enumerate() {
+        for second in &hierarchies[i + 1..] {
+            if let Some(pair) = match_hierarchies(first, second) {
+                pairs.push(pair);
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| {
+        b.coupling_score
+            .total_cmp(&a.coupling_score)
+            .then_with(|| a.hierarchy_a.cmp(&b.hierarchy_a))
+    });
+    pairs
+}
+
+/// Build every inheritance hierarchy as its root followed by its sorted descendants.
+fn collect_hierarchies(index: &ParseIndex) -> Vec<Vec<String>> {
+    let mut children: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut has_known_parent: BTreeSet<String> = BTreeSet::new();
+
+    let classes = index
+        .entities
+        .values()
+        .filter(|entity| matches!(entity.kind, EntityKind::Class | EntityKind::Interface));
+    let mut parents: HashMap<&str, Vec<String>> = HashMap::new();
+    for class in classes {
+        parents
+            .entry(class.name.as_str())
+            .or_default()
+            .extend(base_class_names(&class.metadata));
+    }
+    // Bases defined outside the index (`object`, `ABC`, library types) are
+    // not hierarchy roots; their direct subclasses are.
+    for (name, bases) in &parents {
+        for base in bases {
+            if base != name && parents.contains_key(base.as_str()) {
+                children
+                    .entry(base.clone())
+                    .or_default()
+                    .insert(name.to_string());
+                has_known_parent.insert(name.to_string());
+            }
+        }
+    }
+
+    children
+        .keys()
+        .filter(|root| !has_known_parent.contains(*root))
+        .map(|root| {
+            let mut descendants = BTreeSet::new();
+            let mut stack = vec![root.as_str()];
+            while let Some(current) = stack.pop() {
+                for child in children.get(current).into_iter().flatten() {
+                    if child != root && descendants.insert(child.clone()) {
+                        stack.push(child);
+                    }
+                }
+            }
+            std::iter::once(root.clone()).chain(descendants).collect()
+        })
+        .collect()
+}
+
+/// Base class names recorded by the language adapters, reduced to simple names.
+///
+/// Adapters store them under `base_classes` (Python, C++) or `extends`
+/// (JavaScript, TypeScript).
+fn base_class_names(metadata: &HashMap<String, serde_json::Value>) -> Vec<String> {
+    ["base_classes", "extends"]
+        .iter()
+        .filter_map(|key| metadata.get(*key))
+        .flat_map(|value| match value {
+            serde_json::Value::String(name) => vec![name.as_str()],
+            serde_json::Value::Array(names) => {
+                names.iter().filter_map(|name| name.as_str()).collect()
+            }
+            _ => Vec::new(),
+        })
+        .filter_map(simple_type_name)
+        .collect()
+}
+
+/// Strip access specifiers, qualification and type arguments from a base class.
+///
+/// `public ns::Shape<T>` and `shapes.Shape` both become `Shape`.
+fn simple_type_name(raw: &str) -> Option<String> {
+    let unparameterized = raw.split(['<', '(', '[']).next()?.trim();
+    let unqualified = unparameterized
+        .split_whitespace()
+        .last()?
+        .rsplit(['.', ':'])
+        .next()?;
+    (!unqualified.is_empty()).then(|| unqualified.to_string())
+}
+
+/// Compare two hierarchies and report them when their names run in parallel.
+fn match_hierarchies(first: &[String], second: &[String]) -> Option<ParallelHierarchyPair> {
+    let (position, affix, first_is_plain) = dominant_affix(first, second)?;
+    let (plain, decorated) = if first_is_plain {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    let stems: Vec<String> = decorated
+        .iter()
+        .map(|name| strip_affix(name, position, &affix).unwrap_or(name.as_str()))
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut scored = Vec::new();
+    for (plain_idx, plain_name) in plain.iter().enumerate() {
+        let plain_name = plain_name.to_lowercase();
+        for (stem_idx, stem) in stems.iter().enumerate() {
+            let similarity = normalized_levenshtein(&plain_name, stem);
+            if similarity >= NAME_SIMILARITY_THRESHOLD {
+                scored.push((similarity, plain_idx, stem_idx));
+            }
+        }
+    }
+    scored.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| (a.1, a.2).cmp(&(b.1, b.2)))
+    });
+
+    let mut plain_matched = vec![false; plain.len()];
+    let mut stem_matched = vec![false; stems.len()];
+    let mut matched = 0;
+    for (_, plain_idx, stem_idx) in scored {
+        if !plain_matched[plain_idx] && !stem_matched[stem_idx] {
+            plain_matched[plain_idx] = true;
+            stem_matched[stem_idx] = true;
+            matched += 1;
+        }
+    }
+
+    let coupling_score = 2.0 * matched as f64 / (plain.len() + decorated.len()) as f64;
+    (matched >= MIN_CORRESPONDING_CLASSES && coupling_score >= MIN_COUPLING_SCORE).then(|| {
+        ParallelHierarchyPair {
+            hierarchy_a: plain.to_vec(),
+            hierarchy_b: decorated.to_vec(),
+            coupling_score,
+        }
+    })
+}
+
+/// Find the affix most often separating names of `first` from names of `second`.
+///
+/// Returns its position, its text and whether `first` is the hierarchy without
+/// it. Pairs of names where neither extends the other contribute nothing.
+fn dominant_affix(first: &[String], second: &[String]) -> Option<(AffixPosition, String, bool)> {
+    let mut counts: BTreeMap<(AffixPosition, String, bool), usize> = BTreeMap::new();
+    for a in first {
+        for b in second {
+            for (short, long, first_is_plain) in [(a, b, true), (b, a, false)] {
+                if long.len() <= short.len() {
+                    continue;
+                }
+                if let Some(rest) = long.strip_prefix(short.as_str()) {
+                    *counts
+                        .entry((AffixPosition::Suffix, rest.to_string(), first_is_plain))
+                        .or_default() += 1;
+                }
+                if let Some(rest) = long.strip_suffix(short.as_str()) {
+                    *counts
+                        .entry((AffixPosition::Prefix, rest.to_string(), first_is_plain))
+                        .or_default() += 1;
+                }
+            }
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by(|(key_a, count_a), (key_b, count_b)| {
+            count_a.cmp(count_b).then_with(|| key_b.cmp(key_a))
+        })
+        .map(|(key, _)| key)
+}
+
+/// Remove `affix` from `name` at `position`, when present.
+fn strip_affix<'a>(name: &'a str, position: AffixPosition, affix: &str) -> Option<&'a str> {
+    match position {
+        AffixPosition::Prefix => name.strip_prefix(affix),
+        AffixPosition::Suffix => name.strip_suffix(affix),
+    }
+    .filter(|stem| !stem.is_empty())
+}
+
+/// Levenshtein similarity `1 - distance / max(len)` over characters, in `[0, 1]`.
+fn normalized_levenshtein(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / max_len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::common::{ParsedEntity, SourceLocation};
+
+    fn class(name: &str, bases: &[&str]) -> ParsedEntity {
+        let mut metadata = HashMap::new();
+        metadata.insert("base_classes".to_string(), serde_json::json!(bases));
+        ParsedEntity {
+            id: format!("shapes.py:{name}"),
+            kind: EntityKind::Class,
+            name: name.to_string(),
+            parent: None,
+            children: Vec::new(),
+            location: SourceLocation {
+                file_path: "shapes.py".to_string(),
+                start_line: 1,
+                end_line: 2,
+                start_column: 0,
+                end_column: 0,
+            },
+            metadata,
+            is_test: false,
+        }
+    }
+
+    fn index_of(classes: &[(&str, &[&str])]) -> ParseIndex {
+        let mut index = ParseIndex::new();
+        for (name, bases) in classes {
+            index.add_entity(class(name, bases));
+        }
+        index
+    }
+
+    #[test]
+    fn test_detects_suffixed_parallel_hierarchy() {
+        let index = index_of(&[
+            ("Vehicle", &[]),
+            ("Car", &["Vehicle"]),
+            ("Truck", &["Vehicle"]),
+            ("VehicleDrawer", &[]),
+            ("CarDrawer", &["VehicleDrawer"]),
+            ("TruckDrawer", &["VehicleDrawer"]),
+            ("Unrelated", &[]),
+        ]);
+
+        let pairs = detect_parallel_hierarchies(&index);
+
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].hierarchy_a, vec!["Vehicle", "Car", "Truck"]);
+        assert_eq!(
+            pairs[0].hierarchy_b,
+            vec!["VehicleDrawer", "CarDrawer", "TruckDrawer"]
+        );
+        assert_eq!(pairs[0].coupling_score, 1.0);
+    }
+
+    #[test]
+    fn test_tolerates_small_name_drift_and_missing_counterparts() {
+        let index = index_of(&[
+            ("Shape", &["abc.ABC"]),
+            ("Circle", &["shapes.Shape"]),
+            ("Square", &["Shape"]),
+            ("Triangle", &["Shape"]),
+            ("RenderShape", &[]),
+            ("RenderCircles", &["RenderShape"]),
+            ("RenderSquare", &["RenderShape"]),
+        ]);
+
+        let pairs = detect_parallel_hierarchies(&index);
+
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(
+            pairs[0].hierarchy_a,
+            vec!["Shape", "Circle", "Square", "Triangle"]
+        );
+        assert!((pairs[0].coupling_score - 6.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ignores_hierarchies_without_corresponding_names() {
+        let index = index_of(&[
+            ("Vehicle", &[]),
+            ("Car", &["Vehicle"]),
+            ("Truck", &["Vehicle"]),
+            ("Widget", &[]),
+            ("Button", &["Widget"]),
+            ("Slider", &["Widget"]),
+        ]);
+
+        assert!(detect_parallel_hierarchies(&index).is_empty());
+    }
+
+    #[test]
+    fn test_normalized_levenshtein() {
+        assert_eq!(normalized_levenshtein("car", "car"), 1.0);
+        assert_eq!(normalized_levenshtein("", ""), 1.0);
+        assert!((normalized_levenshtein("circle", "circles") - 6.0 / 7.0).abs() < 1e-9);
+        assert_eq!(normalized_levenshtein("abc", "xyz"), 0.0);
+    }
+}
@@ -22,22 +22,38 @@ use serde::Serialize;
 use crate::core::arena_analysis::ArenaAnalysisResult;
 use crate::core::errors::Result;
 use crate::core::featureset::{CodeEntity, ExtractionContext, FeatureDefinition, FeatureExtractor};
+use crate::lang::registry::detect_language_from_path;
 
+pub mod chains;
 pub mod config;
 pub mod directory;
 pub mod file;
 pub mod health;
+pub mod hierarchy;
 
+pub use chains::{
+    detect_method_chains, FileMethodChains, MethodChainDiagnosis, DEFAULT_MAX_METHOD_CHAIN_LENGTH,
+    METHOD_CHAIN_CODE,
+};
 pub use config::*;
 use directory::DirectoryAnalyzer;
-use file::FileAnalyzer;
+use file::{ChangeCoupledPair, FileAnalyzer, ShotgunCandidate, MAX_CHANGE_COUPLED_PAIRS};
 pub use health::{EntityHealth, HealthScorer};
+pub use hierarchy::{detect_parallel_hierarchies, ParallelHierarchyPair};
 
 /// Combined recommendation output containing both branch reorg and file split packs
 #[derive(Debug, Serialize)]
 pub struct StructureRecommendations {
     pub branch_reorg_packs: Vec<BranchReorgPack>,
     pub file_split_packs: Vec<FileSplitPack>,
+    /// Files flagged with the Shotgun Surgery smell, most co-changed first
+    pub shotgun_surgery: Vec<ShotgunCandidate>,
+    /// File pairs that most often change in the same commits
+    pub change_coupled_pairs: Vec<ChangeCoupledPair>,
+    /// Files containing method chains longer than the configured maximum
+    pub method_chains: Vec<FileMethodChains>,
+    /// Per-entity health for each file with at least one function or class
+    pub entity_health: Vec<FileHealthReport>,
 }
 
 /// Count and iteration methods for [`StructureRecommendations`].
@@ -146,6 +162,12 @@ impl StructureExtractor {
         extractor
     }
 
+    /// Persist project import snapshots under `cache_dir` between runs.
+    pub fn with_import_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.file_analyzer = self.file_analyzer.with_import_cache_dir(cache_dir);
+        self
+    }
+
     /// Initializes the feature definitions for structure analysis.
     fn initialize_features(&mut self) {
         self.features = vec![
@@ -177,6 +199,10 @@ impl StructureExtractor {
                 "file_split_value",
                 "Value score for file splitting recommendation",
             ),
+            FeatureDefinition::new(
+                "lcom4",
+                "Connected components in the file's entity cohesion graph (LCOM4)",
+            ),
         ];
     }
 
@@ -214,6 +240,12 @@ impl StructureExtractor {
         Ok(StructureRecommendations {
             branch_reorg_packs,
             file_split_packs,
+            shotgun_surgery: self.detect_shotgun_surgery(root_path),
+            change_coupled_pairs: self.detect_change_coupled_pairs(root_path),
+            // Method chains and entity health need file sources, which only the
+            // metrics path carries
+            method_chains: Vec::new(),
+            entity_health: Vec::new(),
         })
     }
 
@@ -302,6 +334,10 @@ impl StructureExtractor {
         Ok(StructureRecommendations {
             branch_reorg_packs,
             file_split_packs,
+            shotgun_surgery: self.detect_shotgun_surgery(root_path),
+            change_coupled_pairs: self.detect_change_coupled_pairs(root_path),
+            method_chains: self.find_method_chains(metrics),
+            entity_health: self.report_entity_health(metrics),
         })
     }
 
@@ -363,6 +399,76 @@ impl StructureExtractor {
         Ok(packs)
     }
 
+    /// Shotgun Surgery candidates for a project, empty when file split packs
+    /// are disabled or detection fails, which is logged as a warning.
+    fn detect_shotgun_surgery(&self, root_path: &Path) -> Vec<ShotgunCandidate> {
+        if !self.config.enable_file_split_packs {
+            return Vec::new();
+        }
+        self.file_analyzer
+            .detect_shotgun_surgery(root_path)
+            .map(|candidates| candidates.as_ref().clone())
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Shotgun Surgery detection failed for {}: {}",
+                    root_path.display(),
+                    e
+                );
+                Vec::new()
+            })
+    }
+
+    /// Files whose method chains exceed the configured maximum length.
+    fn find_method_chains(&self, metrics: &[PrecomputedFileMetrics]) -> Vec<FileMethodChains> {
+        let max_length = self.config.max_method_chain_length;
+        metrics
+            .iter()
+            .filter_map(|file| {
+                let language = detect_language_from_path(&file.path.to_string_lossy());
+                let chains = detect_method_chains(&file.source, &language, max_length);
+                (!chains.is_empty()).then(|| FileMethodChains {
+                    path: file.path.clone(),
+                    chains,
+                })
+            })
+            .collect()
+    }
+
+    /// Entity health reports for files containing at least one entity.
+    fn report_entity_health(&self, metrics: &[PrecomputedFileMetrics]) -> Vec<FileHealthReport> {
+        metrics
+            .iter()
+            .filter_map(|file| {
+                let report = self
+                    .file_analyzer
+                    .calculate_entity_health(&file.path, &file.source)
+                    .ok()?;
+                (!report.per_entity.is_empty()).then(|| FileHealthReport {
+                    path: file.path.clone(),
+                    report,
+                })
+            })
+            .collect()
+    }
+
+    /// Most change-coupled file pairs for a project, empty when file split
+    /// packs are disabled or detection fails, which is logged as a warning.
+    fn detect_change_coupled_pairs(&self, root_path: &Path) -> Vec<ChangeCoupledPair> {
+        if !self.config.enable_file_split_packs {
+            return Vec::new();
+        }
+        self.file_analyzer
+            .detect_change_coupled_pairs(root_path, MAX_CHANGE_COUPLED_PAIRS)
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Change coupling detection failed for {}: {}",
+                    root_path.display(),
+                    e
+                );
+                Vec::new()
+            })
+    }
+
     /// Calculate directory metrics - exposed for testing and external use
     pub fn calculate_directory_metrics(&self, dir_path: &Path) -> Result<DirectoryMetrics> {
         self.directory_analyzer
@@ -455,6 +561,13 @@ impl FeatureExtractor for StructureExtractor {
             features.insert("file_split_value".to_string(), 0.0);
         }
 
+        let lcom = self
+            .file_analyzer
+            .file_lcom4(std::path::Path::new(&entity.file_path))
+            .map(|lcom| lcom as f64)
+            .unwrap_or(0.0);
+        features.insert("lcom4".to_string(), lcom);
+
         Ok(features)
     }
 }
@@ -536,6 +649,10 @@ mod tests {
         let recommendations = StructureRecommendations {
             branch_reorg_packs: vec![sample_branch_pack()],
             file_split_packs: vec![sample_file_split_pack()],
+            shotgun_surgery: Vec::new(),
+            change_coupled_pairs: Vec::new(),
+            method_chains: Vec::new(),
+            entity_health: Vec::new(),
         };
 
         assert_eq!(recommendations.len(), 2);
@@ -583,7 +700,8 @@ mod tests {
                 "size_pressure",
                 "loc_dispersion",
                 "branch_reorg_value",
-                "file_split_value"
+                "file_split_value",
+                "lcom4"
             ]
         );
     }
@@ -602,7 +720,7 @@ mod tests {
             .await
             .expect("extract features");
 
-        assert_eq!(extractor.features().len(), 7);
+        assert_eq!(extractor.features().len(), 8);
         for key in [
             "directory_imbalance",
             "file_pressure",
@@ -622,6 +740,7 @@ mod tests {
         }
 
         assert_eq!(features["file_split_value"], 0.0);
+        assert_eq!(features["lcom4"], 0.0);
     }
 
     #[tokio::test]
@@ -0,0 +1,244 @@
+//! Automatic stub insertion for documentation gaps.
+//!
+//! Only `undocumented_*` issues are fixable. Each item is re-checked against
+//! the current file contents before a stub is inserted, so issues for items
+//! with incomplete (rather than missing) docs are left untouched.
+
+use super::languages::python::{find_docstring, indentation};
+use super::{extract_comment_text, DocAuditConfig, DocIssue};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Placeholder text inserted for every missing doc comment.
+const STUB_TEXT: &str = "TODO: document this.";
+
+/// Category prefix shared by all documentation-gap issues.
+const FIXABLE_CATEGORY_PREFIX: &str = "undocumented_";
+
+/// Gap categories on items that already carry docs, so no stub applies.
+const NON_STUB_CATEGORIES: &[&str] = &["undocumented_type"];
+
+/// A stub doc comment written to disk by [`apply_fixes`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AppliedFix {
+    /// File path as reported by the originating issue.
+    pub path: PathBuf,
+    /// Line of the documented symbol in the original file (1-based).
+    pub line: usize,
+    /// The exact text inserted, including indentation.
+    pub inserted: String,
+}
+
+/// Source language of a fixable file, determined by extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StubLanguage {
+    Python,
+    Rust,
+    TypeScript,
+}
+
+/// Stub line to insert before the zero-based `index` of the file's lines.
+struct PlannedInsertion {
+    index: usize,
+    symbol_line: usize,
+    text: String,
+}
+
+/// Insert stub doc comments for every missing-documentation issue.
+///
+/// Issues are grouped per file and each file is rewritten at most once via a
+/// temporary sibling file and a rename, so a failure never leaves a partially
+/// written source file behind. Staleness, README and attribute hygiene issues
+/// are ignored.
+pub fn apply_fixes(issues: &[DocIssue], config: &DocAuditConfig) -> Result<Vec<AppliedFix>> {
+    let mut by_file: BTreeMap<&Path, Vec<usize>> = BTreeMap::new();
+    for issue in issues {
+        if !issue.category.starts_with(FIXABLE_CATEGORY_PREFIX)
+            || NON_STUB_CATEGORIES.contains(&issue.category.as_str())
+        {
+            continue;
+        }
+        if let Some(line) = issue.line {
+            by_file.entry(issue.path.as_path()).or_default().push(line);
+        }
+    }
+
+    let mut applied = Vec::new();
+    for (path, lines) in by_file {
+        let Some(language) = stub_language(path) else {
+            continue;
+        };
+        let absolute = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            config.root.join(path)
+        };
+        applied.extend(fix_file(&absolute, path, language, lines)?);
+    }
+
+    Ok(applied)
+}
+
+/// Plan and apply all stub insertions for a single file.
+fn fix_file(
+    absolute: &Path,
+    reported: &Path,
+    language: StubLanguage,
+    mut symbol_lines: Vec<usize>,
+) -> Result<Vec<AppliedFix>> {
+    let contents = fs::read_to_string(absolute)
+        .with_context(|| format!("Failed to read {} for doc fixes", absolute.display()))?;
+    let lines: Vec<&str> = contents.lines().collect();
+
+    symbol_lines.sort_unstable();
+    symbol_lines.dedup();
+    let planned: Vec<PlannedInsertion> = symbol_lines
+        .into_iter()
+        .filter_map(|line| plan_insertion(&lines, line, language))
+        .collect();
+    if planned.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let newline = if contents.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut output: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    // Insert bottom-up so earlier indices stay valid.
+    for insertion in planned.iter().rev() {
+        output.insert(insertion.index, insertion.text.clone());
+    }
+    let mut rewritten = output.join(newline);
+    if contents.ends_with('\n') {
+        rewritten.push_str(newline);
+    }
+
+    write_atomically(absolute, &rewritten)?;
+
+    Ok(planned
+        .into_iter()
+        .map(|insertion| AppliedFix {
+            path: reported.to_path_buf(),
+            line: insertion.symbol_line,
+            inserted: insertion.text,
+        })
+        .collect())
+}
+
+/// Map a file extension to the language whose stub syntax applies.
+fn stub_language(path: &Path) -> Option<StubLanguage> {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("py" | "pyi") => Some(StubLanguage::Python),
+        Some("rs") => Some(StubLanguage::Rust),
+        Some("ts" | "tsx" | "js" | "jsx") => Some(StubLanguage::TypeScript),
+        _ => None,
+    }
+}
+
+/// Decide where a stub goes for the symbol on 1-based `line`, if it still lacks docs.
+fn plan_insertion(lines: &[&str], line: usize, language: StubLanguage) -> Option<PlannedInsertion> {
+    let index = line.checked_sub(1).filter(|index| *index < lines.len())?;
+    let item = lines[index];
+    let indent = &item[..item.len() - item.trim_start().len()];
+
+    let (insert_at, text) = match language {
+        StubLanguage::Python => {
+            let trimmed = item.trim_start();
+            if !(trimmed.starts_with("def ")
+                || trimmed.starts_with("async def ")
+                || trimmed.starts_with("class "))
+            {
+                return None;
+            }
+            let header_end = python_header_end(lines, index)?;
+            if find_docstring(lines, header_end + 1, indentation(item)).is_some() {
+                return None;
+            }
+            let body_indent = lines[header_end + 1..]
+                .iter()
+                .find(|candidate| !candidate.trim().is_empty())
+                .filter(|candidate| indentation(candidate) > indentation(item))
+                .map(|candidate| {
+                    candidate[..candidate.len() - candidate.trim_start().len()].to_string()
+                })
+                .unwrap_or_else(|| format!("{indent}    "));
+            (
+                header_end + 1,
+                format!("{body_indent}\"\"\"{STUB_TEXT}\"\"\""),
+            )
+        }
+        StubLanguage::Rust | StubLanguage::TypeScript => {
+            if extract_comment_text(lines, index).is_some() {
+                return None;
+            }
+            let mut insert_at = index;
+            while insert_at > 0 && lines[insert_at - 1].trim_start().starts_with("#[") {
+                insert_at -= 1;
+            }
+            let stub = if language == StubLanguage::Rust {
+                format!("{indent}/// {STUB_TEXT}")
+            } else {
+                format!("{indent}/** {STUB_TEXT} */")
+            };
+            (insert_at, stub)
+        }
+    };
+
+    Some(PlannedInsertion {
+        index: insert_at,
+        symbol_line: line,
+        text,
+    })
+}
+
+/// Find the line that closes a (possibly multi-line) Python `def`/`class` header.
+///
+/// Returns `None` for one-line definitions whose body follows the colon, since
+/// those have no block to hold a docstring.
+fn python_header_end(lines: &[&str], start: usize) -> Option<usize> {
+    let mut depth: isize = 0;
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        let code = line.split('#').next().unwrap_or_default();
+        for ch in code.chars() {
+            match ch {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if depth <= 0 {
+            return code.trim_end().ends_with(':').then_some(index);
+        }
+    }
+    None
+}
+
+/// Replace `path` with `contents` via a temporary sibling file and a rename.
+fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{file_name}.valknut-fix.tmp"));
+
+    fs::write(&temp_path, contents)
+        .with_context(|| format!("Failed to write temporary file {}", temp_path.display()))?;
+    if let Ok(metadata) = fs::metadata(path) {
+        let _ = fs::set_permissions(&temp_path, metadata.permissions());
+    }
+    fs::rename(&temp_path, path).with_context(|| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to replace {} with fixed contents", path.display())
+    })
+}
@@ -4,8 +4,25 @@
 //! by tracking commit history relative to documentation files.
 
 use chrono::{DateTime, FixedOffset, TimeZone};
-use git2::{DiffOptions, Oid, Repository};
+use git2::{
+    DiffOptions, ErrorCode, ObjectType, Oid, Repository, Revwalk, TreeWalkMode, TreeWalkResult,
+};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::core::errors::{Result, ValknutError};
+use crate::core::git_utils::auto_detect_git_root;
+
+/// Revision walker starting at the commit `HEAD` resolves to.
+///
+/// Resolving the commit directly works for both branch and detached `HEAD`.
+fn head_walker(repo: &Repository) -> Option<Revwalk<'_>> {
+    let head = repo.head().ok()?.peel_to_commit().ok()?;
+    let mut walker = repo.revwalk().ok()?;
+    walker.push(head.id()).ok()?;
+    Some(walker)
+}
 
 /// Information about a commit.
 #[derive(Clone)]
@@ -25,18 +42,16 @@ pub struct GitHelper {
 /// Git operations for repository analysis.
 impl GitHelper {
     /// Create a new GitHelper for the given root path.
+    ///
+    /// Paths are resolved against the repository root found by
+    /// [`auto_detect_git_root`], so `root` may be any directory inside the
+    /// working tree. Outside a repository, `root` itself is used.
     pub fn new(root: &Path) -> Self {
-        match Repository::discover(root) {
-            Ok(repo) => {
-                let repo_root = repo
-                    .workdir()
-                    .map(|path| path.to_path_buf())
-                    .unwrap_or_else(|| root.to_path_buf());
-                Self {
-                    repo: Some(repo),
-                    repo_root,
-                }
-            }
+        match auto_detect_git_root(root) {
+            Ok(repo_root) => Self {
+                repo: Repository::open(&repo_root).ok(),
+                repo_root,
+            },
             Err(_) => Self {
                 repo: None,
                 repo_root: root.to_path_buf(),
@@ -59,10 +74,7 @@ impl GitHelper {
         let repo = self.repo()?;
         let relative = self.relative_to_repo(path)?;
 
-        let mut walker = repo.revwalk().ok()?;
-        walker.push_head().ok()?;
-
-        for oid in walker {
+        for oid in head_walker(repo)? {
             let oid = oid.ok()?;
             let commit = repo.find_commit(oid).ok()?;
             if commit_touches_path(repo, &commit, &relative) {
@@ -76,6 +88,9 @@ impl GitHelper {
 
     /// Count commits that touched a directory since a given commit,
     /// optionally excluding a specific path.
+    ///
+    /// In a shallow clone whose history stops before `since`, the count cannot
+    /// be known; `Some(0)` is returned and a warning logged instead.
     pub fn commits_since(
         &self,
         since: Oid,
@@ -86,13 +101,16 @@ impl GitHelper {
         let directory_rel = self.relative_to_repo(directory)?;
         let exclude_rel = exclude_path.and_then(|path| self.relative_to_repo(path));
 
-        let mut walker = repo.revwalk().ok()?;
-        walker.push_head().ok()?;
-
         let mut counter = 0usize;
-        for oid in walker {
-            let oid = oid.ok()?;
+        let mut reached_since = false;
+        for oid in head_walker(repo)? {
+            let oid = match oid {
+                Ok(oid) => oid,
+                Err(err) if err.code() == ErrorCode::NotFound => break,
+                Err(_) => return None,
+            };
             if oid == since {
+                reached_since = true;
                 break;
             }
             let commit = repo.find_commit(oid).ok()?;
@@ -101,8 +119,101 @@ impl GitHelper {
             }
         }
 
+        if !reached_since && repo.is_shallow() {
+            warn!(
+                "Shallow clone history ends before commit {}; cannot count commits touching '{}', assuming none",
+                since,
+                directory_rel.display()
+            );
+            return Some(0);
+        }
+
         Some(counter)
     }
+
+    /// Files changed by each of the latest `max_commits` commits reachable from
+    /// `HEAD`, newest first, relative to the repository root.
+    ///
+    /// Merge commits are compared against their first parent. The walk stops
+    /// at the boundary of a shallow clone rather than reporting the boundary
+    /// commit as adding every file.
+    pub fn changed_files_by_commit(&self, max_commits: usize) -> Option<Vec<HashSet<PathBuf>>> {
+        let repo = self.repo()?;
+        let mut history = Vec::new();
+        for oid in head_walker(repo)?.take(max_commits) {
+            let oid = match oid {
+                Ok(oid) => oid,
+                Err(err) if err.code() == ErrorCode::NotFound => break,
+                Err(_) => return None,
+            };
+            let commit = repo.find_commit(oid).ok()?;
+            let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+            if commit.parent_count() > 0 && parent_tree.is_none() {
+                break;
+            }
+            let tree = commit.tree().ok()?;
+            let diff = repo
+                .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+                .ok()?;
+            history.push(
+                diff.deltas()
+                    .filter_map(|delta| delta.new_file().path().or(delta.old_file().path()))
+                    .map(PathBuf::from)
+                    .collect(),
+            );
+        }
+        Some(history)
+    }
+
+    /// Object ID of the commit `HEAD` points at.
+    pub fn head_oid(&self) -> Option<Oid> {
+        self.repo()?
+            .head()
+            .ok()?
+            .peel_to_commit()
+            .ok()
+            .map(|commit| commit.id())
+    }
+
+    /// Contents of every UTF-8 file under a directory as of the given commit.
+    ///
+    /// Paths are relative to the repository root. A directory that did not
+    /// exist at that commit yields an empty list.
+    pub fn directory_sources(
+        &self,
+        commit: Oid,
+        directory: &Path,
+    ) -> Option<Vec<(PathBuf, String)>> {
+        let repo = self.repo()?;
+        let directory_rel = self.relative_to_repo(directory)?;
+        let root_tree = repo.find_commit(commit).ok()?.tree().ok()?;
+
+        let tree = if directory_rel.as_os_str().is_empty() {
+            root_tree
+        } else {
+            match root_tree.get_path(&directory_rel) {
+                Ok(entry) => entry.to_object(repo).ok()?.peel_to_tree().ok()?,
+                Err(_) => return Some(Vec::new()),
+            }
+        };
+
+        let mut files = Vec::new();
+        tree.walk(TreeWalkMode::PreOrder, |parent, entry| {
+            if entry.kind() == Some(ObjectType::Blob) {
+                let content = repo
+                    .find_blob(entry.id())
+                    .ok()
+                    .and_then(|blob| String::from_utf8(blob.content().to_vec()).ok());
+                if let (Some(name), Some(content)) = (entry.name(), content) {
+                    files.push((directory_rel.join(parent).join(name), content));
+                }
+            }
+            TreeWalkResult::Ok
+        })
+        .ok()?;
+
+        Some(files)
+    }
 }
 
 /// Check if a commit touched a specific file path.
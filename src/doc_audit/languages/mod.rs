@@ -7,6 +7,9 @@ pub mod python;
 pub mod rust;
 pub mod typescript;
 
-pub use python::scan_python;
+pub use python::{
+    check_python_module_docstring, check_python_param_name_consistency,
+    check_python_type_annotation_coverage, scan_python, DocStyle,
+};
 pub use rust::scan_rust;
 pub use typescript::scan_typescript;
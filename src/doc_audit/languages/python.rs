@@ -1,15 +1,22 @@
 //! Python docstring scanner for doc audit.
 
-use super::super::{is_incomplete_doc, relative_path, DocIssue};
+use super::super::{
+    is_incomplete_doc, normalize_line_endings, relative_path, DocIssue, FileScan, SymbolCount,
+};
+use edit_distance::edit_distance;
+use std::collections::BTreeSet;
 use std::path::Path;
 
 /// Scans Python source code for missing or incomplete docstrings.
 ///
 /// Detects undocumented functions, async functions, and classes.
 /// Tracks nesting via indentation to report fully-qualified symbol names.
-pub fn scan_python(source: &str, path: &Path, root: &Path) -> Vec<DocIssue> {
+pub fn scan_python(source: &str, path: &Path, root: &Path) -> FileScan {
+    let source = normalize_line_endings(source);
     let lines: Vec<&str> = source.lines().collect();
     let mut issues = Vec::new();
+    let mut symbols = SymbolCount::default();
+    let mut public_names = BTreeSet::new();
     let mut stack: Vec<(usize, String)> = Vec::new();
     let mut index = 0usize;
 
@@ -38,15 +45,55 @@ pub fn scan_python(source: &str, path: &Path, root: &Path) -> Vec<DocIssue> {
                 full_name.push(&symbol);
                 let symbol_name = full_name.join(".");
 
-                let issue_message = match find_docstring(&lines, index + 1, indent) {
+                let definition_line = index + 1;
+                let (signature, signature_end) = collect_signature(&lines, index);
+                let issue_message = match find_docstring(&lines, signature_end + 1, indent) {
                     Some((docstring, end_index)) => {
                         index = end_index;
+                        if kind == "Function" {
+                            if let Some(issue) = check_documented_types(
+                                &signature,
+                                &docstring,
+                                path,
+                                root,
+                                definition_line,
+                                &symbol_name,
+                            ) {
+                                issues.push(issue);
+                            }
+                            if let Some(issue) = check_param_names(
+                                &signature,
+                                &docstring,
+                                path,
+                                root,
+                                definition_line,
+                                &symbol_name,
+                            ) {
+                                issues.push(issue);
+                            }
+                            if let Some(issue) = check_returns_doc(
+                                &lines,
+                                end_index + 1,
+                                indent,
+                                &docstring,
+                                path,
+                                root,
+                                definition_line,
+                                &symbol_name,
+                            ) {
+                                issues.push(issue);
+                            }
+                        }
                         is_incomplete_doc(&docstring)
                             .then(|| format!("{} '{}' has incomplete docstring", kind, symbol_name))
                     }
                     None => Some(format!("{} '{}' is missing a docstring", kind, symbol_name)),
                 };
 
+                symbols.record(issue_message.is_none());
+                if stack.is_empty() && !symbol.starts_with('_') {
+                    public_names.insert(symbol.clone());
+                }
                 if let Some(message) = issue_message {
                     issues.push(build_issue(
                         path,
@@ -64,7 +111,524 @@ pub fn scan_python(source: &str, path: &Path, root: &Path) -> Vec<DocIssue> {
         index += 1;
     }
 
-    issues
+    FileScan {
+        issues,
+        symbols,
+        public_names,
+    }
+}
+
+/// Docstring convention used to document parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocStyle {
+    /// reStructuredText fields: `:param x:` and `:type x: int`.
+    Sphinx,
+    /// `Args:` section with `x (int): description` entries.
+    Google,
+    /// `Parameters` section underlined with dashes and `x : int` entries.
+    NumPy,
+}
+
+/// Factory methods for [`DocStyle`].
+impl DocStyle {
+    /// Detect the parameter documentation style of a docstring.
+    ///
+    /// Returns `None` when the docstring has no recognisable parameter section.
+    pub fn detect(docstring: &str) -> Option<Self> {
+        let lines: Vec<&str> = docstring.lines().map(str::trim).collect();
+        if lines
+            .iter()
+            .any(|line| line.starts_with(":param") || line.starts_with(":type"))
+        {
+            return Some(Self::Sphinx);
+        }
+        if lines
+            .iter()
+            .any(|line| *line == "Args:" || *line == "Arguments:")
+        {
+            return Some(Self::Google);
+        }
+        if numpy_parameters_start(&lines).is_some() {
+            return Some(Self::NumPy);
+        }
+        None
+    }
+}
+
+/// Reports a package `__init__.py` whose first statement is not a docstring.
+///
+/// Empty or whitespace-only files, which merely mark a directory as a package,
+/// are skipped. Otherwise blank lines and comments (including a shebang or
+/// encoding cookie) are skipped; anything else before a string literal is
+/// reported as `missing_module_docstring` on line 1.
+pub fn check_python_module_docstring(source: &str, path: &Path, root: &Path) -> Option<DocIssue> {
+    if source.trim().is_empty() {
+        return None;
+    }
+
+    let first_statement = source
+        .lines()
+        .map(str::trim_start)
+        .find(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let has_docstring = first_statement
+        .and_then(find_string_prefix)
+        .is_some_and(|(prefix_len, _)| prefix_len <= 2);
+    if has_docstring {
+        return None;
+    }
+
+    Some(DocIssue {
+        category: "missing_module_docstring".to_string(),
+        path: relative_path(path, root),
+        line: Some(1),
+        symbol: None,
+        detail: "Package __init__.py is missing a module docstring".to_string(),
+    })
+}
+
+/// Returns annotated parameters of `signature` whose type is not documented.
+///
+/// `self`, `cls` and the bare `*` / `/` markers are skipped; `*args` and
+/// `**kwargs` are reported by their bare names.
+pub fn check_python_type_annotation_coverage(
+    signature: &str,
+    docstring: &str,
+    annotation_style: DocStyle,
+) -> Vec<String> {
+    let documented = documented_types(docstring, annotation_style);
+    annotated_parameters(signature)
+        .into_iter()
+        .filter(|name| !documented.contains(name))
+        .collect()
+}
+
+/// Emits an `undocumented_type` issue when a structured docstring omits annotated types.
+fn check_documented_types(
+    signature: &str,
+    docstring: &str,
+    path: &Path,
+    root: &Path,
+    line: usize,
+    symbol: &str,
+) -> Option<DocIssue> {
+    let style = DocStyle::detect(docstring)?;
+    let missing = check_python_type_annotation_coverage(signature, docstring, style);
+    if missing.is_empty() {
+        return None;
+    }
+
+    Some(DocIssue {
+        category: "undocumented_type".to_string(),
+        path: relative_path(path, root),
+        line: Some(line),
+        symbol: Some(symbol.to_string()),
+        detail: format!(
+            "Function '{}' does not document annotated types for: {}",
+            symbol,
+            missing.join(", ")
+        ),
+    })
+}
+
+/// Pairs signature parameters with the stale docstring names that stand in for them.
+///
+/// Each documented name missing from the signature is paired with the
+/// undocumented signature parameter at the same position, or failing that with
+/// the undocumented parameter closest by edit distance. Returns
+/// `(sig_name, doc_name)` pairs in docstring order.
+pub fn check_python_param_name_consistency(
+    signature: &str,
+    docstring: &str,
+) -> Vec<(String, String)> {
+    let Some(style) = DocStyle::detect(docstring) else {
+        return Vec::new();
+    };
+    let signature_names = parameter_names(signature);
+    let documented = documented_parameters(docstring, style);
+
+    let mut unmatched: Vec<&String> = signature_names
+        .iter()
+        .filter(|name| !documented.contains(name))
+        .collect();
+    let mut pairs = Vec::new();
+    for (position, doc_name) in documented.iter().enumerate() {
+        if signature_names.contains(doc_name) || unmatched.is_empty() {
+            continue;
+        }
+        let index = signature_names
+            .get(position)
+            .and_then(|sig_name| unmatched.iter().position(|name| *name == sig_name))
+            .unwrap_or_else(|| {
+                (0..unmatched.len())
+                    .min_by_key(|&index| edit_distance(unmatched[index], doc_name))
+                    .unwrap_or(0)
+            });
+        pairs.push((unmatched.remove(index).clone(), doc_name.clone()));
+    }
+    pairs
+}
+
+/// Emits a `param_name_mismatch` issue when documented parameter names no
+/// longer match the signature.
+fn check_param_names(
+    signature: &str,
+    docstring: &str,
+    path: &Path,
+    root: &Path,
+    line: usize,
+    symbol: &str,
+) -> Option<DocIssue> {
+    let pairs = check_python_param_name_consistency(signature, docstring);
+    if pairs.is_empty() {
+        return None;
+    }
+
+    let renames: Vec<String> = pairs
+        .iter()
+        .map(|(sig_name, doc_name)| format!("'{}' (documented as '{}')", sig_name, doc_name))
+        .collect();
+    Some(DocIssue {
+        category: "param_name_mismatch".to_string(),
+        path: relative_path(path, root),
+        line: Some(line),
+        symbol: Some(symbol.to_string()),
+        detail: format!(
+            "Function '{}' docstring uses stale parameter names: {}",
+            symbol,
+            renames.join(", ")
+        ),
+    })
+}
+
+/// Emits `spurious_returns_doc` when a `Returns` section documents a function
+/// that never returns a value, or `missing_returns_doc` when a structured
+/// docstring omits the value the function returns.
+///
+/// Placeholder bodies (`...`, `pass`, `raise NotImplementedError`) are skipped.
+fn check_returns_doc(
+    lines: &[&str],
+    body_start: usize,
+    indent: usize,
+    docstring: &str,
+    path: &Path,
+    root: &Path,
+    line: usize,
+    symbol: &str,
+) -> Option<DocIssue> {
+    let first_statement = lines
+        .iter()
+        .skip(body_start)
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty() && !line.starts_with('#'));
+    if first_statement.is_none_or(|statement| {
+        statement == "..."
+            || statement == "pass"
+            || statement.starts_with("raise NotImplementedError")
+    }) {
+        return None;
+    }
+
+    let documents_returns = has_returns_section(docstring);
+    let (category, detail) = match python_has_value_return(lines, body_start, indent) {
+        false if documents_returns => (
+            "spurious_returns_doc",
+            format!(
+                "Function '{}' documents a return value it never returns",
+                symbol
+            ),
+        ),
+        true if !documents_returns && DocStyle::detect(docstring).is_some() => (
+            "missing_returns_doc",
+            format!(
+                "Function '{}' returns a value but has no Returns section",
+                symbol
+            ),
+        ),
+        _ => return None,
+    };
+
+    Some(DocIssue {
+        category: category.to_string(),
+        path: relative_path(path, root),
+        line: Some(line),
+        symbol: Some(symbol.to_string()),
+        detail,
+    })
+}
+
+/// Whether the function body starting at `start` contains `return <expr>`.
+///
+/// The body ends at the first statement indented at or below the `def` at
+/// `indent`. Returns inside nested functions and classes are ignored, and
+/// `return None` counts as a bare `return`.
+fn python_has_value_return(source_lines: &[&str], start: usize, indent: usize) -> bool {
+    let mut nested_indent: Option<usize> = None;
+
+    for line in source_lines.iter().skip(start) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let line_indent = indentation(line);
+        if line_indent <= indent {
+            break;
+        }
+        if nested_indent.is_some_and(|nested| line_indent > nested) {
+            continue;
+        }
+        nested_indent = None;
+
+        if parse_symbol(trimmed).is_some() {
+            nested_indent = Some(line_indent);
+            continue;
+        }
+
+        let Some(rest) = trimmed.strip_prefix("return") else {
+            continue;
+        };
+        if rest.starts_with(|ch: char| ch.is_alphanumeric() || ch == '_') {
+            continue;
+        }
+        let value = rest.trim();
+        if !value.is_empty() && !value.starts_with('#') && value != "None" {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Whether a docstring has a Google, Sphinx or NumPy returns section.
+fn has_returns_section(docstring: &str) -> bool {
+    let lines: Vec<&str> = docstring.lines().map(str::trim).collect();
+    lines.iter().any(|line| {
+        matches!(*line, "Returns:" | "Return:")
+            || line.starts_with(":returns:")
+            || line.starts_with(":return:")
+    }) || lines
+        .windows(2)
+        .any(|pair| pair[0] == "Returns" && is_underline(pair[1]))
+}
+
+/// Joins a possibly multi-line `def` into one string.
+///
+/// Returns the signature and the index of the line holding its closing `:`.
+fn collect_signature(lines: &[&str], start: usize) -> (String, usize) {
+    let mut signature = String::new();
+    let mut depth = 0i32;
+
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        if !signature.is_empty() {
+            signature.push(' ');
+        }
+        signature.push_str(line.trim());
+
+        for ch in line.chars() {
+            match ch {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if depth <= 0 && line.trim_end().ends_with(':') {
+            return (signature, index);
+        }
+    }
+
+    (signature, start)
+}
+
+/// Raw parameter declarations between the signature's parentheses.
+fn split_parameters(signature: &str) -> Vec<String> {
+    let Some(open) = signature.find('(') else {
+        return Vec::new();
+    };
+    let mut depth = 0i32;
+    let mut params = Vec::new();
+    let mut current = String::new();
+
+    for ch in signature[open + 1..].chars() {
+        match ch {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth == 0 => break,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                params.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    params.push(current);
+    params
+}
+
+/// Names of all parameters in order, without `self`, `cls` or `*` / `/` markers.
+fn parameter_names(signature: &str) -> Vec<String> {
+    split_parameters(signature)
+        .iter()
+        .filter_map(|param| {
+            let declaration = param.split(['=', ':']).next()?;
+            let name = declaration.trim().trim_start_matches('*');
+            let skipped = name.is_empty() || name == "/" || name == "self" || name == "cls";
+            (!skipped).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Names of parameters carrying a type annotation.
+fn annotated_parameters(signature: &str) -> Vec<String> {
+    split_parameters(signature)
+        .iter()
+        .filter_map(|param| {
+            let declaration = param.split('=').next()?;
+            let (name, annotation) = declaration.split_once(':')?;
+            let name = name.trim().trim_start_matches('*');
+            let skipped = name.is_empty() || name == "self" || name == "cls";
+            (!skipped && !annotation.trim().is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Parameter names whose type is documented in `docstring`.
+fn documented_types(docstring: &str, style: DocStyle) -> Vec<String> {
+    let lines: Vec<&str> = docstring.lines().collect();
+    match style {
+        DocStyle::Sphinx => lines
+            .iter()
+            .filter_map(|line| sphinx_typed_name(line.trim()))
+            .collect(),
+        DocStyle::Google => parameter_section_entries(&lines, style)
+            .into_iter()
+            .filter_map(|entry| {
+                let (name, rest) = entry.split_once('(')?;
+                let (ty, _) = rest.split_once(')')?;
+                (!ty.trim().is_empty()).then(|| name.trim().trim_start_matches('*').to_string())
+            })
+            .collect(),
+        DocStyle::NumPy => parameter_section_entries(&lines, style)
+            .into_iter()
+            .filter_map(|entry| {
+                let (names, ty) = entry.split_once(':')?;
+                (!ty.trim().is_empty()).then_some(names)
+            })
+            .flat_map(|names| names.split(','))
+            .map(|name| name.trim().trim_start_matches('*').to_string())
+            .collect(),
+    }
+}
+
+/// Parameter names documented in `docstring`, in the order they appear.
+fn documented_parameters(docstring: &str, style: DocStyle) -> Vec<String> {
+    let lines: Vec<&str> = docstring.lines().collect();
+    let names: Vec<&str> = match style {
+        DocStyle::Sphinx => lines
+            .iter()
+            .filter_map(|line| {
+                let rest = line.trim().strip_prefix(":param ")?;
+                let (field, _) = rest.split_once(':')?;
+                field.split_whitespace().next_back()
+            })
+            .collect(),
+        DocStyle::Google => parameter_section_entries(&lines, style)
+            .into_iter()
+            .filter_map(|entry| entry.split(['(', ':']).next())
+            .collect(),
+        DocStyle::NumPy => parameter_section_entries(&lines, style)
+            .into_iter()
+            .flat_map(|entry| entry.split(':').next().unwrap_or(entry).split(','))
+            .collect(),
+    };
+
+    let mut documented: Vec<String> = Vec::new();
+    for name in names {
+        let name = name.trim().trim_start_matches('*');
+        if !name.is_empty() && !documented.iter().any(|existing| existing == name) {
+            documented.push(name.to_string());
+        }
+    }
+    documented
+}
+
+/// Entry lines of the Google `Args:` or NumPy `Parameters` section.
+///
+/// Sphinx docstrings have no section and yield nothing.
+fn parameter_section_entries<'a>(lines: &'a [&'a str], style: DocStyle) -> Vec<&'a str> {
+    match style {
+        DocStyle::Sphinx => Vec::new(),
+        DocStyle::Google => {
+            let Some(header) = lines
+                .iter()
+                .position(|line| matches!(line.trim(), "Args:" | "Arguments:"))
+            else {
+                return Vec::new();
+            };
+            section_entries(&lines[header + 1..]).collect()
+        }
+        DocStyle::NumPy => {
+            let trimmed: Vec<&str> = lines.iter().map(|line| line.trim()).collect();
+            let Some(start) = numpy_parameters_start(&trimmed) else {
+                return Vec::new();
+            };
+            let end = (start..trimmed.len())
+                .find(|&index| {
+                    trimmed
+                        .get(index + 1)
+                        .is_some_and(|next| is_underline(next))
+                })
+                .unwrap_or(trimmed.len());
+            section_entries(&lines[start..end]).collect()
+        }
+    }
+}
+
+/// Extracts the parameter name from `:type x:` or `:param int x:` fields.
+fn sphinx_typed_name(line: &str) -> Option<String> {
+    if let Some(rest) = line.strip_prefix(":type ") {
+        let (name, _) = rest.split_once(':')?;
+        return Some(name.trim().trim_start_matches('*').to_string());
+    }
+
+    let rest = line.strip_prefix(":param ")?;
+    let (field, _) = rest.split_once(':')?;
+    let mut words = field.split_whitespace();
+    let name = words.next_back()?;
+    words
+        .next()
+        .map(|_| name.trim_start_matches('*').to_string())
+}
+
+/// Index of the line after a NumPy `Parameters` header and its dashed underline.
+fn numpy_parameters_start(lines: &[&str]) -> Option<usize> {
+    lines
+        .windows(2)
+        .position(|pair| pair[0] == "Parameters" && is_underline(pair[1]))
+        .map(|index| index + 2)
+}
+
+/// Whether a trimmed line is a NumPy section underline (`----`).
+fn is_underline(line: &str) -> bool {
+    !line.is_empty() && line.chars().all(|ch| ch == '-')
+}
+
+/// Trimmed entry lines of a docstring section body.
+///
+/// Entries share the indentation of the first non-blank line; deeper lines
+/// are descriptions and the section ends at the first shallower line.
+fn section_entries<'a>(body: &'a [&'a str]) -> impl Iterator<Item = &'a str> {
+    let entry_indent = body
+        .iter()
+        .find(|line| !line.trim().is_empty())
+        .map_or(0, |line| indentation(line));
+
+    body.iter()
+        .filter(|line| !line.trim().is_empty())
+        .take_while(move |line| indentation(line) >= entry_indent)
+        .filter(move |line| indentation(line) == entry_indent)
+        .map(|line| line.trim())
 }
 
 /// Creates a documentation issue for an undocumented Python symbol.
@@ -86,7 +650,7 @@ fn build_issue(
 }
 
 /// Returns the number of leading whitespace characters in a line.
-fn indentation(line: &str) -> usize {
+pub(crate) fn indentation(line: &str) -> usize {
     line.chars()
         .take_while(|ch| ch.is_ascii_whitespace())
         .count()
@@ -121,7 +685,11 @@ fn extract_symbol_name(
 /// Searches for a docstring following a symbol definition.
 ///
 /// Returns the docstring content and the line index where it ends.
-fn find_docstring(lines: &[&str], mut index: usize, indent: usize) -> Option<(String, usize)> {
+pub(crate) fn find_docstring(
+    lines: &[&str],
+    mut index: usize,
+    indent: usize,
+) -> Option<(String, usize)> {
     while index < lines.len() {
         let line = lines[index];
         let trimmed = line.trim_start();
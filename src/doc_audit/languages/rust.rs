@@ -1,22 +1,33 @@
 //! Rust rustdoc scanner for doc audit.
 
-use super::super::{extract_comment_text, is_incomplete_doc, relative_path, DocIssue};
+use super::super::{
+    extract_comment_text, is_incomplete_doc, normalize_line_endings, relative_path, DocIssue,
+    FileScan, SymbolCount,
+};
+use std::collections::BTreeSet;
 use std::path::Path;
 
 /// Scans Rust source code for missing or incomplete rustdoc documentation.
 ///
 /// Detects undocumented public functions, structs, enums, traits, impl blocks, and modules.
+/// Also flags `#[deprecated]` attributes without a note and `#[allow(...)]` attributes
+/// without a comment explaining why the lint is silenced.
 /// Test functions and test modules are automatically excluded from the audit.
 /// Nested functions (functions defined inside other functions) are also excluded.
-pub fn scan_rust(source: &str, path: &Path, root: &Path) -> Vec<DocIssue> {
-    let mut state = ScanState::new(source);
+pub fn scan_rust(source: &str, path: &Path, root: &Path) -> FileScan {
+    let source = normalize_line_endings(source);
+    let mut state = ScanState::new(&source);
     let mut issues = Vec::new();
 
     while state.index < state.lines.len() {
         state.process_line(&mut issues, path, root);
     }
 
-    issues
+    FileScan {
+        issues,
+        symbols: state.symbols,
+        public_names: state.public_names,
+    }
 }
 
 /// State for scanning Rust source files.
@@ -26,6 +37,8 @@ struct ScanState<'a> {
     index: usize,
     brace_depth: isize,
     test_module_depth: Option<isize>,
+    symbols: SymbolCount,
+    public_names: BTreeSet<String>,
 }
 
 impl<'a> ScanState<'a> {
@@ -36,6 +49,8 @@ impl<'a> ScanState<'a> {
             index: 0,
             brace_depth: 0,
             test_module_depth: None,
+            symbols: SymbolCount::default(),
+            public_names: BTreeSet::new(),
         }
     }
 
@@ -51,6 +66,10 @@ impl<'a> ScanState<'a> {
 
         self.update_test_module_tracking();
 
+        if trimmed.starts_with("#[") && self.test_module_depth.is_none() {
+            check_attribute_hygiene(&self.lines, self.index, issues, path, root);
+        }
+
         let line_to_check = if trimmed.starts_with("#[") {
             handle_attribute_line(trimmed, &mut self.pending_attrs)
         } else {
@@ -99,6 +118,8 @@ impl<'a> ScanState<'a> {
                 has_test_attr,
                 &mut self.pending_attrs,
                 issues,
+                &mut self.symbols,
+                &mut self.public_names,
                 path,
                 root,
             ) {
@@ -228,6 +249,104 @@ fn handle_attribute_line(trimmed: &str, pending_attrs: &mut Vec<String>) -> Opti
     None
 }
 
+/// Flag `#[deprecated]` without a note and `#[allow(...)]` without a rationale comment.
+fn check_attribute_hygiene(
+    lines: &[&str],
+    index: usize,
+    issues: &mut Vec<DocIssue>,
+    path: &Path,
+    root: &Path,
+) {
+    let attribute = collect_attribute(lines, index);
+    let Some(body) = attribute
+        .strip_prefix("#[")
+        .map(|rest| rest.trim_end().trim_end_matches(']').trim())
+    else {
+        return;
+    };
+
+    if body.starts_with("deprecated") && !deprecation_has_note(body) {
+        let symbol = next_item_name(lines, index);
+        let target = symbol
+            .as_deref()
+            .map(|name| format!(" on '{}'", name))
+            .unwrap_or_default();
+        push_issue(
+            issues,
+            path,
+            root,
+            index + 1,
+            symbol.as_deref(),
+            "incomplete_deprecation",
+            format!(
+                "#[deprecated]{} lacks a note explaining the replacement",
+                target
+            ),
+        );
+    } else if body.starts_with("allow(") && !allow_has_rationale(lines, index, body) {
+        let lints = body
+            .trim_start_matches("allow(")
+            .trim_end_matches(')')
+            .trim();
+        push_issue(
+            issues,
+            path,
+            root,
+            index + 1,
+            next_item_name(lines, index).as_deref(),
+            "unexplained_allow",
+            format!("#[allow({})] lacks a comment explaining why", lints),
+        );
+    }
+}
+
+/// Join an attribute that may span several lines into a single string.
+fn collect_attribute(lines: &[&str], index: usize) -> String {
+    let mut attribute = String::new();
+    let mut depth: isize = 0;
+    for line in &lines[index..] {
+        let code = line.split("//").next().unwrap_or(line).trim();
+        if !attribute.is_empty() {
+            attribute.push(' ');
+        }
+        attribute.push_str(code);
+        depth += code.matches('[').count() as isize - code.matches(']').count() as isize;
+        if depth <= 0 {
+            break;
+        }
+    }
+    attribute
+}
+
+/// Returns true when a `deprecated` attribute body carries a message.
+fn deprecation_has_note(body: &str) -> bool {
+    let args = body.trim_start_matches("deprecated").trim_start();
+    args.starts_with('=') || find_keyword(args, "note").is_some()
+}
+
+/// Returns true when an `allow` attribute has a `reason`, trailing comment, or comment above.
+fn allow_has_rationale(lines: &[&str], index: usize, body: &str) -> bool {
+    if find_keyword(body, "reason").is_some() || lines[index].contains("//") {
+        return true;
+    }
+
+    lines[..index]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .find(|line| !line.starts_with("#["))
+        .is_some_and(|line| line.starts_with("//") && !line.starts_with("///"))
+}
+
+/// Name of the function or type following the attributes at `index`, if any.
+fn next_item_name(lines: &[&str], index: usize) -> Option<String> {
+    let item = lines[index..]
+        .iter()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//"))?;
+    detect_function_name(item).or_else(|| detect_type(item).map(|(_, name)| name))
+}
+
 /// Check if pending attributes include test-related markers
 fn has_test_attribute(pending_attrs: &[String]) -> bool {
     pending_attrs
@@ -235,7 +354,9 @@ fn has_test_attribute(pending_attrs: &[String]) -> bool {
         .any(|attr| attr.contains("cfg(test)") || attr.contains("test"))
 }
 
-/// Process an item line and return the new index if handled
+/// Process an item line and return the new index if handled.
+///
+/// Functions and types declared plainly `pub` are added to `public_names`.
 fn process_item_line(
     trimmed: &str,
     lines: &[&str],
@@ -243,6 +364,8 @@ fn process_item_line(
     has_test_attr: bool,
     pending_attrs: &mut Vec<String>,
     issues: &mut Vec<DocIssue>,
+    symbols: &mut SymbolCount,
+    public_names: &mut BTreeSet<String>,
     path: &Path,
     root: &Path,
 ) -> Option<usize> {
@@ -254,6 +377,7 @@ fn process_item_line(
             index,
             has_test_attr,
             issues,
+            symbols,
             path,
             root,
         ));
@@ -262,7 +386,7 @@ fn process_item_line(
     if let Some(name) = detect_function_name(trimmed) {
         pending_attrs.clear();
         if !has_test_attr {
-            check_item_docs(
+            let documented = check_item_docs(
                 lines,
                 index,
                 &name,
@@ -272,6 +396,10 @@ fn process_item_line(
                 path,
                 root,
             );
+            symbols.record(documented);
+            if trimmed.starts_with("pub ") {
+                public_names.insert(name);
+            }
         }
         return Some(index + 1);
     }
@@ -279,7 +407,7 @@ fn process_item_line(
     if let Some((kind, name)) = detect_type(trimmed) {
         pending_attrs.clear();
         if !has_test_attr {
-            check_item_docs(
+            let documented = check_item_docs(
                 lines,
                 index,
                 &name,
@@ -289,6 +417,10 @@ fn process_item_line(
                 path,
                 root,
             );
+            symbols.record(documented);
+            if trimmed.starts_with("pub ") {
+                public_names.insert(name);
+            }
         }
         return Some(index + 1);
     }
@@ -296,7 +428,8 @@ fn process_item_line(
     if let Some(target) = detect_impl(trimmed) {
         pending_attrs.clear();
         if !has_test_attr {
-            check_impl_docs(lines, index, &target, issues, path, root);
+            let documented = check_impl_docs(lines, index, &target, issues, path, root);
+            symbols.record(documented);
         }
         return Some(index + 1);
     }
@@ -311,6 +444,7 @@ fn handle_module_item(
     index: usize,
     has_test_attr: bool,
     issues: &mut Vec<DocIssue>,
+    symbols: &mut SymbolCount,
     path: &Path,
     root: &Path,
 ) -> usize {
@@ -323,7 +457,9 @@ fn handle_module_item(
         return index + 1;
     }
     if let Some(name) = extract_identifier(trimmed, "mod") {
-        if is_doc_missing(lines, index) {
+        let missing = is_doc_missing(lines, index);
+        symbols.record(!missing);
+        if missing {
             push_issue(
                 issues,
                 path,
@@ -338,7 +474,9 @@ fn handle_module_item(
     index + 1
 }
 
-/// Check docs for a named item (function, struct, enum, trait)
+/// Check docs for a named item (function, struct, enum, trait).
+///
+/// Returns true when the item carries complete rustdoc.
 fn check_item_docs(
     lines: &[&str],
     index: usize,
@@ -348,33 +486,17 @@ fn check_item_docs(
     issues: &mut Vec<DocIssue>,
     path: &Path,
     root: &Path,
-) {
-    if let Some(doc) = extract_comment_text(lines, index) {
-        if is_incomplete_doc(&doc) {
-            push_issue(
-                issues,
-                path,
-                root,
-                index + 1,
-                Some(name),
-                category,
-                format!("{} '{}' has incomplete rustdoc", kind, name),
-            );
-        }
-    } else {
-        push_issue(
-            issues,
-            path,
-            root,
-            index + 1,
-            Some(name),
-            category,
-            format!("{} '{}' lacks rustdoc", kind, name),
-        );
-    }
+) -> bool {
+    let detail = match extract_comment_text(lines, index) {
+        Some(doc) if !is_incomplete_doc(&doc) => return true,
+        Some(_) => format!("{} '{}' has incomplete rustdoc", kind, name),
+        None => format!("{} '{}' lacks rustdoc", kind, name),
+    };
+    push_issue(issues, path, root, index + 1, Some(name), category, detail);
+    false
 }
 
-/// Check docs for impl blocks
+/// Check docs for impl blocks, returning true when the block is documented.
 fn check_impl_docs(
     lines: &[&str],
     index: usize,
@@ -382,30 +504,22 @@ fn check_impl_docs(
     issues: &mut Vec<DocIssue>,
     path: &Path,
     root: &Path,
-) {
-    if let Some(doc) = extract_comment_text(lines, index) {
-        if is_incomplete_doc(&doc) {
-            push_issue(
-                issues,
-                path,
-                root,
-                index + 1,
-                Some(target),
-                "undocumented_rust_impl",
-                format!("impl block for '{}' has incomplete docs", target),
-            );
-        }
-    } else {
-        push_issue(
-            issues,
-            path,
-            root,
-            index + 1,
-            Some(target),
-            "undocumented_rust_impl",
-            format!("impl block for '{}' lacks overview docs", target),
-        );
-    }
+) -> bool {
+    let detail = match extract_comment_text(lines, index) {
+        Some(doc) if !is_incomplete_doc(&doc) => return true,
+        Some(_) => format!("impl block for '{}' has incomplete docs", target),
+        None => format!("impl block for '{}' lacks overview docs", target),
+    };
+    push_issue(
+        issues,
+        path,
+        root,
+        index + 1,
+        Some(target),
+        "undocumented_rust_impl",
+        detail,
+    );
+    false
 }
 
 /// Skips over a brace-delimited block and returns the ending line index.
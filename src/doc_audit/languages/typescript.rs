@@ -1,59 +1,65 @@
 //! TypeScript/JavaScript JSDoc scanner for doc audit.
 
-use super::super::{extract_comment_text, is_incomplete_doc, relative_path, DocIssue};
+use super::super::{
+    extract_comment_text, is_incomplete_doc, normalize_line_endings, relative_path, DocIssue,
+    FileScan, SymbolCount,
+};
+use std::collections::BTreeSet;
 use std::path::Path;
 
 /// Scans TypeScript/JavaScript source code for missing or incomplete JSDoc comments.
 ///
-/// Detects undocumented functions, classes, and arrow function exports.
-pub fn scan_typescript(source: &str, path: &Path, root: &Path) -> Vec<DocIssue> {
+/// Detects undocumented functions, classes, arrow function exports, interfaces,
+/// and type aliases.
+pub fn scan_typescript(source: &str, path: &Path, root: &Path) -> FileScan {
+    let source = normalize_line_endings(source);
     let lines: Vec<&str> = source.lines().collect();
     let mut issues = Vec::new();
+    let mut symbols = SymbolCount::default();
+    let mut public_names = BTreeSet::new();
 
     for index in 0..lines.len() {
-        let line = lines[index];
-        let trimmed = line.trim_start();
+        let trimmed = lines[index].trim_start();
 
-        if let Some(name) = detect_function(trimmed) {
-            push_issue_if_needed(
-                &lines,
-                index,
-                path,
-                root,
-                "undocumented_ts_function",
-                &name,
-                format!("Function '{}' missing doc comment", name),
-                format!("Function '{}' has incomplete doc comment", name),
-                &mut issues,
-            );
-        } else if let Some(name) = detect_class(trimmed) {
-            push_issue_if_needed(
-                &lines,
-                index,
-                path,
-                root,
-                "undocumented_ts_class",
-                &name,
-                format!("Class '{}' missing doc comment", name),
-                format!("Class '{}' has incomplete doc comment", name),
-                &mut issues,
-            );
-        } else if let Some(name) = detect_arrow_function(trimmed) {
+        let detected = detect_function(trimmed)
+            .map(|name| ("undocumented_ts_function", "Function", name))
+            .or_else(|| detect_class(trimmed).map(|name| ("undocumented_ts_class", "Class", name)))
+            .or_else(|| {
+                detect_arrow_function(trimmed)
+                    .map(|name| ("undocumented_ts_arrow", "Function", name))
+            })
+            .or_else(|| {
+                detect_interface(trimmed)
+                    .map(|name| ("undocumented_ts_interface", "Interface", name))
+            })
+            .or_else(|| {
+                detect_type_alias(trimmed).map(|name| ("undocumented_ts_type", "Type alias", name))
+            });
+
+        if let Some((category, kind, name)) = detected {
+            if trimmed.starts_with("export ") {
+                public_names.insert(name.clone());
+            }
             push_issue_if_needed(
                 &lines,
                 index,
                 path,
                 root,
-                "undocumented_ts_arrow",
+                category,
                 &name,
-                format!("Function '{}' missing doc comment", name),
-                format!("Function '{}' has incomplete doc comment", name),
+                format!("{} '{}' missing doc comment", kind, name),
+                format!("{} '{}' has incomplete doc comment", kind, name),
                 &mut issues,
+                &mut symbols,
             );
         }
     }
 
-    issues
+    FileScan {
+        issues,
+        symbols,
+        public_names,
+    }
 }
 
 /// Checks for documentation and pushes an issue if missing or incomplete.
@@ -67,8 +73,11 @@ fn push_issue_if_needed(
     missing_detail: String,
     incomplete_detail: String,
     issues: &mut Vec<DocIssue>,
+    symbols: &mut SymbolCount,
 ) {
-    match extract_comment_text(lines, index) {
+    let doc = extract_comment_text(lines, index);
+    symbols.record(doc.as_deref().is_some_and(|doc| !is_incomplete_doc(doc)));
+    match doc {
         Some(doc) if !is_incomplete_doc(&doc) => {}
         Some(_) => issues.push(build_issue(
             path,
@@ -147,3 +156,47 @@ fn detect_arrow_function(line: &str) -> Option<String> {
         None
     }
 }
+
+/// Strips leading declaration modifiers (`export`, `default`, `declare`) from a line.
+fn strip_declaration_modifiers(line: &str) -> &str {
+    let mut rest = line;
+    loop {
+        let next = ["export ", "default ", "declare "]
+            .iter()
+            .find_map(|modifier| rest.strip_prefix(modifier));
+        match next {
+            Some(stripped) => rest = stripped.trim_start(),
+            None => return rest,
+        }
+    }
+}
+
+/// Extracts the declared identifier, stopping at generics, braces, or assignment.
+fn declared_name(rest: &str) -> Option<String> {
+    let name: String = rest
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+        .collect();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// Detects an interface declaration and returns the interface name.
+fn detect_interface(line: &str) -> Option<String> {
+    let rest = strip_declaration_modifiers(line).strip_prefix("interface ")?;
+    declared_name(rest)
+}
+
+/// Detects a type alias declaration and returns the alias name.
+fn detect_type_alias(line: &str) -> Option<String> {
+    let rest = strip_declaration_modifiers(line).strip_prefix("type ")?;
+    if !rest.contains('=') {
+        // `export type { Foo } from "./foo"` re-exports are not declarations
+        return None;
+    }
+    declared_name(rest)
+}
@@ -2,21 +2,40 @@
 //!
 //! Scans codebases for missing documentation (docstrings in Python, rustdoc in Rust,
 //! JSDoc in TypeScript/JavaScript), missing READMEs in complex directories, and
-//! stale READMEs that haven't been updated alongside the code.
+//! stale READMEs that haven't been updated alongside the code, and long-lived
+//! lint suppressions without an explanation. Documentation gaps can be patched
+//! with TODO stubs via [`apply_fixes`].
 
+mod fix;
 mod git_utils;
 mod languages;
-
-use git_utils::GitHelper;
+mod semantic;
+mod suppressions;
+
+pub use fix::{apply_fixes, AppliedFix};
+pub use git_utils::{CommitInfo, GitHelper};
+pub use languages::{
+    check_python_module_docstring, check_python_param_name_consistency,
+    check_python_type_annotation_coverage, DocStyle,
+};
+pub use semantic::SEMANTIC_STALENESS_THRESHOLD;
+pub use suppressions::scan_inline_suppressions;
+
+use git2::Oid;
 use languages::{scan_python, scan_rust, scan_typescript};
+use semantic::{jaccard_similarity, public_symbols};
 
 use anyhow::{Context, Result};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 /// Default complexity threshold for requiring READMEs.
 pub const DEFAULT_COMPLEXITY_THRESHOLD: usize = 8;
@@ -24,6 +43,22 @@ pub const DEFAULT_COMPLEXITY_THRESHOLD: usize = 8;
 /// Default number of commits before a README is considered stale.
 pub const DEFAULT_MAX_README_COMMITS: usize = 10;
 
+/// Seconds in a day, used to express README age in days.
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// How README staleness is decided.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StalenessMode {
+    /// Stale once more than `max_readme_commits` commits touched the directory.
+    #[default]
+    CommitCount,
+    /// Stale once the directory's public symbols drift from those present at
+    /// the README's last update (Jaccard similarity below
+    /// [`SEMANTIC_STALENESS_THRESHOLD`]).
+    SemanticDiff,
+}
+
 static DEFAULT_IGNORED_DIR_NAMES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     [
         ".git",
@@ -65,15 +100,6 @@ static DEFAULT_IGNORED_DIR_NAMES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
 static DEFAULT_IGNORED_SUFFIXES: Lazy<HashSet<&'static str>> =
     Lazy::new(|| [".lock", ".min.js", ".min.css"].into_iter().collect());
 
-static README_CANDIDATES: [&str; 6] = [
-    "README",
-    "README.md",
-    "README.rst",
-    "README.txt",
-    "readme.md",
-    "Readme.md",
-];
-
 static TODO_MARKERS: [&str; 3] = ["TODO", "FIXME", "TBD"];
 
 static DEFAULT_IGNORED_GLOBS: Lazy<Vec<&'static str>> = Lazy::new(|| {
@@ -129,6 +155,13 @@ pub struct DocAuditConfig {
     pub complexity_threshold: usize,
     /// Commits before README is stale.
     pub max_readme_commits: usize,
+    /// Strategy used to flag stale READMEs.
+    #[serde(default)]
+    pub staleness_mode: StalenessMode,
+    /// Days since the README update after which later directory changes
+    /// make it stale, in addition to `staleness_mode`.
+    #[serde(default)]
+    pub stale_after_days: Option<u64>,
     /// Directories to skip.
     pub ignore_dirs: HashSet<String>,
     /// File suffixes to skip.
@@ -145,6 +178,8 @@ impl DocAuditConfig {
             root,
             complexity_threshold: DEFAULT_COMPLEXITY_THRESHOLD,
             max_readme_commits: DEFAULT_MAX_README_COMMITS,
+            staleness_mode: StalenessMode::default(),
+            stale_after_days: None,
             ignore_dirs: DEFAULT_IGNORED_DIR_NAMES
                 .iter()
                 .map(|item| item.to_string())
@@ -159,6 +194,12 @@ impl DocAuditConfig {
                 .collect(),
         }
     }
+
+    /// Also flag READMEs older than `days` whose directory changed since.
+    pub fn with_git_age_window(mut self, days: u64) -> Self {
+        self.stale_after_days = Some(days);
+        self
+    }
 }
 
 /// Output format for audit results.
@@ -176,6 +217,7 @@ pub struct DocIssue {
     /// Issue category (e.g., "undocumented_python", "missing_readme").
     pub category: String,
     /// Path to the file or directory.
+    #[serde(serialize_with = "serialize_portable_path")]
     pub path: PathBuf,
     /// Line number if applicable.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -187,6 +229,69 @@ pub struct DocIssue {
     pub detail: String,
 }
 
+/// [`Display`](fmt::Display) implementation for [`DocIssue`].
+impl fmt::Display for DocIssue {
+    /// Formats the issue as `<path>:<line> [<category>] <detail>`, omitting an unknown line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        write!(f, " [{}] {}", self.category, self.detail)
+    }
+}
+
+/// Documentable symbols seen while scanning source files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SymbolCount {
+    /// Symbols checked for documentation.
+    pub total: usize,
+    /// Symbols carrying complete documentation.
+    pub documented: usize,
+}
+
+/// Accumulation methods for [`SymbolCount`].
+impl SymbolCount {
+    /// Counts one symbol, documented or not.
+    pub fn record(&mut self, documented: bool) {
+        self.total += 1;
+        if documented {
+            self.documented += 1;
+        }
+    }
+
+    /// Adds another count to this one.
+    pub fn merge(&mut self, other: SymbolCount) {
+        self.total += other.total;
+        self.documented += other.documented;
+    }
+}
+
+/// Issues and symbol counts produced by a language scanner for one file.
+#[derive(Debug, Default)]
+pub struct FileScan {
+    /// Documentation issues found in the file.
+    pub issues: Vec<DocIssue>,
+    /// Symbols the scanner checked.
+    pub symbols: SymbolCount,
+    /// Names of the checked symbols that belong to the file's public API.
+    pub public_names: BTreeSet<String>,
+}
+
+/// Documentation coverage of the symbols declared directly in one directory.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DocCoverageStats {
+    /// Directory relative to the audit root (`.` for the root itself).
+    #[serde(serialize_with = "serialize_portable_path")]
+    pub directory: PathBuf,
+    /// Symbols checked for documentation.
+    pub total_symbols: usize,
+    /// Symbols carrying complete documentation.
+    pub documented_symbols: usize,
+    /// Documented share of symbols, from 0 to 100.
+    pub coverage_pct: f64,
+}
+
 /// Complete audit results.
 #[derive(Debug, Serialize)]
 pub struct AuditResult {
@@ -196,6 +301,11 @@ pub struct AuditResult {
     pub missing_readmes: Vec<DocIssue>,
     /// READMEs not updated with recent changes.
     pub stale_readmes: Vec<DocIssue>,
+    /// Symbols scanned per source file, keyed by the path that was scanned.
+    #[serde(skip)]
+    pub symbol_counts: BTreeMap<PathBuf, SymbolCount>,
+    /// Per-directory documentation coverage, ordered by directory.
+    pub coverage: Vec<DocCoverageStats>,
 }
 
 /// Query methods for [`AuditResult`].
@@ -208,6 +318,61 @@ impl AuditResult {
     }
 }
 
+/// [`Display`](fmt::Display) implementation for [`AuditResult`].
+impl fmt::Display for AuditResult {
+    /// Formats a one-line summary of issue counts per kind.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn plural(count: usize, singular: &str, plural: &str) -> String {
+            format!("{} {}", count, if count == 1 { singular } else { plural })
+        }
+
+        let gaps = self.documentation_issues.len();
+        let missing = self.missing_readmes.len();
+        let stale = self.stale_readmes.len();
+        write!(
+            f,
+            "valknut doc-audit: {}, {}, {} ({} total)",
+            plural(gaps, "doc gap", "doc gaps"),
+            plural(missing, "missing README", "missing READMEs"),
+            plural(stale, "stale README", "stale READMEs"),
+            gaps + missing + stale
+        )
+    }
+}
+
+/// Markup format of a README file, in the order READMEs are preferred when a
+/// directory has several.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReadmeFormat {
+    /// Markdown (`.md`, `.markdown`).
+    Markdown,
+    /// reStructuredText (`.rst`).
+    Rst,
+    /// Plain text (`.txt`) or extensionless README.
+    PlainText,
+}
+
+/// Parsing methods for [`ReadmeFormat`].
+impl ReadmeFormat {
+    /// Format for a README file extension, or `None` if it is not a README format.
+    fn from_extension(extension: Option<&str>) -> Option<Self> {
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("md" | "markdown") => Some(ReadmeFormat::Markdown),
+            Some("rst") => Some(ReadmeFormat::Rst),
+            Some("txt") | None => Some(ReadmeFormat::PlainText),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Detects the markup format of a README from its file extension.
+///
+/// Unrecognised extensions are treated as plain text.
+pub fn detect_readme_format(path: &Path) -> ReadmeFormat {
+    ReadmeFormat::from_extension(path.extension().and_then(|ext| ext.to_str()))
+        .unwrap_or(ReadmeFormat::PlainText)
+}
+
 /// Information about a directory's contents for complexity calculation.
 struct DirectoryInfo {
     /// Files directly in this directory.
@@ -220,17 +385,85 @@ struct DirectoryInfo {
 pub fn run_audit(config: &DocAuditConfig) -> Result<AuditResult> {
     let globset = build_ignore_globset(&config.ignore_globs)?;
     let (dir_info, files) = walk_repository(config, &globset)?;
-    let documentation_issues = scan_documentation(&files, config, &globset);
+    let (mut documentation_issues, symbol_counts) = scan_documentation(&files, config, &globset);
     let complexity_map = compute_complexities(&dir_info);
     let (missing_readmes, readme_index) = detect_missing_readmes(&complexity_map, config);
     let git_helper = GitHelper::new(&config.root);
     let stale_readmes = detect_stale_readmes(&git_helper, &readme_index, config);
+    documentation_issues.extend(detect_unexplained_suppressions(
+        &files,
+        config,
+        &globset,
+        &git_helper,
+    ));
 
-    Ok(AuditResult {
+    let mut result = AuditResult {
         documentation_issues,
         missing_readmes,
         stale_readmes,
-    })
+        symbol_counts,
+        coverage: Vec::new(),
+    };
+    result.coverage = compute_coverage_stats(&result, config);
+    // Directory walks and readme indexes iterate in unspecified order; sort so
+    // identical trees always produce identical reports.
+    sort_issues(&mut result.documentation_issues);
+    sort_issues(&mut result.missing_readmes);
+    sort_issues(&mut result.stale_readmes);
+    Ok(result)
+}
+
+/// Aggregates scanned symbols by the directory holding each file.
+///
+/// Directories are reported relative to the audit root in path order;
+/// directories without any documentable symbols are left out, so CI checks can
+/// compare every `coverage_pct` against a threshold without special cases.
+pub fn compute_coverage_stats(
+    result: &AuditResult,
+    config: &DocAuditConfig,
+) -> Vec<DocCoverageStats> {
+    let mut by_directory: BTreeMap<PathBuf, SymbolCount> = BTreeMap::new();
+    for (file, count) in &result.symbol_counts {
+        let directory = file.parent().unwrap_or(Path::new(""));
+        let mut directory = relative_path(directory, &config.root);
+        if directory.as_os_str().is_empty() {
+            directory = PathBuf::from(".");
+        }
+        by_directory.entry(directory).or_default().merge(*count);
+    }
+
+    by_directory
+        .into_iter()
+        .filter(|(_, count)| count.total > 0)
+        .map(|(directory, count)| DocCoverageStats {
+            directory,
+            total_symbols: count.total,
+            documented_symbols: count.documented,
+            coverage_pct: count.documented as f64 / count.total as f64 * 100.0,
+        })
+        .collect()
+}
+
+/// Orders issues by path, then line, symbol and category.
+fn sort_issues(issues: &mut [DocIssue]) {
+    issues.sort_by(|a, b| {
+        a.path
+            .cmp(&b.path)
+            .then_with(|| a.line.cmp(&b.line))
+            .then_with(|| a.symbol.cmp(&b.symbol))
+            .then_with(|| a.category.cmp(&b.category))
+    });
+}
+
+/// Serializes a path with forward slashes so JSON output is identical across platforms.
+fn serialize_portable_path<S>(path: &Path, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let rendered = path
+        .to_string_lossy()
+        .replace(std::path::MAIN_SEPARATOR, "/");
+    serializer.serialize_str(&rendered)
 }
 
 /// Render audit results as plain text.
@@ -282,6 +515,21 @@ pub fn render_text(result: &AuditResult) -> String {
         &mut output,
     );
 
+    output.push_str("Documentation Coverage\n----------------------\n");
+    if result.coverage.is_empty() {
+        output.push_str("  None\n");
+    }
+    for stats in &result.coverage {
+        output.push_str(&format!(
+            "  - {} - {:.1}% ({}/{} symbols documented)\n",
+            stats.directory.display(),
+            stats.coverage_pct,
+            stats.documented_symbols,
+            stats.total_symbols
+        ));
+    }
+    output.push('\n');
+
     let total = result.documentation_issues.len()
         + result.missing_readmes.len()
         + result.stale_readmes.len();
@@ -298,6 +546,16 @@ pub fn render_json(result: &AuditResult) -> Result<String> {
     serde_json::to_string_pretty(result).context("Failed to serialize audit results to JSON")
 }
 
+/// Stream audit results as JSON into `writer` without buffering the whole
+/// document; wrap unbuffered writers such as files in a `BufWriter`.
+pub fn render_json_to_writer<W: std::io::Write>(
+    result: &AuditResult,
+    writer: &mut W,
+) -> Result<()> {
+    serde_json::to_writer_pretty(writer, result)
+        .context("Failed to serialize audit results to JSON")
+}
+
 /// Builds a GlobSet from the ignore patterns for efficient matching.
 fn build_ignore_globset(patterns: &[String]) -> Result<GlobSet> {
     let mut builder = GlobSetBuilder::new();
@@ -376,14 +634,21 @@ fn should_ignore_dir(path: &Path, config: &DocAuditConfig, globset: &GlobSet) ->
         .unwrap_or(false)
 }
 
-/// Scan a file and collect documentation issues using the provided scanner function.
-fn scan_file_with<F>(file_path: &Path, root: &Path, scanner: F, issues: &mut Vec<DocIssue>)
-where
-    F: FnOnce(&str, &Path, &Path) -> Vec<DocIssue>,
+/// Scan a file, collecting its documentation issues and symbol count.
+fn scan_file_with<F>(
+    file_path: &Path,
+    root: &Path,
+    scanner: F,
+    issues: &mut Vec<DocIssue>,
+    symbol_counts: &mut BTreeMap<PathBuf, SymbolCount>,
+) where
+    F: FnOnce(&str, &Path, &Path) -> FileScan,
 {
     match fs::read_to_string(file_path) {
         Ok(contents) => {
-            issues.extend(scanner(&contents, file_path, root));
+            let scan = scanner(&contents, file_path, root);
+            issues.extend(scan.issues);
+            symbol_counts.insert(file_path.to_path_buf(), scan.symbols);
         }
         Err(err) => {
             issues.push(DocIssue {
@@ -398,12 +663,15 @@ where
 }
 
 /// Scans all files for documentation issues based on file extension.
+///
+/// Also returns the number of documentable symbols seen in each scanned file.
 fn scan_documentation(
     files: &[PathBuf],
     config: &DocAuditConfig,
     globset: &GlobSet,
-) -> Vec<DocIssue> {
+) -> (Vec<DocIssue>, BTreeMap<PathBuf, SymbolCount>) {
     let mut issues = Vec::new();
+    let mut symbol_counts = BTreeMap::new();
 
     for file_path in files {
         if should_ignore_file(file_path, config, globset) {
@@ -415,17 +683,33 @@ fn scan_documentation(
             .and_then(|e| e.to_str())
             .map(|e| e.to_ascii_lowercase());
 
-        match ext.as_deref() {
-            Some("py") => scan_file_with(file_path, &config.root, scan_python, &mut issues),
-            Some("rs") => scan_file_with(file_path, &config.root, scan_rust, &mut issues),
-            Some("ts" | "tsx" | "js" | "jsx") => {
-                scan_file_with(file_path, &config.root, scan_typescript, &mut issues)
-            }
-            _ => {}
-        }
+        let scanner: fn(&str, &Path, &Path) -> FileScan = match ext.as_deref() {
+            Some("py") if is_package_init(file_path) => |source, path, root| {
+                let mut scan = scan_python(source, path, root);
+                scan.issues
+                    .extend(check_python_module_docstring(source, path, root));
+                scan
+            },
+            Some("py" | "pyi") => scan_python,
+            Some("rs") => scan_rust,
+            Some("ts" | "tsx" | "js" | "jsx") => scan_typescript,
+            _ => continue,
+        };
+        scan_file_with(
+            file_path,
+            &config.root,
+            scanner,
+            &mut issues,
+            &mut symbol_counts,
+        );
     }
 
-    issues
+    (issues, symbol_counts)
+}
+
+/// Returns true for a Python package entry point (`__init__.py`).
+fn is_package_init(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "__init__.py")
 }
 
 /// Checks if a file should be ignored based on config and glob patterns.
@@ -444,24 +728,34 @@ fn should_ignore_file(path: &Path, config: &DocAuditConfig, globset: &GlobSet) -
 }
 
 /// Computes complexity scores for each directory (file count + subdirectory complexity).
+///
+/// Directories are grouped by depth and processed deepest level first, so every
+/// child is scored before its parent. Directories within a level are independent
+/// and are scored in parallel.
 fn compute_complexities(dir_info: &HashMap<PathBuf, DirectoryInfo>) -> HashMap<PathBuf, usize> {
-    let mut complexities = HashMap::new();
-    let mut directories: Vec<_> = dir_info.keys().collect();
-    directories.sort_by(|a, b| {
-        let len_a = a.components().count();
-        let len_b = b.components().count();
-        len_b.cmp(&len_a)
-    });
+    let mut levels: BTreeMap<usize, Vec<&PathBuf>> = BTreeMap::new();
+    for directory in dir_info.keys() {
+        levels
+            .entry(directory.components().count())
+            .or_default()
+            .push(directory);
+    }
 
-    for directory in directories {
-        if let Some(info) = dir_info.get(directory) {
-            let mut total = info.files.len();
-            for subdir in &info.subdirs {
-                let subdir_complexity = complexities.get(subdir).copied().unwrap_or(0);
-                total += subdir_complexity + 1;
-            }
-            complexities.insert(directory.clone(), total);
-        }
+    let mut complexities = HashMap::with_capacity(dir_info.len());
+    for directories in levels.into_values().rev() {
+        let level_scores: Vec<(PathBuf, usize)> = directories
+            .par_iter()
+            .filter_map(|directory| {
+                let info = dir_info.get(*directory)?;
+                let mut total = info.files.len();
+                for subdir in &info.subdirs {
+                    let subdir_complexity = complexities.get(subdir).copied().unwrap_or(0);
+                    total += subdir_complexity + 1;
+                }
+                Some(((*directory).clone(), total))
+            })
+            .collect();
+        complexities.extend(level_scores);
     }
 
     complexities
@@ -502,15 +796,24 @@ fn detect_missing_readmes(
     (issues, readmes)
 }
 
-/// Searches for a README file in the given directory using common naming conventions.
+/// Finds the README in `directory`, matching its name case-insensitively and
+/// preferring formats in [`ReadmeFormat`] order when there are several.
 fn find_readme(directory: &Path) -> Option<PathBuf> {
-    for candidate in README_CANDIDATES {
-        let candidate_path = directory.join(candidate);
-        if candidate_path.exists() {
-            return Some(candidate_path);
-        }
-    }
-    None
+    fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_readme_file(path))
+        .min_by_key(|path| (detect_readme_format(path), path.clone()))
+}
+
+/// Whether `path` is named `README` with no extension or a README format extension.
+fn is_readme_file(path: &Path) -> bool {
+    let is_readme = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| stem.eq_ignore_ascii_case("readme"));
+    is_readme && ReadmeFormat::from_extension(path.extension().and_then(|e| e.to_str())).is_some()
 }
 
 /// Detects READMEs that haven't been updated since significant changes to their directory.
@@ -522,32 +825,140 @@ fn detect_stale_readmes(
     let mut issues = Vec::new();
 
     for (readme_path, directory) in readme_index {
-        if let Some(info) = git_helper.last_commit_info(readme_path) {
-            if let Some(count) = git_helper.commits_since(info.oid, directory, Some(readme_path)) {
-                if count > config.max_readme_commits {
-                    let rel_directory = relative_path(directory, &config.root);
-                    issues.push(DocIssue {
-                        category: "stale_readme".to_string(),
-                        path: relative_path(readme_path, &config.root),
-                        line: None,
-                        symbol: None,
-                        detail: format!(
-                            "{} commits touched '{}' since README update on {}",
-                            count,
-                            rel_directory.display(),
-                            info.timestamp
-                        ),
-                    });
-                }
+        let Some(info) = git_helper.last_commit_info(readme_path) else {
+            continue;
+        };
+        let rel_directory = relative_path(directory, &config.root);
+        let detail = match config.staleness_mode {
+            StalenessMode::CommitCount => git_helper
+                .commits_since(info.oid, directory, Some(readme_path))
+                .filter(|&count| count > config.max_readme_commits)
+                .map(|count| {
+                    format!(
+                        "{} commits touched '{}' since README update on {}",
+                        count,
+                        rel_directory.display(),
+                        info.timestamp
+                    )
+                }),
+            StalenessMode::SemanticDiff => public_api_similarity(git_helper, info.oid, directory)
+                .filter(|&similarity| similarity < SEMANTIC_STALENESS_THRESHOLD)
+                .map(|similarity| {
+                    format!(
+                        "public API of '{}' is {:.0}% similar to README update on {}",
+                        rel_directory.display(),
+                        similarity * 100.0,
+                        info.timestamp
+                    )
+                }),
+        };
+        let detail = detail.or_else(|| {
+            let days = config.stale_after_days?;
+            let age_days = readme_age_days(&info)?;
+            if age_days <= days {
+                return None;
             }
+            git_helper
+                .commits_since(info.oid, directory, Some(readme_path))
+                .filter(|&count| count > 0)
+                .map(|count| {
+                    format!(
+                        "README is {} days old (limit {}) and {} commits touched '{}' since",
+                        age_days,
+                        days,
+                        count,
+                        rel_directory.display()
+                    )
+                })
+        });
+
+        if let Some(detail) = detail {
+            issues.push(DocIssue {
+                category: "stale_readme".to_string(),
+                path: relative_path(readme_path, &config.root),
+                line: None,
+                symbol: None,
+                detail,
+            });
         }
     }
 
     issues
 }
 
+/// Flags old, unexplained lint suppressions in Python, Rust and Go sources.
+fn detect_unexplained_suppressions(
+    files: &[PathBuf],
+    config: &DocAuditConfig,
+    globset: &GlobSet,
+    git_helper: &GitHelper,
+) -> Vec<DocIssue> {
+    if git_helper.repo().is_none() {
+        return Vec::new();
+    }
+
+    let mut issues = Vec::new();
+    for file_path in files {
+        let ext = file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        if !matches!(ext.as_deref(), Some("py" | "pyi" | "rs" | "go"))
+            || should_ignore_file(file_path, config, globset)
+        {
+            continue;
+        }
+        if let Ok(source) = fs::read_to_string(file_path) {
+            issues.extend(scan_inline_suppressions(
+                &source,
+                file_path,
+                &config.root,
+                git_helper,
+                config.max_readme_commits,
+            ));
+        }
+    }
+    issues
+}
+
+/// Whole days between a README's last commit and now; `None` for commits
+/// dated in the future.
+fn readme_age_days(info: &CommitInfo) -> Option<u64> {
+    let committed = SystemTime::UNIX_EPOCH
+        + Duration::from_secs(u64::try_from(info.timestamp.timestamp()).ok()?);
+    let age = SystemTime::now().duration_since(committed).ok()?;
+    Some(age.as_secs() / SECONDS_PER_DAY)
+}
+
+/// Jaccard similarity between the public symbols under `directory` at the
+/// `since` commit and at `HEAD`.
+fn public_api_similarity(git_helper: &GitHelper, since: Oid, directory: &Path) -> Option<f64> {
+    let symbols_at = |commit: Oid| -> Option<BTreeSet<String>> {
+        let files = git_helper.directory_sources(commit, directory)?;
+        Some(
+            files
+                .iter()
+                .flat_map(|(path, source)| public_symbols(path, source))
+                .collect(),
+        )
+    };
+
+    let head = git_helper.head_oid()?;
+    Some(jaccard_similarity(&symbols_at(since)?, &symbols_at(head)?))
+}
+
 // Git utilities extracted to git_utils.rs
 
+/// Rewrites `\r\n` and lone `\r` line endings as `\n` so scanners see
+/// identical lines regardless of the platform a file was written on.
+fn normalize_line_endings(source: &str) -> Cow<'_, str> {
+    if source.contains('\r') {
+        Cow::Owned(source.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(source)
+    }
+}
+
 /// Converts an absolute path to a relative path from the root.
 fn relative_path(path: &Path, root: &Path) -> PathBuf {
     path.strip_prefix(root)
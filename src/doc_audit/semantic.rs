@@ -0,0 +1,44 @@
+//! Public API extraction for semantic README staleness checks.
+//!
+//! Instead of counting commits, the semantic check compares the set of public
+//! symbol names a directory exposed when its README was last written with the
+//! set it exposes now. Renames, removals and additions lower the similarity
+//! even when they landed in a single commit.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use super::languages::{scan_python, scan_rust, scan_typescript};
+use super::FileScan;
+
+/// Jaccard similarity below which a README is reported as stale.
+pub const SEMANTIC_STALENESS_THRESHOLD: f64 = 0.7;
+
+/// Public symbol names declared in `source`, chosen by the extension of `path`.
+///
+/// Reuses the doc-audit language scanners, so a symbol counts as public API
+/// exactly when the scanner that checks its documentation reports it as public.
+/// Files in unsupported languages contribute no symbols.
+pub fn public_symbols(path: &Path, source: &str) -> BTreeSet<String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+
+    let scanner: fn(&str, &Path, &Path) -> FileScan = match ext.as_deref() {
+        Some("rs") => scan_rust,
+        Some("py" | "pyi") => scan_python,
+        Some("ts" | "tsx" | "js" | "jsx") => scan_typescript,
+        _ => return BTreeSet::new(),
+    };
+    scanner(source, path, Path::new("")).public_names
+}
+
+/// Jaccard similarity of two symbol sets; two empty sets are identical.
+pub fn jaccard_similarity(old: &BTreeSet<String>, new: &BTreeSet<String>) -> f64 {
+    let union = old.union(new).count();
+    if union == 0 {
+        return 1.0;
+    }
+    old.intersection(new).count() as f64 / union as f64
+}
@@ -0,0 +1,96 @@
+//! Detection of stale, unexplained lint and type-check suppressions.
+//!
+//! Suppression comments such as `# type: ignore` or `// nolint` are often
+//! added to silence an error and then forgotten. Those without a trailing
+//! explanation that have sat untouched for a while are flagged so they can be
+//! justified or removed.
+
+use std::path::Path;
+
+use super::git_utils::GitHelper;
+use super::{normalize_line_endings, relative_path, DocIssue};
+
+/// Suppression markers and the comment delimiter that introduces an
+/// explanation after each of them.
+///
+/// Rust `#[allow(...)]` attributes are not listed here; the Rust attribute
+/// hygiene check already flags those without a rationale.
+const SUPPRESSION_MARKERS: &[(&str, &str)] = &[
+    ("# type: ignore", "#"),
+    ("# noqa", "#"),
+    ("# pylint: disable", "#"),
+    ("// nolint", "//"),
+    ("//nolint", "//"),
+];
+
+/// Flag suppression comments in `source` that carry no trailing explanation
+/// and are older than `max_age_commits` commits.
+///
+/// Age is measured per file, not per line: it is the number of commits to the
+/// file's directory since the last commit touching the file, which is a lower
+/// bound on the age of every line in it. Files outside a git repository or
+/// without history yield no issues.
+pub fn scan_inline_suppressions(
+    source: &str,
+    path: &Path,
+    root: &Path,
+    git_helper: &GitHelper,
+    max_age_commits: usize,
+) -> Vec<DocIssue> {
+    let source = normalize_line_endings(source);
+    let unexplained: Vec<(usize, &str)> = source
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| unexplained_suppression(line).map(|marker| (index + 1, marker)))
+        .collect();
+    if unexplained.is_empty() {
+        return Vec::new();
+    }
+
+    let Some(info) = git_helper.last_commit_info(path) else {
+        return Vec::new();
+    };
+    let Some(age) = git_helper
+        .commits_since(info.oid, path.parent().unwrap_or(root), None)
+        .filter(|&count| count > max_age_commits)
+    else {
+        return Vec::new();
+    };
+
+    unexplained
+        .into_iter()
+        .map(|(line, marker)| DocIssue {
+            category: "unexplained_suppression".to_string(),
+            path: relative_path(path, root),
+            line: Some(line),
+            symbol: None,
+            detail: format!(
+                "`{}` has no explanation and {} commits have passed since {}",
+                marker, age, info.timestamp
+            ),
+        })
+        .collect()
+}
+
+/// Earliest suppression marker on `line` and its explanation delimiter.
+fn find_suppression(line: &str) -> Option<(usize, &'static str, &'static str)> {
+    SUPPRESSION_MARKERS
+        .iter()
+        .filter_map(|&(marker, delimiter)| {
+            line.find(marker).map(|start| (start, marker, delimiter))
+        })
+        .min_by_key(|&(start, _, _)| start)
+}
+
+/// The suppression marker on `line` when it is not followed by an explanation.
+///
+/// A further suppression after the delimiter does not count as one.
+fn unexplained_suppression(line: &str) -> Option<&'static str> {
+    let (start, marker, delimiter) = find_suppression(line)?;
+    let rest = &line[start + marker.len()..];
+    let explained = rest.find(delimiter).is_some_and(|offset| {
+        let comment = &rest[offset..];
+        !comment[delimiter.len()..].trim().is_empty() && find_suppression(comment).is_none()
+    });
+    (!explained).then_some(marker)
+}
@@ -1,7 +1,7 @@
 use super::languages::{scan_python, scan_rust, scan_typescript};
 use super::*;
 use git2::Repository;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use tempfile::tempdir;
@@ -53,6 +53,35 @@ fn audit_reports_python_doc_gap() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn audit_reports_undocumented_python_stubs() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path().to_path_buf();
+
+    fs::write(
+        root.join("api.pyi"),
+        r#"def fetch(url: str) -> bytes: ...
+
+class Client:
+    def get(self, path: str) -> bytes: ...
+"#,
+    )?;
+
+    let mut config = DocAuditConfig::new(root);
+    config.complexity_threshold = usize::MAX;
+
+    let result = run_audit(&config)?;
+    let mut symbols: Vec<&str> = result
+        .documentation_issues
+        .iter()
+        .filter(|issue| issue.path.ends_with("api.pyi"))
+        .filter_map(|issue| issue.symbol.as_deref())
+        .collect();
+    symbols.sort_unstable();
+    assert_eq!(symbols, vec!["Client", "Client.get", "fetch"]);
+    Ok(())
+}
+
 #[test]
 fn audit_reports_missing_readme_for_complex_directory() -> Result<()> {
     let dir = tempdir()?;
@@ -104,6 +133,247 @@ fn audit_reports_stale_readme_after_commits() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn audit_reports_readme_stale_by_age_window() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    let repo = Repository::init(root)?;
+
+    fs::write(root.join("README.md"), "# Project\n")?;
+    stage_and_commit_days_ago(&repo, &["README.md"], "initial", 100);
+
+    let mut config = DocAuditConfig::new(root.to_path_buf());
+    config.complexity_threshold = 0;
+    let stale_paths = |config: &DocAuditConfig| -> Result<Vec<PathBuf>> {
+        Ok(run_audit(config)?
+            .stale_readmes
+            .into_iter()
+            .map(|issue| issue.path)
+            .collect())
+    };
+
+    // An old README is fine while nothing changed after it
+    assert!(stale_paths(&config.clone().with_git_age_window(90))?.is_empty());
+
+    fs::create_dir_all(root.join("src"))?;
+    fs::write(root.join("src/lib.rs"), "pub fn lib() {}")?;
+    stage_and_commit(&repo, &["src/lib.rs"], "add lib");
+
+    // One commit stays under max_readme_commits, so only the age window flags it
+    assert!(stale_paths(&config)?.is_empty());
+    assert_eq!(
+        stale_paths(&config.clone().with_git_age_window(90))?,
+        vec![PathBuf::from("README.md")]
+    );
+    assert!(stale_paths(&config.clone().with_git_age_window(120))?.is_empty());
+
+    let result = run_audit(&config.with_git_age_window(90))?;
+    assert!(result.stale_readmes[0].detail.contains("100 days old"));
+    Ok(())
+}
+
+/// Commits README then two source changes, returning the README commit.
+fn readme_then_two_changes(root: &Path) -> Result<(Repository, git2::Oid)> {
+    let repo = Repository::init(root)?;
+    fs::write(root.join("README.md"), "# Project\n")?;
+    stage_and_commit(&repo, &["README.md"], "initial");
+    let readme_commit = repo.head()?.peel_to_commit()?.id();
+
+    fs::create_dir_all(root.join("src"))?;
+    for body in ["1", "2"] {
+        fs::write(
+            root.join("src/lib.rs"),
+            format!("pub fn lib() {{ {body}; }}"),
+        )?;
+        stage_and_commit(&repo, &["src/lib.rs"], "change lib");
+    }
+    Ok((repo, readme_commit))
+}
+
+#[test]
+fn scan_inline_suppressions_flags_old_unexplained_suppressions() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    let repo = Repository::init(root)?;
+    fs::create_dir_all(root.join("pkg"))?;
+    let source = "\
+import os  # type: ignore
+import sys  # noqa: F401  # re-exported for plugins
+x = 1  # pylint: disable=invalid-name
+y = 2  # type: ignore  # noqa
+";
+    fs::write(root.join("pkg/mod.py"), source)?;
+    stage_and_commit(&repo, &["pkg/mod.py"], "add module");
+    fs::write(root.join("pkg/other.py"), "z = 3\n")?;
+    stage_and_commit(&repo, &["pkg/other.py"], "add other");
+
+    let git_helper = GitHelper::new(root);
+    let path = root.join("pkg/mod.py");
+    let issues = scan_inline_suppressions(source, &path, root, &git_helper, 0);
+    let lines: Vec<_> = issues.iter().filter_map(|issue| issue.line).collect();
+    assert_eq!(lines, vec![1, 3, 4]);
+    assert!(issues
+        .iter()
+        .all(|issue| issue.category == "unexplained_suppression"
+            && issue.path == PathBuf::from("pkg/mod.py")));
+
+    assert!(scan_inline_suppressions(source, &path, root, &git_helper, 1).is_empty());
+    Ok(())
+}
+
+#[test]
+fn scan_inline_suppressions_accepts_trailing_explanations_for_go() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    let repo = Repository::init(root)?;
+    let go = "err := f() //nolint:errcheck\nerr = g() //nolint:errcheck // best effort\n";
+    let rust = "#[allow(dead_code)]\nstruct A;\n";
+    fs::write(root.join("main.go"), go)?;
+    fs::write(root.join("lib.rs"), rust)?;
+    stage_and_commit(&repo, &["main.go", "lib.rs"], "add sources");
+    fs::write(root.join("notes.txt"), "later\n")?;
+    stage_and_commit(&repo, &["notes.txt"], "later change");
+
+    let git_helper = GitHelper::new(root);
+    let go_issues = scan_inline_suppressions(go, &root.join("main.go"), root, &git_helper, 0);
+    assert_eq!(go_issues.len(), 1);
+    assert_eq!(go_issues[0].line, Some(1));
+    // Rust `#[allow(...)]` attributes are covered by the Rust attribute hygiene check
+    assert!(scan_inline_suppressions(rust, &root.join("lib.rs"), root, &git_helper, 0).is_empty());
+    Ok(())
+}
+
+#[test]
+fn commits_since_follows_detached_head() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    let (repo, readme_commit) = readme_then_two_changes(root)?;
+    let head = repo.head()?.peel_to_commit()?.id();
+    repo.set_head_detached(head)?;
+
+    let helper = git_utils::GitHelper::new(root);
+    assert_eq!(helper.commits_since(readme_commit, root, None), Some(2));
+    assert!(helper.last_commit_info(&root.join("README.md")).is_some());
+    Ok(())
+}
+
+#[test]
+fn commits_since_returns_zero_when_shallow_history_ends_early() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    let (repo, readme_commit) = readme_then_two_changes(root)?;
+
+    // Cut history at the first source change, as `git fetch --depth=2` would.
+    let head = repo.head()?.peel_to_commit()?;
+    let boundary = head.parent_id(0)?;
+    fs::write(repo.path().join("shallow"), format!("{boundary}\n"))?;
+    let repo = Repository::open(root)?;
+    assert!(repo.is_shallow());
+
+    let helper = git_utils::GitHelper::new(root);
+    assert_eq!(helper.commits_since(readme_commit, root, None), Some(0));
+    assert_eq!(helper.commits_since(boundary, root, None), Some(1));
+    Ok(())
+}
+
+#[test]
+fn semantic_staleness_flags_public_api_drift_in_a_single_commit() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    let repo = Repository::init(root)?;
+
+    fs::create_dir_all(root.join("src"))?;
+    fs::write(root.join("README.md"), "# Project\n")?;
+    fs::write(
+        root.join("src/lib.rs"),
+        "pub fn parse() {}\npub struct Config;\n",
+    )?;
+    stage_and_commit(&repo, &["README.md", "src/lib.rs"], "initial");
+
+    fs::write(
+        root.join("src/lib.rs"),
+        "pub fn tokenize() {}\npub struct Settings;\n",
+    )?;
+    stage_and_commit(&repo, &["src/lib.rs"], "rename api");
+
+    let mut config = DocAuditConfig::new(root.to_path_buf());
+    config.complexity_threshold = 0;
+    assert!(run_audit(&config)?.stale_readmes.is_empty());
+
+    config.staleness_mode = StalenessMode::SemanticDiff;
+    let result = run_audit(&config)?;
+    assert_eq!(result.stale_readmes.len(), 1);
+    assert!(result.stale_readmes[0].detail.contains("0% similar"));
+    Ok(())
+}
+
+#[test]
+fn semantic_staleness_ignores_changes_that_keep_the_public_api() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    let repo = Repository::init(root)?;
+
+    fs::create_dir_all(root.join("src"))?;
+    fs::write(root.join("README.md"), "# Project\n")?;
+    fs::write(root.join("src/lib.rs"), "pub fn parse() {}\n")?;
+    stage_and_commit(&repo, &["README.md", "src/lib.rs"], "initial");
+
+    for body in ["1", "2", "3"] {
+        fs::write(
+            root.join("src/lib.rs"),
+            format!("pub fn parse() {{ helper({body}); }}\nfn helper(_: u8) {{}}\n"),
+        )?;
+        stage_and_commit(&repo, &["src/lib.rs"], "internal change");
+    }
+
+    let mut config = DocAuditConfig::new(root.to_path_buf());
+    config.complexity_threshold = 0;
+    config.max_readme_commits = 0;
+    config.staleness_mode = StalenessMode::SemanticDiff;
+
+    assert!(run_audit(&config)?.stale_readmes.is_empty());
+    Ok(())
+}
+
+#[test]
+fn public_symbols_reuses_language_scanners_for_exported_names() {
+    use super::semantic::{jaccard_similarity, public_symbols};
+
+    let rust = public_symbols(
+        Path::new("lib.rs"),
+        "pub const fn limit() {}\npub(crate) fn hidden() {}\npub struct Config<T>;\nfn private() {}\n",
+    );
+    assert_eq!(
+        rust.into_iter().collect::<Vec<_>>(),
+        vec!["Config".to_string(), "limit".to_string()]
+    );
+
+    let python = public_symbols(
+        Path::new("mod.py"),
+        "def run():\n    def inner():\n        pass\nasync def fetch():\n    pass\nclass _Hidden:\n    pass\n",
+    );
+    assert_eq!(
+        python.into_iter().collect::<Vec<_>>(),
+        vec!["fetch".to_string(), "run".to_string()]
+    );
+
+    let typescript = public_symbols(
+        Path::new("index.ts"),
+        "export default function main() {}\nexport interface Props {}\nfunction local() {}\n",
+    );
+    assert_eq!(
+        typescript.into_iter().collect::<Vec<_>>(),
+        vec!["Props".to_string(), "main".to_string()]
+    );
+
+    assert!(public_symbols(Path::new("notes.txt"), "pub fn x() {}").is_empty());
+
+    let old: BTreeSet<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    let new: BTreeSet<String> = ["a", "b", "d"].iter().map(|s| s.to_string()).collect();
+    assert!((jaccard_similarity(&old, &new) - 0.5).abs() < 1e-9);
+    assert_eq!(jaccard_similarity(&BTreeSet::new(), &BTreeSet::new()), 1.0);
+}
+
 #[test]
 fn render_helpers_format_output() -> Result<()> {
     let sample = AuditResult {
@@ -128,12 +398,21 @@ fn render_helpers_format_output() -> Result<()> {
             symbol: None,
             detail: "5 commits touched '.' since README update on 2024-01-01T00:00:00+00:00".into(),
         }],
+        symbol_counts: BTreeMap::new(),
+        coverage: vec![DocCoverageStats {
+            directory: PathBuf::from("."),
+            total_symbols: 4,
+            documented_symbols: 3,
+            coverage_pct: 75.0,
+        }],
     };
 
     let text = render_text(&sample);
     assert!(text.contains("Documentation gaps"));
     assert!(text.contains("Missing READMEs"));
     assert!(text.contains("Stale READMEs"));
+    assert!(text.contains("Documentation Coverage"));
+    assert!(text.contains("  - . - 75.0% (3/4 symbols documented)"));
 
     let json = render_json(&sample)?;
     let parsed: serde_json::Value = serde_json::from_str(&json)?;
@@ -141,6 +420,199 @@ fn render_helpers_format_output() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn display_formats_one_line_summaries() {
+    let empty = AuditResult {
+        documentation_issues: Vec::new(),
+        missing_readmes: Vec::new(),
+        stale_readmes: Vec::new(),
+        symbol_counts: BTreeMap::new(),
+        coverage: Vec::new(),
+    };
+    assert_eq!(
+        empty.to_string(),
+        "valknut doc-audit: 0 doc gaps, 0 missing READMEs, 0 stale READMEs (0 total)"
+    );
+
+    let issue = |category: &str, path: &str, line: Option<usize>| DocIssue {
+        category: category.into(),
+        path: PathBuf::from(path),
+        line,
+        symbol: None,
+        detail: "detail".into(),
+    };
+    let result = AuditResult {
+        documentation_issues: vec![
+            issue("undocumented_python", "main.py", Some(3)),
+            issue("undocumented_rust", "lib.rs", Some(7)),
+        ],
+        missing_readmes: vec![issue("missing_readme", "services", None)],
+        stale_readmes: Vec::new(),
+        ..empty
+    };
+    assert_eq!(
+        result.to_string(),
+        "valknut doc-audit: 2 doc gaps, 1 missing README, 0 stale READMEs (3 total)"
+    );
+    assert_eq!(
+        result.documentation_issues[0].to_string(),
+        "main.py:3 [undocumented_python] detail"
+    );
+    assert_eq!(
+        result.missing_readmes[0].to_string(),
+        "services [missing_readme] detail"
+    );
+}
+
+#[test]
+fn render_json_is_stable_and_uses_forward_slashes() -> Result<()> {
+    let sample = AuditResult {
+        documentation_issues: vec![DocIssue {
+            category: "undocumented_rust".into(),
+            path: PathBuf::from("src").join("lib.rs"),
+            line: Some(1),
+            symbol: Some("lib".into()),
+            detail: "Function 'lib' is missing documentation".into(),
+        }],
+        missing_readmes: Vec::new(),
+        stale_readmes: Vec::new(),
+        symbol_counts: BTreeMap::new(),
+        coverage: Vec::new(),
+    };
+
+    let first = render_json(&sample)?;
+    let second = render_json(&sample)?;
+    assert_eq!(first, second);
+    assert!(first.contains("\"src/lib.rs\""));
+    Ok(())
+}
+
+#[test]
+fn render_json_to_writer_matches_render_json() -> Result<()> {
+    let sample = AuditResult {
+        documentation_issues: vec![DocIssue {
+            category: "undocumented_python".into(),
+            path: PathBuf::from("pkg").join("module.py"),
+            line: Some(3),
+            symbol: Some("helper".into()),
+            detail: "Function 'helper' is missing a docstring".into(),
+        }],
+        missing_readmes: Vec::new(),
+        stale_readmes: Vec::new(),
+        symbol_counts: BTreeMap::new(),
+        coverage: Vec::new(),
+    };
+
+    let mut buffer = Vec::new();
+    render_json_to_writer(&sample, &mut buffer)?;
+
+    let parsed: serde_json::Value = serde_json::from_slice(&buffer)?;
+    assert_eq!(parsed["documentation_issues"][0]["path"], "pkg/module.py");
+    assert_eq!(String::from_utf8(buffer)?, render_json(&sample)?);
+    Ok(())
+}
+
+#[test]
+fn run_audit_orders_issues_deterministically() -> Result<()> {
+    let temp = tempdir()?;
+    let root = temp.path();
+    for name in ["zeta", "alpha", "mid"] {
+        let dir = root.join(name);
+        fs::create_dir_all(&dir)?;
+        for idx in 0..3 {
+            fs::write(dir.join(format!("f{idx}.py")), "def f():\n    pass\n")?;
+        }
+    }
+
+    let mut config = DocAuditConfig::new(root.to_path_buf());
+    config.complexity_threshold = 1;
+    let first = render_json(&run_audit(&config)?)?;
+    let second = render_json(&run_audit(&config)?)?;
+    assert_eq!(first, second);
+
+    let result = run_audit(&config)?;
+    let paths: Vec<_> = result.missing_readmes.iter().map(|i| &i.path).collect();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(paths, sorted);
+    Ok(())
+}
+
+#[test]
+fn run_audit_reports_coverage_per_directory() -> Result<()> {
+    let temp = tempdir()?;
+    let root = temp.path();
+    let pkg = root.join("pkg");
+    fs::create_dir_all(&pkg)?;
+    fs::write(
+        root.join("main.py"),
+        "def main():\n    \"\"\"Entry point.\"\"\"\n    pass\n",
+    )?;
+    fs::write(
+        pkg.join("util.py"),
+        "def helper():\n    \"\"\"Help out.\"\"\"\n    pass\n\n\ndef bare():\n    pass\n",
+    )?;
+    fs::write(
+        pkg.join("lib.rs"),
+        "/// Documented.\npub fn documented() {}\n\npub fn undocumented() {}\n",
+    )?;
+    fs::write(pkg.join("notes.txt"), "not source\n")?;
+
+    let config = DocAuditConfig::new(root.to_path_buf());
+    let result = run_audit(&config)?;
+    let stats = compute_coverage_stats(&result, &config);
+    assert_eq!(stats, result.coverage);
+
+    let summary: Vec<_> = stats
+        .iter()
+        .map(|s| (s.directory.clone(), s.documented_symbols, s.total_symbols))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(PathBuf::from("."), 1, 1), (PathBuf::from("pkg"), 2, 4)]
+    );
+    assert!((stats[1].coverage_pct - 50.0).abs() < 1e-9);
+
+    let text = render_text(&result);
+    assert!(text.contains("  - pkg - 50.0% (2/4 symbols documented)"));
+    Ok(())
+}
+
+#[test]
+fn detect_readme_format_distinguishes_markup() {
+    assert_eq!(
+        detect_readme_format(Path::new("docs/README.md")),
+        ReadmeFormat::Markdown
+    );
+    assert_eq!(
+        detect_readme_format(Path::new("README.RST")),
+        ReadmeFormat::Rst
+    );
+    assert_eq!(
+        detect_readme_format(Path::new("README.txt")),
+        ReadmeFormat::PlainText
+    );
+    assert_eq!(
+        detect_readme_format(Path::new("README")),
+        ReadmeFormat::PlainText
+    );
+}
+
+#[test]
+fn find_readme_matches_any_case_and_prefers_markdown() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    assert_eq!(find_readme(root), None);
+
+    fs::write(root.join("readme.RST"), "Title\n=====\n")?;
+    fs::write(root.join("README.html"), "<h1>Title</h1>")?;
+    assert_eq!(find_readme(root), Some(root.join("readme.RST")));
+
+    fs::write(root.join("Readme.markdown"), "# Title\n")?;
+    assert_eq!(find_readme(root), Some(root.join("Readme.markdown")));
+    Ok(())
+}
+
 #[test]
 fn compute_complexities_counts_files_and_subdirectories() {
     let root = PathBuf::from("/tmp/project");
@@ -167,6 +639,56 @@ fn compute_complexities_counts_files_and_subdirectories() {
     assert_eq!(complexities.get(&root), Some(&3));
 }
 
+#[test]
+fn compute_complexities_handles_wide_and_deep_trees() {
+    let root = PathBuf::from("/tmp/project");
+    let mut dir_info = HashMap::new();
+    let mut root_subdirs = Vec::new();
+
+    // 600 directories: 200 branches, each two levels deep with one file per level.
+    for branch in 0..200 {
+        let top = root.join(format!("pkg{branch}"));
+        let mid = top.join("mid");
+        let leaf = mid.join("leaf");
+        dir_info.insert(
+            leaf.clone(),
+            DirectoryInfo {
+                files: vec![leaf.join("a.rs")],
+                subdirs: Vec::new(),
+            },
+        );
+        dir_info.insert(
+            mid.clone(),
+            DirectoryInfo {
+                files: vec![mid.join("b.rs")],
+                subdirs: vec![leaf],
+            },
+        );
+        dir_info.insert(
+            top.clone(),
+            DirectoryInfo {
+                files: vec![top.join("c.rs")],
+                subdirs: vec![mid],
+            },
+        );
+        root_subdirs.push(top);
+    }
+    dir_info.insert(
+        root.clone(),
+        DirectoryInfo {
+            files: Vec::new(),
+            subdirs: root_subdirs,
+        },
+    );
+
+    let complexities = compute_complexities(&dir_info);
+    assert_eq!(complexities.len(), 601);
+    assert_eq!(complexities.get(&root.join("pkg7/mid/leaf")), Some(&1));
+    assert_eq!(complexities.get(&root.join("pkg7/mid")), Some(&3));
+    assert_eq!(complexities.get(&root.join("pkg7")), Some(&5));
+    assert_eq!(complexities.get(&root), Some(&(200 * 6)));
+}
+
 #[test]
 fn detect_missing_readmes_skips_directories_with_existing_docs() -> Result<()> {
     let temp = tempdir()?;
@@ -188,6 +710,48 @@ fn detect_missing_readmes_skips_directories_with_existing_docs() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn scanners_accept_windows_line_endings() -> Result<()> {
+    let temp = tempdir()?;
+    let root = temp.path();
+    let python = concat!(
+        "class Service:\r\n",
+        "    \"\"\"Coordinates work.\r\n",
+        "\r\n",
+        "    Spans several lines.\r\n",
+        "    \"\"\"\r\n",
+        "\r\n",
+        "    def run(self):\r\n",
+        "        \"\"\"Run once.\"\"\"\r\n",
+        "        pass\r\n",
+        "\r\n",
+        "def undocumented():\r\n",
+        "    pass\r\n",
+    );
+    fs::write(root.join("service.py"), python)?;
+
+    let result = run_audit(&DocAuditConfig::new(root.to_path_buf()))?;
+    let symbols: Vec<_> = result
+        .documentation_issues
+        .iter()
+        .filter_map(|issue| issue.symbol.as_deref())
+        .collect();
+    assert_eq!(symbols, vec!["undocumented"]);
+    assert_eq!(result.coverage[0].documented_symbols, 2);
+
+    let path = root.join("lib.rs");
+    let rust = "/// Documented.\r\npub fn documented() {}\r\rpub fn bare() {}\r";
+    let issues = scan_rust(rust, &path, root).issues;
+    assert_eq!(issues.len(), 1, "unexpected issues: {issues:?}");
+    assert_eq!(issues[0].symbol.as_deref(), Some("bare"));
+    assert_eq!(issues[0].line, Some(4));
+
+    let path = root.join("index.ts");
+    let typescript = "/**\r\n * Documented.\r\n */\r\nexport function documented() {}\r\n";
+    assert!(scan_typescript(typescript, &path, root).issues.is_empty());
+    Ok(())
+}
+
 #[test]
 fn should_ignore_file_respects_suffix_configuration() {
     let root = PathBuf::from("/tmp/project");
@@ -236,7 +800,7 @@ async def helper():
     return 3
 "#;
 
-    let issues = scan_python(source, &path, &root);
+    let issues = scan_python(source, &path, &root).issues;
     let symbols: Vec<_> = issues
         .iter()
         .map(|issue| issue.symbol.clone().unwrap_or_default())
@@ -252,6 +816,196 @@ async def helper():
     );
 }
 
+#[test]
+fn doc_style_detection_distinguishes_conventions() {
+    assert_eq!(
+        DocStyle::detect("Run.\n\n    :param x: input\n"),
+        Some(DocStyle::Sphinx)
+    );
+    assert_eq!(
+        DocStyle::detect("Run.\n\n    Args:\n        x: input\n"),
+        Some(DocStyle::Google)
+    );
+    assert_eq!(
+        DocStyle::detect("Run.\n\n    Parameters\n    ----------\n    x : int\n"),
+        Some(DocStyle::NumPy)
+    );
+    assert_eq!(DocStyle::detect("Run the thing."), None);
+}
+
+#[test]
+fn type_annotation_coverage_reports_undocumented_types_per_style() {
+    let signature = "def run(self, x: int, y: str = 'a', z=3, *args: int) -> bool:";
+
+    let sphinx = ":param x: the input\n:type x: int\n:param str y: label\n:param args: extra";
+    assert_eq!(
+        check_python_type_annotation_coverage(signature, sphinx, DocStyle::Sphinx),
+        vec!["args".to_string()]
+    );
+
+    let google = "Run.\n\n    Args:\n        x (int): the input\n        y: label\n            (continued)\n\n    Returns:\n        bool: ok\n";
+    assert_eq!(
+        check_python_type_annotation_coverage(signature, google, DocStyle::Google),
+        vec!["y".to_string(), "args".to_string()]
+    );
+
+    let numpy = "Run.\n\n    Parameters\n    ----------\n    x, y : int\n        Values.\n    args\n        Extra.\n\n    Returns\n    -------\n    args : bool\n";
+    assert_eq!(
+        check_python_type_annotation_coverage(signature, numpy, DocStyle::NumPy),
+        vec!["args".to_string()]
+    );
+}
+
+#[test]
+fn param_name_consistency_pairs_renamed_parameters_per_style() {
+    let signature = "def fetch(self, account_id: int, limit=10, *, verbose=False):";
+
+    let sphinx = ":param user_id: the account\n:param limit: page size\n:param verbose: log";
+    assert_eq!(
+        check_python_param_name_consistency(signature, sphinx),
+        vec![("account_id".to_string(), "user_id".to_string())]
+    );
+
+    let google = "Fetch.\n\n    Args:\n        user_id (int): the account\n        limit: page size\n        verbos: log\n";
+    assert_eq!(
+        check_python_param_name_consistency(signature, google),
+        vec![
+            ("account_id".to_string(), "user_id".to_string()),
+            ("verbose".to_string(), "verbos".to_string()),
+        ]
+    );
+
+    let numpy = "Fetch.\n\n    Parameters\n    ----------\n    limit : int\n    acount_id : int\n";
+    assert_eq!(
+        check_python_param_name_consistency(signature, numpy),
+        vec![("account_id".to_string(), "acount_id".to_string())]
+    );
+
+    let consistent = "Fetch.\n\n    Args:\n        account_id: the account\n";
+    assert!(check_python_param_name_consistency(signature, consistent).is_empty());
+    assert!(check_python_param_name_consistency(signature, "Fetch an account.").is_empty());
+}
+
+#[test]
+fn python_scanner_flags_param_name_mismatch() {
+    let root = PathBuf::from("/tmp/project");
+    let path = root.join("accounts.py");
+    let source = r#"
+def load(account_id):
+    """Load an account.
+
+    Args:
+        user_id: the account to load
+    """
+    print(account_id)
+"#;
+
+    let issues = scan_python(source, &path, &root).issues;
+    assert_eq!(issues.len(), 1, "unexpected issues: {issues:?}");
+    assert_eq!(issues[0].category, "param_name_mismatch");
+    assert_eq!(issues[0].line, Some(2));
+    assert_eq!(issues[0].symbol.as_deref(), Some("load"));
+    assert!(issues[0]
+        .detail
+        .ends_with("'account_id' (documented as 'user_id')"));
+}
+
+#[test]
+fn python_scanner_flags_undocumented_annotated_types() {
+    let root = PathBuf::from("/tmp/project");
+    let path = root.join("typed.py");
+    let source = r#"
+def typed(
+    x: int,
+    y: dict[str, int],
+) -> bool:
+    """Check values.
+
+    :param x: the input
+    :type x: int
+    :param y: the mapping
+    :returns: whether the values are valid
+    """
+    return True
+
+def plain(x: int) -> bool:
+    """Check a value without a parameter section."""
+    return True
+"#;
+
+    let issues = scan_python(source, &path, &root).issues;
+    assert_eq!(issues.len(), 1, "unexpected issues: {issues:?}");
+    assert_eq!(issues[0].category, "undocumented_type");
+    assert_eq!(issues[0].line, Some(2));
+    assert_eq!(issues[0].symbol.as_deref(), Some("typed"));
+    assert!(issues[0].detail.ends_with("for: y"));
+}
+
+#[test]
+fn python_scanner_checks_returns_sections_against_return_statements() {
+    let root = PathBuf::from("/tmp/project");
+    let path = root.join("returns.py");
+    let source = r#"
+def log(message):
+    """Write a message.
+
+    Args:
+        message: text to write
+
+    Returns:
+        Nothing useful.
+    """
+    def render():
+        """Format the message."""
+        return message.upper()
+    print(render())
+    return
+
+def total(values):
+    """Sum values.
+
+    Args:
+        values: numbers to add
+    """
+    if not values:
+        return None
+    return sum(values)
+
+def documented(values):
+    """Sum values.
+
+    Args:
+        values: numbers to add
+
+    Returns:
+        The sum.
+    """
+    return sum(values)
+
+def abstract(values):
+    """Compute something.
+
+    Returns:
+        The result.
+    """
+    raise NotImplementedError
+"#;
+
+    let issues = scan_python(source, &path, &root).issues;
+    let categories: Vec<(&str, Option<&str>)> = issues
+        .iter()
+        .map(|issue| (issue.category.as_str(), issue.symbol.as_deref()))
+        .collect();
+    assert_eq!(
+        categories,
+        vec![
+            ("spurious_returns_doc", Some("log")),
+            ("missing_returns_doc", Some("total")),
+        ]
+    );
+    assert_eq!(issues[0].line, Some(2));
+}
+
 #[test]
 fn rust_scanner_flags_undocumented_items() {
     let root = PathBuf::from("/tmp/project");
@@ -271,7 +1025,7 @@ pub struct Widget;
 fn needs_docs() {}
 "#;
 
-    let issues = scan_rust(source, &path, &root);
+    let issues = scan_rust(source, &path, &root).issues;
     let mut categories: Vec<_> = issues.iter().map(|issue| issue.category.as_str()).collect();
     categories.sort();
 
@@ -293,6 +1047,63 @@ fn needs_docs() {}
     );
 }
 
+#[test]
+fn rust_scanner_flags_deprecations_without_notes_and_unexplained_allows() {
+    let root = PathBuf::from("/tmp/project");
+    let path = root.join("lib.rs");
+    let source = r#"
+/// Old entry point.
+#[deprecated]
+pub fn old_api() {}
+
+/// Older entry point.
+#[deprecated(since = "0.3")]
+pub fn older_api() {}
+
+/// Replaced entry point.
+#[deprecated(since = "0.3", note = "use new_api instead")]
+pub fn replaced_api() {}
+
+/// Short-form note.
+#[deprecated = "use new_api instead"]
+pub fn short_form() {}
+
+/// Silenced without explanation.
+#[allow(dead_code)]
+pub struct Unused;
+
+/// Silenced with explanation.
+// Kept for FFI layout compatibility.
+#[allow(dead_code)]
+pub struct Explained;
+
+/// Trailing explanation.
+#[allow(clippy::too_many_arguments)] // mirrors the C signature
+pub fn wide() {}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused)]
+    fn helper() {}
+}
+"#;
+
+    let issues = scan_rust(source, &path, &root).issues;
+    let symbols_for = |category: &str| -> Vec<String> {
+        issues
+            .iter()
+            .filter(|issue| issue.category == category)
+            .filter_map(|issue| issue.symbol.clone())
+            .collect()
+    };
+
+    assert_eq!(
+        symbols_for("incomplete_deprecation"),
+        vec!["old_api".to_string(), "older_api".to_string()]
+    );
+    assert_eq!(symbols_for("unexplained_allow"), vec!["Unused".to_string()]);
+}
+
 #[test]
 fn typescript_scanner_handles_functions_classes_and_arrows() {
     let root = PathBuf::from("/tmp/project");
@@ -314,7 +1125,7 @@ const compute = (value: number) => {
 };
 "#;
 
-    let issues = scan_typescript(source, &path, &root);
+    let issues = scan_typescript(source, &path, &root).issues;
     let categories: HashSet<_> = issues.iter().map(|issue| issue.category.as_str()).collect();
 
     assert!(categories.contains("undocumented_ts_function"));
@@ -322,7 +1133,130 @@ const compute = (value: number) => {
     assert!(categories.contains("undocumented_ts_arrow"));
 }
 
+#[test]
+fn typescript_scanner_handles_interfaces_and_type_aliases() {
+    let root = PathBuf::from("/tmp/project");
+    let path = root.join("api.ts");
+    let source = r#"
+export interface ApiResponse {
+    status: number;
+}
+
+export type UserId = string;
+
+/**
+ * Payload returned by the session endpoint.
+ */
+export interface SessionPayload<T> {
+    data: T;
+}
+
+/** Opaque identifier for an organization. */
+export type OrgId = string;
+
+export type { Widget } from "./widget";
+"#;
+
+    let issues = scan_typescript(source, &path, &root).issues;
+    let symbols: Vec<_> = issues
+        .iter()
+        .map(|issue| {
+            (
+                issue.category.as_str(),
+                issue.symbol.as_deref().unwrap_or(""),
+            )
+        })
+        .collect();
+
+    assert!(symbols.contains(&("undocumented_ts_interface", "ApiResponse")));
+    assert!(symbols.contains(&("undocumented_ts_type", "UserId")));
+    assert!(!symbols.iter().any(|(_, name)| *name == "SessionPayload"));
+    assert!(!symbols.iter().any(|(_, name)| *name == "OrgId"));
+    assert_eq!(issues.len(), 2, "re-exports should not be reported");
+}
+
+#[test]
+fn apply_fixes_inserts_stubs_for_each_language() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path().to_path_buf();
+    fs::write(
+        root.join("sample.py"),
+        "class Widget:\n    def render(\n        self,\n    ):\n        return 1\n",
+    )?;
+    fs::write(
+        root.join("lib.rs"),
+        "#[derive(Debug)]\npub struct Config;\n\n/// Documented.\npub fn ok() {}\n",
+    )?;
+    fs::write(root.join("util.ts"), "export function helper() {}\n")?;
+
+    let mut config = DocAuditConfig::new(root.clone());
+    config.complexity_threshold = usize::MAX;
+    let result = run_audit(&config)?;
+    let fixes = apply_fixes(&result.documentation_issues, &config)?;
+    assert_eq!(fixes.len(), 4, "unexpected fixes: {fixes:?}");
+
+    assert_eq!(
+        fs::read_to_string(root.join("sample.py"))?,
+        "class Widget:\n    \"\"\"TODO: document this.\"\"\"\n    def render(\n        self,\n    ):\n        \"\"\"TODO: document this.\"\"\"\n        return 1\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("lib.rs"))?,
+        "/// TODO: document this.\n#[derive(Debug)]\npub struct Config;\n\n/// Documented.\npub fn ok() {}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("util.ts"))?,
+        "/** TODO: document this. */\nexport function helper() {}\n"
+    );
+
+    // Stubs count as incomplete docs, so a second pass must not stack more.
+    let rerun = run_audit(&config)?;
+    assert!(!rerun.documentation_issues.is_empty());
+    assert!(apply_fixes(&rerun.documentation_issues, &config)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn apply_fixes_ignores_non_gap_categories() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path().to_path_buf();
+    let source = "pub fn wide() {}\n";
+    fs::write(root.join("lib.rs"), source)?;
+
+    let config = DocAuditConfig::new(root.clone());
+    let issues = vec![
+        DocIssue {
+            category: "stale_readme".to_string(),
+            path: PathBuf::from("lib.rs"),
+            line: Some(1),
+            symbol: None,
+            detail: "stale".to_string(),
+        },
+        DocIssue {
+            category: "undocumented_type".to_string(),
+            path: PathBuf::from("lib.rs"),
+            line: Some(1),
+            symbol: Some("wide".to_string()),
+            detail: "types".to_string(),
+        },
+    ];
+    assert!(apply_fixes(&issues, &config)?.is_empty());
+    assert_eq!(fs::read_to_string(root.join("lib.rs"))?, source);
+    Ok(())
+}
+
 fn stage_and_commit(repo: &Repository, paths: &[&str], message: &str) {
+    let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+    stage_and_commit_as(repo, paths, message, &sig);
+}
+
+fn stage_and_commit_days_ago(repo: &Repository, paths: &[&str], message: &str, days: i64) {
+    let now = git2::Signature::now("Test", "test@example.com").expect("signature");
+    let when = git2::Time::new(now.when().seconds() - days * 24 * 60 * 60, 0);
+    let sig = git2::Signature::new("Test", "test@example.com", &when).expect("signature");
+    stage_and_commit_as(repo, paths, message, &sig);
+}
+
+fn stage_and_commit_as(repo: &Repository, paths: &[&str], message: &str, sig: &git2::Signature) {
     let mut index = repo.index().expect("index");
     for path in paths {
         index.add_path(Path::new(path)).expect("add path");
@@ -330,7 +1264,6 @@ fn stage_and_commit(repo: &Repository, paths: &[&str], message: &str) {
     index.write().expect("write index");
     let tree_id = index.write_tree().expect("write tree");
     let tree = repo.find_tree(tree_id).expect("find tree");
-    let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
 
     let parents: Vec<git2::Commit> = repo
         .head()
@@ -340,6 +1273,50 @@ fn stage_and_commit(repo: &Repository, paths: &[&str], message: &str) {
         .collect();
 
     let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
-    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+    repo.commit(Some("HEAD"), sig, sig, message, &tree, &parent_refs)
         .expect("commit");
 }
+
+#[test]
+fn check_python_module_docstring_accepts_leading_docstring() {
+    let root = Path::new("/repo");
+    let path = root.join("pkg/__init__.py");
+
+    let documented =
+        "#!/usr/bin/env python\n# -*- coding: utf-8 -*-\n\n\"\"\"Package docs.\"\"\"\nimport os\n";
+    assert!(check_python_module_docstring(documented, &path, root).is_none());
+    assert!(check_python_module_docstring("r'''Raw docs.'''\n", &path, root).is_none());
+
+    let issue = check_python_module_docstring("import os\n\"\"\"Too late.\"\"\"\n", &path, root)
+        .expect("expected missing module docstring");
+    assert_eq!(issue.category, "missing_module_docstring");
+    assert_eq!(issue.line, Some(1));
+    assert_eq!(issue.path, PathBuf::from("pkg/__init__.py"));
+    assert!(check_python_module_docstring("# just a comment\n", &path, root).is_some());
+    assert!(check_python_module_docstring("", &path, root).is_none());
+    assert!(check_python_module_docstring(" \n\t\n", &path, root).is_none());
+}
+
+#[test]
+fn audit_reports_missing_module_docstring_only_for_package_init() -> Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    fs::create_dir_all(root.join("pkg"))?;
+    fs::write(root.join("pkg/__init__.py"), "from .core import run\n")?;
+    fs::write(root.join("pkg/core.py"), "import os\n")?;
+    fs::create_dir_all(root.join("pkg/empty"))?;
+    fs::write(root.join("pkg/empty/__init__.py"), "\n")?;
+
+    let mut config = DocAuditConfig::new(root.to_path_buf());
+    config.complexity_threshold = usize::MAX;
+
+    let result = run_audit(&config)?;
+    let flagged: Vec<&PathBuf> = result
+        .documentation_issues
+        .iter()
+        .filter(|issue| issue.category == "missing_module_docstring")
+        .map(|issue| &issue.path)
+        .collect();
+    assert_eq!(flagged, vec![&PathBuf::from("pkg/__init__.py")]);
+    Ok(())
+}
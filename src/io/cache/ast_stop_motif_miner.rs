@@ -17,12 +17,15 @@ use crate::lang::registry::detect_language_from_path;
 
 /// Phase 3: AST Stop-Motif Miner using tree-sitter analysis
 pub struct AstStopMotifMiner {
-    /// Language adapters for AST parsing
+    /// Language adapters keyed by language key, used when no query extractor exists
     language_adapters: HashMap<String, Box<dyn LanguageAdapter>>,
 
     /// Pattern extractor for AST analysis
     pattern_extractor: AstPatternExtractor,
 
+    /// Query-based extractors keyed by language key
+    query_extractors: HashMap<String, AstPatternExtractor>,
+
     /// Frequency thresholds for pattern selection
     frequency_thresholds: PatternThresholds,
 }
@@ -35,31 +38,41 @@ impl AstStopMotifMiner {
 
         // Initialize language adapters
         if let Ok(python_adapter) = PythonLanguageAdapter::new() {
-            language_adapters.insert("python".to_string(), Box::new(python_adapter));
+            language_adapters.insert("py".to_string(), Box::new(python_adapter));
         }
 
         if let Ok(js_adapter) = JavaScriptLanguageAdapter::new() {
-            language_adapters.insert("javascript".to_string(), Box::new(js_adapter));
+            language_adapters.insert("js".to_string(), Box::new(js_adapter));
         }
 
         if let Ok(ts_adapter) = TypeScriptLanguageAdapter::new() {
-            language_adapters.insert("typescript".to_string(), Box::new(ts_adapter));
+            language_adapters.insert("ts".to_string(), Box::new(ts_adapter));
         }
 
         if let Ok(rust_adapter) = RustLanguageAdapter::new() {
-            language_adapters.insert("rust".to_string(), Box::new(rust_adapter));
+            language_adapters.insert("rs".to_string(), Box::new(rust_adapter));
         }
 
         if let Ok(go_adapter) = GoLanguageAdapter::new() {
             language_adapters.insert("go".to_string(), Box::new(go_adapter));
         }
 
+        let query_extractors = ["py", "js", "ts", "rs", "go"]
+            .into_iter()
+            .filter_map(|key| {
+                AstPatternExtractor::with_builtin_query(key)
+                    .ok()
+                    .map(|extractor| (key.to_string(), extractor))
+            })
+            .collect();
+
         let config = AstExtractionConfig::default();
         let thresholds = PatternThresholds::default();
 
         Self {
             language_adapters,
             pattern_extractor: AstPatternExtractor::new(config),
+            query_extractors,
             frequency_thresholds: thresholds,
         }
     }
@@ -70,16 +83,57 @@ impl AstStopMotifMiner {
         functions: &[FunctionInfo],
     ) -> Result<Vec<AstStopMotifEntry>> {
         let start_time = std::time::Instant::now();
+        let (all_patterns, languages_processed) = self.collect_patterns(functions);
+
+        // Analyze pattern frequencies
+        self.pattern_extractor
+            .analyze_pattern_frequencies(&all_patterns);
+
+        // Select stop-motifs based on frequency thresholds
+        let stop_motifs = self.select_stop_motifs(&all_patterns)?;
+
+        let duration = start_time.elapsed();
+        println!(
+            "AST stop-motif mining completed in {:?}ms",
+            duration.as_millis()
+        );
+        println!(
+            "Found {} AST patterns, selected {} as stop-motifs",
+            all_patterns.len(),
+            stop_motifs.len()
+        );
+        println!("Languages processed: {:?}", languages_processed);
+
+        Ok(stop_motifs)
+    }
+
+    /// Extract patterns from every function along with the languages processed.
+    ///
+    /// Each function is handled by exactly one extractor: the tree-sitter query
+    /// for its language when one exists, otherwise the language adapter, so a
+    /// pattern found by both is only counted once.
+    pub(crate) fn collect_patterns(
+        &mut self,
+        functions: &[FunctionInfo],
+    ) -> (Vec<AstPattern>, HashSet<String>) {
         let mut all_patterns = Vec::new();
         let mut languages_processed = HashSet::new();
 
-        // Extract patterns from all functions
         for function in functions {
             let language = self.detect_language(&function.file_path);
+            if let Some(extractor) = self.query_extractors.get(&language) {
+                languages_processed.insert(language);
+                match extractor.extract_patterns(&function.source_code) {
+                    Ok(patterns) => all_patterns.extend(patterns),
+                    Err(e) => eprintln!("Failed to run AST queries on {}: {:?}", function.id, e),
+                }
+                continue;
+            }
+
             let Some(adapter) = self.language_adapters.get_mut(&language) else {
                 continue;
             };
-            languages_processed.insert(language.clone());
+            languages_processed.insert(language);
 
             let Ok(parse_index) = adapter.parse_source(&function.source_code, &function.file_path)
             else {
@@ -96,26 +150,7 @@ impl AstStopMotifMiner {
             }
         }
 
-        // Analyze pattern frequencies
-        self.pattern_extractor
-            .analyze_pattern_frequencies(&all_patterns);
-
-        // Select stop-motifs based on frequency thresholds
-        let stop_motifs = self.select_stop_motifs(&all_patterns)?;
-
-        let duration = start_time.elapsed();
-        println!(
-            "AST stop-motif mining completed in {:?}ms",
-            duration.as_millis()
-        );
-        println!(
-            "Found {} AST patterns, selected {} as stop-motifs",
-            all_patterns.len(),
-            stop_motifs.len()
-        );
-        println!("Languages processed: {:?}", languages_processed);
-
-        Ok(stop_motifs)
+        (all_patterns, languages_processed)
     }
 
     /// Detect programming language from file path
@@ -3,6 +3,8 @@
 mod ast_stop_motif_miner;
 pub mod language_adapters;
 mod pattern_miner;
+#[cfg(feature = "persistent-cache")]
+pub mod persistent;
 pub mod types;
 
 use std::collections::{HashMap, HashSet};
@@ -15,6 +17,7 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use crate::core::errors::{Result, ValknutError, ValknutResultExt};
+use crate::core::pipeline::AnalysisResults;
 
 // Re-export types from submodules
 pub use language_adapters::{
@@ -29,12 +32,21 @@ pub use types::{
 pub use ast_stop_motif_miner::AstStopMotifMiner;
 pub use pattern_miner::PatternMiner;
 
+#[cfg(feature = "persistent-cache")]
+pub use persistent::PersistentLshCache;
+
+/// Current on-disk format version of [`StopMotifCache`].
+pub const STOP_MOTIF_CACHE_VERSION: u32 = 2;
+
 /// Phase 3 Stop-Motifs Cache for automatic boilerplate pattern detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StopMotifCache {
     /// Cache format version for migration support
     pub version: u32,
 
+    /// Valknut release that mined this cache (added in v2)
+    pub valknut_version: String,
+
     /// K-gram size used for token analysis
     pub k_gram_size: usize,
 
@@ -47,6 +59,11 @@ pub struct StopMotifCache {
     /// AST-based patterns from tree-sitter analysis
     pub ast_patterns: Vec<AstStopMotifEntry>,
 
+    /// `ast_patterns` grouped by language; derived, so rebuilt on load instead of stored.
+    /// Refresh it with [`StopMotifCache::index_ast_patterns`] after editing `ast_patterns`.
+    #[serde(skip)]
+    pub ast_patterns_by_language: HashMap<String, Vec<AstStopMotifEntry>>,
+
     /// Last cache update timestamp
     pub last_updated: u64, // Unix timestamp
 
@@ -57,6 +74,65 @@ pub struct StopMotifCache {
     pub mining_stats: MiningStats,
 }
 
+/// Snapshot of the v1 [`StopMotifCache`] schema, kept for migrating old cache files.
+///
+/// Do not add fields here; future schema changes belong on [`StopMotifCache`].
+#[derive(Debug, Clone, Deserialize)]
+struct StopMotifCacheV1 {
+    version: u32,
+    k_gram_size: usize,
+    token_grams: Vec<StopMotifEntry>,
+    pdg_motifs: Vec<StopMotifEntry>,
+    ast_patterns: Vec<AstStopMotifEntry>,
+    last_updated: u64,
+    codebase_signature: String,
+    mining_stats: MiningStats,
+}
+
+/// Migration from the v1 schema for [`StopMotifCache`].
+impl From<StopMotifCacheV1> for StopMotifCache {
+    /// Upgrades a v1 cache, marking the producing release as unknown.
+    fn from(v1: StopMotifCacheV1) -> Self {
+        Self {
+            version: STOP_MOTIF_CACHE_VERSION,
+            valknut_version: "unknown".to_string(),
+            k_gram_size: v1.k_gram_size,
+            token_grams: v1.token_grams,
+            pdg_motifs: v1.pdg_motifs,
+            ast_patterns_by_language: StopMotifCache::index_ast_patterns(&v1.ast_patterns),
+            ast_patterns: v1.ast_patterns,
+            last_updated: v1.last_updated,
+            codebase_signature: v1.codebase_signature,
+            mining_stats: v1.mining_stats,
+        }
+    }
+}
+
+/// AST pattern lookup methods for [`StopMotifCache`].
+impl StopMotifCache {
+    /// Group AST patterns by language, preserving their order within each language.
+    pub fn index_ast_patterns(
+        patterns: &[AstStopMotifEntry],
+    ) -> HashMap<String, Vec<AstStopMotifEntry>> {
+        let mut by_language: HashMap<String, Vec<AstStopMotifEntry>> = HashMap::new();
+        for pattern in patterns {
+            by_language
+                .entry(pattern.language.clone())
+                .or_default()
+                .push(pattern.clone());
+        }
+        by_language
+    }
+
+    /// AST patterns mined for `language`, empty when none were found.
+    pub fn ast_patterns_for_language(&self, language: &str) -> &[AstStopMotifEntry] {
+        self.ast_patterns_by_language
+            .get(language)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
 /// Individual stop-motif entry with frequency and weight information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StopMotifEntry {
@@ -167,6 +243,11 @@ pub struct MiningStats {
 
     /// Languages processed
     pub languages_processed: HashSet<String>,
+
+    /// Function entities mined per language, keyed by the language detected
+    /// from each file extension
+    #[serde(default)]
+    pub entities_per_language: HashMap<String, usize>,
 }
 
 /// Stop-Motifs Cache Manager with refresh and invalidation logic
@@ -337,7 +418,33 @@ impl StopMotifCacheManager {
             )
         })?;
 
-        serde_json::from_str(&content).map_json_err("cache file content")
+        self.try_migrate(&content)
+    }
+
+    /// Parse cache JSON, upgrading and re-saving caches written in an older format.
+    fn try_migrate(&self, raw_json: &str) -> Result<StopMotifCache> {
+        let current_err = match serde_json::from_str::<StopMotifCache>(raw_json) {
+            Ok(mut cache) => {
+                cache.ast_patterns_by_language =
+                    StopMotifCache::index_ast_patterns(&cache.ast_patterns);
+                return Ok(cache);
+            }
+            Err(err) => err,
+        };
+
+        let Ok(v1) = serde_json::from_str::<StopMotifCacheV1>(raw_json) else {
+            return Err(current_err).map_json_err("cache file content");
+        };
+
+        tracing::info!(
+            "Migrating stop-motifs cache from v{} to v{}",
+            v1.version,
+            STOP_MOTIF_CACHE_VERSION
+        );
+        let cache = StopMotifCache::from(v1);
+        self.save_cache(&cache)?;
+
+        Ok(cache)
     }
 
     /// Save cache to disk atomically
@@ -378,6 +485,9 @@ impl StopMotifCacheManager {
     }
 
     /// Get the cache file path
+    ///
+    /// The name predates format versioning and is kept so older caches are
+    /// found and migrated; the format version lives inside the file.
     fn get_cache_path(&self) -> PathBuf {
         self.cache_dir.join("stop_motifs.v1.json")
     }
@@ -428,6 +538,62 @@ pub struct CodebaseInfo {
     pub file_info: HashMap<String, FileInfo>,
 }
 
+/// Construction helpers for [`CodebaseInfo`].
+impl CodebaseInfo {
+    /// Build codebase info from the refactoring candidates of an analysis run.
+    ///
+    /// Candidate paths are resolved against `root`. Each candidate becomes a
+    /// function whose source is its line range (the whole file when it has
+    /// none); every referenced file is read once, hashed with SHA-256 and
+    /// counted towards `total_lines`.
+    pub fn from_analysis_results(results: &AnalysisResults, root: &Path) -> Result<Self> {
+        let mut sources: HashMap<&str, String> = HashMap::new();
+        let mut file_info = HashMap::new();
+        let mut functions = Vec::with_capacity(results.refactoring_candidates.len());
+
+        for candidate in &results.refactoring_candidates {
+            let path = candidate.file_path.as_str();
+            if !sources.contains_key(path) {
+                let full_path = root.join(path);
+                let content = fs::read_to_string(&full_path).map_err(|e| {
+                    ValknutError::io(format!("Failed to read {}", full_path.display()), e)
+                })?;
+                file_info.insert(
+                    path.to_string(),
+                    FileInfo {
+                        line_count: content.lines().count(),
+                        content_hash: Sha256::digest(content.as_bytes()).to_vec(),
+                    },
+                );
+                sources.insert(path, content);
+            }
+
+            let content = &sources[path];
+            let source_code = match candidate.line_range {
+                Some((start, end)) => content
+                    .lines()
+                    .skip(start.saturating_sub(1))
+                    .take(end.saturating_sub(start) + 1)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                None => content.clone(),
+            };
+            functions.push(FunctionInfo {
+                id: candidate.entity_id.clone(),
+                line_count: source_code.lines().count(),
+                source_code,
+                file_path: candidate.file_path.clone(),
+            });
+        }
+
+        Ok(Self {
+            functions,
+            total_lines: file_info.values().map(|info| info.line_count).sum(),
+            file_info,
+        })
+    }
+}
+
 /// Information about a function for pattern analysis
 #[derive(Debug, Clone)]
 pub struct FunctionInfo {
@@ -11,8 +11,10 @@ use sha2::{Digest, Sha256};
 use super::{
     AstPatternCategory, AstStopMotifEntry, AstStopMotifMiner, CacheRefreshPolicy, CodebaseInfo,
     FunctionInfo, MiningStats, PatternCategory, StopMotifCache, StopMotifEntry,
+    STOP_MOTIF_CACHE_VERSION,
 };
 use crate::core::errors::Result;
+use crate::lang::registry::detect_language_from_path;
 
 /// Pattern Mining Engine for extracting frequent k-grams and PDG motifs
 #[derive(Debug)]
@@ -76,6 +78,7 @@ impl PatternMiner {
             percentile_threshold: self.policy.stop_motif_percentile,
             mining_duration_ms: mining_duration,
             languages_processed: HashSet::new(), // Will be updated by AST mining
+            entities_per_language: Self::count_entities_per_language(codebase_info),
         };
 
         tracing::info!(
@@ -85,6 +88,10 @@ impl PatternMiner {
             mining_stats.stop_motifs_selected
         );
 
+        let mut language_breakdown: Vec<_> = mining_stats.entities_per_language.iter().collect();
+        language_breakdown.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        tracing::info!("Functions mined per language: {:?}", language_breakdown);
+
         // Mine AST patterns using the new AST Stop-Motif Miner
         let mut ast_miner = AstStopMotifMiner::new();
         let ast_patterns = ast_miner
@@ -109,7 +116,8 @@ impl PatternMiner {
             ast_patterns.iter().map(|p| p.language.clone()).collect();
 
         Ok(StopMotifCache {
-            version: 1,
+            version: STOP_MOTIF_CACHE_VERSION,
+            valknut_version: env!("CARGO_PKG_VERSION").to_string(),
             k_gram_size: self.policy.k_gram_size,
             token_grams: stop_motifs
                 .clone()
@@ -120,6 +128,7 @@ impl PatternMiner {
                 .into_iter()
                 .filter(|e| e.category != PatternCategory::TokenGram)
                 .collect(),
+            ast_patterns_by_language: StopMotifCache::index_ast_patterns(&ast_patterns),
             ast_patterns,
             last_updated: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
@@ -130,6 +139,17 @@ impl PatternMiner {
         })
     }
 
+    /// Count function entities per language, detected from each file extension.
+    fn count_entities_per_language(codebase_info: &CodebaseInfo) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for function in &codebase_info.functions {
+            *counts
+                .entry(detect_language_from_path(&function.file_path))
+                .or_insert(0) += 1;
+        }
+        counts
+    }
+
     /// Extract all patterns from the codebase
     fn extract_all_patterns(&mut self, codebase_info: &CodebaseInfo) -> Result<()> {
         // Process functions in parallel for performance
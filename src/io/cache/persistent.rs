@@ -0,0 +1,248 @@
+//! File-backed LSH cache that survives process restarts.
+//!
+//! Mirrors the [`LshCache`](crate::detectors::lsh::LshCache) API but stores
+//! tokens and MinHash signatures in a `sled` database, keyed by a stable
+//! xxh3 hash of the source so entries remain valid across runs.
+
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{debug, warn};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::core::errors::{Result, ValknutError};
+use crate::detectors::lsh::CacheStatistics;
+
+/// Name of the sled tree holding tokenized sources.
+const TOKEN_TREE: &str = "tokens";
+
+/// Name of the sled tree holding MinHash signatures.
+const SIGNATURE_TREE: &str = "signatures";
+
+/// Persistent cache for tokenization and signature operations.
+#[derive(Debug, Clone)]
+pub struct PersistentLshCache {
+    /// Underlying database handle, used for flushing
+    db: sled::Db,
+
+    /// Token cache: source_hash -> bincode(tokens)
+    tokens: sled::Tree,
+
+    /// Signature cache: (source_hash, num_hashes, shingle_size) -> bincode(signature)
+    signatures: sled::Tree,
+
+    /// Hit and miss counters for the current process
+    stats: Arc<RwLock<CacheStatistics>>,
+}
+
+/// Factory, caching, and lookup methods for [`PersistentLshCache`].
+impl PersistentLshCache {
+    /// Open (or create) the cache database in `dir`.
+    pub fn open(dir: &Path) -> Result<Self> {
+        let db = sled::open(dir).map_err(|e| {
+            ValknutError::cache(format!(
+                "Failed to open persistent cache at {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        let tokens = Self::open_tree(&db, TOKEN_TREE)?;
+        let signatures = Self::open_tree(&db, SIGNATURE_TREE)?;
+
+        Ok(Self {
+            db,
+            tokens,
+            signatures,
+            stats: Arc::new(RwLock::new(CacheStatistics::default())),
+        })
+    }
+
+    /// Get cached tokens for source code, or None if not cached
+    pub fn get_tokens(&self, source_code: &str) -> Option<Vec<String>> {
+        let key = Self::hash_source(source_code).to_be_bytes();
+        let tokens = Self::read(&self.tokens, &key);
+        self.record(|stats| {
+            if tokens.is_some() {
+                stats.token_hits += 1;
+            } else {
+                stats.token_misses += 1;
+            }
+        });
+        tokens
+    }
+
+    /// Cache tokens for source code
+    pub fn cache_tokens(&self, source_code: &str, tokens: Vec<String>) {
+        let key = Self::hash_source(source_code).to_be_bytes();
+        Self::write(&self.tokens, &key, &tokens);
+    }
+
+    /// Get cached signature, or None if not cached
+    pub fn get_signature(
+        &self,
+        source_code: &str,
+        num_hashes: usize,
+        shingle_size: usize,
+    ) -> Option<Vec<u64>> {
+        let key = Self::signature_key(source_code, num_hashes, shingle_size);
+        let signature = Self::read(&self.signatures, &key);
+        self.record(|stats| {
+            if signature.is_some() {
+                stats.signature_hits += 1;
+            } else {
+                stats.signature_misses += 1;
+            }
+        });
+        signature
+    }
+
+    /// Cache signature for source code and parameters
+    pub fn cache_signature(
+        &self,
+        source_code: &str,
+        num_hashes: usize,
+        shingle_size: usize,
+        signature: Vec<u64>,
+    ) {
+        let key = Self::signature_key(source_code, num_hashes, shingle_size);
+        Self::write(&self.signatures, &key, &signature);
+    }
+
+    /// Get cache statistics for this process
+    pub fn get_statistics(&self) -> CacheStatistics {
+        self.stats
+            .read()
+            .map(|stats| stats.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get cache sizes for monitoring
+    pub fn cache_sizes(&self) -> (usize, usize) {
+        (self.tokens.len(), self.signatures.len())
+    }
+
+    /// Remove all cached entries and reset statistics
+    pub fn clear(&self) -> Result<()> {
+        for tree in [&self.tokens, &self.signatures] {
+            tree.clear()
+                .map_err(|e| ValknutError::cache(format!("Failed to clear cache: {}", e)))?;
+        }
+        if let Ok(mut stats) = self.stats.write() {
+            *stats = CacheStatistics::default();
+        }
+        Ok(())
+    }
+
+    /// Flush pending writes to disk
+    pub fn flush(&self) -> Result<()> {
+        self.db
+            .flush()
+            .map(|_| ())
+            .map_err(|e| ValknutError::cache(format!("Failed to flush cache: {}", e)))
+    }
+
+    /// Opens a named tree within the database.
+    fn open_tree(db: &sled::Db, name: &str) -> Result<sled::Tree> {
+        db.open_tree(name)
+            .map_err(|e| ValknutError::cache(format!("Failed to open cache tree {name}: {e}")))
+    }
+
+    /// Reads and decodes a value, treating storage or decode failures as misses.
+    fn read<T: DeserializeOwned>(tree: &sled::Tree, key: &[u8]) -> Option<T> {
+        let bytes = match tree.get(key) {
+            Ok(bytes) => bytes?,
+            Err(e) => {
+                warn!("Persistent cache read failed: {}", e);
+                return None;
+            }
+        };
+        match bincode::deserialize(&bytes) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                debug!("Discarding undecodable persistent cache entry: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Encodes and stores a value, logging failures rather than propagating them.
+    fn write<T: Serialize>(tree: &sled::Tree, key: &[u8], value: &T) {
+        let result = bincode::serialize(value)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| tree.insert(key, bytes).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!("Persistent cache write failed: {}", e);
+        }
+    }
+
+    /// Applies an update to the statistics if the lock is available.
+    fn record(&self, update: impl FnOnce(&mut CacheStatistics)) {
+        if let Ok(mut stats) = self.stats.write() {
+            update(&mut stats);
+        }
+    }
+
+    /// Builds the signature key from the source hash and LSH parameters.
+    fn signature_key(source_code: &str, num_hashes: usize, shingle_size: usize) -> [u8; 24] {
+        let mut key = [0u8; 24];
+        key[..8].copy_from_slice(&Self::hash_source(source_code).to_be_bytes());
+        key[8..16].copy_from_slice(&(num_hashes as u64).to_be_bytes());
+        key[16..].copy_from_slice(&(shingle_size as u64).to_be_bytes());
+        key
+    }
+
+    /// Computes a hash of the source code that is stable across runs.
+    fn hash_source(source_code: &str) -> u64 {
+        xxh3_64(source_code.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn entries_survive_reopening_the_database() {
+        let dir = tempdir().unwrap();
+        {
+            let cache = PersistentLshCache::open(dir.path()).unwrap();
+            assert!(cache.get_tokens("fn demo()").is_none());
+            cache.cache_tokens("fn demo()", vec!["fn".into(), "demo".into()]);
+            cache.cache_signature("fn demo()", 4, 2, vec![1, 2, 3, 4]);
+            cache.flush().unwrap();
+        }
+
+        let cache = PersistentLshCache::open(dir.path()).unwrap();
+        assert_eq!(
+            cache.get_tokens("fn demo()"),
+            Some(vec!["fn".to_string(), "demo".to_string()])
+        );
+        assert_eq!(
+            cache.get_signature("fn demo()", 4, 2),
+            Some(vec![1, 2, 3, 4])
+        );
+        assert!(cache.get_signature("fn demo()", 8, 2).is_none());
+
+        let stats = cache.get_statistics();
+        assert_eq!(stats.token_hits, 1);
+        assert_eq!(stats.signature_hits, 1);
+        assert_eq!(stats.signature_misses, 1);
+    }
+
+    #[test]
+    fn clear_removes_entries_and_resets_stats() {
+        let dir = tempdir().unwrap();
+        let cache = PersistentLshCache::open(dir.path()).unwrap();
+        cache.cache_tokens("fn a()", vec!["fn".into()]);
+        cache.cache_signature("fn a()", 2, 2, vec![7, 8]);
+        cache.get_tokens("fn a()");
+        assert_eq!(cache.cache_sizes(), (1, 1));
+
+        cache.clear().unwrap();
+        assert_eq!(cache.cache_sizes(), (0, 0));
+        assert_eq!(cache.get_statistics().token_hits, 0);
+    }
+}
@@ -0,0 +1,29 @@
+; Stop-motif patterns for Go.
+;
+; Capture names select the pattern type: @node_type, @control_flow, @framework,
+; @token_sequence, or any other name for a structural subtree pattern.
+; Captures starting with an underscore only feed predicates.
+
+(if_statement) @control_flow
+(for_statement) @control_flow
+(expression_switch_statement) @control_flow
+(type_switch_statement) @control_flow
+(select_statement) @control_flow
+
+(if_statement
+  condition: (binary_expression
+    left: (identifier) @_err
+    right: (nil))
+  (#eq? @_err "err")) @framework
+(defer_statement) @framework
+(go_statement) @framework
+
+(import_declaration) @token_sequence
+
+(call_expression
+  function: (selector_expression
+    operand: (identifier)
+    field: (field_identifier))) @subtree
+
+(function_declaration) @node_type
+(method_declaration) @node_type
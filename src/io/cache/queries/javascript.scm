@@ -0,0 +1,32 @@
+; Stop-motif patterns for JavaScript.
+;
+; Capture names select the pattern type: @node_type, @control_flow, @framework,
+; @token_sequence, or any other name for a structural subtree pattern.
+; Captures starting with an underscore only feed predicates.
+
+(if_statement) @control_flow
+(for_statement) @control_flow
+(for_in_statement) @control_flow
+(while_statement) @control_flow
+(switch_statement) @control_flow
+(try_statement) @control_flow
+
+(await_expression) @framework
+
+(import_statement) @token_sequence
+(member_expression
+  object: (identifier) @_object
+  property: (property_identifier)
+  (#eq? @_object "console")) @token_sequence
+(call_expression
+  function: (identifier) @token_sequence
+  (#eq? @token_sequence "require"))
+
+(call_expression
+  function: (member_expression
+    object: (identifier)
+    property: (property_identifier))) @subtree
+
+(function_declaration) @node_type
+(arrow_function) @node_type
+(class_declaration) @node_type
@@ -0,0 +1,31 @@
+; Stop-motif patterns for Python.
+;
+; Capture names select the pattern type: @node_type, @control_flow, @framework,
+; @token_sequence, or any other name for a structural subtree pattern.
+; Captures starting with an underscore only feed predicates.
+
+(if_statement) @control_flow
+(for_statement) @control_flow
+(while_statement) @control_flow
+(try_statement) @control_flow
+(with_statement) @control_flow
+
+(decorator) @framework
+
+(if_statement
+  condition: (comparison_operator
+    (identifier) @_name)
+  (#eq? @_name "__name__")) @framework
+
+(import_statement) @token_sequence
+(import_from_statement) @token_sequence
+(raise_statement) @token_sequence
+(return_statement (none)) @token_sequence
+
+(call
+  function: (attribute
+    object: (identifier)
+    attribute: (identifier))) @subtree
+
+(function_definition) @node_type
+(class_definition) @node_type
@@ -0,0 +1,24 @@
+; Stop-motif patterns for Rust.
+;
+; Capture names select the pattern type: @node_type, @control_flow, @framework,
+; @token_sequence, or any other name for a structural subtree pattern.
+; Captures starting with an underscore only feed predicates.
+
+(if_expression) @control_flow
+(for_expression) @control_flow
+(while_expression) @control_flow
+(loop_expression) @control_flow
+(match_expression) @control_flow
+(try_expression) @control_flow
+
+(attribute_item) @framework
+
+(use_declaration) @token_sequence
+(macro_invocation
+  macro: (identifier) @token_sequence)
+
+(call_expression
+  function: (field_expression)) @subtree
+
+(function_item) @node_type
+(impl_item) @node_type
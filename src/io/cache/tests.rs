@@ -53,11 +53,13 @@ fn test_get_valid_cache_returns_none_when_expired() {
         .as_secs();
 
     let expired_cache = StopMotifCache {
-        version: 1,
+        version: STOP_MOTIF_CACHE_VERSION,
+        valknut_version: "test".to_string(),
         k_gram_size: policy.k_gram_size,
         token_grams: Vec::new(),
         pdg_motifs: Vec::new(),
         ast_patterns: Vec::new(),
+        ast_patterns_by_language: HashMap::new(),
         last_updated: now - (policy.max_age_days * 24 * 60 * 60) - 1,
         codebase_signature: signature,
         mining_stats: MiningStats::default(),
@@ -84,11 +86,13 @@ fn test_get_valid_cache_returns_none_on_large_signature_change() {
         .as_secs();
 
     let cache = StopMotifCache {
-        version: 1,
+        version: STOP_MOTIF_CACHE_VERSION,
+        valknut_version: "test".to_string(),
         k_gram_size: policy.k_gram_size,
         token_grams: Vec::new(),
         pdg_motifs: Vec::new(),
         ast_patterns: Vec::new(),
+        ast_patterns_by_language: HashMap::new(),
         last_updated: now,
         codebase_signature: signature,
         mining_stats: MiningStats::default(),
@@ -120,11 +124,13 @@ fn test_get_valid_cache_returns_cache_when_fresh() {
         .as_secs();
 
     let cache = StopMotifCache {
-        version: 1,
+        version: STOP_MOTIF_CACHE_VERSION,
+        valknut_version: "test".to_string(),
         k_gram_size: policy.k_gram_size,
         token_grams: Vec::new(),
         pdg_motifs: Vec::new(),
         ast_patterns: Vec::new(),
+        ast_patterns_by_language: HashMap::new(),
         last_updated: now,
         codebase_signature: signature,
         mining_stats: MiningStats::default(),
@@ -136,6 +142,97 @@ fn test_get_valid_cache_returns_cache_when_fresh() {
     assert!(result.is_some(), "expected fresh cache to remain valid");
 }
 
+#[test]
+fn test_load_cache_migrates_v1_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let manager = StopMotifCacheManager::new(temp_dir.path(), CacheRefreshPolicy::default());
+    let cache_path = manager.get_cache_path();
+
+    let v1 = json!({
+        "version": 1,
+        "k_gram_size": 9,
+        "token_grams": [{
+            "pattern": "if LOCAL_VAR == INT_LIT",
+            "support": 12,
+            "idf_score": 1.5,
+            "weight_multiplier": 0.2,
+            "category": "TokenGram"
+        }],
+        "pdg_motifs": [],
+        "ast_patterns": [],
+        "last_updated": 1699123456,
+        "codebase_signature": "abc123",
+        "mining_stats": MiningStats::default()
+    });
+    fs::write(&cache_path, serde_json::to_string_pretty(&v1).unwrap()).unwrap();
+
+    let migrated = manager.load_cache(&cache_path).unwrap();
+    assert_eq!(migrated.version, STOP_MOTIF_CACHE_VERSION);
+    assert_eq!(migrated.valknut_version, "unknown");
+    assert_eq!(migrated.token_grams.len(), 1);
+    assert_eq!(migrated.codebase_signature, "abc123");
+
+    // The upgraded cache is written back so the next load parses directly.
+    let rewritten = fs::read_to_string(&cache_path).unwrap();
+    let reloaded: StopMotifCache = serde_json::from_str(&rewritten).unwrap();
+    assert_eq!(reloaded.version, STOP_MOTIF_CACHE_VERSION);
+}
+
+#[test]
+fn test_load_cache_indexes_ast_patterns_by_language() {
+    let temp_dir = TempDir::new().unwrap();
+    let manager = StopMotifCacheManager::new(temp_dir.path(), CacheRefreshPolicy::default());
+    let cache_path = manager.get_cache_path();
+
+    let pattern = |name: &str, language: &str| AstStopMotifEntry {
+        pattern: name.to_string(),
+        support: 10,
+        idf_score: 1.0,
+        weight_multiplier: 0.2,
+        category: AstPatternCategory::NodeType,
+        language: language.to_string(),
+        metadata: HashMap::new(),
+    };
+    let ast_patterns = vec![
+        pattern("node_type:import", "py"),
+        pattern("node_type:macro", "rs"),
+        pattern("node_type:decorator", "py"),
+    ];
+    let cache = StopMotifCache {
+        version: STOP_MOTIF_CACHE_VERSION,
+        valknut_version: env!("CARGO_PKG_VERSION").to_string(),
+        k_gram_size: 9,
+        token_grams: Vec::new(),
+        pdg_motifs: Vec::new(),
+        ast_patterns_by_language: HashMap::new(),
+        ast_patterns,
+        last_updated: 1699123456,
+        codebase_signature: "abc123".to_string(),
+        mining_stats: MiningStats::default(),
+    };
+    fs::write(&cache_path, serde_json::to_string(&cache).unwrap()).unwrap();
+
+    let loaded = manager.load_cache(&cache_path).unwrap();
+    let python: Vec<&str> = loaded
+        .ast_patterns_for_language("py")
+        .iter()
+        .map(|entry| entry.pattern.as_str())
+        .collect();
+    assert_eq!(python, vec!["node_type:import", "node_type:decorator"]);
+    assert_eq!(loaded.ast_patterns_for_language("rs").len(), 1);
+    assert!(loaded.ast_patterns_for_language("go").is_empty());
+}
+
+#[test]
+fn test_load_cache_rejects_unrecognized_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let manager = StopMotifCacheManager::new(temp_dir.path(), CacheRefreshPolicy::default());
+    let cache_path = manager.get_cache_path();
+    fs::write(&cache_path, r#"{"version": 1, "k_gram_size": 9}"#).unwrap();
+
+    assert!(manager.load_cache(&cache_path).is_err());
+}
+
 #[test]
 fn test_pattern_miner_extracts_kgrams_and_motifs() {
     let mut policy = CacheRefreshPolicy::default();
@@ -227,6 +324,68 @@ fn test_estimate_change_percentage_detects_difference() {
     );
 }
 
+#[test]
+fn test_builtin_queries_compile_for_supported_languages() {
+    for language in ["python", "rust", "javascript", "typescript", "go"] {
+        let extractor = AstPatternExtractor::with_builtin_query(language);
+        assert!(extractor.is_ok(), "query for {language}: {extractor:?}");
+    }
+    assert!(AstPatternExtractor::with_builtin_query("cobol").is_err());
+}
+
+#[test]
+fn test_builtin_python_query_extracts_patterns() {
+    let extractor = AstPatternExtractor::with_builtin_query("python").unwrap();
+    let source = "import os\n\ndef load(path):\n    if not path:\n        return None\n    return os.path.join(path, 'x')\n";
+
+    let patterns = extractor.extract_patterns(source).unwrap();
+    let ids: HashSet<&str> = patterns.iter().map(|p| p.id.as_str()).collect();
+
+    assert!(ids.contains("token_seq:import_os"));
+    assert!(ids.contains("token_seq:return_None"));
+    assert!(ids.contains("node_type:function_definition"));
+    assert!(patterns
+        .iter()
+        .any(|p| p.pattern_type == AstPatternType::ControlFlowPattern));
+    assert!(patterns.iter().all(|p| p.language == "python"));
+}
+
+#[test]
+fn test_from_query_file_uses_capture_names() {
+    let temp_dir = TempDir::new().unwrap();
+    let query_path = temp_dir.path().join("calls.scm");
+    fs::write(
+        &query_path,
+        "(call_expression function: (identifier) @_fn (#eq? @_fn \"unwrap_or\")) @fallback",
+    )
+    .unwrap();
+
+    let extractor = AstPatternExtractor::from_query_file("rust", &query_path).unwrap();
+    let patterns = extractor
+        .extract_patterns("fn f() { unwrap_or(1); other(2); }")
+        .unwrap();
+
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].pattern_type, AstPatternType::SubtreePattern);
+    assert_eq!(
+        patterns[0].subtree_signature.as_deref(),
+        Some("call_expression(identifier arguments)")
+    );
+}
+
+#[test]
+fn test_from_query_file_rejects_invalid_query() {
+    let temp_dir = TempDir::new().unwrap();
+    let query_path = temp_dir.path().join("broken.scm");
+    fs::write(&query_path, "(not_a_real_node) @x").unwrap();
+
+    assert!(AstPatternExtractor::from_query_file("python", &query_path).is_err());
+    assert!(
+        AstPatternExtractor::from_query_file("python", &temp_dir.path().join("missing.scm"))
+            .is_err()
+    );
+}
+
 #[test]
 fn test_ast_stop_motif_miner_extracts_patterns() -> Result<()> {
     let mut miner = AstStopMotifMiner::new();
@@ -254,10 +413,33 @@ fn test_ast_stop_motif_miner_extracts_patterns() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_ast_stop_motif_miner_counts_each_function_once() {
+    let mut miner = AstStopMotifMiner::new();
+    // Both the Python query and the Python adapter emit `token_seq:import_os`.
+    let functions = vec![FunctionInfo {
+        id: "py_func".to_string(),
+        source_code: "import os\n\ndef load(path):\n    return os.path.join(path, 'x')\n"
+            .to_string(),
+        file_path: "load.py".to_string(),
+        line_count: 4,
+    }];
+
+    let (patterns, languages) = miner.collect_patterns(&functions);
+    let support = patterns
+        .iter()
+        .filter(|pattern| pattern.id == "token_seq:import_os")
+        .count();
+
+    assert_eq!(support, 1);
+    assert_eq!(languages, HashSet::from(["py".to_string()]));
+}
+
 #[test]
 fn test_stop_motif_cache_serialization() {
     let cache = StopMotifCache {
-        version: 1,
+        version: STOP_MOTIF_CACHE_VERSION,
+        valknut_version: "test".to_string(),
         k_gram_size: 9,
         token_grams: vec![
             StopMotifEntry {
@@ -311,6 +493,7 @@ fn test_stop_motif_cache_serialization() {
                 metadata: HashMap::new(),
             },
         ],
+        ast_patterns_by_language: HashMap::new(),
         last_updated: 1699123456,
         codebase_signature: "abc123def456".to_string(),
         mining_stats: MiningStats {
@@ -326,12 +509,16 @@ fn test_stop_motif_cache_serialization() {
             languages_processed: ["python".to_string(), "rust".to_string()]
                 .into_iter()
                 .collect(),
+            entities_per_language: HashMap::from([
+                ("python".to_string(), 900),
+                ("rust".to_string(), 600),
+            ]),
         },
     };
 
     // Test serialization
     let json = serde_json::to_string_pretty(&cache).expect("Failed to serialize cache");
-    assert!(json.contains("\"version\": 1"));
+    assert!(json.contains("\"version\": 2"));
     assert!(json.contains("\"k_gram_size\": 9"));
     assert!(json.contains("if LOCAL_VAR == INT_LIT"));
     assert!(json.contains("control:branch"));
@@ -339,7 +526,7 @@ fn test_stop_motif_cache_serialization() {
     // Test deserialization
     let deserialized: StopMotifCache =
         serde_json::from_str(&json).expect("Failed to deserialize cache");
-    assert_eq!(deserialized.version, 1);
+    assert_eq!(deserialized.version, STOP_MOTIF_CACHE_VERSION);
     assert_eq!(deserialized.token_grams.len(), 2);
     assert_eq!(deserialized.pdg_motifs.len(), 2);
     assert_eq!(deserialized.mining_stats.functions_analyzed, 1500);
@@ -470,9 +657,17 @@ fn test_pattern_miner_stop_motif_selection() -> Result<()> {
     let cache = miner.mine_stop_motifs(&codebase_info)?;
 
     // Verify cache structure
-    assert_eq!(cache.version, 1);
+    assert_eq!(cache.version, STOP_MOTIF_CACHE_VERSION);
+    assert_eq!(cache.valknut_version, env!("CARGO_PKG_VERSION"));
     assert_eq!(cache.mining_stats.functions_analyzed, 3);
     assert!(cache.mining_stats.stop_motifs_selected > 0);
+    assert_eq!(
+        cache.mining_stats.entities_per_language,
+        HashMap::from([("rs".to_string(), 3)])
+    );
+
+    let indexed: usize = cache.ast_patterns_by_language.values().map(Vec::len).sum();
+    assert_eq!(indexed, cache.ast_patterns.len());
 
     // Should have both token grams and motifs
     assert!(!cache.token_grams.is_empty() || !cache.pdg_motifs.is_empty());
@@ -828,3 +1023,49 @@ fn test_cache_debug() {
     let debug_str = format!("{:?}", cache);
     assert_eq!(debug_str, "Cache");
 }
+
+#[test]
+fn test_codebase_info_from_analysis_results_reads_candidate_sources() {
+    let root = tempdir().unwrap();
+    fs::create_dir_all(root.path().join("src")).unwrap();
+    let content = "fn first() {\n    let a = 1;\n}\n\nfn second() {\n    let b = 2;\n}\n";
+    fs::write(root.path().join("src/lib.rs"), content).unwrap();
+
+    let mut results = crate::core::pipeline::AnalysisResults::empty();
+    for (id, range) in [("first", (1, 3)), ("second", (5, 7))] {
+        results
+            .refactoring_candidates
+            .push(crate::core::pipeline::RefactoringCandidate {
+                entity_id: id.to_string(),
+                name: id.to_string(),
+                file_path: "src/lib.rs".to_string(),
+                line_range: Some(range),
+                priority: crate::core::scoring::Priority::Medium,
+                score: 0.5,
+                confidence: 0.8,
+                issues: Vec::new(),
+                suggestions: Vec::new(),
+                issue_count: 0,
+                suggestion_count: 0,
+                coverage_percentage: None,
+            });
+    }
+
+    let info = CodebaseInfo::from_analysis_results(&results, root.path()).unwrap();
+
+    assert_eq!(info.functions.len(), 2);
+    assert_eq!(
+        info.functions[1].source_code,
+        "fn second() {\n    let b = 2;\n}"
+    );
+    assert_eq!(info.functions[1].line_count, 3);
+    assert_eq!(info.total_lines, 7);
+    assert_eq!(info.file_info.len(), 1);
+    assert_eq!(
+        info.file_info["src/lib.rs"].content_hash,
+        Sha256::digest(content.as_bytes()).to_vec()
+    );
+
+    results.refactoring_candidates[0].file_path = "src/missing.rs".to_string();
+    assert!(CodebaseInfo::from_analysis_results(&results, root.path()).is_err());
+}
@@ -1,6 +1,23 @@
 //! Type definitions for cache and pattern analysis
 
 use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator};
+
+use crate::core::errors::{Result, ValknutError};
+use crate::lang::registry::{create_parser_for_language, get_tree_sitter_language, language_info};
+
+/// Built-in stop-motif queries keyed by canonical language key.
+const BUILTIN_QUERIES: &[(&str, &str)] = &[
+    ("py", include_str!("queries/python.scm")),
+    ("rs", include_str!("queries/rust.scm")),
+    ("js", include_str!("queries/javascript.scm")),
+    ("ts", include_str!("queries/typescript.scm")),
+    ("go", include_str!("queries/go.scm")),
+];
 
 /// AST pattern extracted from tree-sitter analysis
 #[derive(Debug, Clone)]
@@ -60,6 +77,22 @@ pub struct AstPatternExtractor {
 
     /// Pattern extraction configuration
     pub config: AstExtractionConfig,
+
+    /// Tree-sitter query used by [`AstPatternExtractor::extract_patterns`]
+    query: Option<Arc<CompiledQuery>>,
+}
+
+/// Tree-sitter query compiled for a specific language.
+#[derive(Debug)]
+struct CompiledQuery {
+    /// Canonical language key used to create parsers
+    language_key: &'static str,
+
+    /// Lowercase language name recorded on extracted patterns
+    language_name: String,
+
+    /// Compiled query
+    query: Query,
 }
 
 /// Factory and frequency analysis methods for [`AstPatternExtractor`].
@@ -71,7 +104,89 @@ impl AstPatternExtractor {
             subtree_frequencies: HashMap::new(),
             token_sequence_frequencies: HashMap::new(),
             config,
+            query: None,
+        }
+    }
+
+    /// Create an extractor that matches patterns from a tree-sitter `.scm` query file.
+    pub fn from_query_file(language: &str, query_path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(query_path).map_err(|e| {
+            ValknutError::io(
+                format!("Failed to read query file: {}", query_path.display()),
+                e,
+            )
+        })?;
+        Self::from_query_source(language, &source)
+    }
+
+    /// Create an extractor using the built-in query for `language`.
+    pub fn with_builtin_query(language: &str) -> Result<Self> {
+        let key = language_info(language).map(|info| info.key);
+        let source = BUILTIN_QUERIES
+            .iter()
+            .find(|(builtin, _)| Some(*builtin) == key)
+            .map(|(_, source)| *source)
+            .ok_or_else(|| {
+                ValknutError::unsupported(format!("No built-in AST query for: {}", language))
+            })?;
+        Self::from_query_source(language, source)
+    }
+
+    /// Create an extractor from tree-sitter query source text.
+    pub fn from_query_source(language: &str, source: &str) -> Result<Self> {
+        let info = language_info(language).ok_or_else(|| {
+            ValknutError::unsupported(format!("Unsupported query language: {}", language))
+        })?;
+        let ts_language = get_tree_sitter_language(info.key)?;
+        let query = Query::new(&ts_language, source)
+            .map_err(|e| ValknutError::parse(info.key, format!("Invalid AST query: {}", e)))?;
+
+        let mut extractor = Self::new(AstExtractionConfig::default());
+        extractor.query = Some(Arc::new(CompiledQuery {
+            language_key: info.key,
+            language_name: info.name.to_ascii_lowercase(),
+            query,
+        }));
+        Ok(extractor)
+    }
+
+    /// Extract patterns by running the configured query over `source_code`.
+    ///
+    /// Each capture becomes one pattern; its name selects the pattern type
+    /// (`node_type`, `control_flow`, `framework`, `token_sequence`, anything
+    /// else is a subtree pattern). Captures prefixed with `_` are ignored.
+    /// Returns no patterns when the extractor has no query.
+    pub fn extract_patterns(&self, source_code: &str) -> Result<Vec<AstPattern>> {
+        let Some(compiled) = self.query.as_deref() else {
+            return Ok(Vec::new());
+        };
+
+        let mut parser = create_parser_for_language(compiled.language_key)?;
+        let tree = parser.parse(source_code, None).ok_or_else(|| {
+            ValknutError::parse(compiled.language_key, "Failed to parse source code")
+        })?;
+
+        let capture_names = compiled.query.capture_names();
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&compiled.query, tree.root_node(), source_code.as_bytes());
+
+        let mut patterns = Vec::new();
+        while let Some(query_match) = matches.next() {
+            for capture in query_match.captures {
+                let name = capture_names[capture.index as usize];
+                if name.starts_with('_') {
+                    continue;
+                }
+                patterns.push(pattern_from_capture(
+                    name,
+                    capture.node,
+                    source_code,
+                    &compiled.language_name,
+                ));
+            }
         }
+
+        Ok(patterns)
     }
 
     /// Analyze frequencies of all extracted patterns
@@ -120,6 +235,73 @@ impl AstPatternExtractor {
     }
 }
 
+/// Build an [`AstPattern`] from a single query capture.
+fn pattern_from_capture(
+    capture_name: &str,
+    node: Node<'_>,
+    source_code: &str,
+    language: &str,
+) -> AstPattern {
+    let kind = node.kind().to_string();
+    let mut pattern = AstPattern {
+        id: String::new(),
+        pattern_type: AstPatternType::SubtreePattern,
+        node_type: None,
+        subtree_signature: None,
+        token_sequence: None,
+        language: language.to_string(),
+        metadata: HashMap::new(),
+    };
+
+    match capture_name {
+        "node_type" => {
+            pattern.pattern_type = AstPatternType::NodeType;
+            pattern.id = format!("node_type:{}", kind);
+            pattern.node_type = Some(kind);
+        }
+        "token_sequence" => {
+            let text = node
+                .utf8_text(source_code.as_bytes())
+                .unwrap_or_default()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            pattern.pattern_type = AstPatternType::TokenSequence;
+            pattern.id = format!("token_seq:{}", text.replace(' ', "_"));
+            pattern.token_sequence = Some(text);
+        }
+        _ => {
+            pattern.pattern_type = match capture_name {
+                "control_flow" => AstPatternType::ControlFlowPattern,
+                "framework" => AstPatternType::FrameworkPattern,
+                _ => AstPatternType::SubtreePattern,
+            };
+            let signature = subtree_signature(node);
+            pattern.id = format!("{}:{}", capture_name, signature);
+            pattern.subtree_signature = Some(signature);
+        }
+    }
+
+    pattern
+        .metadata
+        .insert("capture".to_string(), capture_name.into());
+    pattern
+}
+
+/// Describe a node by its kind and the kinds of its named children.
+fn subtree_signature(node: Node<'_>) -> String {
+    let mut cursor = node.walk();
+    let children: Vec<&str> = node
+        .named_children(&mut cursor)
+        .map(|child| child.kind())
+        .collect();
+    if children.is_empty() {
+        node.kind().to_string()
+    } else {
+        format!("{}({})", node.kind(), children.join(" "))
+    }
+}
+
 /// Configuration for AST pattern extraction
 #[derive(Debug, Clone)]
 pub struct AstExtractionConfig {
@@ -0,0 +1,175 @@
+//! Spreadsheet-friendly CSV export of refactoring candidates.
+
+use crate::core::pipeline::{AnalysisResults, RefactoringCandidate};
+
+/// Column names of the CSV export, in order.
+pub const CSV_COLUMNS: &[&str] = &[
+    "file_path",
+    "entity_name",
+    "entity_kind",
+    "refactoring_score",
+    "priority",
+    "issue_codes",
+    "suggestion_codes",
+    "cyclomatic_complexity",
+    "cognitive_complexity",
+    "clone_mass",
+    "coverage_ratio",
+];
+
+/// Render refactoring candidates as RFC 4180 CSV with a header row.
+///
+/// Every field is double-quoted and records end with CRLF. Issue and suggestion
+/// codes are joined with `;`. Metrics missing from a candidate's contributing
+/// features are left empty.
+pub fn render_csv(results: &AnalysisResults) -> String {
+    let mut output = csv_record(CSV_COLUMNS.iter().map(|column| column.to_string()));
+    for candidate in &results.refactoring_candidates {
+        output.push_str(&csv_record(candidate_fields(candidate)));
+    }
+    output
+}
+
+/// Field values for one candidate, in [`CSV_COLUMNS`] order.
+fn candidate_fields(candidate: &RefactoringCandidate) -> Vec<String> {
+    let issue_codes: Vec<&str> = candidate
+        .issues
+        .iter()
+        .map(|issue| issue.code.as_str())
+        .collect();
+    let suggestion_codes: Vec<&str> = candidate
+        .suggestions
+        .iter()
+        .map(|suggestion| suggestion.code.as_str())
+        .collect();
+
+    vec![
+        candidate.file_path.clone(),
+        candidate.name.clone(),
+        entity_kind(&candidate.entity_id),
+        format!("{:.2}", candidate.score),
+        format!("{:?}", candidate.priority),
+        issue_codes.join(";"),
+        suggestion_codes.join(";"),
+        format_metric(feature_value(candidate, "cyclomatic_complexity")),
+        format_metric(feature_value(candidate, "cognitive_complexity")),
+        format_metric(feature_value(candidate, "clone_mass")),
+        format_metric(candidate.coverage_percentage.map(|pct| pct / 100.0)),
+    ]
+}
+
+/// Entity kind from a `file_path:entity_type:entity_name` id, or empty when absent.
+fn entity_kind(entity_id: &str) -> String {
+    let mut parts = entity_id.rsplitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(_), Some(kind), Some(_)) if !kind.is_empty() && kind.parse::<u64>().is_err() => {
+            kind.to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+/// Largest value of a feature across the candidate's issues.
+fn feature_value(candidate: &RefactoringCandidate, feature_name: &str) -> Option<f64> {
+    candidate
+        .issues
+        .iter()
+        .flat_map(|issue| &issue.contributing_features)
+        .filter(|feature| feature.feature_name == feature_name)
+        .map(|feature| feature.value)
+        .reduce(f64::max)
+}
+
+/// Render an optional metric, leaving missing values empty.
+fn format_metric(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Quote each field, doubling embedded quotes, and terminate the record with CRLF.
+fn csv_record(fields: impl IntoIterator<Item = String>) -> String {
+    let quoted: Vec<String> = fields
+        .into_iter()
+        .map(|field| format!("\"{}\"", field.replace('"', "\"\"")))
+        .collect();
+    format!("{}\r\n", quoted.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::pipeline::{FeatureContribution, RefactoringIssue, RefactoringSuggestion};
+    use crate::core::scoring::Priority;
+
+    fn candidate(name: &str) -> RefactoringCandidate {
+        RefactoringCandidate {
+            entity_id: format!("src/lib.rs:function:{name}"),
+            name: name.to_string(),
+            file_path: "src/lib.rs".to_string(),
+            line_range: Some((1, 20)),
+            priority: Priority::High,
+            score: 71.25,
+            confidence: 0.9,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            issue_count: 0,
+            suggestion_count: 0,
+            coverage_percentage: None,
+        }
+    }
+
+    #[test]
+    fn render_csv_emits_header_and_one_row_per_candidate() {
+        let mut results = AnalysisResults::empty();
+        results.refactoring_candidates = vec![candidate("parse"), candidate("render")];
+
+        let output = render_csv(&results);
+        let rows: Vec<&str> = output.split_terminator("\r\n").collect();
+
+        assert_eq!(rows.len(), results.refactoring_candidates.len() + 1);
+        assert!(rows[0].starts_with("\"file_path\",\"entity_name\",\"entity_kind\""));
+        assert!(rows[1].starts_with("\"src/lib.rs\",\"parse\",\"function\",\"71.25\",\"High\""));
+    }
+
+    #[test]
+    fn render_csv_reports_codes_metrics_and_coverage() {
+        let mut flagged = candidate("parse");
+        flagged.coverage_percentage = Some(42.0);
+        flagged.issues.push(RefactoringIssue {
+            code: "CMPLX".to_string(),
+            category: "complexity".to_string(),
+            severity: 2.0,
+            contributing_features: vec![FeatureContribution {
+                feature_name: "cyclomatic_complexity".to_string(),
+                value: 14.0,
+                normalized_value: 0.8,
+                contribution: 1.2,
+            }],
+        });
+        flagged.suggestions.push(RefactoringSuggestion {
+            refactoring_type: "extract_method".to_string(),
+            code: "XTRMTH".to_string(),
+            priority: 0.8,
+            effort: 0.4,
+            impact: 0.7,
+        });
+        let mut results = AnalysisResults::empty();
+        results.refactoring_candidates = vec![flagged];
+
+        let output = render_csv(&results);
+        let row = output.split_terminator("\r\n").nth(1).unwrap();
+        assert_eq!(
+            row,
+            "\"src/lib.rs\",\"parse\",\"function\",\"71.25\",\"High\",\"CMPLX\",\"XTRMTH\",\"14\",\"\",\"\",\"0.42\""
+        );
+    }
+
+    #[test]
+    fn csv_record_escapes_quotes_commas_and_newlines() {
+        let record = csv_record(vec![
+            "say \"hi\"".to_string(),
+            "a,b".to_string(),
+            "line\nbreak".to_string(),
+        ]);
+        assert_eq!(record, "\"say \"\"hi\"\"\",\"a,b\",\"line\nbreak\"\r\n");
+    }
+}
@@ -20,14 +20,15 @@ use std::path::{Path, PathBuf};
 use super::assets::{
     copy_js_assets_to_output, copy_theme_css_to_output, copy_webpage_assets_to_output,
 };
+use super::csv_export::render_csv;
 use super::error::ReportError;
 use super::helpers::{register_helpers, safe_json_value};
 use super::hierarchy::{
     build_unified_hierarchy_with_health, create_file_groups_from_candidates,
-    create_file_groups_from_health,
+    create_file_groups_from_health, group_entity_health_by_file,
 };
 use super::templates::{
-    detect_templates_dir, load_templates_from_dir, register_fallback_template, CSV_TEMPLATE_NAME,
+    detect_templates_dir, load_templates_from_dir, register_fallback_template,
     FALLBACK_TEMPLATE_NAME, MARKDOWN_TEMPLATE_NAME, SONAR_TEMPLATE_NAME,
 };
 
@@ -114,7 +115,7 @@ impl ReportGenerator {
         results: &AnalysisResults,
         output_path: P,
     ) -> Result<(), ReportError> {
-        self.render_template_to_path(CSV_TEMPLATE_NAME, results, output_path)
+        self.generate_csv_report(results, output_path)
     }
 
     pub fn generate_sonar_report<P: AsRef<Path>>(
@@ -143,9 +144,8 @@ impl ReportGenerator {
             ReportFormat::Yaml => {
                 self.generate_yaml_report_with_oracle(results, &oracle_option, output_path)
             }
-            ReportFormat::Csv => {
-                self.generate_csv_report_with_oracle(results, &oracle_option, output_path)
-            }
+            // Oracle plans are not tabular, so CSV lists the candidates alone.
+            ReportFormat::Csv => self.generate_csv_report(results, output_path),
         }
     }
 
@@ -247,18 +247,7 @@ impl ReportGenerator {
         results: &AnalysisResults,
         output_path: P,
     ) -> Result<(), ReportError> {
-        self.generate_csv_report_with_oracle(results, &None, output_path)
-    }
-
-    fn generate_csv_report_with_oracle<P: AsRef<Path>>(
-        &self,
-        results: &AnalysisResults,
-        oracle_response: &Option<crate::oracle::RefactoringOracleResponse>,
-        output_path: P,
-    ) -> Result<(), ReportError> {
-        let data = self.prepare_template_data_with_oracle(results, oracle_response);
-        let rendered = self.render_template(CSV_TEMPLATE_NAME, &data)?;
-        fs::write(output_path, rendered)?;
+        fs::write(output_path, render_csv(results))?;
         Ok(())
     }
 
@@ -311,6 +300,10 @@ impl ReportGenerator {
         // Add Oracle refactoring plan at the TOP for user requirement
         if let Some(oracle) = oracle_response {
             data.insert("oracle_refactoring_plan", safe_json_value(oracle));
+            data.insert(
+                "oracle_tasks",
+                safe_json_value(oracle_tasks_in_order(oracle)),
+            );
             data.insert("has_oracle_data", safe_json_value(true));
         } else {
             data.insert("has_oracle_data", safe_json_value(false));
@@ -380,6 +373,13 @@ impl ReportGenerator {
         data.insert("fileHealth", safe_json_value(&results.file_health));
         data.insert("entity_health", safe_json_value(&results.entity_health));
         data.insert("entityHealth", safe_json_value(&results.entity_health));
+        data.insert(
+            "entity_health_by_file",
+            Value::Array(group_entity_health_by_file(
+                &results.passes.structure.entity_health,
+                &results.project_root,
+            )),
+        );
 
         // Add health metrics for overall project health display
         if let Some(health_metrics) = &results.health_metrics {
@@ -691,6 +691,22 @@ impl ReportGenerator {
     }
 }
 
+/// Oracle tasks in dependency order for listing in reports.
+///
+/// A dependency cycle is a defect in the oracle response; the tasks are then
+/// listed in their original order.
+fn oracle_tasks_in_order(
+    oracle: &crate::oracle::RefactoringOracleResponse,
+) -> Vec<&crate::oracle::RefactoringTask> {
+    oracle.topologically_sorted_tasks().unwrap_or_else(|cycle| {
+        tracing::warn!(
+            "Oracle tasks have cyclic dependencies ({}); listing them unsorted",
+            cycle.join(", ")
+        );
+        oracle.all_tasks().iter().collect()
+    })
+}
+
 #[cfg(test)]
 #[path = "generator_tests.rs"]
 mod tests;
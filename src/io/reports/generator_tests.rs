@@ -9,18 +9,19 @@ use crate::core::pipeline::{
 use crate::io::reports::hierarchy::{
     add_files_to_hierarchy, build_candidate_lookup, build_unified_hierarchy,
     build_unified_hierarchy_with_health, create_file_groups_from_candidates,
-    create_file_groups_from_health,
+    create_file_groups_from_health, group_entity_health_by_file,
 };
 // Use the 3-field MemoryStats from result_types (for AnalysisStatistics)
 use crate::core::pipeline::results::result_types::MemoryStats;
 use crate::core::scoring::{Priority, ScoringResult};
+use crate::detectors::structure::{EntityHealth, FileEntityHealth, FileHealthReport, HealthReport};
 use crate::io::reports::templates;
 use crate::oracle::{
     CodebaseAssessment, RefactoringOracleResponse, RefactoringRoadmap, RefactoringTask,
 };
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
 fn entity_ref(candidate: &RefactoringCandidate) -> RefactoringCandidate {
@@ -134,6 +135,7 @@ fn create_test_results() -> AnalysisResults {
                 imports: vec!["use std::result::Result;".to_string()],
             },
         }],
+        functions: Vec::new(),
         value: crate::detectors::coverage::PackValue {
             file_cov_gain: 0.25,
             repo_cov_gain_est: 0.05,
@@ -272,7 +274,7 @@ fn test_generate_csv_report() {
     assert!(result.is_ok());
 
     let content = fs::read_to_string(&output_path).unwrap();
-    assert!(content.contains("file_path"));
+    assert_eq!(content, render_csv(&results));
     assert!(content.contains("src/test.rs"));
 }
 
@@ -391,6 +393,7 @@ fn sample_oracle_response() -> RefactoringOracleResponse {
                 summary: Some("The codebase has well-structured modules with good separation of concerns. Documentation could use some cleanup.".into()),
                 architectural_narrative: None,
                 architectural_style: Some("Modular Architecture".into()),
+                architectural_patterns: vec![],
                 strengths: vec!["Good separation of concerns".into()],
                 issues: vec!["Large util file".into(), "Documentation gaps".into()],
             },
@@ -423,6 +426,7 @@ fn test_prepare_template_data_marks_oracle_presence() {
     let obj = data.as_object().expect("template data should be object");
     assert_eq!(obj["has_oracle_data"], serde_json::Value::Bool(true));
     assert!(obj.contains_key("oracle_refactoring_plan"));
+    assert_eq!(obj["oracle_tasks"][0]["id"], "T1");
 
     let without_oracle = generator.prepare_template_data(&results);
     let without_obj = without_oracle
@@ -434,6 +438,29 @@ fn test_prepare_template_data_marks_oracle_presence() {
     );
 }
 
+#[test]
+fn test_prepare_template_data_lists_oracle_tasks_in_dependency_order() {
+    let generator = ReportGenerator::new();
+    let results = create_test_results();
+    let mut oracle = sample_oracle_response();
+    let prerequisite = RefactoringTask {
+        id: "T2".into(),
+        title: "Extract config loader".into(),
+        ..oracle.tasks[0].clone()
+    };
+    oracle.tasks[0].depends_on = vec!["T2".into()];
+    oracle.tasks.push(prerequisite);
+
+    let data = generator.prepare_template_data_with_oracle(&results, &Some(oracle));
+    let ids: Vec<&str> = data["oracle_tasks"]
+        .as_array()
+        .expect("oracle tasks")
+        .iter()
+        .filter_map(|task| task["id"].as_str())
+        .collect();
+    assert_eq!(ids, vec!["T2", "T1"]);
+}
+
 #[test]
 fn test_generate_report_with_oracle_all_formats() {
     let temp_dir = TempDir::new().unwrap();
@@ -1136,6 +1163,64 @@ fn test_build_unified_hierarchy_handles_root_level_file_health() {
         .any(|child| child["type"] == "folder" && child["path"] == "src"));
 }
 
+fn entity_health(raw_value: usize, health: f64) -> EntityHealth {
+    EntityHealth {
+        raw_value,
+        percentile: 0.5,
+        health,
+    }
+}
+
+fn health_report(path: &str, per_entity: Vec<(String, EntityHealth)>) -> FileHealthReport {
+    let min_health = per_entity
+        .iter()
+        .map(|(_, health)| health.health)
+        .fold(1.0, f64::min);
+    FileHealthReport {
+        path: PathBuf::from(path),
+        report: HealthReport {
+            aggregate: FileEntityHealth {
+                entity_count: per_entity.len(),
+                total_ast_nodes: per_entity.iter().map(|(_, health)| health.raw_value).sum(),
+                health: 0.5,
+                min_health,
+            },
+            per_entity,
+        },
+    }
+}
+
+#[test]
+fn test_group_entity_health_by_file_lists_worst_entities_first() {
+    let reports = vec![
+        health_report(
+            "/project/src/lib.rs",
+            vec![
+                ("parse".to_string(), entity_health(40, 0.75)),
+                ("Parser::parse_expr".to_string(), entity_health(300, 0.25)),
+            ],
+        ),
+        health_report(
+            "/project/main.rs",
+            vec![("main".to_string(), entity_health(10, 1.0))],
+        ),
+    ];
+
+    let groups = group_entity_health_by_file(&reports, Path::new("/project"));
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]["path"], "main.rs");
+    let lib = &groups[1];
+    assert_eq!(lib["path"], "src/lib.rs");
+    assert_eq!(lib["entityCount"], 2);
+    assert_eq!(lib["health"], 50.0);
+    assert_eq!(lib["minHealth"], 25.0);
+    assert_eq!(lib["entities"][0]["name"], "Parser::parse_expr");
+    assert_eq!(lib["entities"][0]["astNodes"], 300);
+    assert_eq!(lib["entities"][0]["health"], 25.0);
+    assert_eq!(lib["entities"][1]["name"], "parse");
+}
+
 #[test]
 fn test_add_files_to_hierarchy_enriches_metadata() {
     let generator = ReportGenerator::new();
@@ -1390,3 +1475,26 @@ fn test_html_report_uses_hierarchical_data() {
     // Sanity check that we produced non-empty HTML
     assert!(!content.is_empty());
 }
+
+#[test]
+fn test_html_report_lists_entity_health_per_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_path = temp_dir.path().join("entity_health.html");
+    let generator = ReportGenerator::new();
+    if generator.templates_dir.is_none() {
+        return;
+    }
+
+    let mut results = create_test_results();
+    results
+        .entity_health
+        .insert("src/lib.rs:function:parse_header".to_string(), 42.0);
+
+    generator
+        .generate_report(&results, &output_path, ReportFormat::Html)
+        .expect("HTML generation should succeed");
+
+    let content = fs::read_to_string(&output_path).unwrap();
+    assert!(content.contains("<details class=\"entity-health-file\">"));
+    assert!(content.contains("parse_header"));
+}
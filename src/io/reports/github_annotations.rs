@@ -0,0 +1,155 @@
+//! GitHub Actions workflow annotations for pull-request inline comments.
+
+use crate::core::pipeline::{AnalysisResults, RefactoringCandidate};
+use crate::core::scoring::Priority;
+
+/// Render refactoring candidates as GitHub workflow annotation commands.
+///
+/// `Medium` candidates become `::warning` lines and `High`/`Critical` ones
+/// become `::error` lines; lower priorities are omitted. File paths are made
+/// relative to `results.repo_root` when it is set and contains the file.
+pub fn render_github_annotations(results: &AnalysisResults) -> String {
+    let mut output = String::new();
+    for candidate in &results.refactoring_candidates {
+        let level = match candidate.priority {
+            Priority::Critical | Priority::High => "error",
+            Priority::Medium => "warning",
+            Priority::Low | Priority::None => continue,
+        };
+
+        let file = annotation_path(results, &candidate.file_path);
+        let mut properties = format!("file={}", escape_property(&file));
+        if let Some((start, end)) = candidate.line_range {
+            properties.push_str(&format!(",line={start},endLine={end}"));
+        }
+        properties.push_str(&format!(
+            ",title={}",
+            escape_property(&format!("Refactor {}", candidate.name))
+        ));
+
+        output.push_str(&format!(
+            "::{level} {properties}::{}\n",
+            escape_data(&annotation_message(candidate))
+        ));
+    }
+    output
+}
+
+/// Path of `file_path` relative to the repository root, falling back to the
+/// path as stored in the results.
+fn annotation_path(results: &AnalysisResults, file_path: &str) -> String {
+    results
+        .repo_relative_path(file_path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// One-line summary of a candidate's priority, score and issue categories.
+fn annotation_message(candidate: &RefactoringCandidate) -> String {
+    let mut message = format!(
+        "{:?} priority refactoring candidate (score {:.1})",
+        candidate.priority, candidate.score
+    );
+    if !candidate.issues.is_empty() {
+        let categories: Vec<&str> = candidate
+            .issues
+            .iter()
+            .map(|issue| issue.category.as_str())
+            .collect();
+        message.push_str(&format!(": {}", categories.join(", ")));
+    }
+    message
+}
+
+/// Escape an annotation message per the workflow command syntax.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape an annotation property value, which additionally reserves `:` and `,`.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::pipeline::RefactoringIssue;
+    use std::path::PathBuf;
+
+    fn candidate(file_path: &str, priority: Priority) -> RefactoringCandidate {
+        RefactoringCandidate {
+            entity_id: format!("{file_path}::f"),
+            name: "f".to_string(),
+            file_path: file_path.to_string(),
+            line_range: Some((3, 12)),
+            priority,
+            score: 72.5,
+            confidence: 1.0,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            issue_count: 0,
+            suggestion_count: 0,
+            coverage_percentage: None,
+        }
+    }
+
+    #[test]
+    fn render_github_annotations_maps_priority_to_level() {
+        let mut results = AnalysisResults::empty();
+        results.refactoring_candidates = vec![
+            candidate("a.rs", Priority::Critical),
+            candidate("b.rs", Priority::High),
+            candidate("c.rs", Priority::Medium),
+            candidate("d.rs", Priority::Low),
+            candidate("e.rs", Priority::None),
+        ];
+
+        let output = render_github_annotations(&results);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.starts_with("::error "))
+                .count(),
+            2
+        );
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.starts_with("::warning "))
+                .count(),
+            1
+        );
+        assert!(lines[0].starts_with("::error file=a.rs,line=3,endLine=12,title=Refactor f::"));
+    }
+
+    #[test]
+    fn render_github_annotations_paths_are_relative_to_repo_root() {
+        let mut results = AnalysisResults::empty();
+        results.project_root = PathBuf::from("/repo/services/api");
+        results.repo_root = Some(PathBuf::from("/repo"));
+        let mut flagged = candidate("src/main.rs", Priority::High);
+        flagged.issues.push(RefactoringIssue {
+            code: "CMPLX".to_string(),
+            category: "complexity".to_string(),
+            severity: 2.0,
+            contributing_features: Vec::new(),
+        });
+        results.refactoring_candidates = vec![flagged];
+
+        let output = render_github_annotations(&results);
+        assert!(output.contains("file=services/api/src/main.rs,"));
+        assert!(output.trim_end().ends_with("(score 72.5): complexity"));
+    }
+
+    #[test]
+    fn escape_property_encodes_reserved_characters() {
+        assert_eq!(escape_property("a:b,c%\n"), "a%3Ab%2Cc%25%0A");
+        assert_eq!(escape_data("a:b,c"), "a:b,c");
+    }
+}
@@ -12,6 +12,7 @@ use crate::core::pipeline::{
     RefactoringCandidate,
 };
 use crate::core::scoring::Priority;
+use crate::detectors::structure::{EntityHealth, FileHealthReport, HealthReport};
 
 /// Build a unified hierarchy combining directory health with refactoring candidates
 pub fn build_unified_hierarchy(
@@ -317,6 +318,45 @@ pub fn create_file_groups_from_health(
         .collect()
 }
 
+/// Build the per-file entity health listing from structure health reports.
+///
+/// Paths are made relative to `project_root` where possible and health is
+/// scaled to 0-100. Files are sorted by path and entities worst-first.
+pub fn group_entity_health_by_file(
+    reports: &[FileHealthReport],
+    project_root: &Path,
+) -> Vec<serde_json::Value> {
+    let by_file: BTreeMap<String, &HealthReport> = reports
+        .iter()
+        .map(|file| {
+            let path = file.path.strip_prefix(project_root).unwrap_or(&file.path);
+            (path.display().to_string(), &file.report)
+        })
+        .collect();
+
+    by_file
+        .into_iter()
+        .map(|(path, report)| {
+            let mut entities: Vec<&(String, EntityHealth)> = report.per_entity.iter().collect();
+            entities.sort_by(|a, b| a.1.health.total_cmp(&b.1.health).then(a.0.cmp(&b.0)));
+            serde_json::json!({
+                "path": path,
+                "entityCount": report.aggregate.entity_count,
+                "health": report.aggregate.health * 100.0,
+                "minHealth": report.aggregate.min_health * 100.0,
+                "entities": entities
+                    .iter()
+                    .map(|(name, health)| serde_json::json!({
+                        "name": name,
+                        "astNodes": health.raw_value,
+                        "health": health.health * 100.0,
+                    }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
 /// Create real file groups from individual refactoring candidates
 pub fn create_file_groups_from_candidates(
     candidates: &[RefactoringCandidate],
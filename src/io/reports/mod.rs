@@ -1,14 +1,20 @@
 pub mod assets;
+mod csv_export;
 mod error;
 mod generator;
+mod github_annotations;
 mod helpers;
 mod hierarchy;
 mod templates;
+mod trend;
 
+pub use csv_export::{render_csv, CSV_COLUMNS};
 pub use error::ReportError;
 pub use generator::ReportGenerator;
+pub use github_annotations::render_github_annotations;
 pub use hierarchy::{
     add_files_to_hierarchy, build_candidate_lookup, build_unified_hierarchy,
     build_unified_hierarchy_with_health, create_file_groups_from_candidates,
-    create_file_groups_from_health,
+    create_file_groups_from_health, group_entity_health_by_file,
 };
+pub use trend::{compute_trend, CodeHealthTrend, TREND_SCORE_THRESHOLD};
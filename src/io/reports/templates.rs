@@ -8,7 +8,6 @@ use super::error::ReportError;
 
 pub(super) const FALLBACK_TEMPLATE_NAME: &str = "default_html";
 pub(super) const MARKDOWN_TEMPLATE_NAME: &str = "markdown_report";
-pub(super) const CSV_TEMPLATE_NAME: &str = "csv_report";
 pub(super) const SONAR_TEMPLATE_NAME: &str = "sonar_report";
 
 pub(super) fn register_fallback_template(handlebars: &mut Handlebars<'static>) {
@@ -25,12 +24,6 @@ pub(super) fn register_fallback_template(handlebars: &mut Handlebars<'static>) {
         eprintln!("Failed to register fallback Markdown template: {}", err);
     }
 
-    if let Err(err) =
-        handlebars.register_template_string(CSV_TEMPLATE_NAME, include_str!("./default_csv.hbs"))
-    {
-        eprintln!("Failed to register fallback CSV template: {}", err);
-    }
-
     if let Err(err) = handlebars
         .register_template_string(SONAR_TEMPLATE_NAME, include_str!("./default_sonar.hbs"))
     {
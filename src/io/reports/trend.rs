@@ -0,0 +1,163 @@
+//! Code health trend between two analysis snapshots.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::pipeline::AnalysisResults;
+
+/// Minimum change in a file's max refactoring score that counts as a trend.
+pub const TREND_SCORE_THRESHOLD: f64 = 5.0;
+
+/// Files whose refactoring pressure moved between two analysis runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CodeHealthTrend {
+    /// Files whose max refactoring score dropped by more than the threshold
+    pub improved_files: Vec<PathBuf>,
+
+    /// Files whose max refactoring score rose by more than the threshold
+    pub degraded_files: Vec<PathBuf>,
+
+    /// Sum of per-file max score changes (negative means healthier overall)
+    pub net_score_delta: f64,
+}
+
+/// Rendering methods for [`CodeHealthTrend`].
+impl CodeHealthTrend {
+    /// Render the trend as a plain-text "Trend" section.
+    pub fn render_text(&self) -> String {
+        let mut output = String::from("Trend\n-----\n");
+        output.push_str(&format!(
+            "  Net score delta: {:+.1}\n",
+            self.net_score_delta
+        ));
+
+        for (label, files) in [
+            ("Improved", &self.improved_files),
+            ("Degraded", &self.degraded_files),
+        ] {
+            output.push_str(&format!("  {} files: {}\n", label, files.len()));
+            for file in files {
+                output.push_str(&format!("    - {}\n", file.display()));
+            }
+        }
+
+        output
+    }
+}
+
+/// Compare two snapshots by each file's highest refactoring candidate score.
+///
+/// Files without candidates in one snapshot are treated as scoring zero there,
+/// so a file whose issues were all resolved counts as improved.
+pub fn compute_trend(before: &AnalysisResults, after: &AnalysisResults) -> CodeHealthTrend {
+    let before_scores = max_score_by_file(before);
+    let after_scores = max_score_by_file(after);
+
+    let mut files: Vec<&String> = before_scores.keys().chain(after_scores.keys()).collect();
+    files.sort();
+    files.dedup();
+
+    let mut trend = CodeHealthTrend::default();
+    for file in files {
+        let old = before_scores.get(file).copied().unwrap_or(0.0);
+        let new = after_scores.get(file).copied().unwrap_or(0.0);
+        let delta = new - old;
+        trend.net_score_delta += delta;
+
+        if delta < -TREND_SCORE_THRESHOLD {
+            trend.improved_files.push(PathBuf::from(file));
+        } else if delta > TREND_SCORE_THRESHOLD {
+            trend.degraded_files.push(PathBuf::from(file));
+        }
+    }
+
+    trend
+}
+
+/// Highest candidate score per file path.
+fn max_score_by_file(results: &AnalysisResults) -> BTreeMap<String, f64> {
+    let mut scores: BTreeMap<String, f64> = BTreeMap::new();
+    for candidate in &results.refactoring_candidates {
+        scores
+            .entry(candidate.file_path.clone())
+            .and_modify(|score| *score = score.max(candidate.score))
+            .or_insert(candidate.score);
+    }
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::pipeline::RefactoringCandidate;
+    use crate::core::scoring::Priority;
+
+    fn candidate(file_path: &str, score: f64) -> RefactoringCandidate {
+        RefactoringCandidate {
+            entity_id: format!("{file_path}::f"),
+            name: "f".to_string(),
+            file_path: file_path.to_string(),
+            line_range: None,
+            priority: Priority::Medium,
+            score,
+            confidence: 1.0,
+            issues: Vec::new(),
+            suggestions: Vec::new(),
+            issue_count: 0,
+            suggestion_count: 0,
+            coverage_percentage: None,
+        }
+    }
+
+    fn results(candidates: Vec<RefactoringCandidate>) -> AnalysisResults {
+        let mut results = AnalysisResults::empty();
+        results.refactoring_candidates = candidates;
+        results
+    }
+
+    #[test]
+    fn compute_trend_classifies_files_by_max_score_change() {
+        let before = results(vec![
+            candidate("a.rs", 60.0),
+            candidate("a.rs", 20.0),
+            candidate("b.rs", 30.0),
+            candidate("c.rs", 50.0),
+            candidate("gone.rs", 40.0),
+        ]);
+        let after = results(vec![
+            candidate("a.rs", 50.0),
+            candidate("b.rs", 40.0),
+            candidate("c.rs", 53.0),
+            candidate("new.rs", 10.0),
+        ]);
+
+        let trend = compute_trend(&before, &after);
+        assert_eq!(
+            trend.improved_files,
+            vec![PathBuf::from("a.rs"), PathBuf::from("gone.rs")]
+        );
+        assert_eq!(
+            trend.degraded_files,
+            vec![PathBuf::from("b.rs"), PathBuf::from("new.rs")]
+        );
+        assert!((trend.net_score_delta - (-10.0 + 10.0 + 3.0 - 40.0 + 10.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn render_text_lists_trend_section() {
+        let trend = CodeHealthTrend {
+            improved_files: vec![PathBuf::from("a.rs")],
+            degraded_files: Vec::new(),
+            net_score_delta: -12.5,
+        };
+
+        let text = trend.render_text();
+        assert!(text.starts_with("Trend\n"));
+        assert!(text.contains("Net score delta: -12.5"));
+        assert!(text.contains("Improved files: 1"));
+        assert!(text.contains("    - a.rs"));
+        assert!(text.contains("Degraded files: 0"));
+    }
+}
@@ -196,6 +196,7 @@ impl CppAdapter {
             parent: parent_id,
             children: Vec::new(),
             metadata,
+            is_test: false,
         }))
     }
 
@@ -839,6 +840,11 @@ impl LanguageAdapter for CppAdapter {
         "cpp"
     }
 
+    /// Returns the newest language version the grammar parses ("cpp:20").
+    fn language_version(&self) -> &str {
+        "cpp:20"
+    }
+
     fn parse_tree(&mut self, source_code: &str) -> Result<Tree> {
         self.parser
             .parse(source_code, None)
@@ -112,6 +112,7 @@ impl GoAdapter {
                 parent: parent_id.clone(),
                 children: Vec::new(),
                 metadata,
+                is_test: is_go_test_file(file_path),
             };
 
             index.add_entity(entity);
@@ -707,40 +708,55 @@ impl LanguageAdapter for GoAdapter {
         "go"
     }
 
-    /// Extracts import statements from Go source code.
+    /// Returns the newest language version the grammar parses ("go:1.22").
+    fn language_version(&self) -> &str {
+        "go:1.22"
+    }
+
+    /// Extracts import statements from Go source code, including `import (...)` blocks.
     fn extract_imports(&mut self, source: &str) -> Result<Vec<ImportStatement>> {
         let mut imports = Vec::new();
         let mut in_import_block = false;
 
         for (line_number, line) in source.lines().enumerate() {
-            let trimmed = line.trim();
-
-            if trimmed.is_empty() || trimmed.starts_with("//") {
-                continue;
-            }
+            // Drop trailing comments so a `)` inside one cannot close the block
+            let trimmed = line.split("//").next().unwrap_or_default().trim();
 
-            // Handle import block start/end
-            if trimmed == "import (" {
-                in_import_block = true;
-                continue;
-            }
-            if in_import_block && trimmed == ")" {
-                in_import_block = false;
+            if trimmed.is_empty() {
                 continue;
             }
 
-            // Parse import line (either in block or single-line)
-            let import_text = if in_import_block {
-                Some(trimmed)
+            let specs = if in_import_block {
+                trimmed
+            } else if let Some(rest) = trimmed.strip_prefix("import") {
+                if let Some(block) = rest.trim_start().strip_prefix('(') {
+                    in_import_block = true;
+                    block
+                } else if rest.starts_with(char::is_whitespace) {
+                    rest
+                } else {
+                    // An identifier such as `importer`
+                    continue;
+                }
             } else {
-                trimmed.strip_prefix("import ")
+                continue;
             };
 
-            if let Some(text) = import_text {
-                if let Some(path) = Self::parse_go_import_line(text) {
-                    imports.push(Self::create_import_statement(path, line_number + 1));
+            // A block may close on the same line it opens (`import ("a"; "b")`)
+            let specs = match specs.find(')').filter(|_| in_import_block) {
+                Some(close) => {
+                    in_import_block = false;
+                    &specs[..close]
                 }
-            }
+                None => specs,
+            };
+
+            imports.extend(
+                specs
+                    .split(';')
+                    .filter_map(Self::parse_go_import_line)
+                    .map(|path| Self::create_import_statement(path, line_number + 1)),
+            );
         }
 
         Ok(imports)
@@ -756,6 +772,11 @@ impl LanguageAdapter for GoAdapter {
     }
 }
 
+/// Go test code lives exclusively in `_test.go` files.
+fn is_go_test_file(file_path: &str) -> bool {
+    file_path.ends_with("_test.go")
+}
+
 /// Create source location from a tree-sitter node.
 fn create_go_source_location(file_path: &str, node: &Node) -> SourceLocation {
     SourceLocation::from_positions(
@@ -802,6 +823,7 @@ impl EntityExtractor for GoAdapter {
             children: Vec::new(),
             location,
             metadata,
+            is_test: is_go_test_file(file_path),
         }))
     }
 
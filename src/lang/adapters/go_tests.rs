@@ -317,3 +317,23 @@ import (
         assert_eq!(imports.len(), 7, "Should have 7 imports total");
     }
 }
+
+#[test]
+fn test_is_test_flag_from_file_suffix() {
+    let mut adapter = GoAdapter::new().unwrap();
+    let source = r#"
+package server
+
+func Handle() int {
+    return 1
+}
+"#;
+    let index = adapter.parse_source(source, "server/handler.go").unwrap();
+    assert!(index.entities.values().all(|e| !e.is_test));
+
+    let index = adapter
+        .parse_source(source, "server/handler_test.go")
+        .unwrap();
+    assert!(!index.entities.is_empty());
+    assert!(index.entities.values().all(|e| e.is_test));
+}
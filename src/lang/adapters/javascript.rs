@@ -228,6 +228,11 @@ impl LanguageAdapter for JavaScriptAdapter {
         "javascript"
     }
 
+    /// Returns the newest language version the grammar parses ("javascript:es2023").
+    fn language_version(&self) -> &str {
+        "javascript:es2023"
+    }
+
     /// Extracts import and require statements from JavaScript source.
     fn extract_imports(&mut self, source: &str) -> Result<Vec<ImportStatement>> {
         Ok(crate::lang::common::extract_imports_common(
@@ -296,6 +301,7 @@ impl EntityExtractor for JavaScriptAdapter {
             children: Vec::new(),
             location,
             metadata,
+            is_test: false,
         }))
     }
 }
@@ -245,6 +245,39 @@ impl PythonAdapter {
             .collect()
     }
 
+    /// Check whether a `class_definition` node lists `TestCase` among its bases.
+    fn is_test_case_class(node: &Node, source_code: &str) -> bool {
+        let Some(arg_list) = node.child_by_field_name("superclasses") else {
+            return false;
+        };
+        let mut cursor = arg_list.walk();
+        let is_test_case = arg_list
+            .children(&mut cursor)
+            .filter(|child| matches!(child.kind(), "identifier" | "attribute"))
+            .filter_map(|child| child.utf8_text(source_code.as_bytes()).ok())
+            .any(|base| base == "TestCase" || base.ends_with(".TestCase"));
+        is_test_case
+    }
+
+    /// Check whether a definition is test code: a `test_` function, a
+    /// `unittest.TestCase` subclass, or anything nested inside one.
+    fn is_test_definition(node: &Node, name: &str, source_code: &str) -> bool {
+        if node.kind() == "function_definition" && name.starts_with("test_") {
+            return true;
+        }
+
+        let mut current = Some(*node);
+        while let Some(candidate) = current {
+            if candidate.kind() == "class_definition"
+                && Self::is_test_case_class(&candidate, source_code)
+            {
+                return true;
+            }
+            current = candidate.parent();
+        }
+        false
+    }
+
     /// Extract class-specific metadata
     fn extract_class_metadata(
         &self,
@@ -417,6 +450,7 @@ impl PythonAdapter {
         if let Some(parent) = parent_id {
             entity.set_parent(parent);
         }
+        entity.is_test = Self::is_test_definition(&node, resolve(name), source_code);
 
         Ok(Some(entity))
     }
@@ -746,6 +780,11 @@ impl LanguageAdapter for PythonAdapter {
         "python"
     }
 
+    /// Returns the newest language version the grammar parses ("python:3.12").
+    fn language_version(&self) -> &str {
+        "python:3.12"
+    }
+
     /// Extracts import statements from Python source code.
     fn extract_imports(&mut self, source: &str) -> Result<Vec<ImportStatement>> {
         let mut imports = Vec::new();
@@ -825,6 +864,8 @@ impl EntityExtractor for PythonAdapter {
             _ => {}
         }
 
+        let is_test = Self::is_test_definition(&node, &name, source_code);
+
         Ok(Some(ParsedEntity {
             id: entity_id,
             kind: entity_kind,
@@ -833,6 +874,7 @@ impl EntityExtractor for PythonAdapter {
             children: Vec::new(),
             location,
             metadata,
+            is_test,
         }))
     }
 }
@@ -131,6 +131,7 @@ fn test_convert_to_code_entity() {
         parent: None,
         children: vec![],
         metadata: HashMap::new(),
+        is_test: false,
     };
 
     let source = "def test_func(): pass";
@@ -313,3 +314,51 @@ import numpy as np
             .contains(&"Counter".to_string()));
     }
 }
+
+#[test]
+fn test_is_test_flag_for_test_functions_and_test_cases() {
+    let mut adapter = PythonAdapter::new().unwrap();
+    let source = r#"
+import unittest
+
+def compute(x):
+    return x * 2
+
+def test_compute():
+    assert compute(2) == 4
+
+class ComputeTests(unittest.TestCase):
+    def setUp(self):
+        self.value = 2
+
+    def test_double(self):
+        self.assertEqual(compute(self.value), 4)
+
+class Service:
+    def test_connection(self):
+        return True
+
+    def run(self):
+        return compute(1)
+"#;
+    let index = adapter.parse_source(source, "service.py").unwrap();
+    let is_test = |name: &str| {
+        index
+            .entities
+            .values()
+            .find(|e| e.name == name)
+            .unwrap_or_else(|| panic!("missing entity {}", name))
+            .is_test
+    };
+
+    assert!(!is_test("compute"));
+    assert!(is_test("test_compute"));
+    assert!(is_test("ComputeTests"));
+    assert!(
+        is_test("setUp"),
+        "methods of a TestCase subclass are test code"
+    );
+    assert!(!is_test("Service"));
+    assert!(is_test("test_connection"));
+    assert!(!is_test("run"));
+}
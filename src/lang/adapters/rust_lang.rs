@@ -147,6 +147,43 @@ impl RustAdapter {
         false
     }
 
+    /// Check whether an attribute marks test code (`#[test]`, `#[tokio::test]`, `#[cfg(test)]`).
+    fn is_test_attribute(attribute: &str) -> bool {
+        let inner = attribute
+            .trim()
+            .trim_start_matches("#[")
+            .trim_end_matches(']')
+            .trim();
+        let path = inner.split('(').next().unwrap_or_default().trim();
+        path == "test" || path.ends_with("::test") || inner.replace(' ', "") == "cfg(test)"
+    }
+
+    /// Check whether an item, or any item enclosing it, carries a test attribute.
+    fn is_test_item(&self, node: Node, source_code: &str) -> bool {
+        let mut current = Some(node);
+        while let Some(item) = current {
+            let mut sibling = item.prev_sibling();
+            while let Some(attr) = sibling {
+                match attr.kind() {
+                    "attribute_item" => {
+                        let is_test = attr
+                            .utf8_text(source_code.as_bytes())
+                            .map(Self::is_test_attribute)
+                            .unwrap_or(false);
+                        if is_test {
+                            return true;
+                        }
+                    }
+                    "line_comment" | "block_comment" => {}
+                    _ => break,
+                }
+                sibling = attr.prev_sibling();
+            }
+            current = item.parent();
+        }
+        false
+    }
+
     /// Extract parameter names from a parameters node.
     fn extract_parameters<'a>(params_node: &Node, source_code: &'a str) -> Result<Vec<&'a str>> {
         let mut parameters = Vec::new();
@@ -197,6 +234,7 @@ impl RustAdapter {
         let mut parameters = Vec::new();
         let mut return_type = None;
         let mut visibility = "private".to_string();
+        let mut generic_params = Vec::new();
 
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
@@ -205,6 +243,14 @@ impl RustAdapter {
                 "visibility_modifier" => {
                     visibility = child.utf8_text(source_code.as_bytes())?.to_string()
                 }
+                "type_parameters" => {
+                    generic_params = Self::extract_nested_identifiers(
+                        &child,
+                        source_code,
+                        "type_parameter",
+                        "type_identifier",
+                    )?;
+                }
                 "type_identifier" | "reference_type" | "tuple_type" | "array_type"
                 | "generic_type" => {
                     return_type = Some(child.utf8_text(source_code.as_bytes())?.to_string());
@@ -221,10 +267,55 @@ impl RustAdapter {
         if let Some(ret_type) = return_type {
             metadata.insert("return_type".to_string(), Value::String(ret_type));
         }
+        if !generic_params.is_empty() {
+            metadata.insert(
+                "generic_parameters".to_string(),
+                serde_json::json!(generic_params),
+            );
+        }
+        if let Some(impl_type) = Self::enclosing_impl_type(node, source_code) {
+            metadata.insert("impl_type".to_string(), Value::String(impl_type));
+        }
+
+        let (function_calls, instantiations) = Self::extract_call_sites(node, source_code);
+        metadata.insert(
+            "function_calls".to_string(),
+            serde_json::json!(function_calls),
+        );
+        if !instantiations.is_empty() {
+            metadata.insert(
+                "instantiations".to_string(),
+                serde_json::json!(instantiations),
+            );
+        }
 
         Ok(())
     }
 
+    /// Collect call targets and struct literal names in a function body, one entry per site.
+    ///
+    /// Call targets keep explicit type arguments (e.g. `parse::<u32>`).
+    fn extract_call_sites(node: &Node, source_code: &str) -> (Vec<String>, Vec<String>) {
+        let Some(body) = node.child_by_field_name("body") else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let mut calls = Vec::new();
+        let mut instantiations = Vec::new();
+        walk_tree(body, &mut |child| {
+            let (target, sink) = match child.kind() {
+                "call_expression" => (child.child_by_field_name("function"), &mut calls),
+                "struct_expression" => (child.child_by_field_name("name"), &mut instantiations),
+                _ => return,
+            };
+            if let Some(text) = target.and_then(|t| node_text_normalized(&t, source_code).ok()) {
+                sink.push(text.trim().to_string());
+            }
+        });
+
+        (calls, instantiations)
+    }
+
     /// Collect child text of the specified kind from a node.
     fn collect_child_text<'a>(node: &Node, source_code: &'a str, kind: &str) -> Vec<&'a str> {
         let mut cursor = node.walk();
@@ -479,6 +570,25 @@ impl RustAdapter {
         Ok(())
     }
 
+    /// Name of the type implemented by the `impl` block enclosing a node, without generics
+    fn enclosing_impl_type(node: &Node, source_code: &str) -> Option<String> {
+        let mut current = node.parent();
+        while let Some(parent) = current {
+            if parent.kind() == "impl_item" {
+                let mut type_node = parent.child_by_field_name("type")?;
+                if type_node.kind() == "generic_type" {
+                    type_node = type_node.child_by_field_name("type")?;
+                }
+                return type_node
+                    .utf8_text(source_code.as_bytes())
+                    .ok()
+                    .map(str::to_string);
+            }
+            current = parent.parent();
+        }
+        None
+    }
+
     /// Check if a node is inside a trait definition
     fn is_inside_trait(&self, node: Node) -> bool {
         let mut current = node.parent();
@@ -567,14 +677,39 @@ impl LanguageAdapter for RustAdapter {
         "rust"
     }
 
+    /// Returns the newest language version the grammar parses ("rust:2021").
+    fn language_version(&self) -> &str {
+        "rust:2021"
+    }
+
     /// Extracts use statements and mod declarations from Rust source.
+    ///
+    /// `use` statements may carry a visibility and span several lines; they are
+    /// reported at the line where they start.
     fn extract_imports(&mut self, source: &str) -> Result<Vec<ImportStatement>> {
         let mut imports = Vec::new();
+        let mut pending_use: Option<(String, usize)> = None;
 
         for (line_number, line) in source.lines().enumerate() {
-            let trimmed = line.trim();
+            let trimmed = line.split("//").next().unwrap_or_default().trim();
 
-            if trimmed.is_empty() || trimmed.starts_with("//") {
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            // Continue a multi-line use statement until its terminating `;`
+            if let Some((statement, _)) = pending_use.as_mut() {
+                statement.push(' ');
+                statement.push_str(trimmed);
+                if trimmed.ends_with(';') {
+                    if let Some((statement, start_line)) = pending_use.take() {
+                        Self::parse_use_statement(
+                            statement.trim_end_matches(';'),
+                            start_line,
+                            &mut imports,
+                        );
+                    }
+                }
                 continue;
             }
 
@@ -584,9 +719,13 @@ impl LanguageAdapter for RustAdapter {
             }
 
             // Handle use statements
-            if let Some(use_part) = trimmed.strip_prefix("use ") {
-                let use_part = use_part.trim_end_matches(';');
-                Self::parse_use_statement(use_part, line_number + 1, &mut imports);
+            if let Some(use_part) = Self::strip_visibility(trimmed).strip_prefix("use ") {
+                match use_part.strip_suffix(';') {
+                    Some(use_part) => {
+                        Self::parse_use_statement(use_part, line_number + 1, &mut imports)
+                    }
+                    None => pending_use = Some((use_part.to_string(), line_number + 1)),
+                }
             }
         }
 
@@ -644,6 +783,7 @@ impl EntityExtractor for RustAdapter {
             children: Vec::new(),
             location,
             metadata,
+            is_test: self.is_test_item(node, source_code),
         }))
     }
 }
@@ -652,11 +792,7 @@ impl EntityExtractor for RustAdapter {
 impl RustAdapter {
     /// Try to parse a mod declaration line, returning the module name if valid
     fn try_parse_mod_declaration(trimmed: &str) -> Option<&str> {
-        let mod_part = trimmed
-            .strip_prefix("pub mod ")
-            .or_else(|| trimmed.strip_prefix("pub(crate) mod "))
-            .or_else(|| trimmed.strip_prefix("pub(super) mod "))
-            .or_else(|| trimmed.strip_prefix("mod "))?;
+        let mod_part = Self::strip_visibility(trimmed).strip_prefix("mod ")?;
 
         // Only handle external mod declarations (ending with ;), not inline modules
         let mod_name = mod_part.strip_suffix(';')?.trim();
@@ -667,6 +803,23 @@ impl RustAdapter {
         }
     }
 
+    /// Strip a leading visibility modifier (`pub`, `pub(crate)`, `pub(in path)`, ...)
+    fn strip_visibility(item: &str) -> &str {
+        let Some(rest) = item.strip_prefix("pub") else {
+            return item;
+        };
+        if rest.starts_with('(') {
+            match rest.find(')') {
+                Some(close) => rest[close + 1..].trim_start(),
+                None => item,
+            }
+        } else if rest.starts_with(char::is_whitespace) {
+            rest.trim_start()
+        } else {
+            item
+        }
+    }
+
     /// Parse a use statement and extract all module references
     fn parse_use_statement(use_part: &str, line_number: usize, imports: &mut Vec<ImportStatement>) {
         if let Some(stmt) = Self::parse_grouped_use(use_part, line_number) {
@@ -688,15 +841,26 @@ impl RustAdapter {
             return None;
         }
 
-        let items_part = &use_part[brace_pos + 1..];
-        let close_brace = items_part.find('}')?;
-        let items = &items_part[..close_brace];
-
-        let specific_imports: Vec<String> = items
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        // Match the outermost brace so nested groups stay intact
+        let close_brace = use_part.rfind('}').filter(|close| *close > brace_pos)?;
+        let items = &use_part[brace_pos + 1..close_brace];
+
+        let mut specific_imports = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (offset, ch) in items.char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    specific_imports.push(items[start..offset].trim().to_string());
+                    start = offset + 1;
+                }
+                _ => {}
+            }
+        }
+        specific_imports.push(items[start..].trim().to_string());
+        specific_imports.retain(|item| !item.is_empty());
 
         Some(ImportStatement {
             module: format!("{}::", module),
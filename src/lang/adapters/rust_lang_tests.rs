@@ -416,3 +416,48 @@ use anyhow::{Result, Context};
             .contains(&"Error".to_string()));
     }
 }
+
+#[test]
+fn test_is_test_flag_from_attributes() {
+    let mut adapter = RustAdapter::new().unwrap();
+    let source = r#"
+pub fn production() -> u32 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> u32 {
+        production()
+    }
+
+    #[test]
+    fn test_production() {
+        assert_eq!(fixture(), 1);
+    }
+}
+
+#[tokio::test]
+async fn standalone_async_test() {}
+"#;
+    let index = adapter.parse_source(source, "lib.rs").unwrap();
+    let is_test = |name: &str| {
+        index
+            .entities
+            .values()
+            .find(|e| e.name == name)
+            .unwrap_or_else(|| panic!("missing entity {}", name))
+            .is_test
+    };
+
+    assert!(!is_test("production"));
+    assert!(is_test("tests"));
+    assert!(
+        is_test("fixture"),
+        "items inside #[cfg(test)] are test code"
+    );
+    assert!(is_test("test_production"));
+    assert!(is_test("standalone_async_test"));
+}
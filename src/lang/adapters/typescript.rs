@@ -369,6 +369,11 @@ impl LanguageAdapter for TypeScriptAdapter {
         "typescript"
     }
 
+    /// Returns the newest language version the grammar parses ("typescript:5.4").
+    fn language_version(&self) -> &str {
+        "typescript:5.4"
+    }
+
     /// Extracts import and require statements from TypeScript source.
     fn extract_imports(&mut self, source: &str) -> Result<Vec<ImportStatement>> {
         Ok(crate::lang::common::extract_imports_common(source, "type "))
@@ -418,6 +423,7 @@ impl EntityExtractor for TypeScriptAdapter {
             children: Vec::new(),
             location,
             metadata,
+            is_test: false,
         }))
     }
 }
@@ -442,6 +448,11 @@ impl TypeScriptAdapter {
         source_code: &str,
         metadata: &mut std::collections::HashMap<String, serde_json::Value>,
     ) -> Result<()> {
+        let exported = node
+            .parent()
+            .is_some_and(|parent| parent.kind() == "export_statement");
+        metadata.insert("exported".to_string(), serde_json::Value::Bool(exported));
+
         match kind {
             EntityKind::Function | EntityKind::Method => {
                 self.extract_function_metadata(node, source_code, metadata)?;
@@ -64,6 +64,10 @@ pub struct ParsedEntity {
 
     /// Additional metadata
     pub metadata: std::collections::HashMap<String, serde_json::Value>,
+
+    /// Whether the entity is test code, as determined by the language adapter
+    #[serde(default)]
+    pub is_test: bool,
 }
 
 impl ParsedEntity {
@@ -328,6 +332,10 @@ pub trait LanguageAdapter: Send + Sync {
     /// Get language name
     fn language_name(&self) -> &str;
 
+    /// Newest language version the tree-sitter grammar supports, as
+    /// `"<language>:<version>"` (e.g. `"python:3.12"`)
+    fn language_version(&self) -> &str;
+
     /// Extract import statements from source code
     fn extract_imports(&mut self, _source: &str) -> Result<Vec<ImportStatement>> {
         Ok(Vec::new())
@@ -782,6 +790,7 @@ mod tests {
             children: vec!["var1".to_string()],
             location,
             metadata: HashMap::new(),
+            is_test: false,
         };
 
         assert_eq!(entity.id, "func1");
@@ -829,6 +838,7 @@ mod tests {
             children: vec![],
             location,
             metadata: HashMap::new(),
+            is_test: false,
         };
 
         index.add_entity(entity);
@@ -860,6 +870,7 @@ mod tests {
             children: vec![],
             location,
             metadata: HashMap::new(),
+            is_test: false,
         };
 
         index.add_entity(entity);
@@ -901,6 +912,7 @@ mod tests {
             children: vec![],
             location: location1,
             metadata: HashMap::new(),
+            is_test: false,
         };
 
         let entity2 = ParsedEntity {
@@ -911,6 +923,7 @@ mod tests {
             children: vec![],
             location: location2,
             metadata: HashMap::new(),
+            is_test: false,
         };
 
         index.add_entity(entity1);
@@ -952,6 +965,7 @@ mod tests {
                 end_column: 5,
             },
             metadata,
+            is_test: false,
         };
 
         let class = ParsedEntity {
@@ -968,6 +982,7 @@ mod tests {
                 end_column: 1,
             },
             metadata: HashMap::new(),
+            is_test: false,
         };
 
         index.add_entity(function);
@@ -1049,6 +1064,10 @@ mod tests {
             "dummy"
         }
 
+        fn language_version(&self) -> &str {
+            "dummy:1"
+        }
+
         fn extract_code_entities(
             &mut self,
             _source: &str,
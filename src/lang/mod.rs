@@ -16,8 +16,9 @@ pub use adapters::typescript;
 pub use common::{EntityKind, LanguageAdapter, ParseIndex, ParsedEntity, SourceLocation};
 pub use registry::{
     adapter_for_file, adapter_for_language, create_parser_for_language, detect_language_from_path,
-    extension_is_supported, get_tree_sitter_language, language_key_for_path, registered_languages,
-    LanguageInfo, LanguageStability,
+    extension_is_supported, get_tree_sitter_language, language_info, language_key_for_path,
+    registered_language_info, registered_languages, supported_extensions, LanguageInfo,
+    LanguageStability,
 };
 
 // Re-export individual adapters
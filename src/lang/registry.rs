@@ -24,10 +24,16 @@ pub enum LanguageStability {
 pub struct LanguageInfo {
     /// Canonical short key (matches CLI/config usage, e.g. "py").
     pub key: &'static str,
+    /// Language name reported by the adapter (see
+    /// [`LanguageAdapter::language_name`]).
+    pub language: &'static str,
     /// Human-friendly display name.
     pub name: &'static str,
     /// Supported file extensions (without leading dots).
     pub extensions: &'static [&'static str],
+    /// Newest language version the grammar supports (see
+    /// [`LanguageAdapter::language_version`]).
+    pub version: &'static str,
     /// Stability status.
     pub status: LanguageStability,
     /// Feature notes for documentation/UI.
@@ -37,53 +43,112 @@ pub struct LanguageInfo {
 const REGISTERED_LANGUAGES: &[LanguageInfo] = &[
     LanguageInfo {
         key: "py",
+        language: "python",
         name: "Python",
         extensions: &["py", "pyi"],
+        version: "python:3.12",
         status: LanguageStability::Stable,
         notes: "Full analysis & refactoring",
     },
     LanguageInfo {
         key: "ts",
+        language: "typescript",
         name: "TypeScript",
         extensions: &["ts", "tsx", "cts", "mts"],
+        version: "typescript:5.4",
         status: LanguageStability::Stable,
         notes: "Full analysis & type-aware heuristics",
     },
     LanguageInfo {
         key: "js",
+        language: "javascript",
         name: "JavaScript",
         extensions: &["js", "jsx", "mjs", "cjs"],
+        version: "javascript:es2023",
         status: LanguageStability::Stable,
         notes: "Full analysis & complexity metrics",
     },
     LanguageInfo {
         key: "rs",
+        language: "rust",
         name: "Rust",
         extensions: &["rs"],
+        version: "rust:2021",
         status: LanguageStability::Stable,
         notes: "Ownership-aware analysis",
     },
     LanguageInfo {
         key: "go",
+        language: "go",
         name: "Go",
         extensions: &["go"],
+        version: "go:1.22",
         status: LanguageStability::Beta,
         notes: "AST parsing & structure checks",
     },
     LanguageInfo {
         key: "cpp",
+        language: "cpp",
         name: "C++",
         extensions: &["cpp", "cxx", "cc", "c++", "hpp", "hxx", "hh", "h++", "h"],
+        version: "cpp:20",
         status: LanguageStability::Beta,
         notes: "Classes, namespaces, templates",
     },
 ];
 
-/// Return the languages that are compiled into this build.
-pub fn registered_languages() -> &'static [LanguageInfo] {
+/// Identifiers accepted for each canonical language key, including the key itself.
+const LANGUAGE_ALIASES: &[(&str, &[&str])] = &[
+    ("py", &["py", "pyw", "python"]),
+    ("js", &["js", "jsx", "mjs", "cjs", "javascript"]),
+    ("ts", &["ts", "tsx", "cts", "mts", "typescript"]),
+    ("rs", &["rs", "rust"]),
+    ("go", &["go", "golang"]),
+    (
+        "cpp",
+        &[
+            "cpp",
+            "cxx",
+            "cc",
+            "c++",
+            "hpp",
+            "hxx",
+            "hh",
+            "h++",
+            "h",
+            "cplusplus",
+        ],
+    ),
+];
+
+/// Return metadata for the languages that are compiled into this build.
+pub fn registered_language_info() -> &'static [LanguageInfo] {
     REGISTERED_LANGUAGES
 }
 
+/// Return the name of every registered language, sorted alphabetically.
+///
+/// Each name is accepted by [`adapter_for_language`].
+pub fn registered_languages() -> Vec<&'static str> {
+    let mut languages: Vec<&'static str> = REGISTERED_LANGUAGES
+        .iter()
+        .map(|info| info.language)
+        .collect();
+    languages.sort_unstable();
+    languages
+}
+
+/// Return every file extension mapped to a registered language, sorted alphabetically.
+pub fn supported_extensions() -> Vec<&'static str> {
+    let mut extensions: Vec<&'static str> = REGISTERED_LANGUAGES
+        .iter()
+        .flat_map(|info| info.extensions.iter().copied())
+        .collect();
+    extensions.sort_unstable();
+    extensions.dedup();
+    extensions
+}
+
 /// Identify the canonical language key for a file path.
 pub fn language_key_for_path(path: &Path) -> Option<String> {
     let ext = path.extension()?.to_string_lossy().to_ascii_lowercase();
@@ -94,6 +159,14 @@ pub fn language_key_for_path(path: &Path) -> Option<String> {
     find_language_by_extension(&ext).map(|info| info.key.to_string())
 }
 
+/// Look up the registered language matching a language name or key.
+pub fn language_info(language: &str) -> Option<&'static LanguageInfo> {
+    let key = normalize_language_key(language)?;
+    registered_language_info()
+        .iter()
+        .find(|info| info.key == key)
+}
+
 /// Create a language adapter suitable for analysing the provided file.
 pub fn adapter_for_file(path: &Path) -> Result<Box<dyn LanguageAdapter>> {
     let key = language_key_for_path(path).ok_or_else(|| {
@@ -138,14 +211,9 @@ pub fn get_tree_sitter_language(language_key: &str) -> Result<Language> {
     }
 }
 
-/// Detect language key from file path
+/// Detect language key from file path, falling back to `"unknown"` for unsupported files
 pub fn detect_language_from_path(file_path: &str) -> String {
-    std::path::Path::new(file_path)
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .and_then(|ext| find_language_by_extension(&ext.to_ascii_lowercase()))
-        .map(|info| info.key.to_string())
-        .unwrap_or_else(|| "txt".to_string())
+    language_key_for_path(Path::new(file_path)).unwrap_or_else(|| "unknown".to_string())
 }
 
 /// Create a new parser for the given language
@@ -170,7 +238,7 @@ pub fn extension_is_supported(ext: &str) -> bool {
 /// Finds the language info for a given file extension.
 fn find_language_by_extension(ext: &str) -> Option<&'static LanguageInfo> {
     let target = ext.trim_start_matches('.').to_ascii_lowercase();
-    registered_languages().iter().find(|info| {
+    registered_language_info().iter().find(|info| {
         info.extensions
             .iter()
             .any(|candidate| candidate.eq_ignore_ascii_case(&target))
@@ -179,20 +247,17 @@ fn find_language_by_extension(ext: &str) -> Option<&'static LanguageInfo> {
 
 /// Normalizes a language identifier to its canonical key.
 fn normalize_language_key(language: &str) -> Option<&'static str> {
-    match language.to_ascii_lowercase().as_str() {
-        "py" | "pyw" | "python" => Some("py"),
-        "js" | "jsx" | "mjs" | "cjs" | "javascript" => Some("js"),
-        "ts" | "tsx" | "cts" | "mts" | "typescript" => Some("ts"),
-        "rs" | "rust" => Some("rs"),
-        "go" | "golang" => Some("go"),
-        "cpp" | "cxx" | "cc" | "c++" | "hpp" | "hxx" | "hh" | "h++" | "h" | "cplusplus" => {
-            Some("cpp")
-        }
-        other => registered_languages()
-            .iter()
-            .find(|info| info.key == other)
-            .map(|info| info.key),
-    }
+    let language = language.to_ascii_lowercase();
+    LANGUAGE_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&language.as_str()))
+        .map(|(key, _)| *key)
+        .or_else(|| {
+            registered_language_info()
+                .iter()
+                .find(|info| info.key == language)
+                .map(|info| info.key)
+        })
 }
 
 #[cfg(test)]
@@ -251,6 +316,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_registered_languages_lists_adapter_keys() {
+        let languages = registered_languages();
+        for expected in ["go", "javascript", "python", "rust", "typescript"] {
+            assert!(
+                languages.contains(&expected),
+                "{} should be registered",
+                expected
+            );
+        }
+
+        let mut sorted = languages.clone();
+        sorted.sort_unstable();
+        assert_eq!(languages, sorted);
+        assert!(languages.iter().all(|language| {
+            adapter_for_language(language).is_ok_and(|adapter| adapter.language_name() == *language)
+        }));
+    }
+
+    #[test]
+    fn test_adapter_for_file_dispatches_python_stubs() {
+        let adapter = adapter_for_file(Path::new("typings/api.pyi")).expect("stub adapter");
+        assert_eq!(adapter.language_name(), "python");
+    }
+
+    #[test]
+    fn test_adapter_versions_match_registry() {
+        for info in registered_language_info() {
+            let adapter = adapter_for_language(info.key).expect("registered adapter");
+            assert_eq!(adapter.language_version(), info.version);
+            assert!(
+                info.version.contains(':'),
+                "{} is not language:version",
+                info.version
+            );
+        }
+    }
+
+    #[test]
+    fn test_supported_extensions_are_recognised() {
+        let extensions = supported_extensions();
+        assert!(extensions.contains(&"rs"));
+        assert!(extensions.contains(&"tsx"));
+        assert!(extensions.iter().all(|ext| extension_is_supported(ext)));
+    }
+
     #[test]
     fn test_extension_support() {
         for ext in [
@@ -290,4 +401,25 @@ mod tests {
         assert_eq!(detect_language_from_path("test.cpp"), "cpp");
         assert_eq!(detect_language_from_path("test.hpp"), "cpp");
     }
+
+    #[test]
+    fn test_detect_language_from_path_matches_registry_for_every_extension() {
+        for info in registered_language_info() {
+            for ext in info.extensions {
+                let file_path = format!("src/sample.{}", ext);
+                assert_eq!(
+                    detect_language_from_path(&file_path),
+                    language_key_for_path(Path::new(&file_path)).unwrap()
+                );
+                assert_eq!(detect_language_from_path(&file_path), info.key);
+                assert_eq!(
+                    detect_language_from_path(&file_path.to_ascii_uppercase()),
+                    info.key
+                );
+            }
+        }
+        assert_eq!(detect_language_from_path("README"), "unknown");
+        assert_eq!(detect_language_from_path("notes.md"), "unknown");
+        assert_eq!(detect_language_from_path("notes.txt"), "unknown");
+    }
 }
@@ -41,7 +41,7 @@
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     let config = AnalysisConfig::default()
-//!         .with_language("python")
+//!         .add_language("python")?
 //!         .enable_all_modules();
 //!
 //!     let mut engine = ValknutEngine::new(config).await?;
@@ -93,6 +93,7 @@ pub mod core {
     pub mod errors;
     pub mod featureset;
     pub mod file_utils;
+    pub mod git_utils;
     pub mod interned_entities;
     pub mod interning;
     pub mod partitioning;
@@ -206,7 +207,8 @@ mod lib_exports_tests {
     fn analysis_config_reexports_support_builder_flows() {
         let config = AnalysisConfig::default()
             .disable_all_modules()
-            .with_language("rust")
+            .add_language("rust")
+            .unwrap()
             .enable_all_modules()
             .with_confidence_threshold(0.6)
             .exclude_pattern("target/generated/*");
@@ -5,14 +5,14 @@
 use crate::core::errors::{Result, ValknutResultExt};
 use crate::core::partitioning::CodeSlice;
 use crate::core::pipeline::AnalysisResults;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
 use super::condense::{condense_analysis_results_with_budget, get_json_schema_instructions};
 use super::helpers::{
-    build_refactor_hints, calculate_file_priority, html_escape, is_test_file,
-    normalize_path_for_key, truncate_hint, FileCandidate,
+    build_refactor_hints, calculate_file_priority, html_escape, normalize_path_for_key,
+    project_walker, strip_test_entities, truncate_hint, FileCandidate,
 };
 use super::types::OracleConfig;
 
@@ -62,6 +62,8 @@ impl<'a> BundleBuilder<'a> {
         let mut total_tokens = 0;
         let mut files_included = 0;
         let mut files_skipped = 0;
+        let mut files_deduplicated = 0;
+        let mut first_path_by_digest: HashMap<[u8; 32], String> = HashMap::new();
 
         let refactor_hints = build_refactor_hints(analysis_results, project_path);
 
@@ -83,6 +85,22 @@ impl<'a> BundleBuilder<'a> {
 
         // Add files until we hit token budget
         for candidate in candidate_files {
+            // Symlinked or copied files share content; reference the first path instead
+            let digest: [u8; 32] = Sha256::digest(candidate.content.as_bytes()).into();
+            if let Some(original) = first_path_by_digest.get(&digest) {
+                xml_files.push(format!(
+                    "    <file path=\"{}\" duplicate_of=\"{}\"/>",
+                    html_escape(&candidate.path),
+                    html_escape(original)
+                ));
+                files_deduplicated += 1;
+                println!(
+                    "   🔗 Deduplicated: {} (same content as {})",
+                    candidate.path, original
+                );
+                continue;
+            }
+
             if total_tokens + candidate.tokens > self.config.max_tokens {
                 files_skipped += 1;
                 if files_skipped <= 5 {
@@ -104,7 +122,7 @@ impl<'a> BundleBuilder<'a> {
 
             xml_files.push(format!(
                 "    <file path=\"{}\" tuple=\"{}\" hint=\"{}\" type=\"{}\" tokens=\"{}\" priority=\"{:.2}\">\n{}\n    </file>",
-                candidate.path,
+                html_escape(&candidate.path),
                 html_escape(&tuple_label),
                 html_escape(&hints_truncated),
                 candidate.file_type,
@@ -115,6 +133,7 @@ impl<'a> BundleBuilder<'a> {
 
             total_tokens += candidate.tokens;
             files_included += 1;
+            first_path_by_digest.insert(digest, candidate.path.clone());
 
             println!(
                 "   ✅ Included: {} ({} tokens, priority: {:.2})",
@@ -174,6 +193,7 @@ impl<'a> BundleBuilder<'a> {
             {{\n\
               \"assessment\": {{\n\
                 \"summary\": \"<2-3 sentences on overall code quality>\",\n\
+                \"architectural_patterns\": [\"<AP1-AP4>\"],\n\
                 \"strengths\": [\"<strength1>\", \"<strength2>\"],\n\
                 \"issues\": [\"<issue1>\", \"<issue2>\"]\n\
               }},\n\
@@ -210,6 +230,7 @@ impl<'a> BundleBuilder<'a> {
         println!("   📦 Final bundle: ~{} tokens", final_tokens);
         println!("   📁 Files included: {}", files_included);
         println!("   ⏭️  Files skipped: {}", files_skipped);
+        println!("   🔗 Files deduplicated: {}", files_deduplicated);
 
         Ok(final_bundle)
     }
@@ -252,22 +273,11 @@ impl<'a> BundleBuilder<'a> {
 
     /// Collect and prioritize candidate source files.
     fn collect_candidate_files(&self, project_path: &Path) -> Result<Vec<FileCandidate>> {
-        let walker = WalkDir::new(project_path)
-            .max_depth(4)
-            .into_iter()
-            .filter_entry(|e| {
-                let path = e.path();
-                let name = path
-                    .file_name()
-                    .map(|n| n.to_string_lossy())
-                    .unwrap_or_default();
-
-                !name.starts_with('.') && !SKIP_DIRS.iter().any(|d| name == *d)
-            });
-
+        let test_paths = self.config.test_path_matcher();
+        let vendored_paths = self.config.vendored_path_matcher();
         let mut candidate_files = Vec::new();
 
-        for entry in walker {
+        for entry in project_walker(project_path, 4) {
             let entry = entry.map_generic_err("walking project directory")?;
             let path = entry.path();
 
@@ -287,16 +297,19 @@ impl<'a> BundleBuilder<'a> {
                 .to_string_lossy()
                 .to_string();
 
-            if is_test_file(&relative_path) {
+            if test_paths.is_match(&relative_path) {
                 continue;
             }
 
             let Ok(content) = std::fs::read_to_string(path) else {
                 continue;
             };
+            let Some(content) = strip_test_entities(path, &content) else {
+                continue;
+            };
 
             let estimated_tokens = content.len() / 4;
-            let priority = calculate_file_priority(&relative_path, ext, content.len());
+            let priority = calculate_file_priority(&relative_path, ext, &content, &vendored_paths);
 
             candidate_files.push(FileCandidate {
                 path: relative_path,
@@ -329,6 +342,9 @@ pub fn create_slice_bundle(
     let mut total_tokens = 0;
 
     for (path, content) in &slice.contents {
+        let Some(content) = strip_test_entities(path, content) else {
+            continue;
+        };
         let estimated_tokens = content.len() / 4;
         let path_str = path.to_string_lossy();
 
@@ -347,12 +363,12 @@ pub fn create_slice_bundle(
 
         xml_files.push(format!(
             "    <file path=\"{}\" tuple=\"{}\" hint=\"{}\" type=\"{}\" tokens=\"{}\">\n{}\n    </file>",
-            path_str,
+            html_escape(&path_str),
             html_escape(&tuple_label),
             html_escape(&hints_truncated),
             ext,
             estimated_tokens,
-            html_escape(content)
+            html_escape(&content)
         ));
 
         total_tokens += estimated_tokens;
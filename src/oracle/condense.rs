@@ -6,8 +6,9 @@
 use std::collections::HashSet;
 
 use crate::core::errors::Result;
-use crate::core::pipeline::AnalysisResults;
+use crate::core::pipeline::{AnalysisResults, RefactoringCandidate};
 use crate::core::scoring::Priority;
+use crate::detectors::lsh::{clone_clusters, deduplicate_results};
 
 /// Codebook for oracle request/response - reduces token usage by using short codes.
 /// Include this in prompts so the model knows the mapping.
@@ -42,10 +43,42 @@ pub const ORACLE_CODEBOOK: &str = r#"## Codebook (use these codes in your respon
 - E1: low - Quick fix, < 1 hour
 - E2: medium - Few hours to a day
 - E3: high - Multiple days
+
+### Architectural Patterns (AP)
+- AP1: layered - Distinct presentation/domain/data layers with downward dependencies
+- AP2: microservices - Independently deployable services communicating over the network
+- AP3: event-driven - Components coordinate through events, queues or message buses
+- AP4: monolithic - Single deployable unit with shared state and in-process calls
+
+### File Hints (HINT)
+- clone-group:N - File holds a member of an N-entity clone cluster; extract and deduplicate the group
 "#;
 
+/// Number of hub files surfaced to the oracle, in both the JSON and the
+/// budgeted text condensations.
+const TOP_HUB_FILES: usize = 5;
+
+/// How hub files are described to the oracle.
+const HUB_FILE_NOTE: &str = "frequently imported — changes here are high-risk";
+
+/// Number of import cycles surfaced to the oracle.
+const TOP_IMPORT_CYCLES: usize = 5;
+
+/// Refactoring candidates with each clone cluster merged into one entry,
+/// so the oracle does not read the same duplicate N times.
+fn deduplicated_candidates(results: &AnalysisResults) -> Vec<RefactoringCandidate> {
+    let candidates = results.refactoring_candidates.clone();
+    match &results.clone_analysis {
+        Some(clone_analysis) => {
+            deduplicate_results(candidates, &clone_clusters(&clone_analysis.clone_pairs))
+        }
+        None => candidates,
+    }
+}
+
 /// Condense valknut analysis results for AI consumption.
 pub fn condense_analysis_results(results: &AnalysisResults) -> String {
+    let candidates = deduplicated_candidates(results);
     serde_json::to_string_pretty(&serde_json::json!({
         "health_score": results.summary.code_health_score,
         "total_issues": results.summary.refactoring_needed,
@@ -55,7 +88,7 @@ pub fn condense_analysis_results(results: &AnalysisResults) -> String {
         "entities_analyzed": results.summary.entities_analyzed,
         "avg_refactoring_score": results.summary.avg_refactoring_score,
         "code_dictionary": results.code_dictionary.clone(),
-        "top_refactoring_candidates": results.refactoring_candidates.iter()
+        "top_refactoring_candidates": candidates.iter()
             .take(10)
             .map(|c| serde_json::json!({
                 "file": c.file_path,
@@ -67,6 +100,23 @@ pub fn condense_analysis_results(results: &AnalysisResults) -> String {
                 "suggestions": c.suggestions
             }))
             .collect::<Vec<_>>(),
+        "hub_files": results.hub_files.iter()
+            .take(TOP_HUB_FILES)
+            .map(|hub| serde_json::json!({
+                "file": hub.path,
+                "dependents": hub.dependent_count,
+                "risk_score": hub.risk_score,
+                "note": HUB_FILE_NOTE
+            }))
+            .collect::<Vec<_>>(),
+        "import_cycles": results.import_cycles.iter()
+            .take(TOP_IMPORT_CYCLES)
+            .map(|scc| serde_json::json!({
+                "files": scc.members,
+                "size": scc.size,
+                "internal_coupling": scc.internal_coupling
+            }))
+            .collect::<Vec<_>>(),
         "coverage": if !results.coverage_packs.is_empty() {
             Some(serde_json::json!({
                 "files_with_coverage": results.coverage_packs.len(),
@@ -90,8 +140,8 @@ pub fn condense_analysis_results_with_budget(
         token_budget
     );
 
-    let top_candidates: Vec<_> = results
-        .refactoring_candidates
+    let candidates = deduplicated_candidates(results);
+    let top_candidates: Vec<_> = candidates
         .iter()
         .filter(|c| !matches!(c.priority, Priority::None))
         .take(15)
@@ -116,6 +166,24 @@ pub fn condense_analysis_results_with_budget(
 
     let mut current_tokens = condensed.len() / 4;
 
+    let hub_section = format_hub_files(results);
+    let hub_tokens = hub_section.len() / 4;
+    if current_tokens + hub_tokens <= token_budget {
+        condensed.push_str(&hub_section);
+        current_tokens += hub_tokens;
+    } else {
+        println!("   ⏭️  Omitting hub files due to token budget");
+    }
+
+    let cycle_section = format_import_cycles(results);
+    let cycle_tokens = cycle_section.len() / 4;
+    if current_tokens + cycle_tokens <= token_budget {
+        condensed.push_str(&cycle_section);
+        current_tokens += cycle_tokens;
+    } else {
+        println!("   ⏭️  Omitting import cycles due to token budget");
+    }
+
     if !top_candidates.is_empty() {
         condensed.push_str("## Candidates\n");
         current_tokens += 15;
@@ -150,6 +218,51 @@ pub fn condense_analysis_results_with_budget(
     Ok(condensed)
 }
 
+/// Format the top hub files as a `path|dependents|risk` table followed by the
+/// risk note, or nothing when there are none.
+fn format_hub_files(results: &AnalysisResults) -> String {
+    if results.hub_files.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("## Hub Files\npath|dependents|risk\n");
+    for hub in results.hub_files.iter().take(TOP_HUB_FILES) {
+        section.push_str(&format!(
+            "{}|{}|{:.2}\n",
+            hub.path.display(),
+            hub.dependent_count,
+            hub.risk_score
+        ));
+    }
+    section.push_str(&format!("Hub files are {HUB_FILE_NOTE}.\n\n"));
+    section
+}
+
+/// Format the largest import cycles as a `size|coupling|files` table, or
+/// nothing when there are none.
+fn format_import_cycles(results: &AnalysisResults) -> String {
+    if results.import_cycles.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("## Import Cycles\nsize|coupling|files\n");
+    for scc in results.import_cycles.iter().take(TOP_IMPORT_CYCLES) {
+        let files: Vec<String> = scc
+            .members
+            .iter()
+            .map(|member| member.display().to_string())
+            .collect();
+        section.push_str(&format!(
+            "{}|{:.2}|{}\n",
+            scc.size,
+            scc.internal_coupling,
+            files.join(",")
+        ));
+    }
+    section.push('\n');
+    section
+}
+
 /// Collect issue and suggestion codes used by the given candidates.
 fn collect_used_codes(
     candidates: &[&crate::core::pipeline::RefactoringCandidate],
@@ -262,6 +375,7 @@ pub fn get_json_schema_instructions() -> String {
         {{\n\
           \"assessment\": {{\n\
             \"summary\": \"<2-3 sentences on code quality>\",\n\
+            \"architectural_patterns\": [\"<AP1-AP4>\"],\n\
             \"strengths\": [\"<strength>\"],\n\
             \"issues\": [\"<issue>\"]\n\
           }},\n\
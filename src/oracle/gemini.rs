@@ -36,6 +36,17 @@ pub struct GeminiGenerationConfig {
     pub max_output_tokens: i32,
     #[serde(rename = "responseMimeType")]
     pub response_mime_type: String,
+    /// Thinking settings for thinking models; omitted when `None`
+    #[serde(rename = "thinkingConfig", skip_serializing_if = "Option::is_none")]
+    pub thinking_config: Option<GeminiThinkingConfig>,
+}
+
+/// Thinking configuration for Gemini thinking models.
+#[derive(Debug, Serialize)]
+pub struct GeminiThinkingConfig {
+    /// Chain-of-thought token budget
+    #[serde(rename = "thinkingBudget")]
+    pub thinking_budget: u32,
 }
 
 /// Response from the Gemini API.
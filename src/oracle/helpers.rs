@@ -1,11 +1,19 @@
 //! Helper functions and types for the oracle module.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::Path;
 
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::{Walk, WalkBuilder};
+use once_cell::sync::Lazy;
+use tracing::debug;
+
 use crate::core::pipeline::AnalysisResults;
 use crate::core::scoring::Priority;
+use crate::detectors::lsh::clone_clusters;
+use crate::lang::registry::adapter_for_file;
 
+use super::bundle::SKIP_DIRS;
 use super::types::RefactoringTask;
 
 /// High-priority file patterns (boost priority significantly)
@@ -28,12 +36,36 @@ pub const EXTENSION_PRIORITIES: &[(&str, f32)] = &[
     ("cpp", 1.0),
 ];
 
+/// Minimum pairwise density for a clone cluster to be surfaced as a hint
+pub const CLONE_CLUSTER_DENSITY_THRESHOLD: f64 = 0.9;
+
 /// Penalty patterns for low-value files
 pub const PENALTY_PATTERNS: &[&str] = &["test", "spec", "_test"];
 
 /// Strong penalty patterns for generated/build files
 pub const STRONG_PENALTY_PATTERNS: &[&str] = &["generated", "target/", "build/"];
 
+/// Default glob patterns identifying vendored third-party code.
+///
+/// Matched like [`DEFAULT_TEST_PATH_PATTERNS`], so the directories are recognised
+/// at any depth but a file merely named `vendor.rs` is not.
+pub const DEFAULT_VENDORED_PATH_PATTERNS: &[&str] =
+    &["**/vendor/**", "**/third_party/**", "**/node_modules/**"];
+
+/// Header markers that identify generated files
+pub const GENERATED_FILE_MARKERS: &[&str] = &[
+    "DO NOT EDIT",
+    "AUTO-GENERATED",
+    "@generated",
+    "Code generated by",
+];
+
+/// Number of leading lines searched for [`GENERATED_FILE_MARKERS`]
+pub const GENERATED_HEADER_LINES: usize = 5;
+
+/// Priority assigned to vendored and generated files regardless of other factors
+pub const DOWN_RANKED_FILE_PRIORITY: f32 = 0.1;
+
 /// Candidate file for inclusion in the codebase bundle
 #[derive(Debug)]
 pub struct FileCandidate {
@@ -44,73 +76,243 @@ pub struct FileCandidate {
     pub file_type: String,
 }
 
-/// Check if a file path indicates it's a test file
-pub fn is_test_file(path: &str) -> bool {
-    let normalized = path.replace('\\', "/");
-    let lower = normalized.to_lowercase();
+/// Default glob patterns identifying test files.
+///
+/// Patterns are matched against `/`-separated relative paths and are
+/// smart-case: a pattern containing an uppercase letter matches case-sensitively
+/// (so `*IT.java` does not match `Kit.java`), all others ignore case.
+/// Inline Rust `#[cfg(test)]` modules live inside production files and can only
+/// be found by parsing, so only dedicated test files are matched here.
+pub const DEFAULT_TEST_PATH_PATTERNS: &[&str] = &[
+    // Test directories
+    "**/test/**",
+    "**/tests/**",
+    "**/__tests__/**",
+    "**/spec/**",
+    // Rust sibling and in-module test files
+    "**/*_test.rs",
+    "**/*_tests.rs",
+    "**/tests.rs",
+    // JavaScript / TypeScript
+    "**/*.test.{js,jsx,ts,tsx}",
+    "**/*.spec.{js,jsx,ts,tsx}",
+    "**/*_test.{js,ts}",
+    "**/*_spec.{js,ts}",
+    "**/*.stories.{js,jsx,ts,tsx}",
+    // Go
+    "**/*_test.go",
+    // Python
+    "**/test_*.py",
+    "**/*_test.py",
+    "**/conftest.py",
+    // JUnit / TestNG class naming
+    "**/Test*.java",
+    "**/*Test.java",
+    "**/*Tests.java",
+    "**/*IT.java",
+    "**/*_test.java",
+    // C / C++
+    "**/*_test.{c,cpp}",
+];
 
-    // Directory-based markers
-    const DIR_MARKERS: [&str; 4] = ["/test/", "/tests/", "/__tests__/", "/spec/"];
-    if DIR_MARKERS.iter().any(|marker| lower.contains(marker)) {
-        return true;
+/// Owned copy of [`DEFAULT_TEST_PATH_PATTERNS`] for configuration defaults.
+pub fn default_test_path_patterns() -> Vec<String> {
+    DEFAULT_TEST_PATH_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+/// Owned copy of [`DEFAULT_VENDORED_PATH_PATTERNS`] for configuration defaults.
+pub fn default_vendored_path_patterns() -> Vec<String> {
+    DEFAULT_VENDORED_PATH_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+/// Compile smart-case path `patterns`, skipping any that are not valid globs.
+fn compile_path_globs<S: AsRef<str>>(patterns: &[S]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.as_ref();
+        let case_sensitive = pattern.chars().any(char::is_uppercase);
+        if let Ok(glob) = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .case_insensitive(!case_sensitive)
+            .build()
+        {
+            builder.add(glob);
+        }
     }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
 
-    // Leading path components that typically house tests
-    const DIR_PREFIXES: [&str; 3] = ["tests/", "test/", "spec/"];
-    if DIR_PREFIXES.iter().any(|prefix| lower.starts_with(prefix)) {
-        return true;
+/// Compiled set of test path globs.
+#[derive(Debug, Clone)]
+pub struct TestPathMatcher {
+    globs: GlobSet,
+}
+
+/// Factory and matching methods for [`TestPathMatcher`].
+impl TestPathMatcher {
+    /// Compile `patterns`, skipping any that are not valid globs.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        Self {
+            globs: compile_path_globs(patterns),
+        }
     }
 
-    // File-name driven patterns (lowercased for case-insensitive matches)
-    const SUFFIXES: [&str; 16] = [
-        "_test.rs",
-        "_test.py",
-        "_test.js",
-        "_test.ts",
-        ".test.js",
-        ".test.ts",
-        ".test.tsx",
-        ".test.jsx",
-        "_spec.js",
-        "_spec.ts",
-        ".spec.js",
-        ".spec.ts",
-        "_test.go",
-        "_test.java",
-        "_test.cpp",
-        "_test.c",
-    ];
-    if SUFFIXES.iter().any(|suffix| lower.ends_with(suffix)) {
-        return true;
+    /// Check whether `path` matches any of the compiled patterns.
+    pub fn is_match(&self, path: &str) -> bool {
+        self.globs.is_match(path.replace('\\', "/"))
     }
+}
 
-    // Java naming conventions rely on original casing
-    if normalized.ends_with("Test.java")
-        || normalized.ends_with("Tests.java")
-        || (normalized.ends_with(".java") && normalized.contains("Test"))
-    {
-        return true;
+/// Compiled set of vendored path globs.
+#[derive(Debug, Clone)]
+pub struct VendoredPathMatcher {
+    globs: GlobSet,
+}
+
+/// Factory and matching methods for [`VendoredPathMatcher`].
+impl VendoredPathMatcher {
+    /// Compile `patterns`, skipping any that are not valid globs.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        Self {
+            globs: compile_path_globs(patterns),
+        }
     }
 
-    // Rust in-module tests (e.g., src/foo/tests.rs), but ignore the top-level tests.rs file
-    if lower.contains("tests.rs") && !lower.ends_with("/tests.rs") {
-        return true;
+    /// Check whether `path` matches any of the compiled patterns.
+    pub fn is_match(&self, path: &str) -> bool {
+        self.globs.is_match(path.replace('\\', "/"))
     }
+}
 
-    // Python conventions
-    if lower.starts_with("test_")
-        || lower.contains("/test_")
-        || lower.ends_with("/conftest.py")
-        || lower == "conftest.py"
-    {
-        return true;
+/// Walk the files under `project_root` that git would track, up to `max_depth`.
+///
+/// Honours nested `.gitignore` files, those in parent directories up to the
+/// repository root, `.git/info/exclude`, and the user's global gitignore. Hidden
+/// entries and [`SKIP_DIRS`] are skipped as well.
+pub fn project_walker(project_root: &Path, max_depth: usize) -> Walk {
+    WalkBuilder::new(project_root)
+        .standard_filters(true)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .hidden(true)
+        .max_depth(Some(max_depth))
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0 || !SKIP_DIRS.iter().any(|dir| name == *dir)
+        })
+        .build()
+}
+
+static DEFAULT_TEST_PATH_MATCHER: Lazy<TestPathMatcher> =
+    Lazy::new(|| TestPathMatcher::new(DEFAULT_TEST_PATH_PATTERNS));
+
+/// Check if a file path indicates it's a test file, using the default patterns
+pub fn is_test_file(path: &str) -> bool {
+    DEFAULT_TEST_PATH_MATCHER.is_match(path)
+}
+
+static DEFAULT_VENDORED_PATH_MATCHER: Lazy<VendoredPathMatcher> =
+    Lazy::new(|| VendoredPathMatcher::new(DEFAULT_VENDORED_PATH_PATTERNS));
+
+/// Check whether `path` lies in vendored third-party code, using the default patterns
+pub fn is_vendored_path(path: &str) -> bool {
+    DEFAULT_VENDORED_PATH_MATCHER.is_match(path)
+}
+
+/// Remove test code from `content`, using the `is_test` flag the language adapter
+/// sets on each parsed entity.
+///
+/// Catches test code that path patterns miss, such as inline `#[cfg(test)]`
+/// modules. Attribute and decorator lines directly above a removed entity go with
+/// it. Returns `None` when every top-level entity is a test, so the file can be
+/// skipped; files without an adapter, or that fail to parse, are returned unchanged.
+pub fn strip_test_entities(path: &Path, content: &str) -> Option<String> {
+    let Ok(mut adapter) = adapter_for_file(path) else {
+        return Some(content.to_string());
+    };
+    let index = match adapter.parse_source(content, &path.to_string_lossy()) {
+        Ok(index) => index,
+        Err(e) => {
+            debug!("Keeping unparsable file {} as-is: {}", path.display(), e);
+            return Some(content.to_string());
+        }
+    };
+
+    let mut top_level = index
+        .entities
+        .values()
+        .filter(|e| e.parent.is_none())
+        .peekable();
+    if top_level.peek().is_some() && top_level.all(|e| e.is_test) {
+        return None;
     }
 
-    false
+    let lines: Vec<&str> = content.lines().collect();
+    let mut removed = vec![false; lines.len()];
+    for entity in index.entities.values().filter(|e| e.is_test) {
+        let start = entity.location.start_line.saturating_sub(1);
+        let end = entity.location.end_line.min(lines.len());
+        for flag in removed.iter_mut().take(end).skip(start) {
+            *flag = true;
+        }
+        for line in (0..start).rev() {
+            let trimmed = lines[line].trim_start();
+            if !(trimmed.starts_with("#[") || trimmed.starts_with('@')) {
+                break;
+            }
+            removed[line] = true;
+        }
+    }
+
+    if !removed.contains(&true) {
+        return Some(content.to_string());
+    }
+    let kept: Vec<&str> = lines
+        .iter()
+        .zip(&removed)
+        .filter(|(_, removed)| !**removed)
+        .map(|(line, _)| *line)
+        .collect();
+    Some(kept.join("\n"))
+}
+
+/// Check whether the first [`GENERATED_HEADER_LINES`] lines mark the file as generated
+pub fn has_generated_header(content: &str) -> bool {
+    content.lines().take(GENERATED_HEADER_LINES).any(|line| {
+        let line = line.to_ascii_uppercase();
+        GENERATED_FILE_MARKERS
+            .iter()
+            .any(|marker| line.contains(&marker.to_ascii_uppercase()))
+    })
 }
 
 /// Calculate priority score for file inclusion
-pub fn calculate_file_priority(path: &str, extension: &str, size: usize) -> f32 {
+///
+/// Files matched by `vendored_paths` and generated files get
+/// [`DOWN_RANKED_FILE_PRIORITY`] outright.
+pub fn calculate_file_priority(
+    path: &str,
+    extension: &str,
+    content: &str,
+    vendored_paths: &VendoredPathMatcher,
+) -> f32 {
+    if vendored_paths.is_match(path) {
+        debug!("Down-ranking vendored file {}", path);
+        return DOWN_RANKED_FILE_PRIORITY;
+    }
+    if has_generated_header(content) {
+        debug!("Down-ranking generated file {}", path);
+        return DOWN_RANKED_FILE_PRIORITY;
+    }
+
+    let size = content.len();
     let mut priority = 1.0;
 
     // Boost priority for important files using const arrays
@@ -235,9 +437,43 @@ pub fn build_refactor_hints(
         hints.entry(normalized_path).or_default().push(hint);
     }
 
+    append_clone_group_hints(results, project_root, &mut hints);
+
     hints
 }
 
+/// Appends `clone-group:N` hints for files holding members of dense clone clusters.
+///
+/// Clusters are the connected components of the LSH clone pairs; only those
+/// whose density exceeds [`CLONE_CLUSTER_DENSITY_THRESHOLD`] are reported, so
+/// the oracle sees groups that can be extracted into a single shared helper.
+fn append_clone_group_hints(
+    results: &AnalysisResults,
+    project_root: &Path,
+    hints: &mut HashMap<String, Vec<String>>,
+) {
+    let Some(clone_analysis) = &results.clone_analysis else {
+        return;
+    };
+
+    for cluster in clone_clusters(&clone_analysis.clone_pairs) {
+        if cluster.density() <= CLONE_CLUSTER_DENSITY_THRESHOLD {
+            continue;
+        }
+        let hint = format!("clone-group:{}", cluster.members.len());
+        for file in &cluster.files {
+            let key = normalize_path_for_key(
+                Path::new(file)
+                    .strip_prefix(project_root)
+                    .unwrap_or_else(|_| Path::new(file))
+                    .to_string_lossy()
+                    .as_ref(),
+            );
+            hints.entry(key).or_default().push(hint.clone());
+        }
+    }
+}
+
 /// Abbreviates a label to a short identifier for display.
 pub fn abbreviate_label(label: &str) -> String {
     let words = label
@@ -296,12 +532,13 @@ pub fn normalize_path_for_key(path: &str) -> String {
     path.replace('\\', "/")
 }
 
-/// HTML escape utility function
+/// Escape the five XML special characters so content is safe in both text
+/// and quoted attribute values
 pub fn html_escape(content: &str) -> String {
     content
         .replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
         .replace('"', "&quot;")
-        .replace('\'', "&#x27;")
+        .replace('\'', "&apos;")
 }
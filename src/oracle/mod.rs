@@ -14,7 +14,10 @@ pub mod bundle;
 pub mod condense;
 pub mod gemini;
 pub mod helpers;
+pub mod ollama;
 pub mod slicing;
+#[cfg(feature = "streaming")]
+pub mod streaming;
 pub mod types;
 
 use crate::core::errors::{Result, ValknutError, ValknutResultExt};
@@ -24,20 +27,30 @@ use std::path::Path;
 
 // Re-export public types
 pub use types::{
-    CodebaseAssessment, OracleConfig, RefactoringOracleResponse, RefactoringRoadmap,
-    RefactoringTask,
+    CodebaseAssessment, OracleBackend, OracleConfig, RefactoringOracleResponse, RefactoringRoadmap,
+    RefactoringTask, TokenCostEstimate, GEMINI_2_5_PRO_COST_PER_MILLION_TOKENS,
+    GEMINI_2_5_PRO_OUTPUT_COST_PER_MILLION_TOKENS, ORACLE_MAX_OUTPUT_TOKENS,
 };
 
 // Re-export Gemini types for external use
 pub use gemini::{
     GeminiCandidate, GeminiContent, GeminiGenerationConfig, GeminiPart, GeminiRequest,
-    GeminiResponse, GeminiResponseContent, GeminiResponsePart, SliceAnalysisResult,
+    GeminiResponse, GeminiResponseContent, GeminiResponsePart, GeminiThinkingConfig,
+    SliceAnalysisResult,
 };
 
+// Re-export streaming decoders
+#[cfg(feature = "streaming")]
+pub use streaming::{GeminiStreamDecoder, StreamingResponseBuffer};
+
 // Re-export helper functions and types
 pub use helpers::{
-    abbreviate_label, build_refactor_hints, calculate_file_priority, html_escape, is_test_file,
-    normalize_path_for_key, task_priority_score, truncate_hint, FileCandidate,
+    abbreviate_label, build_refactor_hints, calculate_file_priority, default_test_path_patterns,
+    default_vendored_path_patterns, has_generated_header, html_escape, is_test_file,
+    is_vendored_path, normalize_path_for_key, project_walker, strip_test_entities,
+    task_priority_score, truncate_hint, FileCandidate, TestPathMatcher, VendoredPathMatcher,
+    DEFAULT_TEST_PATH_PATTERNS, DEFAULT_VENDORED_PATH_PATTERNS, DOWN_RANKED_FILE_PRIORITY,
+    GENERATED_HEADER_LINES,
 };
 
 // Re-export bundle functions and constants
@@ -53,7 +66,8 @@ pub use condense::{
 
 // Re-export slicing functions
 pub use slicing::{
-    aggregate_slice_results, collect_source_files, partition_codebase, print_slice_info,
+    aggregate_slice_results, collect_source_files, estimate_token_cost, partition_codebase,
+    print_slice_info,
 };
 
 /// AI refactoring oracle that provides intelligent suggestions using Gemini 2.5 Pro
@@ -75,6 +89,12 @@ impl RefactoringOracle {
         slicing::dry_run(&self.config, project_path)
     }
 
+    /// Estimate tokens consumed and API cost of [`Self::generate_suggestions`]
+    /// for `project_path` without calling the API.
+    pub async fn estimate_token_cost(&self, project_path: &Path) -> Result<TokenCostEstimate> {
+        estimate_token_cost(&self.config, project_path)
+    }
+
     /// Generate refactoring suggestions for the given codebase
     pub async fn generate_suggestions(
         &self,
@@ -82,7 +102,7 @@ impl RefactoringOracle {
         analysis_results: &AnalysisResults,
     ) -> Result<RefactoringOracleResponse> {
         // First, estimate total codebase size to decide on slicing strategy
-        let files = collect_source_files(project_path)?;
+        let files = collect_source_files(project_path, &self.config.test_path_matcher())?;
         let total_tokens: usize = files
             .iter()
             .filter_map(|f| std::fs::read_to_string(f).ok())
@@ -117,7 +137,7 @@ impl RefactoringOracle {
             .create_codebase_bundle(project_path, analysis_results)
             .await?;
 
-        self.query_gemini(&bundle, &self.config.model).await
+        self.query_model(&bundle, &self.config.model).await
     }
 
     /// Generate suggestions using sliced analysis (for larger codebases)
@@ -194,17 +214,24 @@ impl RefactoringOracle {
         analysis_results: &AnalysisResults,
     ) -> Result<RefactoringOracleResponse> {
         let bundle = create_slice_bundle(slice, project_path, analysis_results)?;
-        self.query_gemini(&bundle, &self.config.slice_model).await
+        self.query_model(&bundle, &self.config.slice_model).await
     }
 
-    /// Query Gemini API with the bundled content
-    async fn query_gemini(&self, content: &str, model: &str) -> Result<RefactoringOracleResponse> {
-        let url = format!(
-            "{}/{}:generateContent?key={}",
-            self.config.api_endpoint, model, self.config.api_key
-        );
+    /// Send the bundle to the configured backend.
+    ///
+    /// `model` selects the Gemini model; Ollama always uses its configured model.
+    async fn query_model(&self, content: &str, model: &str) -> Result<RefactoringOracleResponse> {
+        match &self.config.backend {
+            OracleBackend::Gemini => self.query_gemini(content, model).await,
+            OracleBackend::Ollama { base_url, model } => {
+                ollama::query_ollama(&self.client, base_url, model, content).await
+            }
+        }
+    }
 
-        let request = GeminiRequest {
+    /// Build the Gemini request body for the bundled content
+    fn build_gemini_request(&self, content: &str) -> GeminiRequest {
+        GeminiRequest {
             contents: vec![GeminiContent {
                 parts: vec![GeminiPart {
                     text: content.to_string(),
@@ -214,10 +241,23 @@ impl RefactoringOracle {
                 temperature: 0.2,
                 top_k: 40,
                 top_p: 0.95,
-                max_output_tokens: 32000,
+                max_output_tokens: ORACLE_MAX_OUTPUT_TOKENS as i32,
                 response_mime_type: "application/json".to_string(),
+                thinking_config: self
+                    .config
+                    .thinking_budget
+                    .map(|thinking_budget| GeminiThinkingConfig { thinking_budget }),
             },
-        };
+        }
+    }
+
+    /// Query Gemini API with the bundled content
+    async fn query_gemini(&self, content: &str, model: &str) -> Result<RefactoringOracleResponse> {
+        let url = format!(
+            "{}/{}:generateContent?key={}",
+            self.config.api_endpoint, model, self.config.api_key
+        );
+        let request = self.build_gemini_request(content);
 
         let response = self
             .client
@@ -262,6 +302,88 @@ impl RefactoringOracle {
         Ok(oracle_response)
     }
 
+    /// Generate suggestions while streaming the model output as it arrives.
+    ///
+    /// Uses the single-bundle approach regardless of codebase size; `on_token`
+    /// receives each chunk of generated text in order. The Ollama backend does
+    /// not stream and returns the full response without calling `on_token`.
+    #[cfg(feature = "streaming")]
+    pub async fn generate_suggestions_streaming(
+        &self,
+        project_path: &Path,
+        analysis_results: &AnalysisResults,
+        on_token: impl Fn(&str) + Send,
+    ) -> Result<RefactoringOracleResponse> {
+        let builder = BundleBuilder::new(&self.config);
+        let bundle = builder
+            .create_codebase_bundle(project_path, analysis_results)
+            .await?;
+
+        if let OracleBackend::Ollama { .. } = self.config.backend {
+            return self.query_model(&bundle, &self.config.model).await;
+        }
+        self.query_gemini_streaming(&bundle, &self.config.model, on_token)
+            .await
+    }
+
+    /// Query Gemini's streaming endpoint, buffering chunks until the response parses
+    #[cfg(feature = "streaming")]
+    async fn query_gemini_streaming(
+        &self,
+        content: &str,
+        model: &str,
+        on_token: impl Fn(&str) + Send,
+    ) -> Result<RefactoringOracleResponse> {
+        use tokio_stream::StreamExt;
+
+        let url = format!(
+            "{}/{}:streamGenerateContent?alt=sse&key={}",
+            self.config.api_endpoint, model, self.config.api_key
+        );
+        let request = self.build_gemini_request(content);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_generic_err("sending streaming request to Gemini API")?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ValknutError::internal(format!(
+                "Gemini API error: {}",
+                error_text
+            )));
+        }
+
+        let mut decoder = GeminiStreamDecoder::new();
+        let mut buffer = StreamingResponseBuffer::new();
+        let mut chunks = response.bytes_stream();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk.map_generic_err("reading Gemini response stream")?;
+            for fragment in decoder.push(&chunk) {
+                on_token(&fragment);
+                if let Some(parsed) = buffer.push(&fragment) {
+                    return Ok(parsed);
+                }
+            }
+        }
+        for fragment in decoder.finish() {
+            on_token(&fragment);
+            if let Some(parsed) = buffer.push(&fragment) {
+                return Ok(parsed);
+            }
+        }
+
+        serde_json::from_str(buffer.text()).map_json_err("streamed Oracle response")
+    }
+
     /// Condense valknut analysis results for AI consumption (delegation method for backward compatibility)
     pub fn condense_analysis_results(&self, results: &AnalysisResults) -> String {
         condense_analysis_results(results)
@@ -0,0 +1,79 @@
+//! Ollama API request and response types for local, offline oracle runs.
+//!
+//! Ollama's `POST /api/generate` endpoint is single-turn, so the bundle is sent
+//! as the prompt unchanged and the generated text is parsed as the oracle JSON.
+
+use serde::{Deserialize, Serialize};
+
+use super::types::RefactoringOracleResponse;
+use crate::core::errors::{Result, ValknutError, ValknutResultExt};
+
+/// Ollama `/api/generate` request body
+#[derive(Debug, Serialize)]
+pub struct OllamaRequest {
+    pub model: String,
+    pub prompt: String,
+    /// Always `false`; the oracle waits for the full response
+    pub stream: bool,
+    /// Always `"json"`, so Ollama constrains generation to valid JSON
+    pub format: String,
+}
+
+/// Ollama `/api/generate` response body
+#[derive(Debug, Deserialize)]
+pub struct OllamaResponse {
+    /// Generated text, expected to hold the oracle JSON
+    pub response: String,
+}
+
+/// Send a bundle to an Ollama server and parse the oracle response.
+///
+/// Connection failures map to [`ValknutError::OracleUnavailable`] so callers
+/// can tell an unreachable server apart from a bad response.
+pub async fn query_ollama(
+    client: &reqwest::Client,
+    base_url: &str,
+    model: &str,
+    prompt: &str,
+) -> Result<RefactoringOracleResponse> {
+    let url = format!("{}/api/generate", base_url.trim_end_matches('/'));
+    let request = OllamaRequest {
+        model: model.to_string(),
+        prompt: prompt.to_string(),
+        stream: false,
+        format: "json".to_string(),
+    };
+
+    let response = client
+        .post(&url)
+        .json(&request)
+        .send()
+        .await
+        .map_err(|err| {
+            if err.is_connect() || err.is_timeout() {
+                ValknutError::oracle_unavailable(base_url, err.to_string())
+            } else {
+                ValknutError::internal(format!(
+                    "Failed during sending request to Ollama API: {err}"
+                ))
+            }
+        })?;
+
+    if !response.status().is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(ValknutError::internal(format!(
+            "Ollama API error: {}",
+            error_text
+        )));
+    }
+
+    let ollama_response: OllamaResponse = response
+        .json()
+        .await
+        .map_generic_err("parsing Ollama API response")?;
+
+    serde_json::from_str(&ollama_response.response).map_json_err("Oracle response")
+}
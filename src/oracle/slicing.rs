@@ -6,25 +6,76 @@ use crate::core::errors::{Result, ValknutError, ValknutResultExt};
 use crate::core::partitioning::{
     CodeSlice, ImportGraphPartitioner, PartitionConfig, PartitionResult,
 };
+use crate::detectors::structure::file::cohesion::calculate_jaccard_similarity;
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
-use super::bundle::{SKIP_DIRS, SOURCE_EXTENSIONS};
+use super::bundle::SOURCE_EXTENSIONS;
 use super::gemini::SliceAnalysisResult;
-use super::helpers::{is_test_file, task_priority_score};
-use super::types::{CodebaseAssessment, OracleConfig, RefactoringOracleResponse, RefactoringTask};
+use super::helpers::{project_walker, task_priority_score, TestPathMatcher};
+use super::types::{
+    CodebaseAssessment, OracleConfig, RefactoringOracleResponse, RefactoringTask,
+    TokenCostEstimate, ORACLE_MAX_OUTPUT_TOKENS,
+};
 
-/// Dry-run mode: show slicing plan without calling the API.
-pub fn dry_run(config: &OracleConfig, project_path: &Path) -> Result<()> {
-    let files = collect_source_files(project_path)?;
-    let total_tokens: usize = files
+/// Minimum title+description similarity for two slice tasks to be merged.
+const TASK_MERGE_SIMILARITY: f64 = 0.7;
+
+/// Approximate tokens in `files`, relative to `project_path`, at ~4 bytes per token.
+fn estimate_source_tokens(project_path: &Path, files: &[PathBuf]) -> usize {
+    files
         .iter()
         .filter_map(|f| {
             let full_path = project_path.join(f);
             std::fs::read_to_string(&full_path).ok()
         })
         .map(|content| content.len() / 4)
-        .sum();
+        .sum()
+}
+
+/// Estimate token usage and API cost of an oracle run without calling the API.
+///
+/// Output tokens are bounded by [`ORACLE_MAX_OUTPUT_TOKENS`] per model call;
+/// one call is made per slice when the codebase would be sliced. Input and
+/// output tokens are priced separately.
+pub fn estimate_token_cost(
+    config: &OracleConfig,
+    project_path: &Path,
+) -> Result<TokenCostEstimate> {
+    let files = collect_source_files(project_path, &config.test_path_matcher())?;
+    let estimated_input_tokens = estimate_source_tokens(project_path, &files);
+
+    let slice_count = if config.enable_slicing && estimated_input_tokens > config.slicing_threshold
+    {
+        let partition_config =
+            PartitionConfig::default().with_token_budget(config.slice_token_budget);
+        let partitioner = ImportGraphPartitioner::new(partition_config);
+        partitioner
+            .partition(project_path, &files)?
+            .slices
+            .len()
+            .max(1)
+    } else {
+        1
+    };
+
+    let estimated_output_tokens = slice_count * ORACLE_MAX_OUTPUT_TOKENS;
+    let estimated_cost_usd = (estimated_input_tokens as f64 * config.cost_per_million_tokens
+        + estimated_output_tokens as f64 * config.output_cost_per_million_tokens)
+        / 1_000_000.0;
+
+    Ok(TokenCostEstimate {
+        estimated_input_tokens,
+        estimated_output_tokens,
+        estimated_cost_usd,
+        slice_count,
+    })
+}
+
+/// Dry-run mode: show slicing plan without calling the API.
+pub fn dry_run(config: &OracleConfig, project_path: &Path) -> Result<()> {
+    let files = collect_source_files(project_path, &config.test_path_matcher())?;
+    let total_tokens = estimate_source_tokens(project_path, &files);
 
     println!("\n🔍 [ORACLE DRY-RUN] Codebase Analysis");
     println!("   📁 Total source files: {}", files.len());
@@ -131,24 +182,14 @@ fn print_slice_details(partition_result: &PartitionResult) {
     }
 }
 
-/// Collect source files from project.
-pub fn collect_source_files(project_path: &Path) -> Result<Vec<PathBuf>> {
+/// Collect source files from project, skipping paths matched by `test_paths`.
+pub fn collect_source_files(
+    project_path: &Path,
+    test_paths: &TestPathMatcher,
+) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
 
-    let walker = WalkDir::new(project_path)
-        .max_depth(6)
-        .into_iter()
-        .filter_entry(|e| {
-            let path = e.path();
-            let name = path
-                .file_name()
-                .map(|n| n.to_string_lossy())
-                .unwrap_or_default();
-
-            !name.starts_with('.') && !SKIP_DIRS.iter().any(|d| name == *d)
-        });
-
-    for entry in walker {
+    for entry in project_walker(project_path, 6) {
         let entry = entry.map_generic_err("walking project directory")?;
         let path = entry.path();
 
@@ -160,7 +201,7 @@ pub fn collect_source_files(project_path: &Path) -> Result<Vec<PathBuf>> {
                         .unwrap_or(path)
                         .to_path_buf();
 
-                    if !is_test_file(&relative.to_string_lossy()) {
+                    if !test_paths.is_match(&relative.to_string_lossy()) {
                         files.push(relative);
                     }
                 }
@@ -240,24 +281,40 @@ fn aggregate_assessments(
     (summaries, strengths, issues)
 }
 
-/// Aggregate and sort tasks from all slice results.
-fn aggregate_tasks(results: &[SliceAnalysisResult]) -> Vec<RefactoringTask> {
-    let mut tasks = Vec::new();
-    let mut id_counter = 1;
+/// Architectural pattern codes reported by any slice, deduplicated in first-seen order.
+fn aggregate_patterns(results: &[SliceAnalysisResult]) -> Vec<String> {
+    let mut patterns: Vec<String> = Vec::new();
+    for result in results {
+        for pattern in &result.response.assessment.architectural_patterns {
+            if !patterns.contains(pattern) {
+                patterns.push(pattern.clone());
+            }
+        }
+    }
+    patterns
+}
 
+/// Aggregate, deduplicate and sort tasks from all slice results.
+fn aggregate_tasks(results: &[SliceAnalysisResult]) -> Vec<RefactoringTask> {
+    let mut candidates = Vec::new();
     for result in results {
         let prefix = get_module_prefix(result);
         for task in result.response.all_tasks() {
-            tasks.push(RefactoringTask {
-                id: format!("T{}", id_counter),
-                title: format!("[{}] {}", prefix, task.title),
-                depends_on: vec![],
-                ..task.clone()
-            });
-            id_counter += 1;
+            candidates.push((prefix.clone(), task.clone()));
         }
     }
 
+    let mut tasks: Vec<RefactoringTask> = deduplicate_tasks(candidates)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (prefixes, task))| RefactoringTask {
+            id: format!("T{}", index + 1),
+            title: format!("[{}] {}", prefixes.join(", "), task.title),
+            depends_on: vec![],
+            ..task
+        })
+        .collect();
+
     tasks.sort_by(|a, b| {
         task_priority_score(b)
             .partial_cmp(&task_priority_score(a))
@@ -267,6 +324,87 @@ fn aggregate_tasks(results: &[SliceAnalysisResult]) -> Vec<RefactoringTask> {
     tasks
 }
 
+/// Merge tasks whose title and description token sets are near-identical.
+///
+/// Each entry pairs a task with the module prefix of the slice it came from;
+/// merged tasks keep every prefix and the union of their files.
+fn deduplicate_tasks(
+    candidates: Vec<(String, RefactoringTask)>,
+) -> Vec<(Vec<String>, RefactoringTask)> {
+    let mut merged: Vec<(Vec<String>, RefactoringTask, BTreeSet<String>)> = Vec::new();
+
+    for (prefix, task) in candidates {
+        let tokens = task_tokens(&task);
+        let duplicate = merged.iter_mut().find(|(_, _, existing)| {
+            calculate_jaccard_similarity(existing, &tokens) > TASK_MERGE_SIMILARITY
+        });
+
+        match duplicate {
+            Some((prefixes, existing, _)) => {
+                if !prefixes.contains(&prefix) {
+                    prefixes.push(prefix);
+                }
+                merge_task_into(existing, task);
+            }
+            None => merged.push((vec![prefix], task, tokens)),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|(prefixes, task, _)| (prefixes, task))
+        .collect()
+}
+
+/// Lowercased word tokens of a task's title and description.
+fn task_tokens(task: &RefactoringTask) -> BTreeSet<String> {
+    format!("{} {}", task.title, task.description)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Fold a duplicate task into `target`, keeping the larger effort and risk.
+fn merge_task_into(target: &mut RefactoringTask, duplicate: RefactoringTask) {
+    target.risk = max_level(
+        target.get_risk().map(str::to_string),
+        duplicate.get_risk().map(str::to_string),
+    );
+    target.effort = max_level(target.effort.take(), duplicate.effort);
+    for file in duplicate.files {
+        if !target.files.contains(&file) {
+            target.files.push(file);
+        }
+    }
+}
+
+/// Pick the higher of two level codes (`E1`-`E3`, `R1`-`R3` or low/medium/high).
+fn max_level(a: Option<String>, b: Option<String>) -> Option<String> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if level_rank(&b) > level_rank(&a) {
+            b
+        } else {
+            a
+        }),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Rank a level code for comparison; unrecognized values rank lowest.
+fn level_rank(level: &str) -> u8 {
+    match level.to_ascii_lowercase().as_str() {
+        "low" => 1,
+        "medium" => 2,
+        "high" => 3,
+        code => code
+            .chars()
+            .last()
+            .and_then(|c| c.to_digit(10))
+            .map_or(0, |digit| digit as u8),
+    }
+}
+
 /// Aggregate results from multiple slices into a single response.
 pub fn aggregate_slice_results(
     slice_results: Vec<SliceAnalysisResult>,
@@ -294,6 +432,7 @@ pub fn aggregate_slice_results(
             )),
             architectural_narrative: None,
             architectural_style: None,
+            architectural_patterns: aggregate_patterns(&slice_results),
             strengths: strengths.into_iter().take(5).collect(),
             issues: issues.into_iter().take(10).collect(),
         },
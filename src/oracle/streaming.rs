@@ -0,0 +1,160 @@
+//! Server-Sent Events decoding for streamed Gemini responses.
+//!
+//! Gemini's `streamGenerateContent?alt=sse` endpoint sends one
+//! [`GeminiResponse`] per `data:` event, each carrying the next slice of the
+//! generated text. The oracle's JSON only parses once every slice has arrived.
+
+use super::gemini::GeminiResponse;
+use super::types::RefactoringOracleResponse;
+
+/// Incremental decoder that turns SSE body chunks into generated text fragments.
+#[derive(Debug, Default)]
+pub struct GeminiStreamDecoder {
+    /// Bytes received after the last complete event
+    pending: Vec<u8>,
+}
+
+/// Decoding methods for [`GeminiStreamDecoder`].
+impl GeminiStreamDecoder {
+    /// Create a decoder with an empty buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a body chunk and return the text of every event it completes.
+    ///
+    /// Chunks may split events (or UTF-8 sequences) anywhere; incomplete data
+    /// is held until a later chunk finishes it.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(chunk);
+
+        let mut fragments = Vec::new();
+        while let Some((end, separator_len)) = find_event_boundary(&self.pending) {
+            let event: Vec<u8> = self.pending.drain(..end + separator_len).collect();
+            fragments.extend(decode_event(&event[..end]));
+        }
+        fragments
+    }
+
+    /// Flush a trailing event that was not followed by a blank line.
+    pub fn finish(&mut self) -> Vec<String> {
+        let event = std::mem::take(&mut self.pending);
+        decode_event(&event).into_iter().collect()
+    }
+}
+
+/// Accumulates streamed text until it forms a complete oracle response.
+#[derive(Debug, Default)]
+pub struct StreamingResponseBuffer {
+    text: String,
+}
+
+/// Buffering methods for [`StreamingResponseBuffer`].
+impl StreamingResponseBuffer {
+    /// Create an empty buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a fragment and return the response once the buffer parses.
+    pub fn push(&mut self, fragment: &str) -> Option<RefactoringOracleResponse> {
+        self.text.push_str(fragment);
+        if !self.text.trim_end().ends_with('}') {
+            return None;
+        }
+        serde_json::from_str(self.text.trim()).ok()
+    }
+
+    /// Text accumulated so far
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Position and length of the first blank-line event separator in `buffer`.
+fn find_event_boundary(buffer: &[u8]) -> Option<(usize, usize)> {
+    (0..buffer.len()).find_map(|i| {
+        if buffer[i..].starts_with(b"\r\n\r\n") {
+            Some((i, 4))
+        } else if buffer[i..].starts_with(b"\n\n") {
+            Some((i, 2))
+        } else {
+            None
+        }
+    })
+}
+
+/// Extract the generated text from a single SSE event, if it carries any.
+fn decode_event(event: &[u8]) -> Option<String> {
+    let event = String::from_utf8_lossy(event);
+    let data: Vec<&str> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    if data.is_empty() {
+        return None;
+    }
+
+    let response: GeminiResponse = match serde_json::from_str(&data.join("\n")) {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::debug!("Skipping undecodable Gemini stream event: {}", e);
+            return None;
+        }
+    };
+    let text: String = response
+        .candidates
+        .into_iter()
+        .next()?
+        .content
+        .parts
+        .into_iter()
+        .map(|part| part.text)
+        .collect();
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(text: &str) -> String {
+        let payload = serde_json::json!({
+            "candidates": [{ "content": { "parts": [{ "text": text }] } }]
+        });
+        format!("data: {}\r\n\r\n", payload)
+    }
+
+    #[test]
+    fn decoder_reassembles_events_split_across_chunks() {
+        let body = format!("{}{}", event("{\"a\":"), event(" 1}"));
+        let (first, second) = body.as_bytes().split_at(body.len() / 2 + 3);
+
+        let mut decoder = GeminiStreamDecoder::new();
+        let mut fragments = decoder.push(first);
+        fragments.extend(decoder.push(second));
+        fragments.extend(decoder.finish());
+
+        assert_eq!(fragments, vec!["{\"a\":".to_string(), " 1}".to_string()]);
+    }
+
+    #[test]
+    fn decoder_skips_events_without_candidates() {
+        let mut decoder = GeminiStreamDecoder::new();
+        let fragments = decoder.push(b": keep-alive\n\ndata: {\"usageMetadata\": {}}\n\n");
+        assert!(fragments.is_empty());
+    }
+
+    #[test]
+    fn buffer_parses_once_the_response_is_complete() {
+        let json = r#"{"assessment": {"summary": "ok"}, "tasks": []}"#;
+        let (head, tail) = json.split_at(json.len() / 2);
+
+        let mut buffer = StreamingResponseBuffer::new();
+        assert!(buffer.push(head).is_none());
+        let response = buffer.push(tail).expect("complete response parses");
+        assert_eq!(response.assessment.get_summary(), "ok");
+        assert_eq!(buffer.text(), json);
+    }
+}
@@ -12,6 +12,7 @@ use crate::core::pipeline::*;
 // Use the 3-field MemoryStats from result_types (for AnalysisStatistics)
 use crate::core::pipeline::results::result_types::MemoryStats;
 use crate::core::scoring::Priority;
+use crate::detectors::graph::{HubFile, Scc};
 
 fn oracle_config_fixture(max_tokens: usize) -> OracleConfig {
     OracleConfig {
@@ -23,6 +24,12 @@ fn oracle_config_fixture(max_tokens: usize) -> OracleConfig {
         slice_token_budget: 200_000,
         slice_model: "gemini-2.0-flash".to_string(),
         slicing_threshold: 300_000,
+        test_path_patterns: default_test_path_patterns(),
+        vendored_path_patterns: default_vendored_path_patterns(),
+        thinking_budget: None,
+        backend: OracleBackend::Gemini,
+        cost_per_million_tokens: GEMINI_2_5_PRO_COST_PER_MILLION_TOKENS,
+        output_cost_per_million_tokens: GEMINI_2_5_PRO_OUTPUT_COST_PER_MILLION_TOKENS,
     }
 }
 
@@ -171,6 +178,10 @@ fn analysis_results_fixture(project_root: &Path) -> AnalysisResults {
         },
         clone_analysis: None,
         coverage_packs: Vec::new(),
+        coverage_hot_spots: Vec::new(),
+        shotgun_surgery: Vec::new(),
+        change_coupled_pairs: Vec::new(),
+        unique_entities: Vec::new(),
         warnings: Vec::new(),
         health_metrics: Some(HealthMetrics {
             overall_health_score: 58.0,
@@ -186,6 +197,10 @@ fn analysis_results_fixture(project_root: &Path) -> AnalysisResults {
         file_health: HashMap::new(),
         entity_health: HashMap::new(),
         directory_health_tree: None,
+        cancelled: false,
+        repo_root: None,
+        hub_files: Vec::new(),
+        import_cycles: Vec::new(),
     }
 }
 
@@ -200,6 +215,12 @@ fn test_oracle_config_creation() {
         slice_token_budget: 200_000,
         slice_model: "gemini-2.0-flash".to_string(),
         slicing_threshold: 300_000,
+        test_path_patterns: default_test_path_patterns(),
+        vendored_path_patterns: default_vendored_path_patterns(),
+        thinking_budget: None,
+        backend: OracleBackend::Gemini,
+        cost_per_million_tokens: GEMINI_2_5_PRO_COST_PER_MILLION_TOKENS,
+        output_cost_per_million_tokens: GEMINI_2_5_PRO_OUTPUT_COST_PER_MILLION_TOKENS,
     };
 
     assert_eq!(config.api_key, "test-key");
@@ -275,38 +296,210 @@ fn test_is_test_file_patterns() {
     assert!(!is_test_file("src/api/mod.rs"));
 }
 
+#[test]
+fn test_is_test_file_ecosystem_conventions() {
+    assert!(is_test_file("web/src/__tests__/Button.tsx"));
+    assert!(is_test_file("web/src/Button.spec.ts"));
+    assert!(is_test_file("web/src/Button.test.ts"));
+    assert!(is_test_file("web/src/Button.stories.tsx"));
+    assert!(is_test_file("pkg/server/handler_test.go"));
+    assert!(is_test_file("project/tests/helpers.py"));
+    assert!(is_test_file("src/main/java/com/acme/ParserTest.java"));
+    assert!(is_test_file("src/main/java/com/acme/ParserTests.java"));
+    assert!(is_test_file("src/main/java/com/acme/ParserIT.java"));
+    assert!(is_test_file("src\\oracle\\tests.rs"));
+
+    // Uppercase patterns stay case-sensitive
+    assert!(!is_test_file("src/main/java/com/acme/Kit.java"));
+    assert!(!is_test_file("web/src/Button.tsx"));
+    assert!(!is_test_file("pkg/server/handler.go"));
+}
+
+#[test]
+fn test_strip_test_entities_removes_inline_test_modules() {
+    let source = "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn adds() {\n        assert_eq!(add(1, 2), 3);\n    }\n}\n";
+
+    let stripped = strip_test_entities(Path::new("src/math.rs"), source).unwrap();
+    assert!(stripped.contains("pub fn add"));
+    assert!(!stripped.contains("cfg(test)"));
+    assert!(!stripped.contains("mod tests"));
+    assert!(!stripped.contains("assert_eq!"));
+}
+
+#[test]
+fn test_strip_test_entities_skips_all_test_files() {
+    let go_source = "package server\n\nfunc TestHandle(t *testing.T) {\n}\n";
+    assert!(strip_test_entities(Path::new("server/handler_test.go"), go_source).is_none());
+    assert_eq!(
+        strip_test_entities(Path::new("server/handler.go"), go_source).as_deref(),
+        Some(go_source)
+    );
+
+    let py_source = "def test_one():\n    assert True\n\ndef test_two():\n    assert True\n";
+    assert!(strip_test_entities(Path::new("checks.py"), py_source).is_none());
+
+    let unsupported = "just notes";
+    assert_eq!(
+        strip_test_entities(Path::new("notes.txt"), unsupported).as_deref(),
+        Some(unsupported)
+    );
+}
+
+#[test]
+fn test_oracle_config_custom_test_path_patterns() {
+    let config = oracle_config_fixture(100_000);
+    assert_eq!(
+        config.test_path_patterns.len(),
+        DEFAULT_TEST_PATH_PATTERNS.len()
+    );
+
+    let config = config.with_test_path_patterns(vec!["**/fixtures/**".to_string()]);
+    let matcher = config.test_path_matcher();
+    assert!(matcher.is_match("data/fixtures/sample.rs"));
+    assert!(!matcher.is_match("tests/integration.rs"));
+}
+
+#[test]
+fn collect_source_files_respects_nested_gitignore_and_info_exclude() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("project");
+    let root = root.as_path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("generated")).unwrap();
+    fs::create_dir_all(root.join(".git/info")).unwrap();
+    fs::write(root.join(".gitignore"), "generated/\nsecrets.rs\n").unwrap();
+    fs::write(root.join(".git/info/exclude"), "scratch.rs\n").unwrap();
+    fs::write(root.join("src/.gitignore"), "draft.rs\n").unwrap();
+    for file in [
+        "src/main.rs",
+        "src/draft.rs",
+        "src/secrets.rs",
+        "src/scratch.rs",
+        "generated/bindings.rs",
+    ] {
+        fs::write(root.join(file), "fn main() {}\n").unwrap();
+    }
+
+    let matcher = TestPathMatcher::new(&default_test_path_patterns());
+    let files = collect_source_files(root, &matcher).unwrap();
+
+    assert_eq!(files, vec![PathBuf::from("src/main.rs")]);
+}
+
+#[tokio::test]
+async fn estimate_token_cost_sums_source_tokens_and_prices_single_call() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("project");
+    let root = root.as_path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/main.rs"), "x".repeat(4_000)).unwrap();
+    fs::write(root.join("src/lib.rs"), "y".repeat(2_000)).unwrap();
+    fs::write(root.join("notes.md"), "z".repeat(8_000)).unwrap();
+
+    let config = oracle_config_fixture(400_000)
+        .with_cost_per_million_tokens(2.0)
+        .with_output_cost_per_million_tokens(8.0);
+    let estimate = RefactoringOracle::new(config)
+        .estimate_token_cost(root)
+        .await
+        .unwrap();
+
+    assert_eq!(estimate.estimated_input_tokens, 1_500);
+    assert_eq!(estimate.slice_count, 1);
+    assert_eq!(estimate.estimated_output_tokens, ORACLE_MAX_OUTPUT_TOKENS);
+    let expected_cost = (1_500.0 * 2.0 + ORACLE_MAX_OUTPUT_TOKENS as f64 * 8.0) / 1_000_000.0;
+    assert!((estimate.estimated_cost_usd - expected_cost).abs() < 1e-12);
+}
+
+#[test]
+fn ollama_config_estimates_zero_cost() {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("project");
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("main.py"), "print('hi')\n".repeat(100)).unwrap();
+
+    let config = OracleConfig::ollama("http://localhost:11434", "llama3");
+    let estimate = estimate_token_cost(&config, &root).unwrap();
+
+    assert!(estimate.estimated_input_tokens > 0);
+    assert_eq!(estimate.estimated_cost_usd, 0.0);
+}
+
 #[test]
 fn test_calculate_file_priority() {
+    let vendored = VendoredPathMatcher::new(DEFAULT_VENDORED_PATH_PATTERNS);
+
     // High priority files
-    assert!(calculate_file_priority("src/main.rs", "rs", 1000) > 3.0);
-    assert!(calculate_file_priority("src/lib.rs", "rs", 1000) > 3.0);
-    assert!(calculate_file_priority("src/core/mod.rs", "rs", 1000) > 3.0);
+    assert!(calculate_file_priority("src/main.rs", "rs", &source_of_len(1000), &vendored) > 3.0);
+    assert!(calculate_file_priority("src/lib.rs", "rs", &source_of_len(1000), &vendored) > 3.0);
+    assert!(
+        calculate_file_priority("src/core/mod.rs", "rs", &source_of_len(1000), &vendored) > 3.0
+    );
 
     // Config and API files get boost
-    assert!(calculate_file_priority("src/config.rs", "rs", 1000) > 2.0);
-    assert!(calculate_file_priority("src/api/mod.rs", "rs", 1000) > 2.0);
+    assert!(calculate_file_priority("src/config.rs", "rs", &source_of_len(1000), &vendored) > 2.0);
+    assert!(calculate_file_priority("src/api/mod.rs", "rs", &source_of_len(1000), &vendored) > 2.0);
 
     // Language priorities
     assert!(
-        calculate_file_priority("src/module.rs", "rs", 1000)
-            > calculate_file_priority("src/module.py", "py", 1000)
+        calculate_file_priority("src/module.rs", "rs", &source_of_len(1000), &vendored)
+            > calculate_file_priority("src/module.py", "py", &source_of_len(1000), &vendored)
     );
     assert!(
-        calculate_file_priority("src/module.py", "py", 1000)
-            > calculate_file_priority("src/module.c", "c", 1000)
+        calculate_file_priority("src/module.py", "py", &source_of_len(1000), &vendored)
+            > calculate_file_priority("src/module.c", "c", &source_of_len(1000), &vendored)
     );
 
     // Size penalties
     assert!(
-        calculate_file_priority("src/large.rs", "rs", 100_000)
-            < calculate_file_priority("src/small.rs", "rs", 1000)
+        calculate_file_priority("src/large.rs", "rs", &source_of_len(100_000), &vendored)
+            < calculate_file_priority("src/small.rs", "rs", &source_of_len(1000), &vendored)
     );
 
     // Test file penalty
     assert!(
-        calculate_file_priority("src/module.rs", "rs", 1000)
-            > calculate_file_priority("src/module_test.rs", "rs", 1000)
+        calculate_file_priority("src/module.rs", "rs", &source_of_len(1000), &vendored)
+            > calculate_file_priority("src/module_test.rs", "rs", &source_of_len(1000), &vendored)
+    );
+}
+
+fn source_of_len(len: usize) -> String {
+    "x".repeat(len)
+}
+
+#[test]
+fn test_calculate_file_priority_down_ranks_vendored_and_generated_files() {
+    let vendored = VendoredPathMatcher::new(DEFAULT_VENDORED_PATH_PATTERNS);
+    let source = source_of_len(1000);
+    assert_eq!(
+        calculate_file_priority("src/vendor/lib.rs", "rs", &source, &vendored),
+        DOWN_RANKED_FILE_PRIORITY
+    );
+    assert_eq!(
+        calculate_file_priority("third_party/zlib/main.rs", "rs", &source, &vendored),
+        DOWN_RANKED_FILE_PRIORITY
+    );
+    assert!(calculate_file_priority("src/vendor.rs", "rs", &source, &vendored) > 1.0);
+
+    let generated = "// Copyright\n// Code generated by protoc-gen-go. DO NOT EDIT.\npackage pb\n";
+    assert_eq!(
+        calculate_file_priority("src/lib.rs", "rs", generated, &vendored),
+        DOWN_RANKED_FILE_PRIORITY
     );
+    assert!(has_generated_header("# auto-generated by build.py\n"));
+
+    assert!(is_vendored_path("web\\node_modules\\react\\index.js"));
+    let custom = OracleConfig::ollama("http://localhost:11434", "llama3")
+        .with_vendored_path_patterns(vec!["**/extern/**".to_string()])
+        .vendored_path_matcher();
+    assert_eq!(
+        calculate_file_priority("extern/zlib/main.rs", "rs", &source, &custom),
+        DOWN_RANKED_FILE_PRIORITY
+    );
+    assert!(calculate_file_priority("src/vendor/lib.rs", "rs", &source, &custom) > 1.0);
+
+    let late_marker = format!("{}// @generated\n", "line\n".repeat(GENERATED_HEADER_LINES));
+    assert!(!has_generated_header(&late_marker));
 }
 
 #[test]
@@ -316,11 +509,45 @@ fn test_html_escape() {
     assert_eq!(html_escape("hello & world"), "hello &amp; world");
     assert_eq!(html_escape("<tag>"), "&lt;tag&gt;");
     assert_eq!(html_escape("\"quoted\""), "&quot;quoted&quot;");
-    assert_eq!(html_escape("'single'"), "&#x27;single&#x27;");
+    assert_eq!(html_escape("'single'"), "&apos;single&apos;");
     assert_eq!(
         html_escape("<script>alert('hello');</script>"),
-        "&lt;script&gt;alert(&#x27;hello&#x27;);&lt;/script&gt;"
+        "&lt;script&gt;alert(&apos;hello&apos;);&lt;/script&gt;"
+    );
+}
+
+#[test]
+fn test_html_escape_produces_well_formed_xml() {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let snippet = r#"if a < b && b > c { println!("{}", 'x'); }"#;
+    let xml = format!(
+        "<file tuple=\"{}\" hint='{}'>{}</file>",
+        html_escape(snippet),
+        html_escape(snippet),
+        html_escape(snippet)
     );
+
+    let mut reader = Reader::from_str(&xml);
+    let mut attributes = Vec::new();
+    let mut text = String::new();
+    loop {
+        match reader.read_event().expect("well-formed XML") {
+            Event::Start(start) => {
+                for attribute in start.attributes() {
+                    let attribute = attribute.expect("valid attribute");
+                    attributes.push(attribute.unescape_value().unwrap().into_owned());
+                }
+            }
+            Event::Text(content) => text.push_str(&content.unescape().unwrap()),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    assert_eq!(attributes, vec![snippet.to_string(), snippet.to_string()]);
+    assert_eq!(text, snippet);
 }
 
 #[test]
@@ -348,6 +575,7 @@ fn test_codebase_assessment_structure() {
         ),
         architectural_narrative: None,
         architectural_style: Some("Pipeline Architecture with Modular Detectors".to_string()),
+        architectural_patterns: vec![],
         strengths: vec!["Good modularity".to_string()],
         issues: vec![
             "Configuration complexity".to_string(),
@@ -406,6 +634,7 @@ fn test_oracle_response_structure() {
             summary: Some("The codebase is well-structured.".to_string()),
             architectural_narrative: None,
             architectural_style: None,
+            architectural_patterns: vec![],
             strengths: vec!["Good modularity".to_string()],
             issues: vec!["Testing".to_string()],
         },
@@ -420,6 +649,57 @@ fn test_oracle_response_structure() {
     assert!(response.all_tasks().is_empty());
 }
 
+fn response_with_dependencies(edges: &[(&str, &[&str])]) -> RefactoringOracleResponse {
+    let tasks = edges
+        .iter()
+        .map(|(id, depends_on)| RefactoringTask {
+            id: id.to_string(),
+            depends_on: depends_on.iter().map(|dep| dep.to_string()).collect(),
+            ..slice_task("Task", "Task", "src/lib.rs", "E1", "R1")
+        })
+        .collect();
+    RefactoringOracleResponse {
+        assessment: CodebaseAssessment {
+            summary: None,
+            architectural_narrative: None,
+            architectural_style: None,
+            architectural_patterns: vec![],
+            strengths: vec![],
+            issues: vec![],
+        },
+        tasks,
+        refactoring_roadmap: None,
+    }
+}
+
+#[test]
+fn test_topologically_sorted_tasks_orders_dependencies_first() {
+    let response = response_with_dependencies(&[
+        ("T3", &["T1", "T2"]),
+        ("T1", &[]),
+        ("T4", &["T3", "T9"]),
+        ("T2", &["T1"]),
+    ]);
+
+    let ids: Vec<&str> = response
+        .topologically_sorted_tasks()
+        .expect("acyclic")
+        .iter()
+        .map(|task| task.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["T1", "T2", "T3", "T4"]);
+}
+
+#[test]
+fn test_topologically_sorted_tasks_reports_cycles() {
+    let response = response_with_dependencies(&[("T1", &[]), ("T2", &["T3"]), ("T3", &["T2"])]);
+
+    assert_eq!(
+        response.topologically_sorted_tasks().unwrap_err(),
+        vec!["T2".to_string(), "T3".to_string()]
+    );
+}
+
 #[test]
 fn test_condense_analysis_results() {
     use std::collections::HashMap;
@@ -465,6 +745,10 @@ fn test_condense_analysis_results() {
         },
         clone_analysis: None,
         coverage_packs: vec![],
+        coverage_hot_spots: vec![],
+        shotgun_surgery: Vec::new(),
+        change_coupled_pairs: Vec::new(),
+        unique_entities: Vec::new(),
         warnings: vec![],
         health_metrics: None,
         code_dictionary: CodeDictionary::default(),
@@ -473,6 +757,10 @@ fn test_condense_analysis_results() {
         file_health: HashMap::new(),
         entity_health: HashMap::new(),
         directory_health_tree: None,
+        cancelled: false,
+        repo_root: None,
+        hub_files: Vec::new(),
+        import_cycles: Vec::new(),
     };
 
     let condensed = oracle.condense_analysis_results(&results);
@@ -500,6 +788,7 @@ fn test_gemini_request_structure() {
             top_p: 0.95,
             max_output_tokens: 8192,
             response_mime_type: "application/json".to_string(),
+            thinking_config: None,
         },
     };
 
@@ -511,6 +800,28 @@ fn test_gemini_request_structure() {
     );
 }
 
+#[test]
+fn test_gemini_generation_config_nests_thinking_budget_only_when_set() {
+    let mut generation_config = GeminiGenerationConfig {
+        temperature: 0.2,
+        top_k: 40,
+        top_p: 0.95,
+        max_output_tokens: 8192,
+        response_mime_type: "application/json".to_string(),
+        thinking_config: None,
+    };
+
+    let json = serde_json::to_value(&generation_config).unwrap();
+    assert!(json.get("thinkingConfig").is_none());
+
+    generation_config.thinking_config = Some(GeminiThinkingConfig {
+        thinking_budget: 4096,
+    });
+    let json = serde_json::to_value(&generation_config).unwrap();
+    assert_eq!(json["thinkingConfig"]["thinkingBudget"], 4096);
+    assert!(json.get("thinkingBudget").is_none());
+}
+
 #[test]
 fn test_gemini_response_structure() {
     let response = GeminiResponse {
@@ -570,6 +881,121 @@ fn build_refactor_hints_normalizes_paths_and_limits_size() {
     );
 }
 
+fn clone_pair(source: (&str, &str), target: (&str, &str)) -> serde_json::Value {
+    serde_json::json!({
+        "source": { "id": source.0, "name": source.0, "path": source.1 },
+        "target": { "id": target.0, "name": target.0, "path": target.1 },
+        "similarity": 0.95,
+    })
+}
+
+#[test]
+fn build_refactor_hints_flags_dense_clone_groups() {
+    let project = tempdir().unwrap();
+    let root = project.path().join("workspace");
+    let mut results = analysis_results_fixture(&root);
+    let a = root.join("src/a.rs").to_string_lossy().into_owned();
+    let b = root.join("src/b.rs").to_string_lossy().into_owned();
+    let c = root.join("src/c.rs").to_string_lossy().into_owned();
+    let d = root.join("src/d.rs").to_string_lossy().into_owned();
+    let e = root.join("src/e.rs").to_string_lossy().into_owned();
+    let f = root.join("src/f.rs").to_string_lossy().into_owned();
+    results.clone_analysis = Some(CloneAnalysisResults {
+        denoising_enabled: false,
+        auto_calibration_applied: None,
+        candidates_before_denoising: None,
+        candidates_after_denoising: 5,
+        calibrated_threshold: None,
+        quality_score: None,
+        avg_similarity: None,
+        max_similarity: None,
+        verification: None,
+        phase_filtering_stats: None,
+        performance_metrics: None,
+        notes: Vec::new(),
+        clone_pairs: vec![
+            // Fully connected triangle: density 1.0.
+            clone_pair(("a1", &a), ("b1", &b)),
+            clone_pair(("b1", &b), ("c1", &c)),
+            clone_pair(("a1", &a), ("c1", &c)),
+            // Chain of three: density 2/3.
+            clone_pair(("d1", &d), ("e1", &e)),
+            clone_pair(("e1", &e), ("f1", &f)),
+        ],
+    });
+
+    let hints = build_refactor_hints(&results, &root);
+
+    for file in ["src/a.rs", "src/b.rs", "src/c.rs"] {
+        assert_eq!(
+            hints.get(file).map(Vec::as_slice),
+            Some(&["clone-group:3".to_string()][..]),
+            "{file} should carry the dense cluster hint"
+        );
+    }
+    for file in ["src/d.rs", "src/e.rs", "src/f.rs"] {
+        assert!(!hints.contains_key(file), "{file} is in a sparse cluster");
+    }
+}
+
+#[test]
+fn condense_analysis_results_merges_clone_cluster_candidates() {
+    let project = tempdir().unwrap();
+    let root = project.path().join("workspace");
+    let mut results = analysis_results_fixture(&root);
+    let candidates: Vec<RefactoringCandidate> = ["src/a.rs", "src/b.rs", "src/c.rs"]
+        .iter()
+        .map(|file| {
+            sample_candidate(
+                Path::new(file),
+                "parse",
+                "CMPLX",
+                "XTRMTH",
+                "extract_method",
+                Priority::High,
+                0.6,
+                0.5,
+            )
+        })
+        .collect();
+    let ids: Vec<&str> = candidates.iter().map(|c| c.entity_id.as_str()).collect();
+    let pairs = vec![
+        clone_pair((ids[0], "src/a.rs"), (ids[1], "src/b.rs")),
+        clone_pair((ids[1], "src/b.rs"), (ids[2], "src/c.rs")),
+    ];
+    results.clone_analysis = Some(CloneAnalysisResults {
+        denoising_enabled: false,
+        auto_calibration_applied: None,
+        candidates_before_denoising: None,
+        candidates_after_denoising: 2,
+        calibrated_threshold: None,
+        quality_score: None,
+        avg_similarity: None,
+        max_similarity: None,
+        verification: None,
+        phase_filtering_stats: None,
+        performance_metrics: None,
+        notes: Vec::new(),
+        clone_pairs: pairs,
+    });
+    results.refactoring_candidates = candidates;
+
+    let condensed: serde_json::Value =
+        serde_json::from_str(&condense_analysis_results(&results)).unwrap();
+    let top = condensed["top_refactoring_candidates"].as_array().unwrap();
+    assert_eq!(top.len(), 1);
+    assert_eq!(
+        top[0]["entity"],
+        "parse (+2 clones in src/a.rs, src/b.rs, src/c.rs)"
+    );
+    assert_eq!(top[0]["suggestion_codes"][0], "DEDUP");
+}
+
+#[test]
+fn oracle_codebook_documents_clone_group_hint() {
+    assert!(ORACLE_CODEBOOK.contains("clone-group:N"));
+}
+
 #[tokio::test]
 async fn create_codebase_bundle_includes_readme_and_skips_large_files() {
     let project = tempdir().unwrap();
@@ -614,6 +1040,35 @@ async fn create_codebase_bundle_includes_readme_and_skips_large_files() {
     );
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn create_codebase_bundle_references_symlinked_duplicates() {
+    let project = tempdir().unwrap();
+    let root = project.path().join("workspace");
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("src/lib.rs"),
+        "pub fn compute(value: i32) -> i32 { value * 2 }\n",
+    )
+    .unwrap();
+    std::os::unix::fs::symlink(root.join("src/lib.rs"), root.join("src/alias.rs")).unwrap();
+
+    let results = analysis_results_fixture(&root);
+    let config = oracle_config_fixture(10_000);
+    let bundle = BundleBuilder::new(&config)
+        .create_codebase_bundle(&root, &results)
+        .await
+        .expect("bundle creation");
+
+    assert_eq!(bundle.matches("value * 2").count(), 1);
+    assert!(
+        bundle.contains("duplicate_of=\"src/lib.rs\"")
+            || bundle.contains("duplicate_of=\"src/alias.rs\""),
+        "second path should reference the first"
+    );
+    assert!(bundle.contains("files_included=\"1\""));
+}
+
 #[test]
 fn condense_analysis_results_with_budget_handles_limits_and_health_section() {
     let project = tempdir().unwrap();
@@ -655,3 +1110,333 @@ fn condense_analysis_results_with_budget_handles_limits_and_health_section() {
         "refactoring candidate names should appear when budget allows"
     );
 }
+
+#[test]
+fn condense_analysis_results_lists_top_five_hub_files() {
+    let mut results = AnalysisResults::empty();
+    results.hub_files = (1..=6)
+        .map(|i| HubFile {
+            path: PathBuf::from(format!("src/hub{i}.rs")),
+            dependent_count: 20 - i,
+            risk_score: 0.5,
+        })
+        .collect();
+
+    let condensed = condense_analysis_results_with_budget(&results, 1_000).expect("condense");
+    assert!(condensed.contains("## Hub Files\npath|dependents|risk\nsrc/hub1.rs|19|0.50\n"));
+    assert!(!condensed.contains("src/hub6.rs"));
+    assert!(condensed.contains(
+        "src/hub5.rs|15|0.50\nHub files are frequently imported — changes here are high-risk.\n"
+    ));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&condense_analysis_results(&results)).unwrap();
+    let hubs = json["hub_files"].as_array().unwrap();
+    assert_eq!(hubs.len(), 5);
+    assert_eq!(
+        hubs[0]["note"],
+        "frequently imported — changes here are high-risk"
+    );
+}
+
+#[test]
+fn condense_analysis_results_omits_hub_files_over_budget() {
+    let mut results = AnalysisResults::empty();
+    results.hub_files = vec![HubFile {
+        path: PathBuf::from("src/core/pipeline.rs"),
+        dependent_count: 45,
+        risk_score: 0.9,
+    }];
+
+    let condensed = condense_analysis_results_with_budget(&results, 0).expect("condense");
+    assert!(!condensed.contains("## Hub Files"));
+
+    results.hub_files.clear();
+    let condensed = condense_analysis_results_with_budget(&results, 1_000).expect("condense");
+    assert!(!condensed.contains("## Hub Files"));
+}
+
+#[test]
+fn condense_analysis_results_lists_import_cycles() {
+    let mut results = AnalysisResults::empty();
+    results.import_cycles = vec![Scc {
+        members: vec![PathBuf::from("src/a.rs"), PathBuf::from("src/b.rs")],
+        size: 2,
+        internal_coupling: 1.0,
+    }];
+
+    let condensed = condense_analysis_results_with_budget(&results, 1_000).expect("condense");
+    assert!(condensed.contains("## Import Cycles\nsize|coupling|files\n2|1.00|src/a.rs,src/b.rs\n"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&condense_analysis_results(&results)).unwrap();
+    assert_eq!(json["import_cycles"][0]["size"], 2);
+
+    let condensed = condense_analysis_results_with_budget(&results, 0).expect("condense");
+    assert!(!condensed.contains("## Import Cycles"));
+}
+
+fn slice_task(
+    title: &str,
+    description: &str,
+    file: &str,
+    effort: &str,
+    risk: &str,
+) -> RefactoringTask {
+    RefactoringTask {
+        id: "T1".to_string(),
+        title: title.to_string(),
+        description: description.to_string(),
+        category: "C3".to_string(),
+        files: vec![file.to_string()],
+        risk: Some(risk.to_string()),
+        risk_level: None,
+        impact: Some("I2".to_string()),
+        effort: Some(effort.to_string()),
+        mitigation: None,
+        required: None,
+        depends_on: vec![],
+        benefits: vec![],
+    }
+}
+
+fn slice_result(slice_id: usize, module: &str, tasks: Vec<RefactoringTask>) -> SliceAnalysisResult {
+    SliceAnalysisResult {
+        slice_id,
+        primary_module: Some(module.to_string()),
+        response: RefactoringOracleResponse {
+            assessment: CodebaseAssessment {
+                summary: Some(format!("{module} summary")),
+                architectural_narrative: None,
+                architectural_style: None,
+                architectural_patterns: vec![],
+                strengths: vec![],
+                issues: vec![],
+            },
+            tasks,
+            refactoring_roadmap: None,
+        },
+    }
+}
+
+#[test]
+fn test_aggregate_slice_results_merges_similar_tasks() {
+    let description = "Replace unwrap calls with typed errors and propagate failures to callers";
+    let slices = vec![
+        slice_result(
+            0,
+            "api",
+            vec![slice_task(
+                "Improve error handling",
+                description,
+                "src/api/handler.rs",
+                "E1",
+                "R3",
+            )],
+        ),
+        slice_result(
+            1,
+            "core",
+            vec![
+                slice_task(
+                    "Improve error handling",
+                    description,
+                    "src/core/engine.rs",
+                    "E3",
+                    "R1",
+                ),
+                slice_task(
+                    "Split configuration module",
+                    "Move validation helpers out of the oversized config file",
+                    "src/core/config.rs",
+                    "E2",
+                    "R2",
+                ),
+            ],
+        ),
+    ];
+
+    let response = aggregate_slice_results(slices, Path::new(".")).unwrap();
+    assert_eq!(response.tasks.len(), 2);
+
+    let merged = response
+        .tasks
+        .iter()
+        .find(|task| task.title.ends_with("Improve error handling"))
+        .expect("merged error handling task");
+    assert_eq!(merged.title, "[api, core] Improve error handling");
+    assert_eq!(
+        merged.files,
+        vec![
+            "src/api/handler.rs".to_string(),
+            "src/core/engine.rs".to_string()
+        ]
+    );
+    assert_eq!(merged.effort.as_deref(), Some("E3"));
+    assert_eq!(merged.get_risk(), Some("R3"));
+
+    let mut ids: Vec<_> = response.tasks.iter().map(|task| task.id.as_str()).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec!["T1", "T2"]);
+}
+
+#[test]
+fn test_aggregate_slice_results_merges_on_exact_token_jaccard() {
+    let task = |title: &str, description: &str, file: &str| {
+        slice_task(title, description, file, "E1", "R1")
+    };
+    let slices = vec![
+        slice_result(
+            0,
+            "api",
+            vec![task(
+                "Alpha beta gamma",
+                "delta epsilon zeta eta theta iota kappa",
+                "src/api/a.rs",
+            )],
+        ),
+        slice_result(
+            1,
+            "core",
+            vec![
+                // Case and punctuation aside, 7 of the first task's 10 tokens: kept apart
+                task(
+                    "DELTA epsilon zeta",
+                    "eta, theta, iota kappa",
+                    "src/core/b.rs",
+                ),
+                // 8 of the first task's 10 tokens: merged
+                task(
+                    "alpha beta",
+                    "delta epsilon zeta eta theta iota",
+                    "src/core/c.rs",
+                ),
+            ],
+        ),
+    ];
+
+    let response = aggregate_slice_results(slices, Path::new(".")).unwrap();
+    assert_eq!(response.tasks.len(), 2);
+    let mut files: Vec<_> = response
+        .tasks
+        .iter()
+        .map(|task| task.files.clone())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            vec!["src/api/a.rs".to_string(), "src/core/c.rs".to_string()],
+            vec!["src/core/b.rs".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_aggregate_slice_results_deduplicates_architectural_patterns() {
+    let mut api = slice_result(0, "api", vec![]);
+    api.response.assessment.architectural_patterns = vec!["AP1".to_string(), "AP3".to_string()];
+    let mut core = slice_result(1, "core", vec![]);
+    core.response.assessment.architectural_patterns = vec!["AP3".to_string(), "AP4".to_string()];
+
+    let response = aggregate_slice_results(vec![api, core], Path::new(".")).unwrap();
+    assert_eq!(
+        response.assessment.architectural_patterns,
+        vec!["AP1".to_string(), "AP3".to_string(), "AP4".to_string()]
+    );
+}
+
+#[test]
+fn test_codebase_assessment_parses_and_renders_architectural_patterns() {
+    assert!(ORACLE_CODEBOOK.contains("AP3: event-driven"));
+    assert!(get_json_schema_instructions().contains("architectural_patterns"));
+
+    let assessment: CodebaseAssessment = serde_json::from_str(
+        r#"{"summary": "Layered service", "architectural_patterns": ["AP1", "AP4"], "strengths": ["Clear layers"], "issues": ["Shared state"]}"#,
+    )
+    .unwrap();
+    assert_eq!(assessment.architectural_patterns, vec!["AP1", "AP4"]);
+    assert_eq!(
+        assessment.render_text(),
+        "Assessment: Layered service\n\
+         Architectural patterns: AP1, AP4\n  \
+         + Clear layers\n  \
+         - Shared state\n"
+    );
+
+    let legacy: CodebaseAssessment = serde_json::from_str(r#"{"summary": "ok"}"#).unwrap();
+    assert!(legacy.architectural_patterns.is_empty());
+    assert_eq!(legacy.render_text(), "Assessment: ok\n");
+}
+
+#[tokio::test]
+async fn test_query_ollama_parses_generated_oracle_json() {
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let oracle_json = serde_json::json!({
+        "assessment": { "summary": "Local model review", "strengths": [], "issues": [] },
+        "tasks": []
+    });
+    Mock::given(method("POST"))
+        .and(path("/api/generate"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "llama3",
+            "prompt": "bundle contents",
+            "stream": false,
+            "format": "json"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "model": "llama3",
+            "response": oracle_json.to_string(),
+            "done": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response = ollama::query_ollama(
+        &reqwest::Client::new(),
+        &format!("{}/", server.uri()),
+        "llama3",
+        "bundle contents",
+    )
+    .await
+    .expect("ollama response");
+
+    assert_eq!(response.assessment.get_summary(), "Local model review");
+    assert!(response.all_tasks().is_empty());
+}
+
+#[tokio::test]
+async fn test_query_ollama_reports_unreachable_server() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let err = ollama::query_ollama(&reqwest::Client::new(), &base_url, "llama3", "bundle")
+        .await
+        .unwrap_err();
+
+    match err {
+        ValknutError::OracleUnavailable { endpoint, .. } => assert_eq!(endpoint, base_url),
+        other => panic!("expected OracleUnavailable, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_ollama_config_routes_both_models_to_backend() {
+    let config = OracleConfig::ollama("http://localhost:11434", "qwen2.5-coder");
+
+    assert!(config.api_key.is_empty());
+    assert_eq!(config.model, "qwen2.5-coder");
+    assert_eq!(config.slice_model, "qwen2.5-coder");
+    assert_eq!(
+        config.backend,
+        OracleBackend::Ollama {
+            base_url: "http://localhost:11434".to_string(),
+            model: "qwen2.5-coder".to_string(),
+        }
+    );
+}
@@ -1,9 +1,27 @@
 //! Oracle types for configuration and responses.
 
+use std::collections::{BTreeSet, HashMap};
+
 use serde::{Deserialize, Serialize};
 
 use crate::core::errors::{Result, ValknutError};
 
+use super::helpers::{
+    default_test_path_patterns, default_vendored_path_patterns, TestPathMatcher,
+    VendoredPathMatcher,
+};
+
+/// Gemini 2.5 Pro input price in USD per million tokens, the default for
+/// [`OracleConfig::cost_per_million_tokens`].
+pub const GEMINI_2_5_PRO_COST_PER_MILLION_TOKENS: f64 = 1.25;
+
+/// Gemini 2.5 Pro output price in USD per million tokens, the default for
+/// [`OracleConfig::output_cost_per_million_tokens`].
+pub const GEMINI_2_5_PRO_OUTPUT_COST_PER_MILLION_TOKENS: f64 = 10.0;
+
+/// Output token cap requested for every oracle call.
+pub const ORACLE_MAX_OUTPUT_TOKENS: usize = 32_000;
+
 /// Configuration for the refactoring oracle
 #[derive(Debug, Clone)]
 pub struct OracleConfig {
@@ -23,6 +41,37 @@ pub struct OracleConfig {
     pub slice_model: String,
     /// Threshold for enabling slicing (if total tokens > this, use slices)
     pub slicing_threshold: usize,
+    /// Glob patterns for test files excluded from bundles and slices
+    /// (default: [`DEFAULT_TEST_PATH_PATTERNS`](super::helpers::DEFAULT_TEST_PATH_PATTERNS))
+    pub test_path_patterns: Vec<String>,
+    /// Glob patterns for vendored files ranked last in bundles
+    /// (default: [`DEFAULT_VENDORED_PATH_PATTERNS`](super::helpers::DEFAULT_VENDORED_PATH_PATTERNS))
+    pub vendored_path_patterns: Vec<String>,
+    /// Thinking budget for Gemini 2.5 thinking models (default: None, not sent)
+    pub thinking_budget: Option<u32>,
+    /// Model service that receives the bundles (default: Gemini)
+    pub backend: OracleBackend,
+    /// API price in USD per million input tokens, used for cost estimates
+    /// (default: [`GEMINI_2_5_PRO_COST_PER_MILLION_TOKENS`])
+    pub cost_per_million_tokens: f64,
+    /// API price in USD per million output tokens, used for cost estimates
+    /// (default: [`GEMINI_2_5_PRO_OUTPUT_COST_PER_MILLION_TOKENS`])
+    pub output_cost_per_million_tokens: f64,
+}
+
+/// Model service the oracle sends bundles to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OracleBackend {
+    /// Google Gemini via `api_endpoint`, `model` and `slice_model`
+    #[default]
+    Gemini,
+    /// Local Ollama server, for offline or air-gapped use
+    Ollama {
+        /// Server URL, e.g. `http://localhost:11434`
+        base_url: String,
+        /// Model used for both full and slice analysis
+        model: String,
+    },
 }
 
 /// Factory and builder methods for [`OracleConfig`].
@@ -42,9 +91,39 @@ impl OracleConfig {
             slice_token_budget: 200_000,
             slice_model: "gemini-3-flash-preview".to_string(),
             slicing_threshold: 300_000, // Use slicing if codebase > 300k tokens
+            test_path_patterns: default_test_path_patterns(),
+            vendored_path_patterns: default_vendored_path_patterns(),
+            thinking_budget: None,
+            backend: OracleBackend::Gemini,
+            cost_per_million_tokens: GEMINI_2_5_PRO_COST_PER_MILLION_TOKENS,
+            output_cost_per_million_tokens: GEMINI_2_5_PRO_OUTPUT_COST_PER_MILLION_TOKENS,
         })
     }
 
+    /// Create configuration for a local Ollama server; no API key is needed.
+    pub fn ollama(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        let model = model.into();
+        Self {
+            api_key: String::new(),
+            max_tokens: 400_000,
+            api_endpoint: String::new(),
+            model: model.clone(),
+            enable_slicing: true,
+            slice_token_budget: 200_000,
+            slice_model: model.clone(),
+            slicing_threshold: 300_000,
+            test_path_patterns: default_test_path_patterns(),
+            vendored_path_patterns: default_vendored_path_patterns(),
+            thinking_budget: None,
+            backend: OracleBackend::Ollama {
+                base_url: base_url.into(),
+                model,
+            },
+            cost_per_million_tokens: 0.0,
+            output_cost_per_million_tokens: 0.0,
+        }
+    }
+
     /// Sets the maximum token limit for codebase analysis.
     pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
         self.max_tokens = max_tokens;
@@ -68,6 +147,66 @@ impl OracleConfig {
         self.enable_slicing = enabled;
         self
     }
+
+    /// Sets the glob patterns used to recognise test files.
+    pub fn with_test_path_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.test_path_patterns = patterns;
+        self
+    }
+
+    /// Sets the glob patterns used to recognise vendored files.
+    pub fn with_vendored_path_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.vendored_path_patterns = patterns;
+        self
+    }
+
+    /// Sets the thinking budget sent to thinking-capable models.
+    pub fn with_thinking_budget(mut self, budget: Option<u32>) -> Self {
+        self.thinking_budget = budget;
+        self
+    }
+
+    /// Sets the model service that receives the bundles.
+    pub fn with_backend(mut self, backend: OracleBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Sets the API price in USD per million input tokens used for cost estimates.
+    pub fn with_cost_per_million_tokens(mut self, cost: f64) -> Self {
+        self.cost_per_million_tokens = cost;
+        self
+    }
+
+    /// Sets the API price in USD per million output tokens used for cost estimates.
+    pub fn with_output_cost_per_million_tokens(mut self, cost: f64) -> Self {
+        self.output_cost_per_million_tokens = cost;
+        self
+    }
+
+    /// Compiles [`Self::test_path_patterns`] into a matcher.
+    pub fn test_path_matcher(&self) -> TestPathMatcher {
+        TestPathMatcher::new(&self.test_path_patterns)
+    }
+
+    /// Compiles [`Self::vendored_path_patterns`] into a matcher.
+    pub fn vendored_path_matcher(&self) -> VendoredPathMatcher {
+        VendoredPathMatcher::new(&self.vendored_path_patterns)
+    }
+}
+
+/// Projected token usage and API cost of an oracle run, from
+/// [`RefactoringOracle::estimate_token_cost`](super::RefactoringOracle::estimate_token_cost).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenCostEstimate {
+    /// Approximate tokens of source sent to the model
+    pub estimated_input_tokens: usize,
+    /// Upper bound on generated tokens across all calls
+    pub estimated_output_tokens: usize,
+    /// Approximate API cost in USD
+    pub estimated_cost_usd: f64,
+    /// Number of model calls; 1 unless the codebase is sliced
+    pub slice_count: usize,
 }
 
 /// Response from the AI refactoring oracle
@@ -95,6 +234,58 @@ impl RefactoringOracleResponse {
             &[]
         }
     }
+
+    /// All tasks ordered so every task comes after the tasks it depends on.
+    ///
+    /// Tasks whose dependencies are satisfied keep their original relative
+    /// order, and dependencies on unknown task IDs are ignored. A dependency
+    /// cycle yields `Err` with the IDs of every task that could not be ordered.
+    pub fn topologically_sorted_tasks(
+        &self,
+    ) -> std::result::Result<Vec<&RefactoringTask>, Vec<String>> {
+        let tasks = self.all_tasks();
+        let index_of: HashMap<&str, usize> = tasks
+            .iter()
+            .enumerate()
+            .map(|(index, task)| (task.id.as_str(), index))
+            .collect();
+
+        let mut pending: Vec<usize> = vec![0; tasks.len()];
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+        for (index, task) in tasks.iter().enumerate() {
+            let known: BTreeSet<usize> = task
+                .depends_on
+                .iter()
+                .filter_map(|id| index_of.get(id.as_str()).copied())
+                .collect();
+            pending[index] = known.len();
+            for dependency in known {
+                dependents[dependency].push(index);
+            }
+        }
+
+        let mut ready: BTreeSet<usize> = (0..tasks.len()).filter(|&i| pending[i] == 0).collect();
+        let mut sorted = Vec::with_capacity(tasks.len());
+        while let Some(index) = ready.pop_first() {
+            sorted.push(&tasks[index]);
+            for &dependent in &dependents[index] {
+                pending[dependent] -= 1;
+                if pending[dependent] == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+
+        if sorted.len() < tasks.len() {
+            return Err(tasks
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| pending[*index] > 0)
+                .map(|(_, task)| task.id.clone())
+                .collect());
+        }
+        Ok(sorted)
+    }
 }
 
 /// Assessment of overall codebase quality from the oracle.
@@ -109,6 +300,9 @@ pub struct CodebaseAssessment {
     /// Legacy: architectural style
     #[serde(default)]
     pub architectural_style: Option<String>,
+    /// Architectural pattern codes (AP1-AP4) identified
+    #[serde(default)]
+    pub architectural_patterns: Vec<String>,
     /// Code strengths identified
     #[serde(default)]
     pub strengths: Vec<String>,
@@ -126,6 +320,24 @@ impl CodebaseAssessment {
             .or(self.architectural_narrative.as_deref())
             .unwrap_or("No summary provided")
     }
+
+    /// Plain-text rendering of the assessment for terminal output
+    pub fn render_text(&self) -> String {
+        let mut out = format!("Assessment: {}\n", self.get_summary());
+        if !self.architectural_patterns.is_empty() {
+            out.push_str(&format!(
+                "Architectural patterns: {}\n",
+                self.architectural_patterns.join(", ")
+            ));
+        }
+        for strength in &self.strengths {
+            out.push_str(&format!("  + {}\n", strength));
+        }
+        for issue in &self.issues {
+            out.push_str(&format!("  - {}\n", issue));
+        }
+        out
+    }
 }
 
 /// Legacy container for refactoring tasks in execution order.
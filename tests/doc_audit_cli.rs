@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::tempdir;
+
+fn run_doc_audit(root: &Path, extra_args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_valknut"))
+        .arg("doc-audit")
+        .arg("--root")
+        .arg(root)
+        .args(extra_args)
+        .output()
+        .expect("failed to launch valknut binary")
+}
+
+fn write_undocumented_fixture(root: &Path) {
+    fs::write(root.join("main.py"), "def main():\n    return 1\n").unwrap();
+}
+
+#[test]
+fn doc_audit_reports_issues_without_failing_by_default() {
+    let temp = tempdir().unwrap();
+    write_undocumented_fixture(temp.path());
+
+    let output = run_doc_audit(temp.path(), &["--format", "json"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(parsed["documentation_issues"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn doc_audit_fail_on_issues_sets_exit_code() {
+    let temp = tempdir().unwrap();
+    write_undocumented_fixture(temp.path());
+
+    let output = run_doc_audit(temp.path(), &["--fail-on-issues"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn doc_audit_fail_on_issues_passes_clean_tree() {
+    let temp = tempdir().unwrap();
+    fs::write(
+        temp.path().join("main.py"),
+        "def main():\n    \"\"\"Entry point.\"\"\"\n    return 1\n",
+    )
+    .unwrap();
+
+    let output = run_doc_audit(
+        temp.path(),
+        &["--fail-on-issues", "--complexity-threshold", "10"],
+    );
+    assert!(
+        output.status.success(),
+        "stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
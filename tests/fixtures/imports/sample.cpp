@@ -0,0 +1,11 @@
+#include <iostream>
+#include <vector>
+#include <string>
+#include "config.h"
+#include "util/strings.hpp"
+
+int main() {
+    std::vector<std::string> names;
+    std::cout << names.size() << std::endl;
+    return 0;
+}
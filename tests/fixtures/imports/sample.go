@@ -0,0 +1,15 @@
+package main
+
+import "fmt"
+import(
+	"os"
+	"strings" // string helpers (std)
+	_ "github.com/lib/pq"
+	log "github.com/sirupsen/logrus"
+)
+import ("net/http"; "time")
+
+func main() {
+	fmt.Println(os.Args, strings.ToUpper("x"), http.StatusOK, time.Now())
+	log.Info("done")
+}
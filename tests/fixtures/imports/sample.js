@@ -0,0 +1,9 @@
+import React from 'react';
+import { useState, useEffect } from 'react';
+import * as path from 'path';
+import lodash, { debounce } from 'lodash';
+const fs = require('fs');
+
+export function main() {
+  return [React, useState, useEffect, path, lodash, debounce, fs];
+}
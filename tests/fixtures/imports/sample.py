@@ -0,0 +1,9 @@
+import os
+import sys as system
+from collections import OrderedDict, defaultdict
+from typing import *
+from .models import User
+
+
+def main():
+    return OrderedDict(), defaultdict(list), User, os, system
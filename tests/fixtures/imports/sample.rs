@@ -0,0 +1,16 @@
+mod config;
+pub(crate) mod parser; // parsing helpers
+
+use std::collections::HashMap;
+pub use crate::config::Settings;
+use std::{
+    fs,
+    io::{self, Read},
+};
+use serde::Serialize;
+
+pub fn main() -> io::Result<HashMap<String, String>> {
+    let mut contents = String::new();
+    fs::File::open("settings.toml")?.read_to_string(&mut contents)?;
+    Ok(HashMap::new())
+}
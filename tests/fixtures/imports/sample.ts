@@ -0,0 +1,9 @@
+import express from 'express';
+import { Router, type Request } from 'express';
+import * as http from 'http';
+import type { Config } from './config';
+const util = require('util');
+
+export function main(config: Config, request: Request): unknown {
+  return [express, Router, http, util, config, request];
+}
@@ -143,11 +143,11 @@ async fn pipeline_enables_lsh_and_coverage_analysis() -> Result<()> {
     valknut_config.analysis.enable_lsh_analysis = true;
     valknut_config.analysis.enable_coverage_analysis = true;
     valknut_config.denoise.enabled = true;
-    valknut_config.denoise.min_function_tokens = 1;
+    valknut_config.denoise.min_function_tokens = 5;
     valknut_config.denoise.min_match_tokens = 1;
     valknut_config.denoise.require_blocks = 1;
-    valknut_config.dedupe.min_function_tokens = 1;
-    valknut_config.dedupe.min_ast_nodes = 1;
+    valknut_config.dedupe.min_function_tokens = 5;
+    valknut_config.dedupe.min_ast_nodes = 3;
     valknut_config.dedupe.min_match_tokens = 1;
     valknut_config.coverage.search_paths = vec!["./coverage/".to_string(), "./".to_string()];
     valknut_config.coverage.file_patterns = vec!["coverage.lcov".to_string()];
@@ -157,7 +157,8 @@ async fn pipeline_enables_lsh_and_coverage_analysis() -> Result<()> {
     pipeline_config.enable_coverage_analysis = true;
     pipeline_config.file_extensions = vec!["rs".to_string()];
 
-    let pipeline = AnalysisPipeline::new_with_config(pipeline_config, valknut_config);
+    let pipeline = AnalysisPipeline::new_with_config(pipeline_config, valknut_config)
+        .expect("pipeline configuration should be valid");
     let results = pipeline
         .analyze_paths(&[root], None)
         .await
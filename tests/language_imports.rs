@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+
+use valknut_rs::detectors::structure::ImportStatement;
+use valknut_rs::lang::adapter_for_file;
+
+fn fixture_path(file_name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/imports")
+        .join(file_name)
+}
+
+fn extract_fixture_imports(file_name: &str) -> Vec<ImportStatement> {
+    let path = fixture_path(file_name);
+    let source = std::fs::read_to_string(&path).expect("fixture should be readable");
+    let mut adapter = adapter_for_file(&path).expect("fixture language should be supported");
+    adapter
+        .extract_imports(&source)
+        .expect("imports should parse")
+}
+
+fn modules(imports: &[ImportStatement]) -> Vec<&str> {
+    imports
+        .iter()
+        .map(|import| import.module.as_str())
+        .collect()
+}
+
+fn find<'a>(imports: &'a [ImportStatement], module: &str) -> &'a ImportStatement {
+    imports
+        .iter()
+        .find(|import| import.module == module)
+        .unwrap_or_else(|| panic!("missing import of {module}"))
+}
+
+#[test]
+fn python_fixture_imports_are_parsed() {
+    let imports = extract_fixture_imports("sample.py");
+
+    assert_eq!(
+        modules(&imports),
+        vec!["os", "sys", "collections", "typing", ".models"]
+    );
+    assert_eq!(
+        find(&imports, "collections").imports,
+        Some(vec!["OrderedDict".to_string(), "defaultdict".to_string()])
+    );
+    assert_eq!(find(&imports, "typing").import_type, "star");
+    assert_eq!(find(&imports, ".models").line_number, 5);
+}
+
+#[test]
+fn javascript_fixture_imports_are_parsed() {
+    let imports = extract_fixture_imports("sample.js");
+
+    assert_eq!(
+        modules(&imports),
+        vec!["react", "react", "path", "lodash", "fs"]
+    );
+    assert_eq!(
+        imports[1].imports,
+        Some(vec!["useState".to_string(), "useEffect".to_string()])
+    );
+    assert_eq!(find(&imports, "path").import_type, "star");
+    assert_eq!(find(&imports, "fs").line_number, 5);
+}
+
+#[test]
+fn typescript_fixture_imports_are_parsed() {
+    let imports = extract_fixture_imports("sample.ts");
+
+    assert_eq!(
+        modules(&imports),
+        vec!["express", "express", "http", "./config", "util"]
+    );
+    assert_eq!(
+        imports[1].imports,
+        Some(vec!["Router".to_string(), "Request".to_string()])
+    );
+    assert_eq!(find(&imports, "http").import_type, "star");
+}
+
+#[test]
+fn rust_fixture_imports_include_use_and_mod() {
+    let imports = extract_fixture_imports("sample.rs");
+
+    assert_eq!(
+        modules(&imports),
+        vec![
+            "config",
+            "parser",
+            "std::collections::HashMap",
+            "crate::config::Settings",
+            "std::",
+            "serde::Serialize",
+        ]
+    );
+    assert_eq!(find(&imports, "parser").import_type, "mod");
+    assert_eq!(find(&imports, "crate::config::Settings").line_number, 5);
+
+    let grouped = find(&imports, "std::");
+    assert_eq!(grouped.line_number, 6);
+    assert_eq!(
+        grouped.imports,
+        Some(vec!["fs".to_string(), "io::{self, Read}".to_string()])
+    );
+}
+
+#[test]
+fn go_fixture_imports_include_every_block_entry() {
+    let imports = extract_fixture_imports("sample.go");
+
+    assert_eq!(
+        modules(&imports),
+        vec![
+            "fmt",
+            "os",
+            "strings",
+            "github.com/lib/pq",
+            "github.com/sirupsen/logrus",
+            "net/http",
+            "time",
+        ]
+    );
+    assert_eq!(find(&imports, "github.com/sirupsen/logrus").line_number, 8);
+    assert_eq!(find(&imports, "time").line_number, 10);
+}
+
+#[test]
+fn cpp_fixture_includes_are_parsed() {
+    let imports = extract_fixture_imports("sample.cpp");
+
+    assert_eq!(
+        modules(&imports),
+        vec![
+            "iostream",
+            "vector",
+            "string",
+            "config.h",
+            "util/strings.hpp"
+        ]
+    );
+}